@@ -0,0 +1,812 @@
+//! Direct HTTP backend for OpenAI-compatible chat-completions APIs.
+//!
+//! Unlike the CLI backends, nothing is spawned per iteration: [`ApiBackend`]
+//! sends the prompt to `<base_url>/chat/completions`, streams the reply
+//! through a [`StreamHandler`], and runs the tools the model calls itself.
+//! The tools are deliberately minimal — read, write and edit a file, run a
+//! shell command, and emit a Ralph event. The file tools only accept paths
+//! that resolve inside the workspace, symlinks included; `run_command`
+//! hands its command to `sh -c` unrestricted unless a sandbox is configured
+//! (see [`ApiBackend::with_sandbox`]).
+
+use crate::stream_handler::{SessionResult, StreamHandler};
+use ralph_core::{ApiBackendConfig, Sandbox};
+use serde::Deserialize;
+use serde_json::{Value, json};
+use std::io::Write;
+use std::path::{Component, Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::process::Command;
+
+/// Longest tool output returned to the model, in bytes.
+const MAX_TOOL_OUTPUT: usize = 16 * 1024;
+
+/// Errors creating an [`ApiBackend`].
+#[derive(Debug, thiserror::Error)]
+pub enum ApiBackendError {
+    #[error("api backend requires a model (set cli.api.model)")]
+    MissingModel,
+
+    #[error("failed to create HTTP client: {0}")]
+    Client(#[from] reqwest::Error),
+}
+
+/// Token usage reported by the API.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ApiUsage {
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+}
+
+/// Result of running one prompt through the API backend.
+#[derive(Debug, Clone)]
+pub struct ApiExecutionResult {
+    /// Assistant text from every turn, followed by the error on failure.
+    pub output: String,
+    /// Whether the model finished without an error.
+    pub success: bool,
+    /// Whether the iteration timeout expired.
+    pub timed_out: bool,
+    /// Token usage summed over every request.
+    pub usage: ApiUsage,
+    /// Estimated cost from `usage` and the configured prices.
+    pub cost_usd: f64,
+    /// Number of requests made.
+    pub turns: u32,
+}
+
+/// A chat-completions backend with a built-in tool loop.
+pub struct ApiBackend {
+    config: ApiBackendConfig,
+    model: String,
+    reasoning_effort: Option<String>,
+    sandbox: Option<Sandbox>,
+    api_key: Option<String>,
+    workspace: PathBuf,
+    client: reqwest::Client,
+}
+
+impl ApiBackend {
+    /// Creates a backend that runs tools in `workspace`. The API key is read
+    /// from the environment variable named by `api_key_env`.
+    pub fn new(
+        config: &ApiBackendConfig,
+        workspace: impl Into<PathBuf>,
+    ) -> Result<Self, ApiBackendError> {
+        let model = config
+            .model
+            .clone()
+            .filter(|model| !model.is_empty())
+            .ok_or(ApiBackendError::MissingModel)?;
+        let workspace = workspace.into();
+        let workspace = std::fs::canonicalize(&workspace).unwrap_or(workspace);
+        let api_key = std::env::var(&config.api_key_env)
+            .ok()
+            .filter(|key| !key.is_empty());
+        Ok(Self {
+            config: config.clone(),
+            model,
+            reasoning_effort: None,
+            sandbox: None,
+            api_key,
+            workspace,
+            client: reqwest::Client::builder().build()?,
+        })
+    }
+
+    /// Overrides the API key read from the environment.
+    pub fn with_api_key(mut self, api_key: Option<String>) -> Self {
+        self.api_key = api_key;
+        self
+    }
+
+    /// Sends `reasoning_effort` with every request.
+    pub fn with_reasoning_effort(mut self, effort: Option<String>) -> Self {
+        self.reasoning_effort = effort;
+        self
+    }
+
+    /// Runs `run_command` tool calls inside `sandbox`.
+    pub fn with_sandbox(mut self, sandbox: Option<Sandbox>) -> Self {
+        self.sandbox = sandbox;
+        self
+    }
+
+    /// The chat-completions URL requests are sent to.
+    pub fn endpoint(&self) -> String {
+        format!(
+            "{}/chat/completions",
+            self.config.base_url.trim_end_matches('/')
+        )
+    }
+
+    /// Runs `prompt` until the model stops calling tools, `max_turns` is
+    /// reached, a request fails, or `timeout` expires.
+    ///
+    /// Failures are reported in the result rather than as errors so they can
+    /// be classified like a CLI backend's output.
+    pub async fn execute(
+        &self,
+        prompt: &str,
+        handler: &mut dyn StreamHandler,
+        timeout: Option<Duration>,
+    ) -> ApiExecutionResult {
+        let start = Instant::now();
+        let mut session = Session::default();
+
+        let run = self.run(prompt, handler, &mut session);
+        let result = match timeout {
+            Some(limit) => tokio::time::timeout(limit, run).await.ok(),
+            None => Some(run.await),
+        };
+        let timed_out = result.is_none();
+        let error = match result {
+            Some(Ok(())) => None,
+            Some(Err(error)) => Some(error),
+            None => Some(format!(
+                "timed out after {}s",
+                timeout.unwrap_or_default().as_secs()
+            )),
+        };
+
+        if let Some(ref error) = error {
+            handler.on_error(error);
+            if !session.output.is_empty() && !session.output.ends_with('\n') {
+                session.output.push('\n');
+            }
+            session.output.push_str(error);
+            session.output.push('\n');
+        }
+
+        let cost_usd = self.cost(session.usage);
+        handler.on_complete(&SessionResult {
+            duration_ms: start.elapsed().as_millis() as u64,
+            total_cost_usd: cost_usd,
+            num_turns: session.turns,
+            is_error: error.is_some(),
+        });
+
+        ApiExecutionResult {
+            output: session.output,
+            success: error.is_none(),
+            timed_out,
+            usage: session.usage,
+            cost_usd,
+            turns: session.turns,
+        }
+    }
+
+    fn cost(&self, usage: ApiUsage) -> f64 {
+        (usage.prompt_tokens as f64 * self.config.input_cost_per_mtok
+            + usage.completion_tokens as f64 * self.config.output_cost_per_mtok)
+            / 1_000_000.0
+    }
+
+    async fn run(
+        &self,
+        prompt: &str,
+        handler: &mut dyn StreamHandler,
+        session: &mut Session,
+    ) -> Result<(), String> {
+        let mut messages = vec![json!({ "role": "user", "content": prompt })];
+        loop {
+            if session.turns >= self.config.max_turns {
+                return Err(format!(
+                    "model did not finish within {} turns",
+                    self.config.max_turns
+                ));
+            }
+            session.turns += 1;
+
+            let reply = self.complete(&messages, handler, session).await?;
+            if !reply.text.is_empty() {
+                session.output.push_str(&reply.text);
+                if !session.output.ends_with('\n') {
+                    session.output.push('\n');
+                }
+            }
+            messages.push(reply.to_message());
+            if reply.tool_calls.is_empty() {
+                return Ok(());
+            }
+
+            for call in &reply.tool_calls {
+                let output = match serde_json::from_str::<Value>(&call.arguments) {
+                    Ok(input) => {
+                        handler.on_tool_call(&call.name, &call.id, &input);
+                        self.run_tool(&call.name, &input).await
+                    }
+                    Err(e) => {
+                        handler.on_tool_call(&call.name, &call.id, &Value::Null);
+                        Err(format!("invalid tool arguments: {e}"))
+                    }
+                };
+                let output = output.unwrap_or_else(|e| format!("Error: {e}"));
+                let output = truncate_output(output);
+                handler.on_tool_result(&call.id, &output);
+                messages.push(json!({
+                    "role": "tool",
+                    "tool_call_id": call.id,
+                    "content": output,
+                }));
+            }
+        }
+    }
+
+    /// Sends one request and accumulates the (streamed or whole) reply.
+    async fn complete(
+        &self,
+        messages: &[Value],
+        handler: &mut dyn StreamHandler,
+        session: &mut Session,
+    ) -> Result<Reply, String> {
+        let mut body = json!({
+            "model": self.model,
+            "messages": messages,
+            "tools": tool_definitions(),
+            "stream": true,
+            "stream_options": { "include_usage": true },
+        });
+        if let Some(max_tokens) = self.config.max_tokens {
+            body["max_tokens"] = json!(max_tokens);
+        }
+        if let Some(ref effort) = self.reasoning_effort {
+            body["reasoning_effort"] = json!(effort);
+        }
+
+        let mut request = self.client.post(self.endpoint()).json(&body);
+        if let Some(ref api_key) = self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+        let mut response = request
+            .send()
+            .await
+            .map_err(|e| format!("request to {} failed: {e}", self.endpoint()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(format!("HTTP {}: {}", status.as_u16(), body.trim()));
+        }
+
+        let streaming = response
+            .headers()
+            .get(reqwest::header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(|value| value.starts_with("text/event-stream"));
+
+        let mut reply = Reply::default();
+        if !streaming {
+            let text = response
+                .text()
+                .await
+                .map_err(|e| format!("failed to read response: {e}"))?;
+            let chunk: ChatChunk = serde_json::from_str(&text)
+                .map_err(|e| format!("invalid completion response: {e}"))?;
+            reply.apply(chunk, handler, session);
+            return Ok(reply);
+        }
+
+        let mut events = SseBuffer::default();
+        'stream: while let Some(bytes) = response
+            .chunk()
+            .await
+            .map_err(|e| format!("failed to read response stream: {e}"))?
+        {
+            for data in events.push(&bytes) {
+                if data == "[DONE]" {
+                    break 'stream;
+                }
+                let chunk: ChatChunk = serde_json::from_str(&data)
+                    .map_err(|e| format!("invalid stream chunk: {e}"))?;
+                reply.apply(chunk, handler, session);
+            }
+        }
+        Ok(reply)
+    }
+
+    async fn run_tool(&self, name: &str, input: &Value) -> Result<String, String> {
+        let arg = |key: &str| {
+            input
+                .get(key)
+                .and_then(Value::as_str)
+                .ok_or_else(|| format!("missing string argument '{key}'"))
+        };
+
+        match name {
+            "read_file" => {
+                let path = self.resolve(arg("path")?)?;
+                tokio::fs::read_to_string(&path)
+                    .await
+                    .map_err(|e| format!("cannot read {}: {e}", path.display()))
+            }
+            "write_file" => {
+                let path = self.resolve(arg("path")?)?;
+                let content = arg("content")?;
+                if let Some(parent) = path.parent() {
+                    tokio::fs::create_dir_all(parent)
+                        .await
+                        .map_err(|e| format!("cannot create {}: {e}", parent.display()))?;
+                }
+                tokio::fs::write(&path, content)
+                    .await
+                    .map_err(|e| format!("cannot write {}: {e}", path.display()))?;
+                Ok(format!("Wrote {} bytes to {}", content.len(), arg("path")?))
+            }
+            "edit_file" => {
+                let path = self.resolve(arg("path")?)?;
+                let old_text = arg("old_text")?;
+                let new_text = arg("new_text")?;
+                let content = tokio::fs::read_to_string(&path)
+                    .await
+                    .map_err(|e| format!("cannot read {}: {e}", path.display()))?;
+                match content.matches(old_text).count() {
+                    0 => return Err("old_text not found".to_string()),
+                    1 => {}
+                    n => {
+                        return Err(format!("old_text matches {n} times; include more context"));
+                    }
+                }
+                tokio::fs::write(&path, content.replacen(old_text, new_text, 1))
+                    .await
+                    .map_err(|e| format!("cannot write {}: {e}", path.display()))?;
+                Ok(format!("Edited {}", arg("path")?))
+            }
+            "run_command" => self.run_command(arg("command")?).await,
+            "ralph_emit" => {
+                let topic = arg("topic")?;
+                if topic.trim().is_empty() {
+                    return Err("topic must not be empty".to_string());
+                }
+                let payload = input.get("payload").cloned().unwrap_or(Value::Null);
+                self.emit(topic, payload)?;
+                Ok(format!("Event emitted: {topic}"))
+            }
+            other => Err(format!("unknown tool '{other}'")),
+        }
+    }
+
+    async fn run_command(&self, command: &str) -> Result<String, String> {
+        let shell_args = ["-c".to_string(), command.to_string()];
+        let mut child = match &self.sandbox {
+            Some(sandbox) => {
+                let wrapped = sandbox.wrap("sh", &shell_args);
+                let mut child = Command::new(wrapped.program);
+                child.args(wrapped.args).envs(wrapped.env);
+                child
+            }
+            None => {
+                let mut child = Command::new("sh");
+                child.args(shell_args);
+                child
+            }
+        };
+        let child = child
+            .current_dir(&self.workspace)
+            .stdin(std::process::Stdio::null())
+            .kill_on_drop(true)
+            .output();
+        let limit = Duration::from_secs(self.config.command_timeout_secs);
+        let output = tokio::time::timeout(limit, child)
+            .await
+            .map_err(|_| format!("command timed out after {}s", limit.as_secs()))?
+            .map_err(|e| format!("cannot run command: {e}"))?;
+
+        let mut result = String::from_utf8_lossy(&output.stdout).into_owned();
+        result.push_str(&String::from_utf8_lossy(&output.stderr));
+        let code = output
+            .status
+            .code()
+            .map_or_else(|| "none".to_string(), |code| code.to_string());
+        Ok(format!("exit code: {code}\n{result}"))
+    }
+
+    /// Appends an event the same way `ralph emit` does: to the file named in
+    /// `.ralph/current-events`, or `.ralph/events.jsonl` without one.
+    fn emit(&self, topic: &str, payload: Value) -> Result<(), String> {
+        let ralph_dir = self.workspace.join(".ralph");
+        let events_file = std::fs::read_to_string(ralph_dir.join("current-events"))
+            .map(|path| self.workspace.join(path.trim()))
+            .unwrap_or_else(|_| ralph_dir.join("events.jsonl"));
+        if let Some(parent) = events_file.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("cannot create {}: {e}", parent.display()))?;
+        }
+
+        let payload = match payload {
+            Value::String(text) if text.is_empty() => Value::Null,
+            other => other,
+        };
+        let record = json!({
+            "topic": topic,
+            "payload": payload,
+            "ts": chrono::Utc::now().to_rfc3339(),
+        });
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&events_file)
+            .map_err(|e| format!("cannot open {}: {e}", events_file.display()))?;
+        writeln!(file, "{record}").map_err(|e| format!("cannot write event: {e}"))
+    }
+
+    fn resolve(&self, path: &str) -> Result<PathBuf, String> {
+        resolve_in_workspace(&self.workspace, path)
+    }
+}
+
+/// Resolves `path` against `workspace`, rejecting paths that leave it.
+///
+/// `..` is applied lexically, then symlinks are followed through the part of
+/// the path that exists, so a link pointing out of the workspace is rejected
+/// too. The returned path is the canonical one that was checked.
+fn resolve_in_workspace(workspace: &Path, path: &str) -> Result<PathBuf, String> {
+    let mut resolved = PathBuf::new();
+    for component in workspace.join(path).components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            other => resolved.push(other),
+        }
+    }
+    let workspace = canonicalize_existing(workspace);
+    let resolved = canonicalize_existing(&resolved);
+    if resolved.starts_with(&workspace) {
+        Ok(resolved)
+    } else {
+        Err(format!("path '{path}' is outside the workspace"))
+    }
+}
+
+/// Canonicalizes the longest existing prefix of `path` and appends the rest,
+/// so paths to files that are about to be created can still be checked.
+fn canonicalize_existing(path: &Path) -> PathBuf {
+    let mut existing = path;
+    let mut missing = Vec::new();
+    loop {
+        if let Ok(canonical) = existing.canonicalize() {
+            return missing
+                .iter()
+                .rev()
+                .fold(canonical, |resolved, name| resolved.join(name));
+        }
+        match (existing.parent(), existing.file_name()) {
+            (Some(parent), Some(name)) => {
+                missing.push(name);
+                existing = parent;
+            }
+            _ => return path.to_path_buf(),
+        }
+    }
+}
+
+/// Keeps the end of long tool output, where errors and results usually are.
+fn truncate_output(output: String) -> String {
+    if output.len() <= MAX_TOOL_OUTPUT {
+        return output;
+    }
+    let mut start = output.len() - MAX_TOOL_OUTPUT;
+    while !output.is_char_boundary(start) {
+        start += 1;
+    }
+    format!("[{} bytes truncated]\n{}", start, &output[start..])
+}
+
+fn tool_definitions() -> Value {
+    let tool = |name: &str, description: &str, properties: Value, required: &[&str]| {
+        json!({
+            "type": "function",
+            "function": {
+                "name": name,
+                "description": description,
+                "parameters": {
+                    "type": "object",
+                    "properties": properties,
+                    "required": required,
+                },
+            },
+        })
+    };
+    let string = |description: &str| json!({ "type": "string", "description": description });
+
+    json!([
+        tool(
+            "read_file",
+            "Read a UTF-8 file in the workspace.",
+            json!({ "path": string("Path relative to the workspace root") }),
+            &["path"],
+        ),
+        tool(
+            "write_file",
+            "Create or overwrite a file in the workspace.",
+            json!({
+                "path": string("Path relative to the workspace root"),
+                "content": string("Full file content"),
+            }),
+            &["path", "content"],
+        ),
+        tool(
+            "edit_file",
+            "Replace one exact occurrence of old_text with new_text in a file.",
+            json!({
+                "path": string("Path relative to the workspace root"),
+                "old_text": string("Text to replace; must occur exactly once"),
+                "new_text": string("Replacement text"),
+            }),
+            &["path", "old_text", "new_text"],
+        ),
+        tool(
+            "run_command",
+            "Run a shell command in the workspace root and return its exit code and output.",
+            json!({ "command": string("Command passed to sh -c") }),
+            &["command"],
+        ),
+        tool(
+            "ralph_emit",
+            "Emit a Ralph event, like `ralph emit <topic> <payload>`.",
+            json!({
+                "topic": string("Event topic, e.g. build.done"),
+                "payload": string("Event payload"),
+            }),
+            &["topic"],
+        ),
+    ])
+}
+
+#[derive(Default)]
+struct Session {
+    output: String,
+    usage: ApiUsage,
+    turns: u32,
+}
+
+/// One assistant message being assembled from stream chunks.
+#[derive(Default)]
+struct Reply {
+    text: String,
+    tool_calls: Vec<PendingToolCall>,
+}
+
+#[derive(Default)]
+struct PendingToolCall {
+    id: String,
+    name: String,
+    arguments: String,
+}
+
+impl Reply {
+    fn apply(&mut self, chunk: ChatChunk, handler: &mut dyn StreamHandler, session: &mut Session) {
+        if let Some(usage) = chunk.usage {
+            session.usage.prompt_tokens += usage.prompt_tokens;
+            session.usage.completion_tokens += usage.completion_tokens;
+        }
+        for choice in chunk.choices {
+            let delta = choice.delta;
+            if let Some(text) = delta.content.filter(|text| !text.is_empty()) {
+                handler.on_text(&text);
+                self.text.push_str(&text);
+            }
+            for (position, call) in delta.tool_calls.unwrap_or_default().into_iter().enumerate() {
+                let index = call.index.unwrap_or(position);
+                if self.tool_calls.len() <= index {
+                    self.tool_calls
+                        .resize_with(index + 1, PendingToolCall::default);
+                }
+                let pending = &mut self.tool_calls[index];
+                if let Some(id) = call.id {
+                    pending.id = id;
+                }
+                if let Some(name) = call.function.name {
+                    pending.name.push_str(&name);
+                }
+                if let Some(arguments) = call.function.arguments {
+                    pending.arguments.push_str(&arguments);
+                }
+            }
+        }
+        for (index, call) in self.tool_calls.iter_mut().enumerate() {
+            if call.id.is_empty() {
+                call.id = format!("call_{index}");
+            }
+        }
+    }
+
+    fn to_message(&self) -> Value {
+        let mut message = json!({
+            "role": "assistant",
+            "content": if self.text.is_empty() { Value::Null } else { json!(self.text) },
+        });
+        if !self.tool_calls.is_empty() {
+            message["tool_calls"] = self
+                .tool_calls
+                .iter()
+                .map(|call| {
+                    json!({
+                        "id": call.id,
+                        "type": "function",
+                        "function": { "name": call.name, "arguments": call.arguments },
+                    })
+                })
+                .collect();
+        }
+        message
+    }
+}
+
+/// A streamed chunk or, with `message` in place of `delta`, a whole
+/// completion.
+#[derive(Deserialize)]
+struct ChatChunk {
+    #[serde(default)]
+    choices: Vec<ChunkChoice>,
+    usage: Option<ChunkUsage>,
+}
+
+#[derive(Deserialize)]
+struct ChunkChoice {
+    #[serde(default, alias = "message")]
+    delta: ChunkDelta,
+}
+
+#[derive(Default, Deserialize)]
+struct ChunkDelta {
+    content: Option<String>,
+    tool_calls: Option<Vec<ToolCallDelta>>,
+}
+
+#[derive(Deserialize)]
+struct ToolCallDelta {
+    index: Option<usize>,
+    id: Option<String>,
+    #[serde(default)]
+    function: FunctionDelta,
+}
+
+#[derive(Default, Deserialize)]
+struct FunctionDelta {
+    name: Option<String>,
+    arguments: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ChunkUsage {
+    #[serde(default)]
+    prompt_tokens: u64,
+    #[serde(default)]
+    completion_tokens: u64,
+}
+
+/// Splits a server-sent event stream into `data:` payloads.
+#[derive(Default)]
+struct SseBuffer {
+    pending: Vec<u8>,
+}
+
+impl SseBuffer {
+    fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.pending.extend_from_slice(bytes);
+        let mut data = Vec::new();
+        while let Some(end) = self.pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            if let Some(payload) = line.trim_end().strip_prefix("data:") {
+                data.push(payload.trim_start().to_string());
+            }
+        }
+        data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_in_workspace() {
+        let workspace = Path::new("/work/repo");
+        assert_eq!(
+            resolve_in_workspace(workspace, "src/lib.rs").unwrap(),
+            PathBuf::from("/work/repo/src/lib.rs")
+        );
+        assert_eq!(
+            resolve_in_workspace(workspace, "./src/../Cargo.toml").unwrap(),
+            PathBuf::from("/work/repo/Cargo.toml")
+        );
+        assert_eq!(
+            resolve_in_workspace(workspace, "/work/repo/README.md").unwrap(),
+            PathBuf::from("/work/repo/README.md")
+        );
+        assert!(resolve_in_workspace(workspace, "../other/secret").is_err());
+        assert!(resolve_in_workspace(workspace, "src/../../../etc/passwd").is_err());
+        assert!(resolve_in_workspace(workspace, "/etc/passwd").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_resolve_in_workspace_rejects_symlink_escapes() {
+        let outside = tempfile::tempdir().unwrap();
+        std::fs::write(outside.path().join("secret"), "x").unwrap();
+        let workspace = tempfile::tempdir().unwrap();
+        std::fs::create_dir(workspace.path().join("src")).unwrap();
+        std::os::unix::fs::symlink(outside.path(), workspace.path().join("escape")).unwrap();
+        std::os::unix::fs::symlink(
+            outside.path().join("secret"),
+            workspace.path().join("src/link"),
+        )
+        .unwrap();
+
+        assert!(resolve_in_workspace(workspace.path(), "escape/secret").is_err());
+        assert!(resolve_in_workspace(workspace.path(), "escape/new.txt").is_err());
+        assert!(resolve_in_workspace(workspace.path(), "src/link").is_err());
+
+        let canonical = workspace.path().canonicalize().unwrap();
+        assert_eq!(
+            resolve_in_workspace(workspace.path(), "src/new/file.rs").unwrap(),
+            canonical.join("src/new/file.rs")
+        );
+    }
+
+    #[test]
+    fn test_sse_buffer_handles_split_lines() {
+        let mut buffer = SseBuffer::default();
+        assert!(buffer.push(b"data: {\"a\":").is_empty());
+        assert_eq!(
+            buffer.push(b"1}\n\n: comment\ndata: [DONE]\n"),
+            vec!["{\"a\":1}".to_string(), "[DONE]".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_reply_assembles_streamed_tool_calls() {
+        let mut reply = Reply::default();
+        let mut session = Session::default();
+        let mut handler = crate::QuietStreamHandler;
+        let chunks = [
+            r#"{"choices":[{"delta":{"content":"Reading"}}]}"#,
+            r#"{"choices":[{"delta":{"tool_calls":[{"index":0,"id":"call_a","function":{"name":"read_file","arguments":"{\"pa"}}]}}]}"#,
+            r#"{"choices":[{"delta":{"tool_calls":[{"index":0,"function":{"arguments":"th\":\"a.txt\"}"}}]}}]}"#,
+            r#"{"choices":[],"usage":{"prompt_tokens":12,"completion_tokens":5}}"#,
+        ];
+        for chunk in chunks {
+            reply.apply(
+                serde_json::from_str(chunk).unwrap(),
+                &mut handler,
+                &mut session,
+            );
+        }
+
+        assert_eq!(reply.text, "Reading");
+        assert_eq!(reply.tool_calls.len(), 1);
+        assert_eq!(reply.tool_calls[0].id, "call_a");
+        assert_eq!(reply.tool_calls[0].name, "read_file");
+        assert_eq!(reply.tool_calls[0].arguments, r#"{"path":"a.txt"}"#);
+        assert_eq!(
+            session.usage,
+            ApiUsage {
+                prompt_tokens: 12,
+                completion_tokens: 5
+            }
+        );
+        assert_eq!(
+            reply.to_message()["tool_calls"][0]["function"]["name"],
+            "read_file"
+        );
+    }
+
+    #[test]
+    fn test_reply_accepts_whole_completion() {
+        let mut reply = Reply::default();
+        let mut session = Session::default();
+        let completion = r#"{"choices":[{"message":{"role":"assistant","content":"Done","tool_calls":null}}],"usage":{"prompt_tokens":3,"completion_tokens":1}}"#;
+        reply.apply(
+            serde_json::from_str(completion).unwrap(),
+            &mut crate::QuietStreamHandler,
+            &mut session,
+        );
+        assert_eq!(reply.text, "Done");
+        assert!(reply.tool_calls.is_empty());
+        assert_eq!(session.usage.completion_tokens, 1);
+    }
+}
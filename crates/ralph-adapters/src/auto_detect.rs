@@ -3,6 +3,9 @@
 //! When config specifies `agent: auto`, this module handles detecting
 //! which backends are available in the system PATH.
 
+use ralph_core::BackendDefinition;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
 use std::process::Command;
 use std::sync::OnceLock;
 use tracing::debug;
@@ -72,8 +75,26 @@ impl std::error::Error for NoBackendError {}
 /// for exit code 0. The command may differ from the backend name (e.g.,
 /// "kiro" backend uses "kiro-cli" command).
 pub fn is_backend_available(backend: &str) -> bool {
-    let command = detection_command(backend);
-    let result = Command::new(command).arg("--version").output();
+    is_backend_available_with(backend, &HashMap::new())
+}
+
+/// Like [`is_backend_available`], using a declared backend's
+/// `version_command` when `backend` names one.
+pub fn is_backend_available_with<S: BuildHasher>(
+    backend: &str,
+    definitions: &HashMap<String, BackendDefinition, S>,
+) -> bool {
+    let argv = definitions.get(backend).map_or_else(
+        || {
+            vec![
+                detection_command(backend).to_string(),
+                "--version".to_string(),
+            ]
+        },
+        BackendDefinition::version_check,
+    );
+    let command = argv[0].as_str();
+    let result = Command::new(command).args(&argv[1..]).output();
 
     match result {
         Ok(output) => {
@@ -110,6 +131,23 @@ pub fn is_backend_available(backend: &str) -> bool {
 pub fn detect_backend<F>(priority: &[&str], adapter_enabled: F) -> Result<String, NoBackendError>
 where
     F: Fn(&str) -> bool,
+{
+    detect_backend_with(priority, adapter_enabled, &HashMap::new())
+}
+
+/// Like [`detect_backend`], checking declared backends with their
+/// `version_command`.
+///
+/// # Errors
+/// Returns `NoBackendError` if none of the enabled backends is available.
+pub fn detect_backend_with<F, S>(
+    priority: &[&str],
+    adapter_enabled: F,
+    definitions: &HashMap<String, BackendDefinition, S>,
+) -> Result<String, NoBackendError>
+where
+    F: Fn(&str) -> bool,
+    S: BuildHasher,
 {
     debug!(priority = ?priority, "Starting backend auto-detection");
 
@@ -132,7 +170,7 @@ where
 
         checked.push(backend.to_string());
 
-        if is_backend_available(backend) {
+        if is_backend_available_with(backend, definitions) {
             debug!(backend = backend, "Backend detected and selected");
             // Cache the result (ignore if already set)
             let _ = DETECTED_BACKEND.set(Some(backend.to_string()));
@@ -172,6 +210,19 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_is_backend_available_uses_version_command() {
+        let definitions: HashMap<String, BackendDefinition> =
+            serde_json::from_value(serde_json::json!({
+                "present": { "command": "definitely_not_a_real_command_xyz123", "version_command": ["true"] },
+                "absent": { "command": "definitely_not_a_real_command_xyz123" },
+            }))
+            .unwrap();
+
+        assert!(is_backend_available_with("present", &definitions));
+        assert!(!is_backend_available_with("absent", &definitions));
+    }
+
     #[test]
     fn test_detect_backend_with_disabled_adapters() {
         // All adapters disabled should fail
@@ -1,8 +1,13 @@
 //! CLI backend definitions for different AI tools.
 
-use ralph_core::{CliConfig, HatBackend};
+use crate::model_selection::{ModelSelection, ModelSelectionError, model_selection_flags};
+use ralph_core::backend_definition::BackendDefinition;
+use ralph_core::{BackendOutputFormat, CliConfig, HatBackend, OutputRules};
+use std::collections::HashMap;
 use std::fmt;
+use std::hash::BuildHasher;
 use std::io::Write;
+use std::sync::Arc;
 use tempfile::NamedTempFile;
 
 /// Output format supported by a CLI backend.
@@ -18,6 +23,8 @@ pub enum OutputFormat {
     StreamJson,
     /// Newline-delimited JSON stream (Pi with --mode json)
     PiStreamJson,
+    /// Plain text classified line by line (declared backends with `output_format: lines`)
+    Lines,
 }
 
 /// Error when creating a custom backend without a command.
@@ -56,6 +63,8 @@ pub struct CliBackend {
     pub output_format: OutputFormat,
     /// Environment variables to set when spawning the process.
     pub env_vars: Vec<(String, String)>,
+    /// Line parser and usage rules for backends declared under `backends:`.
+    pub output_rules: Option<Arc<OutputRules>>,
 }
 
 impl CliBackend {
@@ -64,7 +73,23 @@ impl CliBackend {
     /// # Errors
     /// Returns `CustomBackendError` if backend is "custom" but no command is specified.
     pub fn from_config(config: &CliConfig) -> Result<Self, CustomBackendError> {
+        Self::from_config_with(config, &HashMap::new())
+    }
+
+    /// Creates a backend from configuration, resolving names declared under
+    /// `backends:` before the built-in ones.
+    ///
+    /// # Errors
+    /// Returns `CustomBackendError` if backend is "custom" but no command is
+    /// specified, or if the named definition is invalid.
+    pub fn from_config_with(
+        config: &CliConfig,
+        definitions: &HashMap<String, BackendDefinition>,
+    ) -> Result<Self, CustomBackendError> {
         let mut backend = match config.backend.as_str() {
+            name if definitions.contains_key(name) => {
+                Self::from_definition(&definitions[name], false)?
+            }
             "claude" => Self::claude(),
             "kiro" => Self::kiro(),
             "gemini" => Self::gemini(),
@@ -114,6 +139,7 @@ impl CliBackend {
             prompt_flag: Some("-p".to_string()),
             output_format: OutputFormat::StreamJson,
             env_vars: vec![],
+            output_rules: None,
         }
     }
 
@@ -137,6 +163,7 @@ impl CliBackend {
             prompt_flag: None,
             output_format: OutputFormat::Text,
             env_vars: vec![],
+            output_rules: None,
         }
     }
 
@@ -155,6 +182,7 @@ impl CliBackend {
             prompt_flag: None,
             output_format: OutputFormat::Text,
             env_vars: vec![],
+            output_rules: None,
         }
     }
 
@@ -175,6 +203,7 @@ impl CliBackend {
             prompt_flag: None,
             output_format: OutputFormat::Text,
             env_vars: vec![],
+            output_rules: None,
         };
         backend.args.extend(extra_args.iter().cloned());
         backend
@@ -188,7 +217,19 @@ impl CliBackend {
         name: &str,
         extra_args: &[String],
     ) -> Result<Self, CustomBackendError> {
-        let mut backend = Self::from_name(name)?;
+        Self::from_name_with_args_with(name, extra_args, &HashMap::new())
+    }
+
+    /// Like [`Self::from_name_with_args`], also resolving declared backends.
+    ///
+    /// # Errors
+    /// Returns error if the backend name is invalid.
+    pub fn from_name_with_args_with(
+        name: &str,
+        extra_args: &[String],
+        definitions: &HashMap<String, BackendDefinition>,
+    ) -> Result<Self, CustomBackendError> {
+        let mut backend = Self::from_name_with(name, definitions)?;
         backend.args.extend(extra_args.iter().cloned());
         if backend.command == "codex" {
             Self::reconcile_codex_args(&mut backend.args);
@@ -201,6 +242,20 @@ impl CliBackend {
     /// # Errors
     /// Returns error if the backend name is invalid.
     pub fn from_name(name: &str) -> Result<Self, CustomBackendError> {
+        Self::from_name_with(name, &HashMap::new())
+    }
+
+    /// Like [`Self::from_name`], also resolving declared backends.
+    ///
+    /// # Errors
+    /// Returns error if the backend name is invalid.
+    pub fn from_name_with(
+        name: &str,
+        definitions: &HashMap<String, BackendDefinition>,
+    ) -> Result<Self, CustomBackendError> {
+        if let Some(definition) = definitions.get(name) {
+            return Self::from_definition(definition, false);
+        }
         match name {
             "claude" => Ok(Self::claude()),
             "kiro" => Ok(Self::kiro()),
@@ -219,10 +274,21 @@ impl CliBackend {
     /// # Errors
     /// Returns error if the backend configuration is invalid.
     pub fn from_hat_backend(hat_backend: &HatBackend) -> Result<Self, CustomBackendError> {
+        Self::from_hat_backend_with(hat_backend, &HashMap::new())
+    }
+
+    /// Like [`Self::from_hat_backend`], also resolving declared backends.
+    ///
+    /// # Errors
+    /// Returns error if the backend configuration is invalid.
+    pub fn from_hat_backend_with(
+        hat_backend: &HatBackend,
+        definitions: &HashMap<String, BackendDefinition>,
+    ) -> Result<Self, CustomBackendError> {
         match hat_backend {
-            HatBackend::Named(name) => Self::from_name(name),
+            HatBackend::Named(name) => Self::from_name_with(name, definitions),
             HatBackend::NamedWithArgs { backend_type, args } => {
-                Self::from_name_with_args(backend_type, args)
+                Self::from_name_with_args_with(backend_type, args, definitions)
             }
             HatBackend::KiroAgent { agent, args, .. } => {
                 Ok(Self::kiro_with_agent(agent.clone(), args))
@@ -234,6 +300,7 @@ impl CliBackend {
                 prompt_flag: None,
                 output_format: OutputFormat::Text,
                 env_vars: vec![],
+                output_rules: None,
             }),
         }
     }
@@ -247,6 +314,7 @@ impl CliBackend {
             prompt_flag: Some("-p".to_string()),
             output_format: OutputFormat::Text,
             env_vars: vec![],
+            output_rules: None,
         }
     }
 
@@ -259,6 +327,7 @@ impl CliBackend {
             prompt_flag: None, // Positional argument
             output_format: OutputFormat::Text,
             env_vars: vec![],
+            output_rules: None,
         }
     }
 
@@ -271,6 +340,7 @@ impl CliBackend {
             prompt_flag: Some("-x".to_string()),
             output_format: OutputFormat::Text,
             env_vars: vec![],
+            output_rules: None,
         }
     }
 
@@ -286,6 +356,7 @@ impl CliBackend {
             prompt_flag: Some("-p".to_string()),
             output_format: OutputFormat::Text,
             env_vars: vec![],
+            output_rules: None,
         }
     }
 
@@ -302,6 +373,7 @@ impl CliBackend {
             prompt_flag: None, // Positional argument
             output_format: OutputFormat::Text,
             env_vars: vec![],
+            output_rules: None,
         }
     }
 
@@ -323,6 +395,7 @@ impl CliBackend {
                 "CLAUDE_CODE_EXPERIMENTAL_AGENT_TEAMS".to_string(),
                 "1".to_string(),
             )],
+            output_rules: None,
         }
     }
 
@@ -345,6 +418,20 @@ impl CliBackend {
     /// # Errors
     /// Returns `CustomBackendError` if the backend name is not recognized.
     pub fn for_interactive_prompt(backend_name: &str) -> Result<Self, CustomBackendError> {
+        Self::for_interactive_prompt_with(backend_name, &HashMap::new())
+    }
+
+    /// Like [`Self::for_interactive_prompt`], also resolving declared backends.
+    ///
+    /// # Errors
+    /// Returns `CustomBackendError` if the backend name is not recognized.
+    pub fn for_interactive_prompt_with(
+        backend_name: &str,
+        definitions: &HashMap<String, BackendDefinition>,
+    ) -> Result<Self, CustomBackendError> {
+        if let Some(definition) = definitions.get(backend_name) {
+            return Self::from_definition(definition, true);
+        }
         match backend_name {
             "claude" => Ok(Self::claude_interactive()),
             "kiro" => Ok(Self::kiro_interactive()),
@@ -370,6 +457,7 @@ impl CliBackend {
             prompt_flag: None,
             output_format: OutputFormat::Text,
             env_vars: vec![],
+            output_rules: None,
         }
     }
 
@@ -385,6 +473,7 @@ impl CliBackend {
             prompt_flag: Some("-i".to_string()), // NOT -p!
             output_format: OutputFormat::Text,
             env_vars: vec![],
+            output_rules: None,
         }
     }
 
@@ -400,6 +489,7 @@ impl CliBackend {
             prompt_flag: None, // Positional argument
             output_format: OutputFormat::Text,
             env_vars: vec![],
+            output_rules: None,
         }
     }
 
@@ -415,6 +505,7 @@ impl CliBackend {
             prompt_flag: Some("-x".to_string()),
             output_format: OutputFormat::Text,
             env_vars: vec![],
+            output_rules: None,
         }
     }
 
@@ -430,6 +521,7 @@ impl CliBackend {
             prompt_flag: Some("-p".to_string()),
             output_format: OutputFormat::Text,
             env_vars: vec![],
+            output_rules: None,
         }
     }
 
@@ -450,6 +542,7 @@ impl CliBackend {
             prompt_flag: None, // Positional argument
             output_format: OutputFormat::Text,
             env_vars: vec![],
+            output_rules: None,
         }
     }
 
@@ -468,6 +561,7 @@ impl CliBackend {
             prompt_flag: None, // Positional argument
             output_format: OutputFormat::Text,
             env_vars: vec![],
+            output_rules: None,
         }
     }
 
@@ -488,6 +582,7 @@ impl CliBackend {
             prompt_flag: Some("--prompt".to_string()),
             output_format: OutputFormat::Text,
             env_vars: vec![],
+            output_rules: None,
         }
     }
 
@@ -508,6 +603,7 @@ impl CliBackend {
             prompt_flag: None, // Positional argument
             output_format: OutputFormat::PiStreamJson,
             env_vars: vec![],
+            output_rules: None,
         }
     }
 
@@ -523,6 +619,7 @@ impl CliBackend {
             prompt_flag: None, // Positional argument
             output_format: OutputFormat::Text,
             env_vars: vec![],
+            output_rules: None,
         }
     }
 
@@ -545,9 +642,90 @@ impl CliBackend {
             prompt_flag: config.prompt_flag.clone(),
             output_format: OutputFormat::Text,
             env_vars: vec![],
+            output_rules: None,
+        })
+    }
+
+    /// Creates a backend from a `backends:` definition.
+    ///
+    /// `interactive` selects the definition's interactive arguments and
+    /// prompt flag, when it has them.
+    ///
+    /// # Errors
+    /// Returns `CustomBackendError` if the definition has no command or its
+    /// output rules don't compile (config validation reports the details).
+    pub fn from_definition(
+        definition: &BackendDefinition,
+        interactive: bool,
+    ) -> Result<Self, CustomBackendError> {
+        if definition.command.trim().is_empty() {
+            return Err(CustomBackendError);
+        }
+        let output_rules = definition.output_rules().map_err(|e| {
+            tracing::warn!(command = %definition.command, error = %e, "Invalid backend definition");
+            CustomBackendError
+        })?;
+
+        let (args, prompt_flag) = match (&definition.interactive, interactive) {
+            (Some(overrides), true) => (
+                overrides.args.clone(),
+                overrides
+                    .prompt_flag
+                    .clone()
+                    .or_else(|| definition.prompt_flag.clone()),
+            ),
+            _ => (definition.args.clone(), definition.prompt_flag.clone()),
+        };
+        let output_format = match definition.output_format {
+            BackendOutputFormat::Text => OutputFormat::Text,
+            BackendOutputFormat::ClaudeStream => OutputFormat::StreamJson,
+            BackendOutputFormat::PiStream => OutputFormat::PiStreamJson,
+            BackendOutputFormat::Lines => OutputFormat::Lines,
+        };
+        let has_rules = output_rules.line_parser.is_some() || output_rules.usage.is_some();
+
+        Ok(Self {
+            command: definition.command.clone(),
+            args,
+            prompt_mode: if definition.prompt_mode == "stdin" {
+                PromptMode::Stdin
+            } else {
+                PromptMode::Arg
+            },
+            prompt_flag,
+            output_format,
+            env_vars: definition
+                .env
+                .iter()
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+            output_rules: has_rules.then(|| Arc::new(output_rules)),
         })
     }
 
+    /// Extracts the run's cost from its output using the backend's usage
+    /// rules. `None` for built-in backends and when nothing matched.
+    pub fn extract_cost(&self, output: &str) -> Option<f64> {
+        self.output_rules.as_ref()?.usage.as_ref()?.cost(output)
+    }
+
+    /// Adds the flags that select `selection`'s model and reasoning effort,
+    /// translated for `backend_name`.
+    ///
+    /// # Errors
+    /// Returns `ModelSelectionError` if the backend cannot honour the selection.
+    pub fn apply_model_selection<S: BuildHasher>(
+        &mut self,
+        backend_name: &str,
+        selection: &ModelSelection,
+        definitions: &HashMap<String, BackendDefinition, S>,
+    ) -> Result<(), ModelSelectionError> {
+        let flags = model_selection_flags(backend_name, selection, definitions)?;
+        self.args.extend(flags.args);
+        self.env_vars.extend(flags.env_vars);
+        Ok(())
+    }
+
     /// Builds the full command with arguments for execution.
     ///
     /// # Arguments
@@ -1006,6 +1184,69 @@ mod tests {
         );
     }
 
+    fn definitions() -> HashMap<String, BackendDefinition> {
+        serde_json::from_value(serde_json::json!({
+            "aider": {
+                "command": "aider",
+                "args": ["--yes-always"],
+                "prompt_flag": "--message",
+                "interactive": { "args": ["--no-auto-commits"] },
+                "env": { "AIDER_ANALYTICS": "false" },
+                "output_format": "lines",
+                "usage": { "cost": r"\$([\d.]+) session" },
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_declared_backend_resolves_by_name() {
+        let definitions = definitions();
+        let config = CliConfig {
+            backend: "aider".to_string(),
+            args: vec!["--model".to_string(), "sonnet".to_string()],
+            ..Default::default()
+        };
+        let backend = CliBackend::from_config_with(&config, &definitions).unwrap();
+        let (cmd, args, stdin, _temp) = backend.build_command("fix it", false);
+
+        assert_eq!(cmd, "aider");
+        assert_eq!(
+            args,
+            ["--yes-always", "--model", "sonnet", "--message", "fix it"]
+        );
+        assert!(stdin.is_none());
+        assert_eq!(backend.output_format, OutputFormat::Lines);
+        assert_eq!(
+            backend.env_vars,
+            [("AIDER_ANALYTICS".to_string(), "false".to_string())]
+        );
+        assert_eq!(
+            backend.extract_cost("Cost: $0.01 message, $0.42 session."),
+            Some(0.42)
+        );
+
+        let hat = HatBackend::Named("aider".to_string());
+        assert!(CliBackend::from_hat_backend_with(&hat, &definitions).is_ok());
+        assert!(CliBackend::from_hat_backend(&hat).is_err());
+        assert!(CliBackend::from_name("aider").is_err());
+    }
+
+    #[test]
+    fn test_declared_backend_interactive_args() {
+        let backend = CliBackend::for_interactive_prompt_with("aider", &definitions()).unwrap();
+        let (_, args, _, _temp) = backend.build_command("plan it", true);
+
+        assert_eq!(args, ["--no-auto-commits", "--message", "plan it"]);
+    }
+
+    #[test]
+    fn test_builtin_backends_have_no_output_rules() {
+        let backend = CliBackend::claude();
+        assert!(backend.output_rules.is_none());
+        assert_eq!(backend.extract_cost("$1.00 session"), None);
+    }
+
     #[test]
     fn test_kiro_with_agent() {
         let backend = CliBackend::kiro_with_agent("my-agent".to_string(), &[]);
@@ -10,6 +10,7 @@ use crate::cli_backend::{OutputFormat, PromptMode};
 use nix::sys::signal::{Signal, kill};
 #[cfg(unix)]
 use nix::unistd::Pid;
+use ralph_core::Sandbox;
 use std::io::Write;
 use std::process::Stdio;
 use std::time::Duration;
@@ -34,12 +35,22 @@ pub struct ExecutionResult {
 #[derive(Debug)]
 pub struct CliExecutor {
     backend: CliBackend,
+    sandbox: Option<Sandbox>,
 }
 
 impl CliExecutor {
     /// Creates a new executor with the given backend.
     pub fn new(backend: CliBackend) -> Self {
-        Self { backend }
+        Self {
+            backend,
+            sandbox: None,
+        }
+    }
+
+    /// Launches the backend inside `sandbox`.
+    pub fn with_sandbox(mut self, sandbox: Option<Sandbox>) -> Self {
+        self.sandbox = sandbox;
+        self
     }
 
     /// Executes a prompt and streams output to the provided writer.
@@ -60,6 +71,13 @@ impl CliExecutor {
         // Note: _temp_file is kept alive for the duration of this function scope.
         // For large prompts (>7000 chars), Claude reads from the temp file.
         let (cmd, args, stdin_input, _temp_file) = self.backend.build_command(prompt, false);
+        let (cmd, args, sandbox_env) = match &self.sandbox {
+            Some(sandbox) => {
+                let wrapped = sandbox.wrap(&cmd, &args);
+                (wrapped.program, wrapped.args, wrapped.env)
+            }
+            None => (cmd, args, Vec::new()),
+        };
 
         let mut command = Command::new(&cmd);
         command.args(&args);
@@ -73,6 +91,7 @@ impl CliExecutor {
 
         // Apply backend-specific environment variables (e.g., Agent Teams env var)
         command.envs(self.backend.env_vars.iter().map(|(k, v)| (k, v)));
+        command.envs(sandbox_env);
 
         debug!(
             command = %cmd,
@@ -247,6 +266,7 @@ mod tests {
             prompt_flag: None,
             output_format: OutputFormat::Text,
             env_vars: vec![],
+            output_rules: None,
         };
 
         let executor = CliExecutor::new(backend);
@@ -272,6 +292,7 @@ mod tests {
             prompt_flag: None,
             output_format: OutputFormat::Text,
             env_vars: vec![],
+            output_rules: None,
         };
 
         let executor = CliExecutor::new(backend);
@@ -290,6 +311,7 @@ mod tests {
             prompt_flag: None,
             output_format: OutputFormat::Text,
             env_vars: vec![],
+            output_rules: None,
         };
 
         let executor = CliExecutor::new(backend);
@@ -312,6 +334,7 @@ mod tests {
             prompt_flag: None,
             output_format: OutputFormat::Text,
             env_vars: vec![],
+            output_rules: None,
         };
 
         let executor = CliExecutor::new(backend);
@@ -340,6 +363,7 @@ mod tests {
             prompt_flag: None,
             output_format: OutputFormat::Text,
             env_vars: vec![],
+            output_rules: None,
         };
 
         let executor = CliExecutor::new(backend);
@@ -9,14 +9,29 @@
 //! - Pi (pi-coding-agent)
 //! - Amp
 //! - Custom commands
+//! - OpenAI-compatible HTTP APIs (`api`)
 //!
-//! Each adapter implements the common CLI executor interface.
+//! Each CLI adapter implements the common CLI executor interface. The `api`
+//! backend calls a chat-completions endpoint directly and runs its own
+//! minimal tool loop (see `ApiBackend`); `testing::MockChatServer` stands in
+//! for the endpoint in tests.
 //!
 //! ## Auto-Detection
 //!
 //! When config specifies `agent: auto`, the `auto_detect` module handles
 //! detecting which backends are available in the system PATH.
 //!
+//! ## Failure Recovery
+//!
+//! The `recovery` module classifies failed iterations (rate limit, quota,
+//! auth, crash) and decides whether to back off, fail over to another
+//! backend, or let the failure count.
+//!
+//! ## Model Selection
+//!
+//! The `model_selection` module translates a hat's `model` and `effort` into
+//! each backend's flags and knows which models the built-in backends accept.
+//!
 //! ## PTY Mode
 //!
 //! The `pty_executor` module provides PTY-based execution for Claude CLI,
@@ -24,17 +39,23 @@
 //! allowing Ralph to orchestrate iterations. Supports interactive mode (user
 //! input forwarded) and observe mode (output-only).
 
+mod api_backend;
 mod auto_detect;
 mod claude_stream;
 mod cli_backend;
 mod cli_executor;
+mod model_selection;
 mod pi_stream;
 mod pty_executor;
 pub mod pty_handle;
+mod recovery;
 mod stream_handler;
+pub mod testing;
 
+pub use api_backend::{ApiBackend, ApiBackendError, ApiExecutionResult, ApiUsage};
 pub use auto_detect::{
-    DEFAULT_PRIORITY, NoBackendError, detect_backend, detect_backend_default, is_backend_available,
+    DEFAULT_PRIORITY, NoBackendError, detect_backend, detect_backend_default, detect_backend_with,
+    is_backend_available, is_backend_available_with,
 };
 pub use claude_stream::{
     AssistantMessage, ClaudeStreamEvent, ClaudeStreamParser, ContentBlock, Usage, UserContentBlock,
@@ -42,6 +63,10 @@ pub use claude_stream::{
 };
 pub use cli_backend::{CliBackend, CustomBackendError, OutputFormat, PromptMode};
 pub use cli_executor::{CliExecutor, ExecutionResult};
+pub use model_selection::{
+    ModelSelection, ModelSelectionError, ModelSelectionFlags, is_known_model, known_models,
+    model_selection_flags,
+};
 pub use pi_stream::{
     PiAssistantEvent, PiContentBlock, PiCost, PiSessionState, PiStreamEvent, PiStreamParser,
     PiToolResult, PiTurnMessage, PiUsage, dispatch_pi_stream_event,
@@ -50,6 +75,7 @@ pub use pty_executor::{
     CtrlCAction, CtrlCState, PtyConfig, PtyExecutionResult, PtyExecutor, TerminationType,
 };
 pub use pty_handle::{ControlCommand, PtyHandle};
+pub use recovery::{BackendFailure, BackendRecovery, RecoveryAction, classify_failure};
 pub use stream_handler::{
     ConsoleStreamHandler, PrettyStreamHandler, QuietStreamHandler, SessionResult, StreamHandler,
     TuiStreamHandler,
@@ -0,0 +1,393 @@
+//! Model and reasoning-effort selection.
+//!
+//! Hats (and `cli`) can ask for a `model` and an `effort`. Each backend spells
+//! these differently — Claude takes `--model` and a thinking-token budget,
+//! Codex a `model_reasoning_effort` config override, Pi a `--thinking` level —
+//! so [`model_selection_flags`] translates a [`ModelSelection`] into the
+//! arguments and environment for one backend. [`is_known_model`] backs the
+//! model checks in `ralph hats validate`.
+
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+use ralph_core::backend_definition::BackendDefinition;
+
+/// Backend name of the HTTP API backend, which takes its model from the
+/// request body rather than a flag.
+const API_BACKEND: &str = "api";
+
+/// A model and reasoning effort requested for an iteration.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelSelection {
+    /// Model name passed to the backend.
+    pub model: Option<String>,
+    /// Reasoning effort: "minimal", "low", "medium", or "high".
+    pub effort: Option<String>,
+}
+
+impl ModelSelection {
+    /// Returns true when neither a model nor an effort is requested.
+    pub fn is_empty(&self) -> bool {
+        self.model.is_none() && self.effort.is_none()
+    }
+}
+
+/// Why a backend cannot honour a [`ModelSelection`].
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum ModelSelectionError {
+    #[error("backend '{backend}' does not support selecting a model")]
+    ModelUnsupported { backend: String },
+
+    #[error("backend '{backend}' does not support reasoning effort")]
+    EffortUnsupported { backend: String },
+
+    #[error("backend '{backend}' does not support effort '{effort}' (supported: {supported})")]
+    EffortLevelUnsupported {
+        backend: String,
+        effort: String,
+        supported: String,
+    },
+}
+
+/// How a built-in backend receives the reasoning effort.
+#[derive(Debug, Clone, Copy)]
+enum EffortStyle {
+    /// No effort control.
+    None,
+    /// Claude's `MAX_THINKING_TOKENS` budget.
+    ThinkingTokens,
+    /// Codex's `-c model_reasoning_effort=<level>` override.
+    CodexConfig,
+    /// A flag followed by the level.
+    Flag(&'static str),
+}
+
+/// Model and effort support of a built-in backend.
+struct ModelSupport {
+    /// Models the backend is known to accept.
+    models: &'static [&'static str],
+    /// Prefixes of full model ids the backend accepts (e.g. "claude-").
+    model_prefixes: &'static [&'static str],
+    effort: EffortStyle,
+    efforts: &'static [&'static str],
+}
+
+const NO_EFFORT: &[&str] = &[];
+const ALL_EFFORTS: &[&str] = &["minimal", "low", "medium", "high"];
+
+/// Support table for built-in backends that take `--model`. Backends not
+/// listed here (amp, custom) cannot select a model.
+fn builtin_support(backend: &str) -> Option<ModelSupport> {
+    let support = match backend {
+        "claude" => ModelSupport {
+            models: &["sonnet", "opus", "haiku", "opusplan", "default"],
+            model_prefixes: &["claude-"],
+            effort: EffortStyle::ThinkingTokens,
+            efforts: &["low", "medium", "high"],
+        },
+        "codex" => ModelSupport {
+            models: &[
+                "gpt-5",
+                "gpt-5-codex",
+                "gpt-5-mini",
+                "gpt-5-nano",
+                "o3",
+                "o4-mini",
+                "codex-mini-latest",
+            ],
+            model_prefixes: &["gpt-", "o3-", "o4-"],
+            effort: EffortStyle::CodexConfig,
+            efforts: ALL_EFFORTS,
+        },
+        "gemini" => ModelSupport {
+            models: &[
+                "gemini-2.5-pro",
+                "gemini-2.5-flash",
+                "gemini-2.5-flash-lite",
+            ],
+            model_prefixes: &["gemini-"],
+            effort: EffortStyle::None,
+            efforts: NO_EFFORT,
+        },
+        "kiro" => ModelSupport {
+            models: &[
+                "auto",
+                "claude-sonnet-4",
+                "claude-sonnet-4.5",
+                "claude-haiku-4.5",
+            ],
+            model_prefixes: &["claude-"],
+            effort: EffortStyle::None,
+            efforts: NO_EFFORT,
+        },
+        "copilot" => ModelSupport {
+            models: &[
+                "claude-sonnet-4.5",
+                "claude-sonnet-4",
+                "claude-haiku-4.5",
+                "gpt-5",
+            ],
+            model_prefixes: &["claude-", "gpt-"],
+            effort: EffortStyle::None,
+            efforts: NO_EFFORT,
+        },
+        // OpenCode and Pi route to many providers; any model id is accepted.
+        "opencode" => ModelSupport {
+            models: &[],
+            model_prefixes: &[],
+            effort: EffortStyle::None,
+            efforts: NO_EFFORT,
+        },
+        "pi" => ModelSupport {
+            models: &[],
+            model_prefixes: &[],
+            effort: EffortStyle::Flag("--thinking"),
+            efforts: ALL_EFFORTS,
+        },
+        _ => return None,
+    };
+    Some(support)
+}
+
+/// Models a built-in backend is known to accept. Empty when the backend
+/// accepts any model id or cannot select a model.
+pub fn known_models(backend: &str) -> &'static [&'static str] {
+    builtin_support(backend).map_or(&[], |support| support.models)
+}
+
+/// Returns true when `model` is one the backend is known to accept.
+///
+/// Backends without a model list (opencode, pi, api, declared backends)
+/// accept any model.
+pub fn is_known_model(backend: &str, model: &str) -> bool {
+    let Some(support) = builtin_support(backend) else {
+        return true;
+    };
+    if support.models.is_empty() && support.model_prefixes.is_empty() {
+        return true;
+    }
+    support.models.contains(&model)
+        || support
+            .model_prefixes
+            .iter()
+            .any(|prefix| model.starts_with(prefix))
+}
+
+/// Arguments and environment that select a model and effort.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ModelSelectionFlags {
+    /// Arguments appended to the backend's arguments.
+    pub args: Vec<String>,
+    /// Environment variables set on the spawned process.
+    pub env_vars: Vec<(String, String)>,
+}
+
+/// Translates `selection` into the flags `backend` understands.
+///
+/// Backends declared under `backends:` use their `model_flag` and
+/// `effort_flag`. The `api` backend takes no flags; its model and effort go
+/// into the request instead, so only the effort level is checked.
+///
+/// # Errors
+/// Returns `ModelSelectionError` if the backend cannot select a model or
+/// does not support the requested effort.
+pub fn model_selection_flags<S: BuildHasher>(
+    backend: &str,
+    selection: &ModelSelection,
+    definitions: &HashMap<String, BackendDefinition, S>,
+) -> Result<ModelSelectionFlags, ModelSelectionError> {
+    let mut flags = ModelSelectionFlags::default();
+
+    if let Some(definition) = definitions.get(backend) {
+        if let Some(model) = &selection.model {
+            let flag = definition.model_flag.as_ref().ok_or_else(|| {
+                ModelSelectionError::ModelUnsupported {
+                    backend: backend.to_string(),
+                }
+            })?;
+            flags.args.extend([flag.clone(), model.clone()]);
+        }
+        if let Some(effort) = &selection.effort {
+            let flag = definition.effort_flag.as_ref().ok_or_else(|| {
+                ModelSelectionError::EffortUnsupported {
+                    backend: backend.to_string(),
+                }
+            })?;
+            flags.args.extend([flag.clone(), effort.clone()]);
+        }
+        return Ok(flags);
+    }
+
+    if backend == API_BACKEND {
+        if let Some(effort) = &selection.effort {
+            check_effort_level(backend, effort, ALL_EFFORTS)?;
+        }
+        return Ok(flags);
+    }
+
+    let Some(support) = builtin_support(backend) else {
+        if selection.model.is_some() {
+            return Err(ModelSelectionError::ModelUnsupported {
+                backend: backend.to_string(),
+            });
+        }
+        if selection.effort.is_some() {
+            return Err(ModelSelectionError::EffortUnsupported {
+                backend: backend.to_string(),
+            });
+        }
+        return Ok(flags);
+    };
+
+    if let Some(model) = &selection.model {
+        flags.args.extend(["--model".to_string(), model.clone()]);
+    }
+    if let Some(effort) = &selection.effort {
+        if matches!(support.effort, EffortStyle::None) {
+            return Err(ModelSelectionError::EffortUnsupported {
+                backend: backend.to_string(),
+            });
+        }
+        check_effort_level(backend, effort, support.efforts)?;
+        match support.effort {
+            EffortStyle::None => {}
+            EffortStyle::ThinkingTokens => flags.env_vars.push((
+                "MAX_THINKING_TOKENS".to_string(),
+                thinking_tokens(effort).to_string(),
+            )),
+            EffortStyle::CodexConfig => flags
+                .args
+                .extend(["-c".to_string(), format!("model_reasoning_effort={effort}")]),
+            EffortStyle::Flag(flag) => flags.args.extend([flag.to_string(), effort.clone()]),
+        }
+    }
+    Ok(flags)
+}
+
+fn check_effort_level(
+    backend: &str,
+    effort: &str,
+    supported: &[&str],
+) -> Result<(), ModelSelectionError> {
+    if supported.contains(&effort) {
+        Ok(())
+    } else {
+        Err(ModelSelectionError::EffortLevelUnsupported {
+            backend: backend.to_string(),
+            effort: effort.to_string(),
+            supported: supported.join(", "),
+        })
+    }
+}
+
+/// Claude thinking budgets, matching its "think" / "think hard" /
+/// "ultrathink" keywords.
+fn thinking_tokens(effort: &str) -> u32 {
+    match effort {
+        "low" => 4_000,
+        "medium" => 10_000,
+        _ => 31_999,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn selection(model: Option<&str>, effort: Option<&str>) -> ModelSelection {
+        ModelSelection {
+            model: model.map(str::to_string),
+            effort: effort.map(str::to_string),
+        }
+    }
+
+    fn flags(
+        backend: &str,
+        selection: &ModelSelection,
+    ) -> Result<ModelSelectionFlags, ModelSelectionError> {
+        model_selection_flags(backend, selection, &HashMap::new())
+    }
+
+    #[test]
+    fn test_claude_model_and_thinking_budget() {
+        let flags = flags("claude", &selection(Some("opus"), Some("high"))).unwrap();
+        assert_eq!(flags.args, vec!["--model", "opus"]);
+        assert_eq!(
+            flags.env_vars,
+            vec![("MAX_THINKING_TOKENS".to_string(), "31999".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_codex_effort_uses_config_override() {
+        let flags = flags("codex", &selection(Some("gpt-5-codex"), Some("minimal"))).unwrap();
+        assert_eq!(
+            flags.args,
+            vec![
+                "--model",
+                "gpt-5-codex",
+                "-c",
+                "model_reasoning_effort=minimal"
+            ]
+        );
+        assert!(flags.env_vars.is_empty());
+    }
+
+    #[test]
+    fn test_pi_effort_uses_thinking_flag() {
+        let flags = flags("pi", &selection(None, Some("low"))).unwrap();
+        assert_eq!(flags.args, vec!["--thinking", "low"]);
+    }
+
+    #[test]
+    fn test_unsupported_selections_are_errors() {
+        assert_eq!(
+            flags("amp", &selection(Some("x"), None)),
+            Err(ModelSelectionError::ModelUnsupported {
+                backend: "amp".to_string()
+            })
+        );
+        assert_eq!(
+            flags("gemini", &selection(None, Some("high"))),
+            Err(ModelSelectionError::EffortUnsupported {
+                backend: "gemini".to_string()
+            })
+        );
+        assert!(matches!(
+            flags("claude", &selection(None, Some("minimal"))),
+            Err(ModelSelectionError::EffortLevelUnsupported { .. })
+        ));
+        assert!(
+            flags("api", &selection(Some("gpt-4o"), Some("high"))).unwrap()
+                == ModelSelectionFlags::default()
+        );
+    }
+
+    #[test]
+    fn test_declared_backend_uses_its_flags() {
+        let definition: BackendDefinition = serde_json::from_value(serde_json::json!({
+            "command": "aider",
+            "model_flag": "--model",
+        }))
+        .unwrap();
+        let definitions = HashMap::from([("aider".to_string(), definition)]);
+
+        let flags =
+            model_selection_flags("aider", &selection(Some("sonnet"), None), &definitions).unwrap();
+        assert_eq!(flags.args, vec!["--model", "sonnet"]);
+        assert!(
+            model_selection_flags("aider", &selection(None, Some("high")), &definitions).is_err()
+        );
+    }
+
+    #[test]
+    fn test_is_known_model() {
+        assert!(is_known_model("claude", "sonnet"));
+        assert!(is_known_model("claude", "claude-opus-4-1"));
+        assert!(!is_known_model("claude", "sonet"));
+        assert!(!is_known_model("gemini", "gpt-5"));
+        assert!(is_known_model("opencode", "anthropic/claude-sonnet-4"));
+        assert!(is_known_model("my-agent", "anything"));
+        assert!(known_models("codex").contains(&"gpt-5-codex"));
+    }
+}
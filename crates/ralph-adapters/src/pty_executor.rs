@@ -27,6 +27,7 @@ use nix::sys::signal::{Signal, kill};
 #[cfg(unix)]
 use nix::unistd::Pid;
 use portable_pty::{CommandBuilder, PtyPair, PtySize, native_pty_system};
+use ralph_core::{LineParser, ParsedLine, Sandbox};
 use std::io::{self, Read, Write};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -192,6 +193,8 @@ pub struct PtyExecutor {
     // This replaces the previous inference via output_rx.is_none() which broke
     // after the streaming refactor (handle() is no longer called in TUI mode).
     tui_mode: bool,
+    // Namespace sandbox the backend is launched in, if enabled.
+    sandbox: Option<Sandbox>,
 }
 
 impl PtyExecutor {
@@ -214,6 +217,7 @@ impl PtyExecutor {
             terminated_tx,
             terminated_rx: Some(terminated_rx),
             tui_mode: false,
+            sandbox: None,
         }
     }
 
@@ -240,6 +244,11 @@ impl PtyExecutor {
         self.backend = backend;
     }
 
+    /// Launches the backend inside `sandbox` (or directly with `None`).
+    pub fn set_sandbox(&mut self, sandbox: Option<Sandbox>) {
+        self.sandbox = sandbox;
+    }
+
     /// Returns a handle for TUI integration.
     ///
     /// Can only be called once - panics if called multiple times.
@@ -282,6 +291,13 @@ impl PtyExecutor {
 
         let (cmd, args, stdin_input, temp_file) =
             self.backend.build_command(prompt, self.config.interactive);
+        let (cmd, args, sandbox_env) = match &self.sandbox {
+            Some(sandbox) => {
+                let wrapped = sandbox.wrap(&cmd, &args);
+                (wrapped.program, wrapped.args, wrapped.env)
+            }
+            None => (cmd, args, Vec::new()),
+        };
 
         let mut cmd_builder = CommandBuilder::new(&cmd);
         cmd_builder.args(&args);
@@ -294,7 +310,7 @@ impl PtyExecutor {
         cmd_builder.env("TERM", "xterm-256color");
 
         // Apply backend-specific environment variables (e.g., Agent Teams env var)
-        for (key, value) in &self.backend.env_vars {
+        for (key, value) in self.backend.env_vars.iter().chain(&sandbox_env) {
             cmd_builder.env(key, value);
         }
         let child = pair
@@ -578,6 +594,15 @@ impl PtyExecutor {
         // Text format streams raw output directly to handler
         let is_stream_json = output_format == OutputFormat::StreamJson;
         let is_pi_stream = output_format == OutputFormat::PiStreamJson;
+        // Lines format classifies plain-text lines with a declared backend's rules
+        let line_parser = if output_format == OutputFormat::Lines {
+            self.backend
+                .output_rules
+                .as_ref()
+                .and_then(|rules| rules.line_parser.as_ref())
+        } else {
+            None
+        };
         // Pi thinking deltas are noisy for plain console output but useful in TUI.
         let show_pi_thinking = is_pi_stream && self.tui_mode;
         let is_real_pi_backend = self.backend.command == "pi";
@@ -621,6 +646,8 @@ impl PtyExecutor {
         let mut extracted_text = String::new();
         // Pi session state for accumulating cost/turns (wall-clock for duration)
         let mut pi_state = PiSessionState::new();
+        // Tool calls seen by the line parser, used to pair results with calls
+        let mut line_tool_calls = 0usize;
         let start_time = Instant::now();
         let timeout_duration = if !self.config.interactive || self.config.idle_timeout_secs == 0 {
             None
@@ -744,6 +771,21 @@ impl PtyExecutor {
                                             );
                                         }
                                     }
+                                } else if let Some(parser) = line_parser {
+                                    // Lines format: classify each complete line
+                                    line_buffer.push_str(text);
+
+                                    while let Some(newline_pos) = line_buffer.find('\n') {
+                                        let line = line_buffer[..newline_pos].to_string();
+                                        line_buffer = line_buffer[newline_pos + 1..].to_string();
+
+                                        dispatch_parsed_line(
+                                            parser,
+                                            &line,
+                                            handler,
+                                            &mut line_tool_calls,
+                                        );
+                                    }
                                 } else {
                                     // Text format: Stream raw output directly to handler
                                     // This preserves ANSI escape codes for TUI rendering
@@ -768,6 +810,15 @@ impl PtyExecutor {
                                     &mut pi_state,
                                     show_pi_thinking,
                                 );
+                            } else if let Some(parser) = line_parser
+                                && !line_buffer.is_empty()
+                            {
+                                dispatch_parsed_line(
+                                    parser,
+                                    &line_buffer,
+                                    handler,
+                                    &mut line_tool_calls,
+                                );
                             }
                             break;
                         }
@@ -836,6 +887,19 @@ impl PtyExecutor {
                                         );
                                     }
                                 }
+                            } else if let Some(parser) = line_parser {
+                                // Lines: classify each complete line
+                                line_buffer.push_str(text);
+                                while let Some(newline_pos) = line_buffer.find('\n') {
+                                    let line = line_buffer[..newline_pos].to_string();
+                                    line_buffer = line_buffer[newline_pos + 1..].to_string();
+                                    dispatch_parsed_line(
+                                        parser,
+                                        &line,
+                                        handler,
+                                        &mut line_tool_calls,
+                                    );
+                                }
                             } else {
                                 // Text: stream raw output to handler
                                 handler.on_text(text);
@@ -861,6 +925,10 @@ impl PtyExecutor {
                         &mut pi_state,
                         show_pi_thinking,
                     );
+                } else if let Some(parser) = line_parser
+                    && !line_buffer.is_empty()
+                {
+                    dispatch_parsed_line(parser, &line_buffer, handler, &mut line_tool_calls);
                 }
 
                 let final_termination = resolve_termination_type(exit_code, termination);
@@ -1635,6 +1703,31 @@ fn dispatch_stream_event<H: StreamHandler>(
     }
 }
 
+/// Dispatches one line of a `lines`-format backend to the handler.
+///
+/// Events are still parsed from the full stripped output, so text lines are
+/// not accumulated into `extracted_text`.
+fn dispatch_parsed_line<H: StreamHandler>(
+    parser: &LineParser,
+    line: &str,
+    handler: &mut H,
+    tool_calls: &mut usize,
+) {
+    let line = strip_ansi(line.as_bytes());
+    match parser.parse(line.trim_end_matches('\r')) {
+        ParsedLine::Text(text) => handler.on_text(&format!("{text}\n")),
+        ParsedLine::ToolCall { name, input } => {
+            *tool_calls += 1;
+            handler.on_tool_call(&name, &format!("line-{tool_calls}"), &input);
+        }
+        ParsedLine::ToolResult(output) => {
+            handler.on_tool_result(&format!("line-{tool_calls}"), &output);
+        }
+        ParsedLine::Error(message) => handler.on_error(&message),
+        ParsedLine::Skip => {}
+    }
+}
+
 /// Builds a `PtyExecutionResult` from the accumulated output and exit status.
 ///
 /// # Arguments
@@ -2125,6 +2218,7 @@ mod tests {
             prompt_flag: None,
             output_format: OutputFormat::Text,
             env_vars: vec![],
+            output_rules: None,
         };
         let config = PtyConfig {
             interactive: false,
@@ -2159,6 +2253,7 @@ mod tests {
             prompt_flag: None,
             output_format: OutputFormat::Text,
             env_vars: vec![],
+            output_rules: None,
         };
         let config = PtyConfig {
             interactive: false,
@@ -2192,6 +2287,7 @@ mod tests {
             prompt_flag: None,
             output_format: OutputFormat::Text,
             env_vars: vec![],
+            output_rules: None,
         };
         let config = PtyConfig {
             interactive: false,
@@ -2228,6 +2324,7 @@ mod tests {
             prompt_flag: None,
             output_format: OutputFormat::StreamJson,
             env_vars: vec![],
+            output_rules: None,
         };
         let config = PtyConfig {
             interactive: false,
@@ -2269,6 +2366,7 @@ mod tests {
             prompt_flag: None,
             output_format: OutputFormat::Text,
             env_vars: vec![],
+            output_rules: None,
         };
         let config = PtyConfig {
             interactive: true,
@@ -0,0 +1,520 @@
+//! Backend failure classification and recovery.
+//!
+//! When an iteration fails, the loop runner asks [`classify_failure`] whether
+//! the backend itself is the problem — a rate limit, an exhausted quota, an
+//! auth error, or a crash — as opposed to the agent simply doing a bad job.
+//! [`BackendRecovery`] then decides what to do about it: wait for the rate
+//! limit to reset and retry, switch to the next available backend in
+//! `agent_priority`, or let the failure count as usual.
+
+use std::collections::HashSet;
+use std::sync::LazyLock;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use regex::Regex;
+use tracing::debug;
+
+use ralph_core::{BackendRecoveryConfig, RalphConfig};
+
+use crate::auto_detect::is_backend_available_with;
+
+/// Only the end of the output is inspected, so an agent that merely talks
+/// about rate limits earlier in its run is not misclassified.
+const TAIL_BYTES: usize = 4096;
+
+/// Consecutive crashes before a crashing backend is failed over.
+const CRASHES_BEFORE_FAILOVER: u32 = 2;
+
+/// Extra time added to a backend's reset hint, so the retry lands after it.
+const RESET_MARGIN: Duration = Duration::from_secs(2);
+
+/// Why a backend could not complete an iteration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BackendFailure {
+    /// Too many requests; `retry_after` is the backend's reset hint, if any.
+    RateLimit { retry_after: Option<Duration> },
+    /// Usage quota or credit exhausted.
+    Quota,
+    /// Missing, invalid, or expired credentials.
+    Auth,
+    /// The backend process died (signal or abnormal exit).
+    Crash,
+}
+
+impl BackendFailure {
+    /// Short name for logs and events.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BackendFailure::RateLimit { .. } => "rate_limit",
+            BackendFailure::Quota => "quota",
+            BackendFailure::Auth => "auth",
+            BackendFailure::Crash => "crash",
+        }
+    }
+}
+
+/// Case-insensitive patterns that identify a failure class.
+struct Patterns {
+    auth: &'static [&'static str],
+    quota: &'static [&'static str],
+    rate_limit: &'static [&'static str],
+}
+
+const COMMON: Patterns = Patterns {
+    auth: &[
+        "invalid api key",
+        "invalid_api_key",
+        "authentication_error",
+        "authentication failed",
+        "not logged in",
+        "please log in",
+        "login required",
+    ],
+    quota: &[
+        "insufficient_quota",
+        "quota exceeded",
+        "exceeded your current quota",
+        "credit balance is too low",
+        "out of credits",
+    ],
+    rate_limit: &[
+        "rate limit",
+        "rate_limit",
+        "ratelimit",
+        "too many requests",
+        "overloaded",
+    ],
+};
+
+/// Backend-specific wording on top of [`COMMON`].
+fn backend_patterns(backend: &str) -> Patterns {
+    match backend {
+        "claude" => Patterns {
+            auth: &["please run /login", "oauth token has expired"],
+            quota: &[],
+            // "Claude AI usage limit reached|<reset epoch>" resets, so it is a
+            // rate limit rather than a quota.
+            rate_limit: &["usage limit reached", "overloaded_error"],
+        },
+        "gemini" => Patterns {
+            auth: &["api key not valid", "permission_denied"],
+            quota: &["quota exceeded for quota metric"],
+            rate_limit: &["resource_exhausted"],
+        },
+        "codex" => Patterns {
+            auth: &["please run `codex login`", "codex login"],
+            quota: &["you've hit your usage limit"],
+            rate_limit: &["exceeded retry limit"],
+        },
+        "copilot" => Patterns {
+            auth: &["no authentication information found", "gh auth login"],
+            quota: &["monthly quota"],
+            rate_limit: &[],
+        },
+        _ => Patterns {
+            auth: &[],
+            quota: &[],
+            rate_limit: &[],
+        },
+    }
+}
+
+/// HTTP status codes reported as such (`status: 429`, `HTTP 401`, `"code":429`).
+static STATUS_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)\b(?:status|code|error|http)["\s:=]*(401|403|429)\b"#).unwrap()
+});
+
+/// Claude's `usage limit reached|<unix timestamp>`.
+static RESET_EPOCH_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?i)limit reached\|(\d{10})").unwrap());
+
+/// `try again in 20s`, `retry after 1.5 minutes`, `resets in 2h`, `retry-after: 30`.
+static RETRY_IN_RE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)(?:try again in|retry in|retry after|retry-after:|resets? in)\s*(\d+(?:\.\d+)?)\s*(ms|milliseconds?|s|secs?|seconds?|m|mins?|minutes?|h|hrs?|hours?)?\b",
+    )
+    .unwrap()
+});
+
+/// Gemini's `"retryDelay": "17s"`.
+static RETRY_DELAY_RE: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r#"(?i)"retryDelay"\s*:\s*"(\d+(?:\.\d+)?)s""#).unwrap());
+
+/// Classifies a failed iteration's output. Returns `None` when the failure
+/// does not look like the backend's fault.
+///
+/// Only call this for iterations that failed: successful output is never a
+/// backend failure, whatever it says.
+pub fn classify_failure(
+    backend: &str,
+    exit_code: Option<i32>,
+    output: &str,
+) -> Option<BackendFailure> {
+    classify_failure_at(backend, exit_code, output, SystemTime::now())
+}
+
+fn classify_failure_at(
+    backend: &str,
+    exit_code: Option<i32>,
+    output: &str,
+    now: SystemTime,
+) -> Option<BackendFailure> {
+    let tail = tail(output).to_lowercase();
+    let specific = backend_patterns(backend);
+    let matches = |common: &[&str], specific: &[&str]| {
+        common
+            .iter()
+            .chain(specific)
+            .any(|pattern| tail.contains(pattern))
+    };
+    let status = STATUS_RE
+        .captures(&tail)
+        .and_then(|caps| caps.get(1))
+        .map(|code| code.as_str().to_string());
+
+    let failure = if matches(COMMON.auth, specific.auth)
+        || matches!(status.as_deref(), Some("401" | "403"))
+    {
+        Some(BackendFailure::Auth)
+    } else if matches(COMMON.quota, specific.quota) {
+        Some(BackendFailure::Quota)
+    } else if matches(COMMON.rate_limit, specific.rate_limit) || status.as_deref() == Some("429") {
+        Some(BackendFailure::RateLimit {
+            retry_after: parse_retry_after(&tail, now),
+        })
+    } else if exit_code.is_none_or(|code| code >= 128) {
+        // No exit code means the process was killed by a signal; shells
+        // report signal deaths as 128 + signal.
+        Some(BackendFailure::Crash)
+    } else {
+        None
+    };
+    debug!(backend, ?exit_code, ?failure, "Classified backend failure");
+    failure
+}
+
+/// The last [`TAIL_BYTES`] of the output, on a character boundary.
+fn tail(output: &str) -> &str {
+    let mut start = output.len().saturating_sub(TAIL_BYTES);
+    while !output.is_char_boundary(start) {
+        start += 1;
+    }
+    &output[start..]
+}
+
+/// Extracts how long until the rate limit resets, if the backend said.
+fn parse_retry_after(text: &str, now: SystemTime) -> Option<Duration> {
+    if let Some(caps) = RESET_EPOCH_RE.captures(text) {
+        let reset = UNIX_EPOCH + Duration::from_secs(caps[1].parse().ok()?);
+        return Some(reset.duration_since(now).unwrap_or_default());
+    }
+    if let Some(caps) = RETRY_DELAY_RE.captures(text) {
+        return Duration::try_from_secs_f64(caps[1].parse().ok()?).ok();
+    }
+    let caps = RETRY_IN_RE.captures(text)?;
+    let amount: f64 = caps[1].parse().ok()?;
+    let unit = caps.get(2).map_or("s", |unit| unit.as_str());
+    let secs = match unit.chars().next() {
+        Some('m') if unit.starts_with("ms") || unit.starts_with("milli") => amount / 1000.0,
+        Some('m') => amount * 60.0,
+        Some('h') => amount * 3600.0,
+        _ => amount,
+    };
+    Duration::try_from_secs_f64(secs).ok()
+}
+
+/// What the loop should do about a failed iteration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecoveryAction {
+    /// Wait, then run the same iteration again on the same backend.
+    Retry { delay: Duration },
+    /// Run the same iteration again on another backend, and keep using it.
+    Failover { backend: String },
+    /// Nothing to do; the failure counts as a normal failed iteration.
+    GiveUp,
+}
+
+/// Tracks backend failures across a loop and decides how to recover.
+pub struct BackendRecovery {
+    config: BackendRecoveryConfig,
+    /// Enabled backends from `agent_priority`, in order.
+    priority: Vec<String>,
+    /// Backends already failed over from; never switched back to.
+    abandoned: HashSet<String>,
+    rate_limit_retries: u32,
+    crashes: u32,
+    is_available: Box<dyn Fn(&str) -> bool + Send + Sync>,
+}
+
+impl BackendRecovery {
+    /// Creates the recovery policy from `cli.recovery` and `agent_priority`.
+    pub fn new(config: &RalphConfig) -> Self {
+        Self {
+            config: config.cli.recovery.clone(),
+            priority: config
+                .get_agent_priority()
+                .into_iter()
+                .filter(|backend| config.adapter_settings(backend).enabled)
+                .map(String::from)
+                .collect(),
+            abandoned: HashSet::new(),
+            rate_limit_retries: 0,
+            crashes: 0,
+            is_available: {
+                let definitions = config.backends.clone();
+                Box::new(move |backend| is_backend_available_with(backend, &definitions))
+            },
+        }
+    }
+
+    /// Replaces the availability check (`<backend> --version` by default).
+    pub fn with_availability(
+        mut self,
+        is_available: impl Fn(&str) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.is_available = Box::new(is_available);
+        self
+    }
+
+    /// Resets the retry counters after an iteration the backend completed.
+    pub fn record_success(&mut self) {
+        self.rate_limit_retries = 0;
+        self.crashes = 0;
+    }
+
+    /// Decides how to handle `failure` from `backend`. `can_failover` is
+    /// false when the iteration ran on a hat-level backend, which failover
+    /// does not replace.
+    pub fn on_failure(
+        &mut self,
+        backend: &str,
+        failure: &BackendFailure,
+        can_failover: bool,
+    ) -> RecoveryAction {
+        let action = match failure {
+            BackendFailure::RateLimit { retry_after } => {
+                if let Some(delay) = self.backoff_delay(*retry_after) {
+                    self.rate_limit_retries += 1;
+                    return RecoveryAction::Retry { delay };
+                }
+                self.failover(backend, can_failover)
+            }
+            BackendFailure::Quota | BackendFailure::Auth => self.failover(backend, can_failover),
+            BackendFailure::Crash => {
+                self.crashes += 1;
+                if self.crashes >= CRASHES_BEFORE_FAILOVER {
+                    self.failover(backend, can_failover)
+                } else {
+                    RecoveryAction::GiveUp
+                }
+            }
+        };
+        if action == RecoveryAction::GiveUp {
+            // The failure counts against the loop; start the next
+            // iteration with a fresh retry budget.
+            self.rate_limit_retries = 0;
+        }
+        action
+    }
+
+    /// How long to wait before retrying a rate-limited iteration, or `None`
+    /// if it should not be retried.
+    fn backoff_delay(&self, retry_after: Option<Duration>) -> Option<Duration> {
+        if !self.config.backoff || self.rate_limit_retries >= self.config.max_retries {
+            return None;
+        }
+        let max = Duration::from_secs(self.config.max_backoff_secs);
+        match retry_after {
+            // Waiting out a long reset is pointless; let failover handle it.
+            Some(hint) if hint > max => None,
+            Some(hint) => Some(hint + RESET_MARGIN),
+            None => {
+                let factor = 2u64.saturating_pow(self.rate_limit_retries);
+                Some(
+                    Duration::from_secs(self.config.initial_backoff_secs.saturating_mul(factor))
+                        .min(max),
+                )
+            }
+        }
+    }
+
+    fn failover(&mut self, backend: &str, can_failover: bool) -> RecoveryAction {
+        if !self.config.failover || !can_failover {
+            return RecoveryAction::GiveUp;
+        }
+        self.abandoned.insert(backend.to_string());
+        let next = self
+            .priority
+            .iter()
+            .filter(|candidate| !self.abandoned.contains(candidate.as_str()))
+            .find(|candidate| (self.is_available)(candidate))
+            .cloned();
+        match next {
+            Some(backend) => {
+                self.rate_limit_retries = 0;
+                self.crashes = 0;
+                RecoveryAction::Failover { backend }
+            }
+            None => RecoveryAction::GiveUp,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(failover: bool) -> BackendRecovery {
+        let mut config = RalphConfig::default();
+        config.agent_priority = vec!["claude".into(), "gemini".into(), "codex".into()];
+        config.cli.recovery.failover = failover;
+        BackendRecovery::new(&config).with_availability(|backend| backend != "gemini")
+    }
+
+    #[test]
+    fn test_classify_failure_by_backend_wording() {
+        let now = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let cases = [
+            (
+                "claude",
+                "Claude AI usage limit reached|1700003600",
+                Some(BackendFailure::RateLimit {
+                    retry_after: Some(Duration::from_hours(1)),
+                }),
+            ),
+            (
+                "claude",
+                "Invalid API key · Please run /login",
+                Some(BackendFailure::Auth),
+            ),
+            (
+                "gemini",
+                r#"{"error": {"status": "RESOURCE_EXHAUSTED", "retryDelay": "17s"}}"#,
+                Some(BackendFailure::RateLimit {
+                    retry_after: Some(Duration::from_secs(17)),
+                }),
+            ),
+            (
+                "codex",
+                "You've hit your usage limit. Upgrade to Pro",
+                Some(BackendFailure::Quota),
+            ),
+            (
+                "codex",
+                "stream error: exceeded retry limit, last status: 429; try again in 1.5 minutes",
+                Some(BackendFailure::RateLimit {
+                    retry_after: Some(Duration::from_secs(90)),
+                }),
+            ),
+            ("amp", "HTTP 401", Some(BackendFailure::Auth)),
+            ("amp", "Tests failed: 3 of 40", None),
+        ];
+        for (backend, output, expected) in cases {
+            assert_eq!(
+                classify_failure_at(backend, Some(1), output, now),
+                expected,
+                "{backend}: {output}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_classify_failure_crashes_and_tail() {
+        assert_eq!(
+            classify_failure("claude", None, "working..."),
+            Some(BackendFailure::Crash)
+        );
+        assert_eq!(
+            classify_failure("claude", Some(137), ""),
+            Some(BackendFailure::Crash)
+        );
+        // Rate-limit chatter early in a long output is ignored.
+        let output = format!("fixing the rate limit middleware\n{}", "x".repeat(5000));
+        assert_eq!(classify_failure("claude", Some(1), &output), None);
+    }
+
+    #[test]
+    fn test_rate_limits_back_off_then_fail_over() {
+        let mut recovery = policy(true);
+        let limited = BackendFailure::RateLimit { retry_after: None };
+
+        // Exponential backoff without a reset hint.
+        for expected in [30, 60, 120] {
+            assert_eq!(
+                recovery.on_failure("claude", &limited, true),
+                RecoveryAction::Retry {
+                    delay: Duration::from_secs(expected)
+                }
+            );
+        }
+        // Retries exhausted: gemini is unavailable, so codex is next.
+        assert_eq!(
+            recovery.on_failure("claude", &limited, true),
+            RecoveryAction::Failover {
+                backend: "codex".to_string()
+            }
+        );
+
+        // A reset hint is waited out, plus a margin.
+        let hinted = BackendFailure::RateLimit {
+            retry_after: Some(Duration::from_secs(10)),
+        };
+        assert_eq!(
+            recovery.on_failure("codex", &hinted, true),
+            RecoveryAction::Retry {
+                delay: Duration::from_secs(12)
+            }
+        );
+
+        // A reset beyond max_backoff_secs is not waited for, and no
+        // backends are left.
+        let long = BackendFailure::RateLimit {
+            retry_after: Some(Duration::from_hours(5)),
+        };
+        assert_eq!(
+            recovery.on_failure("codex", &long, true),
+            RecoveryAction::GiveUp
+        );
+    }
+
+    #[test]
+    fn test_failover_requires_config_and_global_backend() {
+        let mut recovery = policy(false);
+        assert_eq!(
+            recovery.on_failure("claude", &BackendFailure::Quota, true),
+            RecoveryAction::GiveUp
+        );
+
+        let mut recovery = policy(true);
+        assert_eq!(
+            recovery.on_failure("claude", &BackendFailure::Auth, false),
+            RecoveryAction::GiveUp
+        );
+        assert_eq!(
+            recovery.on_failure("claude", &BackendFailure::Auth, true),
+            RecoveryAction::Failover {
+                backend: "codex".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_crashes_fail_over_after_repeats() {
+        let mut recovery = policy(true);
+        assert_eq!(
+            recovery.on_failure("claude", &BackendFailure::Crash, true),
+            RecoveryAction::GiveUp
+        );
+        recovery.record_success();
+        assert_eq!(
+            recovery.on_failure("claude", &BackendFailure::Crash, true),
+            RecoveryAction::GiveUp
+        );
+        assert_eq!(
+            recovery.on_failure("claude", &BackendFailure::Crash, true),
+            RecoveryAction::Failover {
+                backend: "codex".to_string()
+            }
+        );
+    }
+}
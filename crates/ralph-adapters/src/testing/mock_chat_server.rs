@@ -0,0 +1,299 @@
+//! A local stand-in for an OpenAI-compatible chat-completions API.
+//!
+//! [`MockChatServer`] listens on a loopback port and answers
+//! `POST /v1/chat/completions` with scripted replies, streamed as
+//! server-sent events when the request asks for `stream: true`. Every
+//! reply reports 10 prompt and 5 completion tokens.
+//!
+//! ```no_run
+//! # async fn example() -> std::io::Result<()> {
+//! use ralph_adapters::testing::{MockChatServer, MockReply};
+//!
+//! let server = MockChatServer::start().await?;
+//! server.push(MockReply::tool_call("read_file", serde_json::json!({ "path": "README.md" })));
+//! server.push(MockReply::text("Done"));
+//! // Point `cli.api.base_url` at `server.base_url()`.
+//! # Ok(())
+//! # }
+//! ```
+
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use serde_json::{Value, json};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::task::JoinHandle;
+
+/// Tokens reported for every reply.
+const PROMPT_TOKENS: u64 = 10;
+const COMPLETION_TOKENS: u64 = 5;
+
+/// A scripted response to one chat-completions request.
+#[derive(Debug, Clone)]
+pub enum MockReply {
+    /// An assistant message with no tool calls, ending the tool loop.
+    Text(String),
+    /// A single tool call.
+    ToolCall { name: String, arguments: Value },
+    /// An HTTP error response.
+    Error { status: u16, body: String },
+}
+
+impl MockReply {
+    pub fn text(text: impl Into<String>) -> Self {
+        Self::Text(text.into())
+    }
+
+    pub fn tool_call(name: impl Into<String>, arguments: Value) -> Self {
+        Self::ToolCall {
+            name: name.into(),
+            arguments,
+        }
+    }
+
+    pub fn error(status: u16, body: impl Into<String>) -> Self {
+        Self::Error {
+            status,
+            body: body.into(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct ServerState {
+    replies: VecDeque<MockReply>,
+    requests: Vec<Value>,
+    next_call_id: u32,
+}
+
+/// An in-process chat-completions server for offline tests.
+///
+/// Stops listening when dropped.
+pub struct MockChatServer {
+    port: u16,
+    state: Arc<Mutex<ServerState>>,
+    accept_task: JoinHandle<()>,
+}
+
+impl MockChatServer {
+    /// Starts the server on a free loopback port.
+    pub async fn start() -> std::io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let port = listener.local_addr()?.port();
+        let state = Arc::new(Mutex::new(ServerState::default()));
+
+        let accept_state = state.clone();
+        let accept_task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let state = accept_state.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = serve_connection(stream, &state).await {
+                        tracing::debug!(error = %e, "mock chat connection failed");
+                    }
+                });
+            }
+        });
+
+        Ok(Self {
+            port,
+            state,
+            accept_task,
+        })
+    }
+
+    /// Base URL to use as `cli.api.base_url`.
+    pub fn base_url(&self) -> String {
+        format!("http://127.0.0.1:{}/v1", self.port)
+    }
+
+    /// Queues the reply to the next request.
+    pub fn push(&self, reply: MockReply) {
+        lock(&self.state).replies.push_back(reply);
+    }
+
+    /// Bodies of every request received so far, in order.
+    pub fn requests(&self) -> Vec<Value> {
+        lock(&self.state).requests.clone()
+    }
+}
+
+impl Drop for MockChatServer {
+    fn drop(&mut self) {
+        self.accept_task.abort();
+    }
+}
+
+fn lock(state: &Mutex<ServerState>) -> MutexGuard<'_, ServerState> {
+    state.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+async fn serve_connection(
+    mut stream: TcpStream,
+    state: &Mutex<ServerState>,
+) -> std::io::Result<()> {
+    let Some((path, body)) = read_request(&mut stream).await? else {
+        return Ok(());
+    };
+
+    let response = if path.trim_end_matches('/').ends_with("/chat/completions") {
+        let request: Value = serde_json::from_slice(&body).unwrap_or(Value::Null);
+        let stream_reply = request["stream"].as_bool().unwrap_or(false);
+        let mut state = lock(state);
+        state.requests.push(request);
+        let call_id = format!("call_{}", state.next_call_id);
+        state.next_call_id += 1;
+        match state.replies.pop_front() {
+            Some(MockReply::Error { status, body }) => {
+                http_response(status, "application/json", &body)
+            }
+            Some(reply) if stream_reply => {
+                http_response(200, "text/event-stream", &stream_body(&reply, &call_id))
+            }
+            Some(reply) => http_response(
+                200,
+                "application/json",
+                &completion_body(&reply, &call_id).to_string(),
+            ),
+            None => http_response(
+                500,
+                "application/json",
+                r#"{"error":{"message":"no scripted reply"}}"#,
+            ),
+        }
+    } else {
+        http_response(
+            404,
+            "application/json",
+            r#"{"error":{"message":"Not Found"}}"#,
+        )
+    };
+
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+fn http_response(status: u16, content_type: &str, body: &str) -> String {
+    let reason = match status {
+        200 => "OK",
+        404 => "Not Found",
+        429 => "Too Many Requests",
+        500 => "Internal Server Error",
+        _ => "Error",
+    };
+    format!(
+        "HTTP/1.1 {status} {reason}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    )
+}
+
+fn usage() -> Value {
+    json!({
+        "prompt_tokens": PROMPT_TOKENS,
+        "completion_tokens": COMPLETION_TOKENS,
+        "total_tokens": PROMPT_TOKENS + COMPLETION_TOKENS,
+    })
+}
+
+/// The reply as a sequence of SSE chunks, splitting text and arguments so
+/// clients have to reassemble them.
+fn stream_body(reply: &MockReply, call_id: &str) -> String {
+    let mut chunks = Vec::new();
+    match reply {
+        MockReply::Text(text) => {
+            let middle = text
+                .char_indices()
+                .map(|(i, _)| i)
+                .nth(text.chars().count() / 2)
+                .unwrap_or(text.len());
+            let (first, second) = text.split_at(middle);
+            for part in [first, second] {
+                chunks.push(json!({ "choices": [{ "index": 0, "delta": { "content": part } }] }));
+            }
+        }
+        MockReply::ToolCall { name, arguments } => {
+            let arguments = arguments.to_string();
+            let (first, second) = arguments.split_at(arguments.len() / 2);
+            let opening = json!({
+                "index": 0,
+                "id": call_id,
+                "type": "function",
+                "function": { "name": name, "arguments": first },
+            });
+            let rest = json!({ "index": 0, "function": { "arguments": second } });
+            for call in [opening, rest] {
+                chunks.push(
+                    json!({ "choices": [{ "index": 0, "delta": { "tool_calls": [call] } }] }),
+                );
+            }
+        }
+        MockReply::Error { .. } => {}
+    }
+    chunks.push(json!({ "choices": [], "usage": usage() }));
+
+    let mut body = String::new();
+    for chunk in chunks {
+        body.push_str(&format!("data: {chunk}\n\n"));
+    }
+    body.push_str("data: [DONE]\n\n");
+    body
+}
+
+fn completion_body(reply: &MockReply, call_id: &str) -> Value {
+    let message = match reply {
+        MockReply::ToolCall { name, arguments } => json!({
+            "role": "assistant",
+            "content": null,
+            "tool_calls": [{
+                "id": call_id,
+                "type": "function",
+                "function": { "name": name, "arguments": arguments.to_string() },
+            }],
+        }),
+        MockReply::Text(text) => json!({ "role": "assistant", "content": text }),
+        MockReply::Error { .. } => Value::Null,
+    };
+    json!({ "choices": [{ "index": 0, "message": message }], "usage": usage() })
+}
+
+/// Reads a request's path and body (`Content-Length` framed).
+async fn read_request(stream: &mut TcpStream) -> std::io::Result<Option<(String, Vec<u8>)>> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 8192];
+    let header_end = loop {
+        if let Some(pos) = buf.windows(4).position(|window| window == b"\r\n\r\n") {
+            break pos;
+        }
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(None);
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    };
+
+    let head = String::from_utf8_lossy(&buf[..header_end]).into_owned();
+    let mut lines = head.split("\r\n");
+    let path = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .unwrap_or("/")
+        .to_string();
+    let headers: HashMap<String, String> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+    let length: usize = headers
+        .get("content-length")
+        .and_then(|len| len.parse().ok())
+        .unwrap_or(0);
+
+    let mut body = buf[header_end + 4..].to_vec();
+    while body.len() < length {
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            break;
+        }
+        body.extend_from_slice(&chunk[..n]);
+    }
+    Ok(Some((path, body)))
+}
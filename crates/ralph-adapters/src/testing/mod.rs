@@ -0,0 +1,5 @@
+//! Testing utilities for running the API backend offline.
+
+pub mod mock_chat_server;
+
+pub use mock_chat_server::{MockChatServer, MockReply};
@@ -0,0 +1,221 @@
+#[cfg(unix)]
+mod api_backend_integration {
+    use ralph_adapters::testing::{MockChatServer, MockReply};
+    use ralph_adapters::{ApiBackend, SessionResult, StreamHandler, classify_failure};
+    use ralph_core::ApiBackendConfig;
+    use serde_json::json;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    #[derive(Default)]
+    struct CapturingHandler {
+        texts: Vec<String>,
+        tool_calls: Vec<(String, String, serde_json::Value)>,
+        tool_results: Vec<(String, String)>,
+        errors: Vec<String>,
+        completions: Vec<SessionResult>,
+    }
+
+    impl StreamHandler for CapturingHandler {
+        fn on_text(&mut self, text: &str) {
+            self.texts.push(text.to_string());
+        }
+
+        fn on_tool_call(&mut self, name: &str, id: &str, input: &serde_json::Value) {
+            self.tool_calls
+                .push((name.to_string(), id.to_string(), input.clone()));
+        }
+
+        fn on_tool_result(&mut self, id: &str, output: &str) {
+            self.tool_results.push((id.to_string(), output.to_string()));
+        }
+
+        fn on_error(&mut self, error: &str) {
+            self.errors.push(error.to_string());
+        }
+
+        fn on_complete(&mut self, result: &SessionResult) {
+            self.completions.push(result.clone());
+        }
+    }
+
+    fn backend(server: &MockChatServer, workspace: &TempDir) -> ApiBackend {
+        let config = ApiBackendConfig {
+            base_url: server.base_url(),
+            model: Some("test-model".to_string()),
+            input_cost_per_mtok: 1.0,
+            output_cost_per_mtok: 2.0,
+            ..ApiBackendConfig::default()
+        };
+        ApiBackend::new(&config, workspace.path())
+            .unwrap()
+            .with_api_key(Some("test-key".to_string()))
+    }
+
+    #[tokio::test]
+    async fn test_tool_loop_runs_tools_in_workspace() {
+        let workspace = TempDir::new().unwrap();
+        std::fs::write(workspace.path().join("notes.txt"), "status: draft\n").unwrap();
+        let server = MockChatServer::start().await.unwrap();
+        server.push(MockReply::tool_call(
+            "read_file",
+            json!({ "path": "notes.txt" }),
+        ));
+        server.push(MockReply::tool_call(
+            "edit_file",
+            json!({ "path": "notes.txt", "old_text": "draft", "new_text": "done" }),
+        ));
+        server.push(MockReply::tool_call(
+            "write_file",
+            json!({ "path": "out/result.txt", "content": "ok" }),
+        ));
+        server.push(MockReply::tool_call(
+            "run_command",
+            json!({ "command": "cat out/result.txt; exit 3" }),
+        ));
+        server.push(MockReply::tool_call(
+            "ralph_emit",
+            json!({ "topic": "build.done", "payload": "all good" }),
+        ));
+        server.push(MockReply::tool_call(
+            "read_file",
+            json!({ "path": "../outside.txt" }),
+        ));
+        server.push(MockReply::text("Finished the task."));
+
+        let mut handler = CapturingHandler::default();
+        let result = backend(&server, &workspace)
+            .execute("Update the notes", &mut handler, None)
+            .await;
+
+        assert!(result.success, "output: {}", result.output);
+        assert_eq!(result.turns, 7);
+        assert_eq!(result.output, "Finished the task.\n");
+        assert_eq!(result.usage.prompt_tokens, 70);
+        assert_eq!(result.usage.completion_tokens, 35);
+        assert!((result.cost_usd - 0.00014).abs() < 1e-12);
+        assert_eq!(handler.texts.concat(), "Finished the task.");
+        assert_eq!(handler.completions.len(), 1);
+        assert!(!handler.completions[0].is_error);
+
+        assert_eq!(
+            std::fs::read_to_string(workspace.path().join("notes.txt")).unwrap(),
+            "status: done\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(workspace.path().join("out/result.txt")).unwrap(),
+            "ok"
+        );
+        let events = std::fs::read_to_string(workspace.path().join(".ralph/events.jsonl")).unwrap();
+        assert!(events.contains(r#""topic":"build.done""#), "{events}");
+        assert!(events.contains(r#""payload":"all good""#), "{events}");
+
+        let names: Vec<&str> = handler
+            .tool_calls
+            .iter()
+            .map(|(name, _, _)| name.as_str())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "read_file",
+                "edit_file",
+                "write_file",
+                "run_command",
+                "ralph_emit",
+                "read_file"
+            ]
+        );
+        assert_eq!(handler.tool_results[0].1, "status: draft\n");
+        assert_eq!(handler.tool_results[3].1, "exit code: 3\nok");
+        assert!(
+            handler.tool_results[5].1.contains("outside the workspace"),
+            "{:?}",
+            handler.tool_results[5]
+        );
+
+        // Each request carries the conversation so far, tools, and the model.
+        let requests = server.requests();
+        assert_eq!(requests.len(), 7);
+        assert_eq!(requests[0]["model"], "test-model");
+        assert_eq!(requests[0]["messages"][0]["content"], "Update the notes");
+        assert_eq!(requests[0]["tools"].as_array().unwrap().len(), 5);
+        let second = requests[1]["messages"].as_array().unwrap();
+        assert_eq!(second[1]["tool_calls"][0]["function"]["name"], "read_file");
+        assert_eq!(second[2]["role"], "tool");
+        assert_eq!(second[2]["tool_call_id"], second[1]["tool_calls"][0]["id"]);
+        assert_eq!(second[2]["content"], "status: draft\n");
+    }
+
+    #[tokio::test]
+    async fn test_reasoning_effort_is_sent_when_set() {
+        let workspace = TempDir::new().unwrap();
+        let server = MockChatServer::start().await.unwrap();
+        server.push(MockReply::text("Done."));
+        server.push(MockReply::text("Done."));
+
+        let mut handler = CapturingHandler::default();
+        backend(&server, &workspace)
+            .execute("Hello", &mut handler, None)
+            .await;
+        backend(&server, &workspace)
+            .with_reasoning_effort(Some("high".to_string()))
+            .execute("Hello", &mut handler, None)
+            .await;
+
+        let requests = server.requests();
+        assert!(requests[0].get("reasoning_effort").is_none());
+        assert_eq!(requests[1]["reasoning_effort"], "high");
+    }
+
+    #[tokio::test]
+    async fn test_http_errors_are_classified() {
+        let workspace = TempDir::new().unwrap();
+        let server = MockChatServer::start().await.unwrap();
+        server.push(MockReply::error(
+            429,
+            r#"{"error":{"message":"Rate limit reached. Please try again in 20s."}}"#,
+        ));
+
+        let mut handler = CapturingHandler::default();
+        let result = backend(&server, &workspace)
+            .execute("Hello", &mut handler, None)
+            .await;
+
+        assert!(!result.success);
+        assert!(!result.timed_out);
+        assert!(result.output.starts_with("HTTP 429:"), "{}", result.output);
+        assert_eq!(handler.errors.len(), 1);
+        assert!(handler.completions[0].is_error);
+        let failure = classify_failure("api", Some(1), &result.output);
+        assert_eq!(failure.map(|f| f.as_str()), Some("rate_limit"));
+    }
+
+    #[tokio::test]
+    async fn test_max_turns_stops_the_loop() {
+        let workspace = TempDir::new().unwrap();
+        let server = MockChatServer::start().await.unwrap();
+        for _ in 0..3 {
+            server.push(MockReply::tool_call(
+                "run_command",
+                json!({ "command": "true" }),
+            ));
+        }
+        let config = ApiBackendConfig {
+            base_url: server.base_url(),
+            model: Some("test-model".to_string()),
+            max_turns: 2,
+            ..ApiBackendConfig::default()
+        };
+
+        let mut handler = CapturingHandler::default();
+        let result = ApiBackend::new(&config, workspace.path())
+            .unwrap()
+            .execute("Loop forever", &mut handler, Some(Duration::from_secs(30)))
+            .await;
+
+        assert!(!result.success);
+        assert_eq!(result.turns, 2);
+        assert!(result.output.contains("did not finish within 2 turns"));
+    }
+}
@@ -48,6 +48,7 @@ mod pty_executor_integration {
             prompt_flag: None,
             output_format: OutputFormat::Text,
             env_vars: vec![],
+            output_rules: None,
         };
         let config = PtyConfig {
             interactive: false,
@@ -79,6 +80,7 @@ mod pty_executor_integration {
             prompt_flag: None,
             output_format: OutputFormat::StreamJson,
             env_vars: vec![],
+            output_rules: None,
         };
         let config = PtyConfig {
             interactive: false,
@@ -113,6 +115,7 @@ mod pty_executor_integration {
             prompt_flag: None,
             output_format: OutputFormat::StreamJson,
             env_vars: vec![],
+            output_rules: None,
         };
         let config = PtyConfig {
             interactive: false,
@@ -154,6 +157,7 @@ mod pty_executor_integration {
             prompt_flag: None,
             output_format: OutputFormat::PiStreamJson,
             env_vars: vec![],
+            output_rules: None,
         };
         let config = PtyConfig {
             interactive: false,
@@ -216,6 +220,7 @@ mod pty_executor_integration {
             prompt_flag: None,
             output_format: OutputFormat::PiStreamJson,
             env_vars: vec![],
+            output_rules: None,
         };
         let config = PtyConfig {
             interactive: false,
@@ -254,6 +259,7 @@ mod pty_executor_integration {
             prompt_flag: None,
             output_format: OutputFormat::PiStreamJson,
             env_vars: vec![],
+            output_rules: None,
         };
         let config = PtyConfig {
             interactive: false,
@@ -290,6 +296,7 @@ mod pty_executor_integration {
             prompt_flag: None,
             output_format: OutputFormat::PiStreamJson,
             env_vars: vec![],
+            output_rules: None,
         };
         let config = PtyConfig {
             interactive: false,
@@ -5,6 +5,7 @@
 //! This crate provides:
 //! - Recording sessions by observing EventBus events
 //! - Replaying sessions with timing and UX output control
+//! - Exporting sessions to asciinema casts and HTML reports
 //! - Batch benchmarking with isolated workspaces
 //! - Metrics collection for benchmark comparison
 
@@ -12,8 +13,8 @@ use anyhow::{Context, Result};
 use clap::{Parser, Subcommand, ValueEnum};
 use ralph_adapters::{CliBackend, CliExecutor, detect_backend};
 use ralph_core::{
-    CleanupPolicy, CliCapture, EventLoop, PlayerConfig, RalphConfig, ReplayMode, SessionPlayer,
-    TaskSuite, TerminationReason, WorkspaceManager,
+    CleanupPolicy, CliCapture, EventLoop, ExportConfig, PlayerConfig, RalphConfig, ReplayMode,
+    SessionExporter, SessionPlayer, TaskSuite, TerminationReason, WorkspaceManager,
 };
 use ralph_proto::FrameCapture;
 use std::fs::{self, File};
@@ -92,6 +93,32 @@ enum Commands {
         filter: Option<String>,
     },
 
+    /// Export a recorded session to asciinema or HTML
+    Export {
+        /// Path to session JSONL file
+        session: PathBuf,
+
+        /// Export format: asciicast (asciinema v2 .cast), html (timeline report)
+        #[arg(long, value_enum, default_value = "asciicast")]
+        format: ExportFormat,
+
+        /// Output file (defaults to the session path with a .cast/.html extension)
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+
+        /// Terminal width used when the recording has no resize event
+        #[arg(long, default_value = "80")]
+        width: u16,
+
+        /// Terminal height used when the recording has no resize event
+        #[arg(long, default_value = "24")]
+        height: u16,
+
+        /// Title for the cast header or HTML report (defaults to the session file name)
+        #[arg(long)]
+        title: Option<String>,
+    },
+
     /// List recorded sessions or workspaces
     List {
         /// What to list: sessions, workspaces
@@ -122,6 +149,25 @@ impl From<UxMode> for ReplayMode {
     }
 }
 
+/// Session export format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum ExportFormat {
+    /// asciinema v2 cast file (terminal writes and resizes)
+    Asciicast,
+    /// Self-contained HTML report interleaving events and terminal output
+    Html,
+}
+
+impl ExportFormat {
+    /// File extension for this format.
+    fn extension(self) -> &'static str {
+        match self {
+            ExportFormat::Asciicast => "cast",
+            ExportFormat::Html => "html",
+        }
+    }
+}
+
 /// What to list
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 enum ListTarget {
@@ -169,6 +215,14 @@ async fn main() -> Result<()> {
             step,
             filter,
         } => cmd_replay(session, ux_mode, speed, step, filter),
+        Commands::Export {
+            session,
+            format,
+            output,
+            width,
+            height,
+            title,
+        } => cmd_export(session, format, output, width, height, title),
         Commands::List { what, dir } => cmd_list(what, dir),
     }
 }
@@ -448,6 +502,14 @@ async fn run_task_loop(
         let iteration = event_loop.state().iteration + 1;
         info!("Task '{}' iteration {}", task.name, iteration);
 
+        if let Some(ref rec) = recorder {
+            rec.record_meta(Record::meta_iteration(
+                iteration,
+                rec.elapsed().as_millis() as u64,
+                hat_id.as_str(),
+            ));
+        }
+
         // Build prompt for this hat
         let prompt = match event_loop.build_prompt(&hat_id) {
             Some(p) => p,
@@ -509,6 +571,17 @@ async fn run_task_loop(
     let iterations = state.iteration;
     let reason_str = format_termination_reason(&termination_reason);
 
+    if let Some(ref rec) = recorder {
+        rec.record_meta(Record::meta_termination(
+            &reason_str,
+            iterations,
+            rec.elapsed().as_secs_f64(),
+            rec.ux_write_count(),
+        ));
+        rec.flush()
+            .with_context(|| "Failed to flush session recording")?;
+    }
+
     info!(
         "Task '{}' completed: {} iterations, reason: {}",
         task.name, iterations, reason_str
@@ -576,6 +649,51 @@ fn cmd_replay(
     Ok(())
 }
 
+/// Export a recorded session to a shareable format
+fn cmd_export(
+    session_path: PathBuf,
+    format: ExportFormat,
+    output: Option<PathBuf>,
+    width: u16,
+    height: u16,
+    title: Option<String>,
+) -> Result<()> {
+    let file = File::open(&session_path)
+        .with_context(|| format!("Failed to open session file: {:?}", session_path))?;
+    let player = SessionPlayer::from_reader(BufReader::new(file))
+        .with_context(|| "Failed to parse session file")?;
+
+    let title = title.unwrap_or_else(|| {
+        session_path
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "ralph session".to_string())
+    });
+    let config = ExportConfig::default()
+        .with_size(width, height)
+        .with_title(title);
+    let exporter = SessionExporter::new(&player).with_config(config);
+
+    let output_path = output.unwrap_or_else(|| session_path.with_extension(format.extension()));
+    let out = File::create(&output_path)
+        .with_context(|| format!("Failed to create output file: {:?}", output_path))?;
+    let mut writer = BufWriter::new(out);
+
+    match format {
+        ExportFormat::Asciicast => exporter.write_asciicast(&mut writer),
+        ExportFormat::Html => exporter.write_html(&mut writer),
+    }
+    .with_context(|| "Failed to export session")?;
+
+    info!(
+        "Exported {} records to {:?}",
+        player.record_count(),
+        output_path
+    );
+
+    Ok(())
+}
+
 /// List sessions or workspaces
 fn cmd_list(what: ListTarget, dir: Option<PathBuf>) -> Result<()> {
     let search_dir = dir.unwrap_or_else(|| PathBuf::from("."));
@@ -753,6 +871,12 @@ mod tests {
         assert_eq!(&ts[8..9], "-");
     }
 
+    #[test]
+    fn test_export_format_extension() {
+        assert_eq!(ExportFormat::Asciicast.extension(), "cast");
+        assert_eq!(ExportFormat::Html.extension(), "html");
+    }
+
     #[test]
     fn test_ux_mode_conversion() {
         assert_eq!(ReplayMode::from(UxMode::Terminal), ReplayMode::Terminal);
@@ -194,7 +194,8 @@ async fn onboard_telegram(args: OnboardArgs, use_colors: bool) -> Result<()> {
     print!("  Checking token with Telegram API...");
     io::stdout().flush()?;
 
-    let bot_info = match telegram_get_me(&token).await {
+    let api_url = resolve_api_url();
+    let bot_info = match telegram_get_me(&api_url, &token).await {
         Ok(info) => {
             println!();
             print_success(use_colors, &format!("Token valid! Bot: @{}", info.username));
@@ -225,7 +226,7 @@ async fn onboard_telegram(args: OnboardArgs, use_colors: bool) -> Result<()> {
         print!("  Waiting for message... (timeout: {}s)", args.timeout);
         io::stdout().flush()?;
 
-        match telegram_get_updates(&token, args.timeout).await {
+        match telegram_get_updates(&api_url, &token, args.timeout).await {
             Ok(update) => {
                 println!();
                 print_success(
@@ -313,6 +314,7 @@ async fn onboard_telegram(args: OnboardArgs, use_colors: bool) -> Result<()> {
     println!("Step 5: Verify");
 
     match telegram_send_message(
+        &api_url,
         &token,
         chat_id,
         "Ralph bot setup complete! I'm ready to assist during orchestration runs.",
@@ -422,7 +424,7 @@ async fn bot_status(use_colors: bool) -> Result<()> {
     if let Some(token) = effective_token {
         print!("  Validating token with Telegram API...");
         io::stdout().flush()?;
-        match telegram_get_me(&token).await {
+        match telegram_get_me(&resolve_api_url(), &token).await {
             Ok(info) => {
                 println!();
                 print_success(
@@ -462,7 +464,7 @@ async fn bot_test(args: TestArgs, use_colors: bool) -> Result<()> {
     print!("  Sending message to chat {}...", chat_id);
     io::stdout().flush()?;
 
-    match telegram_send_message(&token, chat_id, &args.message).await {
+    match telegram_send_message(&resolve_api_url(), &token, chat_id, &args.message).await {
         Ok(_) => {
             println!();
             print_success(use_colors, "Message sent!");
@@ -534,8 +536,11 @@ async fn run_daemon(
         .context(
             "No bot token available. Run `ralph bot onboard --telegram` or set RALPH_TELEGRAM_BOT_TOKEN",
         )?;
-    let chat_id = resolve_chat_id()
-        .context("No chat_id found. Run `ralph bot onboard --telegram` to detect it")?;
+    let access = config_path
+        .as_ref()
+        .map(|path| load_config_robot_access_from(path))
+        .transpose()?
+        .unwrap_or_default();
 
     if use_colors {
         println!("\x1b[1mRalph Daemon\x1b[0m (Telegram)");
@@ -543,18 +548,60 @@ async fn run_daemon(
         println!("Ralph Daemon (Telegram)");
     }
 
-    // Build the adapter
-    let adapter = ralph_telegram::TelegramDaemon::new(token, chat_id);
+    // Build the adapter: the access list serves several chats, otherwise the onboarded chat
+    let adapter = if access.is_empty() {
+        let chat_id = resolve_chat_id().context(
+            "No chat_id found. Run `ralph bot onboard --telegram` to detect it, or configure RObot.access",
+        )?;
+        ralph_telegram::TelegramDaemon::new(token, chat_id)
+    } else {
+        println!("Serving {} access rule(s)", access.len());
+        ralph_telegram::TelegramDaemon::with_access(token, ralph_proto::AccessList::new(access))
+    };
+
+    // A custom Bot API server (self-hosted or a local mock) replaces api.telegram.org
+    let api_url = resolve_api_url_from(
+        std::env::var("RALPH_TELEGRAM_API_URL").ok(),
+        config_path
+            .as_ref()
+            .and_then(|path| load_config_api_url_from(path)),
+    );
+    let adapter = if api_url == ralph_telegram::DEFAULT_API_URL {
+        adapter
+    } else {
+        println!("Bot API: {api_url}");
+        adapter.with_api_url(ralph_telegram::parse_api_url(&api_url)?)
+    };
+
+    // Queued jobs run alongside the current loop only when parallel loops are enabled
+    let parallel = config_path
+        .as_ref()
+        .is_none_or(|path| load_config_parallel_from(path));
+    let adapter = adapter.with_parallel(parallel);
+
+    // Mask secrets in daemon replies (loops install their own config when they start)
+    if let Some(config) = config_path
+        .as_ref()
+        .and_then(|path| load_config_redaction_from(path))
+    {
+        ralph_core::redaction::install(
+            ralph_core::Redactor::from_config(&config).context("Invalid redaction pattern")?,
+        );
+    }
 
     // Build the start_loop callback — wraps our CLI loop runner
-    let start_loop: ralph_proto::StartLoopFn = Box::new(move |prompt: String| {
-        let config_path = config_path.clone();
-        Box::pin(async move {
-            let ws = std::env::current_dir()?;
-            let reason = crate::loop_runner::start_loop(prompt, ws, config_path).await?;
-            Ok(format!("{:?}", reason))
-        })
-    });
+    let start_loop: ralph_proto::StartLoopFn =
+        Box::new(move |request: ralph_proto::LoopRequest| {
+            let config_path = config_path.clone();
+            Box::pin(async move {
+                let ws = std::env::current_dir()?;
+                if request.parallel {
+                    crate::loop_runner::start_worktree_loop(request.prompt, ws, config_path).await
+                } else {
+                    crate::loop_runner::start_loop(request.prompt, ws, config_path).await
+                }
+            })
+        });
 
     adapter.run_daemon(workspace_root, start_loop).await?;
 
@@ -577,10 +624,31 @@ struct UpdateInfo {
     from_name: String,
 }
 
+/// Build the URL of a Bot API method on the given server.
+fn method_url(api_url: &str, token: &str, method: &str) -> String {
+    format!("{}/bot{}/{}", api_url.trim_end_matches('/'), token, method)
+}
+
+/// HTTP client for the Bot API; loopback servers (local mocks) bypass proxies.
+fn api_client(api_url: &str) -> reqwest::Client {
+    let loopback = reqwest::Url::parse(api_url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_string))
+        .is_some_and(|host| matches!(host.as_str(), "localhost" | "127.0.0.1" | "[::1]"));
+    if loopback {
+        reqwest::Client::builder()
+            .no_proxy()
+            .build()
+            .unwrap_or_default()
+    } else {
+        reqwest::Client::new()
+    }
+}
+
 /// Validate a bot token via the Telegram getMe API.
-async fn telegram_get_me(token: &str) -> Result<BotInfo> {
-    let url = format!("https://api.telegram.org/bot{}/getMe", token);
-    let client = reqwest::Client::new();
+async fn telegram_get_me(api_url: &str, token: &str) -> Result<BotInfo> {
+    let url = method_url(api_url, token, "getMe");
+    let client = api_client(api_url);
     let resp = client
         .get(&url)
         .timeout(std::time::Duration::from_secs(10))
@@ -623,8 +691,8 @@ async fn telegram_get_me(token: &str) -> Result<BotInfo> {
 }
 
 /// Long-poll for the first message sent to the bot.
-async fn telegram_get_updates(token: &str, timeout_secs: u64) -> Result<UpdateInfo> {
-    let client = reqwest::Client::new();
+async fn telegram_get_updates(api_url: &str, token: &str, timeout_secs: u64) -> Result<UpdateInfo> {
+    let client = api_client(api_url);
     let deadline = std::time::Instant::now() + std::time::Duration::from_secs(timeout_secs);
 
     // Telegram long polling uses a max of 50 seconds per request
@@ -639,8 +707,9 @@ async fn telegram_get_updates(token: &str, timeout_secs: u64) -> Result<UpdateIn
         let this_timeout = std::cmp::min(poll_timeout, remaining);
 
         let mut url = format!(
-            "https://api.telegram.org/bot{}/getUpdates?timeout={}",
-            token, this_timeout
+            "{}?timeout={}",
+            method_url(api_url, token, "getUpdates"),
+            this_timeout
         );
         if let Some(off) = offset {
             url.push_str(&format!("&offset={}", off));
@@ -697,9 +766,14 @@ async fn telegram_get_updates(token: &str, timeout_secs: u64) -> Result<UpdateIn
 }
 
 /// Send a message to a Telegram chat.
-pub(crate) async fn telegram_send_message(token: &str, chat_id: i64, text: &str) -> Result<()> {
-    let url = format!("https://api.telegram.org/bot{}/sendMessage", token);
-    let client = reqwest::Client::new();
+pub(crate) async fn telegram_send_message(
+    api_url: &str,
+    token: &str,
+    chat_id: i64,
+    text: &str,
+) -> Result<()> {
+    let url = method_url(api_url, token, "sendMessage");
+    let client = api_client(api_url);
 
     let payload = serde_json::json!({
         "chat_id": chat_id,
@@ -910,6 +984,63 @@ fn load_config_bot_token_from(path: &Path) -> Option<String> {
         .map(String::from)
 }
 
+/// Read `RObot.telegram.api_url` from a config file.
+fn load_config_api_url_from(path: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(path).ok()?;
+    let config: serde_yaml::Value = serde_yaml::from_str(&content).ok()?;
+    config
+        .get("RObot")
+        .or_else(|| config.get("robot"))
+        .and_then(|r| r.get("telegram"))
+        .and_then(|t| t.get("api_url"))
+        .and_then(|v| v.as_str())
+        .map(String::from)
+}
+
+/// Read the `RObot.access` list from a config file.
+fn load_config_robot_access_from(path: &Path) -> Result<Vec<ralph_proto::RobotAccessRule>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let config: serde_yaml::Value = serde_yaml::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    let Some(access) = config
+        .get("RObot")
+        .or_else(|| config.get("robot"))
+        .and_then(|r| r.get("access"))
+    else {
+        return Ok(Vec::new());
+    };
+    let rules: Vec<ralph_proto::RobotAccessRule> = serde_yaml::from_value(access.clone())
+        .with_context(|| format!("Invalid RObot.access in {}", path.display()))?;
+    if let Some(index) = rules
+        .iter()
+        .position(|rule| rule.chat_id.is_none() && rule.user_id.is_none())
+    {
+        anyhow::bail!(
+            "Invalid RObot.access[{index}] in {}: each rule needs a chat_id, a user_id, or both",
+            path.display()
+        );
+    }
+    Ok(rules)
+}
+
+/// Read `features.parallel` from a config file (defaults to enabled).
+fn load_config_parallel_from(path: &Path) -> bool {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_yaml::from_str::<serde_yaml::Value>(&content).ok())
+        .and_then(|config| config.get("features")?.get("parallel")?.as_bool())
+        .unwrap_or(true)
+}
+
+/// Read the `redaction:` section from a config file.
+fn load_config_redaction_from(path: &Path) -> Option<ralph_core::RedactionConfig> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_yaml::from_str::<serde_yaml::Value>(&content).ok())
+        .and_then(|config| serde_yaml::from_value(config.get("redaction")?.clone()).ok())
+}
+
 /// Read bot token from ralph.yml (legacy).
 fn load_config_bot_token() -> Option<String> {
     load_config_bot_token_from(Path::new("ralph.yml"))
@@ -962,6 +1093,20 @@ pub(crate) fn resolve_token() -> Option<String> {
     )
 }
 
+fn resolve_api_url_from(env_url: Option<String>, config_url: Option<String>) -> String {
+    normalize_token(env_url)
+        .or_else(|| normalize_token(config_url))
+        .unwrap_or_else(|| ralph_telegram::DEFAULT_API_URL.to_string())
+}
+
+/// Resolve the Bot API base URL (env > config > api.telegram.org).
+pub(crate) fn resolve_api_url() -> String {
+    resolve_api_url_from(
+        std::env::var("RALPH_TELEGRAM_API_URL").ok(),
+        load_config_api_url_from(Path::new("ralph.yml")),
+    )
+}
+
 /// Resolve chat_id from telegram state.
 pub(crate) fn resolve_chat_id() -> Option<i64> {
     let content = std::fs::read_to_string(".ralph/telegram-state.json").ok()?;
@@ -1276,6 +1421,67 @@ mod tests {
         assert_eq!(payload["text"].as_str().unwrap(), "Hello from Ralph!");
     }
 
+    #[test]
+    fn test_resolve_api_url_from_prefers_env_then_config() {
+        assert_eq!(
+            resolve_api_url_from(
+                Some("http://127.0.0.1:9000".to_string()),
+                Some("http://cfg".to_string())
+            ),
+            "http://127.0.0.1:9000"
+        );
+        assert_eq!(
+            resolve_api_url_from(Some("  ".to_string()), Some("http://cfg".to_string())),
+            "http://cfg"
+        );
+        assert_eq!(
+            resolve_api_url_from(None, None),
+            ralph_telegram::DEFAULT_API_URL
+        );
+        assert_eq!(
+            method_url("http://127.0.0.1:9000/", "123:abc", "getMe"),
+            "http://127.0.0.1:9000/bot123:abc/getMe"
+        );
+    }
+
+    #[test]
+    fn test_load_config_api_url_from_reads_robot_telegram() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("ralph.yml");
+        std::fs::write(
+            &path,
+            "RObot:\n  telegram:\n    api_url: http://localhost:8081\n",
+        )
+        .unwrap();
+        assert_eq!(
+            load_config_api_url_from(&path).as_deref(),
+            Some("http://localhost:8081")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_telegram_helpers_against_mock_server() {
+        let server = ralph_telegram::testing::MockTelegramServer::start()
+            .await
+            .unwrap();
+        let api_url = server.api_url();
+
+        let info = telegram_get_me(&api_url, "123:mock").await.unwrap();
+        assert!(!info.username.is_empty());
+
+        server.send_user_message(4242, 7, "hi");
+        let update = telegram_get_updates(&api_url, "123:mock", 5).await.unwrap();
+        assert_eq!(update.chat_id, 4242);
+
+        telegram_send_message(&api_url, "123:mock", 4242, "Hello from Ralph!")
+            .await
+            .unwrap();
+        let sent = server.sent_messages();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].chat_id, 4242);
+        assert_eq!(sent[0].text, "Hello from Ralph!");
+    }
+
     #[test]
     fn test_telegram_error_response_parsing() {
         let body: serde_json::Value = serde_json::from_str(
@@ -1355,6 +1561,43 @@ mod tests {
         assert_eq!(token.as_deref(), Some("token-lower"));
     }
 
+    #[test]
+    fn test_load_config_robot_access_from_reads_rules() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config_path = temp_dir.path().join("custom.yml");
+        let yaml = "RObot:\n  access:\n    - chat_id: -100\n      role: viewer\n    - user_id: 7\n      role: operator\n";
+        std::fs::write(&config_path, yaml).unwrap();
+
+        let rules = load_config_robot_access_from(&config_path).unwrap();
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].chat_id, Some(-100));
+        assert_eq!(rules[1].role, ralph_proto::RobotRole::Operator);
+
+        std::fs::write(&config_path, "RObot:\n  enabled: true\n").unwrap();
+        assert!(
+            load_config_robot_access_from(&config_path)
+                .unwrap()
+                .is_empty()
+        );
+
+        std::fs::write(&config_path, "RObot:\n  access:\n    - role: viewer\n").unwrap();
+        let err = load_config_robot_access_from(&config_path).unwrap_err();
+        assert!(err.to_string().contains("RObot.access[0]"));
+    }
+
+    #[test]
+    fn test_load_config_parallel_from_defaults_to_enabled() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config_path = temp_dir.path().join("ralph.yml");
+        assert!(load_config_parallel_from(&config_path));
+
+        std::fs::write(&config_path, "features:\n  auto_merge: true\n").unwrap();
+        assert!(load_config_parallel_from(&config_path));
+
+        std::fs::write(&config_path, "features:\n  parallel: false\n").unwrap();
+        assert!(!load_config_parallel_from(&config_path));
+    }
+
     #[test]
     fn test_save_bot_token_config_writes_token() {
         let temp_dir = tempfile::tempdir().unwrap();
@@ -0,0 +1,467 @@
+//! CLI commands for the `ralph config` namespace, and layered config loading.
+//!
+//! Every command loads its configuration through [`load_config`] (or
+//! [`load_config_sync`] where remote sources are not supported), which stacks
+//! these layers, lowest precedence first:
+//!
+//! 1. User defaults from `~/.config/ralph/config.yml` (`$XDG_CONFIG_HOME` is
+//!    honored; `RALPH_USER_CONFIG` points elsewhere, or disables it when empty)
+//! 2. Each `-c` file, builtin preset or URL, in order, with their `extends:`
+//! 3. Each `-c dotted.path=value` override, in order
+//!
+//! Subcommands:
+//! - `show`: Print the configuration the layers set (`--resolved` prints every
+//!   effective value, including defaults, with where it came from, after a
+//!   header listing each layer including the user defaults file)
+
+use crate::ConfigSource;
+use crate::display::colors;
+use crate::presets;
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+use ralph_core::config_layers::key_string;
+use ralph_core::{LayeredConfig, RalphConfig};
+use serde_yaml::Value;
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tracing::{info, warn};
+
+/// Inspect the layered configuration.
+#[derive(Parser, Debug)]
+pub struct ConfigArgs {
+    #[command(subcommand)]
+    pub command: ConfigCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ConfigCommands {
+    /// Show the merged configuration
+    Show {
+        /// Print every effective value, including defaults, annotated with its origin
+        #[arg(long, visible_alias = "origin")]
+        resolved: bool,
+    },
+}
+
+/// Execute a config command.
+pub async fn execute(
+    config_sources: &[ConfigSource],
+    args: ConfigArgs,
+    use_colors: bool,
+) -> Result<()> {
+    let layers = load_layers(config_sources).await?;
+    let mut stdout = std::io::stdout();
+
+    match args.command {
+        ConfigCommands::Show { resolved: false } => {
+            write!(stdout, "{}", serde_yaml::to_string(layers.value())?)?;
+            Ok(())
+        }
+        ConfigCommands::Show { resolved: true } => {
+            let config = resolve(&layers)?;
+            show_resolved(
+                &mut stdout,
+                &layers,
+                &config,
+                user_config_path().as_deref(),
+                use_colors,
+            )
+        }
+    }
+}
+
+/// Loads, merges and normalizes configuration from all sources.
+pub(crate) async fn load_config(config_sources: &[ConfigSource]) -> Result<RalphConfig> {
+    resolve(&load_layers(config_sources).await?)
+}
+
+/// Like [`load_config`], for commands that cannot fetch remote sources.
+pub(crate) fn load_config_sync(config_sources: &[ConfigSource]) -> Result<RalphConfig> {
+    if let Some(ConfigSource::Remote(url)) = config_sources
+        .iter()
+        .find(|s| matches!(s, ConfigSource::Remote(_)))
+    {
+        anyhow::bail!(
+            "Remote config URLs are not supported for this command.\n\nPlease use a local config file or builtin preset instead.\nURL: {}",
+            url
+        );
+    }
+    resolve(&build_layers(config_sources, &HashMap::new())?)
+}
+
+async fn load_layers(config_sources: &[ConfigSource]) -> Result<LayeredConfig> {
+    let mut remote = HashMap::new();
+    for source in config_sources {
+        if let ConfigSource::Remote(url) = source {
+            remote.insert(url.clone(), fetch_remote(url).await?);
+        }
+    }
+    build_layers(config_sources, &remote)
+}
+
+async fn fetch_remote(url: &str) -> Result<String> {
+    info!("Fetching config from {}", url);
+    let response = reqwest::get(url)
+        .await
+        .with_context(|| format!("Failed to fetch config from {}", url))?;
+
+    if !response.status().is_success() {
+        anyhow::bail!(
+            "Failed to fetch config from {}: HTTP {}",
+            url,
+            response.status()
+        );
+    }
+
+    response
+        .text()
+        .await
+        .with_context(|| format!("Failed to read config content from {}", url))
+}
+
+fn build_layers(
+    config_sources: &[ConfigSource],
+    remote: &HashMap<String, String>,
+) -> Result<LayeredConfig> {
+    let mut layers = LayeredConfig::new()
+        .with_presets(|name| presets::get_preset(name).map(|p| p.content.to_string()));
+
+    if let Some(path) = user_config_path()
+        && path.exists()
+    {
+        layers
+            .add_file(&path)
+            .with_context(|| format!("Failed to load user config from {:?}", path))?;
+    }
+
+    let (primary_sources, overrides): (Vec<_>, Vec<_>) = config_sources
+        .iter()
+        .partition(|s| !matches!(s, ConfigSource::Override { .. }));
+
+    let default_source = [ConfigSource::File(PathBuf::from("ralph.yml"))];
+    let primary_sources: Vec<&ConfigSource> = if primary_sources.is_empty() {
+        default_source.iter().collect()
+    } else {
+        primary_sources
+    };
+
+    for source in primary_sources {
+        match source {
+            ConfigSource::File(path) => {
+                if path.exists() {
+                    layers
+                        .add_file(path)
+                        .with_context(|| format!("Failed to load config from {:?}", path))?;
+                } else {
+                    warn!("Config file {:?} not found, using defaults", path);
+                }
+            }
+            ConfigSource::Builtin(name) => {
+                if presets::get_preset(name).is_none() {
+                    let available = presets::preset_names().join(", ");
+                    anyhow::bail!(
+                        "Unknown preset '{}'. Run `ralph run --list-presets` to see available presets.\n\nAvailable: {}",
+                        name,
+                        available
+                    );
+                }
+                layers
+                    .add_preset(name)
+                    .with_context(|| format!("Failed to parse builtin preset '{}'", name))?;
+            }
+            ConfigSource::Remote(url) => {
+                let content = remote
+                    .get(url)
+                    .with_context(|| format!("Config from {} was not fetched", url))?;
+                layers
+                    .add_yaml(url, content, None)
+                    .with_context(|| format!("Failed to parse config from {}", url))?;
+            }
+            ConfigSource::Override { .. } => unreachable!("Partitioned out overrides"),
+        }
+    }
+
+    for source in overrides {
+        if let ConfigSource::Override { key, value } = source {
+            layers.add_override(&format!("{key}={value}"))?;
+        }
+    }
+
+    Ok(layers)
+}
+
+/// Deserializes the layers, normalizes v1 fields and sets the workspace root.
+fn resolve(layers: &LayeredConfig) -> Result<RalphConfig> {
+    let mut config = layers
+        .to_config()
+        .context("Failed to parse merged configuration")?;
+
+    for key in layers.unknown_overrides(&config) {
+        warn!("Unknown config key '{}' in -c override (ignored)", key);
+    }
+
+    config.normalize();
+
+    // workspace_root is #[serde(skip)], so it must be set from the runtime cwd
+    config.core.workspace_root =
+        std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
+
+    Ok(config)
+}
+
+/// Returns the user-level defaults file, if one applies.
+pub(crate) fn user_config_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os("RALPH_USER_CONFIG") {
+        return (!path.is_empty()).then(|| PathBuf::from(path));
+    }
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+    Some(base.join("ralph").join("config.yml"))
+}
+
+fn show_resolved<W: Write>(
+    writer: &mut W,
+    layers: &LayeredConfig,
+    config: &RalphConfig,
+    user_config: Option<&Path>,
+    use_colors: bool,
+) -> Result<()> {
+    write_layer_header(writer, layers, user_config, use_colors)?;
+
+    let mut value = serde_yaml::to_value(config)?;
+    order_like(&mut value, layers.value());
+
+    let mut path = Vec::new();
+    if let Value::Mapping(map) = &value {
+        render_mapping(writer, map, &mut path, 0, layers, use_colors)?;
+    }
+    Ok(())
+}
+
+/// Lists the layers as YAML comments, always naming the user defaults file
+/// since it is loaded implicitly.
+fn write_layer_header<W: Write>(
+    writer: &mut W,
+    layers: &LayeredConfig,
+    user_config: Option<&Path>,
+    use_colors: bool,
+) -> Result<()> {
+    let user_origin = user_config.map(|path| path.display().to_string());
+    let user_line = match (&user_origin, user_config) {
+        (Some(origin), Some(path)) if layers.sources().contains(origin) => {
+            format!("user: {}", path.display())
+        }
+        (_, Some(path)) => format!("user: {} (not found)", path.display()),
+        (_, None) => "user: disabled (RALPH_USER_CONFIG is empty)".to_string(),
+    };
+
+    let mut lines = vec!["Layers, lowest precedence first:".to_string()];
+    lines.push(format!("  {user_line}"));
+    lines.extend(
+        layers
+            .sources()
+            .iter()
+            .filter(|source| Some(*source) != user_origin.as_ref())
+            .map(|source| format!("  {source}")),
+    );
+    for line in lines {
+        if use_colors {
+            writeln!(writer, "{}# {line}{}", colors::DIM, colors::RESET)?;
+        } else {
+            writeln!(writer, "# {line}")?;
+        }
+    }
+    Ok(())
+}
+
+/// Reorders mapping keys to follow `reference` so map-typed sections (hats,
+/// events) print in the order they were written rather than hash order.
+fn order_like(value: &mut Value, reference: &Value) {
+    let (Value::Mapping(map), Value::Mapping(reference)) = (value, reference) else {
+        return;
+    };
+
+    let mut ordered = serde_yaml::Mapping::new();
+    for key in reference.keys() {
+        if let Some(child) = map.remove(key) {
+            ordered.insert(key.clone(), child);
+        }
+    }
+    ordered.extend(std::mem::take(map));
+
+    for (key, child) in &mut ordered {
+        if let Some(reference_child) = reference.get(key) {
+            order_like(child, reference_child);
+        }
+    }
+    *map = ordered;
+}
+
+fn render_mapping<W: Write>(
+    writer: &mut W,
+    map: &serde_yaml::Mapping,
+    path: &mut Vec<String>,
+    indent: usize,
+    layers: &LayeredConfig,
+    use_colors: bool,
+) -> Result<()> {
+    let pad = " ".repeat(indent);
+    for (key, value) in map {
+        path.push(key_string(key));
+        let key_text = inline_yaml(key)?;
+
+        match value {
+            Value::Mapping(child) if !child.is_empty() => {
+                writeln!(writer, "{pad}{key_text}:")?;
+                render_mapping(writer, child, path, indent + 2, layers, use_colors)?;
+            }
+            _ => {
+                let origin = layers.origin(path);
+                let comment = if use_colors {
+                    format!("{}# {}{}", colors::DIM, origin, colors::RESET)
+                } else {
+                    format!("# {origin}")
+                };
+
+                match block_yaml(value)? {
+                    None => writeln!(
+                        writer,
+                        "{pad}{key_text}: {}  {comment}",
+                        inline_yaml(value)?
+                    )?,
+                    Some(block) => {
+                        writeln!(writer, "{pad}{key_text}:  {comment}")?;
+                        for line in block.lines() {
+                            writeln!(writer, "{pad}  {line}")?;
+                        }
+                    }
+                }
+            }
+        }
+        path.pop();
+    }
+    Ok(())
+}
+
+/// Renders a value on one line, using flow style for sequences.
+fn inline_yaml(value: &Value) -> Result<String> {
+    match value {
+        Value::Sequence(items) if !items.is_empty() => {
+            let items: Result<Vec<String>> = items.iter().map(inline_yaml).collect();
+            Ok(format!("[{}]", items?.join(", ")))
+        }
+        _ => Ok(serde_yaml::to_string(value)?.trim_end().to_string()),
+    }
+}
+
+/// Sequences longer than this print as block lists.
+const MAX_INLINE_WIDTH: usize = 80;
+
+/// Returns block YAML for multi-line strings and long or nested sequences.
+fn block_yaml(value: &Value) -> Result<Option<String>> {
+    let fits_inline = |item: &Value| match item {
+        Value::String(s) => !s.contains('\n'),
+        Value::Bool(_) | Value::Number(_) | Value::Null => true,
+        _ => false,
+    };
+    let needs_block = match value {
+        Value::String(_) => !fits_inline(value),
+        Value::Sequence(items) => {
+            !items.iter().all(fits_inline) || inline_yaml(value)?.len() > MAX_INLINE_WIDTH
+        }
+        _ => false,
+    };
+    if !needs_block {
+        return Ok(None);
+    }
+    Ok(Some(serde_yaml::to_string(value)?.trim_end().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layers_from(yaml: &str, overrides: &[&str]) -> LayeredConfig {
+        let mut layers = LayeredConfig::new()
+            .with_presets(|name| presets::get_preset(name).map(|p| p.content.to_string()));
+        layers.add_yaml("ralph.yml", yaml, None).unwrap();
+        for o in overrides {
+            layers.add_override(o).unwrap();
+        }
+        layers
+    }
+
+    fn render(layers: &LayeredConfig) -> String {
+        let config = resolve(layers).unwrap();
+        let mut buf = Vec::new();
+        show_resolved(&mut buf, layers, &config, None, false).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    #[test]
+    fn test_show_resolved_annotates_origins() {
+        let layers = layers_from(
+            "extends: builtin:code-assist\ncli:\n  backend: kiro\n",
+            &["event_loop.max_iterations=9"],
+        );
+        let output = render(&layers);
+
+        assert!(
+            output.contains("  backend: kiro  # ralph.yml\n"),
+            "{output}"
+        );
+        assert!(output.contains("  max_iterations: 9  # -c event_loop.max_iterations=9\n"));
+        assert!(output.contains("  scratchpad: .ralph/agent/scratchpad.md  # default\n"));
+        assert!(output.contains("hats:\n"));
+        assert!(output.contains("# builtin:code-assist"));
+        assert!(output.starts_with(
+            "# Layers, lowest precedence first:\n\
+             #   user: disabled (RALPH_USER_CONFIG is empty)\n\
+             #   builtin:code-assist\n\
+             #   ralph.yml\n\
+             #   -c event_loop.max_iterations=9\n"
+        ));
+    }
+
+    #[test]
+    fn test_show_resolved_renders_multiline_and_lists() {
+        let layers = layers_from(
+            r#"
+features:
+  preflight:
+    skip: [git, tools]
+hats:
+  builder:
+    name: "Builder"
+    description: "Builds"
+    triggers: ["build.task"]
+    publishes: ["build.done"]
+    instructions: |
+      Line one
+      Line two
+"#,
+            &[],
+        );
+        let output = render(&layers);
+
+        assert!(
+            output.contains("    skip: [git, tools]  # ralph.yml\n"),
+            "{output}"
+        );
+        assert!(output.contains("    instructions:  # ralph.yml\n"));
+        assert!(output.contains("Line one\n"));
+        assert!(output.contains("Line two"));
+    }
+
+    #[test]
+    fn test_order_like_follows_reference() {
+        let mut value: Value = serde_yaml::from_str("b: 1\na: 2\nc: 3\n").unwrap();
+        let reference: Value = serde_yaml::from_str("c: 0\na: 0\n").unwrap();
+        order_like(&mut value, &reference);
+        let keys: Vec<String> = value.as_mapping().unwrap().keys().map(key_string).collect();
+        assert_eq!(keys, vec!["c", "a", "b"]);
+    }
+}
@@ -5,20 +5,23 @@
 //! Subcommands:
 //! - `list`: Show all configured hats (Name, Description)
 //! - `show`: Show detailed configuration for a specific hat
+//! - `validate`: Check hat topology (`--strict` also fails on warnings)
 
 use crate::ConfigSource;
 use crate::display::colors;
-use crate::presets;
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand, ValueEnum};
 use indicatif::{ProgressBar, ProgressStyle};
-use ralph_adapters::{CliBackend, detect_backend_default};
-use ralph_core::{HatRegistry, RalphConfig};
+use ralph_adapters::{
+    CliBackend, ModelSelection, detect_backend_default, is_known_model, known_models,
+    model_selection_flags,
+};
+use ralph_core::{HatRegistry, IssueSeverity, RalphConfig, TopologyAnalyzer, TopologyIssue};
+use ralph_proto::Hat;
 use std::collections::HashSet;
 use std::io::Write;
 use std::process::{Command, Stdio};
 use std::time::Duration;
-use tracing::warn;
 
 /// Manage configured hats.
 #[derive(Parser, Debug)]
@@ -30,7 +33,11 @@ pub struct HatsArgs {
 #[derive(Subcommand, Debug)]
 pub enum HatsCommands {
     /// Validate hat topology and report issues
-    Validate,
+    Validate {
+        /// Treat warnings (orphan events, unreachable hats, unguarded cycles) as errors
+        #[arg(long)]
+        strict: bool,
+    },
     /// Display hat topology graph
     Graph {
         /// Output format (unicode, ascii, compact, mermaid)
@@ -80,7 +87,7 @@ pub struct ShowArgs {
 pub fn execute(config_sources: &[ConfigSource], args: HatsArgs, use_colors: bool) -> Result<()> {
     let config = load_config(config_sources)?;
 
-    let registry = HatRegistry::from_config(&config);
+    let registry = HatRegistry::try_from_config(&config)?;
     let mut stdout = std::io::stdout();
 
     match args.command {
@@ -94,7 +101,9 @@ pub fn execute(config_sources: &[ConfigSource], args: HatsArgs, use_colors: bool
         Some(HatsCommands::Show(show_args)) => {
             show_hat(&mut stdout, &registry, &show_args.name, use_colors)
         }
-        Some(HatsCommands::Validate) => validate_hats(&mut stdout, &config, &registry, use_colors),
+        Some(HatsCommands::Validate { strict }) => {
+            validate_hats(&mut stdout, &config, &registry, strict, use_colors)
+        }
         Some(HatsCommands::Graph { format, backend }) => {
             graph_hats(&mut stdout, &config, &registry, format, backend.as_deref())
         }
@@ -103,75 +112,24 @@ pub fn execute(config_sources: &[ConfigSource], args: HatsArgs, use_colors: bool
 
 /// Load configuration from config sources, with proper error handling.
 ///
-/// Supports:
-/// - File paths (local config files)
-/// - Builtin presets (e.g., `builtin:confession-loop`)
-///
-/// Remote URLs and overrides are not supported; returns an error with guidance.
+/// Uses the same layering as `ralph run` (user defaults, `extends:`,
+/// `-c key=value` overrides), except that remote URLs are not supported and
+/// an explicitly named config file must exist.
 fn load_config(config_sources: &[ConfigSource]) -> Result<RalphConfig> {
-    // Filter out overrides and remote URLs - not supported for hats command
-    let sources: Vec<_> = config_sources
-        .iter()
-        .filter(|s| !matches!(s, ConfigSource::Override { .. }))
-        .collect();
-
-    if sources.is_empty() {
-        // No config source specified - use defaults
-        warn!("No config source specified, using defaults");
-        return Ok(RalphConfig::default());
-    }
-
-    if sources.len() > 1 {
-        warn!("Multiple config sources specified, using first one. Others ignored.");
-    }
-
-    let source = &sources[0];
-
-    match source {
-        ConfigSource::File(path) => {
-            if path.exists() {
-                RalphConfig::from_file(path)
-                    .with_context(|| format!("Failed to load config from {:?}", path))
-            } else if path.as_path() == std::path::Path::new("ralph.yml") {
-                // Default path doesn't exist - this is fine, use defaults
-                warn!("Config file 'ralph.yml' not found, using defaults");
-                Ok(RalphConfig::default())
-            } else {
-                // User explicitly specified a config file that doesn't exist - this is an error
-                Err(anyhow::anyhow!(
-                    "Config file not found: {:?}\n\nTo use default configuration, omit the -c/--config flag.\nTo see available presets, run: ralph init --list-presets\nSee: docs/reference/troubleshooting.md#config-not-found",
-                    path
-                ))
-            }
-        }
-        ConfigSource::Builtin(name) => {
-            let preset = presets::get_preset(name).ok_or_else(|| {
-                let available = presets::preset_names().join(", ");
-                anyhow::anyhow!(
-                    "Unknown preset '{}'. Run `ralph init --list-presets` to see available presets.\n\nAvailable: {}",
-                    name,
-                    available
-                )
-            })?;
-            RalphConfig::parse_yaml(preset.content)
-                .with_context(|| format!("Failed to parse builtin preset '{}'", name))
-        }
-        ConfigSource::Remote(url) => {
-            // Remote configs require async - not supported in hats command
-            Err(anyhow::anyhow!(
-                "Remote config URLs are not supported for `ralph hats`.\n\nPlease use a local config file or builtin preset instead.\nURL: {}",
-                url
-            ))
-        }
-        ConfigSource::Override { key, value } => {
-            // This should never happen since we filter out overrides above
-            Err(anyhow::anyhow!(
-                "Config overrides are not supported for `ralph hats`.\n\nPlease use a local config file or builtin preset instead.\nOverride: {}={}",
-                key,
-                value
-            ))
+    for source in config_sources {
+        if let ConfigSource::File(path) = source
+            && !path.exists()
+            && path.as_path() != std::path::Path::new("ralph.yml")
+        {
+            // User explicitly specified a config file that doesn't exist - this is an error
+            anyhow::bail!(
+                "Config file not found: {:?}\n\nTo use default configuration, omit the -c/--config flag.\nTo see available presets, run: ralph init --list-presets\nSee: docs/reference/troubleshooting.md#config-not-found",
+                path
+            );
         }
     }
+
+    crate::config_cli::load_config_sync(config_sources)
 }
 
 fn list_hats_json<W: Write>(writer: &mut W, registry: &HatRegistry) -> Result<()> {
@@ -220,6 +178,7 @@ fn validate_hats<W: Write>(
     writer: &mut W,
     config: &RalphConfig,
     registry: &HatRegistry,
+    strict: bool,
     use_colors: bool,
 ) -> Result<()> {
     writeln!(writer, "Hat Topology Validation")?;
@@ -241,70 +200,84 @@ fn validate_hats<W: Write>(
 
     writeln!(writer, "Checks:")?;
 
-    let mut warnings = 0;
-    let mut errors = 0;
+    let report = TopologyAnalyzer::new(config, registry).analyze();
 
     // 1. Starting event validation
-    if let Some(start) = &config.event_loop.starting_event {
-        if registry.has_subscriber(start) {
-            let hat = registry.get_for_topic(start).unwrap();
-            print_check(
-                writer,
-                CheckResult::Ok,
-                &format!("Starting event '{}' has subscriber ({})", start, hat.name),
-                use_colors,
-            )?;
-        } else {
-            print_check(
-                writer,
-                CheckResult::Error,
-                &format!("starting_event '{}' has no subscribers", start),
-                use_colors,
-            )?;
-            errors += 1;
-        }
+    if let Some(start) = &config.event_loop.starting_event
+        && let Some(hat) = registry.get_for_topic(start)
+    {
+        print_check(
+            writer,
+            CheckResult::Ok,
+            &format!("Starting event '{}' has subscriber ({})", start, hat.name),
+            use_colors,
+        )?;
     }
 
-    // 2. Orphan event detection (published but no subscribers)
-    for hat in registry.all() {
-        for pub_event in &hat.publishes {
-            let topic = pub_event.as_str();
-            // Ignore loop completion promise
-            if topic == config.event_loop.completion_promise {
-                continue;
-            }
-            // Ignore if Ralph subscribes (task.start, etc - though Ralph usually PUBLISHES task.start)
-            // Ralph conceptually subscribes to everything as fallback, but we want to warn if no SPECIFIC hat handles it.
-            if !registry.has_subscriber(topic) {
-                print_check(
-                    writer,
-                    CheckResult::Warn,
-                    &format!(
-                        "Event '{}' published by '{}' has no hat subscribers",
-                        topic, hat.name
-                    ),
-                    use_colors,
-                )?;
-                warnings += 1;
-            }
-        }
+    // 2. Topology issues (orphan events, unreachable hats, cycles, completion)
+    for issue in &report.issues {
+        let result = match issue.severity() {
+            IssueSeverity::Error => CheckResult::Error,
+            IssueSeverity::Warning => CheckResult::Warn,
+        };
+        print_check(writer, result, &issue.to_string(), use_colors)?;
     }
 
-    // 3. Dead end detection
-    let mut dead_ends = 0;
-    for hat in registry.all() {
-        if hat.publishes.is_empty() {
-            // It's okay to be a dead end if it's the Summarizer (which outputs completion promise via stdout/file, not event)
-            // But usually they publish something.
-            // Just info.
-            // print_check(CheckResult::Ok, &format!("Hat '{}' is a dead end (publishes nothing)", hat.name), use_colors);
-            dead_ends += 1;
-        }
+    let has_issue = |matches: fn(&TopologyIssue) -> bool| report.issues.iter().any(matches);
+    if !has_issue(|i| matches!(i, TopologyIssue::UnreachableHat { .. })) {
+        print_check(
+            writer,
+            CheckResult::Ok,
+            &format!("All hats reachable from '{}'", report.entry_topic),
+            use_colors,
+        )?;
     }
-    if dead_ends == 0 {
+    if !has_issue(|i| matches!(i, TopologyIssue::UnguardedCycle { .. })) {
+        print_check(writer, CheckResult::Ok, "No unguarded cycles", use_colors)?;
+    }
+    if report.completion_reachable {
+        print_check(
+            writer,
+            CheckResult::Ok,
+            &format!(
+                "Completion promise '{}' is reachable",
+                config.event_loop.completion_promise
+            ),
+            use_colors,
+        )?;
+    }
+
+    // 3. Dead end detection
+    if registry.all().all(|hat| !hat.publishes.is_empty()) {
         print_check(writer, CheckResult::Ok, "No dead-end hats", use_colors)?;
     }
 
+    // 4. Model and effort selection
+    let (model_errors, model_warnings) = check_model_selections(config);
+    for msg in &model_errors {
+        print_check(writer, CheckResult::Error, msg, use_colors)?;
+    }
+    for msg in &model_warnings {
+        print_check(writer, CheckResult::Warn, msg, use_colors)?;
+    }
+
+    // 5. Trigger conditions (the registry only builds when they all parse)
+    let conditional = registry
+        .all()
+        .map(|hat| hat.conditions.len())
+        .sum::<usize>();
+    if conditional > 0 {
+        print_check(
+            writer,
+            CheckResult::Ok,
+            &format!("Trigger conditions parse ({conditional} conditional triggers)"),
+            use_colors,
+        )?;
+    }
+
+    let errors = report.errors().count() + model_errors.len();
+    let warnings = report.warnings().count() + model_warnings.len();
+
     writeln!(writer)?;
     if errors > 0 {
         writeln!(
@@ -314,6 +287,16 @@ fn validate_hats<W: Write>(
         )?;
         // Return error to propagate failure to main
         return Err(anyhow::anyhow!("Validation failed with {} errors", errors));
+    } else if warnings > 0 && strict {
+        writeln!(
+            writer,
+            "Result: Invalid ({} warnings, --strict treats warnings as errors)",
+            warnings
+        )?;
+        return Err(anyhow::anyhow!(
+            "Strict validation failed with {} warnings",
+            warnings
+        ));
     } else if warnings > 0 {
         writeln!(writer, "Result: Valid ({} warnings)", warnings)?;
     } else {
@@ -322,6 +305,77 @@ fn validate_hats<W: Write>(
     Ok(())
 }
 
+/// Checks each hat's model and effort against the backend it runs on.
+///
+/// Returns `(errors, warnings)`: a selection the backend cannot express is an
+/// error, a model missing from the backend's known list only a warning.
+fn check_model_selections(config: &RalphConfig) -> (Vec<String>, Vec<String>) {
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+
+    let mut hats: Vec<_> = config.hats.iter().collect();
+    hats.sort_by_key(|(id, _)| id.as_str());
+    for (id, hat) in hats {
+        // Hats on the global backend inherit cli.model and cli.effort.
+        let (backend, selection) = match &hat.backend {
+            Some(backend) => (
+                backend.to_cli_backend(),
+                ModelSelection {
+                    model: hat.model.clone(),
+                    effort: hat.effort.clone(),
+                },
+            ),
+            None => (
+                config.cli.backend.clone(),
+                ModelSelection {
+                    model: hat.model.clone().or_else(|| config.cli.model.clone()),
+                    effort: hat.effort.clone().or_else(|| config.cli.effort.clone()),
+                },
+            ),
+        };
+        // An auto-detected backend is unknown until the loop starts.
+        if selection.is_empty() || backend == "auto" {
+            continue;
+        }
+
+        if let Err(e) = model_selection_flags(&backend, &selection, &config.backends) {
+            errors.push(format!("Hat '{id}': {e}"));
+            continue;
+        }
+        if let Some(model) = &selection.model
+            && !config.backends.contains_key(&backend)
+            && !is_known_model(&backend, model)
+        {
+            warnings.push(format!(
+                "Hat '{id}': model '{model}' is not a known {backend} model (known: {})",
+                known_models(&backend).join(", ")
+            ));
+        }
+    }
+
+    (errors, warnings)
+}
+
+/// Label for an edge into `hat` via `sub`, including its `when:` condition.
+fn trigger_label(hat: &Hat, sub: &str) -> String {
+    match hat.conditions.get(sub) {
+        Some(condition) => format!("{sub} when {condition}"),
+        None => sub.to_string(),
+    }
+}
+
+/// Quotes an edge label for Mermaid, which chokes on `>`, `|` and friends.
+fn mermaid_label(label: &str) -> String {
+    if label
+        .chars()
+        .all(|c| c.is_alphanumeric() || "._-*".contains(c))
+    {
+        label.to_string()
+    } else {
+        format!("\"{}\"", label.replace('"', "#quot;"))
+    }
+}
+
 enum CheckResult {
     Ok,
     Warn,
@@ -402,7 +456,7 @@ fn render_hat_dag_via_ai(
     let prompt = build_diagram_prompt(registry);
 
     // Create backend and generate diagram
-    let backend = CliBackend::from_name(&backend_name)
+    let backend = CliBackend::from_name_with(&backend_name, &config.backends)
         .map_err(|e| anyhow::anyhow!("Failed to create backend '{}': {}", backend_name, e))?;
 
     // Show spinner while generating
@@ -477,7 +531,7 @@ fn render_hat_dag_via_ai(
 fn resolve_backend(flag_override: Option<&str>, config: &RalphConfig) -> Result<String> {
     // 1. CLI flag takes precedence
     if let Some(backend) = flag_override {
-        validate_backend_name(backend)?;
+        validate_backend_name(backend, config)?;
         return Ok(backend.to_string());
     }
 
@@ -490,12 +544,14 @@ fn resolve_backend(flag_override: Option<&str>, config: &RalphConfig) -> Result<
     detect_backend_default().map_err(|e| anyhow::anyhow!("{}", e))
 }
 
-/// Validates a backend name.
-fn validate_backend_name(name: &str) -> Result<()> {
+/// Validates a backend name against the built-ins and the config's
+/// declared backends.
+fn validate_backend_name(name: &str, config: &RalphConfig) -> Result<()> {
     match name {
         "claude" | "kiro" | "gemini" | "codex" | "amp" | "copilot" | "opencode" | "pi" => Ok(()),
+        _ if config.backends.contains_key(name) => Ok(()),
         _ => Err(anyhow::anyhow!(
-            "Unknown backend: {}\n\nValid backends: claude, kiro, gemini, codex, amp, copilot, opencode, pi",
+            "Unknown backend: {}\n\nValid backends: claude, kiro, gemini, codex, amp, copilot, opencode, pi, or a name defined under `backends:`",
             name
         )),
     }
@@ -522,7 +578,7 @@ fn build_diagram_prompt(registry: &HatRegistry) -> String {
             prompt.push_str(&format!(
                 "- Ralph → {} (triggers on: {})\n",
                 hat.name,
-                sub.as_str()
+                trigger_label(hat, sub.as_str())
             ));
         }
     }
@@ -554,7 +610,7 @@ fn build_diagram_prompt(registry: &HatRegistry) -> String {
                         "- {} → {} (via event: {})\n",
                         source.name,
                         target.name,
-                        pub_event.as_str()
+                        trigger_label(target, pub_event.as_str())
                     ));
                 }
             }
@@ -612,7 +668,11 @@ fn generate_mermaid_string(registry: &HatRegistry) -> String {
     for hat in registry.all() {
         let node_id = sanitize_id(&hat.name);
         for sub in &hat.subscriptions {
-            output.push_str(&format!("    Ralph -->|{}| {}\n", sub.as_str(), node_id));
+            output.push_str(&format!(
+                "    Ralph -->|{}| {}\n",
+                mermaid_label(&trigger_label(hat, sub.as_str())),
+                node_id
+            ));
         }
     }
 
@@ -647,7 +707,7 @@ fn generate_mermaid_string(registry: &HatRegistry) -> String {
                     output.push_str(&format!(
                         "    {} -.->|{}| {}\n",
                         source_id,
-                        pub_event.as_str(),
+                        mermaid_label(&trigger_label(target, pub_event.as_str())),
                         target_id
                     ));
                 }
@@ -771,7 +831,7 @@ mod tests {
         let mut buf = Vec::new();
 
         // Validation might exit process on error, so we test warning scenario
-        validate_hats(&mut buf, &config, &registry, false).unwrap();
+        validate_hats(&mut buf, &config, &registry, false, false).unwrap();
         let output = String::from_utf8(buf).unwrap();
 
         // Should warn about build.done having no subscribers
@@ -781,6 +841,102 @@ mod tests {
         assert!(output.contains("Result: Valid (1 warnings)"));
     }
 
+    #[test]
+    fn test_validate_hats_strict_fails_on_warnings() {
+        let mut registry = HatRegistry::new();
+        registry.register(mock_hat("Builder", &["build.task"], &["build.done"]));
+
+        let config = RalphConfig::default();
+        let mut buf = Vec::new();
+
+        let result = validate_hats(&mut buf, &config, &registry, true, false);
+        let output = String::from_utf8(buf).unwrap();
+
+        assert!(result.is_err());
+        assert!(
+            output.contains("Result: Invalid (1 warnings, --strict treats warnings as errors)")
+        );
+    }
+
+    #[test]
+    fn test_validate_hats_reports_unguarded_cycle_without_exit() {
+        let mut registry = HatRegistry::new();
+        registry.register(mock_hat("Planner", &["plan.start"], &["build.task"]));
+        registry.register(mock_hat("Builder", &["build.task"], &["plan.start"]));
+
+        let mut config = RalphConfig::default();
+        config.event_loop.starting_event = Some("plan.start".to_string());
+        let mut buf = Vec::new();
+
+        let result = validate_hats(&mut buf, &config, &registry, false, false);
+        let output = String::from_utf8(buf).unwrap();
+
+        assert!(result.is_err());
+        assert!(output.contains("Starting event 'plan.start' has subscriber (Planner)"));
+        assert!(
+            output.contains(
+                "[err] Cycle Builder -> Planner has no max_activations guard and no exit"
+            )
+        );
+        assert!(
+            output
+                .contains("[err] No reachable hat can lead to completion promise 'LOOP_COMPLETE'")
+        );
+    }
+
+    #[test]
+    fn test_validate_hats_checks_model_selection() {
+        let config: RalphConfig = serde_yaml::from_str(
+            r#"
+cli:
+  backend: claude
+hats:
+  builder:
+    name: Builder
+    description: Builds
+    triggers: ["build.task"]
+    publishes: ["LOOP_COMPLETE"]
+    model: sonet
+  reviewer:
+    name: Reviewer
+    description: Reviews
+    triggers: ["review.task"]
+    publishes: ["LOOP_COMPLETE"]
+    backend: gemini
+    effort: high
+"#,
+        )
+        .unwrap();
+        let registry = HatRegistry::from_config(&config);
+        let mut buf = Vec::new();
+
+        let result = validate_hats(&mut buf, &config, &registry, false, false);
+        let output = String::from_utf8(buf).unwrap();
+
+        assert!(result.is_err());
+        assert!(
+            output.contains(
+                "[err] Hat 'reviewer': backend 'gemini' does not support reasoning effort"
+            ),
+            "{output}"
+        );
+        assert!(
+            output.contains("[warn] Hat 'builder': model 'sonet' is not a known claude model"),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn test_builtin_presets_have_no_topology_errors() {
+        for preset in crate::presets::list_presets() {
+            let config: RalphConfig = serde_yaml::from_str(preset.content).unwrap();
+            let registry = HatRegistry::from_config(&config);
+            let report = TopologyAnalyzer::new(&config, &registry).analyze();
+            let errors: Vec<String> = report.errors().map(ToString::to_string).collect();
+            assert!(errors.is_empty(), "{}: {:?}", preset.name, errors);
+        }
+    }
+
     #[test]
     fn test_graph_hats_mermaid() {
         let mut registry = HatRegistry::new();
@@ -847,6 +1003,64 @@ mod tests {
         assert!(output.contains("A -.->|mid| B"));
     }
 
+    #[test]
+    fn test_generate_mermaid_string_labels_conditional_edges() {
+        let mut registry = HatRegistry::new();
+        registry.register(mock_hat("Reviewer", &["review.task"], &["review.done"]));
+        registry.register(
+            mock_hat("Fixer", &["review.done"], &["review.task"]).with_condition(
+                "review.done",
+                ralph_proto::Condition::parse("$.failures > 0").unwrap(),
+            ),
+        );
+
+        let output = generate_mermaid_string(&registry);
+
+        assert!(
+            output.contains("Ralph -->|review.task| Reviewer"),
+            "{output}"
+        );
+        assert!(
+            output.contains("Ralph -->|\"review.done when $.failures > 0\"| Fixer"),
+            "{output}"
+        );
+        assert!(
+            output.contains("Reviewer -.->|\"review.done when $.failures > 0\"| Fixer"),
+            "{output}"
+        );
+    }
+
+    #[test]
+    fn test_validate_hats_checks_trigger_conditions() {
+        let yaml = r#"
+hats:
+  fixer:
+    name: Fixer
+    description: Fixes failures
+    triggers: ["review.done"]
+    publishes: ["LOOP_COMPLETE"]
+    when: "$.failures > 0"
+"#;
+        let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
+        let registry = HatRegistry::from_config(&config);
+        let mut buf = Vec::new();
+        validate_hats(&mut buf, &config, &registry, false, false).ok();
+        let output = String::from_utf8(buf).unwrap();
+        assert!(
+            output.contains("[ok] Trigger conditions parse (1 conditional triggers)"),
+            "{output}"
+        );
+
+        let config: RalphConfig = serde_yaml::from_str(&yaml.replace("> 0", "> > 0")).unwrap();
+        let error = HatRegistry::try_from_config(&config)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            error.contains("Invalid condition for trigger 'review.done' on hat 'fixer'"),
+            "{error}"
+        );
+    }
+
     #[test]
     fn test_show_hat_found() {
         let mut registry = HatRegistry::new();
@@ -879,7 +1093,7 @@ mod tests {
         let config = RalphConfig::default();
         let mut buf = Vec::new();
 
-        validate_hats(&mut buf, &config, &registry, false).unwrap();
+        validate_hats(&mut buf, &config, &registry, false, false).unwrap();
         let output = String::from_utf8(buf).unwrap();
 
         assert!(output.contains("No hats configured"));
@@ -895,7 +1109,7 @@ mod tests {
         let config = RalphConfig::default();
         let mut buf = Vec::new();
 
-        validate_hats(&mut buf, &config, &registry, false).unwrap();
+        validate_hats(&mut buf, &config, &registry, false, false).unwrap();
         let output = String::from_utf8(buf).unwrap();
 
         assert!(output.contains("No dead-end hats") || output.contains("Result: Valid"));
@@ -1054,20 +1268,29 @@ mod tests {
 
     #[test]
     fn test_validate_backend_name_valid() {
-        assert!(validate_backend_name("claude").is_ok());
-        assert!(validate_backend_name("kiro").is_ok());
-        assert!(validate_backend_name("gemini").is_ok());
-        assert!(validate_backend_name("codex").is_ok());
-        assert!(validate_backend_name("amp").is_ok());
+        let config = RalphConfig::default();
+        assert!(validate_backend_name("claude", &config).is_ok());
+        assert!(validate_backend_name("kiro", &config).is_ok());
+        assert!(validate_backend_name("gemini", &config).is_ok());
+        assert!(validate_backend_name("codex", &config).is_ok());
+        assert!(validate_backend_name("amp", &config).is_ok());
     }
 
     #[test]
     fn test_validate_backend_name_invalid() {
-        let result = validate_backend_name("unknown-backend");
+        let config = RalphConfig::default();
+        let result = validate_backend_name("unknown-backend", &config);
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("Unknown backend"));
     }
 
+    #[test]
+    fn test_validate_backend_name_declared() {
+        let config: RalphConfig =
+            serde_yaml::from_str("backends:\n  aider:\n    command: aider\n").unwrap();
+        assert!(validate_backend_name("aider", &config).is_ok());
+    }
+
     #[test]
     fn test_resolve_backend_flag_override() {
         let config = RalphConfig::default();
@@ -39,7 +39,7 @@ async fn send_progress(args: ProgressArgs) -> Result<()> {
     let chat_id = bot::resolve_chat_id()
         .context("No chat_id found. Run `ralph bot onboard --telegram` to detect it")?;
 
-    bot::telegram_send_message(&token, chat_id, &args.message).await?;
+    bot::telegram_send_message(&bot::resolve_api_url(), &token, chat_id, &args.message).await?;
 
     println!("Sent.");
     Ok(())
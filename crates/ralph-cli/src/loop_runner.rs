@@ -6,17 +6,20 @@
 
 use anyhow::{Context, Result};
 use ralph_adapters::{
-    CliBackend, CliExecutor, ConsoleStreamHandler, OutputFormat as BackendOutputFormat,
-    PrettyStreamHandler, PtyConfig, PtyExecutor, QuietStreamHandler, TuiStreamHandler,
+    ApiBackend, BackendFailure, BackendRecovery, CliBackend, CliExecutor, ConsoleStreamHandler,
+    ModelSelection, OutputFormat as BackendOutputFormat, PrettyStreamHandler, PtyConfig,
+    PtyExecutor, QuietStreamHandler, RecoveryAction, StreamHandler, TuiStreamHandler,
+    classify_failure,
 };
 use ralph_core::{
     CompletionAction, EventLogger, EventLoop, EventParser, EventRecord, LoopCompletionHandler,
-    LoopContext, LoopHistory, LoopRegistry, MergeQueue, RalphConfig, Record, SessionRecorder,
-    SummaryWriter, TerminationReason,
+    LoopContext, LoopHistory, LoopRegistry, MergeQueue, RalphConfig, Record, Redactor, Sandbox,
+    ScopeViolation, ScopeViolationAction, SessionRecorder, SummaryWriter, TerminationReason,
+    WorkspaceSnapshot, WriteScope,
 };
-use ralph_proto::{Event, HatId};
+use ralph_proto::{Event, HatId, LoopOutcome};
 use ralph_tui::Tui;
-use std::ffi::OsStr;
+use std::ffi::{OsStr, OsString};
 use std::fs::{self, File};
 use std::io::{BufWriter, IsTerminal, stdin, stdout};
 use std::path::{Path, PathBuf};
@@ -34,8 +37,18 @@ pub(crate) struct ExecutionOutcome {
     pub output: String,
     pub success: bool,
     pub termination: Option<TerminationReason>,
+    /// Why the backend failed, when the iteration failed because of it.
+    pub failure: Option<BackendFailure>,
+    /// Estimated cost of the iteration, when the backend reports usage.
+    pub cost_usd: f64,
 }
 
+/// Backend name for the direct HTTP backend, which has no CLI to spawn.
+const API_BACKEND: &str = "api";
+
+/// How often the events file is checked for approval decisions while idle.
+const APPROVAL_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
 /// Core loop implementation supporting both fresh start and continue modes.
 ///
 /// # Arguments
@@ -60,6 +73,11 @@ pub async fn run_loop_impl(
     // "The orchestrator must run as a process group leader"
     process_management::setup_process_group();
 
+    // Mask secrets in everything the loop writes or sends from here on
+    ralph_core::redaction::install(
+        Redactor::from_config(&config.redaction).context("Invalid redaction pattern")?,
+    );
+
     let use_colors = color_mode.should_use_colors();
 
     // Determine effective execution mode (with fallback logic)
@@ -129,6 +147,19 @@ pub async fn run_loop_impl(
 
         debug!("Created events file for this run: {}", relative_events_path);
 
+        // Drop timers, held approvals and decisions left by a previous run;
+        // only --continue picks up the first two
+        for path in [
+            ctx.timers_path(),
+            ctx.approvals_path(),
+            ctx.approval_decisions_path(),
+        ] {
+            if path.exists() {
+                fs::remove_file(&path)
+                    .with_context(|| format!("Failed to clear loop state: {:?}", path))?;
+            }
+        }
+
         // Clear scratchpad for fresh objective start
         // Stale content from previous runs can confuse the agent about current task state
         let scratchpad_path = ctx.scratchpad_path();
@@ -156,17 +187,9 @@ pub async fn run_loop_impl(
     // Capture the robot service shutdown flag so signal handlers can interrupt wait_for_response()
     let robot_shutdown = event_loop.robot_shutdown_flag();
 
-    // For resume mode, we initialize with a different event topic
-    // This tells the planner to read existing scratchpad rather than creating a new one
-    if resume {
-        event_loop.initialize_resume(&prompt_content);
-    } else {
-        event_loop.initialize(&prompt_content);
-    }
-
     // Set up session recording if requested
     // This records all events to a JSONL file for replay testing
-    let _session_recorder: Option<Arc<SessionRecorder<BufWriter<File>>>> =
+    let session_recorder: Option<Arc<SessionRecorder<BufWriter<File>>>> =
         if let Some(record_path) = record_session {
             let file = File::create(&record_path).with_context(|| {
                 format!("Failed to create session recording file: {:?}", record_path)
@@ -190,6 +213,15 @@ pub async fn run_loop_impl(
             None
         };
 
+    // Initialize after the session recorder is wired so the start event is recorded.
+    // For resume mode, we initialize with a different event topic
+    // This tells the planner to read existing scratchpad rather than creating a new one
+    if resume {
+        event_loop.initialize_resume(&prompt_content);
+    } else {
+        event_loop.initialize(&prompt_content);
+    }
+
     // Initialize event logger for debugging (uses context for path resolution)
     let mut event_logger = EventLogger::from_context(&ctx);
 
@@ -210,12 +242,26 @@ pub async fn run_loop_impl(
 
     // Create backend from config - TUI mode uses the same backend as non-TUI
     // The TUI is an observation layer that displays output, not a different mode
-    let mut backend = CliBackend::from_config(&config.cli).map_err(|e| anyhow::Error::new(e))?;
+    let mut backend = CliBackend::from_config_with(&config.cli, &config.backends)
+        .map_err(|e| anyhow::Error::new(e))?;
 
     // Append custom args from CLI if provided (e.g., `ralph run -b opencode -- --model="some-model"`)
     if !custom_args.is_empty() {
         backend.args.extend(custom_args);
     }
+    // Launch backends inside the namespace sandbox when enabled.
+    let sandbox = if config.sandbox.enabled {
+        let sandbox = Sandbox::new(&config.sandbox, &config.core.workspace_root)
+            .context("Failed to set up the sandbox (see `ralph preflight --check sandbox`)")?;
+        info!("Running backends in a {} sandbox", sandbox.engine_name());
+        Some(sandbox)
+    } else {
+        None
+    };
+
+    // Name of the global backend; changes when recovery fails over.
+    let mut backend_name = config.cli.backend.clone();
+    let mut backend_recovery = BackendRecovery::new(&config);
 
     // Create PTY executor if using interactive mode
     let mut pty_executor = if use_pty {
@@ -230,7 +276,9 @@ pub async fn run_loop_impl(
             workspace_root: config.core.workspace_root.clone(),
             ..PtyConfig::from_env()
         };
-        Some(PtyExecutor::new(backend.clone(), pty_config))
+        let mut executor = PtyExecutor::new(backend.clone(), pty_config);
+        executor.set_sandbox(sandbox.clone());
+        Some(executor)
     } else {
         None
     };
@@ -251,7 +299,8 @@ pub async fn run_loop_impl(
         let tui = Tui::new()
             .with_hat_map(hat_map)
             .with_termination_signal(terminated_rx)
-            .with_events_path(resolve_current_events_path(&ctx));
+            .with_events_path(resolve_current_events_path(&ctx))
+            .with_approval_decisions_path(ctx.approval_decisions_path());
 
         // Get shared state and guidance queue before spawning (for content streaming)
         let state = tui.state();
@@ -698,6 +747,44 @@ pub async fn run_loop_impl(
             return Ok(reason);
         }
 
+        // Publish delayed events and expired watchdogs that have fallen due
+        for fired in event_loop.tick_timers() {
+            let record = EventRecord::new(
+                event_loop.state().iteration,
+                "timer",
+                &fired,
+                None::<&HatId>,
+            );
+            if let Err(e) = event_logger.log(&record) {
+                warn!("Failed to log timer event: {}", e);
+            }
+        }
+        // Settle held events the human decided on, and reject timed-out ones
+        let decisions = event_loop.read_approval_decisions();
+        if !decisions.is_empty()
+            && let Some(ref recorder) = session_recorder
+        {
+            recorder.record_meta(Record::meta_approval_decisions(
+                event_loop.state().iteration,
+                &decisions,
+            ));
+        }
+        for settled in event_loop.tick_approvals_with(&decisions) {
+            let record = EventRecord::new(
+                event_loop.state().iteration,
+                "approval",
+                &settled,
+                None::<&HatId>,
+            );
+            if let Err(e) = event_logger.log(&record) {
+                warn!("Failed to log approval decision: {}", e);
+            }
+        }
+        if let Some(mut s) = tui_state.as_ref().and_then(|state| state.lock().ok()) {
+            s.pending_timers = event_loop.pending_timers();
+            s.pending_approvals = event_loop.pending_approvals();
+        }
+
         // Get next hat to execute, with fallback recovery if no pending events
         let hat_id = match event_loop.next_hat() {
             Some(id) => {
@@ -706,6 +793,33 @@ pub async fn run_loop_impl(
                 id.clone()
             }
             None => {
+                // Nothing to do until a held event is decided or a delayed event
+                // or watchdog falls due: wait for it (in bounded steps so limits
+                // and interrupts are honoured)
+                let approval_poll = event_loop
+                    .awaiting_approval()
+                    .then_some(APPROVAL_POLL_INTERVAL);
+                let wait = [
+                    event_loop.next_timer_due_in(),
+                    event_loop.next_approval_due_in(),
+                    approval_poll,
+                ]
+                .into_iter()
+                .flatten()
+                .min();
+                if let Some(wait) = wait {
+                    let wait = wait.min(Duration::from_mins(1)) + Duration::from_millis(50);
+                    debug!("Waiting {}ms for a timer or approval", wait.as_millis());
+                    let mut interrupt_rx_timer = interrupt_rx.clone();
+                    tokio::select! {
+                        () = tokio::time::sleep(wait) => {}
+                        _ = interrupt_rx_timer.changed() => {}
+                    }
+
+                    // Decisions are picked up by tick_approvals at the top of the loop
+                    continue;
+                }
+
                 // No pending events - try to recover by injecting a fallback event
                 // This triggers the built-in planner to assess the situation
                 consecutive_fallbacks += 1;
@@ -785,6 +899,15 @@ pub async fn run_loop_impl(
             hat_id.clone()
         };
 
+        // Record the hat switch so exported sessions can show it in the timeline
+        if let Some(ref recorder) = session_recorder {
+            recorder.record_meta(Record::meta_iteration(
+                iteration,
+                event_loop.state().elapsed().as_millis() as u64,
+                display_hat.as_str(),
+            ));
+        }
+
         // Per spec: Print iteration demarcation separator
         // "Each iteration must be clearly demarcated in the output so users can
         // visually distinguish where one iteration ends and another begins."
@@ -844,68 +967,83 @@ pub async fn run_loop_impl(
 
         // Step 2: Resolve effective backend and determine backend name for timeout
         // Note: backend_name_for_timeout is owned String to avoid lifetime issues with hat_backend reference
-        let (effective_backend, backend_name_for_timeout): (CliBackend, String) =
-            match hat_backend_opt {
-                Some(hat_backend) => {
-                    // Hat has custom backend configuration
-                    match CliBackend::from_hat_backend(hat_backend) {
-                        Ok(hat_backend_instance) => {
-                            debug!(
-                                "Using hat-level backend for '{}': {:?}",
-                                display_hat, hat_backend
-                            );
+        let (mut effective_backend, mut backend_name_for_timeout, uses_global_backend): (
+            CliBackend,
+            String,
+            bool,
+        ) = match hat_backend_opt {
+            Some(hat_backend) => {
+                // Hat has custom backend configuration
+                match CliBackend::from_hat_backend_with(hat_backend, &config.backends) {
+                    Ok(hat_backend_instance) => {
+                        debug!(
+                            "Using hat-level backend for '{}': {:?}",
+                            display_hat, hat_backend
+                        );
 
-                            // Determine backend name for timeout based on hat backend type
-                            // Use owned String to avoid borrowing issues and improve code clarity
-                            let backend_name = match hat_backend {
-                                ralph_core::HatBackend::Named(name) => name.clone(),
-                                ralph_core::HatBackend::NamedWithArgs { backend_type, .. } => {
-                                    backend_type.clone()
-                                }
-                                ralph_core::HatBackend::KiroAgent { .. } => "kiro".to_string(),
-                                // For Custom backends, extract command name from path
-                                // Handles both Unix ("/usr/bin/codex") and commands with args ("ollama run llama3")
-                                ralph_core::HatBackend::Custom { command, .. } => {
-                                    // First split by whitespace to handle commands with arguments
-                                    // e.g., "ollama run llama3" -> "ollama"
-                                    let base_command =
-                                        command.split_whitespace().next().unwrap_or(command);
-                                    // Then extract filename from path
-                                    // e.g., "/usr/bin/codex" -> "codex"
-                                    std::path::Path::new(base_command)
-                                        .file_name()
-                                        .and_then(|s| s.to_str())
-                                        .unwrap_or("custom")
-                                        .to_string()
-                                }
-                            };
-
-                            (hat_backend_instance, backend_name)
-                        }
-                        Err(e) => {
-                            // Failed to create backend from hat config - fall back to global
-                            warn!(
-                                "Failed to create backend from hat configuration for '{}': {}. Falling back to global backend.",
-                                display_hat, e
-                            );
-                            // IMPORTANT: Use global backend name for timeout since we're using global backend
-                            (backend.clone(), config.cli.backend.clone())
-                        }
+                        // Determine backend name for timeout based on hat backend type
+                        // Use owned String to avoid borrowing issues and improve code clarity
+                        let backend_name = match hat_backend {
+                            ralph_core::HatBackend::Named(name) => name.clone(),
+                            ralph_core::HatBackend::NamedWithArgs { backend_type, .. } => {
+                                backend_type.clone()
+                            }
+                            ralph_core::HatBackend::KiroAgent { .. } => "kiro".to_string(),
+                            // For Custom backends, extract command name from path
+                            // Handles both Unix ("/usr/bin/codex") and commands with args ("ollama run llama3")
+                            ralph_core::HatBackend::Custom { command, .. } => {
+                                // First split by whitespace to handle commands with arguments
+                                // e.g., "ollama run llama3" -> "ollama"
+                                let base_command =
+                                    command.split_whitespace().next().unwrap_or(command);
+                                // Then extract filename from path
+                                // e.g., "/usr/bin/codex" -> "codex"
+                                std::path::Path::new(base_command)
+                                    .file_name()
+                                    .and_then(|s| s.to_str())
+                                    .unwrap_or("custom")
+                                    .to_string()
+                            }
+                        };
+
+                        (hat_backend_instance, backend_name, false)
+                    }
+                    Err(e) => {
+                        // Failed to create backend from hat config - fall back to global
+                        warn!(
+                            "Failed to create backend from hat configuration for '{}': {}. Falling back to global backend.",
+                            display_hat, e
+                        );
+                        // IMPORTANT: Use global backend name for timeout since we're using global backend
+                        (backend.clone(), backend_name.clone(), true)
                     }
                 }
-                None => {
-                    // No custom backend - use global configuration
-                    debug!(
-                        "Using global backend for '{}': {}",
-                        display_hat, config.cli.backend
-                    );
-                    (backend.clone(), config.cli.backend.clone())
-                }
-            };
+            }
+            None => {
+                // No custom backend - use global configuration
+                debug!(
+                    "Using global backend for '{}': {}",
+                    display_hat, backend_name
+                );
+                (backend.clone(), backend_name.clone(), true)
+            }
+        };
 
-        // Step 3: Get timeout from config based on actual backend being used
-        let timeout_secs = config.adapter_settings(&backend_name_for_timeout).timeout;
-        let timeout = Some(Duration::from_secs(timeout_secs));
+        // Step 3: Add the flags for the hat's model and reasoning effort
+        let mut model_selection = resolve_model_selection(
+            &config,
+            event_loop.registry().get_config(&display_hat),
+            uses_global_backend,
+            &backend_name,
+        );
+        if let Err(e) = effective_backend.apply_model_selection(
+            &backend_name_for_timeout,
+            &model_selection,
+            &config.backends,
+        ) {
+            warn!("Ignoring model selection for '{}': {}", display_hat, e);
+            model_selection = ModelSelection::default();
+        }
 
         // For TUI mode, get the shared lines buffer for this iteration.
         // The buffer is owned by TuiState's IterationBuffer, so writes from
@@ -925,69 +1063,216 @@ pub async fn run_loop_impl(
                     state,
                     hat_display.clone(),
                     backend_name_for_timeout.clone(),
+                    model_selection.model.clone(),
                     config.event_loop.max_iterations,
                 )
             } else {
                 None
             };
 
-        // Race execution against interrupt signal for immediate termination on Ctrl+C
-        let mut interrupt_rx_clone = interrupt_rx.clone();
-        let interrupt_rx_for_pty = interrupt_rx.clone();
-        let tui_lines_for_pty = tui_lines.clone();
-        let execute_future = async {
-            if use_pty {
-                execute_pty(
-                    pty_executor.as_mut(),
-                    &effective_backend,
-                    &config,
-                    &prompt,
-                    user_interactive,
-                    interrupt_rx_for_pty,
-                    verbosity,
-                    tui_lines_for_pty,
-                )
-                .await
-            } else {
-                let executor = CliExecutor::new(effective_backend.clone());
-                let result = executor
-                    .execute(&prompt, stdout(), timeout, verbosity == Verbosity::Verbose)
-                    .await?;
-                Ok(ExecutionOutcome {
-                    output: result.output,
-                    success: result.success,
-                    termination: None,
-                })
-            }
-        };
+        // Snapshot the working tree so the hat's write_scope can be checked afterwards
+        let write_scope = event_loop
+            .registry()
+            .get_config(&display_hat)
+            .and_then(|hat| hat.write_scope.clone());
+        let scope_snapshot = write_scope.as_ref().and_then(|_| {
+            WorkspaceSnapshot::capture(&config.core.workspace_root)
+                .map_err(|e| warn!("Cannot enforce write_scope for '{}': {}", display_hat, e))
+                .ok()
+        });
 
-        let outcome = tokio::select! {
-            result = execute_future => result?,
-            _ = interrupt_rx_clone.changed() => {
-                // Immediately terminate children via process group signal
-                #[cfg(unix)]
-                {
-                    use nix::sys::signal::{killpg, Signal};
-                    use nix::unistd::getpgrp;
-                    let pgid = getpgrp();
-                    debug!("Sending SIGTERM to process group {}", pgid);
-                    let _ = killpg(pgid, Signal::SIGTERM);
-
-                    // Wait briefly for graceful exit, then SIGKILL
-                    tokio::time::sleep(Duration::from_millis(250)).await;
-                    let _ = killpg(pgid, Signal::SIGKILL);
+        // Execute, retrying the same prompt when the backend is rate-limited
+        // or recovery fails over to another backend.
+        let mut iteration_cost = 0.0;
+        let outcome = loop {
+            // Step 4: Get timeout from config based on actual backend being used
+            let timeout_secs = config.adapter_settings(&backend_name_for_timeout).timeout;
+            let timeout = Some(Duration::from_secs(timeout_secs));
+
+            // Race execution against interrupt signal for immediate termination on Ctrl+C
+            let mut interrupt_rx_clone = interrupt_rx.clone();
+            let interrupt_rx_for_pty = interrupt_rx.clone();
+            let tui_lines_for_pty = tui_lines.clone();
+            let execute_future = async {
+                if backend_name_for_timeout == API_BACKEND {
+                    execute_api(
+                        &config,
+                        sandbox.as_ref(),
+                        &model_selection,
+                        &prompt,
+                        timeout,
+                        verbosity,
+                        tui_lines_for_pty,
+                    )
+                    .await
+                } else if use_pty {
+                    execute_pty(
+                        pty_executor.as_mut(),
+                        &effective_backend,
+                        &backend_name_for_timeout,
+                        &config,
+                        &prompt,
+                        user_interactive,
+                        interrupt_rx_for_pty,
+                        verbosity,
+                        tui_lines_for_pty,
+                    )
+                    .await
+                } else {
+                    let executor =
+                        CliExecutor::new(effective_backend.clone()).with_sandbox(sandbox.clone());
+                    let result = executor
+                        .execute(&prompt, stdout(), timeout, verbosity == Verbosity::Verbose)
+                        .await?;
+                    let failure = if result.success || result.timed_out {
+                        None
+                    } else {
+                        classify_failure(
+                            &backend_name_for_timeout,
+                            result.exit_code,
+                            &result.output,
+                        )
+                    };
+                    let cost_usd = effective_backend
+                        .extract_cost(&result.output)
+                        .unwrap_or(0.0);
+                    Ok(ExecutionOutcome {
+                        output: result.output,
+                        success: result.success,
+                        termination: None,
+                        failure,
+                        cost_usd,
+                    })
                 }
+            };
 
-                let reason = TerminationReason::Interrupted;
-                let terminate_event = event_loop.publish_terminate_event(&reason);
-                log_terminate_event(&mut event_logger, event_loop.state().iteration, &terminate_event);
-                handle_termination(&reason, event_loop.state(), &config.core.scratchpad, &loop_history, &loop_context, auto_merge, &prompt_content);
-                // Signal TUI to exit immediately on interrupt
-                let _ = terminated_tx.send(true);
-                return Ok(reason);
+            let outcome = tokio::select! {
+                result = execute_future => result?,
+                _ = interrupt_rx_clone.changed() => {
+                    // Immediately terminate children via process group signal
+                    #[cfg(unix)]
+                    {
+                        use nix::sys::signal::{killpg, Signal};
+                        use nix::unistd::getpgrp;
+                        let pgid = getpgrp();
+                        debug!("Sending SIGTERM to process group {}", pgid);
+                        let _ = killpg(pgid, Signal::SIGTERM);
+
+                        // Wait briefly for graceful exit, then SIGKILL
+                        tokio::time::sleep(Duration::from_millis(250)).await;
+                        let _ = killpg(pgid, Signal::SIGKILL);
+                    }
+
+                    let reason = TerminationReason::Interrupted;
+                    let terminate_event = event_loop.publish_terminate_event(&reason);
+                    log_terminate_event(&mut event_logger, event_loop.state().iteration, &terminate_event);
+                    handle_termination(&reason, event_loop.state(), &config.core.scratchpad, &loop_history, &loop_context, auto_merge, &prompt_content);
+                    // Signal TUI to exit immediately on interrupt
+                    let _ = terminated_tx.send(true);
+                    return Ok(reason);
+                }
+            };
+
+            if outcome.cost_usd > 0.0 {
+                event_loop.add_model_cost(
+                    &backend_name_for_timeout,
+                    model_selection.model.as_deref(),
+                    outcome.cost_usd,
+                );
+                iteration_cost += outcome.cost_usd;
+            }
+
+            let Some(failure) = outcome.failure.clone() else {
+                if outcome.success {
+                    backend_recovery.record_success();
+                }
+                break outcome;
+            };
+            match backend_recovery.on_failure(
+                &backend_name_for_timeout,
+                &failure,
+                uses_global_backend,
+            ) {
+                RecoveryAction::GiveUp => break outcome,
+                RecoveryAction::Retry { delay } => {
+                    warn!(
+                        "Backend '{}' is rate-limited, retrying in {}s",
+                        backend_name_for_timeout,
+                        delay.as_secs()
+                    );
+                    log_backend_event(
+                        &mut event_logger,
+                        iteration,
+                        "backend.rate_limited",
+                        format!(
+                            "Backend '{}' is rate-limited; retrying in {}s",
+                            backend_name_for_timeout,
+                            delay.as_secs()
+                        ),
+                    );
+                    tokio::select! {
+                        () = tokio::time::sleep(delay) => {}
+                        _ = interrupt_rx_clone.changed() => {
+                            let reason = TerminationReason::Interrupted;
+                            let terminate_event = event_loop.publish_terminate_event(&reason);
+                            log_terminate_event(&mut event_logger, event_loop.state().iteration, &terminate_event);
+                            handle_termination(&reason, event_loop.state(), &config.core.scratchpad, &loop_history, &loop_context, auto_merge, &prompt_content);
+                            let _ = terminated_tx.send(true);
+                            return Ok(reason);
+                        }
+                    }
+                }
+                RecoveryAction::Failover { backend: next } => {
+                    let new_backend = match CliBackend::from_config_with(
+                        &failover_cli_config(&config.cli, &next),
+                        &config.backends,
+                    ) {
+                        Ok(new_backend) => new_backend,
+                        Err(e) => {
+                            warn!("Cannot fail over to backend '{}': {}", next, e);
+                            break outcome;
+                        }
+                    };
+                    warn!(
+                        "Backend '{}' failed ({}), switching to '{}' for the remaining iterations",
+                        backend_name,
+                        failure.as_str(),
+                        next
+                    );
+                    log_backend_event(
+                        &mut event_logger,
+                        iteration,
+                        "backend.failover",
+                        format!("{} -> {} ({})", backend_name, next, failure.as_str()),
+                    );
+                    if let Some(ref history) = loop_history
+                        && let Err(e) =
+                            history.record_backend_switched(&backend_name, &next, failure.as_str())
+                    {
+                        warn!("Failed to record backend switch in history: {}", e);
+                    }
+                    backend = new_backend;
+                    backend_name = next;
+                    effective_backend = backend.clone();
+                    backend_name_for_timeout = backend_name.clone();
+                    model_selection = ModelSelection::default();
+                }
             }
         };
 
+        if let Some(ref history) = loop_history
+            && let Err(e) = history.record_iteration_usage(
+                iteration,
+                display_hat.as_str(),
+                &backend_name_for_timeout,
+                model_selection.model.as_deref(),
+                model_selection.effort.as_deref(),
+                iteration_cost,
+            )
+        {
+            warn!("Failed to record iteration usage in history: {}", e);
+        }
+
         if let Some(reason) = outcome.termination {
             let terminate_event = event_loop.publish_terminate_event(&reason);
             log_terminate_event(
@@ -1014,6 +1299,16 @@ pub async fn run_loop_impl(
         let output = outcome.output;
         let success = outcome.success;
 
+        // Record raw backend output so `ralph run --replay` can substitute it
+        if let Some(ref recorder) = session_recorder {
+            recorder.record_meta(Record::meta_agent_output(
+                iteration,
+                display_hat.as_str(),
+                &output,
+                success,
+            ));
+        }
+
         // Note: TUI lines are now written directly to IterationBuffer during streaming,
         // so no post-execution transfer is needed.
         if let Some(mut s) = tui_state.as_ref().and_then(|state| state.lock().ok()) {
@@ -1066,13 +1361,46 @@ pub async fn run_loop_impl(
             warn!(error = %e, "Failed to check planning session responses");
         }
 
+        let scope_violation = match (&write_scope, &scope_snapshot) {
+            (Some(scope), Some(snapshot)) => check_write_scope(
+                scope,
+                snapshot,
+                &config.core.workspace_root,
+                &display_hat,
+                |changed| {
+                    if let Some(ref recorder) = session_recorder {
+                        recorder.record_meta(Record::meta_changed_files(iteration, changed));
+                    }
+                },
+            ),
+            _ => None,
+        };
+
         // Read events from JSONL that agent may have written
-        let agent_wrote_events = matches!(
-            event_loop
-                .process_events_from_jsonl()
-                .inspect_err(|e| warn!(error = %e, "Failed to read events from JSONL")),
-            Ok(true)
-        );
+        let mut agent_wrote_events = match event_loop.read_events_from_jsonl() {
+            Ok(mut parsed) => {
+                if let Some(ref recorder) = session_recorder {
+                    recorder.record_meta(Record::meta_agent_events(iteration, &parsed));
+                }
+                if scope_violation
+                    .as_ref()
+                    .is_some_and(|v| v.action == ScopeViolationAction::Reject)
+                {
+                    parsed.events.clear();
+                }
+                event_loop.process_parsed_events(parsed)
+            }
+            Err(e) => {
+                warn!(error = %e, "Failed to read events from JSONL");
+                false
+            }
+        };
+
+        // Tell the loop which files were out of scope (replaces default_publishes)
+        if let Some(violation) = scope_violation {
+            event_loop.publish_or_hold(violation.to_event());
+            agent_wrote_events = true;
+        }
 
         // Inject default_publishes for active hats only when agent wrote no events
         if !agent_wrote_events {
@@ -1197,6 +1525,7 @@ fn prepare_tui_iteration(
     tui_state: &Arc<std::sync::Mutex<ralph_tui::TuiState>>,
     hat_display: String,
     backend: String,
+    model: Option<String>,
     max_iterations: u32,
 ) -> Option<Arc<std::sync::Mutex<Vec<ratatui::text::Line<'static>>>>> {
     let Ok(mut state) = tui_state.lock() else {
@@ -1206,12 +1535,14 @@ fn prepare_tui_iteration(
     // state was reset by earlier events.
     state.max_iterations = Some(max_iterations);
     state.start_new_iteration_with_metadata(Some(hat_display), Some(backend));
+    state.set_latest_iteration_model(model);
     state.latest_iteration_lines_handle()
 }
 
 async fn execute_pty(
     executor: Option<&mut PtyExecutor>,
     backend: &CliBackend,
+    backend_name: &str,
     config: &RalphConfig,
     prompt: &str,
     interactive: bool,
@@ -1244,6 +1575,12 @@ async fn execute_pty(
             ..PtyConfig::from_env()
         };
         temp_executor = PtyExecutor::new(backend.clone(), pty_config);
+        if config.sandbox.enabled {
+            temp_executor.set_sandbox(Some(
+                Sandbox::new(&config.sandbox, &config.core.workspace_root)
+                    .context("Failed to set up the sandbox")?,
+            ));
+        }
         &mut temp_executor
     };
 
@@ -1317,7 +1654,23 @@ async fn execute_pty(
 
     match result {
         Ok(pty_result) => {
+            // Only natural exits are classified: interrupts and idle timeouts
+            // are not the backend's fault.
+            let failure = if pty_result.success
+                || pty_result.termination != ralph_adapters::TerminationType::Natural
+            {
+                None
+            } else {
+                classify_failure(
+                    backend_name,
+                    pty_result.exit_code,
+                    &pty_result.stripped_output,
+                )
+            };
             let termination = convert_termination_type(pty_result.termination, interactive);
+            let cost_usd = backend
+                .extract_cost(&pty_result.stripped_output)
+                .unwrap_or(0.0);
 
             // Use extracted_text for event parsing when available (NDJSON backends like Claude),
             // otherwise fall back to stripped_output (non-JSON backends or interactive mode).
@@ -1332,6 +1685,8 @@ async fn execute_pty(
                 output: output_for_parsing,
                 success: pty_result.success,
                 termination,
+                failure,
+                cost_usd,
             })
         }
         Err(e) => {
@@ -1342,6 +1697,62 @@ async fn execute_pty(
     }
 }
 
+/// Runs one iteration through the `api` backend, streaming to the same
+/// handlers as the PTY path.
+async fn execute_api(
+    config: &RalphConfig,
+    sandbox: Option<&Sandbox>,
+    selection: &ModelSelection,
+    prompt: &str,
+    timeout: Option<Duration>,
+    verbosity: Verbosity,
+    tui_lines: Option<Arc<std::sync::Mutex<Vec<ratatui::text::Line<'static>>>>>,
+) -> Result<ExecutionOutcome> {
+    let api_config = ralph_core::ApiBackendConfig {
+        model: selection
+            .model
+            .clone()
+            .or_else(|| config.cli.api.model.clone()),
+        ..config.cli.api.clone()
+    };
+    let backend = ApiBackend::new(&api_config, &config.core.workspace_root)
+        .context("Failed to create api backend")?
+        .with_reasoning_effort(selection.effort.clone())
+        .with_sandbox(sandbox.cloned());
+
+    let verbose = verbosity == Verbosity::Verbose;
+    let mut handler: Box<dyn StreamHandler> = if let Some(lines) = tui_lines {
+        Box::new(TuiStreamHandler::with_lines(verbose, lines))
+    } else if verbosity == Verbosity::Quiet {
+        Box::new(QuietStreamHandler)
+    } else if stdout().is_terminal() {
+        Box::new(PrettyStreamHandler::new(verbose))
+    } else {
+        Box::new(ConsoleStreamHandler::new(verbose))
+    };
+
+    let result = backend.execute(prompt, handler.as_mut(), timeout).await;
+    info!(
+        "api backend: {} requests, {} prompt / {} completion tokens, est. ${:.4}",
+        result.turns, result.usage.prompt_tokens, result.usage.completion_tokens, result.cost_usd
+    );
+
+    // HTTP failures carry no exit code; report them as a plain failure so
+    // only the status and message decide the classification.
+    let failure = if result.success || result.timed_out {
+        None
+    } else {
+        classify_failure(API_BACKEND, Some(1), &result.output)
+    };
+    Ok(ExecutionOutcome {
+        output: result.output,
+        success: result.success,
+        termination: None,
+        failure,
+        cost_usd: result.cost_usd,
+    })
+}
+
 /// Logs events parsed from output to the event history file.
 ///
 /// When an event has no subscriber (orphan), also logs an `event.orphaned`
@@ -1410,6 +1821,94 @@ fn log_events_from_output(
     }
 }
 
+/// Logs a `backend.*` system event (rate-limit backoff, failover) to the
+/// event history.
+fn log_backend_event(logger: &mut EventLogger, iteration: u32, topic: &str, payload: String) {
+    let event = Event::new(topic, payload);
+    let record = EventRecord::new(iteration, "loop", &event, None::<&HatId>);
+    if let Err(e) = logger.log(&record) {
+        warn!("Failed to log {} event: {}", topic, e);
+    }
+}
+
+/// Checks the files changed during a hat's iteration against its write scope.
+///
+/// The changed files are handed to `on_changed` (for session recording) before
+/// the check. Reverts the out-of-scope files when the scope asks for it.
+/// Errors from git are logged and treated as no violation.
+fn check_write_scope(
+    scope: &WriteScope,
+    snapshot: &WorkspaceSnapshot,
+    workspace: &Path,
+    hat_id: &HatId,
+    on_changed: impl FnOnce(&[String]),
+) -> Option<ScopeViolation> {
+    let changed = snapshot
+        .changed_paths(workspace)
+        .map_err(|e| warn!("Cannot check write_scope for '{}': {}", hat_id, e))
+        .ok()?;
+    on_changed(&changed);
+    let violation = scope.check(hat_id.as_str(), &changed)?;
+    warn!(
+        hat = %hat_id,
+        files = ?violation.files,
+        action = ?violation.action,
+        "Hat modified files outside its write_scope"
+    );
+    if violation.action == ScopeViolationAction::Revert
+        && let Err(e) = snapshot.revert(workspace, &violation.files)
+    {
+        warn!("Failed to revert out-of-scope changes: {}", e);
+    }
+    Some(violation)
+}
+
+/// Resolves the model and reasoning effort for a hat's iteration.
+///
+/// A hat's own `model`/`effort` apply to the backend it declares, or to the
+/// global backend when it declares none, in which case unset fields inherit
+/// `cli.model`/`cli.effort`. Once the loop has failed over, the global
+/// selection is dropped: the names belong to the original backend.
+fn resolve_model_selection(
+    config: &RalphConfig,
+    hat: Option<&ralph_core::HatConfig>,
+    uses_global_backend: bool,
+    global_backend: &str,
+) -> ModelSelection {
+    let hat_has_backend = hat.is_some_and(|hat| hat.backend.is_some());
+    if !uses_global_backend {
+        return ModelSelection {
+            model: hat.and_then(|hat| hat.model.clone()),
+            effort: hat.and_then(|hat| hat.effort.clone()),
+        };
+    }
+    if global_backend != config.cli.backend {
+        return ModelSelection::default();
+    }
+    // A hat whose own backend failed to load keeps only the global selection.
+    let hat = hat.filter(|_| !hat_has_backend);
+    ModelSelection {
+        model: hat
+            .and_then(|hat| hat.model.clone())
+            .or_else(|| config.cli.model.clone()),
+        effort: hat
+            .and_then(|hat| hat.effort.clone())
+            .or_else(|| config.cli.effort.clone()),
+    }
+}
+
+/// CLI config for failing over to `backend`: the same prompt settings, but
+/// without the command override and args meant for the previous backend.
+fn failover_cli_config(cli: &ralph_core::CliConfig, backend: &str) -> ralph_core::CliConfig {
+    ralph_core::CliConfig {
+        backend: backend.to_string(),
+        command: None,
+        args: Vec::new(),
+        prompt_flag: None,
+        ..cli.clone()
+    }
+}
+
 /// Logs the loop.terminate system event to the event history.
 ///
 /// Per spec: loop.terminate is an observer-only event published on loop exit.
@@ -1452,7 +1951,9 @@ fn get_last_commit_info() -> Option<String> {
 /// 5. Default PROMPT.md
 ///
 /// Note: CLI overrides are already applied to config before this function is called.
-fn resolve_prompt_content(event_loop_config: &ralph_core::EventLoopConfig) -> Result<String> {
+pub(crate) fn resolve_prompt_content(
+    event_loop_config: &ralph_core::EventLoopConfig,
+) -> Result<String> {
     debug!(
         inline_prompt = ?event_loop_config.prompt.as_ref().map(|s| format!("{}...", &s[..s.len().min(50)])),
         prompt_file = %event_loop_config.prompt_file,
@@ -1691,12 +2192,12 @@ pub fn process_pending_merges_cli(repo_root: &Path) {
 /// responsible for Telegram interaction — the spawned loop has `robot.enabled`
 /// disabled to prevent a second Telegram poller from conflicting.
 ///
-/// Returns `Ok(TerminationReason)` on completion or `Err` on fatal errors.
+/// Returns the loop ID and `TerminationReason` on completion or `Err` on fatal errors.
 pub async fn start_loop(
     prompt: String,
     workspace_root: PathBuf,
     config_path: Option<PathBuf>,
-) -> Result<TerminationReason> {
+) -> Result<LoopOutcome> {
     use crate::{ColorMode, ConfigSource, load_config_with_overrides};
 
     // Load config from file or defaults
@@ -1731,9 +2232,11 @@ pub async fn start_loop(
     // Auto-detect backend if needed
     if config.cli.backend == "auto" {
         let priority = config.get_agent_priority();
-        let detected = ralph_adapters::detect_backend(&priority, |backend| {
-            config.adapter_settings(backend).enabled
-        });
+        let detected = ralph_adapters::detect_backend_with(
+            &priority,
+            |backend| config.adapter_settings(backend).enabled,
+            &config.backends,
+        );
         match detected {
             Ok(backend) => {
                 info!("Auto-detected backend: {}", backend);
@@ -1756,18 +2259,112 @@ pub async fn start_loop(
     let loop_context = ralph_core::LoopContext::primary(workspace_root);
 
     // Run the loop headlessly
-    run_loop_impl(
+    let reason = run_loop_impl(
         config,
         ColorMode::Never,
         false, // not resume
         false, // no TUI
         Verbosity::Normal,
         None, // no session recording
-        Some(loop_context),
+        Some(loop_context.clone()),
         Vec::new(), // no custom args
         None,       // default auto-merge
     )
-    .await
+    .await?;
+
+    Ok(loop_outcome(&loop_context, &reason))
+}
+
+/// Environment variable naming a file that `ralph run` writes its
+/// [`LoopOutcome`] to as JSON. Set by [`start_worktree_loop`].
+pub(crate) const LOOP_OUTCOME_ENV: &str = "RALPH_LOOP_OUTCOME_FILE";
+
+/// Start a loop alongside the primary loop (e.g., for the bot daemon).
+///
+/// Runs `ralph run` as a child process: it finds the loop lock held and
+/// spawns into a worktree as usual. The child runs with `RObot.enabled=false`
+/// so it never competes with the primary loop for Telegram updates, and
+/// reports its loop ID and `TerminationReason` through [`LOOP_OUTCOME_ENV`].
+pub async fn start_worktree_loop(
+    prompt: String,
+    workspace_root: PathBuf,
+    config_path: Option<PathBuf>,
+) -> Result<LoopOutcome> {
+    let config_args: Vec<OsString> = config_path.into_iter().map(OsString::from).collect();
+    start_loop_process(prompt, workspace_root, &config_args).await
+}
+
+/// Runs `ralph run` as a child process with the given `-c` sources.
+///
+/// The child takes the loop lock if it is free and spawns into a worktree
+/// otherwise. Like [`start_worktree_loop`], it runs with `RObot.enabled=false`
+/// and reports its [`LoopOutcome`] through [`LOOP_OUTCOME_ENV`].
+pub async fn start_loop_process(
+    prompt: String,
+    workspace_root: PathBuf,
+    config_args: &[OsString],
+) -> Result<LoopOutcome> {
+    use std::sync::atomic::{AtomicU32, Ordering};
+    static NEXT_OUTCOME_FILE: AtomicU32 = AtomicU32::new(0);
+
+    let exe = std::env::current_exe().context("Failed to locate the ralph executable")?;
+    let ralph_dir = workspace_root.join(".ralph");
+    fs::create_dir_all(&ralph_dir).context("Failed to create .ralph directory")?;
+    let outcome_path = ralph_dir.join(format!(
+        "loop-outcome-{}-{}.json",
+        std::process::id(),
+        NEXT_OUTCOME_FILE.fetch_add(1, Ordering::Relaxed)
+    ));
+
+    let mut command = tokio::process::Command::new(exe);
+    for source in config_args {
+        command.arg("-c").arg(source);
+    }
+    command
+        .args(["-c", "RObot.enabled=false", "run", "--autonomous", "-p"])
+        .arg(&prompt)
+        .current_dir(&workspace_root)
+        .env(LOOP_OUTCOME_ENV, &outcome_path)
+        .stdin(std::process::Stdio::null())
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .kill_on_drop(true);
+    let status = command
+        .status()
+        .await
+        .context("Failed to start worktree loop")?;
+
+    let outcome = fs::read_to_string(&outcome_path)
+        .ok()
+        .and_then(|json| serde_json::from_str(&json).ok());
+    let _ = fs::remove_file(&outcome_path);
+    outcome.with_context(|| format!("Loop exited ({status}) without reporting a result"))
+}
+
+/// Describes how a loop ended, reading its ID from the `current-loop-id` marker.
+fn loop_outcome(ctx: &LoopContext, reason: &TerminationReason) -> LoopOutcome {
+    let loop_id = fs::read_to_string(ctx.ralph_dir().join("current-loop-id"))
+        .ok()
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty());
+    LoopOutcome {
+        loop_id,
+        termination: format!("{reason:?}"),
+    }
+}
+
+/// Writes the loop outcome to the file named by [`LOOP_OUTCOME_ENV`], if set.
+pub(crate) fn write_loop_outcome(ctx: &LoopContext, reason: &TerminationReason) {
+    let Some(path) = std::env::var_os(LOOP_OUTCOME_ENV) else {
+        return;
+    };
+    let outcome = loop_outcome(ctx, reason);
+    let result = serde_json::to_string(&outcome)
+        .map_err(anyhow::Error::from)
+        .and_then(|json| fs::write(&path, json).map_err(anyhow::Error::from));
+    if let Err(e) = result {
+        warn!("Failed to write loop outcome to {:?}: {}", path, e);
+    }
 }
 
 /// Creates a robot service (Telegram) for human-in-the-loop communication.
@@ -1778,32 +2375,59 @@ fn create_robot_service(
     config: &RalphConfig,
     context: &LoopContext,
 ) -> Option<Box<dyn ralph_proto::RobotService>> {
-    let workspace_root = context.workspace().to_path_buf();
-    let bot_token = config.robot.resolve_bot_token();
-    let timeout_secs = config.robot.timeout_seconds.unwrap_or(300);
     let loop_id = context
         .loop_id()
         .map(String::from)
         .unwrap_or_else(|| "main".to_string());
+    let service = telegram_service(config, context.workspace().to_path_buf(), loop_id)?;
 
-    match ralph_telegram::TelegramService::new(workspace_root, bot_token, timeout_secs, loop_id) {
-        Ok(service) => {
-            if let Err(e) = service.start() {
-                warn!(error = %e, "Failed to start robot service");
-                return None;
-            }
-            info!(
-                bot_token = %service.bot_token_masked(),
-                timeout_secs = service.timeout_secs(),
-                "Robot human-in-the-loop service active"
-            );
-            Some(Box::new(service))
-        }
+    if let Err(e) = service.start() {
+        warn!(error = %e, "Failed to start robot service");
+        return None;
+    }
+    info!(
+        bot_token = %service.bot_token_masked(),
+        timeout_secs = service.timeout_secs(),
+        "Robot human-in-the-loop service active"
+    );
+    Some(Box::new(service))
+}
+
+/// Builds (but does not start) the Telegram service described by `RObot`.
+///
+/// Returns `None`, with a warning, if the service cannot be created.
+pub(crate) fn telegram_service(
+    config: &RalphConfig,
+    workspace_root: PathBuf,
+    loop_id: String,
+) -> Option<ralph_telegram::TelegramService> {
+    let bot_token = config.robot.resolve_bot_token();
+    let timeout_secs = config.robot.timeout_seconds.unwrap_or(300);
+
+    let service = match ralph_telegram::TelegramService::new(
+        workspace_root,
+        bot_token,
+        timeout_secs,
+        loop_id,
+    ) {
+        Ok(service) => service,
         Err(e) => {
             warn!(error = %e, "Failed to create robot service");
-            None
+            return None;
+        }
+    };
+    let mut service =
+        service.with_access(ralph_proto::AccessList::new(config.robot.access.clone()));
+    if let Some(api_url) = config.robot.resolve_api_url() {
+        match ralph_telegram::parse_api_url(&api_url) {
+            Ok(url) => service = service.with_api_url(url),
+            Err(e) => {
+                warn!(error = %e, "Invalid Telegram API URL");
+                return None;
+            }
         }
     }
+    Some(service)
 }
 
 #[cfg(test)]
@@ -1852,12 +2476,60 @@ mod tests {
     fn test_prepare_tui_iteration_seeds_max_iterations() {
         let state = Arc::new(Mutex::new(ralph_tui::TuiState::new()));
 
-        let lines = prepare_tui_iteration(&state, "Planner".to_string(), "claude".to_string(), 42);
+        let lines = prepare_tui_iteration(
+            &state,
+            "Planner".to_string(),
+            "claude".to_string(),
+            Some("opus".to_string()),
+            42,
+        );
 
         assert!(lines.is_some(), "should return a lines handle");
         let state = state.lock().expect("state lock");
         assert_eq!(state.max_iterations, Some(42));
         assert_eq!(state.total_iterations(), 1);
+        assert_eq!(state.current_iteration_model(), Some("opus"));
+    }
+
+    #[test]
+    fn test_resolve_model_selection_inherits_and_overrides() {
+        let config: RalphConfig = serde_yaml::from_str(
+            r"
+cli:
+  backend: claude
+  model: sonnet
+  effort: low
+hats:
+  planner:
+    name: Planner
+    description: Plans
+    model: opus
+  reviewer:
+    name: Reviewer
+    description: Reviews
+    backend: gemini
+    model: gemini-2.5-pro
+",
+        )
+        .unwrap();
+        let planner = config.hats.get("planner");
+        let reviewer = config.hats.get("reviewer");
+
+        let selection = resolve_model_selection(&config, planner, true, "claude");
+        assert_eq!(selection.model.as_deref(), Some("opus"));
+        assert_eq!(selection.effort.as_deref(), Some("low"));
+
+        let selection = resolve_model_selection(&config, None, true, "claude");
+        assert_eq!(selection.model.as_deref(), Some("sonnet"));
+
+        // Hat-level backends never inherit the global selection.
+        let selection = resolve_model_selection(&config, reviewer, false, "claude");
+        assert_eq!(selection.model.as_deref(), Some("gemini-2.5-pro"));
+        assert_eq!(selection.effort, None);
+
+        // After failover the names no longer apply.
+        let selection = resolve_model_selection(&config, planner, true, "codex");
+        assert!(selection.is_empty());
     }
 
     #[cfg(unix)]
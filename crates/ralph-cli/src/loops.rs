@@ -9,6 +9,7 @@
 //! - `retry`: Re-run merge for failed loop
 //! - `discard`: Abandon loop and cleanup
 //! - `stop`: Terminate running loop
+//! - `approve` / `reject`: Decide on the oldest event held for approval
 //! - `prune`: Clean up stale loops
 //! - `attach`: Open shell in worktree
 //! - `diff`: Show changes from merge-base
@@ -49,6 +50,12 @@ pub enum LoopsCommands {
     /// Stop a running loop
     Stop(StopArgs),
 
+    /// Approve the oldest event held for approval
+    Approve(ApproveArgs),
+
+    /// Reject the oldest event held for approval
+    Reject(RejectArgs),
+
     /// Clean up stale loops (crashed processes)
     Prune,
 
@@ -126,6 +133,24 @@ pub struct StopArgs {
     pub force: bool,
 }
 
+#[derive(Parser, Debug)]
+pub struct ApproveArgs {
+    /// Loop ID (group-id). If omitted, answers the primary loop.
+    #[arg(value_name = "LOOP_ID")]
+    pub loop_id: Option<String>,
+}
+
+#[derive(Parser, Debug)]
+pub struct RejectArgs {
+    /// Loop ID (group-id). If omitted, answers the primary loop.
+    #[arg(value_name = "LOOP_ID")]
+    pub loop_id: Option<String>,
+
+    /// Why the event is rejected (the payload of `<topic>.rejected`)
+    #[arg(long, short)]
+    pub reason: Option<String>,
+}
+
 #[derive(Parser, Debug)]
 pub struct AttachArgs {
     /// Loop ID
@@ -174,6 +199,14 @@ pub fn execute(args: LoopsArgs, use_colors: bool) -> Result<()> {
         Some(LoopsCommands::Retry(retry_args)) => retry_merge(retry_args),
         Some(LoopsCommands::Discard(discard_args)) => discard_loop(discard_args),
         Some(LoopsCommands::Stop(stop_args)) => stop_loop(stop_args),
+        Some(LoopsCommands::Approve(args)) => decide_approval(args.loop_id.as_deref(), "approve"),
+        Some(LoopsCommands::Reject(args)) => {
+            let reply = match args.reason {
+                Some(reason) => format!("reject: {reason}"),
+                None => "reject".to_string(),
+            };
+            decide_approval(args.loop_id.as_deref(), &reply)
+        }
         Some(LoopsCommands::Prune) => prune_stale(),
         Some(LoopsCommands::Attach(attach_args)) => attach_to_loop(attach_args),
         Some(LoopsCommands::Diff(diff_args)) => show_diff(diff_args),
@@ -752,6 +785,37 @@ fn stop_loop(args: StopArgs) -> Result<()> {
     Ok(())
 }
 
+/// Answers the oldest event a loop holds for approval.
+///
+/// The decision goes to the loop's approval decisions file, which the loop
+/// reads at its next iteration boundary (or while waiting for a decision).
+fn decide_approval(loop_id: Option<&str>, reply: &str) -> Result<()> {
+    use ralph_core::{APPROVAL_DECISIONS_FILE, ApprovalQueue, LoopContext};
+
+    let cwd = std::env::current_dir()?;
+    let (loop_id, worktree_path) = match loop_id {
+        Some(id) => resolve_loop(&cwd, id)?,
+        None => ("(primary)".to_string(), None),
+    };
+    let target_root = worktree_path.map_or_else(|| cwd.clone(), PathBuf::from);
+    let context = LoopContext::primary(target_root);
+
+    let held = ApprovalQueue::load(&context.approvals_path())
+        .with_context(|| format!("Failed to read held approvals of loop '{loop_id}'"))?;
+    let Some(oldest) = held.iter().next() else {
+        bail!("Loop '{}' has no event awaiting approval", loop_id);
+    };
+
+    let decisions_path = context.approval_decisions_path();
+    ralph_core::record_decision(&decisions_path, reply)
+        .with_context(|| format!("Failed to write {APPROVAL_DECISIONS_FILE}"))?;
+    println!(
+        "Answered `{}` for loop '{}': {}",
+        oldest.topic, loop_id, reply
+    );
+    Ok(())
+}
+
 /// Prune stale loops.
 fn prune_stale() -> Result<()> {
     let cwd = std::env::current_dir()?;
@@ -13,6 +13,7 @@
 //! - Work item tracking via `ralph task`
 
 mod bot;
+mod config_cli;
 mod display;
 mod doctor;
 mod hats;
@@ -21,8 +22,12 @@ mod interact;
 mod loop_runner;
 mod loops;
 mod memory;
+mod pipeline_cli;
 mod preflight;
 mod presets;
+mod prompt_cli;
+mod replay;
+mod schedule_cli;
 mod skill_cli;
 mod sop_runner;
 mod task_cli;
@@ -33,12 +38,13 @@ mod web;
 
 use anyhow::{Context, Result};
 use clap::{ArgAction, CommandFactory, Parser, Subcommand, ValueEnum};
-use ralph_adapters::detect_backend;
+use ralph_adapters::detect_backend_with;
 use ralph_core::{
     CheckStatus, EventHistory, LockError, LoopContext, LoopEntry, LoopLock, LoopRegistry,
     PreflightReport, PreflightRunner, RalphConfig, TerminationReason,
     worktree::{WorktreeConfig, create_worktree, ensure_gitignore, remove_worktree},
 };
+use std::ffi::OsString;
 use std::fs;
 use std::io::{IsTerminal, Write, stdout};
 use std::path::{Path, PathBuf};
@@ -242,22 +248,21 @@ impl ConfigSource {
     /// Parse a config source string into its variant.
     ///
     /// Format:
-    /// - `core.field=value` → Override (for core.* fields)
+    /// - `dotted.path=value` → Override (any config field, e.g. `event_loop.max_iterations=5`)
     /// - `builtin:preset-name` → Builtin preset
     /// - `http://...` or `https://...` → Remote URL
     /// - Anything else → File path
     fn parse(s: &str) -> Self {
-        // Check for core.* override pattern first (prevents false positives on paths with '=')
-        // Only treat as override if it starts with "core." AND contains '='
-        if s.starts_with("core.")
-            && let Some((key, value)) = s.split_once('=')
+        // An existing file wins, so paths containing '=' still load
+        if let Some((key, value)) = s.split_once('=')
+            && is_override_key(key)
+            && !Path::new(s).exists()
         {
             return ConfigSource::Override {
                 key: key.to_string(),
                 value: value.to_string(),
             };
         }
-        // Existing logic unchanged
         if let Some(name) = s.strip_prefix("builtin:") {
             ConfigSource::Builtin(name.to_string())
         } else if s.starts_with("http://") || s.starts_with("https://") {
@@ -268,40 +273,28 @@ impl ConfigSource {
     }
 }
 
-/// Known core fields that can be overridden via CLI.
-const KNOWN_CORE_FIELDS: &[&str] = &["scratchpad", "specs_dir"];
-
-/// Applies CLI config overrides to the loaded configuration.
-///
-/// Overrides are in the format `core.field=value` and take precedence
-/// over values from the config file.
-pub(crate) fn apply_config_overrides(
-    config: &mut RalphConfig,
-    sources: &[ConfigSource],
-) -> anyhow::Result<()> {
-    for source in sources {
-        if let ConfigSource::Override { key, value } = source {
-            match key.as_str() {
-                "core.scratchpad" => {
-                    config.core.scratchpad = value.clone();
-                }
-                "core.specs_dir" => {
-                    config.core.specs_dir = value.clone();
-                }
-                other => {
-                    // Note: with core.* prefix requirement in parse(), this branch
-                    // only handles unknown core.* fields
-                    let field = other.strip_prefix("core.").unwrap_or(other);
-                    warn!(
-                        "Unknown core field '{}'. Known fields: {}",
-                        field,
-                        KNOWN_CORE_FIELDS.join(", ")
-                    );
-                }
-            }
+impl ConfigSource {
+    /// Renders the source back into a `-c` argument, for child processes.
+    pub(crate) fn to_arg(&self) -> OsString {
+        match self {
+            ConfigSource::File(path) => path.clone().into_os_string(),
+            ConfigSource::Builtin(name) => format!("builtin:{name}").into(),
+            ConfigSource::Remote(url) => url.into(),
+            ConfigSource::Override { key, value } => format!("{key}={value}").into(),
         }
     }
-    Ok(())
+}
+
+/// Returns true if `key` looks like a dotted config path rather than a file path.
+///
+/// Segments may be double-quoted to contain dots (`events."build.done".description`).
+fn is_override_key(key: &str) -> bool {
+    let unquoted: String = key.split('"').step_by(2).collect();
+    key.contains('.')
+        && key.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+        && unquoted
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
 }
 
 /// Ensures the scratchpad's parent directory exists, creating it if needed.
@@ -321,46 +314,11 @@ pub(crate) fn ensure_scratchpad_directory(config: &RalphConfig) -> anyhow::Resul
 /// This is the common sync path used by resume_command and clean_command.
 /// For the full async path (including Remote URLs), see run_command.
 ///
-/// Returns the loaded config with overrides applied and workspace_root set.
+/// Returns the merged config with overrides applied and workspace_root set.
 pub(crate) fn load_config_with_overrides(
     config_sources: &[ConfigSource],
 ) -> anyhow::Result<RalphConfig> {
-    // Partition sources: file sources vs overrides
-    let (primary_sources, overrides): (Vec<_>, Vec<_>) = config_sources
-        .iter()
-        .partition(|s| !matches!(s, ConfigSource::Override { .. }));
-
-    // Load configuration from first file source, or default ralph.yml
-    let mut config = if let Some(ConfigSource::File(path)) = primary_sources.first() {
-        if path.exists() {
-            RalphConfig::from_file(path)
-                .with_context(|| format!("Failed to load config from {:?}", path))?
-        } else {
-            warn!("Config file {:?} not found, using defaults", path);
-            RalphConfig::default()
-        }
-    } else {
-        // Only overrides specified - load default ralph.yml as base
-        let default_path = PathBuf::from("ralph.yml");
-        if default_path.exists() {
-            RalphConfig::from_file(&default_path)
-                .with_context(|| "Failed to load config from ralph.yml")?
-        } else {
-            RalphConfig::default()
-        }
-    };
-
-    config.normalize();
-
-    // Set workspace_root to current directory
-    config.core.workspace_root =
-        std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
-
-    // Apply CLI config overrides
-    let override_sources: Vec<_> = overrides.into_iter().cloned().collect();
-    apply_config_overrides(&mut config, &override_sources)?;
-
-    Ok(config)
+    config_cli::load_config_sync(config_sources)
 }
 
 /// Ralph Orchestrator - Multi-agent orchestration framework
@@ -373,8 +331,8 @@ struct Cli {
     // ─────────────────────────────────────────────────────────────────────────
     // Global options (available for all subcommands)
     // ─────────────────────────────────────────────────────────────────────────
-    /// Configuration source: file path, builtin:preset, URL, or core.field=value override.
-    /// Can be specified multiple times. Overrides are applied after config file loading.
+    /// Configuration source: file path, builtin:preset, URL, or dotted.path=value override.
+    /// Can be specified multiple times. Sources deep-merge in order; overrides apply last.
     #[arg(short, long, default_value = "ralph.yml", global = true, action = ArgAction::Append)]
     config: Vec<String>,
 
@@ -436,12 +394,24 @@ enum Commands {
     /// Manage configured hats
     Hats(hats::HatsArgs),
 
+    /// Inspect the layered configuration
+    Config(config_cli::ConfigArgs),
+
+    /// Inspect the prompts sent to the agent
+    Prompt(prompt_cli::PromptArgs),
+
     /// Run the web dashboard
     Web(web::WebArgs),
 
     /// Manage Telegram bot setup and testing
     Bot(bot::BotArgs),
 
+    /// Run loops on cron-style schedules
+    Schedule(schedule_cli::ScheduleArgs),
+
+    /// Run multi-stage loop pipelines
+    Pipeline(pipeline_cli::PipelineArgs),
+
     /// Generate shell completions
     Completions(CompletionsArgs),
 }
@@ -555,6 +525,11 @@ struct RunArgs {
     #[arg(long, value_name = "FILE")]
     record_session: Option<PathBuf>,
 
+    /// Replay a recorded session through the current config instead of calling a backend.
+    /// Reports divergences in hat selection, published events and termination.
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["record_session", "dry_run"])]
+    replay: Option<PathBuf>,
+
     /// Custom backend command and arguments (use after --)
     #[arg(last = true)]
     custom_args: Vec<String>,
@@ -653,6 +628,14 @@ struct EmitArgs {
     #[arg(long)]
     pub ts: Option<String>,
 
+    /// Deliver the event after a delay instead of immediately (e.g. 30s, 20m, 1h)
+    #[arg(long, value_name = "DURATION")]
+    pub delay: Option<String>,
+
+    /// Deliver the event after this many more iterations
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u32).range(1..))]
+    pub after_iterations: Option<u32>,
+
     /// Path to events file (defaults to .ralph/events.jsonl)
     #[arg(long, default_value = ".ralph/events.jsonl")]
     pub file: PathBuf,
@@ -856,10 +839,22 @@ async fn main() -> Result<()> {
         Some(Commands::Hats(args)) => {
             hats::execute(&config_sources, args, cli.color.should_use_colors())
         }
+        Some(Commands::Config(args)) => {
+            config_cli::execute(&config_sources, args, cli.color.should_use_colors()).await
+        }
+        Some(Commands::Prompt(args)) => {
+            prompt_cli::execute(&config_sources, args, cli.color.should_use_colors()).await
+        }
         Some(Commands::Web(args)) => web::execute(args).await,
         Some(Commands::Bot(args)) => {
             bot::execute(args, &config_sources, cli.color.should_use_colors()).await
         }
+        Some(Commands::Schedule(args)) => {
+            schedule_cli::execute(&config_sources, args, cli.color.should_use_colors()).await
+        }
+        Some(Commands::Pipeline(args)) => {
+            pipeline_cli::execute(&config_sources, args, cli.color.should_use_colors()).await
+        }
         Some(Commands::Completions(args)) => completions_command(args),
         None => {
             // Default to run with TUI enabled (new default behavior)
@@ -880,6 +875,7 @@ async fn main() -> Result<()> {
                 verbose: false,
                 quiet: false,
                 record_session: None,
+                replay: None,
                 custom_args: Vec::new(),
             };
             run_command(&config_sources, cli.verbose, cli.color, args).await
@@ -1048,88 +1044,8 @@ async fn run_command(
     color_mode: ColorMode,
     args: RunArgs,
 ) -> Result<()> {
-    // Partition sources: file/builtin/remote sources vs overrides
-    let (primary_sources, overrides): (Vec<_>, Vec<_>) = config_sources
-        .iter()
-        .partition(|s| !matches!(s, ConfigSource::Override { .. }));
-
-    // Warn if multiple config sources are specified
-    if primary_sources.len() > 1 {
-        warn!("Multiple config sources specified, using first one. Others ignored.");
-    }
-
-    // Load configuration based on first primary source, or default if only overrides
-    let mut config = if let Some(source) = primary_sources.first() {
-        match source {
-            ConfigSource::File(path) => {
-                if path.exists() {
-                    RalphConfig::from_file(path)
-                        .with_context(|| format!("Failed to load config from {:?}", path))?
-                } else {
-                    warn!("Config file {:?} not found, using defaults", path);
-                    RalphConfig::default()
-                }
-            }
-            ConfigSource::Builtin(name) => {
-                let preset = presets::get_preset(name).ok_or_else(|| {
-                    let available = presets::preset_names().join(", ");
-                    anyhow::anyhow!(
-                        "Unknown preset '{}'. Run `ralph run --list-presets` to see available presets.\n\nAvailable: {}",
-                        name,
-                        available
-                    )
-                })?;
-                RalphConfig::parse_yaml(preset.content)
-                    .with_context(|| format!("Failed to parse builtin preset '{}'", name))?
-            }
-            ConfigSource::Remote(url) => {
-                info!("Fetching config from {}", url);
-                let response = reqwest::get(url)
-                    .await
-                    .with_context(|| format!("Failed to fetch config from {}", url))?;
-
-                if !response.status().is_success() {
-                    anyhow::bail!(
-                        "Failed to fetch config from {}: HTTP {}",
-                        url,
-                        response.status()
-                    );
-                }
-
-                let content = response
-                    .text()
-                    .await
-                    .with_context(|| format!("Failed to read config content from {}", url))?;
-
-                RalphConfig::parse_yaml(&content)
-                    .with_context(|| format!("Failed to parse config from {}", url))?
-            }
-            ConfigSource::Override { .. } => unreachable!("Partitioned out overrides"),
-        }
-    } else {
-        // Only overrides specified - load default ralph.yml as base
-        let default_path = PathBuf::from("ralph.yml");
-        if default_path.exists() {
-            RalphConfig::from_file(&default_path)
-                .with_context(|| "Failed to load config from ralph.yml")?
-        } else {
-            warn!("Config file ralph.yml not found, using defaults");
-            RalphConfig::default()
-        }
-    };
-
-    // Normalize v1 flat fields into v2 nested structure
-    config.normalize();
-
-    // Set workspace_root to current directory (critical for E2E tests in isolated workspaces).
-    // This must happen after config load because workspace_root has #[serde(skip)] and
-    // defaults to cwd at deserialize time - but we need it set to the actual runtime cwd.
-    config.core.workspace_root =
-        std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
-
-    // Apply CLI config overrides (takes precedence over config file values)
-    let override_sources: Vec<_> = overrides.into_iter().cloned().collect();
-    apply_config_overrides(&mut config, &override_sources)?;
+    // Merge user defaults, config sources (with `extends:`) and overrides
+    let mut config = config_cli::load_config(config_sources).await?;
 
     // Handle --continue mode: check scratchpad exists before proceeding
     let resume = args.continue_mode;
@@ -1193,12 +1109,20 @@ async fn run_command(
         eprintln!("{warning}");
     }
 
+    // Replay never touches a backend, so it runs before backend detection
+    if let Some(ref session) = args.replay {
+        let use_colors = color_mode.should_use_colors();
+        return replay::run_replay(&config, session, use_colors);
+    }
+
     // Handle auto-detection if backend is "auto"
     if config.cli.backend == "auto" {
         let priority = config.get_agent_priority();
-        let detected = detect_backend(&priority, |backend| {
-            config.adapter_settings(backend).enabled
-        });
+        let detected = detect_backend_with(
+            &priority,
+            |backend| config.adapter_settings(backend).enabled,
+            &config.backends,
+        );
 
         match detected {
             Ok(backend) => {
@@ -1469,11 +1393,12 @@ async fn run_command(
         enable_tui,
         verbosity,
         args.record_session,
-        Some(loop_context),
+        Some(loop_context.clone()),
         custom_args,
         auto_merge_override,
     )
     .await?;
+    loop_runner::write_loop_outcome(&loop_context, &reason);
 
     // Handle restart: exec-replace current process with same CLI args
     if matches!(reason, TerminationReason::RestartRequested) {
@@ -1576,9 +1501,11 @@ async fn resume_command(
     // Handle auto-detection if backend is "auto"
     if config.cli.backend == "auto" {
         let priority = config.get_agent_priority();
-        let detected = detect_backend(&priority, |backend| {
-            config.adapter_settings(backend).enabled
-        });
+        let detected = detect_backend_with(
+            &priority,
+            |backend| config.adapter_settings(backend).enabled,
+            &config.backends,
+        );
 
         match detected {
             Ok(backend) => {
@@ -1881,6 +1808,14 @@ fn clean_command(
 fn emit_command(color_mode: ColorMode, args: EmitArgs) -> Result<()> {
     let use_colors = color_mode.should_use_colors();
 
+    // Approval decisions must come from a human, never from the agent
+    if args.topic == ralph_core::APPROVAL_RESPONSE_TOPIC {
+        anyhow::bail!(
+            "`{}` cannot be emitted: approvals are decided by a human, in the TUI, over Telegram, or with `ralph loops approve` / `ralph loops reject`",
+            ralph_core::APPROVAL_RESPONSE_TOPIC
+        );
+    }
+
     // Generate timestamp if not provided
     let ts = args.ts.unwrap_or_else(|| chrono::Utc::now().to_rfc3339());
 
@@ -1893,9 +1828,17 @@ fn emit_command(color_mode: ColorMode, args: EmitArgs) -> Result<()> {
         args.payload
     };
 
+    // Resolve deferred delivery before writing anything
+    let delay = args
+        .delay
+        .as_deref()
+        .map(ralph_core::timers::parse_duration)
+        .transpose()
+        .map_err(|e| anyhow::anyhow!("Invalid --delay: {e}"))?;
+
     // Build the event record
     // We use serde_json directly to ensure proper escaping
-    let record = serde_json::json!({
+    let mut record = serde_json::json!({
         "topic": args.topic,
         "payload": if args.json && !payload.is_empty() {
             // Parse and embed as object
@@ -1907,6 +1850,13 @@ fn emit_command(color_mode: ColorMode, args: EmitArgs) -> Result<()> {
         },
         "ts": ts
     });
+    if let Some(delay) = delay {
+        let deliver_at = chrono::Utc::now() + chrono::Duration::from_std(delay)?;
+        record["deliver_at"] = serde_json::Value::String(deliver_at.to_rfc3339());
+    }
+    if let Some(n) = args.after_iterations {
+        record["after_iterations"] = serde_json::Value::from(n);
+    }
 
     // Read events path from marker file, fall back to CLI arg if marker doesn't exist
     // This ensures `ralph emit` writes to the same events file as the active run
@@ -1934,15 +1884,26 @@ fn emit_command(color_mode: ColorMode, args: EmitArgs) -> Result<()> {
     writeln!(file, "{}", json_line)?;
 
     // Success message
+    let mut deadlines = Vec::new();
+    if let Some(delay) = delay {
+        deadlines.push(ralph_core::timers::format_duration(delay));
+    }
+    if let Some(n) = args.after_iterations {
+        deadlines.push(format!("{n} iteration{}", if n == 1 { "" } else { "s" }));
+    }
+    let status = if deadlines.is_empty() {
+        format!("Event emitted: {}", args.topic)
+    } else {
+        format!(
+            "Event scheduled: {} (in {})",
+            args.topic,
+            deadlines.join(" or ")
+        )
+    };
     if use_colors {
-        println!(
-            "{}✓{} Event emitted: {}",
-            colors::GREEN,
-            colors::RESET,
-            args.topic
-        );
+        println!("{}✓{} {}", colors::GREEN, colors::RESET, status);
     } else {
-        println!("Event emitted: {}", args.topic);
+        println!("{}", status);
     }
 
     Ok(())
@@ -2130,7 +2091,7 @@ fn plan_command(
     sop_runner::run_sop(config).map_err(|e| match e {
         SopRunError::NoBackend(no_backend) => anyhow::Error::new(no_backend),
         SopRunError::UnknownBackend(name) => anyhow::anyhow!(
-            "Unknown backend: {}\n\nValid backends: claude, kiro, gemini, codex, amp",
+            "Unknown backend: {}\n\nValid backends: claude, kiro, gemini, codex, amp, or a name defined under `backends:`",
             name
         ),
         SopRunError::SpawnError(io_err) => anyhow::anyhow!("Failed to spawn backend: {}", io_err),
@@ -2184,7 +2145,7 @@ fn code_task_command(
     sop_runner::run_sop(config).map_err(|e| match e {
         SopRunError::NoBackend(no_backend) => anyhow::Error::new(no_backend),
         SopRunError::UnknownBackend(name) => anyhow::anyhow!(
-            "Unknown backend: {}\n\nValid backends: claude, kiro, gemini, codex, amp",
+            "Unknown backend: {}\n\nValid backends: claude, kiro, gemini, codex, amp, or a name defined under `backends:`",
             name
         ),
         SopRunError::SpawnError(io_err) => anyhow::anyhow!("Failed to spawn backend: {}", io_err),
@@ -2392,31 +2353,35 @@ mod tests {
         }
     }
 
+    /// Loads `sources` from an empty temp dir so no local ralph.yml leaks in.
+    fn load_overrides_only(sources: &[ConfigSource]) -> RalphConfig {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let _cwd = CwdGuard::set(temp_dir.path());
+        load_config_with_overrides(sources).unwrap()
+    }
+
     #[test]
     fn test_apply_config_overrides_scratchpad() {
-        let mut config = RalphConfig::default();
         let sources = vec![ConfigSource::Override {
             key: "core.scratchpad".to_string(),
             value: ".custom/scratch.md".to_string(),
         }];
-        apply_config_overrides(&mut config, &sources).unwrap();
+        let config = load_overrides_only(&sources);
         assert_eq!(config.core.scratchpad, ".custom/scratch.md");
     }
 
     #[test]
     fn test_apply_config_overrides_specs_dir() {
-        let mut config = RalphConfig::default();
         let sources = vec![ConfigSource::Override {
             key: "core.specs_dir".to_string(),
             value: "./specifications/".to_string(),
         }];
-        apply_config_overrides(&mut config, &sources).unwrap();
+        let config = load_overrides_only(&sources);
         assert_eq!(config.core.specs_dir, "./specifications/");
     }
 
     #[test]
     fn test_apply_config_overrides_multiple() {
-        let mut config = RalphConfig::default();
         let sources = vec![
             ConfigSource::Override {
                 key: "core.scratchpad".to_string(),
@@ -2427,39 +2392,60 @@ mod tests {
                 value: "./my-specs/".to_string(),
             },
         ];
-        apply_config_overrides(&mut config, &sources).unwrap();
+        let config = load_overrides_only(&sources);
         assert_eq!(config.core.scratchpad, ".custom/scratch.md");
         assert_eq!(config.core.specs_dir, "./my-specs/");
     }
 
     #[test]
     fn test_apply_config_overrides_unknown_field() {
-        // Unknown core.* fields should warn but not error
-        let mut config = RalphConfig::default();
-        let original_scratchpad = config.core.scratchpad.clone();
+        // Unknown fields should warn but not error
+        let original_scratchpad = RalphConfig::default().core.scratchpad;
         let sources = vec![ConfigSource::Override {
             key: "core.unknown_field".to_string(),
             value: "some_value".to_string(),
         }];
-        // Should not error
-        apply_config_overrides(&mut config, &sources).unwrap();
+        let config = load_overrides_only(&sources);
         // Original values should be unchanged
         assert_eq!(config.core.scratchpad, original_scratchpad);
     }
 
     #[test]
-    fn test_config_source_parse_non_core_with_equals_is_file() {
-        // Non-core.* prefix with '=' should be treated as file path per spec
+    fn test_apply_config_overrides_any_field() {
+        let sources = [
+            ConfigSource::parse("event_loop.max_iterations=5"),
+            ConfigSource::parse("features.preflight.enabled=true"),
+            ConfigSource::parse("cli.backend=kiro"),
+        ];
+        let config = load_overrides_only(&sources);
+        assert_eq!(config.event_loop.max_iterations, 5);
+        assert!(config.features.preflight.enabled);
+        assert_eq!(config.cli.backend, "kiro");
+    }
+
+    #[test]
+    fn test_config_source_parse_dotted_override() {
         let source = ConfigSource::parse("event_loop.max_iterations=5");
         match source {
-            ConfigSource::File(path) => {
-                assert_eq!(
-                    path,
-                    std::path::PathBuf::from("event_loop.max_iterations=5")
-                )
+            ConfigSource::Override { key, value } => {
+                assert_eq!(key, "event_loop.max_iterations");
+                assert_eq!(value, "5");
             }
-            _ => panic!("Expected File variant, not Override"),
+            _ => panic!("Expected Override variant"),
         }
+
+        let source = ConfigSource::parse("events.\"build.done\".description=Built");
+        assert!(matches!(source, ConfigSource::Override { .. }));
+    }
+
+    #[test]
+    fn test_config_source_parse_existing_file_with_equals_is_file() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let _cwd = CwdGuard::set(temp_dir.path());
+        std::fs::write("preset.v2=final.yml", "").unwrap();
+
+        let source = ConfigSource::parse("preset.v2=final.yml");
+        assert!(matches!(source, ConfigSource::File(_)));
     }
 
     #[test]
@@ -2587,15 +2573,23 @@ core:
         )
         .unwrap();
 
-        let mut config = RalphConfig::from_file(&config_path).unwrap();
-        assert_eq!(config.core.scratchpad, ".agent/scratchpad.md");
+        assert_eq!(
+            RalphConfig::from_file(&config_path)
+                .unwrap()
+                .core
+                .scratchpad,
+            ".agent/scratchpad.md"
+        );
 
         // Apply override
-        let overrides = vec![ConfigSource::Override {
-            key: "core.scratchpad".to_string(),
-            value: ".custom/scratch.md".to_string(),
-        }];
-        apply_config_overrides(&mut config, &overrides).unwrap();
+        let sources = vec![
+            ConfigSource::File(config_path),
+            ConfigSource::Override {
+                key: "core.scratchpad".to_string(),
+                value: ".custom/scratch.md".to_string(),
+            },
+        ];
+        let config = load_overrides_only(&sources);
 
         assert_eq!(config.core.scratchpad, ".custom/scratch.md");
         assert_eq!(config.core.specs_dir, "./specs/"); // Unchanged
@@ -2904,6 +2898,7 @@ core:
             verbose: false,
             quiet: false,
             record_session: None,
+            replay: None,
             custom_args: Vec::new(),
         }
     }
@@ -0,0 +1,384 @@
+//! CLI commands for the `ralph pipeline` namespace.
+//!
+//! Runs a chain of loops described by a pipeline file, where each stage
+//! picks up the previous stage's handoff, summary, and open tasks.
+//!
+//! Subcommands:
+//! - `run`: Start a new run of a pipeline file
+//! - `status`: Show the stages of a run (latest by default)
+//! - `resume`: Continue an interrupted or halted run
+//!
+//! Stages run in place, one `ralph run` child process at a time, so a stage
+//! refuses to start while another loop holds the workspace's loop lock.
+//! Run state lives in `.ralph/pipelines/<run-id>/`.
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{Context, Result};
+use chrono::Local;
+use clap::{Parser, Subcommand};
+use ralph_core::{
+    LoopLock, PipelineDefinition, PipelineRun, PipelineState, PipelineStatus, StageRecord,
+    TerminationReason,
+};
+
+use crate::ConfigSource;
+use crate::display::colors;
+
+/// Run multi-stage loop pipelines.
+#[derive(Parser, Debug)]
+pub struct PipelineArgs {
+    #[command(subcommand)]
+    pub command: PipelineCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PipelineCommands {
+    /// Start a new run of a pipeline file
+    Run(RunArgs),
+
+    /// Show the stages of a pipeline run
+    Status(StatusArgs),
+
+    /// Continue an interrupted or halted pipeline run
+    Resume(ResumeArgs),
+}
+
+#[derive(Parser, Debug)]
+pub struct RunArgs {
+    /// Pipeline file (YAML)
+    pub file: PathBuf,
+
+    /// Overall goal, replacing the pipeline file's `prompt`
+    #[arg(short, long)]
+    pub prompt: Option<String>,
+}
+
+#[derive(Parser, Debug, Default)]
+pub struct StatusArgs {
+    /// Run ID (default: the most recent run)
+    pub run_id: Option<String>,
+
+    /// Output JSON instead of a table
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct ResumeArgs {
+    /// Run ID (default: the most recent run)
+    pub run_id: Option<String>,
+
+    /// Re-run from this stage instead of where the run stopped
+    #[arg(long, value_name = "STAGE")]
+    pub from: Option<String>,
+}
+
+/// Execute a pipeline command.
+pub async fn execute(
+    config_sources: &[ConfigSource],
+    args: PipelineArgs,
+    use_colors: bool,
+) -> Result<()> {
+    let workspace_root = std::env::current_dir()?;
+    match args.command {
+        PipelineCommands::Run(run_args) => {
+            let mut definition = PipelineDefinition::load(&run_args.file)
+                .with_context(|| format!("Failed to load pipeline {}", run_args.file.display()))?;
+            if let Some(prompt) = run_args.prompt {
+                definition.prompt = Some(prompt);
+            }
+            let run = PipelineRun::create(&workspace_root, definition)?;
+            println!("Pipeline run {}", run.id());
+            let runner = StageRunner::new(workspace_root, config_sources, use_colors);
+            runner.drive(&run, None).await
+        }
+        PipelineCommands::Status(status_args) => {
+            let run = resolve_run(&workspace_root, status_args.run_id.as_deref())?;
+            let status = run.status()?;
+            if status_args.json {
+                println!("{}", serde_json::to_string_pretty(&status)?);
+            } else {
+                print_status(&workspace_root, &status, use_colors);
+            }
+            Ok(())
+        }
+        PipelineCommands::Resume(resume_args) => {
+            let run = resolve_run(&workspace_root, resume_args.run_id.as_deref())?;
+            let status = run.status()?;
+            let start_at = match (&resume_args.from, &status.state) {
+                (Some(stage), _) => status
+                    .stages
+                    .iter()
+                    .position(|record| &record.name == stage)
+                    .with_context(|| format!("Pipeline has no stage '{stage}'"))?,
+                (None, PipelineState::Pending { stage } | PipelineState::Halted { stage, .. }) => {
+                    *stage
+                }
+                (None, PipelineState::Completed { .. }) => {
+                    anyhow::bail!(
+                        "Pipeline run {} already completed. Use --from <stage> to re-run a stage.",
+                        run.id()
+                    );
+                }
+            };
+            println!("Resuming pipeline run {}", run.id());
+            let runner = StageRunner::new(workspace_root, config_sources, use_colors);
+            runner.drive(&run, Some(start_at)).await
+        }
+    }
+}
+
+/// Opens the given run, or the most recent one.
+fn resolve_run(workspace_root: &Path, run_id: Option<&str>) -> Result<PipelineRun> {
+    match run_id {
+        Some(id) => Ok(PipelineRun::open(workspace_root, id)?),
+        None => PipelineRun::latest(workspace_root)?
+            .context("No pipeline runs found. Start one with `ralph pipeline run <file>`."),
+    }
+}
+
+fn print_status(workspace_root: &Path, status: &PipelineStatus, use_colors: bool) {
+    let (bold, dim, reset) = if use_colors {
+        (colors::BOLD, colors::DIM, colors::RESET)
+    } else {
+        ("", "", "")
+    };
+    println!("{bold}Pipeline run {}{reset}", status.id);
+    if let Some(started_at) = status.started_at {
+        println!(
+            "{dim}Started {}{reset}",
+            started_at.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S")
+        );
+    }
+    println!();
+    println!(
+        "{bold}{:<4}{:<20} {:<22} LOOP{reset}",
+        "#", "STAGE", "RESULT"
+    );
+    let locked = LoopLock::is_locked(workspace_root).unwrap_or(false);
+    for (index, record) in status.stages.iter().enumerate() {
+        println!(
+            "{:<4}{:<20} {:<22} {dim}{}{reset}",
+            index + 1,
+            record.name,
+            stage_result(record, locked),
+            record.loop_id.as_deref().unwrap_or("-"),
+        );
+    }
+    println!();
+    println!("{}", describe_state(status));
+}
+
+/// The RESULT column: the outcome, or where an unfinished stage stands.
+fn stage_result(record: &StageRecord, locked: bool) -> &str {
+    match &record.outcome {
+        Some(outcome) => outcome,
+        None if record.is_unfinished() && locked => "running",
+        None if record.is_unfinished() => "interrupted",
+        None => "pending",
+    }
+}
+
+/// One-line description of where the run stands.
+fn describe_state(status: &PipelineStatus) -> String {
+    match &status.state {
+        PipelineState::Pending { stage } => format!(
+            "Next stage: {}. Continue with `ralph pipeline resume {}`.",
+            status.stages[*stage].name, status.id
+        ),
+        PipelineState::Halted { stage, outcome } => {
+            let halted = &status.stages[*stage].name;
+            match status.stages.get(stage + 1) {
+                Some(next) if TerminationReason::from_name(outcome).is_some() => format!(
+                    "Halted: stage '{}' does not accept '{halted}' ending with {outcome}. \
+                     Re-run it with `ralph pipeline resume {}`.",
+                    next.name, status.id
+                ),
+                _ => format!(
+                    "Halted: stage '{halted}' failed ({outcome}). \
+                     Re-run it with `ralph pipeline resume {}`.",
+                    status.id
+                ),
+            }
+        }
+        PipelineState::Completed { outcome } => {
+            format!("Completed: the last stage ended with {outcome}.")
+        }
+    }
+}
+
+/// Runs pipeline stages as `ralph run` child processes in the workspace.
+struct StageRunner {
+    workspace_root: PathBuf,
+    /// `-c` sources for stages that name neither a preset nor a config.
+    default_sources: Vec<OsString>,
+    /// `-c key=value` overrides, applied to every stage.
+    overrides: Vec<OsString>,
+    use_colors: bool,
+}
+
+impl StageRunner {
+    fn new(workspace_root: PathBuf, config_sources: &[ConfigSource], use_colors: bool) -> Self {
+        Self {
+            workspace_root,
+            default_sources: config_sources.iter().map(ConfigSource::to_arg).collect(),
+            overrides: config_sources
+                .iter()
+                .filter(|source| matches!(source, ConfigSource::Override { .. }))
+                .map(ConfigSource::to_arg)
+                .collect(),
+            use_colors,
+        }
+    }
+
+    /// Runs stages until the pipeline completes or halts, starting with
+    /// `start_at` if given and otherwise with the run's next pending stage.
+    async fn drive(&self, run: &PipelineRun, mut start_at: Option<usize>) -> Result<()> {
+        loop {
+            let status = run.status()?;
+            let index = match (start_at.take(), &status.state) {
+                (Some(index), _) | (None, &PipelineState::Pending { stage: index }) => index,
+                (None, PipelineState::Halted { .. }) => {
+                    anyhow::bail!("{}", describe_state(&status));
+                }
+                (None, PipelineState::Completed { outcome }) => {
+                    println!("{}", describe_state(&status));
+                    if TerminationReason::from_name(outcome).is_some_and(|r| r.is_success()) {
+                        return Ok(());
+                    }
+                    anyhow::bail!("Pipeline run {} did not complete", run.id());
+                }
+            };
+            self.run_stage(run, index, &status).await?;
+        }
+    }
+
+    async fn run_stage(
+        &self,
+        run: &PipelineRun,
+        index: usize,
+        status: &PipelineStatus,
+    ) -> Result<()> {
+        let stages = &run.definition().stages;
+        let stage = &stages[index];
+        if LoopLock::is_locked(&self.workspace_root).unwrap_or(false) {
+            anyhow::bail!(
+                "Another loop is running in this workspace. Stop it, then continue with `ralph pipeline resume {}`.",
+                run.id()
+            );
+        }
+        let prompt = run
+            .stage_prompt(index, &self.workspace_root, status)
+            .with_context(|| format!("Failed to build the prompt for stage '{}'", stage.name))?;
+
+        println!(
+            "[{}] Stage {}/{} '{}' starting",
+            timestamp(),
+            index + 1,
+            stages.len(),
+            stage.name
+        );
+        run.record_stage_started(&stage.name)?;
+        let since = SystemTime::now();
+        let result = crate::loop_runner::start_loop_process(
+            prompt,
+            self.workspace_root.clone(),
+            &self.stage_sources(&stage.config_sources()),
+        )
+        .await;
+        if let Err(e) = run.capture_artifacts(&stage.name, &self.workspace_root, since) {
+            tracing::warn!("Failed to save stage artifacts: {}", e);
+        }
+
+        let (loop_id, outcome) = match result {
+            Ok(outcome) => (outcome.loop_id, outcome.termination),
+            Err(e) => (None, format!("error: {e:#}")),
+        };
+        run.record_stage_finished(&stage.name, loop_id.as_deref(), &outcome)?;
+
+        let success = TerminationReason::from_name(&outcome).is_some_and(|r| r.is_success());
+        let (color, reset) = match (self.use_colors, success) {
+            (false, _) => ("", ""),
+            (true, true) => (colors::GREEN, colors::RESET),
+            (true, false) => (colors::YELLOW, colors::RESET),
+        };
+        println!(
+            "[{}] {color}Stage '{}' finished: {}{reset}",
+            timestamp(),
+            stage.name,
+            outcome
+        );
+        Ok(())
+    }
+
+    /// `-c` arguments for a stage: its own preset and config plus the
+    /// command-line overrides, or the command-line sources if it has none.
+    fn stage_sources(&self, stage_sources: &[String]) -> Vec<OsString> {
+        if stage_sources.is_empty() {
+            return self.default_sources.clone();
+        }
+        stage_sources
+            .iter()
+            .map(OsString::from)
+            .chain(self.overrides.iter().cloned())
+            .collect()
+    }
+}
+
+fn timestamp() -> String {
+    Local::now().format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stage_sources_layer_overrides_over_stage_config() {
+        let runner = StageRunner::new(
+            PathBuf::from("."),
+            &[
+                ConfigSource::File(PathBuf::from("ralph.yml")),
+                ConfigSource::Override {
+                    key: "event_loop.max_iterations".to_string(),
+                    value: "5".to_string(),
+                },
+            ],
+            false,
+        );
+        assert_eq!(
+            runner.stage_sources(&[]),
+            vec![
+                OsString::from("ralph.yml"),
+                OsString::from("event_loop.max_iterations=5"),
+            ]
+        );
+        assert_eq!(
+            runner.stage_sources(&["builtin:feature".to_string()]),
+            vec![
+                OsString::from("builtin:feature"),
+                OsString::from("event_loop.max_iterations=5"),
+            ]
+        );
+    }
+
+    #[test]
+    fn stage_result_distinguishes_unfinished_stages() {
+        let mut record = StageRecord {
+            name: "build".to_string(),
+            started_at: None,
+            finished_at: None,
+            loop_id: None,
+            outcome: None,
+        };
+        assert_eq!(stage_result(&record, false), "pending");
+        record.started_at = Some(chrono::Utc::now());
+        assert_eq!(stage_result(&record, true), "running");
+        assert_eq!(stage_result(&record, false), "interrupted");
+        record.outcome = Some("MaxIterations".to_string());
+        assert_eq!(stage_result(&record, false), "MaxIterations");
+    }
+}
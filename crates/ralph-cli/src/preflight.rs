@@ -1,12 +1,10 @@
 //! Preflight command for validating configuration and environment.
 
-use anyhow::{Context, Result};
+use anyhow::Result;
 use clap::{ArgAction, Parser, ValueEnum};
 use ralph_core::{CheckResult, CheckStatus, PreflightReport, PreflightRunner, RalphConfig};
-use std::path::PathBuf;
-use tracing::{info, warn};
 
-use crate::{ConfigSource, presets};
+use crate::ConfigSource;
 
 #[derive(Parser, Debug)]
 pub struct PreflightArgs {
@@ -195,80 +193,7 @@ fn print_check_line(check: &CheckResult, name_width: usize, use_colors: bool) {
 pub(crate) async fn load_config_for_preflight(
     config_sources: &[ConfigSource],
 ) -> Result<RalphConfig> {
-    let (primary_sources, overrides): (Vec<_>, Vec<_>) = config_sources
-        .iter()
-        .partition(|source| !matches!(source, ConfigSource::Override { .. }));
-
-    if primary_sources.len() > 1 {
-        warn!("Multiple config sources specified, using first one. Others ignored.");
-    }
-
-    let mut config = if let Some(source) = primary_sources.first() {
-        match source {
-            ConfigSource::File(path) => {
-                if path.exists() {
-                    RalphConfig::from_file(path)
-                        .with_context(|| format!("Failed to load config from {:?}", path))?
-                } else {
-                    warn!("Config file {:?} not found, using defaults", path);
-                    RalphConfig::default()
-                }
-            }
-            ConfigSource::Builtin(name) => {
-                let preset = presets::get_preset(name).ok_or_else(|| {
-                    let available = presets::preset_names().join(", ");
-                    anyhow::anyhow!(
-                        "Unknown preset '{}'. Run `ralph run --list-presets` to see available presets.\n\nAvailable: {}",
-                        name,
-                        available
-                    )
-                })?;
-                RalphConfig::parse_yaml(preset.content)
-                    .with_context(|| format!("Failed to parse builtin preset '{}'.", name))?
-            }
-            ConfigSource::Remote(url) => {
-                info!("Fetching config from {}", url);
-                let response = reqwest::get(url)
-                    .await
-                    .with_context(|| format!("Failed to fetch config from {}", url))?;
-
-                if !response.status().is_success() {
-                    anyhow::bail!(
-                        "Failed to fetch config from {}: HTTP {}",
-                        url,
-                        response.status()
-                    );
-                }
-
-                let content = response
-                    .text()
-                    .await
-                    .with_context(|| format!("Failed to read config content from {}", url))?;
-
-                RalphConfig::parse_yaml(&content)
-                    .with_context(|| format!("Failed to parse config from {}", url))?
-            }
-            ConfigSource::Override { .. } => unreachable!("Partitioned out overrides"),
-        }
-    } else {
-        let default_path = PathBuf::from("ralph.yml");
-        if default_path.exists() {
-            RalphConfig::from_file(&default_path)
-                .with_context(|| "Failed to load config from ralph.yml")?
-        } else {
-            warn!("Config file ralph.yml not found, using defaults");
-            RalphConfig::default()
-        }
-    };
-
-    config.normalize();
-    config.core.workspace_root =
-        std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
-
-    let override_sources: Vec<_> = overrides.into_iter().cloned().collect();
-    crate::apply_config_overrides(&mut config, &override_sources)?;
-
-    Ok(config)
+    crate::config_cli::load_config(config_sources).await
 }
 
 pub(crate) fn config_source_label(config_sources: &[ConfigSource]) -> String {
@@ -312,8 +237,9 @@ mod tests {
 
     #[test]
     fn config_source_label_handles_sources() {
-        let file_label =
-            config_source_label(&[ConfigSource::File(PathBuf::from("/tmp/ralph.yml"))]);
+        let file_label = config_source_label(&[ConfigSource::File(std::path::PathBuf::from(
+            "/tmp/ralph.yml",
+        ))]);
         assert_eq!(file_label, "/tmp/ralph.yml");
 
         let builtin_label = config_source_label(&[ConfigSource::Builtin("starter".to_string())]);
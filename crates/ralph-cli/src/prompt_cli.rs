@@ -0,0 +1,423 @@
+//! CLI commands for the `ralph prompt` namespace.
+//!
+//! Subcommands:
+//! - `preview`: Render the exact prompt an iteration would send, annotated per
+//!   section with approximate token counts and any `prompt_budget` trimming
+
+use crate::ConfigSource;
+use crate::display::colors;
+use anyhow::{Context, Result, bail};
+use clap::{Parser, Subcommand};
+use ralph_core::{
+    DiagnosticsCollector, EventLoop, HatRegistry, LoopContext, PromptAssembly, RalphConfig,
+};
+use ralph_proto::{Event, HatId};
+use serde::Deserialize;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Inspect the prompts Ralph sends to the agent.
+#[derive(Parser, Debug)]
+pub struct PromptArgs {
+    #[command(subcommand)]
+    pub command: PromptCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PromptCommands {
+    /// Render the prompt for an iteration with per-section token counts
+    Preview(PreviewArgs),
+}
+
+/// Arguments for `ralph prompt preview`.
+#[derive(Parser, Debug)]
+pub struct PreviewArgs {
+    /// Render the prompt with this hat active, as if one of its triggers fired
+    #[arg(long)]
+    pub hat: Option<String>,
+
+    /// YAML or JSON checkpoint with the objective and pending events to render
+    #[arg(long, value_name = "CHECKPOINT")]
+    pub iteration_state: Option<PathBuf>,
+
+    /// Inline prompt text (overrides config)
+    #[arg(short = 'p', long = "prompt", conflicts_with = "prompt_file")]
+    pub prompt_text: Option<String>,
+
+    /// Prompt file path (overrides config)
+    #[arg(short = 'P', long = "prompt-file")]
+    pub prompt_file: Option<PathBuf>,
+
+    /// Only print the per-section token summary
+    #[arg(long)]
+    pub summary: bool,
+}
+
+/// Iteration state loaded from `--iteration-state`.
+///
+/// ```yaml
+/// objective: Add rate limiting to the API   # defaults to the configured prompt
+/// events:
+///   - topic: build.done
+///     payload: "tests: pass, lint: pass"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct IterationCheckpoint {
+    objective: Option<String>,
+    events: Vec<CheckpointEvent>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckpointEvent {
+    topic: String,
+    #[serde(default)]
+    payload: String,
+}
+
+/// Execute a prompt command.
+pub async fn execute(
+    config_sources: &[ConfigSource],
+    args: PromptArgs,
+    use_colors: bool,
+) -> Result<()> {
+    match args.command {
+        PromptCommands::Preview(args) => preview(config_sources, args, use_colors).await,
+    }
+}
+
+async fn preview(
+    config_sources: &[ConfigSource],
+    args: PreviewArgs,
+    use_colors: bool,
+) -> Result<()> {
+    let mut config = crate::config_cli::load_config(config_sources).await?;
+    if let Some(text) = args.prompt_text {
+        config.event_loop.prompt = Some(text);
+        config.event_loop.prompt_file = String::new();
+    } else if let Some(path) = args.prompt_file {
+        config.event_loop.prompt_file = path.to_string_lossy().to_string();
+        config.event_loop.prompt = None;
+    }
+
+    let checkpoint = match &args.iteration_state {
+        Some(path) => load_checkpoint(path)?,
+        None => IterationCheckpoint::default(),
+    };
+    let objective = match checkpoint.objective {
+        Some(objective) => objective,
+        None => crate::loop_runner::resolve_prompt_content(&config.event_loop)?,
+    };
+
+    let assembly = build_preview(config, &objective, checkpoint.events, args.hat.as_deref())?;
+
+    let mut stdout = std::io::stdout();
+    if !args.summary {
+        write_sections(&mut stdout, &assembly, use_colors)?;
+    }
+    write_summary(&mut stdout, &assembly, use_colors)?;
+    Ok(())
+}
+
+fn load_checkpoint(path: &Path) -> Result<IterationCheckpoint> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read iteration state {}", path.display()))?;
+    // YAML is a superset of JSON, so this accepts both formats.
+    serde_yaml::from_str(&content)
+        .with_context(|| format!("Failed to parse iteration state {}", path.display()))
+}
+
+/// Builds the prompt for the requested state.
+///
+/// The loop reads the workspace's scratchpad, tasks and memories like a real
+/// iteration would, but never writes loop state, so previewing next to a
+/// running loop leaves its `.ralph/agent/` files alone.
+fn build_preview(
+    config: RalphConfig,
+    objective: &str,
+    events: Vec<CheckpointEvent>,
+    hat: Option<&str>,
+) -> Result<PromptAssembly> {
+    // The event loop expects every hat's `when:` to parse
+    HatRegistry::try_from_config(&config)?;
+    let context = LoopContext::primary(config.core.workspace_root.clone());
+    let mut event_loop =
+        EventLoop::with_context_and_diagnostics(config, context, DiagnosticsCollector::disabled());
+    event_loop.disable_state_persistence();
+
+    let hat = hat.filter(|id| *id != "ralph");
+    if events.is_empty() && hat.is_none() {
+        // First iteration: the start event carries the objective.
+        event_loop.initialize(objective);
+    } else {
+        event_loop.set_objective(objective);
+        for event in events {
+            event_loop
+                .bus()
+                .publish(Event::new(event.topic, event.payload));
+        }
+    }
+
+    if let Some(hat_id) = hat {
+        let trigger = hat_trigger(&event_loop, hat_id)?;
+        event_loop.bus().publish(Event::new(trigger, objective));
+    }
+
+    event_loop
+        .build_prompt_assembly(&HatId::new("ralph"))
+        .context("No prompt to build for this state")
+}
+
+/// Returns the first concrete trigger of a configured hat.
+fn hat_trigger(event_loop: &EventLoop, hat_id: &str) -> Result<String> {
+    let registry = event_loop.registry();
+    if registry.is_empty() {
+        bail!("No hats configured; --hat requires a hat-based configuration");
+    }
+    let Some(hat) = registry.get(&HatId::new(hat_id)) else {
+        let mut ids: Vec<&str> = registry.ids().map(HatId::as_str).collect();
+        ids.sort_unstable();
+        bail!(
+            "Unknown hat '{}'. Configured hats: {}",
+            hat_id,
+            ids.join(", ")
+        );
+    };
+    hat.subscriptions
+        .iter()
+        .map(|topic| topic.as_str())
+        .find(|topic| !topic.contains('*'))
+        .map(str::to_string)
+        .with_context(|| format!("Hat '{hat_id}' has no concrete trigger to activate it"))
+}
+
+fn write_sections<W: Write>(
+    writer: &mut W,
+    assembly: &PromptAssembly,
+    use_colors: bool,
+) -> Result<()> {
+    for section in assembly.sections() {
+        let annotation = if section.is_dropped() {
+            format!(
+                "dropped by prompt_budget, was ~{}",
+                section.original_tokens()
+            )
+        } else if section.is_trimmed() {
+            format!(
+                "~{} tokens, trimmed from ~{}",
+                section.tokens(),
+                section.original_tokens()
+            )
+        } else {
+            format!("~{} tokens", section.tokens())
+        };
+        let header = format!("─── {} ({}) ───", section.name(), annotation);
+        if use_colors {
+            writeln!(writer, "{}{}{}", colors::CYAN, header, colors::RESET)?;
+        } else {
+            writeln!(writer, "{header}")?;
+        }
+
+        let text = section.text();
+        if !text.is_empty() {
+            writeln!(writer, "{}", text.trim_end_matches('\n'))?;
+        }
+        writeln!(writer)?;
+    }
+    Ok(())
+}
+
+fn write_summary<W: Write>(
+    writer: &mut W,
+    assembly: &PromptAssembly,
+    use_colors: bool,
+) -> Result<()> {
+    let width = assembly
+        .sections()
+        .iter()
+        .map(|s| s.name().len())
+        .max()
+        .unwrap_or(0)
+        .max("Total".len());
+
+    writeln!(writer, "Token estimate (~4 chars per token):")?;
+    for section in assembly.sections() {
+        let note = if section.is_dropped() {
+            format!("  (dropped, was {})", section.original_tokens())
+        } else if section.is_trimmed() {
+            format!("  (trimmed from {})", section.original_tokens())
+        } else {
+            String::new()
+        };
+        writeln!(
+            writer,
+            "  {:<width$}  {:>6}{}",
+            section.name(),
+            section.tokens(),
+            note
+        )?;
+    }
+
+    let total = assembly.total_tokens();
+    let budget = match assembly.budget() {
+        0 => "no prompt_budget".to_string(),
+        budget if total > budget => {
+            let over = format!("over prompt_budget of {budget}");
+            if use_colors {
+                format!("{}{}{}", colors::YELLOW, over, colors::RESET)
+            } else {
+                over
+            }
+        }
+        budget => format!("prompt_budget {budget}"),
+    };
+    writeln!(writer, "  {:<width$}  {:>6}  ({})", "Total", total, budget)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ralph_core::prompt_assembly::{SECTION_MEMORIES, SECTION_PENDING_EVENTS};
+
+    fn hat_config() -> RalphConfig {
+        serde_yaml::from_str(
+            r#"
+hats:
+  builder:
+    name: "Builder"
+    triggers: ["build.task"]
+    publishes: ["build.done"]
+    instructions: "BUILDER INSTRUCTIONS"
+  reviewer:
+    name: "Reviewer"
+    triggers: ["build.done"]
+    publishes: ["review.done"]
+    instructions: "REVIEWER INSTRUCTIONS"
+"#,
+        )
+        .unwrap()
+    }
+
+    fn render(assembly: &PromptAssembly, summary_only: bool) -> String {
+        let mut out = Vec::new();
+        if !summary_only {
+            write_sections(&mut out, assembly, false).unwrap();
+        }
+        write_summary(&mut out, assembly, false).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn preview_defaults_to_first_iteration() {
+        let assembly = build_preview(hat_config(), "Ship it", Vec::new(), None).unwrap();
+        let prompt = assembly.render();
+
+        assert!(prompt.contains("Ship it"));
+        assert!(prompt.contains("task.start"));
+        assert!(!prompt.contains("BUILDER INSTRUCTIONS"));
+    }
+
+    #[test]
+    fn preview_with_hat_includes_its_instructions() {
+        let assembly =
+            build_preview(hat_config(), "Ship it", Vec::new(), Some("reviewer")).unwrap();
+        let prompt = assembly.render();
+
+        assert!(prompt.contains("REVIEWER INSTRUCTIONS"));
+        assert!(!prompt.contains("BUILDER INSTRUCTIONS"));
+        assert!(!prompt.contains("task.start"));
+    }
+
+    #[test]
+    fn preview_replays_checkpoint_events() {
+        let checkpoint: IterationCheckpoint = serde_yaml::from_str(
+            r#"{"events": [{"topic": "build.task", "payload": "Add /health endpoint"}]}"#,
+        )
+        .unwrap();
+        let assembly = build_preview(hat_config(), "Ship it", checkpoint.events, None).unwrap();
+
+        let events = assembly.section(SECTION_PENDING_EVENTS).unwrap().text();
+        assert!(events.contains("Add /health endpoint"));
+        assert!(assembly.render().contains("BUILDER INSTRUCTIONS"));
+    }
+
+    #[test]
+    fn preview_leaves_loop_state_untouched() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let agent_dir = temp_dir.path().join(".ralph/agent");
+        std::fs::create_dir_all(&agent_dir).unwrap();
+        std::fs::write(agent_dir.join("timers.json"), "running loop timers").unwrap();
+        std::fs::write(agent_dir.join("approvals.json"), "running loop approvals").unwrap();
+
+        let mut config: RalphConfig = serde_yaml::from_str(
+            r"
+event_loop:
+  watchdogs:
+    - expect: build.done
+      within: 20m
+      else: build.timeout
+events:
+  deploy.*:
+    requires_approval: true
+",
+        )
+        .unwrap();
+        config.core.workspace_root = temp_dir.path().to_path_buf();
+        let snapshot = |dir: &Path| -> Vec<(PathBuf, Vec<u8>)> {
+            let mut files: Vec<_> = std::fs::read_dir(dir)
+                .unwrap()
+                .map(|entry| {
+                    let path = entry.unwrap().path();
+                    let content = std::fs::read(&path).unwrap();
+                    (path, content)
+                })
+                .collect();
+            files.sort();
+            files
+        };
+        let before = snapshot(&agent_dir);
+
+        build_preview(config.clone(), "Ship it", Vec::new(), None).unwrap();
+        let events = vec![CheckpointEvent {
+            topic: "deploy.prod".to_string(),
+            payload: "v2".to_string(),
+        }];
+        build_preview(config, "Ship it", events, None).unwrap();
+
+        assert_eq!(snapshot(&agent_dir), before);
+    }
+
+    #[test]
+    fn preview_rejects_unknown_hat() {
+        let err = build_preview(hat_config(), "Ship it", Vec::new(), Some("tester")).unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Unknown hat 'tester'. Configured hats: builder, reviewer")
+        );
+
+        let err =
+            build_preview(RalphConfig::default(), "Ship it", Vec::new(), Some("x")).unwrap_err();
+        assert!(err.to_string().contains("No hats configured"));
+    }
+
+    #[test]
+    fn output_annotates_sections_and_budget() {
+        let mut assembly = PromptAssembly::new();
+        assembly.push(ralph_core::PromptSection::new(
+            SECTION_MEMORIES,
+            "memory\n".repeat(100),
+        ));
+        assembly.push(ralph_core::PromptSection::new("core", "## CORE\n"));
+        assembly.fit_to_budget(5);
+
+        let output = render(&assembly, false);
+        assert!(output.contains("─── memories (dropped by prompt_budget, was ~175) ───"));
+        assert!(output.contains("─── core (~2 tokens) ───\n## CORE\n"));
+        assert!(output.contains("  memories       0  (dropped, was 175)\n"));
+        assert!(output.contains("  Total          2  (prompt_budget 5)\n"));
+
+        let summary = render(&assembly, true);
+        assert!(!summary.contains("## CORE"));
+    }
+}
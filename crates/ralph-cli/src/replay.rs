@@ -0,0 +1,152 @@
+//! Deterministic loop replay for `ralph run --replay`.
+//!
+//! Feeds the agent output captured by `--record-session` back through the
+//! orchestration loop built from the current configuration. No backend is
+//! spawned, so a replay is fast and reproducible; any change in hat routing,
+//! accepted events or termination shows up as a divergence.
+
+use anyhow::{Context, Result, bail};
+use ralph_core::RalphConfig;
+use ralph_core::testing::{LoopRecording, LoopReplayReport, LoopReplayer};
+use std::io::{self, Write};
+use std::path::Path;
+
+use crate::display::colors;
+
+/// Replays a recorded session and prints a divergence report.
+///
+/// Returns an error when the replay diverged from the recording so the
+/// exit code can gate CI.
+pub fn run_replay(config: &RalphConfig, session: &Path, use_colors: bool) -> Result<()> {
+    let recording = LoopRecording::from_file(session)
+        .with_context(|| format!("Failed to load recorded session {}", session.display()))?;
+
+    let report = LoopReplayer::new(config.clone()).run(&recording);
+
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+    print_report(&mut handle, session, &recording, &report, use_colors)?;
+
+    if !report.is_faithful() {
+        bail!(
+            "Replay diverged from recording in {} place(s)",
+            report.divergences.len()
+        );
+    }
+    Ok(())
+}
+
+fn print_report<W: Write>(
+    writer: &mut W,
+    session: &Path,
+    recording: &LoopRecording,
+    report: &LoopReplayReport,
+    use_colors: bool,
+) -> Result<()> {
+    writeln!(writer, "Replaying {}", session.display())?;
+    writeln!(
+        writer,
+        "  Iterations: {} replayed / {} recorded",
+        report.iterations,
+        recording.iterations.len()
+    )?;
+    if !report.hats.is_empty() {
+        writeln!(writer, "  Hats: {}", report.hats.join(" → "))?;
+    }
+    let termination = report
+        .termination
+        .as_ref()
+        .map_or("none", |reason| reason.as_str());
+    writeln!(
+        writer,
+        "  Termination: {} (recorded: {})",
+        termination,
+        recording.termination.as_deref().unwrap_or("none")
+    )?;
+
+    for divergence in &report.divergences {
+        if use_colors {
+            writeln!(
+                writer,
+                "  [{}diverged{}] {}",
+                colors::RED,
+                colors::RESET,
+                divergence
+            )?;
+        } else {
+            writeln!(writer, "  [diverged] {}", divergence)?;
+        }
+    }
+
+    writeln!(writer)?;
+    if report.is_faithful() {
+        writeln!(writer, "Result: Faithful")?;
+    } else {
+        writeln!(writer, "Result: Diverged")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ralph_core::testing::Divergence;
+
+    fn recording() -> LoopRecording {
+        LoopRecording {
+            start_topic: "task.start".to_string(),
+            prompt: "do it".to_string(),
+            initial_published: vec!["task.start".to_string()],
+            iterations: Vec::new(),
+            decisions: Vec::new(),
+            termination: Some("completed".to_string()),
+            started_at: 0,
+            ended_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_print_report_faithful() {
+        let report = LoopReplayReport {
+            iterations: 0,
+            hats: Vec::new(),
+            termination: None,
+            divergences: Vec::new(),
+        };
+        let mut out = Vec::new();
+        print_report(
+            &mut out,
+            Path::new("session.jsonl"),
+            &recording(),
+            &report,
+            false,
+        )
+        .unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("Replaying session.jsonl"));
+        assert!(text.contains("Termination: none (recorded: completed)"));
+        assert!(text.contains("Result: Faithful"));
+    }
+
+    #[test]
+    fn test_print_report_lists_divergences() {
+        let report = LoopReplayReport {
+            iterations: 1,
+            hats: vec!["ralph".to_string()],
+            termination: None,
+            divergences: vec![Divergence::RecordingExhausted { iteration: 2 }],
+        };
+        let mut out = Vec::new();
+        print_report(
+            &mut out,
+            Path::new("session.jsonl"),
+            &recording(),
+            &report,
+            false,
+        )
+        .unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("[diverged]"));
+        assert!(text.contains("Result: Diverged"));
+    }
+}
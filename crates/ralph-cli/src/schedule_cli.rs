@@ -0,0 +1,441 @@
+//! CLI commands for the `ralph schedule` namespace.
+//!
+//! Runs recurring loops on cron-style schedules from the `schedules:` config
+//! section and `.ralph/schedules.yml`.
+//!
+//! Subcommands:
+//! - `list`: Show schedules with their next and last runs
+//! - `run`: Run one schedule now
+//! - `daemon`: Run schedules as they come due, until interrupted
+//!
+//! Each run is a `ralph run` child process: it takes the loop lock when it is
+//! free and spawns into a worktree otherwise. Runs are recorded in
+//! `.ralph/schedule-history.jsonl` and reported through the configured robot
+//! service (Telegram), if any.
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+use clap::{Parser, Subcommand};
+use ralph_core::schedule::{last_runs, schedule_history};
+use ralph_core::{LoopHistory, RalphConfig, ScheduleEntry, Scheduler, Trigger, load_schedules};
+use ralph_proto::{LoopOutcome, RobotService};
+use serde::Serialize;
+
+use crate::ConfigSource;
+use crate::display::colors;
+
+/// The daemon re-checks the clock at least this often, so schedules stay
+/// accurate across suspend/resume and clock changes.
+const MAX_SLEEP: Duration = Duration::from_mins(1);
+
+/// Run loops on a schedule.
+#[derive(Parser, Debug)]
+pub struct ScheduleArgs {
+    #[command(subcommand)]
+    pub command: Option<ScheduleCommands>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ScheduleCommands {
+    /// List schedules with their next and last runs (default if no subcommand)
+    List(ListArgs),
+
+    /// Run a schedule once, now
+    Run(RunArgs),
+
+    /// Run schedules as they come due until interrupted
+    Daemon,
+}
+
+#[derive(Parser, Debug, Default)]
+pub struct ListArgs {
+    /// Output JSON instead of a table
+    #[arg(long)]
+    pub json: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct RunArgs {
+    /// Name of the schedule to run
+    pub name: String,
+}
+
+/// One row of `ralph schedule list`.
+#[derive(Debug, Serialize)]
+struct ScheduleRow {
+    name: String,
+    cron: String,
+    enabled: bool,
+    next_run: Option<String>,
+    last_run: Option<String>,
+    last_outcome: Option<String>,
+}
+
+/// Execute a schedule command.
+pub async fn execute(
+    config_sources: &[ConfigSource],
+    args: ScheduleArgs,
+    use_colors: bool,
+) -> Result<()> {
+    let config = crate::load_config_with_overrides(config_sources)?;
+    let workspace_root = std::env::current_dir()?;
+    let entries = load_schedules(&workspace_root, &config.schedules)?;
+
+    match args.command {
+        None => list_schedules(&workspace_root, entries, &ListArgs::default(), use_colors),
+        Some(ScheduleCommands::List(list_args)) => {
+            list_schedules(&workspace_root, entries, &list_args, use_colors)
+        }
+        Some(ScheduleCommands::Run(run_args)) => {
+            let entry = entries
+                .into_iter()
+                .find(|entry| entry.name == run_args.name)
+                .ok_or_else(|| ralph_core::ScheduleError::NotFound(run_args.name.clone()))?;
+            let runner = ScheduleRunner::new(&config, workspace_root, config_sources);
+            let success = runner.run(&entry, use_colors).await;
+            runner.stop();
+            if !success {
+                anyhow::bail!("Scheduled run '{}' did not complete", entry.name);
+            }
+            Ok(())
+        }
+        Some(ScheduleCommands::Daemon) => {
+            run_daemon(&config, workspace_root, config_sources, entries, use_colors).await
+        }
+    }
+}
+
+fn list_schedules(
+    workspace_root: &Path,
+    entries: Vec<ScheduleEntry>,
+    args: &ListArgs,
+    use_colors: bool,
+) -> Result<()> {
+    let runs = last_runs(&schedule_history(workspace_root))?;
+    let now = Local::now();
+    let rows: Vec<ScheduleRow> = entries
+        .iter()
+        .map(|entry| {
+            let next_run = entry
+                .enabled
+                .then(|| entry.cron_schedule().ok()?.next_after(&now))
+                .flatten()
+                .map(|next| format_next(Some(&next)));
+            let last = runs.iter().find(|run| run.schedule == entry.name);
+            ScheduleRow {
+                name: entry.name.clone(),
+                cron: entry.cron.clone(),
+                enabled: entry.enabled,
+                next_run,
+                last_run: last.map(|run| {
+                    run.started_at
+                        .with_timezone(&Local)
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string()
+                }),
+                last_outcome: last
+                    .map(|run| run.outcome.clone().unwrap_or_else(|| "running".to_string())),
+            }
+        })
+        .collect();
+
+    if args.json {
+        println!("{}", serde_json::to_string_pretty(&rows)?);
+        return Ok(());
+    }
+    if rows.is_empty() {
+        println!(
+            "No schedules found. Add entries under `schedules:` in ralph.yml or .ralph/schedules.yml."
+        );
+        return Ok(());
+    }
+
+    let (bold, dim, reset) = if use_colors {
+        (colors::BOLD, colors::DIM, colors::RESET)
+    } else {
+        ("", "", "")
+    };
+    println!(
+        "{bold}{:<20} {:<16} {:<17} {:<17} LAST RESULT{reset}",
+        "NAME", "CRON", "NEXT RUN", "LAST RUN"
+    );
+    for row in &rows {
+        let next = if row.enabled {
+            row.next_run.as_deref().unwrap_or("never")
+        } else {
+            "disabled"
+        };
+        println!(
+            "{:<20} {:<16} {:<17} {dim}{:<17} {}{reset}",
+            row.name,
+            row.cron,
+            next,
+            row.last_run.as_deref().unwrap_or("-"),
+            row.last_outcome.as_deref().unwrap_or("-"),
+        );
+    }
+    Ok(())
+}
+
+/// Runs the scheduler until Ctrl+C, starting each due entry in the background.
+async fn run_daemon(
+    config: &RalphConfig,
+    workspace_root: PathBuf,
+    config_sources: &[ConfigSource],
+    entries: Vec<ScheduleEntry>,
+    use_colors: bool,
+) -> Result<()> {
+    let mut scheduler = Scheduler::new(entries, &Local::now())?;
+    let runner = std::sync::Arc::new(ScheduleRunner::new(config, workspace_root, config_sources));
+
+    if use_colors {
+        println!("{}Ralph Scheduler{}", colors::BOLD, colors::RESET);
+    } else {
+        println!("Ralph Scheduler");
+    }
+    let mut scheduled = 0;
+    for (entry, next) in scheduler.upcoming() {
+        scheduled += 1;
+        println!(
+            "  {} ({}) next run: {}",
+            entry.name,
+            entry.cron,
+            format_next(next)
+        );
+    }
+    if scheduled == 0 {
+        anyhow::bail!(
+            "No enabled schedules. Add entries under `schedules:` in ralph.yml or .ralph/schedules.yml."
+        );
+    }
+
+    let (done_tx, mut done_rx) = tokio::sync::mpsc::unbounded_channel::<String>();
+    loop {
+        let now = Local::now();
+        for trigger in scheduler.poll(&now) {
+            match trigger {
+                Trigger::Run(entry) => {
+                    let runner = runner.clone();
+                    let done_tx = done_tx.clone();
+                    tokio::spawn(Box::pin(async move {
+                        runner.run(&entry, use_colors).await;
+                        let _ = done_tx.send(entry.name);
+                    }));
+                }
+                Trigger::Skip { name, reason } => runner.skip(&name, &reason),
+            }
+        }
+
+        let sleep = scheduler
+            .next_due()
+            .and_then(|next| (next - Local::now()).to_std().ok())
+            .unwrap_or(MAX_SLEEP)
+            .min(MAX_SLEEP);
+        tokio::select! {
+            Some(name) = done_rx.recv() => scheduler.finish(&name),
+            () = tokio::time::sleep(sleep) => {}
+            _ = tokio::signal::ctrl_c() => {
+                println!("Scheduler stopping");
+                break;
+            }
+        }
+    }
+
+    if let Ok(runner) = std::sync::Arc::try_unwrap(runner) {
+        runner.stop();
+    }
+    Ok(())
+}
+
+/// Starts scheduled runs, records them in the schedule history, and reports
+/// their results.
+struct ScheduleRunner {
+    workspace_root: PathBuf,
+    history: LoopHistory,
+    /// `-c` sources for entries that name neither a preset nor a config.
+    default_sources: Vec<OsString>,
+    robot: Option<Box<dyn RobotService>>,
+}
+
+impl ScheduleRunner {
+    fn new(config: &RalphConfig, workspace_root: PathBuf, config_sources: &[ConfigSource]) -> Self {
+        let robot = config
+            .robot
+            .enabled
+            .then(|| {
+                crate::loop_runner::telegram_service(
+                    config,
+                    workspace_root.clone(),
+                    "schedule".to_string(),
+                )
+            })
+            .flatten()
+            .map(|service| Box::new(service) as Box<dyn RobotService>);
+        Self {
+            history: schedule_history(&workspace_root),
+            default_sources: config_sources.iter().map(ConfigSource::to_arg).collect(),
+            workspace_root,
+            robot,
+        }
+    }
+
+    /// Runs the entry to completion. Returns true if the loop completed.
+    async fn run(&self, entry: &ScheduleEntry, use_colors: bool) -> bool {
+        println!("[{}] Starting scheduled run '{}'", timestamp(), entry.name);
+        if let Err(e) = self.history.record_scheduled_run_started(&entry.name) {
+            tracing::warn!("Failed to record scheduled run: {}", e);
+        }
+
+        let result = self.start(entry).await;
+        let (loop_id, outcome, success) = match &result {
+            Ok(outcome) => (
+                outcome.loop_id.clone(),
+                outcome.termination.clone(),
+                outcome.termination == "CompletionPromise",
+            ),
+            Err(e) => (None, format!("error: {e:#}"), false),
+        };
+        if let Err(e) = self.history.record_scheduled_run_finished(
+            &entry.name,
+            loop_id.as_deref(),
+            &outcome,
+            success,
+        ) {
+            tracing::warn!("Failed to record scheduled run: {}", e);
+        }
+
+        let (color, reset) = match (use_colors, success) {
+            (false, _) => ("", ""),
+            (true, true) => (colors::GREEN, colors::RESET),
+            (true, false) => (colors::RED, colors::RESET),
+        };
+        println!(
+            "[{}] {color}Scheduled run '{}' finished: {}{reset}",
+            timestamp(),
+            entry.name,
+            outcome
+        );
+        self.notify(&finished_message(&entry.name, &result));
+        success
+    }
+
+    async fn start(&self, entry: &ScheduleEntry) -> Result<LoopOutcome> {
+        let prompt = entry
+            .resolve_prompt(&self.workspace_root)
+            .with_context(|| format!("Failed to read the prompt for '{}'", entry.name))?;
+        let entry_sources = entry.config_sources();
+        let sources: Vec<OsString> = if entry_sources.is_empty() {
+            self.default_sources.clone()
+        } else {
+            entry_sources.into_iter().map(OsString::from).collect()
+        };
+        crate::loop_runner::start_loop_process(prompt, self.workspace_root.clone(), &sources).await
+    }
+
+    fn skip(&self, name: &str, reason: &str) {
+        println!(
+            "[{}] Skipping scheduled run '{}': {}",
+            timestamp(),
+            name,
+            reason
+        );
+        if let Err(e) = self.history.record_scheduled_run_skipped(name, reason) {
+            tracing::warn!("Failed to record skipped run: {}", e);
+        }
+        self.notify(&format!("Scheduled run **{name}** skipped: {reason}"));
+    }
+
+    fn notify(&self, message: &str) {
+        if let Some(robot) = &self.robot
+            && let Err(e) = robot.send_notification(message)
+        {
+            tracing::warn!("Failed to send schedule notification: {}", e);
+        }
+    }
+
+    fn stop(self) {
+        if let Some(robot) = self.robot {
+            robot.stop();
+        }
+    }
+}
+
+/// Describes how a scheduled run ended, for the robot service.
+fn finished_message(name: &str, result: &Result<LoopOutcome>) -> String {
+    match result {
+        Ok(outcome) => {
+            let mut message = format!(
+                "Scheduled run **{name}** finished: `{}`",
+                outcome.termination
+            );
+            if let Some(loop_id) = &outcome.loop_id {
+                message.push_str(&format!("\nLoop: `{loop_id}`"));
+            }
+            message
+        }
+        Err(e) => format!("Scheduled run **{name}** failed: {e:#}"),
+    }
+}
+
+fn timestamp() -> String {
+    Local::now().format("%Y-%m-%d %H:%M:%S").to_string()
+}
+
+/// Formats an optional next-run time for display.
+fn format_next(next: Option<&DateTime<Local>>) -> String {
+    next.map_or_else(
+        || "never".to_string(),
+        |next| next.format("%Y-%m-%d %H:%M").to_string(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finished_message_reports_outcome_and_loop() {
+        let outcome = LoopOutcome {
+            loop_id: Some("primary-1".to_string()),
+            termination: "CompletionPromise".to_string(),
+        };
+        let message = finished_message("audit", &Ok(outcome));
+        assert_eq!(
+            message,
+            "Scheduled run **audit** finished: `CompletionPromise`\nLoop: `primary-1`"
+        );
+
+        let message = finished_message("audit", &Err(anyhow::anyhow!("no backend")));
+        assert_eq!(message, "Scheduled run **audit** failed: no backend");
+    }
+
+    #[test]
+    fn runner_falls_back_to_daemon_config_sources() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let runner = ScheduleRunner::new(
+            &RalphConfig::default(),
+            dir.path().to_path_buf(),
+            &[
+                ConfigSource::File(PathBuf::from("ralph.yml")),
+                ConfigSource::Builtin("code-assist".to_string()),
+                ConfigSource::Override {
+                    key: "event_loop.max_iterations".to_string(),
+                    value: "5".to_string(),
+                },
+            ],
+        );
+        assert!(runner.robot.is_none());
+        assert_eq!(
+            runner.default_sources,
+            vec![
+                OsString::from("ralph.yml"),
+                OsString::from("builtin:code-assist"),
+                OsString::from("event_loop.max_iterations=5"),
+            ]
+        );
+    }
+}
@@ -2,11 +2,17 @@
 //!
 //! Provides subcommands for interacting with skills:
 //! - `load`: Load a skill by name and output its content
-//! - `list`: List available skills
+//! - `list`: List available skills and installed skill packs
+//! - `install`: Vendor a skill pack from a directory or git repository
+//! - `remove`: Remove an installed skill pack
+//! - `stats`: Summarize which skills were injected by triggers and loaded on demand
 
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand, ValueEnum};
-use ralph_core::{RalphConfig, SkillRegistry};
+use ralph_core::{
+    PackSource, RalphConfig, SkillLock, SkillRegistry, SkillSource, SkillUsageLog,
+    SkillUsageRecord, SkillUsageStats,
+};
 use serde::Serialize;
 use std::path::{Path, PathBuf};
 
@@ -40,6 +46,15 @@ pub enum SkillCommands {
 
     /// List available skills
     List(ListArgs),
+
+    /// Install a skill pack into .ralph/skills/ and record it in the lockfile
+    Install(InstallArgs),
+
+    /// Remove an installed skill pack
+    Remove(RemoveArgs),
+
+    /// Show how often skills were injected by triggers or loaded on demand
+    Stats(StatsArgs),
 }
 
 #[derive(Parser, Debug)]
@@ -56,6 +71,28 @@ pub struct ListArgs {
     pub format: OutputFormat,
 }
 
+/// Arguments for the `skill install` command.
+#[derive(Parser, Debug)]
+pub struct InstallArgs {
+    /// Pack directory or git URL (append `#<rev>` to pin a branch, tag or commit)
+    pub source: String,
+}
+
+/// Arguments for the `skill remove` command.
+#[derive(Parser, Debug)]
+pub struct RemoveArgs {
+    /// Name of the installed pack
+    pub name: String,
+}
+
+/// Arguments for the `skill stats` command.
+#[derive(Parser, Debug)]
+pub struct StatsArgs {
+    /// Output format (quiet prints skill names only)
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+    pub format: OutputFormat,
+}
+
 /// Execute a skill command.
 pub fn execute(args: SkillArgs) -> Result<()> {
     let root = resolve_root(args.root)?;
@@ -63,6 +100,9 @@ pub fn execute(args: SkillArgs) -> Result<()> {
     match args.command {
         SkillCommands::Load(load_args) => execute_load(&root, &load_args.name),
         SkillCommands::List(list_args) => execute_list(&root, list_args),
+        SkillCommands::Install(install_args) => execute_install(&root, &install_args.source),
+        SkillCommands::Remove(remove_args) => execute_remove(&root, &remove_args.name),
+        SkillCommands::Stats(stats_args) => execute_stats(&root, stats_args.format),
     }
 }
 
@@ -72,6 +112,12 @@ fn execute_load(root: &Path, name: &str) -> Result<()> {
     match registry.load_skill(name) {
         Some(content) => {
             print!("{content}");
+            // Telemetry must never break loading a skill.
+            if let Err(e) =
+                SkillUsageLog::for_workspace(root).append(&[SkillUsageRecord::loaded(name)])
+            {
+                tracing::debug!("Failed to record skill load: {}", e);
+            }
             Ok(())
         }
         None => {
@@ -123,9 +169,33 @@ fn execute_list(root: &Path, args: ListArgs) -> Result<()> {
                     name, source_truncated, description_truncated
                 );
             }
+
+            let lock = load_lock(root)?;
+            if !lock.packs.is_empty() {
+                println!();
+                println!("Installed packs:");
+                println!("{:<24} {:<12} {:<60}", "Pack", "Version", "Source");
+                println!("{}", "-".repeat(98));
+                for (name, pack) in &lock.packs {
+                    let source = match &pack.rev {
+                        Some(rev) => format!("{} @ {}", pack.source, &rev[..rev.len().min(12)]),
+                        None => pack.source.clone(),
+                    };
+                    println!(
+                        "{:<24} {:<12} {:<60}",
+                        crate::display::truncate(name, 24),
+                        crate::display::truncate(&pack.version, 12),
+                        source
+                    );
+                }
+            }
         }
         OutputFormat::Json => {
-            let items: Vec<SkillListItem> = skills.into_iter().map(SkillListItem::from).collect();
+            let lock = load_lock(root)?;
+            let items: Vec<SkillListItem> = skills
+                .into_iter()
+                .map(|skill| SkillListItem::new(skill, &lock))
+                .collect();
             println!("{}", serde_json::to_string_pretty(&items)?);
         }
         OutputFormat::Quiet => {
@@ -138,6 +208,134 @@ fn execute_list(root: &Path, args: ListArgs) -> Result<()> {
     Ok(())
 }
 
+fn execute_install(root: &Path, source: &str) -> Result<()> {
+    let source = match PackSource::parse(source) {
+        PackSource::Path(path) if path.is_relative() => {
+            let cwd = std::env::current_dir().context("failed to get current directory")?;
+            PackSource::Path(cwd.join(path))
+        }
+        source => source,
+    };
+
+    let (name, locked) =
+        ralph_core::skill_pack::install(&source, root).context("Failed to install skill pack")?;
+
+    println!(
+        "Installed {}@{} ({} skill(s): {})",
+        name,
+        locked.version,
+        locked.skills.len(),
+        locked.skills.join(", ")
+    );
+    if !locked.requires_tools.is_empty() {
+        println!(
+            "Requires tools: {} (checked by `ralph preflight`)",
+            locked.requires_tools.join(", ")
+        );
+    }
+    println!("Locked in {}", SkillLock::path(root).display());
+    Ok(())
+}
+
+fn execute_remove(root: &Path, name: &str) -> Result<()> {
+    if !ralph_core::skill_pack::uninstall(name, root).context("Failed to remove skill pack")? {
+        anyhow::bail!("Skill pack '{}' is not installed", name);
+    }
+    println!("Removed skill pack {name}");
+    Ok(())
+}
+
+fn execute_stats(root: &Path, format: OutputFormat) -> Result<()> {
+    let log = SkillUsageLog::for_workspace(root);
+    let records = log
+        .read()
+        .with_context(|| format!("Failed to read {}", log.path().display()))?;
+    let stats = SkillUsageLog::stats(&records);
+
+    match format {
+        OutputFormat::Table => {
+            if stats.is_empty() {
+                println!("No skill usage recorded yet");
+                return Ok(());
+            }
+
+            println!(
+                "{:<24} {:>8} {:>8} {:>14}  {:<40}",
+                "Skill", "Injected", "Loaded", "Loaded (inj.)", "Top trigger"
+            );
+            println!("{}", "-".repeat(98));
+            for stat in &stats {
+                let top_trigger = top_reason(stat).unwrap_or("-");
+                println!(
+                    "{:<24} {:>8} {:>8} {:>14}  {:<40}",
+                    crate::display::truncate(&stat.skill, 24),
+                    stat.injected,
+                    stat.loaded,
+                    stat.loaded_after_injection,
+                    crate::display::truncate(top_trigger, 40)
+                );
+            }
+
+            let untriggered: Vec<&str> = stats
+                .iter()
+                .filter(|stat| stat.injected == 0 && stat.loaded > 0)
+                .map(|stat| stat.skill.as_str())
+                .collect();
+            if !untriggered.is_empty() {
+                println!();
+                println!(
+                    "Loaded on demand but never injected (consider adding triggers): {}",
+                    untriggered.join(", ")
+                );
+            }
+        }
+        OutputFormat::Json => {
+            let items: Vec<SkillStatsItem> = stats.iter().map(SkillStatsItem::from).collect();
+            println!("{}", serde_json::to_string_pretty(&items)?);
+        }
+        OutputFormat::Quiet => {
+            for stat in &stats {
+                println!("{}", stat.skill);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Most frequent trigger reason for a skill (ties broken alphabetically).
+fn top_reason(stat: &SkillUsageStats) -> Option<&str> {
+    stat.reasons
+        .iter()
+        .max_by(|a, b| a.1.cmp(b.1).then_with(|| b.0.cmp(a.0)))
+        .map(|(reason, _)| reason.as_str())
+}
+
+#[derive(Serialize)]
+struct SkillStatsItem<'a> {
+    skill: &'a str,
+    injected: usize,
+    loaded: usize,
+    loaded_after_injection: usize,
+    reasons: &'a std::collections::BTreeMap<String, usize>,
+}
+
+impl<'a> From<&'a SkillUsageStats> for SkillStatsItem<'a> {
+    fn from(stat: &'a SkillUsageStats) -> Self {
+        Self {
+            skill: &stat.skill,
+            injected: stat.injected,
+            loaded: stat.loaded,
+            loaded_after_injection: stat.loaded_after_injection,
+            reasons: &stat.reasons,
+        }
+    }
+}
+
+fn load_lock(root: &Path) -> Result<SkillLock> {
+    SkillLock::load(root).context("Failed to read skill lockfile")
+}
+
 fn build_registry(root: &Path) -> Result<SkillRegistry> {
     let config = load_config(root);
     let active_backend = Some(config.cli.backend.as_str());
@@ -147,8 +345,9 @@ fn build_registry(root: &Path) -> Result<SkillRegistry> {
 
 fn format_source(skill: &ralph_core::SkillEntry) -> String {
     match &skill.source {
-        ralph_core::SkillSource::BuiltIn => "built-in".to_string(),
-        ralph_core::SkillSource::File(path) => path.display().to_string(),
+        SkillSource::BuiltIn => "built-in".to_string(),
+        SkillSource::File(path) => path.display().to_string(),
+        SkillSource::Pack { name, version, .. } => format!("pack {name}@{version}"),
     }
 }
 
@@ -158,18 +357,46 @@ struct SkillListItem {
     description: String,
     source: String,
     path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pack: Option<PackInfo>,
     hats: Vec<String>,
     backends: Vec<String>,
     tags: Vec<String>,
     auto_inject: bool,
+    requires_tools: Vec<String>,
+}
+
+/// Provenance of a skill that came from an installed pack.
+#[derive(Debug, Serialize)]
+struct PackInfo {
+    name: String,
+    version: String,
+    source: Option<String>,
+    rev: Option<String>,
 }
 
-impl From<&ralph_core::SkillEntry> for SkillListItem {
-    fn from(skill: &ralph_core::SkillEntry) -> Self {
-        let (source, path) = match &skill.source {
-            ralph_core::SkillSource::BuiltIn => ("built-in".to_string(), None),
-            ralph_core::SkillSource::File(path) => {
-                ("file".to_string(), Some(path.display().to_string()))
+impl SkillListItem {
+    fn new(skill: &ralph_core::SkillEntry, lock: &SkillLock) -> Self {
+        let (source, path, pack) = match &skill.source {
+            SkillSource::BuiltIn => ("built-in".to_string(), None, None),
+            SkillSource::File(path) => ("file".to_string(), Some(path.display().to_string()), None),
+            SkillSource::Pack {
+                name,
+                version,
+                path,
+            } => {
+                let locked = lock.packs.get(name);
+                let pack = PackInfo {
+                    name: name.clone(),
+                    version: version.clone(),
+                    source: locked.map(|p| p.source.clone()),
+                    rev: locked.and_then(|p| p.rev.clone()),
+                };
+                (
+                    "pack".to_string(),
+                    Some(path.display().to_string()),
+                    Some(pack),
+                )
             }
         };
 
@@ -178,10 +405,12 @@ impl From<&ralph_core::SkillEntry> for SkillListItem {
             description: skill.description.clone(),
             source,
             path,
+            pack,
             hats: skill.hats.clone(),
             backends: skill.backends.clone(),
             tags: skill.tags.clone(),
             auto_inject: skill.auto_inject,
+            requires_tools: skill.requires_tools.clone(),
         }
     }
 }
@@ -7,7 +7,8 @@
 //! 3. Spawn an interactive session with the backend
 
 use ralph_adapters::{CliBackend, CustomBackendError, NoBackendError, detect_backend_default};
-use ralph_core::RalphConfig;
+use ralph_core::{BackendDefinition, RalphConfig};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use thiserror::Error;
@@ -134,6 +135,7 @@ pub fn run_sop(config: SopRunConfig) -> Result<(), SopRunError> {
                 prompt_flag: None, // Prompt appended as last arg by default
                 output_format: ralph_adapters::OutputFormat::Text,
                 env_vars: vec![],
+                output_rules: None,
             }
         } else {
             // For custom backend from config, we need to load the configuration to get the command/args
@@ -159,7 +161,10 @@ pub fn run_sop(config: SopRunConfig) -> Result<(), SopRunError> {
     } else if config.agent_teams && is_claude {
         CliBackend::claude_interactive_teams()
     } else {
-        CliBackend::for_interactive_prompt(&backend_name)?
+        let definitions = load_config(config.config_path.as_ref())
+            .map(|config| config.backends)
+            .unwrap_or_default();
+        CliBackend::for_interactive_prompt_with(&backend_name, &definitions)?
     };
 
     // 4. Spawn the interactive session
@@ -178,16 +183,19 @@ fn resolve_backend(
     flag_override: Option<&str>,
     config_path: Option<&PathBuf>,
 ) -> Result<String, SopRunError> {
+    let file_config = load_config(config_path);
+
     // 1. CLI flag takes precedence
     if let Some(backend) = flag_override {
-        validate_backend_name(backend)?;
+        let definitions = file_config
+            .map(|config| config.backends)
+            .unwrap_or_default();
+        validate_backend_name(backend, &definitions)?;
         return Ok(backend.to_string());
     }
 
     // 2. Check config file
-    if let Some(path) = config_path
-        && path.exists()
-        && let Ok(config) = RalphConfig::from_file(path)
+    if let Some(config) = file_config
         && config.cli.backend != "auto"
     {
         return Ok(config.cli.backend);
@@ -197,11 +205,23 @@ fn resolve_backend(
     detect_backend_default().map_err(SopRunError::NoBackend)
 }
 
-/// Validates a backend name.
-fn validate_backend_name(name: &str) -> Result<(), SopRunError> {
+/// Loads the config file, if there is one and it parses.
+fn load_config(config_path: Option<&PathBuf>) -> Option<RalphConfig> {
+    config_path
+        .filter(|path| path.exists())
+        .and_then(|path| RalphConfig::from_file(path).ok())
+}
+
+/// Validates a backend name against the built-ins and the config's
+/// declared backends.
+fn validate_backend_name(
+    name: &str,
+    definitions: &HashMap<String, BackendDefinition>,
+) -> Result<(), SopRunError> {
     match name {
         "claude" | "kiro" | "gemini" | "codex" | "amp" | "copilot" | "opencode" | "pi"
         | "custom" => Ok(()),
+        _ if definitions.contains_key(name) => Ok(()),
         _ => Err(SopRunError::UnknownBackend(name.to_string())),
     }
 }
@@ -320,19 +340,19 @@ mod tests {
 
     #[test]
     fn test_validate_backend_name_valid() {
-        assert!(validate_backend_name("claude").is_ok());
-        assert!(validate_backend_name("kiro").is_ok());
-        assert!(validate_backend_name("gemini").is_ok());
-        assert!(validate_backend_name("codex").is_ok());
-        assert!(validate_backend_name("amp").is_ok());
-        assert!(validate_backend_name("copilot").is_ok());
-        assert!(validate_backend_name("opencode").is_ok());
-        assert!(validate_backend_name("custom").is_ok());
+        assert!(validate_backend_name("claude", &HashMap::new()).is_ok());
+        assert!(validate_backend_name("kiro", &HashMap::new()).is_ok());
+        assert!(validate_backend_name("gemini", &HashMap::new()).is_ok());
+        assert!(validate_backend_name("codex", &HashMap::new()).is_ok());
+        assert!(validate_backend_name("amp", &HashMap::new()).is_ok());
+        assert!(validate_backend_name("copilot", &HashMap::new()).is_ok());
+        assert!(validate_backend_name("opencode", &HashMap::new()).is_ok());
+        assert!(validate_backend_name("custom", &HashMap::new()).is_ok());
     }
 
     #[test]
     fn test_validate_backend_name_invalid() {
-        let result = validate_backend_name("invalid_backend");
+        let result = validate_backend_name("invalid_backend", &HashMap::new());
         assert!(result.is_err());
 
         if let Err(SopRunError::UnknownBackend(name)) = result {
@@ -368,6 +388,18 @@ mod tests {
         assert_eq!(backend, "gemini");
     }
 
+    #[test]
+    fn test_resolve_backend_accepts_declared_backend_flag() {
+        let temp_dir = tempfile::tempdir().expect("temp dir");
+        let config_path = temp_dir.path().join("ralph.yml");
+        std::fs::write(&config_path, "backends:\n  aider:\n    command: aider\n")
+            .expect("write config");
+
+        let backend = resolve_backend(Some("aider"), Some(&config_path)).expect("backend");
+        assert_eq!(backend, "aider");
+        assert!(resolve_backend(Some("aider"), None).is_err());
+    }
+
     #[test]
     fn test_run_sop_custom_args_missing_errors() {
         let temp_dir = tempfile::tempdir().expect("temp dir");
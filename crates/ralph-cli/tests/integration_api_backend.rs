@@ -0,0 +1,116 @@
+//! Integration tests for `ralph run` with the `api` backend.
+#![cfg(unix)]
+
+use ralph_adapters::testing::{MockChatServer, MockReply};
+use serde_json::json;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+use tokio::process::Command;
+
+fn write_config(temp_path: &Path, base_url: &str) {
+    let config = format!(
+        r#"
+event_loop:
+  completion_promise: "LOOP_COMPLETE"
+  max_iterations: 2
+
+cli:
+  backend: "api"
+  api:
+    base_url: "{base_url}"
+    model: "test-model"
+    api_key_env: "RALPH_TEST_API_KEY"
+    input_cost_per_mtok: 2000
+    output_cost_per_mtok: 2000
+
+core:
+  scratchpad: ".ralph/agent/scratchpad.md"
+
+features:
+  preflight:
+    enabled: false
+"#
+    );
+    fs::write(temp_path.join("ralph.yml"), config).unwrap();
+}
+
+#[tokio::test]
+async fn test_run_with_api_backend() {
+    let temp_dir = TempDir::new().unwrap();
+    let server = MockChatServer::start().await.unwrap();
+    write_config(temp_dir.path(), &server.base_url());
+    server.push(MockReply::tool_call(
+        "write_file",
+        json!({ "path": "hello.txt", "content": "hello from the api backend\n" }),
+    ));
+    server.push(MockReply::tool_call(
+        "ralph_emit",
+        json!({ "topic": "LOOP_COMPLETE", "payload": "hello.txt written" }),
+    ));
+    server.push(MockReply::text("Wrote hello.txt."));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
+        .args(["run", "--no-tui", "-p", "Write hello.txt"])
+        .env("RALPH_TEST_API_KEY", "test-key")
+        .current_dir(temp_dir.path())
+        .output()
+        .await
+        .expect("execute ralph");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        output.status.success(),
+        "stdout: {stdout}\nstderr: {stderr}"
+    );
+    assert!(stdout.contains("Wrote hello.txt."), "stdout: {stdout}");
+
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("hello.txt")).unwrap(),
+        "hello from the api backend\n"
+    );
+    let requests = server.requests();
+    assert_eq!(requests.len(), 3);
+    assert_eq!(requests[0]["model"], "test-model");
+    assert!(
+        requests[0]["messages"][0]["content"]
+            .as_str()
+            .unwrap()
+            .contains("Write hello.txt")
+    );
+
+    // 3 requests x 15 tokens at $2000 per million tokens.
+    let summary = fs::read_to_string(temp_dir.path().join(".ralph/agent/summary.md")).unwrap();
+    assert!(summary.contains("**Est. cost:** $0.09"), "{summary}");
+}
+
+#[tokio::test]
+async fn test_api_backend_rate_limit_is_retried() {
+    let temp_dir = TempDir::new().unwrap();
+    let server = MockChatServer::start().await.unwrap();
+    write_config(temp_dir.path(), &server.base_url());
+    server.push(MockReply::error(
+        429,
+        r#"{"error":{"message":"Rate limit exceeded, retry after 0s"}}"#,
+    ));
+    server.push(MockReply::tool_call(
+        "ralph_emit",
+        json!({ "topic": "LOOP_COMPLETE" }),
+    ));
+    server.push(MockReply::text("Done."));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
+        .args(["run", "--no-tui", "-p", "Say done"])
+        .current_dir(temp_dir.path())
+        .output()
+        .await
+        .expect("execute ralph");
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(server.requests().len(), 3);
+}
@@ -0,0 +1,244 @@
+//! Integration tests for human approval gates.
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+
+/// A fake agent that, on its first run only, emits `deploy.start` followed
+/// by whatever extra event lines are appended below.
+fn agent_script(extra: &str) -> String {
+    format!(
+        r#"#!/bin/sh
+[ -f .ralph/ran ] && exit 0
+touch .ralph/ran
+events=$(cat .ralph/current-events 2>/dev/null || echo .ralph/events.jsonl)
+echo '{{"topic":"deploy.start","payload":"ship v1.2","ts":"2026-01-01T00:00:00Z"}}' >> "$events"
+{extra}
+"#
+    )
+}
+
+fn ralph(temp_path: &Path) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_ralph"));
+    command
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
+        .current_dir(temp_path);
+    command
+}
+
+/// Runs a loop with `deploy.start` gated and returns the published topics.
+///
+/// With a `decision`, runs `ralph loops <decision>` from outside the loop as
+/// soon as the held event shows up in the approvals file.
+fn run_gated_loop(
+    temp_path: &Path,
+    agent: &str,
+    approval_timeout: &str,
+    decision: Option<&[&str]>,
+) -> (Vec<String>, String) {
+    let agent_path = temp_path.join("fake-agent");
+    fs::write(&agent_path, agent).unwrap();
+    fs::set_permissions(&agent_path, fs::Permissions::from_mode(0o755)).unwrap();
+    fs::create_dir_all(temp_path.join(".ralph")).unwrap();
+
+    let config = format!(
+        r#"
+event_loop:
+  completion_promise: "LOOP_COMPLETE"
+  max_iterations: 3
+
+events:
+  deploy.start:
+    description: "Deployment requested"
+    requires_approval: true
+    approval_timeout: "{approval_timeout}"
+
+cli:
+  backend: fake-agent
+
+backends:
+  fake-agent:
+    command: "{}"
+
+core:
+  scratchpad: ".ralph/agent/scratchpad.md"
+
+features:
+  preflight:
+    enabled: false
+"#,
+        agent_path.display()
+    );
+    fs::write(temp_path.join("ralph.yml"), config).unwrap();
+
+    let child = ralph(temp_path)
+        .args([
+            "run",
+            "--no-tui",
+            "--record-session",
+            "session.jsonl",
+            "-p",
+            "Deploy",
+        ])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("execute ralph");
+
+    if let Some(decision) = decision {
+        let approvals_path = temp_path.join(".ralph/agent/approvals.json");
+        let deadline = Instant::now() + Duration::from_secs(30);
+        while !approvals_path.exists() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        let answer = ralph(temp_path)
+            .arg("loops")
+            .args(decision)
+            .output()
+            .expect("execute ralph loops");
+        assert!(
+            answer.status.success(),
+            "{}",
+            String::from_utf8_lossy(&answer.stderr)
+        );
+    }
+
+    let output = child.wait_with_output().expect("wait for ralph");
+    let logs = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let topics = fs::read_to_string(temp_path.join("session.jsonl"))
+        .unwrap()
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter(|record| record["event"] == "bus.publish")
+        .filter_map(|record| record["data"]["topic"].as_str().map(ToString::to_string))
+        .collect();
+    (topics, logs)
+}
+
+#[test]
+fn test_gated_event_is_delivered_after_approval() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let (topics, logs) =
+        run_gated_loop(temp_dir.path(), &agent_script(""), "1h", Some(&["approve"]));
+
+    assert!(
+        topics.iter().any(|t| t == "deploy.start"),
+        "{topics:?}\n{logs}"
+    );
+    assert!(
+        !topics.iter().any(|t| t == "deploy.start.rejected"),
+        "{topics:?}\n{logs}"
+    );
+    assert!(!temp_dir.path().join(".ralph/agent/approvals.json").exists());
+}
+
+#[test]
+fn test_gated_event_is_rejected_by_the_human() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let (topics, logs) = run_gated_loop(
+        temp_dir.path(),
+        &agent_script(""),
+        "1h",
+        Some(&["reject", "--reason", "change freeze"]),
+    );
+
+    assert!(
+        topics.iter().any(|t| t == "deploy.start.rejected"),
+        "{topics:?}\n{logs}"
+    );
+    assert!(
+        !topics.iter().any(|t| t == "deploy.start"),
+        "{topics:?}\n{logs}"
+    );
+}
+
+#[test]
+fn test_agent_cannot_approve_its_own_event() {
+    let temp_dir = TempDir::new().unwrap();
+    let agent = agent_script(&format!(
+        r#"echo '{{"topic":"approval.response","payload":"approve","ts":"2026-01-01T00:00:00Z"}}' >> "$events"
+"{}" emit approval.response approve && touch .ralph/emit-succeeded"#,
+        env!("CARGO_BIN_EXE_ralph")
+    ));
+
+    let (topics, logs) = run_gated_loop(temp_dir.path(), &agent, "1s", None);
+
+    // Still held until the timeout rejected it
+    assert!(
+        topics.iter().any(|t| t == "deploy.start.rejected"),
+        "{topics:?}\n{logs}"
+    );
+    assert!(
+        !topics.iter().any(|t| t == "deploy.start"),
+        "{topics:?}\n{logs}"
+    );
+    assert!(!temp_dir.path().join(".ralph/emit-succeeded").exists());
+}
+
+#[test]
+fn test_emit_refuses_approval_responses() {
+    let temp_dir = TempDir::new().unwrap();
+    fs::create_dir_all(temp_dir.path().join(".ralph")).unwrap();
+
+    let output = ralph(temp_dir.path())
+        .args(["emit", "approval.response", "approve"])
+        .output()
+        .expect("execute ralph emit");
+
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("ralph loops approve"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
+
+#[test]
+fn test_approve_without_held_event_fails() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let output = ralph(temp_dir.path())
+        .args(["loops", "approve"])
+        .output()
+        .expect("execute ralph loops approve");
+
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("no event awaiting approval"),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(
+        !temp_dir
+            .path()
+            .join(".ralph/approval-decisions.jsonl")
+            .exists()
+    );
+}
+
+#[test]
+fn test_gated_event_is_rejected_on_timeout() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let (topics, logs) = run_gated_loop(temp_dir.path(), &agent_script(""), "1s", None);
+
+    assert!(
+        topics.iter().any(|t| t == "deploy.start.rejected"),
+        "{topics:?}\n{logs}"
+    );
+    assert!(
+        !topics.iter().any(|t| t == "deploy.start"),
+        "{topics:?}\n{logs}"
+    );
+}
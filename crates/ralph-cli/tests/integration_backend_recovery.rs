@@ -0,0 +1,124 @@
+//! Integration tests for backend failover during `ralph run`.
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+fn write_script(path: &Path, body: &str) {
+    fs::write(path, format!("#!/bin/sh\n{body}\n")).unwrap();
+    fs::set_permissions(path, fs::Permissions::from_mode(0o755)).unwrap();
+}
+
+/// Runs `ralph run` with `bin` first on PATH.
+fn run_ralph(temp_path: &Path, bin: &Path) -> Output {
+    let path = format!(
+        "{}:{}",
+        bin.display(),
+        std::env::var("PATH").unwrap_or_default()
+    );
+    Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
+        .args(["run", "--no-tui", "-p", "Add rate limiting"])
+        .env("PATH", path)
+        .current_dir(temp_path)
+        .output()
+        .expect("execute ralph")
+}
+
+/// The configured backend always fails with an exhausted quota; a fake
+/// `gemini` on PATH succeeds.
+fn setup(temp_path: &Path, failover: bool) -> std::path::PathBuf {
+    let bin = temp_path.join("bin");
+    fs::create_dir_all(&bin).unwrap();
+    write_script(
+        &bin.join("broken-agent"),
+        "echo 'Error: insufficient_quota: You exceeded your current quota'\nexit 1",
+    );
+    write_script(&bin.join("gemini"), "echo 'gemini ran'\nexit 0");
+
+    let config = format!(
+        r#"
+agent_priority: [gemini]
+
+event_loop:
+  completion_promise: "LOOP_COMPLETE"
+  max_iterations: 1
+
+cli:
+  backend: "custom"
+  command: "{}"
+  recovery:
+    failover: {failover}
+
+core:
+  scratchpad: ".ralph/agent/scratchpad.md"
+
+features:
+  preflight:
+    enabled: false
+"#,
+        bin.join("broken-agent").display()
+    );
+    fs::write(temp_path.join("ralph.yml"), config).unwrap();
+    bin
+}
+
+fn read(path: &Path) -> String {
+    fs::read_to_string(path).unwrap_or_default()
+}
+
+fn events(temp_path: &Path) -> String {
+    let ralph_dir = temp_path.join(".ralph");
+    fs::read_dir(&ralph_dir)
+        .unwrap()
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.extension().is_some_and(|ext| ext == "jsonl")
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| name.starts_with("events"))
+        })
+        .map(|path| read(&path))
+        .collect()
+}
+
+#[test]
+fn test_quota_failure_fails_over_to_next_backend() {
+    let temp_dir = TempDir::new().unwrap();
+    let bin = setup(temp_dir.path(), true);
+
+    let output = run_ralph(temp_dir.path(), &bin);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("gemini ran"),
+        "stdout: {stdout}\nstderr: {stderr}"
+    );
+
+    let history = read(&temp_dir.path().join(".ralph/history.jsonl"));
+    assert!(
+        history.contains(
+            r#""kind":"backend_switched","from":"custom","to":"gemini","reason":"quota""#
+        ),
+        "history: {history}"
+    );
+    assert!(events(temp_dir.path()).contains("backend.failover"));
+}
+
+#[test]
+fn test_failover_is_opt_in() {
+    let temp_dir = TempDir::new().unwrap();
+    let bin = setup(temp_dir.path(), false);
+
+    let output = run_ralph(temp_dir.path(), &bin);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!stdout.contains("gemini ran"), "stdout: {stdout}");
+
+    let history = read(&temp_dir.path().join(".ralph/history.jsonl"));
+    assert!(!history.contains("backend_switched"), "history: {history}");
+}
@@ -33,6 +33,7 @@ core:
 
     // Run ralph clean
     let output = Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("clean")
         .arg("--config")
         .arg(temp_path.join("ralph.yml"))
@@ -78,6 +79,7 @@ core:
 
     // Run ralph clean with custom config
     let output = Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("clean")
         .arg("--config")
         .arg(temp_path.join("custom.yml"))
@@ -116,6 +118,7 @@ core:
 
     // Run ralph clean with --dry-run
     let output = Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("clean")
         .arg("--config")
         .arg(temp_path.join("ralph.yml"))
@@ -161,6 +164,7 @@ core:
 
     // Run ralph clean
     let output = Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("clean")
         .arg("--config")
         .arg(temp_path.join("ralph.yml"))
@@ -203,6 +207,7 @@ core:
 
     // Run ralph clean with --color never
     let output = Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("clean")
         .arg("--config")
         .arg(temp_path.join("ralph.yml"))
@@ -241,6 +246,7 @@ core:
 
     // Run ralph clean with --color always
     let output = Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("clean")
         .arg("--config")
         .arg(temp_path.join("ralph.yml"))
@@ -288,6 +294,7 @@ core:
 
     // Run ralph clean
     let output = Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("clean")
         .arg("--config")
         .arg(temp_path.join("ralph.yml"))
@@ -0,0 +1,138 @@
+//! Integration tests for layered configuration and `ralph config show`.
+
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+fn ralph(dir: &Path, user_config: &Path, args: &[&str]) -> Result<Output> {
+    Ok(Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .args(args)
+        .env("RALPH_USER_CONFIG", user_config)
+        .env("RALPH_TEST_MAX", "12")
+        .env("RUST_BACKTRACE", "0")
+        .current_dir(dir)
+        .output()?)
+}
+
+#[test]
+fn test_config_show_resolved_reports_every_layer() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let dir = temp_dir.path();
+    let user_config = dir.join("user.yml");
+    fs::write(
+        &user_config,
+        "cli:\n  backend: kiro\n  idle_timeout_secs: 99\n",
+    )?;
+    fs::write(
+        dir.join("ralph.yml"),
+        r"
+extends: builtin:feature
+cli:
+  backend: claude
+event_loop:
+  max_iterations: ${RALPH_TEST_MAX}
+",
+    )?;
+
+    let output = ralph(
+        dir,
+        &user_config,
+        &[
+            "config",
+            "show",
+            "--resolved",
+            "-c",
+            "ralph.yml",
+            "-c",
+            "core.specs_dir=my-specs",
+        ],
+    )?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    assert!(
+        stdout.contains("  backend: claude  # ralph.yml\n"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("  idle_timeout_secs: 99  # "), "{stdout}");
+    assert!(stdout.contains("user.yml\n"));
+    assert!(stdout.contains("  max_iterations: 12  # ralph.yml\n"));
+    assert!(stdout.contains("  specs_dir: my-specs  # -c core.specs_dir=my-specs\n"));
+    assert!(stdout.contains("# builtin:feature"));
+    assert!(stdout.contains("# default"));
+    assert!(
+        stdout.contains(&format!("#   user: {}\n", user_config.display())),
+        "{stdout}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_config_show_origin_lists_missing_user_layer() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let dir = temp_dir.path();
+    let user_config = dir.join("absent.yml");
+
+    let output = ralph(dir, &user_config, &["config", "show", "--origin"])?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    assert!(
+        stdout.contains(&format!(
+            "#   user: {} (not found)\n",
+            user_config.display()
+        )),
+        "{stdout}"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_config_show_merges_multiple_sources() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let dir = temp_dir.path();
+    fs::write(dir.join("base.yml"), "core:\n  specs_dir: base-specs\n")?;
+    fs::write(dir.join("local.yml"), "cli:\n  backend: gemini\n")?;
+
+    let output = ralph(
+        dir,
+        Path::new(""),
+        &["config", "show", "-c", "base.yml", "-c", "local.yml"],
+    )?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    assert!(stdout.contains("specs_dir: base-specs"), "{stdout}");
+    assert!(stdout.contains("backend: gemini"));
+    Ok(())
+}
+
+#[test]
+fn test_undefined_env_var_fails_with_hint() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let dir = temp_dir.path();
+    fs::write(
+        dir.join("ralph.yml"),
+        "cli:\n  backend: ${RALPH_TEST_DEFINITELY_UNSET}\n",
+    )?;
+
+    let output = ralph(dir, Path::new(""), &["config", "show"])?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(!output.status.success());
+    assert!(stderr.contains("RALPH_TEST_DEFINITELY_UNSET"), "{stderr}");
+    Ok(())
+}
@@ -0,0 +1,157 @@
+//! Integration tests for backends declared under `backends:` in ralph.yml.
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+/// A fake agent that records how it was invoked, prints a mix of lines for
+/// the line parser, reports a cost, and completes the loop.
+const AGENT_SCRIPT: &str = r#"#!/bin/sh
+printf '%s\n' "$@" > invocation.txt
+echo "$MY_AGENT_MODE" > mode.txt
+echo 'thinking...'
+echo '> shell {"command":"ls"}'
+echo 'Task finished.'
+events=$(cat .ralph/current-events 2>/dev/null || echo .ralph/events.jsonl)
+echo '{"topic":"LOOP_COMPLETE","payload":"done","ts":"2026-01-01T00:00:00Z"}' >> "$events"
+echo 'Total cost: $0.37'
+"#;
+
+fn setup(temp_path: &Path) {
+    let agent = temp_path.join("my-agent");
+    fs::write(&agent, AGENT_SCRIPT).unwrap();
+    fs::set_permissions(&agent, fs::Permissions::from_mode(0o755)).unwrap();
+
+    let config = format!(
+        r#"
+event_loop:
+  completion_promise: "LOOP_COMPLETE"
+  max_iterations: 2
+
+cli:
+  backend: my-agent
+
+backends:
+  my-agent:
+    command: "{}"
+    args: ["--headless"]
+    prompt_flag: "--task"
+    env:
+      MY_AGENT_MODE: ci
+    output_format: lines
+    line_parser:
+      skip: '^thinking'
+      tool_call: '^> (?P<name>\w+) (?P<input>.*)$'
+    usage:
+      cost: 'Total cost: \$([\d.]+)'
+
+core:
+  scratchpad: ".ralph/agent/scratchpad.md"
+
+features:
+  preflight:
+    enabled: false
+"#,
+        agent.display()
+    );
+    fs::write(temp_path.join("ralph.yml"), config).unwrap();
+}
+
+fn run_ralph(temp_path: &Path, extra_args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
+        .arg("run")
+        .args(extra_args)
+        .args(["--no-tui", "-p", "List the files"])
+        .current_dir(temp_path)
+        .output()
+        .expect("execute ralph")
+}
+
+#[test]
+fn test_declared_backend_runs_with_its_definition() {
+    let temp_dir = TempDir::new().unwrap();
+    setup(temp_dir.path());
+
+    let output = run_ralph(temp_dir.path(), &[]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        output.status.success(),
+        "stdout: {stdout}\nstderr: {stderr}"
+    );
+    assert!(stdout.contains("Task finished."), "stdout: {stdout}");
+    assert!(!stdout.contains("thinking..."), "stdout: {stdout}");
+
+    let invocation = fs::read_to_string(temp_dir.path().join("invocation.txt")).unwrap();
+    let args: Vec<&str> = invocation.lines().collect();
+    assert_eq!(args[..2], ["--headless", "--task"]);
+    assert!(invocation.contains("List the files"), "{invocation}");
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("mode.txt")).unwrap(),
+        "ci\n"
+    );
+
+    let summary = fs::read_to_string(temp_dir.path().join(".ralph/agent/summary.md")).unwrap();
+    assert!(summary.contains("**Est. cost:** $0.37"), "{summary}");
+}
+
+#[test]
+fn test_declared_backend_selected_with_flag() {
+    let temp_dir = TempDir::new().unwrap();
+    setup(temp_dir.path());
+    let config_path = temp_dir.path().join("ralph.yml");
+    let config = fs::read_to_string(&config_path)
+        .unwrap()
+        .replace("backend: my-agent", "backend: claude");
+    fs::write(&config_path, config).unwrap();
+
+    let output = run_ralph(temp_dir.path(), &["-b", "my-agent"]);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(temp_dir.path().join("invocation.txt").exists());
+}
+
+#[test]
+fn test_declared_backend_receives_model_and_effort() {
+    let temp_dir = TempDir::new().unwrap();
+    setup(temp_dir.path());
+    let config_path = temp_dir.path().join("ralph.yml");
+    let config = fs::read_to_string(&config_path)
+        .unwrap()
+        .replace(
+            "  backend: my-agent\n",
+            "  backend: my-agent\n  model: fast-1\n  effort: high\n",
+        )
+        .replace(
+            "    prompt_flag: \"--task\"\n",
+            "    prompt_flag: \"--task\"\n    model_flag: \"--model\"\n    effort_flag: \"--reasoning\"\n",
+        );
+    fs::write(&config_path, config).unwrap();
+
+    let output = run_ralph(temp_dir.path(), &[]);
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let invocation = fs::read_to_string(temp_dir.path().join("invocation.txt")).unwrap();
+    let args: Vec<&str> = invocation.lines().collect();
+    assert_eq!(
+        args[..5],
+        ["--headless", "--model", "fast-1", "--reasoning", "high"]
+    );
+
+    let summary = fs::read_to_string(temp_dir.path().join(".ralph/agent/summary.md")).unwrap();
+    assert!(
+        summary.contains("**Cost by model:** my-agent/fast-1 $0.37"),
+        "{summary}"
+    );
+}
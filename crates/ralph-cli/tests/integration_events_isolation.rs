@@ -59,6 +59,7 @@ fn test_fresh_run_creates_marker_file() -> Result<()> {
 
     // Run ralph
     let _output = Command::new(ralph_bin())
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("run")
         .arg("--config")
         .arg(temp_path.join("ralph.yml"))
@@ -101,6 +102,7 @@ fn test_marker_file_contains_timestamped_path() -> Result<()> {
 
     // Run ralph
     let _output = Command::new(ralph_bin())
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("run")
         .arg("--config")
         .arg(temp_path.join("ralph.yml"))
@@ -132,6 +134,7 @@ fn test_ralph_emit_creates_timestamped_events_file() -> Result<()> {
 
     // Run ralph to create marker file
     let _output = Command::new(ralph_bin())
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("run")
         .arg("--config")
         .arg(temp_path.join("ralph.yml"))
@@ -147,6 +150,7 @@ fn test_ralph_emit_creates_timestamped_events_file() -> Result<()> {
 
     // Use ralph emit to write to the marker-specified file
     let output = Command::new(ralph_bin())
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("emit")
         .arg("test.topic")
         .arg("test payload")
@@ -195,6 +199,7 @@ fn test_consecutive_runs_get_isolated_marker_paths() -> Result<()> {
 
     // First run
     let _output1 = Command::new(ralph_bin())
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("run")
         .arg("--config")
         .arg(temp_path.join("ralph.yml"))
@@ -210,6 +215,7 @@ fn test_consecutive_runs_get_isolated_marker_paths() -> Result<()> {
 
     // Second run
     let _output2 = Command::new(ralph_bin())
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("run")
         .arg("--config")
         .arg(temp_path.join("ralph.yml"))
@@ -256,6 +262,7 @@ fn test_ralph_emit_writes_to_marker_specified_file() -> Result<()> {
 
     // Run ralph to create marker file
     let _output = Command::new(ralph_bin())
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("run")
         .arg("--config")
         .arg(temp_path.join("ralph.yml"))
@@ -277,6 +284,7 @@ fn test_ralph_emit_writes_to_marker_specified_file() -> Result<()> {
 
     // Use ralph emit to write an event
     let output = Command::new(ralph_bin())
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("emit")
         .arg("test.topic")
         .arg("test payload")
@@ -342,6 +350,7 @@ fn test_ralph_emit_fallback_without_marker() -> Result<()> {
 
     // Use ralph emit (should fall back to default)
     let output = Command::new(ralph_bin())
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("emit")
         .arg("fallback.topic")
         .arg("fallback payload")
@@ -387,6 +396,7 @@ fn test_continue_uses_existing_marker_file() -> Result<()> {
 
     // First: run ralph to create marker file
     let _output = Command::new(ralph_bin())
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("run")
         .arg("--config")
         .arg(temp_path.join("ralph.yml"))
@@ -407,6 +417,7 @@ fn test_continue_uses_existing_marker_file() -> Result<()> {
 
     // Continue - should NOT create a new marker/events file
     let _output = Command::new(ralph_bin())
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("run")
         .arg("--continue")
         .arg("--config")
@@ -438,6 +449,7 @@ fn test_continue_preserves_marker_path() -> Result<()> {
 
     // First: run ralph
     let _output = Command::new(ralph_bin())
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("run")
         .arg("--config")
         .arg(temp_path.join("ralph.yml"))
@@ -458,6 +470,7 @@ fn test_continue_preserves_marker_path() -> Result<()> {
 
     // Continue
     let _output = Command::new(ralph_bin())
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("run")
         .arg("--continue")
         .arg("--config")
@@ -512,6 +525,7 @@ fn test_stale_events_dont_pollute_new_runs() -> Result<()> {
 
     // Now run ralph fresh - it should create a NEW events file
     let _output = Command::new(ralph_bin())
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("run")
         .arg("--config")
         .arg(temp_path.join("ralph.yml"))
@@ -575,6 +589,7 @@ fn test_new_run_ignores_stale_marker() -> Result<()> {
 
     // Run ralph fresh
     let _output = Command::new(ralph_bin())
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("run")
         .arg("--config")
         .arg(temp_path.join("ralph.yml"))
@@ -622,6 +637,7 @@ fn test_ralph_directory_created() -> Result<()> {
 
     // Run ralph
     let _output = Command::new(ralph_bin())
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("run")
         .arg("--config")
         .arg(temp_path.join("ralph.yml"))
@@ -20,6 +20,7 @@ use tempfile::TempDir;
 /// Run ralph loops command with given args in the temp directory.
 fn ralph_loops(temp_path: &std::path::Path, args: &[&str]) -> std::process::Output {
     Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("loops")
         .args(args)
         .current_dir(temp_path)
@@ -14,6 +14,7 @@ use tempfile::TempDir;
 /// Run ralph tools memory command with given args in the temp directory.
 fn ralph_memory(temp_path: &std::path::Path, args: &[&str]) -> std::process::Output {
     Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("tools")
         .arg("memory")
         .args(args)
@@ -690,6 +691,7 @@ fn test_memory_list_color_never() -> Result<()> {
 
     // Run with --color never via the main CLI
     let output = Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("--color")
         .arg("never")
         .arg("tools")
@@ -102,6 +102,7 @@ fn write_loop_lock(temp_path: &std::path::Path, pid: u32, prompt: &str) -> Resul
 /// Run ralph loops command with given args in the temp directory.
 fn ralph_loops(temp_path: &std::path::Path, args: &[&str]) -> std::process::Output {
     Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("loops")
         .args(args)
         .current_dir(temp_path)
@@ -0,0 +1,160 @@
+//! Integration tests for `ralph pipeline` CLI commands.
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+fn ralph_pipeline(temp_path: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
+        .arg("pipeline")
+        .args(args)
+        .current_dir(temp_path)
+        .output()
+        .expect("Failed to execute ralph pipeline command")
+}
+
+/// Writes a config whose backend exits immediately, so every stage ends
+/// with `MaxIterations`, and a two-stage pipeline whose second stage
+/// requires `requires`.
+fn setup(temp_path: &Path, requires: &str) {
+    let config = r#"
+event_loop:
+  completion_promise: "LOOP_COMPLETE"
+  max_iterations: 1
+
+cli:
+  backend: "custom"
+  command: "true"
+
+core:
+  scratchpad: ".ralph/agent/scratchpad.md"
+
+features:
+  preflight:
+    enabled: false
+"#;
+    fs::write(temp_path.join("ralph.yml"), config).unwrap();
+    let pipeline = format!(
+        "name: ship\nprompt: Add rate limiting\nstages:\n  - name: spec\n    prompt: Write the spec\n  - name: build\n    requires: {requires}\n"
+    );
+    fs::write(temp_path.join("pipeline.yml"), pipeline).unwrap();
+}
+
+fn status_json(temp_path: &Path) -> serde_json::Value {
+    let output = ralph_pipeline(temp_path, &["status", "--json"]);
+    assert!(
+        output.status.success(),
+        "pipeline status failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn test_pipeline_halts_when_gate_rejects_previous_stage() {
+    let temp_dir = TempDir::new().unwrap();
+    setup(temp_dir.path(), "[completed]");
+
+    let output = ralph_pipeline(temp_dir.path(), &["run", "pipeline.yml"]);
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stdout.contains("Stage 'spec' finished: MaxIterations"),
+        "stdout: {stdout}\nstderr: {stderr}"
+    );
+    assert!(
+        stderr.contains("stage 'build' does not accept 'spec' ending with MaxIterations"),
+        "stderr: {stderr}"
+    );
+
+    let status = status_json(temp_dir.path());
+    assert_eq!(status["pipeline"], "ship");
+    assert_eq!(status["state"], "halted");
+    assert_eq!(status["stage"], 0);
+    assert_eq!(status["stages"][0]["outcome"], "MaxIterations");
+    assert!(status["stages"][1]["started_at"].is_null());
+
+    // The stage's summary is kept with the run.
+    let run_id = status["id"].as_str().unwrap();
+    assert!(
+        temp_dir
+            .path()
+            .join(".ralph/pipelines")
+            .join(run_id)
+            .join("spec/summary.md")
+            .exists()
+    );
+}
+
+#[test]
+fn test_pipeline_runs_every_stage_when_gates_accept() {
+    let temp_dir = TempDir::new().unwrap();
+    setup(temp_dir.path(), "[any]");
+
+    let output = ralph_pipeline(temp_dir.path(), &["run", "pipeline.yml"]);
+    // The last stage never emits the completion promise either.
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Stage 2/2 'build' starting"),
+        "stdout: {stdout}\nstderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let status = status_json(temp_dir.path());
+    assert_eq!(status["state"], "completed");
+    assert_eq!(status["outcome"], "MaxIterations");
+
+    let history = fs::read_to_string(
+        temp_dir
+            .path()
+            .join(".ralph/pipelines")
+            .join(status["id"].as_str().unwrap())
+            .join("history.jsonl"),
+    )
+    .unwrap();
+    assert!(history.contains("pipeline_started"));
+    assert_eq!(history.matches("stage_finished").count(), 2);
+}
+
+#[test]
+fn test_pipeline_resume_from_stage() {
+    let temp_dir = TempDir::new().unwrap();
+    setup(temp_dir.path(), "[completed]");
+    ralph_pipeline(temp_dir.path(), &["run", "pipeline.yml"]);
+
+    let output = ralph_pipeline(temp_dir.path(), &["resume", "--from", "build"]);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("Resuming pipeline run ship-"), "stdout: {stdout}");
+    assert!(stdout.contains("Stage 2/2 'build' starting"), "stdout: {stdout}");
+
+    let status = status_json(temp_dir.path());
+    assert_eq!(status["state"], "completed");
+
+    let output = ralph_pipeline(temp_dir.path(), &["resume"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("already completed"), "stderr: {stderr}");
+}
+
+#[test]
+fn test_pipeline_rejects_invalid_file_and_missing_runs() {
+    let temp_dir = TempDir::new().unwrap();
+    setup(temp_dir.path(), "[finished]");
+
+    let output = ralph_pipeline(temp_dir.path(), &["run", "pipeline.yml"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(
+        stderr.contains("unknown termination reason 'finished'"),
+        "stderr: {stderr}"
+    );
+
+    let output = ralph_pipeline(temp_dir.path(), &["status"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("No pipeline runs found"), "stderr: {stderr}");
+}
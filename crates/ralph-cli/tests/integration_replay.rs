@@ -0,0 +1,174 @@
+//! Integration tests for `ralph run --replay`.
+//!
+//! Replays hand-written session recordings through the CLI. No backend is
+//! invoked, so these run without any agent installed.
+
+use anyhow::Result;
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+const CONFIG: &str = r#"
+cli:
+  backend: claude
+event_loop:
+  completion_promise: "LOOP_COMPLETE"
+  max_iterations: 5
+"#;
+
+/// A single-iteration solo session where the agent emits the completion event.
+const SESSION: &str = r###"{"ts":1,"event":"bus.publish","data":{"topic":"task.start","payload":"Build it","source":null,"target":null}}
+{"ts":2,"event":"_meta.iteration","data":{"n":1,"elapsed_ms":0,"hat":"ralph"}}
+{"ts":3,"event":"_meta.agent_output","data":{"n":1,"hat":"ralph","output":"Done","success":true}}
+{"ts":4,"event":"_meta.agent_events","data":{"n":1,"events":{"events":[{"topic":"LOOP_COMPLETE","payload":"done","ts":"2026-01-01T00:00:00Z"}],"malformed":[]}}}
+{"ts":5,"event":"bus.publish","data":{"topic":"loop.terminate","payload":"## Reason\ncompleted\n\n## Status\nok","source":null,"target":null}}
+"###;
+
+/// Gates `deploy.start` on approval and expects `deploy.done` within a minute.
+const TIMED_CONFIG: &str = r#"
+cli:
+  backend: claude
+event_loop:
+  completion_promise: "LOOP_COMPLETE"
+  max_iterations: 5
+  watchdogs:
+    - expect: deploy.done
+      within: 1m
+      else: deploy.overdue
+events:
+  deploy.start:
+    requires_approval: true
+"#;
+
+/// A session under `TIMED_CONFIG`: the agent asks to deploy, a human approves
+/// four seconds later, and the watchdog fires while the loop sits idle.
+const TIMED_SESSION: &str = r###"{"ts":1000,"event":"bus.publish","data":{"topic":"task.start","payload":"Deploy it","source":null,"target":null}}
+{"ts":1001,"event":"_meta.iteration","data":{"n":1,"elapsed_ms":0,"hat":"ralph"}}
+{"ts":1002,"event":"_meta.agent_output","data":{"n":1,"hat":"ralph","output":"Requesting a deploy","success":true}}
+{"ts":1003,"event":"_meta.agent_events","data":{"n":1,"events":{"events":[{"topic":"deploy.start","payload":"v1.2","ts":"2026-01-01T00:00:00Z"}],"malformed":[]}}}
+{"ts":5000,"event":"_meta.approval_decisions","data":{"n":1,"decisions":["approve"]}}
+{"ts":5000,"event":"bus.publish","data":{"topic":"deploy.start","payload":"v1.2","source":null,"target":null}}
+{"ts":5001,"event":"_meta.iteration","data":{"n":2,"elapsed_ms":4000,"hat":"ralph"}}
+{"ts":5002,"event":"_meta.agent_output","data":{"n":2,"hat":"ralph","output":"Deploying","success":true}}
+{"ts":5003,"event":"_meta.agent_events","data":{"n":2,"events":{"events":[],"malformed":[]}}}
+{"ts":61000,"event":"bus.publish","data":{"topic":"deploy.overdue","payload":"Watchdog expired: 'deploy.done' did not arrive in time","source":null,"target":null}}
+{"ts":61001,"event":"_meta.iteration","data":{"n":3,"elapsed_ms":60000,"hat":"ralph"}}
+{"ts":61002,"event":"_meta.agent_output","data":{"n":3,"hat":"ralph","output":"Giving up","success":true}}
+{"ts":61003,"event":"_meta.agent_events","data":{"n":3,"events":{"events":[{"topic":"LOOP_COMPLETE","payload":"done","ts":"2026-01-01T00:00:00Z"}],"malformed":[]}}}
+{"ts":61004,"event":"bus.publish","data":{"topic":"loop.terminate","payload":"## Reason\ncompleted\n\n## Status\nok","source":null,"target":null}}
+"###;
+
+fn replay(dir: &Path, session: &str) -> Result<Output> {
+    replay_with(dir, CONFIG, session)
+}
+
+fn replay_with(dir: &Path, config: &str, session: &str) -> Result<Output> {
+    fs::write(dir.join("ralph.yml"), config)?;
+    fs::write(dir.join("session.jsonl"), session)?;
+    Ok(Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
+        .args(["run", "--replay", "session.jsonl", "--config", "ralph.yml"])
+        .env("RUST_BACKTRACE", "0")
+        .current_dir(dir)
+        .output()?)
+}
+
+#[test]
+fn test_replay_faithful_session_succeeds() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let output = replay(temp_dir.path(), SESSION)?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "replay should succeed: {stdout}\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("Iterations: 1 replayed / 1 recorded"));
+    assert!(stdout.contains("Termination: completed (recorded: completed)"));
+    assert!(stdout.contains("Result: Faithful"));
+    Ok(())
+}
+
+#[test]
+fn test_replay_reports_divergence() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    // The recording claims the loop published an event the agent never emitted.
+    let session = SESSION.replacen(
+        "{\"ts\":5,",
+        "{\"ts\":5,\"event\":\"bus.publish\",\"data\":{\"topic\":\"build.done\",\"payload\":\"x\",\"source\":null,\"target\":null}}\n{\"ts\":6,",
+        1,
+    );
+    let output = replay(temp_dir.path(), &session)?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success(), "diverged replay should fail");
+    assert!(stdout.contains("[diverged] iteration 1: 'build.done'"));
+    assert!(stdout.contains("Result: Diverged"));
+    Ok(())
+}
+
+#[test]
+fn test_replay_rejects_session_without_output() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let session = r#"{"ts":1,"event":"bus.publish","data":{"topic":"task.start","payload":"Build it","source":null,"target":null}}
+"#;
+    let output = replay(temp_dir.path(), session)?;
+
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("no backend output"), "stderr: {stderr}");
+    Ok(())
+}
+
+#[test]
+fn test_replay_runs_watchdogs_and_approvals_on_the_recorded_clock() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let output = replay_with(temp_dir.path(), TIMED_CONFIG, TIMED_SESSION)?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        output.status.success(),
+        "replay should succeed: {stdout}\n{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(stdout.contains("Iterations: 3 replayed / 3 recorded"));
+    assert!(stdout.contains("Result: Faithful"));
+    Ok(())
+}
+
+#[test]
+fn test_replay_reports_loop_left_waiting_for_approval() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let session = TIMED_SESSION
+        .lines()
+        .filter(|line| !line.contains("_meta.approval_decisions"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let output = replay_with(temp_dir.path(), TIMED_CONFIG, &session)?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success(), "diverged replay should fail");
+    assert!(
+        stdout.contains("iteration 1: 'deploy.start' was published in the recording"),
+        "{stdout}"
+    );
+    assert!(stdout.contains("Result: Diverged"));
+    Ok(())
+}
+
+#[test]
+fn test_replay_reports_watchdog_that_no_longer_fires() -> Result<()> {
+    let temp_dir = TempDir::new()?;
+    let config = TIMED_CONFIG.replace("within: 1m", "within: 2m");
+    let output = replay_with(temp_dir.path(), &config, TIMED_SESSION)?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(!output.status.success(), "diverged replay should fail");
+    assert!(
+        stdout.contains("iteration 3: replay was still waiting for a timer or approval"),
+        "{stdout}"
+    );
+    Ok(())
+}
@@ -42,6 +42,7 @@ core:
 
     // Run ralph run --continue - should fail with error about missing scratchpad
     let output = Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("run")
         .arg("--continue")
         .arg("--config")
@@ -110,6 +111,7 @@ Previous work completed on feature B.
 
     // Run ralph run --continue --no-tui (needed for tracing output to stdout)
     let output = Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("run")
         .arg("--continue")
         .arg("--no-tui")
@@ -177,6 +179,7 @@ This is a continued session.
 
     // Run ralph run --continue
     let _output = Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("run")
         .arg("--continue")
         .arg("--config")
@@ -239,6 +242,7 @@ core:
     fs::write(agent_dir.join("scratchpad.md"), scratchpad_content)?;
 
     let _output = Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("run")
         .arg("--config")
         .arg(temp_path.join("ralph.yml"))
@@ -259,6 +263,7 @@ core:
 
     // Test 2: Run ralph run --continue (should publish task.resume)
     let _output = Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("run")
         .arg("--continue")
         .arg("--config")
@@ -348,6 +353,7 @@ This scratchpad contains UNIQUE_CONTENT_MARKER for testing.
 
     // Run ralph run --continue --no-tui (needed for tracing output to stdout)
     let output = Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("run")
         .arg("--continue")
         .arg("--no-tui")
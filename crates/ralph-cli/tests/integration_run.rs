@@ -3,6 +3,7 @@ use tempfile::TempDir;
 
 fn run_ralph(temp_path: &std::path::Path, args: &[&str]) -> std::process::Output {
     Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .args(args)
         .current_dir(temp_path)
         .output()
@@ -0,0 +1,148 @@
+//! Integration tests for running backends inside the `sandbox:` namespace.
+#![cfg(target_os = "linux")]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+/// A fake agent that writes inside the workspace, attempts a write outside
+/// it, and completes the loop.
+const AGENT_SCRIPT: &str = r#"#!/bin/sh
+echo inside > inside.txt
+if echo outside > "$OUTSIDE_DIR/outside.txt" 2>/dev/null; then
+  echo writable > outside-status.txt
+else
+  echo read-only > outside-status.txt
+fi
+echo "$TMPDIR" > tmpdir.txt
+events=$(cat .ralph/current-events 2>/dev/null || echo .ralph/events.jsonl)
+echo '{"topic":"LOOP_COMPLETE","payload":"done","ts":"2026-01-01T00:00:00Z"}' >> "$events"
+"#;
+
+/// Whether unprivileged user namespaces work on this machine.
+fn unshare_available() -> bool {
+    Command::new("unshare")
+        .args(["--user", "--map-root-user", "--mount", "true"])
+        .output()
+        .is_ok_and(|output| output.status.success())
+}
+
+fn setup(temp_path: &Path, outside: &Path, sandbox: &str) {
+    let agent = temp_path.join("sandboxed-agent");
+    fs::write(&agent, AGENT_SCRIPT).unwrap();
+    fs::set_permissions(&agent, fs::Permissions::from_mode(0o755)).unwrap();
+
+    let config = format!(
+        r#"
+event_loop:
+  completion_promise: "LOOP_COMPLETE"
+  max_iterations: 2
+
+cli:
+  backend: sandboxed-agent
+
+backends:
+  sandboxed-agent:
+    command: "{}"
+    env:
+      OUTSIDE_DIR: "{}"
+    output_format: lines
+
+{sandbox}
+
+core:
+  scratchpad: ".ralph/agent/scratchpad.md"
+
+features:
+  preflight:
+    enabled: false
+"#,
+        agent.display(),
+        outside.display()
+    );
+    fs::write(temp_path.join("ralph.yml"), config).unwrap();
+}
+
+fn run_ralph(temp_path: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
+        .args(["run", "--no-tui", "-p", "Write some files"])
+        .current_dir(temp_path)
+        .output()
+        .expect("execute ralph")
+}
+
+#[test]
+fn test_sandbox_makes_host_read_only() {
+    if !unshare_available() {
+        eprintln!("skipping: user namespaces are unavailable");
+        return;
+    }
+    let temp_dir = TempDir::new().unwrap();
+    let outside = TempDir::new().unwrap();
+    setup(
+        temp_dir.path(),
+        outside.path(),
+        "sandbox:\n  enabled: true\n  engine: unshare",
+    );
+
+    let output = run_ralph(temp_dir.path());
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(temp_dir.path().join("inside.txt").exists());
+    assert!(!outside.path().join("outside.txt").exists());
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("outside-status.txt")).unwrap(),
+        "read-only\n"
+    );
+    let tmpdir = fs::read_to_string(temp_dir.path().join("tmpdir.txt")).unwrap();
+    assert!(tmpdir.trim_end().ends_with(".ralph/tmp"), "{tmpdir}");
+}
+
+#[test]
+fn test_sandbox_binds_are_writable() {
+    if !unshare_available() {
+        eprintln!("skipping: user namespaces are unavailable");
+        return;
+    }
+    let temp_dir = TempDir::new().unwrap();
+    let outside = TempDir::new().unwrap();
+    let sandbox = format!(
+        "sandbox:\n  enabled: true\n  engine: unshare\n  binds:\n    - path: \"{}\"\n      writable: true",
+        outside.path().display()
+    );
+    setup(temp_dir.path(), outside.path(), &sandbox);
+
+    let output = run_ralph(temp_dir.path());
+    assert!(
+        output.status.success(),
+        "stderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(
+        fs::read_to_string(outside.path().join("outside.txt")).unwrap(),
+        "outside\n"
+    );
+}
+
+#[test]
+fn test_sandbox_with_missing_bind_fails_clearly() {
+    let temp_dir = TempDir::new().unwrap();
+    let outside = TempDir::new().unwrap();
+    setup(
+        temp_dir.path(),
+        outside.path(),
+        "sandbox:\n  enabled: true\n  binds:\n    - path: /does/not/exist",
+    );
+
+    let output = run_ralph(temp_dir.path());
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("/does/not/exist"), "stderr: {stderr}");
+    assert!(!temp_dir.path().join("inside.txt").exists());
+}
@@ -0,0 +1,126 @@
+//! Integration tests for `ralph schedule` CLI commands.
+
+use std::fs;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+fn ralph_schedule(temp_path: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
+        .arg("schedule")
+        .args(args)
+        .current_dir(temp_path)
+        .output()
+        .expect("Failed to execute ralph schedule command")
+}
+
+/// Writes a config whose backend exits immediately, with one schedule in the
+/// config and one in `.ralph/schedules.yml`.
+fn setup(temp_path: &Path) {
+    let config = r#"
+event_loop:
+  completion_promise: "LOOP_COMPLETE"
+  max_iterations: 1
+
+cli:
+  backend: "custom"
+  command: "true"
+
+core:
+  scratchpad: ".ralph/agent/scratchpad.md"
+
+features:
+  preflight:
+    enabled: false
+
+schedules:
+  - name: audit
+    cron: "0 3 * * 1-5"
+    prompt: "Audit dependencies"
+"#;
+    fs::write(temp_path.join("ralph.yml"), config).unwrap();
+    fs::create_dir_all(temp_path.join(".ralph")).unwrap();
+    fs::write(
+        temp_path.join(".ralph/schedules.yml"),
+        "schedules:\n  - name: docs\n    cron: \"@weekly\"\n    prompt: Refresh docs\n    enabled: false\n",
+    )
+    .unwrap();
+}
+
+fn list_json(temp_path: &Path) -> Vec<serde_json::Value> {
+    let output = ralph_schedule(temp_path, &["list", "--json"]);
+    assert!(
+        output.status.success(),
+        "schedule list failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[test]
+fn test_schedule_list_merges_config_and_schedules_file() {
+    let temp_dir = TempDir::new().unwrap();
+    setup(temp_dir.path());
+
+    let rows = list_json(temp_dir.path());
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0]["name"], "audit");
+    assert!(rows[0]["next_run"].is_string());
+    assert_eq!(rows[1]["name"], "docs");
+    assert_eq!(rows[1]["enabled"], false);
+    assert!(rows[1]["next_run"].is_null());
+}
+
+#[test]
+fn test_schedule_list_rejects_invalid_cron() {
+    let temp_dir = TempDir::new().unwrap();
+    setup(temp_dir.path());
+    fs::write(
+        temp_dir.path().join(".ralph/schedules.yml"),
+        "schedules:\n  - name: broken\n    cron: \"61 * * * *\"\n    prompt: x\n",
+    )
+    .unwrap();
+
+    let output = ralph_schedule(temp_dir.path(), &["list"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Invalid cron expression"), "stderr: {stderr}");
+}
+
+#[test]
+fn test_schedule_run_records_history() {
+    let temp_dir = TempDir::new().unwrap();
+    setup(temp_dir.path());
+
+    let output = ralph_schedule(temp_dir.path(), &["run", "audit"]);
+    // The backend never emits the completion promise, so the run is reported
+    // as incomplete.
+    assert!(!output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Scheduled run 'audit' finished: MaxIterations"),
+        "stdout: {stdout}\nstderr: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let history =
+        fs::read_to_string(temp_dir.path().join(".ralph/schedule-history.jsonl")).unwrap();
+    assert!(history.contains("scheduled_run_started"));
+    assert!(history.contains("scheduled_run_finished"));
+
+    let rows = list_json(temp_dir.path());
+    assert_eq!(rows[0]["last_outcome"], "MaxIterations");
+    assert!(rows[1]["last_run"].is_null());
+}
+
+#[test]
+fn test_schedule_run_unknown_name_fails() {
+    let temp_dir = TempDir::new().unwrap();
+    setup(temp_dir.path());
+
+    let output = ralph_schedule(temp_dir.path(), &["run", "missing"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("No schedule named 'missing'"), "stderr: {stderr}");
+}
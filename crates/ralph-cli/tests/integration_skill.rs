@@ -6,6 +6,7 @@ use tempfile::TempDir;
 
 fn ralph_skill(temp_path: &std::path::Path, args: &[&str]) -> std::process::Output {
     Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("tools")
         .arg("skill")
         .args(args)
@@ -18,6 +19,7 @@ fn ralph_skill(temp_path: &std::path::Path, args: &[&str]) -> std::process::Outp
 
 fn ralph_skill_no_root(current_path: &std::path::Path, args: &[&str]) -> std::process::Output {
     Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("tools")
         .arg("skill")
         .args(args)
@@ -222,3 +224,106 @@ Loaded from configured parent skills dir.
     let load_stdout = ralph_skill_no_root_ok(&nested_dir, &["load", "test-driven-development"]);
     assert!(load_stdout.contains("Loaded from configured parent skills dir."));
 }
+
+#[test]
+fn test_skill_install_list_and_remove_pack() {
+    let temp_dir = TempDir::new().expect("temp dir");
+    let temp_path = temp_dir.path();
+    let pack_dir = temp_path.join("vendor").join("rust-pack");
+    fs::create_dir_all(&pack_dir).expect("create pack dir");
+    fs::write(
+        pack_dir.join("skill-pack.yml"),
+        "name: rust-tools\nversion: 1.2.0\nskills: [cargo.md]\nrequires_tools: [cargo]\n",
+    )
+    .expect("write manifest");
+    fs::write(
+        pack_dir.join("cargo.md"),
+        "---\nname: cargo-expert\ndescription: Cargo workflows\n---\nUse cargo.\n",
+    )
+    .expect("write skill");
+
+    let stdout = ralph_skill_ok(temp_path, &["install", "vendor/rust-pack"]);
+    assert!(stdout.contains("Installed rust-tools@1.2.0 (1 skill(s): cargo-expert)"));
+    assert!(stdout.contains("Requires tools: cargo"));
+    assert!(
+        temp_path
+            .join(".ralph/skills/rust-tools/cargo.md")
+            .is_file()
+    );
+    let lock = fs::read_to_string(temp_path.join(".ralph/skills/skills.lock")).expect("lock");
+    assert!(lock.contains("rust-tools:"));
+    assert!(lock.contains("version: 1.2.0"));
+
+    let stdout = ralph_skill_ok(temp_path, &["list"]);
+    assert!(stdout.contains("cargo-expert"));
+    assert!(stdout.contains("pack rust-tools@1.2.0"));
+    assert!(stdout.contains("Installed packs:"));
+    assert!(stdout.contains("rust-pack"), "source path is shown");
+
+    let json = ralph_skill_ok(temp_path, &["list", "--format", "json"]);
+    let items: serde_json::Value = serde_json::from_str(&json).expect("json");
+    let skill = items
+        .as_array()
+        .expect("array")
+        .iter()
+        .find(|item| item["name"] == "cargo-expert")
+        .expect("pack skill listed");
+    assert_eq!(skill["source"], "pack");
+    assert_eq!(skill["pack"]["version"], "1.2.0");
+    assert_eq!(skill["requires_tools"][0], "cargo");
+
+    let stdout = ralph_skill_ok(temp_path, &["remove", "rust-tools"]);
+    assert!(stdout.contains("Removed skill pack rust-tools"));
+    let output = ralph_skill(temp_path, &["remove", "rust-tools"]);
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_skill_install_rejects_directory_without_manifest() {
+    let temp_dir = TempDir::new().expect("temp dir");
+    let temp_path = temp_dir.path();
+
+    let output = ralph_skill(temp_path, &["install", "."]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("No skill-pack.yml found"), "{stderr}");
+}
+
+#[test]
+fn test_skill_stats_reports_injections_and_loads() {
+    let temp_dir = TempDir::new().expect("temp dir");
+    let temp_path = temp_dir.path();
+
+    let stdout = ralph_skill_ok(temp_path, &["stats"]);
+    assert!(stdout.contains("No skill usage recorded yet"));
+
+    // An injection recorded by the event loop, then two on-demand loads.
+    fs::create_dir_all(temp_path.join(".ralph")).expect("create .ralph");
+    fs::write(
+        temp_path.join(".ralph/skill-usage.jsonl"),
+        r#"{"ts":"2026-01-01T00:00:00Z","skill":"ralph-tools","kind":"injected","iteration":1,"reason":"keyword 'task'"}"#
+            .to_string()
+            + "\n",
+    )
+    .expect("write usage log");
+    ralph_skill_ok(temp_path, &["load", "ralph-tools"]);
+    ralph_skill_ok(temp_path, &["load", "robot-interaction"]);
+
+    let stdout = ralph_skill_ok(temp_path, &["stats"]);
+    let ralph_tools = stdout
+        .lines()
+        .find(|line| line.starts_with("ralph-tools"))
+        .expect("ralph-tools row");
+    assert!(ralph_tools.contains("keyword 'task'"), "{stdout}");
+    assert_eq!(
+        ralph_tools.split_whitespace().take(4).collect::<Vec<_>>(),
+        vec!["ralph-tools", "1", "1", "1"]
+    );
+    assert!(stdout.contains("consider adding triggers): robot-interaction"));
+
+    let json = ralph_skill_ok(temp_path, &["stats", "--format", "json"]);
+    let items: serde_json::Value = serde_json::from_str(&json).expect("valid json");
+    assert_eq!(items[1]["skill"], "robot-interaction");
+    assert_eq!(items[1]["loaded"], 1);
+    assert_eq!(items[1]["injected"], 0);
+}
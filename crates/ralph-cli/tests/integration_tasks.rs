@@ -6,6 +6,7 @@ use tempfile::TempDir;
 
 fn ralph_task(temp_path: &std::path::Path, args: &[&str]) -> std::process::Output {
     Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
         .arg("tools")
         .arg("task")
         .args(args)
@@ -0,0 +1,142 @@
+//! Integration tests for delayed events and watchdogs.
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+/// A fake agent that, on its first run only, defers an event by one
+/// iteration. It never publishes `build.done`.
+const AGENT_SCRIPT: &str = r#"#!/bin/sh
+[ -f .ralph/ran ] && exit 0
+touch .ralph/ran
+events=$(cat .ralph/current-events 2>/dev/null || echo .ralph/events.jsonl)
+echo '{"topic":"review.later","payload":"check again","ts":"2026-01-01T00:00:00Z","after_iterations":1}' >> "$events"
+"#;
+
+fn ralph(temp_path: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
+        .args(args)
+        .current_dir(temp_path)
+        .output()
+        .expect("execute ralph")
+}
+
+#[test]
+fn test_emit_writes_deferred_delivery_fields() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let output = ralph(
+        temp_dir.path(),
+        &[
+            "emit",
+            "review.later",
+            "check again",
+            "--delay",
+            "5m",
+            "--after-iterations",
+            "2",
+        ],
+    );
+    assert!(output.status.success(), "{output:?}");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("Event scheduled: review.later (in 5m or 2 iterations)"),
+        "{stdout}"
+    );
+
+    let line = fs::read_to_string(temp_dir.path().join(".ralph/events.jsonl")).unwrap();
+    let record: serde_json::Value = serde_json::from_str(line.trim()).unwrap();
+    assert_eq!(record["after_iterations"], 2);
+    let deliver_at =
+        chrono::DateTime::parse_from_rfc3339(record["deliver_at"].as_str().unwrap()).unwrap();
+    let delay = deliver_at.with_timezone(&chrono::Utc) - chrono::Utc::now();
+    assert!(
+        delay.num_seconds() > 240 && delay.num_seconds() <= 300,
+        "{delay}"
+    );
+}
+
+#[test]
+fn test_emit_rejects_invalid_delay() {
+    let temp_dir = TempDir::new().unwrap();
+
+    let output = ralph(temp_dir.path(), &["emit", "x", "--delay", "soon"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid --delay"));
+    assert!(!temp_dir.path().join(".ralph/events.jsonl").exists());
+}
+
+#[test]
+fn test_run_delivers_delayed_events_and_fires_watchdogs() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path();
+
+    let agent = temp_path.join("fake-agent");
+    fs::write(&agent, AGENT_SCRIPT).unwrap();
+    fs::set_permissions(&agent, fs::Permissions::from_mode(0o755)).unwrap();
+    fs::create_dir_all(temp_path.join(".ralph")).unwrap();
+
+    let config = format!(
+        r#"
+event_loop:
+  completion_promise: "LOOP_COMPLETE"
+  max_iterations: 4
+  watchdogs:
+    - expect: build.done
+      within_iterations: 2
+      else: build.timeout
+
+cli:
+  backend: fake-agent
+
+backends:
+  fake-agent:
+    command: "{}"
+
+core:
+  scratchpad: ".ralph/agent/scratchpad.md"
+
+features:
+  preflight:
+    enabled: false
+"#,
+        agent.display()
+    );
+    fs::write(temp_path.join("ralph.yml"), config).unwrap();
+
+    let output = ralph(
+        temp_path,
+        &[
+            "run",
+            "--no-tui",
+            "--record-session",
+            "session.jsonl",
+            "-p",
+            "Wait",
+        ],
+    );
+    let logs = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let topics: Vec<String> = fs::read_to_string(temp_path.join("session.jsonl"))
+        .unwrap()
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter_map(|record| record["data"]["topic"].as_str().map(ToString::to_string))
+        .collect();
+    assert!(
+        topics.iter().any(|t| t == "review.later"),
+        "{topics:?}\n{logs}"
+    );
+    assert!(
+        topics.iter().any(|t| t == "build.timeout"),
+        "{topics:?}\n{logs}"
+    );
+}
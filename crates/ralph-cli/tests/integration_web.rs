@@ -68,6 +68,7 @@ exit 1",
         };
 
         let output = Command::new(env!("CARGO_BIN_EXE_ralph"))
+            .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
             .current_dir(workspace)
             .env("PATH", path)
             .args([
@@ -0,0 +1,139 @@
+//! Integration tests for per-hat `write_scope` enforcement.
+#![cfg(unix)]
+
+use std::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::Path;
+use std::process::{Command, Output};
+use tempfile::TempDir;
+
+/// A fake agent that edits a spec (in scope), creates a source file (out of
+/// scope) and publishes its result event. Later iterations do nothing.
+const AGENT_SCRIPT: &str = r#"#!/bin/sh
+[ -f .ralph/reviewed ] && exit 0
+touch .ralph/reviewed
+mkdir -p specs src
+echo "reviewed" > specs/plan.md
+echo "fn sneaky() {}" > src/sneaky.rs
+events=$(cat .ralph/current-events 2>/dev/null || echo .ralph/events.jsonl)
+echo '{"topic":"review.finished","payload":"looks good","ts":"2026-01-01T00:00:00Z"}' >> "$events"
+"#;
+
+fn git(dir: &Path, args: &[&str]) {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("execute git");
+    assert!(output.status.success(), "git {args:?} failed");
+}
+
+fn setup(temp_path: &Path, on_violation: &str) {
+    git(temp_path, &["init", "-q", "--initial-branch=main"]);
+    git(temp_path, &["config", "user.email", "test@test.local"]);
+    git(temp_path, &["config", "user.name", "Test User"]);
+    fs::write(
+        temp_path.join(".gitignore"),
+        ".ralph/\nreviewer-agent\nsession.jsonl\n",
+    )
+    .unwrap();
+    git(temp_path, &["add", "."]);
+    git(temp_path, &["commit", "-q", "-m", "Initial commit"]);
+
+    let agent = temp_path.join("reviewer-agent");
+    fs::write(&agent, AGENT_SCRIPT).unwrap();
+    fs::set_permissions(&agent, fs::Permissions::from_mode(0o755)).unwrap();
+
+    let config = format!(
+        r#"
+event_loop:
+  completion_promise: "LOOP_COMPLETE"
+  starting_event: "review.start"
+  max_iterations: 2
+
+cli:
+  backend: reviewer-agent
+
+backends:
+  reviewer-agent:
+    command: "{}"
+
+hats:
+  reviewer:
+    name: "Reviewer"
+    description: "Reviews the plan"
+    triggers: ["review.start"]
+    publishes: ["review.finished"]
+    instructions: "Review the plan."
+    write_scope:
+      allow: ["specs/**"]
+      on_violation: {on_violation}
+
+core:
+  scratchpad: ".ralph/agent/scratchpad.md"
+
+features:
+  preflight:
+    enabled: false
+"#,
+        agent.display()
+    );
+    fs::write(temp_path.join("ralph.yml"), config).unwrap();
+}
+
+fn run_ralph(temp_path: &Path) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_ralph"))
+        .env("RALPH_USER_CONFIG", "/nonexistent/ralph-user-config.yml")
+        .args(["run", "--no-tui", "--record-session", "session.jsonl"])
+        .args(["-p", "Review the plan"])
+        .current_dir(temp_path)
+        .output()
+        .expect("execute ralph")
+}
+
+/// Topics of the `bus.publish` records in the session recording.
+fn published_topics(temp_path: &Path) -> Vec<String> {
+    fs::read_to_string(temp_path.join("session.jsonl"))
+        .unwrap()
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter_map(|record| record["data"]["topic"].as_str().map(ToString::to_string))
+        .collect()
+}
+
+#[test]
+fn test_write_scope_rejects_events_on_violation() {
+    let temp_dir = TempDir::new().unwrap();
+    setup(temp_dir.path(), "reject");
+
+    let output = run_ralph(temp_dir.path());
+    let logs = format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert!(logs.contains("outside its write_scope"), "{logs}");
+
+    // Changes stay, but the hat's event never reaches the bus
+    assert!(temp_dir.path().join("src/sneaky.rs").exists());
+    let topics = published_topics(temp_dir.path());
+    assert!(topics.iter().any(|t| t == "scope.violation"), "{topics:?}");
+    assert!(!topics.iter().any(|t| t == "review.finished"), "{topics:?}");
+}
+
+#[test]
+fn test_write_scope_reverts_out_of_scope_files() {
+    let temp_dir = TempDir::new().unwrap();
+    setup(temp_dir.path(), "revert");
+
+    run_ralph(temp_dir.path());
+
+    assert!(!temp_dir.path().join("src/sneaky.rs").exists());
+    assert_eq!(
+        fs::read_to_string(temp_dir.path().join("specs/plan.md")).unwrap(),
+        "reviewed\n"
+    );
+    let topics = published_topics(temp_dir.path());
+    assert!(topics.iter().any(|t| t == "scope.violation"), "{topics:?}");
+    assert!(topics.iter().any(|t| t == "review.finished"), "{topics:?}");
+}
@@ -0,0 +1,441 @@
+//! Human approval gates on selected topics.
+//!
+//! Topics marked `requires_approval: true` under `events:` are not delivered
+//! when emitted. The loop holds them and asks a human — through the robot
+//! service when one is configured, otherwise through the TUI or
+//! `ralph loops approve` / `ralph loops reject`. Each answer is appended to
+//! `.ralph/approval-decisions.jsonl` as an `approval.response` and settles the
+//! oldest held event. The agent cannot answer: `ralph emit` refuses the topic
+//! and an `approval.response` in the events JSONL is ignored. `human.response`
+//! is left to `human.interact` questions, so the two never answer each other:
+//!
+//! - **Approved** (`approve`, `yes`, `ok`, `lgtm`, ...) — the event is
+//!   delivered unchanged.
+//! - **Rejected** (anything else) — `<topic>.rejected` is published with the
+//!   human's reason as payload.
+//!
+//! A held event whose `approval_timeout` passes is rejected with a timeout
+//! reason. Held events are persisted to `.ralph/agent/approvals.json` so that
+//! `ralph run --continue` asks again.
+
+use chrono::{DateTime, Utc};
+use ralph_proto::{Event, HatId};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Duration;
+
+use crate::timers::format_duration;
+
+/// Topic of the human's answer to an approval request.
+pub const APPROVAL_RESPONSE_TOPIC: &str = "approval.response";
+
+/// File under a loop's `.ralph/` directory that human decisions are appended to.
+pub const APPROVAL_DECISIONS_FILE: &str = "approval-decisions.jsonl";
+
+/// Replies that approve a held event (matched against the first word).
+const APPROVE_WORDS: &[&str] = &["approve", "approved", "yes", "y", "ok", "okay", "lgtm"];
+
+/// Leading words dropped from a rejection reason.
+const REJECT_WORDS: &[&str] = &["reject", "rejected", "no", "n", "deny", "denied"];
+
+/// Returns the topic published when an event on `topic` is rejected.
+pub fn rejected_topic(topic: &str) -> String {
+    format!("{topic}.rejected")
+}
+
+/// Appends a human's reply to an approval request to the decisions file at
+/// `path`, as an `approval.response` event line.
+pub fn record_decision(path: &Path, reply: &str) -> std::io::Result<()> {
+    use std::io::Write;
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let line = serde_json::json!({
+        "topic": APPROVAL_RESPONSE_TOPIC,
+        "payload": reply,
+        "ts": Utc::now().to_rfc3339(),
+    });
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+    writeln!(file, "{line}")
+}
+
+/// A human's answer to an approval request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApprovalDecision {
+    Approved,
+    Rejected { reason: String },
+}
+
+impl ApprovalDecision {
+    /// Parses an `approval.response` payload.
+    ///
+    /// A reply whose first word is `approve`, `yes`, `ok`, `lgtm` (or similar)
+    /// approves. Anything else rejects, with the reply as the reason; a
+    /// leading `reject:` or `no,` is dropped from it.
+    pub fn parse(reply: &str) -> Self {
+        let reply = reply.trim();
+        let first = reply
+            .split(|c: char| !c.is_alphanumeric())
+            .next()
+            .unwrap_or_default()
+            .to_lowercase();
+        if APPROVE_WORDS.contains(&first.as_str()) {
+            return Self::Approved;
+        }
+
+        let reason = if REJECT_WORDS.contains(&first.as_str()) {
+            reply[first.len()..]
+                .trim_start_matches(|c: char| c == ':' || c == ',' || c == '-' || c.is_whitespace())
+        } else {
+            reply
+        };
+        let reason = if reason.is_empty() {
+            "Rejected without a reason".to_string()
+        } else {
+            reason.to_string()
+        };
+        Self::Rejected { reason }
+    }
+}
+
+/// An event held until a human approves it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PendingApproval {
+    pub topic: String,
+    pub payload: String,
+    /// Hat that emitted the event, restored when it is delivered.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<HatId>,
+    pub requested_at: DateTime<Utc>,
+    /// Deadline after which the event is rejected.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_at: Option<DateTime<Utc>>,
+    /// Whether the human has been asked. Only the oldest held event is asked at a time.
+    #[serde(default, skip_serializing)]
+    pub asked: bool,
+}
+
+impl PendingApproval {
+    /// The question sent to the human, as a structured `human.interact`
+    /// payload with Approve / Reject options whose answer comes back as
+    /// `approval.response`.
+    pub fn question(&self) -> String {
+        let mut question = format!("Approve `{}`?", self.topic);
+        if !self.payload.trim().is_empty() {
+            question.push_str("\n\n");
+            question.push_str(self.payload.trim());
+        }
+        if let Some(due_at) = self.due_at {
+            let within = (due_at - self.requested_at).to_std().unwrap_or_default();
+            question.push_str(&format!(
+                "\n\nRejected automatically after {}.",
+                format_duration(within)
+            ));
+        }
+        question.push_str("\nReply with a reason to reject.");
+        serde_json::json!({
+            "question": question,
+            "options": ["Approve", "Reject"],
+            "reply_topic": APPROVAL_RESPONSE_TOPIC,
+        })
+        .to_string()
+    }
+
+    /// One-line description for status displays, e.g. `deploy.start (9m left)`.
+    pub fn describe(&self, now: DateTime<Utc>) -> String {
+        match self.due_at {
+            Some(due_at) => {
+                let left = (due_at - now).to_std().unwrap_or_default();
+                format!("{} ({} left)", self.topic, format_duration(left))
+            }
+            None => self.topic.clone(),
+        }
+    }
+
+    /// Builds the event published once the human has decided.
+    fn settle(self, decision: ApprovalDecision) -> Event {
+        match decision {
+            ApprovalDecision::Approved => {
+                let event = Event::new(self.topic.as_str(), self.payload);
+                match self.source {
+                    Some(source) => event.with_source(source),
+                    None => event,
+                }
+            }
+            ApprovalDecision::Rejected { reason } => {
+                Event::new(rejected_topic(&self.topic).as_str(), reason)
+            }
+        }
+    }
+}
+
+/// Events held for approval, oldest first.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ApprovalQueue {
+    pending: Vec<PendingApproval>,
+}
+
+impl ApprovalQueue {
+    /// Creates an empty queue.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns true if no events are held.
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+
+    /// Returns the number of held events.
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    /// Iterates over held events, oldest first.
+    pub fn iter(&self) -> impl Iterator<Item = &PendingApproval> {
+        self.pending.iter()
+    }
+
+    /// Holds an event until it is approved, rejected, or `timeout` passes.
+    pub fn hold(&mut self, event: &Event, timeout: Option<Duration>, now: DateTime<Utc>) {
+        let due_at = timeout
+            .and_then(|t| chrono::Duration::from_std(t).ok())
+            .map(|t| now + t);
+        self.pending.push(PendingApproval {
+            topic: event.topic.to_string(),
+            payload: event.payload.clone(),
+            source: event.source.clone(),
+            requested_at: now,
+            due_at,
+            asked: false,
+        });
+    }
+
+    /// Returns the oldest held event if the human has not been asked about it yet.
+    pub fn next_to_ask(&mut self) -> Option<&mut PendingApproval> {
+        self.pending.first_mut().filter(|approval| !approval.asked)
+    }
+
+    /// Marks the oldest held event as not yet asked, so it is asked again.
+    pub fn ask_again(&mut self) {
+        if let Some(approval) = self.pending.first_mut() {
+            approval.asked = false;
+        }
+    }
+
+    /// Settles the oldest held event, returning the event to publish.
+    pub fn decide(&mut self, decision: ApprovalDecision) -> Option<Event> {
+        if self.pending.is_empty() {
+            return None;
+        }
+        Some(self.pending.remove(0).settle(decision))
+    }
+
+    /// Removes held events whose deadline has passed and returns their
+    /// rejection events.
+    pub fn take_expired(&mut self, now: DateTime<Utc>) -> Vec<Event> {
+        let (expired, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut self.pending)
+            .into_iter()
+            .partition(|approval| approval.due_at.is_some_and(|at| now >= at));
+        self.pending = pending;
+        expired
+            .into_iter()
+            .map(|approval| {
+                let within = approval
+                    .due_at
+                    .map(|at| (at - approval.requested_at).to_std().unwrap_or_default())
+                    .unwrap_or_default();
+                let reason = format!("No approval within {}", format_duration(within));
+                approval.settle(ApprovalDecision::Rejected { reason })
+            })
+            .collect()
+    }
+
+    /// Time until the earliest approval deadline, if any.
+    pub fn next_due_in(&self, now: DateTime<Utc>) -> Option<Duration> {
+        self.pending
+            .iter()
+            .filter_map(|approval| approval.due_at)
+            .min()
+            .map(|at| (at - now).to_std().unwrap_or_default())
+    }
+
+    /// Loads saved held events. A missing file yields an empty queue.
+    ///
+    /// Loaded events count as not yet asked, so a resumed loop asks again.
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::new()),
+            Err(e) => return Err(e),
+        };
+        serde_json::from_str(&content)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    /// Saves held events, removing the file when none are held.
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if self.pending.is_empty() {
+            return match std::fs::remove_file(path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+                _ => Ok(()),
+            };
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        std::fs::write(path, json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rejected(reason: &str) -> ApprovalDecision {
+        ApprovalDecision::Rejected {
+            reason: reason.to_string(),
+        }
+    }
+
+    #[test]
+    fn parses_decisions() {
+        assert_eq!(
+            ApprovalDecision::parse("Approve"),
+            ApprovalDecision::Approved
+        );
+        assert_eq!(
+            ApprovalDecision::parse(" yes, go ahead"),
+            ApprovalDecision::Approved
+        );
+        assert_eq!(ApprovalDecision::parse("LGTM"), ApprovalDecision::Approved);
+        assert_eq!(
+            ApprovalDecision::parse("reject: tests are red"),
+            rejected("tests are red")
+        );
+        assert_eq!(
+            ApprovalDecision::parse("no - wait for the freeze"),
+            rejected("wait for the freeze")
+        );
+        assert_eq!(
+            ApprovalDecision::parse("Not before Monday"),
+            rejected("Not before Monday")
+        );
+        assert_eq!(
+            ApprovalDecision::parse("Reject"),
+            rejected("Rejected without a reason")
+        );
+    }
+
+    #[test]
+    fn settles_oldest_first() {
+        let now = Utc::now();
+        let mut queue = ApprovalQueue::new();
+        queue.hold(
+            &Event::new("deploy.start", "v1.2").with_source("deployer"),
+            None,
+            now,
+        );
+        queue.hold(&Event::new("db.migrate", "drop users"), None, now);
+
+        assert_eq!(queue.next_to_ask().unwrap().topic, "deploy.start");
+        queue.next_to_ask().unwrap().asked = true;
+        assert!(queue.next_to_ask().is_none());
+
+        let event = queue.decide(ApprovalDecision::Approved).unwrap();
+        assert_eq!(event.topic.as_str(), "deploy.start");
+        assert_eq!(event.payload, "v1.2");
+        assert_eq!(event.source, Some(HatId::new("deployer")));
+
+        assert_eq!(queue.next_to_ask().unwrap().topic, "db.migrate");
+        let event = queue.decide(rejected("not today")).unwrap();
+        assert_eq!(event.topic.as_str(), "db.migrate.rejected");
+        assert_eq!(event.payload, "not today");
+
+        assert!(queue.is_empty());
+        assert!(queue.decide(ApprovalDecision::Approved).is_none());
+    }
+
+    #[test]
+    fn expired_approvals_are_rejected() {
+        let now = Utc::now();
+        let mut queue = ApprovalQueue::new();
+        queue.hold(
+            &Event::new("deploy.start", ""),
+            Some(Duration::from_mins(10)),
+            now,
+        );
+        queue.hold(&Event::new("db.migrate", ""), None, now);
+
+        assert_eq!(queue.next_due_in(now), Some(Duration::from_mins(10)));
+        assert!(queue.take_expired(now).is_empty());
+
+        let expired = queue.take_expired(now + chrono::Duration::minutes(10));
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].topic.as_str(), "deploy.start.rejected");
+        assert_eq!(expired[0].payload, "No approval within 10m");
+        assert_eq!(queue.len(), 1);
+        assert_eq!(queue.next_due_in(now), None);
+    }
+
+    #[test]
+    fn question_offers_approve_and_reject() {
+        let now = Utc::now();
+        let mut queue = ApprovalQueue::new();
+        queue.hold(
+            &Event::new("deploy.start", "Ship v1.2"),
+            Some(Duration::from_mins(30)),
+            now,
+        );
+        let question: serde_json::Value =
+            serde_json::from_str(&queue.iter().next().unwrap().question()).unwrap();
+
+        let text = question["question"].as_str().unwrap();
+        assert!(
+            text.starts_with("Approve `deploy.start`?\n\nShip v1.2"),
+            "{text}"
+        );
+        assert!(text.contains("after 30m"), "{text}");
+        assert_eq!(
+            question["options"],
+            serde_json::json!(["Approve", "Reject"])
+        );
+        assert_eq!(question["reply_topic"], APPROVAL_RESPONSE_TOPIC);
+    }
+
+    #[test]
+    fn save_and_load_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("agent/approvals.json");
+        let now = Utc::now();
+
+        let mut queue = ApprovalQueue::new();
+        queue.hold(
+            &Event::new("deploy.start", "v1.2").with_source("deployer"),
+            Some(Duration::from_mins(5)),
+            now,
+        );
+        queue.next_to_ask().unwrap().asked = true;
+        queue.save(&path).unwrap();
+
+        let mut loaded = ApprovalQueue::load(&path).unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(
+            loaded.iter().next().unwrap().source,
+            Some(HatId::new("deployer"))
+        );
+        // A resumed loop asks again
+        assert!(loaded.next_to_ask().is_some());
+
+        loaded.decide(ApprovalDecision::Approved);
+        loaded.save(&path).unwrap();
+        assert!(!path.exists());
+        assert!(ApprovalQueue::load(&path).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,591 @@
+//! Declarative backend definitions.
+//!
+//! The `backends:` section of the config describes agent CLIs that Ralph has
+//! no built-in adapter for. A defined backend can be named anywhere a
+//! built-in one is accepted — `cli.backend`, `ralph run -b`, a hat's
+//! `backend`, and `agent_priority`:
+//!
+//! ```yaml
+//! backends:
+//!   aider:
+//!     command: aider
+//!     args: ["--yes-always", "--no-pretty"]
+//!     prompt_flag: "--message"
+//!     interactive:
+//!       args: []
+//!     env:
+//!       AIDER_ANALYTICS: "false"
+//!     version_command: ["aider", "--version"]
+//!     output_format: lines
+//!     line_parser:
+//!       skip: '^─+$'
+//!       tool_call: '^Applied edit to (?P<input>.+)$'
+//!       error: '^Error: (?P<message>.+)$'
+//!     usage:
+//!       cost: '\$([\d.]+) session'
+//! ```
+//!
+//! `output_format` selects how the backend's stdout is read: `text` passes it
+//! through, `claude-stream` and `pi-stream` reuse the built-in NDJSON parsers,
+//! and `lines` runs each line through the [`LineParser`] built from
+//! `line_parser`. `usage` rules pull the run's cost out of its output.
+
+use std::collections::BTreeMap;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::config::ConfigError;
+
+/// Backend names with a built-in adapter.
+pub const BUILTIN_BACKENDS: &[&str] = &[
+    "claude", "kiro", "gemini", "codex", "amp", "copilot", "opencode", "pi",
+];
+
+/// Backend names with special meaning that a definition cannot take.
+const RESERVED_BACKEND_NAMES: &[&str] = &["auto", "custom", "api"];
+
+/// One entry of the `backends:` config map.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackendDefinition {
+    /// Executable to run.
+    pub command: String,
+
+    /// Arguments for headless (autonomous) runs, placed before the prompt.
+    #[serde(default)]
+    pub args: Vec<String>,
+
+    /// Overrides for interactive runs (`ralph plan`, `ralph run -i`).
+    /// Without it, interactive runs use the headless arguments.
+    #[serde(default)]
+    pub interactive: Option<InteractiveDefinition>,
+
+    /// How to pass the prompt: "arg" or "stdin".
+    #[serde(default = "default_prompt_mode")]
+    pub prompt_mode: String,
+
+    /// Flag placed before the prompt argument (e.g. "-p").
+    #[serde(default)]
+    pub prompt_flag: Option<String>,
+
+    /// Flag that selects the model (e.g. "--model"). Without it, hats and
+    /// `cli.model` cannot pick a model for this backend.
+    #[serde(default)]
+    pub model_flag: Option<String>,
+
+    /// Flag that selects the reasoning effort (e.g. "--reasoning").
+    #[serde(default)]
+    pub effort_flag: Option<String>,
+
+    /// Environment variables set on the spawned process.
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+
+    /// Command that succeeds when the backend is installed.
+    /// Defaults to `<command> --version`.
+    #[serde(default)]
+    pub version_command: Option<Vec<String>>,
+
+    /// How the backend's output is parsed.
+    #[serde(default)]
+    pub output_format: BackendOutputFormat,
+
+    /// Line rules for `output_format: lines`.
+    #[serde(default)]
+    pub line_parser: Option<LineParserRules>,
+
+    /// Cost and token extraction rules.
+    #[serde(default)]
+    pub usage: Option<UsageRules>,
+}
+
+fn default_prompt_mode() -> String {
+    "arg".to_string()
+}
+
+/// Interactive-mode overrides for a defined backend.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InteractiveDefinition {
+    /// Arguments for interactive runs, replacing the headless ones.
+    #[serde(default)]
+    pub args: Vec<String>,
+
+    /// Prompt flag for interactive runs. Falls back to the headless flag.
+    #[serde(default)]
+    pub prompt_flag: Option<String>,
+}
+
+/// Output formats a defined backend can declare.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum BackendOutputFormat {
+    /// Plain text, passed through as-is.
+    #[default]
+    Text,
+    /// Claude-compatible `stream-json` NDJSON.
+    ClaudeStream,
+    /// Pi-compatible `--mode json` NDJSON.
+    PiStream,
+    /// Plain text classified line by line with [`LineParserRules`].
+    Lines,
+}
+
+/// Regexes that classify output lines for `output_format: lines`.
+///
+/// Rules are tried in the order skip, error, tool_call, tool_result, text.
+/// Lines matching no rule are text, unless a `text` rule is set, in which
+/// case they are dropped.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LineParserRules {
+    /// Assistant text. The `text` group, if present, is the displayed text.
+    #[serde(default)]
+    pub text: Option<String>,
+
+    /// Tool invocations. Named groups: `name` and `input` (JSON or plain text).
+    #[serde(default)]
+    pub tool_call: Option<String>,
+
+    /// Tool output. Named group: `output`.
+    #[serde(default)]
+    pub tool_result: Option<String>,
+
+    /// Errors. Named group: `message`.
+    #[serde(default)]
+    pub error: Option<String>,
+
+    /// Lines to drop entirely (banners, separators, spinners).
+    #[serde(default)]
+    pub skip: Option<String>,
+}
+
+/// Regexes that extract usage from a backend's output.
+///
+/// Each regex's first capture group must hold a number; the last match in
+/// the output wins. A `cost` match is used as-is, otherwise the cost is
+/// computed from token counts and the per-million-token prices.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct UsageRules {
+    /// Total cost in USD.
+    #[serde(default)]
+    pub cost: Option<String>,
+
+    /// Input (prompt) token count.
+    #[serde(default)]
+    pub input_tokens: Option<String>,
+
+    /// Output (completion) token count.
+    #[serde(default)]
+    pub output_tokens: Option<String>,
+
+    /// USD per million input tokens.
+    #[serde(default)]
+    pub input_cost_per_mtok: f64,
+
+    /// USD per million output tokens.
+    #[serde(default)]
+    pub output_cost_per_mtok: f64,
+}
+
+impl BackendDefinition {
+    /// Checks that the definition is usable under `name`.
+    ///
+    /// # Errors
+    /// Returns `ConfigError::InvalidBackendDefinition` describing the problem.
+    pub fn validate(&self, name: &str) -> Result<(), ConfigError> {
+        let invalid = |reason: String| ConfigError::InvalidBackendDefinition {
+            name: name.to_string(),
+            reason,
+        };
+
+        if BUILTIN_BACKENDS.contains(&name) || RESERVED_BACKEND_NAMES.contains(&name) {
+            return Err(invalid(format!(
+                "'{name}' is a built-in backend name; pick another name"
+            )));
+        }
+        if self.command.trim().is_empty() {
+            return Err(invalid("'command' must not be empty".to_string()));
+        }
+        if !matches!(self.prompt_mode.as_str(), "arg" | "stdin") {
+            return Err(invalid(format!(
+                "prompt_mode must be \"arg\" or \"stdin\", got \"{}\"",
+                self.prompt_mode
+            )));
+        }
+        if self
+            .version_command
+            .as_ref()
+            .is_some_and(|argv| argv.first().is_none_or(|cmd| cmd.trim().is_empty()))
+        {
+            return Err(invalid("'version_command' must not be empty".to_string()));
+        }
+        if self.line_parser.is_some() && self.output_format != BackendOutputFormat::Lines {
+            return Err(invalid(
+                "'line_parser' only applies to output_format: lines".to_string(),
+            ));
+        }
+
+        self.output_rules().map_err(|e| invalid(e.to_string()))?;
+        Ok(())
+    }
+
+    /// The argv used to check that the backend is installed.
+    pub fn version_check(&self) -> Vec<String> {
+        match &self.version_command {
+            Some(argv) if !argv.is_empty() => argv.clone(),
+            _ => vec![self.command.clone(), "--version".to_string()],
+        }
+    }
+
+    /// Compiles the definition's line parser and usage rules.
+    ///
+    /// # Errors
+    /// Returns `OutputRulesError` if a regex is invalid or lacks a required group.
+    pub fn output_rules(&self) -> Result<OutputRules, OutputRulesError> {
+        let line_parser = match (&self.line_parser, self.output_format) {
+            (Some(rules), _) => Some(LineParser::compile(rules)?),
+            (None, BackendOutputFormat::Lines) => {
+                Some(LineParser::compile(&LineParserRules::default())?)
+            }
+            (None, _) => None,
+        };
+        let usage = self
+            .usage
+            .as_ref()
+            .map(UsageExtractor::compile)
+            .transpose()?;
+        Ok(OutputRules { line_parser, usage })
+    }
+}
+
+/// Error compiling a definition's output rules.
+#[derive(Debug, thiserror::Error)]
+pub enum OutputRulesError {
+    #[error("invalid {field} regex: {source}")]
+    Regex {
+        field: &'static str,
+        source: regex::Error,
+    },
+
+    #[error("{field} regex needs a '{group}' capture group")]
+    MissingGroup {
+        field: &'static str,
+        group: &'static str,
+    },
+}
+
+fn compile(
+    field: &'static str,
+    pattern: Option<&String>,
+) -> Result<Option<Regex>, OutputRulesError> {
+    pattern
+        .map(|pattern| {
+            Regex::new(pattern).map_err(|source| OutputRulesError::Regex { field, source })
+        })
+        .transpose()
+}
+
+/// Compiled output handling for a defined backend.
+#[derive(Debug, Clone, Default)]
+pub struct OutputRules {
+    /// Set for `output_format: lines`.
+    pub line_parser: Option<LineParser>,
+    /// Set when the definition has `usage` rules.
+    pub usage: Option<UsageExtractor>,
+}
+
+/// How [`LineParser::parse`] classified a line.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ParsedLine {
+    Text(String),
+    ToolCall { name: String, input: Value },
+    ToolResult(String),
+    Error(String),
+    Skip,
+}
+
+/// Compiled [`LineParserRules`].
+#[derive(Debug, Clone)]
+pub struct LineParser {
+    text: Option<Regex>,
+    tool_call: Option<Regex>,
+    tool_result: Option<Regex>,
+    error: Option<Regex>,
+    skip: Option<Regex>,
+}
+
+impl LineParser {
+    /// Compiles the rules.
+    ///
+    /// # Errors
+    /// Returns `OutputRulesError` if a regex is invalid, or if a `tool_call`
+    /// regex has neither a `name` nor an `input` group.
+    pub fn compile(rules: &LineParserRules) -> Result<Self, OutputRulesError> {
+        let tool_call = compile("line_parser.tool_call", rules.tool_call.as_ref())?;
+        if let Some(regex) = &tool_call
+            && !regex
+                .capture_names()
+                .flatten()
+                .any(|group| group == "name" || group == "input")
+        {
+            return Err(OutputRulesError::MissingGroup {
+                field: "line_parser.tool_call",
+                group: "name",
+            });
+        }
+
+        Ok(Self {
+            text: compile("line_parser.text", rules.text.as_ref())?,
+            tool_call,
+            tool_result: compile("line_parser.tool_result", rules.tool_result.as_ref())?,
+            error: compile("line_parser.error", rules.error.as_ref())?,
+            skip: compile("line_parser.skip", rules.skip.as_ref())?,
+        })
+    }
+
+    /// Classifies one line of output (without its trailing newline).
+    pub fn parse(&self, line: &str) -> ParsedLine {
+        if self.skip.as_ref().is_some_and(|re| re.is_match(line)) {
+            return ParsedLine::Skip;
+        }
+        if let Some(caps) = self.error.as_ref().and_then(|re| re.captures(line)) {
+            return ParsedLine::Error(group_or_match(&caps, "message"));
+        }
+        if let Some(caps) = self.tool_call.as_ref().and_then(|re| re.captures(line)) {
+            let name = caps
+                .name("name")
+                .map_or_else(|| "tool".to_string(), |m| m.as_str().trim().to_string());
+            let input = caps.name("input").map_or(Value::Null, |m| {
+                let raw = m.as_str().trim();
+                serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()))
+            });
+            return ParsedLine::ToolCall { name, input };
+        }
+        if let Some(caps) = self.tool_result.as_ref().and_then(|re| re.captures(line)) {
+            return ParsedLine::ToolResult(group_or_match(&caps, "output"));
+        }
+        match &self.text {
+            None => ParsedLine::Text(line.to_string()),
+            Some(re) => re.captures(line).map_or(ParsedLine::Skip, |caps| {
+                ParsedLine::Text(group_or_match(&caps, "text"))
+            }),
+        }
+    }
+}
+
+fn group_or_match(caps: &regex::Captures<'_>, group: &str) -> String {
+    caps.name(group)
+        .or_else(|| caps.get(0))
+        .map(|m| m.as_str().to_string())
+        .unwrap_or_default()
+}
+
+/// Compiled [`UsageRules`].
+#[derive(Debug, Clone)]
+pub struct UsageExtractor {
+    cost: Option<Regex>,
+    input_tokens: Option<Regex>,
+    output_tokens: Option<Regex>,
+    input_cost_per_mtok: f64,
+    output_cost_per_mtok: f64,
+}
+
+impl UsageExtractor {
+    /// Compiles the rules.
+    ///
+    /// # Errors
+    /// Returns `OutputRulesError` if a regex is invalid or has no capture group.
+    pub fn compile(rules: &UsageRules) -> Result<Self, OutputRulesError> {
+        let with_group = |field: &'static str, pattern: Option<&String>| {
+            let regex = compile(field, pattern)?;
+            if regex.as_ref().is_some_and(|re| re.captures_len() < 2) {
+                return Err(OutputRulesError::MissingGroup {
+                    field,
+                    group: "value",
+                });
+            }
+            Ok(regex)
+        };
+
+        Ok(Self {
+            cost: with_group("usage.cost", rules.cost.as_ref())?,
+            input_tokens: with_group("usage.input_tokens", rules.input_tokens.as_ref())?,
+            output_tokens: with_group("usage.output_tokens", rules.output_tokens.as_ref())?,
+            input_cost_per_mtok: rules.input_cost_per_mtok,
+            output_cost_per_mtok: rules.output_cost_per_mtok,
+        })
+    }
+
+    /// The run's cost in USD, or `None` when nothing matched.
+    pub fn cost(&self, output: &str) -> Option<f64> {
+        if let Some(cost) = last_number(self.cost.as_ref(), output) {
+            return Some(cost);
+        }
+
+        let input = last_number(self.input_tokens.as_ref(), output);
+        let output_tokens = last_number(self.output_tokens.as_ref(), output);
+        if input.is_none() && output_tokens.is_none() {
+            return None;
+        }
+        Some(
+            (input.unwrap_or(0.0) * self.input_cost_per_mtok
+                + output_tokens.unwrap_or(0.0) * self.output_cost_per_mtok)
+                / 1_000_000.0,
+        )
+    }
+}
+
+/// The first capture group of the last match, parsed as a number
+/// (thousands separators allowed).
+fn last_number(regex: Option<&Regex>, text: &str) -> Option<f64> {
+    regex?
+        .captures_iter(text)
+        .filter_map(|caps| caps.get(1))
+        .filter_map(|m| m.as_str().replace(',', "").parse().ok())
+        .last()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn definition(yaml: &str) -> BackendDefinition {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_definition_defaults() {
+        let def = definition("command: my-agent");
+        assert_eq!(def.prompt_mode, "arg");
+        assert_eq!(def.output_format, BackendOutputFormat::Text);
+        assert_eq!(def.version_check(), ["my-agent", "--version"]);
+        assert!(def.validate("my-agent").is_ok());
+
+        let rules = def.output_rules().unwrap();
+        assert!(rules.line_parser.is_none());
+        assert!(rules.usage.is_none());
+    }
+
+    #[test]
+    fn test_definition_rejects_builtin_and_reserved_names() {
+        let def = definition("command: my-agent");
+        for name in ["claude", "pi", "auto", "custom", "api"] {
+            let err = def.validate(name).unwrap_err().to_string();
+            assert!(err.contains("built-in backend name"), "{name}: {err}");
+        }
+    }
+
+    #[test]
+    fn test_definition_rejects_bad_fields() {
+        let cases = [
+            ("command: ''", "'command' must not be empty"),
+            ("command: a\nprompt_mode: file", "prompt_mode must be"),
+            ("command: a\nversion_command: []", "'version_command'"),
+            (
+                "command: a\nline_parser:\n  text: '.*'",
+                "only applies to output_format: lines",
+            ),
+            (
+                "command: a\noutput_format: lines\nline_parser:\n  error: '(['",
+                "invalid line_parser.error regex",
+            ),
+            (
+                "command: a\noutput_format: lines\nline_parser:\n  tool_call: '^> '",
+                "needs a 'name' capture group",
+            ),
+            (
+                "command: a\nusage:\n  cost: 'cost'",
+                "needs a 'value' capture group",
+            ),
+        ];
+        for (yaml, expected) in cases {
+            let err = definition(yaml).validate("mine").unwrap_err().to_string();
+            assert!(err.contains(expected), "{yaml}: {err}");
+        }
+    }
+
+    #[test]
+    fn test_line_parser_classifies_lines() {
+        let parser = LineParser::compile(&LineParserRules {
+            tool_call: Some(r"^> (?P<name>\w+) (?P<input>.*)$".to_string()),
+            tool_result: Some(r"^< (?P<output>.*)$".to_string()),
+            error: Some(r"^ERROR: (?P<message>.*)$".to_string()),
+            skip: Some(r"^-+$".to_string()),
+            text: None,
+        })
+        .unwrap();
+
+        assert_eq!(parser.parse("-----"), ParsedLine::Skip);
+        assert_eq!(
+            parser.parse("ERROR: disk full"),
+            ParsedLine::Error("disk full".to_string())
+        );
+        assert_eq!(
+            parser.parse(r#"> read {"path":"a.rs"}"#),
+            ParsedLine::ToolCall {
+                name: "read".to_string(),
+                input: serde_json::json!({ "path": "a.rs" }),
+            }
+        );
+        assert_eq!(
+            parser.parse("> shell cargo test"),
+            ParsedLine::ToolCall {
+                name: "shell".to_string(),
+                input: Value::String("cargo test".to_string()),
+            }
+        );
+        assert_eq!(
+            parser.parse("< 3 passed"),
+            ParsedLine::ToolResult("3 passed".to_string())
+        );
+        assert_eq!(
+            parser.parse("All done."),
+            ParsedLine::Text("All done.".to_string())
+        );
+    }
+
+    #[test]
+    fn test_line_parser_text_rule_filters_other_lines() {
+        let parser = LineParser::compile(&LineParserRules {
+            text: Some(r"^assistant: (?P<text>.*)$".to_string()),
+            ..LineParserRules::default()
+        })
+        .unwrap();
+
+        assert_eq!(
+            parser.parse("assistant: hello"),
+            ParsedLine::Text("hello".to_string())
+        );
+        assert_eq!(parser.parse("debug: noise"), ParsedLine::Skip);
+    }
+
+    #[test]
+    fn test_usage_prefers_reported_cost() {
+        let usage = UsageExtractor::compile(&UsageRules {
+            cost: Some(r"session cost: \$([\d.]+)".to_string()),
+            input_tokens: Some(r"in=([\d,]+)".to_string()),
+            input_cost_per_mtok: 3.0,
+            ..UsageRules::default()
+        })
+        .unwrap();
+
+        let output = "session cost: $0.10\nin=1,000\nsession cost: $0.25\n";
+        assert_eq!(usage.cost(output), Some(0.25));
+        assert_eq!(usage.cost("nothing here"), None);
+    }
+
+    #[test]
+    fn test_usage_computes_cost_from_tokens() {
+        let usage = UsageExtractor::compile(&UsageRules {
+            input_tokens: Some(r"in=([\d,]+)".to_string()),
+            output_tokens: Some(r"out=(\d+)".to_string()),
+            input_cost_per_mtok: 3.0,
+            output_cost_per_mtok: 15.0,
+            ..UsageRules::default()
+        })
+        .unwrap();
+
+        let cost = usage.cost("tokens: in=1,000,000 out=200000").unwrap();
+        assert!((cost - 6.0).abs() < 1e-9, "{cost}");
+    }
+}
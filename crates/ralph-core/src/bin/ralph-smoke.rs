@@ -0,0 +1,259 @@
+//! # ralph-smoke
+//!
+//! Runs JSONL smoke fixtures through `SmokeRunner` and reports the results
+//! as text, JUnit XML or TAP, so CI can consume them like the e2e reports.
+//!
+//! ```bash
+//! # Every fixture in a directory, as JUnit XML
+//! ralph-smoke --format junit --output smoke.xml crates/ralph-core/tests/fixtures
+//!
+//! # Individual fixtures, as TAP on stdout
+//! ralph-smoke --format tap tests/fixtures/basic_session.jsonl
+//! ```
+//!
+//! Each fixture directory becomes a test suite and each fixture a test case.
+//! Exits non-zero if any fixture fails.
+
+use ralph_core::testing::report::{TestCase, junit_xml, tap};
+use ralph_core::testing::{SmokeRunner, SmokeTestConfig, list_fixtures};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+const USAGE: &str = "\
+Usage: ralph-smoke [OPTIONS] <FIXTURE|DIR>...
+
+Options:
+  -f, --format <text|junit|tap>  Output format [default: text]
+  -o, --output <FILE>            Write the report to FILE instead of stdout
+      --timeout <SECS>           Per-fixture timeout in seconds [default: 30]
+  -h, --help                     Print help";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Junit,
+    Tap,
+}
+
+#[derive(Debug)]
+struct Options {
+    format: Format,
+    output: Option<PathBuf>,
+    timeout: Duration,
+    paths: Vec<PathBuf>,
+}
+
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Option<Options>, String> {
+    let mut options = Options {
+        format: Format::Text,
+        output: None,
+        timeout: Duration::from_secs(30),
+        paths: Vec::new(),
+    };
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| {
+            args.next()
+                .ok_or_else(|| format!("{flag} requires a value"))
+        };
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "-f" | "--format" => {
+                options.format = match value(&arg)?.as_str() {
+                    "text" => Format::Text,
+                    "junit" => Format::Junit,
+                    "tap" => Format::Tap,
+                    other => return Err(format!("unknown format '{other}'")),
+                };
+            }
+            "-o" | "--output" => options.output = Some(PathBuf::from(value(&arg)?)),
+            "--timeout" => {
+                let secs = value(&arg)?;
+                let secs: u64 = secs
+                    .parse()
+                    .map_err(|_| format!("invalid timeout '{secs}'"))?;
+                options.timeout = Duration::from_secs(secs);
+            }
+            flag if flag.starts_with('-') => return Err(format!("unknown option '{flag}'")),
+            _ => options.paths.push(PathBuf::from(arg)),
+        }
+    }
+
+    if options.paths.is_empty() {
+        return Err("no fixtures given".to_string());
+    }
+    Ok(Some(options))
+}
+
+/// Expands directories into their fixtures, paired with the suite name.
+fn collect_fixtures(paths: &[PathBuf]) -> Result<Vec<(String, PathBuf)>, String> {
+    let mut fixtures = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            let suite = suite_name(path);
+            let found = list_fixtures(path)
+                .map_err(|e| format!("failed to list {}: {e}", path.display()))?;
+            fixtures.extend(found.into_iter().map(|f| (suite.clone(), f)));
+        } else {
+            let suite = path
+                .parent()
+                .map_or_else(|| "smoke".to_string(), suite_name);
+            fixtures.push((suite, path.clone()));
+        }
+    }
+    Ok(fixtures)
+}
+
+fn suite_name(dir: &Path) -> String {
+    dir.file_name()
+        .map_or_else(|| "smoke".to_string(), |n| n.to_string_lossy().into_owned())
+}
+
+fn run_fixture(suite: &str, fixture: &Path, timeout: Duration) -> TestCase {
+    let name = fixture.file_stem().map_or_else(
+        || fixture.display().to_string(),
+        |s| s.to_string_lossy().into_owned(),
+    );
+    let config = SmokeTestConfig::new(fixture).with_timeout(timeout);
+
+    let start = Instant::now();
+    let outcome = SmokeRunner::run(&config);
+    let duration = start.elapsed();
+
+    match outcome {
+        Ok(result) if result.completed_successfully() => TestCase::passed(suite, name, duration),
+        Ok(result) => TestCase::failed(
+            suite,
+            name,
+            duration,
+            format!("terminated with {:?}", result.termination_reason()),
+            format!(
+                "iterations: {}\nevents: {}\noutput_bytes: {}",
+                result.iterations_run(),
+                result.event_count(),
+                result.output_bytes()
+            ),
+        ),
+        Err(e) => TestCase::failed(suite, name, duration, e.to_string(), ""),
+    }
+}
+
+fn render_text(cases: &[TestCase]) -> String {
+    let mut out = String::new();
+    for case in cases {
+        match &case.failure {
+            None => out.push_str(&format!("PASS {}/{}\n", case.suite, case.name)),
+            Some(failure) => {
+                out.push_str(&format!(
+                    "FAIL {}/{}: {}\n",
+                    case.suite, case.name, failure.message
+                ));
+            }
+        }
+    }
+    let failed = cases.iter().filter(|c| !c.is_passed()).count();
+    out.push_str(&format!(
+        "\n{} passed, {} failed\n",
+        cases.len() - failed,
+        failed
+    ));
+    out
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(e) => {
+            eprintln!("error: {e}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let fixtures = match collect_fixtures(&options.paths) {
+        Ok(fixtures) => fixtures,
+        Err(e) => {
+            eprintln!("error: {e}");
+            return ExitCode::from(2);
+        }
+    };
+
+    let cases: Vec<TestCase> = fixtures
+        .iter()
+        .map(|(suite, fixture)| run_fixture(suite, fixture, options.timeout))
+        .collect();
+
+    let report = match options.format {
+        Format::Text => render_text(&cases),
+        Format::Junit => junit_xml("ralph-smoke", &cases),
+        Format::Tap => tap(&cases),
+    };
+
+    match &options.output {
+        Some(path) => {
+            if let Err(e) = std::fs::write(path, &report) {
+                eprintln!("error: failed to write {}: {e}", path.display());
+                return ExitCode::from(2);
+            }
+        }
+        None => print!("{report}"),
+    }
+
+    if cases.iter().all(TestCase::is_passed) {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| (*s).to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let options = parse_args(args(&[
+            "-f",
+            "junit",
+            "-o",
+            "out.xml",
+            "--timeout",
+            "5",
+            "fx",
+        ]))
+        .unwrap()
+        .unwrap();
+        assert_eq!(options.format, Format::Junit);
+        assert_eq!(options.output, Some(PathBuf::from("out.xml")));
+        assert_eq!(options.timeout, Duration::from_secs(5));
+        assert_eq!(options.paths, vec![PathBuf::from("fx")]);
+    }
+
+    #[test]
+    fn test_parse_args_errors() {
+        assert!(parse_args(args(&[])).is_err());
+        assert!(parse_args(args(&["--format", "xml", "fx"])).is_err());
+        assert!(parse_args(args(&["--format"])).is_err());
+        assert!(parse_args(args(&["--help"])).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_run_missing_fixture_fails() {
+        let case = run_fixture(
+            "smoke",
+            Path::new("/nonexistent.jsonl"),
+            Duration::from_secs(1),
+        );
+        assert!(!case.is_passed());
+        assert_eq!(case.name, "nonexistent");
+    }
+}
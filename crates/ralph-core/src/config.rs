@@ -3,7 +3,7 @@
 //! This module supports both v1.x flat configuration format and v2.0 nested format.
 //! Users can switch from Python v1.x to Rust v2.0 with zero config changes.
 
-use ralph_proto::Topic;
+use ralph_proto::{Condition, RobotAccessRule, Topic};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -34,6 +34,20 @@ pub struct RalphConfig {
     #[serde(default)]
     pub hats: HashMap<String, HatConfig>,
 
+    /// Declarative backend definitions, keyed by backend name.
+    /// A defined name is accepted anywhere a built-in backend name is.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub backends: HashMap<String, crate::backend_definition::BackendDefinition>,
+
+    /// Runs backends inside Linux namespaces with a read-only filesystem
+    /// outside the workspace.
+    #[serde(default)]
+    pub sandbox: crate::sandbox::SandboxConfig,
+
+    /// Masks secrets in events, diagnostics, recordings and robot messages.
+    #[serde(default)]
+    pub redaction: crate::redaction::RedactionConfig,
+
     /// Event metadata definitions (optional).
     /// Defines what each event topic means, enabling auto-derived instructions.
     /// If a hat uses custom events, define them here for proper behavior injection.
@@ -133,6 +147,11 @@ pub struct RalphConfig {
     /// RObot (Ralph-Orchestrator bot) configuration for Telegram-based interaction.
     #[serde(default, rename = "RObot")]
     pub robot: RobotConfig,
+
+    /// Recurring loops run by `ralph schedule daemon`, alongside any in
+    /// `.ralph/schedules.yml`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub schedules: Vec<crate::schedule::ScheduleEntry>,
 }
 
 fn default_true() -> bool {
@@ -147,6 +166,9 @@ impl Default for RalphConfig {
             cli: CliConfig::default(),
             core: CoreConfig::default(),
             hats: HashMap::new(),
+            backends: HashMap::new(),
+            sandbox: crate::sandbox::SandboxConfig::default(),
+            redaction: crate::redaction::RedactionConfig::default(),
             events: HashMap::new(),
             // V1 compatibility fields
             agent: None,
@@ -178,6 +200,8 @@ impl Default for RalphConfig {
             features: FeaturesConfig::default(),
             // RObot (Ralph-Orchestrator bot)
             robot: RobotConfig::default(),
+            // Scheduled loops
+            schedules: Vec::new(),
         }
     }
 }
@@ -383,6 +407,19 @@ impl RalphConfig {
             return Err(ConfigError::CustomBackendRequiresCommand);
         }
 
+        // Check api backend has a model
+        if self.cli.backend == "api"
+            && self
+                .cli
+                .api
+                .model
+                .as_ref()
+                .or(self.cli.model.as_ref())
+                .is_none_or(String::is_empty)
+        {
+            return Err(ConfigError::ApiBackendRequiresModel);
+        }
+
         // Check for deferred features
         if self.archive_prompts {
             warnings.push(ConfigWarning::DeferredFeature {
@@ -437,6 +474,58 @@ impl RalphConfig {
         // Validate RObot config
         self.robot.validate()?;
 
+        // Validate declarative backend definitions
+        for (name, definition) in &self.backends {
+            definition.validate(name)?;
+        }
+
+        // Check reasoning effort levels
+        if let Some(effort) = &self.cli.effort {
+            check_effort("cli.effort", effort)?;
+        }
+        for (hat_id, hat_config) in &self.hats {
+            if let Some(effort) = &hat_config.effort {
+                check_effort(&format!("hats.{hat_id}.effort"), effort)?;
+            }
+        }
+
+        // Check watchdog deadlines
+        for watchdog in &self.event_loop.watchdogs {
+            watchdog
+                .validate()
+                .map_err(|reason| ConfigError::InvalidWatchdog {
+                    expect: watchdog.expect.clone(),
+                    reason,
+                })?;
+        }
+
+        // Check approval timeouts parse
+        for (topic, metadata) in &self.events {
+            if let Some(timeout) = &metadata.approval_timeout {
+                crate::timers::parse_duration(timeout).map_err(|reason| {
+                    ConfigError::InvalidApprovalTimeout {
+                        topic: topic.clone(),
+                        reason,
+                    }
+                })?;
+            }
+        }
+
+        // Check trigger conditions parse
+        for (hat_id, hat_config) in &self.hats {
+            hat_config.parse_conditions(hat_id)?;
+        }
+
+        // Check redaction patterns compile
+        for pattern in &self.redaction.patterns {
+            if let Err(e) = regex::Regex::new(pattern) {
+                return Err(ConfigError::InvalidRedactionPattern {
+                    pattern: pattern.clone(),
+                    reason: e.to_string(),
+                });
+            }
+        }
+
         // Check for required description field on all hats
         for (hat_id, hat_config) in &self.hats {
             if hat_config
@@ -514,6 +603,20 @@ impl RalphConfig {
     }
 }
 
+/// Reasoning effort levels accepted by `cli.effort` and hat `effort`.
+pub const EFFORT_LEVELS: &[&str] = &["minimal", "low", "medium", "high"];
+
+fn check_effort(field: &str, effort: &str) -> Result<(), ConfigError> {
+    if EFFORT_LEVELS.contains(&effort) {
+        Ok(())
+    } else {
+        Err(ConfigError::InvalidEffort {
+            field: field.to_string(),
+            value: effort.to_string(),
+        })
+    }
+}
+
 /// Configuration warnings emitted during validation.
 #[derive(Debug, Clone)]
 pub enum ConfigWarning {
@@ -625,6 +728,30 @@ pub struct EventLoopConfig {
     /// ```
     #[serde(default)]
     pub initial_prompt_template: Option<String>,
+
+    /// Approximate token budget for each iteration prompt (0 = unlimited).
+    ///
+    /// When the assembled prompt exceeds the budget, lower-priority sections
+    /// are trimmed in order: memories (tail cut), skill index (dropped), then
+    /// the oldest scratchpad content. Use `ralph prompt preview` to inspect
+    /// section sizes.
+    #[serde(default)]
+    pub prompt_budget: usize,
+
+    /// Watchdogs that publish a fallback event when an expected event is late.
+    ///
+    /// Example:
+    /// ```yaml
+    /// event_loop:
+    ///   watchdogs:
+    ///     - expect: build.done
+    ///       after: build.task
+    ///       within: 20m
+    ///       within_iterations: 3
+    ///       else: build.timeout
+    /// ```
+    #[serde(default)]
+    pub watchdogs: Vec<crate::timers::WatchdogConfig>,
 }
 
 fn default_prompt_file() -> String {
@@ -663,6 +790,8 @@ impl Default for EventLoopConfig {
             mutation_score_warn_threshold: None,
             persistent: false,
             initial_prompt_template: None,
+            prompt_budget: 0,
+            watchdogs: Vec::new(),
         }
     }
 }
@@ -755,7 +884,7 @@ impl CoreConfig {
 /// CLI backend configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CliConfig {
-    /// Backend to use: "claude", "kiro", "gemini", "codex", "amp", "pi", or "custom".
+    /// Backend to use: "claude", "kiro", "gemini", "codex", "amp", "pi", "api", or "custom".
     #[serde(default = "default_backend")]
     pub backend: String,
 
@@ -787,6 +916,154 @@ pub struct CliConfig {
     /// If None, defaults to "-p" for arg mode.
     #[serde(default)]
     pub prompt_flag: Option<String>,
+
+    /// How the loop reacts when the backend fails with a rate limit, an
+    /// exhausted quota, an auth error, or a crash.
+    #[serde(default)]
+    pub recovery: BackendRecoveryConfig,
+
+    /// Settings for the `api` backend, which calls a chat-completions
+    /// endpoint directly instead of spawning a CLI.
+    #[serde(default)]
+    pub api: ApiBackendConfig,
+
+    /// Model to request from the backend (e.g. "sonnet", "gpt-5-codex").
+    /// Hats without their own `model` inherit this one.
+    #[serde(default)]
+    pub model: Option<String>,
+
+    /// Reasoning effort to request: "minimal", "low", "medium", or "high".
+    /// Hats without their own `effort` inherit this one.
+    #[serde(default)]
+    pub effort: Option<String>,
+}
+
+/// Backend failure recovery: rate-limit backoff and failover.
+///
+/// Rate-limited iterations are retried after the backend's reset time (or
+/// an exponential backoff when it gives none). With `failover` enabled, the
+/// loop switches to the next available backend in `agent_priority` when the
+/// current one is out of quota, unauthenticated, keeps crashing, or stays
+/// rate-limited.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackendRecoveryConfig {
+    /// Wait and retry rate-limited iterations.
+    #[serde(default = "default_true")]
+    pub backoff: bool,
+
+    /// First wait when the backend reports no reset time; doubles per retry.
+    #[serde(default = "default_initial_backoff_secs")]
+    pub initial_backoff_secs: u64,
+
+    /// Longest wait. A rate limit that resets later than this is treated as
+    /// exhausted rather than waited out.
+    #[serde(default = "default_max_backoff_secs")]
+    pub max_backoff_secs: u64,
+
+    /// Rate-limit retries per iteration before giving up on the backend.
+    #[serde(default = "default_max_rate_limit_retries")]
+    pub max_retries: u32,
+
+    /// Switch to the next available backend in `agent_priority` for the
+    /// remaining iterations when the current one cannot continue.
+    #[serde(default)]
+    pub failover: bool,
+}
+
+fn default_initial_backoff_secs() -> u64 {
+    30
+}
+
+fn default_max_backoff_secs() -> u64 {
+    900
+}
+
+fn default_max_rate_limit_retries() -> u32 {
+    3
+}
+
+impl Default for BackendRecoveryConfig {
+    fn default() -> Self {
+        Self {
+            backoff: true,
+            initial_backoff_secs: default_initial_backoff_secs(),
+            max_backoff_secs: default_max_backoff_secs(),
+            max_retries: default_max_rate_limit_retries(),
+            failover: false,
+        }
+    }
+}
+
+/// The `api` backend: an OpenAI-compatible chat-completions endpoint.
+///
+/// Ralph runs the tool loop itself (file reads, writes and edits, shell
+/// commands, and `ralph emit`). File tools stay inside the workspace; shell
+/// commands are only restricted by the sandbox, when enabled.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApiBackendConfig {
+    /// Base URL of the API; `/chat/completions` is appended.
+    #[serde(default = "default_api_base_url")]
+    pub base_url: String,
+
+    /// Model name sent with every request. Required for the `api` backend.
+    #[serde(default)]
+    pub model: Option<String>,
+
+    /// Environment variable holding the API key. No `Authorization` header
+    /// is sent when it is unset, which suits local servers.
+    #[serde(default = "default_api_key_env")]
+    pub api_key_env: String,
+
+    /// Most model round trips per iteration before the iteration fails.
+    #[serde(default = "default_api_max_turns")]
+    pub max_turns: u32,
+
+    /// Completion token limit per request.
+    #[serde(default)]
+    pub max_tokens: Option<u32>,
+
+    /// Timeout for each `run_command` tool call.
+    #[serde(default = "default_api_command_timeout_secs")]
+    pub command_timeout_secs: u64,
+
+    /// Price per million prompt tokens, for cost reporting.
+    #[serde(default)]
+    pub input_cost_per_mtok: f64,
+
+    /// Price per million completion tokens, for cost reporting.
+    #[serde(default)]
+    pub output_cost_per_mtok: f64,
+}
+
+fn default_api_base_url() -> String {
+    "https://api.openai.com/v1".to_string()
+}
+
+fn default_api_key_env() -> String {
+    "OPENAI_API_KEY".to_string()
+}
+
+fn default_api_max_turns() -> u32 {
+    50
+}
+
+fn default_api_command_timeout_secs() -> u64 {
+    300
+}
+
+impl Default for ApiBackendConfig {
+    fn default() -> Self {
+        Self {
+            base_url: default_api_base_url(),
+            model: None,
+            api_key_env: default_api_key_env(),
+            max_turns: default_api_max_turns(),
+            max_tokens: None,
+            command_timeout_secs: default_api_command_timeout_secs(),
+            input_cost_per_mtok: 0.0,
+            output_cost_per_mtok: 0.0,
+        }
+    }
 }
 
 fn default_backend() -> String {
@@ -815,6 +1092,10 @@ impl Default for CliConfig {
             idle_timeout_secs: default_idle_timeout(),
             args: Vec::new(),
             prompt_flag: None,
+            recovery: BackendRecoveryConfig::default(),
+            api: ApiBackendConfig::default(),
+            model: None,
+            effort: None,
         }
     }
 }
@@ -999,7 +1280,7 @@ impl Default for SkillsConfig {
 /// Per-skill configuration override.
 ///
 /// Allows enabling/disabling individual skills and overriding their
-/// frontmatter fields (hats, backends, tags, auto_inject).
+/// frontmatter fields (hats, backends, tags, auto_inject, triggers).
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SkillOverride {
     /// Disable a discovered skill.
@@ -1021,6 +1302,10 @@ pub struct SkillOverride {
     /// Inject full content into prompt (not just index entry).
     #[serde(default)]
     pub auto_inject: Option<bool>,
+
+    /// Replace the skill's `triggers:` frontmatter.
+    #[serde(default)]
+    pub triggers: Option<crate::skill_triggers::SkillTriggers>,
 }
 
 /// Preflight check configuration.
@@ -1161,6 +1446,8 @@ impl TuiConfig {
 ///     description: "Deployment has been requested"
 ///     on_trigger: "Prepare artifacts, validate config, check dependencies"
 ///     on_publish: "Signal that deployment should begin"
+///     requires_approval: true
+///     approval_timeout: 30m
 /// ```
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct EventMetadata {
@@ -1177,6 +1464,16 @@ pub struct EventMetadata {
     /// Describes when/how the hat should emit this event.
     #[serde(default)]
     pub on_publish: String,
+
+    /// Hold the event until a human approves it; a rejection publishes
+    /// `<topic>.rejected` instead. The key may be a glob pattern.
+    #[serde(default)]
+    pub requires_approval: bool,
+
+    /// How long to wait for approval (e.g. `30m`) before rejecting.
+    /// Waits indefinitely when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval_timeout: Option<String>,
 }
 
 /// Backend configuration for a hat.
@@ -1267,10 +1564,29 @@ pub struct HatConfig {
     #[serde(default)]
     pub backend: Option<HatBackend>,
 
+    /// Model for this hat (inherits from cli.model if not specified).
+    #[serde(default)]
+    pub model: Option<String>,
+
+    /// Reasoning effort for this hat (inherits from cli.effort if not specified).
+    #[serde(default)]
+    pub effort: Option<String>,
+
     /// Default event to publish if hat forgets to write an event.
     #[serde(default)]
     pub default_publishes: Option<String>,
 
+    /// Payload condition for this hat's triggers.
+    ///
+    /// Either one expression applied to every trigger, or a map from trigger
+    /// to expression. See `ralph_proto::Condition` for the syntax.
+    #[serde(default)]
+    pub when: Option<TriggerWhen>,
+
+    /// Paths this hat may modify, checked after each of its iterations.
+    #[serde(default)]
+    pub write_scope: Option<crate::write_scope::WriteScope>,
+
     /// Maximum number of times this hat may be activated in a single loop run.
     ///
     /// When the limit is exceeded, the orchestrator publishes `<hat_id>.exhausted`
@@ -1288,6 +1604,54 @@ impl HatConfig {
     pub fn publish_topics(&self) -> Vec<Topic> {
         self.publishes.iter().map(|s| Topic::new(s)).collect()
     }
+
+    /// Returns `(trigger, expression)` pairs from the `when` field.
+    pub fn trigger_conditions(&self) -> Vec<(&str, &str)> {
+        match &self.when {
+            None => Vec::new(),
+            Some(TriggerWhen::All(expression)) => self
+                .triggers
+                .iter()
+                .map(|trigger| (trigger.as_str(), expression.as_str()))
+                .collect(),
+            Some(TriggerWhen::PerTrigger(map)) => map
+                .iter()
+                .map(|(trigger, expression)| (trigger.as_str(), expression.as_str()))
+                .collect(),
+        }
+    }
+
+    /// Parses the `when` field into conditions keyed by trigger.
+    pub fn parse_conditions(
+        &self,
+        hat_id: &str,
+    ) -> Result<HashMap<String, Condition>, ConfigError> {
+        let mut conditions = HashMap::new();
+        for (trigger, expression) in self.trigger_conditions() {
+            let invalid = |reason: String| ConfigError::InvalidCondition {
+                hat: hat_id.to_string(),
+                trigger: trigger.to_string(),
+                reason,
+            };
+            if !self.triggers.iter().any(|t| t == trigger) {
+                return Err(invalid("not one of the hat's triggers".to_string()));
+            }
+            let condition = Condition::parse(expression)
+                .map_err(|e| invalid(format!("'{expression}': {e}")))?;
+            conditions.insert(trigger.to_string(), condition);
+        }
+        Ok(conditions)
+    }
+}
+
+/// The `when` field of a hat: payload conditions for its triggers.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TriggerWhen {
+    /// One expression applied to every trigger.
+    All(String),
+    /// Expressions keyed by trigger; unlisted triggers are unconditional.
+    PerTrigger(std::collections::BTreeMap<String, String>),
 }
 
 /// RObot (Ralph-Orchestrator bot) configuration.
@@ -1305,6 +1669,11 @@ impl HatConfig {
 ///   checkin_interval_seconds: 120  # Optional: send status every 2 min
 ///   telegram:
 ///     bot_token: "..."  # Or set RALPH_TELEGRAM_BOT_TOKEN env var
+///   access:                        # Optional: omit to allow the single detected chat
+///     - chat_id: -1001234567890    # everyone in this group can view
+///       role: viewer
+///     - user_id: 123456789         # this user can operate from any allowed chat
+///       role: operator
 /// ```
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct RobotConfig {
@@ -1324,11 +1693,36 @@ pub struct RobotConfig {
     /// Telegram bot configuration.
     #[serde(default)]
     pub telegram: Option<TelegramBotConfig>,
+
+    /// Chats and users allowed to talk to the bot, with their roles.
+    /// Empty means unrestricted (single-chat setups).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub access: Vec<RobotAccessRule>,
 }
 
 impl RobotConfig {
     /// Validates the RObot config. Returns an error if enabled but misconfigured.
     pub fn validate(&self) -> Result<(), ConfigError> {
+        if let Some(index) = self
+            .access
+            .iter()
+            .position(|rule| rule.chat_id.is_none() && rule.user_id.is_none())
+        {
+            return Err(ConfigError::RobotMissingField {
+                field: format!("RObot.access[{index}]"),
+                hint: "each access rule needs a chat_id, a user_id, or both".to_string(),
+            });
+        }
+
+        if let Some(api_url) = self.resolve_api_url()
+            && !(api_url.starts_with("http://") || api_url.starts_with("https://"))
+        {
+            return Err(ConfigError::RobotMissingField {
+                field: "RObot.telegram.api_url".to_string(),
+                hint: format!("'{api_url}' must be an http:// or https:// URL"),
+            });
+        }
+
         if !self.enabled {
             return Ok(());
         }
@@ -1384,6 +1778,21 @@ impl RobotConfig {
                 .flatten()
             })
     }
+
+    /// Resolves the Bot API base URL, if not the public Telegram server.
+    ///
+    /// `RALPH_TELEGRAM_API_URL` takes precedence over `RObot.telegram.api_url`.
+    pub fn resolve_api_url(&self) -> Option<String> {
+        std::env::var("RALPH_TELEGRAM_API_URL")
+            .ok()
+            .or_else(|| {
+                self.telegram
+                    .as_ref()
+                    .and_then(|telegram| telegram.api_url.clone())
+            })
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty())
+    }
 }
 
 /// Telegram bot configuration.
@@ -1391,6 +1800,11 @@ impl RobotConfig {
 pub struct TelegramBotConfig {
     /// Bot token. Optional if `RALPH_TELEGRAM_BOT_TOKEN` env var is set.
     pub bot_token: Option<String>,
+
+    /// Base URL of the Bot API server (default `https://api.telegram.org`).
+    /// Point it at a self-hosted server, or a local mock to run offline.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub api_url: Option<String>,
 }
 
 /// Configuration errors.
@@ -1424,6 +1838,33 @@ pub enum ConfigError {
     )]
     CustomBackendRequiresCommand,
 
+    #[error(
+        "API backend requires a model.\nFix: set 'cli.api.model' (or 'cli.model') in your config."
+    )]
+    ApiBackendRequiresModel,
+
+    #[error("Invalid backend definition 'backends.{name}': {reason}")]
+    InvalidBackendDefinition { name: String, reason: String },
+
+    #[error("Invalid '{field}': \"{value}\" (expected minimal, low, medium, or high)")]
+    InvalidEffort { field: String, value: String },
+
+    #[error("Invalid redaction pattern '{pattern}': {reason}")]
+    InvalidRedactionPattern { pattern: String, reason: String },
+
+    #[error("Invalid watchdog for '{expect}' in event_loop.watchdogs: {reason}")]
+    InvalidWatchdog { expect: String, reason: String },
+
+    #[error("Invalid approval_timeout for event '{topic}': {reason}")]
+    InvalidApprovalTimeout { topic: String, reason: String },
+
+    #[error("Invalid condition for trigger '{trigger}' on hat '{hat}': {reason}")]
+    InvalidCondition {
+        hat: String,
+        trigger: String,
+        reason: String,
+    },
+
     #[error(
         "Reserved trigger '{trigger}' used by hat '{hat}' - task.start and task.resume are reserved for Ralph (the coordinator). Use a delegated event like 'work.start' instead.\nSee: docs/reference/troubleshooting.md#reserved-trigger"
     )]
@@ -1438,6 +1879,23 @@ pub enum ConfigError {
         "RObot config error: {field} - {hint}\nSee: docs/reference/troubleshooting.md#robot-config"
     )]
     RobotMissingField { field: String, hint: String },
+
+    #[error(
+        "Cannot extend '{target}' from {origin}: {reason}\nSee: docs/guide/configuration.md#layered-configuration"
+    )]
+    Extends {
+        origin: String,
+        target: String,
+        reason: String,
+    },
+
+    #[error(
+        "Environment variable '{name}' referenced in {origin} is not set.\nFix: export it, give a default with '${{{name}:-value}}', or write '$${{' for a literal '${{'."
+    )]
+    UndefinedEnvVar { name: String, origin: String },
+
+    #[error("Invalid config override '{input}': {reason}")]
+    InvalidOverride { input: String, reason: String },
 }
 
 #[cfg(test)]
@@ -1541,6 +1999,222 @@ agent_priority: [gemini, claude, codex]
         assert_eq!(priority, vec!["gemini", "claude", "codex"]);
     }
 
+    #[test]
+    fn test_backend_recovery_config() {
+        let config = RalphConfig::default();
+        assert_eq!(config.cli.recovery, BackendRecoveryConfig::default());
+        assert!(config.cli.recovery.backoff);
+        assert!(!config.cli.recovery.failover);
+
+        let yaml = r"
+cli:
+  backend: claude
+  recovery:
+    failover: true
+    max_backoff_secs: 120
+";
+        let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.cli.recovery.failover);
+        assert_eq!(config.cli.recovery.max_backoff_secs, 120);
+        assert_eq!(config.cli.recovery.initial_backoff_secs, 30);
+        assert_eq!(config.cli.recovery.max_retries, 3);
+    }
+
+    #[test]
+    fn test_api_backend_config() {
+        let config = RalphConfig::default();
+        assert_eq!(config.cli.api.base_url, "https://api.openai.com/v1");
+        assert_eq!(config.cli.api.api_key_env, "OPENAI_API_KEY");
+
+        let yaml = r"
+cli:
+  backend: api
+";
+        let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::ApiBackendRequiresModel)
+        ));
+
+        let yaml = r"
+cli:
+  backend: api
+  api:
+    base_url: http://127.0.0.1:8080/v1
+    model: local-model
+    output_cost_per_mtok: 2.5
+";
+        let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.cli.api.model.as_deref(), Some("local-model"));
+        assert!((config.cli.api.output_cost_per_mtok - 2.5).abs() < f64::EPSILON);
+        assert_eq!(config.cli.api.max_turns, 50);
+    }
+
+    #[test]
+    fn test_model_and_effort_selection() {
+        let yaml = r"
+cli:
+  backend: claude
+  model: sonnet
+  effort: low
+hats:
+  planner:
+    name: Planner
+    description: Plans
+    model: opus
+    effort: high
+";
+        let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.validate().is_ok());
+        assert_eq!(config.cli.model.as_deref(), Some("sonnet"));
+        assert_eq!(config.hats["planner"].effort.as_deref(), Some("high"));
+
+        let config: RalphConfig = serde_yaml::from_str(&yaml.replace("high", "max")).unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidEffort { field, value })
+                if field == "hats.planner.effort" && value == "max"
+        ));
+    }
+
+    #[test]
+    fn test_watchdogs_are_validated() {
+        let yaml = r"
+event_loop:
+  watchdogs:
+    - expect: build.done
+      after: build.task
+      within: 20m
+      else: build.timeout
+";
+        let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.validate().is_ok());
+        let watchdog = &config.event_loop.watchdogs[0];
+        assert_eq!(watchdog.else_topic, "build.timeout");
+        assert_eq!(watchdog.after.as_deref(), Some("build.task"));
+
+        let config: RalphConfig = serde_yaml::from_str(&yaml.replace("20m", "soon")).unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidWatchdog { expect, .. }) if expect == "build.done"
+        ));
+    }
+
+    #[test]
+    fn test_approval_timeouts_are_validated() {
+        let yaml = r"
+events:
+  deploy.start:
+    description: Deployment requested
+    requires_approval: true
+    approval_timeout: 30m
+";
+        let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.validate().is_ok());
+        let metadata = &config.events["deploy.start"];
+        assert!(metadata.requires_approval);
+        assert_eq!(metadata.approval_timeout.as_deref(), Some("30m"));
+
+        let config: RalphConfig = serde_yaml::from_str(&yaml.replace("30m", "later")).unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidApprovalTimeout { topic, .. }) if topic == "deploy.start"
+        ));
+    }
+
+    #[test]
+    fn test_trigger_conditions_are_validated() {
+        let yaml = r#"
+hats:
+  fixer:
+    name: "Fixer"
+    description: "Fixes failures"
+    triggers: ["review.done", "build.failed"]
+    when:
+      review.done: "$.failures > 0"
+"#;
+        let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.validate().is_ok());
+        let conditions = config.hats["fixer"].parse_conditions("fixer").unwrap();
+        assert_eq!(conditions.len(), 1);
+        assert_eq!(conditions["review.done"].as_str(), "$.failures > 0");
+
+        let shared: RalphConfig =
+            serde_yaml::from_str(&yaml.replace("when:\n      review.done:", "when:")).unwrap();
+        assert_eq!(shared.hats["fixer"].trigger_conditions().len(), 2);
+
+        let config: RalphConfig = serde_yaml::from_str(&yaml.replace("> 0", ">")).unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidCondition { hat, trigger, .. })
+                if hat == "fixer" && trigger == "review.done"
+        ));
+
+        let config: RalphConfig =
+            serde_yaml::from_str(&yaml.replace("review.done: \"", "review.started: \"")).unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidCondition { trigger, .. }) if trigger == "review.started"
+        ));
+    }
+
+    #[test]
+    fn test_redaction_patterns_are_validated() {
+        let config = RalphConfig::default();
+        assert!(config.redaction.enabled);
+
+        let yaml = r#"
+redaction:
+  patterns: ["acme_[a-z0-9]{24}"]
+  env: [DATABASE_URL]
+"#;
+        let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.redaction.env, vec!["DATABASE_URL"]);
+        assert!(config.validate().is_ok());
+
+        let config: RalphConfig = serde_yaml::from_str(&yaml.replace("{24}", "{24")).unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidRedactionPattern { .. })
+        ));
+    }
+
+    #[test]
+    fn test_backend_definitions() {
+        let yaml = r#"
+cli:
+  backend: aider
+backends:
+  aider:
+    command: aider
+    args: ["--yes-always"]
+    prompt_flag: "--message"
+    env:
+      AIDER_ANALYTICS: "false"
+    output_format: lines
+    line_parser:
+      error: '^Error: (?P<message>.*)$'
+"#;
+        let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.validate().is_ok());
+        let aider = &config.backends["aider"];
+        assert_eq!(aider.command, "aider");
+        assert_eq!(aider.prompt_flag.as_deref(), Some("--message"));
+        assert_eq!(aider.env["AIDER_ANALYTICS"], "false");
+
+        let yaml = r"
+backends:
+  claude:
+    command: my-claude
+";
+        let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(matches!(
+            config.validate(),
+            Err(ConfigError::InvalidBackendDefinition { name, .. }) if name == "claude"
+        ));
+    }
+
     #[test]
     fn test_default_agent_priority() {
         let config = RalphConfig::default();
@@ -1972,7 +2646,10 @@ event_loop:
             initial_prompt_template: Some("Test template".to_string()),
             ..EventLoopConfig::default()
         };
-        assert_eq!(config.initial_prompt_template, Some("Test template".to_string()));
+        assert_eq!(
+            config.initial_prompt_template,
+            Some("Test template".to_string())
+        );
     }
 
     #[test]
@@ -2492,6 +3169,7 @@ RObot:
             timeout_seconds: None,
             checkin_interval_seconds: None,
             telegram: None,
+            access: Vec::new(),
         };
         let result = robot.validate();
         assert!(result.is_err());
@@ -2515,7 +3193,9 @@ RObot:
             checkin_interval_seconds: None,
             telegram: Some(TelegramBotConfig {
                 bot_token: Some("config-token".to_string()),
+                api_url: None,
             }),
+            access: Vec::new(),
         };
 
         // When RALPH_TELEGRAM_BOT_TOKEN is not set, config token is returned
@@ -2534,6 +3214,7 @@ RObot:
             timeout_seconds: Some(300),
             checkin_interval_seconds: None,
             telegram: None,
+            access: Vec::new(),
         };
 
         // Without env var AND without config token, resolve returns None
@@ -2553,7 +3234,9 @@ RObot:
             checkin_interval_seconds: None,
             telegram: Some(TelegramBotConfig {
                 bot_token: Some("test-token".to_string()),
+                api_url: None,
             }),
+            access: Vec::new(),
         };
         assert!(robot.validate().is_ok());
     }
@@ -2571,6 +3254,7 @@ RObot:
             timeout_seconds: Some(300),
             checkin_interval_seconds: None,
             telegram: None,
+            access: Vec::new(),
         };
         let result = robot.validate();
         assert!(result.is_err());
@@ -2595,7 +3279,11 @@ RObot:
             enabled: true,
             timeout_seconds: Some(300),
             checkin_interval_seconds: None,
-            telegram: Some(TelegramBotConfig { bot_token: None }),
+            telegram: Some(TelegramBotConfig {
+                bot_token: None,
+                api_url: None,
+            }),
+            access: Vec::new(),
         };
         let result = robot.validate();
         assert!(result.is_err());
@@ -2608,6 +3296,66 @@ RObot:
         );
     }
 
+    #[test]
+    fn test_robot_config_api_url() {
+        if std::env::var("RALPH_TELEGRAM_API_URL").is_ok() {
+            return;
+        }
+
+        let yaml = r"
+RObot:
+  enabled: true
+  timeout_seconds: 60
+  telegram:
+    bot_token: test-token
+    api_url: http://127.0.0.1:8081/
+";
+        let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(
+            config.robot.resolve_api_url().as_deref(),
+            Some("http://127.0.0.1:8081/")
+        );
+        assert!(config.robot.validate().is_ok());
+
+        let mut invalid = config.robot.clone();
+        invalid.telegram.as_mut().unwrap().api_url = Some("127.0.0.1:8081".to_string());
+        let err = invalid.validate().unwrap_err();
+        assert!(
+            matches!(&err, ConfigError::RobotMissingField { field, .. }
+                if field == "RObot.telegram.api_url"),
+            "Expected api_url validation failure, got: {:?}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_robot_config_access_rules() {
+        let yaml = r"
+RObot:
+  access:
+    - chat_id: -100123
+      role: viewer
+    - user_id: 42
+      role: operator
+";
+        let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.robot.access.len(), 2);
+        assert_eq!(config.robot.access[0].chat_id, Some(-100_123));
+        assert_eq!(
+            config.robot.access[1].role,
+            ralph_proto::RobotRole::Operator
+        );
+        assert!(config.robot.validate().is_ok());
+
+        let yaml = "RObot:\n  access:\n    - role: viewer\n";
+        let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
+        let err = config.robot.validate().unwrap_err();
+        assert!(
+            matches!(&err, ConfigError::RobotMissingField { field, .. } if field == "RObot.access[0]"),
+            "got: {err:?}"
+        );
+    }
+
     #[test]
     fn test_extra_instructions_merged_during_normalize() {
         let yaml = r#"
@@ -0,0 +1,782 @@
+//! Layered configuration loading.
+//!
+//! A [`LayeredConfig`] stacks YAML layers (user defaults, presets, project
+//! files, `-c key=value` overrides) and deep-merges them before deserializing
+//! into a [`RalphConfig`]. Each layer may:
+//!
+//! - `extends:` a builtin preset (`builtin:feature`) or another file, which is
+//!   loaded first and merged underneath it
+//! - reference environment variables as `${VAR}` or `${VAR:-default}`
+//!   (write `$${` for a literal `${`); prompt text such as hat
+//!   `instructions` is left as written
+//!
+//! Mappings merge key by key, so a project can add one hat to a preset or
+//! tweak a single field of a preset's hat. Scalars and lists replace.
+//!
+//! The origin of every value is tracked so `ralph config show --resolved` can
+//! report where each setting came from.
+
+use crate::config::{ConfigError, RalphConfig};
+use serde_yaml::{Mapping, Value};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tracing::debug;
+
+/// Origin reported for values no layer sets.
+pub const DEFAULT_ORIGIN: &str = "default";
+
+type Lookup = Box<dyn Fn(&str) -> Option<String>>;
+
+/// A stack of configuration layers merged in the order they are added.
+pub struct LayeredConfig {
+    value: Value,
+    origins: BTreeMap<Vec<String>, String>,
+    sources: Vec<String>,
+    override_paths: Vec<Vec<String>>,
+    presets: Option<Lookup>,
+    env: Lookup,
+}
+
+impl Default for LayeredConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LayeredConfig {
+    /// Creates an empty stack that reads `${VAR}` from the process environment.
+    pub fn new() -> Self {
+        Self {
+            value: Value::Mapping(Mapping::new()),
+            origins: BTreeMap::new(),
+            sources: Vec::new(),
+            override_paths: Vec::new(),
+            presets: None,
+            env: Box::new(|name| std::env::var(name).ok()),
+        }
+    }
+
+    /// Sets how `extends: builtin:<name>` is resolved to preset YAML.
+    #[must_use]
+    pub fn with_presets(mut self, lookup: impl Fn(&str) -> Option<String> + 'static) -> Self {
+        self.presets = Some(Box::new(lookup));
+        self
+    }
+
+    /// Sets how `${VAR}` references are resolved.
+    #[must_use]
+    pub fn with_env(mut self, lookup: impl Fn(&str) -> Option<String> + 'static) -> Self {
+        self.env = Box::new(lookup);
+        self
+    }
+
+    /// Adds a YAML file, resolving its `extends:` relative to the file.
+    pub fn add_file(&mut self, path: &Path) -> Result<(), ConfigError> {
+        let content = std::fs::read_to_string(path)?;
+        let origin = path.display().to_string();
+        self.add_layer(&origin, &content, path.parent(), &mut vec![chain_key(path)])
+    }
+
+    /// Adds a builtin preset by name.
+    pub fn add_preset(&mut self, name: &str) -> Result<(), ConfigError> {
+        let origin = format!("builtin:{name}");
+        let content = self
+            .preset_content(name)
+            .ok_or_else(|| ConfigError::Extends {
+                origin: "command line".to_string(),
+                target: origin.clone(),
+                reason: "unknown preset".to_string(),
+            })?;
+        self.add_layer(&origin, &content, None, &mut vec![origin.clone()])
+    }
+
+    /// Adds YAML content under the given origin label.
+    ///
+    /// Relative `extends:` paths resolve against `base_dir`, or the current
+    /// directory when `None`.
+    pub fn add_yaml(
+        &mut self,
+        origin: &str,
+        content: &str,
+        base_dir: Option<&Path>,
+    ) -> Result<(), ConfigError> {
+        self.add_layer(origin, content, base_dir, &mut vec![origin.to_string()])
+    }
+
+    /// Applies a `dotted.path=value` override.
+    ///
+    /// The value is read as YAML when it is a number, boolean, null or a flow
+    /// collection (`[a, b]`, `{k: v}`); anything else is taken as a string.
+    pub fn add_override(&mut self, input: &str) -> Result<(), ConfigError> {
+        let (path, value) = parse_override(input)?;
+        set_path(&mut self.value, &path, value.clone()).map_err(|reason| {
+            ConfigError::InvalidOverride {
+                input: input.to_string(),
+                reason,
+            }
+        })?;
+        record_origins(
+            &mut self.origins,
+            &mut path.clone(),
+            &value,
+            &format!("-c {input}"),
+        );
+        self.sources.push(format!("-c {input}"));
+        self.override_paths.push(path);
+        Ok(())
+    }
+
+    /// Returns the merged YAML of every layer added so far.
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
+
+    /// Returns the origin of every layer merged so far, lowest precedence
+    /// first; `extends:` targets appear before the layer that extends them.
+    pub fn sources(&self) -> &[String] {
+        &self.sources
+    }
+
+    /// Returns which layer set the value at `path`, or [`DEFAULT_ORIGIN`].
+    pub fn origin(&self, path: &[String]) -> &str {
+        (0..=path.len())
+            .rev()
+            .find_map(|len| self.origins.get(&path[..len]))
+            .map_or(DEFAULT_ORIGIN, String::as_str)
+    }
+
+    /// Deserializes the merged layers into a config.
+    pub fn to_config(&self) -> Result<RalphConfig, ConfigError> {
+        Ok(serde_yaml::from_value(self.value.clone())?)
+    }
+
+    /// Returns overrides whose path does not exist in `config`, i.e. keys
+    /// that were dropped during deserialization (usually typos).
+    pub fn unknown_overrides(&self, config: &RalphConfig) -> Vec<String> {
+        let Ok(resolved) = serde_yaml::to_value(config) else {
+            return Vec::new();
+        };
+        self.override_paths
+            .iter()
+            .filter(|path| lookup_path(&resolved, path).is_none())
+            .map(|path| path.join("."))
+            .collect()
+    }
+
+    fn preset_content(&self, name: &str) -> Option<String> {
+        self.presets.as_ref().and_then(|lookup| lookup(name))
+    }
+
+    fn add_layer(
+        &mut self,
+        origin: &str,
+        content: &str,
+        base_dir: Option<&Path>,
+        chain: &mut Vec<String>,
+    ) -> Result<(), ConfigError> {
+        let mut layer: Value = serde_yaml::from_str(content)?;
+        if layer.is_null() {
+            layer = Value::Mapping(Mapping::new());
+        }
+        if !layer.is_mapping() {
+            return Err(ConfigError::Extends {
+                origin: origin.to_string(),
+                target: origin.to_string(),
+                reason: "configuration must be a YAML mapping".to_string(),
+            });
+        }
+
+        interpolate_env(&mut layer, &*self.env, origin)?;
+
+        let parents = match layer.as_mapping_mut().and_then(|m| m.remove("extends")) {
+            None => Vec::new(),
+            Some(Value::String(target)) => vec![target],
+            Some(Value::Sequence(targets)) => targets
+                .into_iter()
+                .map(|t| match t {
+                    Value::String(s) => Ok(s),
+                    _ => Err(extends_error(origin, "?", "entries must be strings")),
+                })
+                .collect::<Result<_, _>>()?,
+            Some(_) => {
+                return Err(extends_error(
+                    origin,
+                    "?",
+                    "must be a string or a list of strings",
+                ));
+            }
+        };
+
+        for target in parents {
+            let (parent_origin, parent_content, parent_dir, key) =
+                self.resolve_extends(origin, &target, base_dir)?;
+            if chain.contains(&key) {
+                return Err(extends_error(origin, &target, "circular extends"));
+            }
+            chain.push(key);
+            self.add_layer(
+                &parent_origin,
+                &parent_content,
+                parent_dir.as_deref(),
+                chain,
+            )?;
+            chain.pop();
+        }
+
+        debug!(origin, "Merging config layer");
+        record_origins(&mut self.origins, &mut Vec::new(), &layer, origin);
+        merge(&mut self.value, layer);
+        self.sources.push(origin.to_string());
+        Ok(())
+    }
+
+    /// Resolves an `extends:` target to (origin, content, base dir, chain key).
+    fn resolve_extends(
+        &self,
+        origin: &str,
+        target: &str,
+        base_dir: Option<&Path>,
+    ) -> Result<(String, String, Option<PathBuf>, String), ConfigError> {
+        if let Some(name) = target.strip_prefix("builtin:") {
+            let content = self
+                .preset_content(name)
+                .ok_or_else(|| extends_error(origin, target, "unknown preset"))?;
+            return Ok((target.to_string(), content, None, target.to_string()));
+        }
+        if target.starts_with("http://") || target.starts_with("https://") {
+            return Err(extends_error(
+                origin,
+                target,
+                "remote configs cannot be extended; download the file or use a builtin preset",
+            ));
+        }
+
+        let path = base_dir.map_or_else(|| PathBuf::from(target), |dir| dir.join(target));
+        let content = std::fs::read_to_string(&path)
+            .map_err(|e| extends_error(origin, target, &format!("{}: {e}", path.display())))?;
+        let key = chain_key(&path);
+        Ok((
+            path.display().to_string(),
+            content,
+            path.parent().map(Path::to_path_buf),
+            key,
+        ))
+    }
+}
+
+fn extends_error(origin: &str, target: &str, reason: &str) -> ConfigError {
+    ConfigError::Extends {
+        origin: origin.to_string(),
+        target: target.to_string(),
+        reason: reason.to_string(),
+    }
+}
+
+fn chain_key(path: &Path) -> String {
+    std::fs::canonicalize(path)
+        .unwrap_or_else(|_| path.to_path_buf())
+        .display()
+        .to_string()
+}
+
+/// Deep-merges `overlay` into `base`: mappings merge by key, everything else replaces.
+pub fn merge(base: &mut Value, overlay: Value) {
+    match (base, overlay) {
+        (Value::Mapping(base), Value::Mapping(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+/// Records `origin` for every leaf of `value`, replacing origins of any
+/// values the layer overwrites.
+fn record_origins(
+    origins: &mut BTreeMap<Vec<String>, String>,
+    path: &mut Vec<String>,
+    value: &Value,
+    origin: &str,
+) {
+    if let Value::Mapping(map) = value
+        && !map.is_empty()
+    {
+        for (key, child) in map {
+            path.push(key_string(key));
+            record_origins(origins, path, child, origin);
+            path.pop();
+        }
+        return;
+    }
+    origins.retain(|existing, _| !existing.starts_with(path) || existing.len() == path.len());
+    origins.insert(path.clone(), origin.to_string());
+}
+
+/// Renders a mapping key as a plain string.
+pub fn key_string(key: &Value) -> String {
+    match key {
+        Value::String(s) => s.clone(),
+        other => serde_yaml::to_string(other)
+            .map(|s| s.trim_end().to_string())
+            .unwrap_or_default(),
+    }
+}
+
+/// Keys whose values are prompt text handed to the agent verbatim.
+///
+/// Instructions and prompts routinely contain `${...}` (shell snippets,
+/// template examples), so they are never interpolated, including under
+/// nested keys.
+const PROSE_KEYS: &[&str] = &[
+    "prompt",
+    "initial_prompt_template",
+    "guardrails",
+    "description",
+    "instructions",
+    "extra_instructions",
+    "on_trigger",
+    "on_publish",
+];
+
+/// Replaces `${VAR}` and `${VAR:-default}` in every string scalar outside
+/// [`PROSE_KEYS`].
+fn interpolate_env(
+    value: &mut Value,
+    env: &dyn Fn(&str) -> Option<String>,
+    origin: &str,
+) -> Result<(), ConfigError> {
+    match value {
+        Value::String(s) if s.contains('$') => {
+            let whole_reference = s.starts_with("${") && s.find('}') == Some(s.len() - 1);
+            let replaced = interpolate_str(s, env, origin)?;
+            // `max_iterations: ${MAX}` should still deserialize as a number
+            if whole_reference
+                && let Ok(typed @ (Value::Bool(_) | Value::Number(_))) =
+                    serde_yaml::from_str::<Value>(&replaced)
+            {
+                *value = typed;
+            } else {
+                *s = replaced;
+            }
+        }
+        Value::Sequence(items) => {
+            for item in items {
+                interpolate_env(item, env, origin)?;
+            }
+        }
+        Value::Mapping(map) => {
+            for (key, child) in map.iter_mut() {
+                if key.as_str().is_some_and(|k| PROSE_KEYS.contains(&k)) {
+                    continue;
+                }
+                interpolate_env(child, env, origin)?;
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+fn interpolate_str(
+    s: &str,
+    env: &dyn Fn(&str) -> Option<String>,
+    origin: &str,
+) -> Result<String, ConfigError> {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(pos) = rest.find('$') {
+        out.push_str(&rest[..pos]);
+        let tail = &rest[pos..];
+        if let Some(after) = tail.strip_prefix("$${") {
+            out.push_str("${");
+            rest = after;
+        } else if let Some(after) = tail.strip_prefix("${")
+            && let Some(end) = after.find('}')
+        {
+            let reference = &after[..end];
+            let (name, default) = match reference.split_once(":-") {
+                Some((name, default)) => (name, Some(default)),
+                None => (reference, None),
+            };
+            match env(name).filter(|v| !v.is_empty() || default.is_none()) {
+                Some(value) => out.push_str(&value),
+                None => match default {
+                    Some(default) => out.push_str(default),
+                    None => {
+                        return Err(ConfigError::UndefinedEnvVar {
+                            name: name.to_string(),
+                            origin: origin.to_string(),
+                        });
+                    }
+                },
+            }
+            rest = &after[end + 1..];
+        } else {
+            out.push('$');
+            rest = &tail[1..];
+        }
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+/// Splits `a.b."c.d"=value` into a key path and a YAML value.
+pub fn parse_override(input: &str) -> Result<(Vec<String>, Value), ConfigError> {
+    let invalid = |reason: &str| ConfigError::InvalidOverride {
+        input: input.to_string(),
+        reason: reason.to_string(),
+    };
+    let (key, raw) = input
+        .split_once('=')
+        .ok_or_else(|| invalid("expected key=value"))?;
+    let path = parse_dotted_path(key).ok_or_else(|| invalid("malformed key path"))?;
+
+    let trimmed = raw.trim();
+    let value = if trimmed.is_empty() {
+        Value::String(String::new())
+    } else {
+        match serde_yaml::from_str::<Value>(trimmed) {
+            Ok(v @ (Value::Bool(_) | Value::Number(_) | Value::Null)) => v,
+            Ok(v @ (Value::Sequence(_) | Value::Mapping(_)))
+                if trimmed.starts_with('[') || trimmed.starts_with('{') =>
+            {
+                v
+            }
+            _ => Value::String(raw.to_string()),
+        }
+    };
+    Ok((path, value))
+}
+
+/// Parses a dotted key path; segments containing dots can be double-quoted.
+fn parse_dotted_path(key: &str) -> Option<Vec<String>> {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut quoted = false;
+    let mut was_quoted = false;
+    for c in key.trim().chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                was_quoted = true;
+            }
+            '.' if !quoted => {
+                if current.is_empty() && !was_quoted {
+                    return None;
+                }
+                segments.push(std::mem::take(&mut current));
+                was_quoted = false;
+            }
+            c => current.push(c),
+        }
+    }
+    if quoted || (current.is_empty() && !was_quoted) {
+        return None;
+    }
+    segments.push(current);
+    Some(segments)
+}
+
+/// Sets `value` at `path`, creating intermediate mappings.
+fn set_path(root: &mut Value, path: &[String], value: Value) -> Result<(), String> {
+    let (last, parents) = path.split_last().ok_or("empty key path")?;
+    let mut node = root;
+    for (depth, segment) in parents.iter().enumerate() {
+        if node.is_null() {
+            *node = Value::Mapping(Mapping::new());
+        }
+        let Value::Mapping(map) = node else {
+            return Err(format!("'{}' is not a section", path[..depth].join(".")));
+        };
+        node = map
+            .entry(Value::String(segment.clone()))
+            .or_insert(Value::Null);
+    }
+    if node.is_null() {
+        *node = Value::Mapping(Mapping::new());
+    }
+    let Value::Mapping(map) = node else {
+        return Err(format!("'{}' is not a section", parents.join(".")));
+    };
+    map.insert(Value::String(last.clone()), value);
+    Ok(())
+}
+
+fn lookup_path<'v>(root: &'v Value, path: &[String]) -> Option<&'v Value> {
+    path.iter().try_fold(root, |node, segment| {
+        node.as_mapping()?.get(segment.as_str())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    const PRESET: &str = r#"
+event_loop:
+  completion_promise: "PRESET_DONE"
+  max_iterations: 50
+hats:
+  builder:
+    name: "Builder"
+    description: "Builds"
+    triggers: ["build.task"]
+    publishes: ["build.done"]
+events:
+  build.done:
+    description: "Build finished"
+"#;
+
+    fn stack() -> LayeredConfig {
+        LayeredConfig::new()
+            .with_presets(|name| (name == "feature").then(|| PRESET.to_string()))
+            .with_env(|name| {
+                HashMap::from([("MAX", "7"), ("BACKEND", "kiro"), ("EMPTY", "")])
+                    .get(name)
+                    .map(|v| (*v).to_string())
+            })
+    }
+
+    fn path(s: &str) -> Vec<String> {
+        s.split('.').map(str::to_string).collect()
+    }
+
+    #[test]
+    fn test_extends_builtin_deep_merges_hats_and_events() {
+        let mut layers = stack();
+        layers
+            .add_yaml(
+                "ralph.yml",
+                r#"
+extends: builtin:feature
+event_loop:
+  max_iterations: 20
+hats:
+  builder:
+    max_activations: 3
+  reviewer:
+    name: "Reviewer"
+    description: "Reviews"
+    triggers: ["build.done"]
+    publishes: ["PRESET_DONE"]
+"#,
+                None,
+            )
+            .unwrap();
+
+        let config = layers.to_config().unwrap();
+        assert_eq!(config.event_loop.max_iterations, 20);
+        assert_eq!(config.event_loop.completion_promise, "PRESET_DONE");
+        assert_eq!(config.hats.len(), 2);
+        assert_eq!(config.hats["builder"].name, "Builder");
+        assert_eq!(config.hats["builder"].max_activations, Some(3));
+        assert!(config.events.contains_key("build.done"));
+
+        assert_eq!(
+            layers.origin(&path("event_loop.max_iterations")),
+            "ralph.yml"
+        );
+        assert_eq!(
+            layers.origin(&path("event_loop.completion_promise")),
+            "builtin:feature"
+        );
+        assert_eq!(layers.origin(&path("hats.builder.name")), "builtin:feature");
+        assert_eq!(layers.origin(&path("cli.backend")), DEFAULT_ORIGIN);
+        assert_eq!(layers.sources(), ["builtin:feature", "ralph.yml"]);
+    }
+
+    #[test]
+    fn test_extends_relative_file_and_cycle_detection() {
+        let temp = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp.path().join("base")).unwrap();
+        std::fs::write(
+            temp.path().join("base/common.yml"),
+            "core:\n  specs_dir: shared-specs\n",
+        )
+        .unwrap();
+        std::fs::write(
+            temp.path().join("ralph.yml"),
+            "extends: base/common.yml\ncli:\n  backend: claude\n",
+        )
+        .unwrap();
+
+        let mut layers = stack();
+        layers.add_file(&temp.path().join("ralph.yml")).unwrap();
+        let config = layers.to_config().unwrap();
+        assert_eq!(config.core.specs_dir, "shared-specs");
+        assert_eq!(config.cli.backend, "claude");
+
+        std::fs::write(temp.path().join("a.yml"), "extends: b.yml\n").unwrap();
+        std::fs::write(temp.path().join("b.yml"), "extends: a.yml\n").unwrap();
+        let err = stack().add_file(&temp.path().join("a.yml")).unwrap_err();
+        assert!(err.to_string().contains("circular extends"), "{err}");
+    }
+
+    #[test]
+    fn test_unknown_preset_in_extends() {
+        let err = stack()
+            .add_yaml("ralph.yml", "extends: builtin:nope\n", None)
+            .unwrap_err();
+        assert!(matches!(err, ConfigError::Extends { .. }));
+        assert!(err.to_string().contains("unknown preset"));
+    }
+
+    #[test]
+    fn test_env_interpolation() {
+        let mut layers = stack();
+        layers
+            .add_yaml(
+                "ralph.yml",
+                r#"
+cli:
+  backend: ${BACKEND}
+event_loop:
+  max_iterations: ${MAX}
+  completion_promise: "${UNSET:-DONE}"
+core:
+  scratchpad: "$${HOME}/${EMPTY:-pad}.md"
+"#,
+                None,
+            )
+            .unwrap();
+
+        let config = layers.to_config().unwrap();
+        assert_eq!(config.cli.backend, "kiro");
+        assert_eq!(config.event_loop.max_iterations, 7);
+        assert_eq!(config.event_loop.completion_promise, "DONE");
+        assert_eq!(config.core.scratchpad, "${HOME}/pad.md");
+
+        let err = stack()
+            .add_yaml("ralph.yml", "cli:\n  backend: ${MISSING}\n", None)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ConfigError::UndefinedEnvVar { ref name, .. } if name == "MISSING"
+        ));
+    }
+
+    #[test]
+    fn test_env_references_in_prompt_text_are_kept_verbatim() {
+        let preset = r#"
+event_loop:
+  prompt: "Deploy to ${TARGET}"
+core:
+  guardrails: ["Never print ${API_KEY}"]
+hats:
+  builder:
+    name: "Builder"
+    description: "Uses ${BACKEND}"
+    triggers: ["build.task"]
+    publishes: ["build.done"]
+    instructions: |
+      Run `echo ${HOME:-/root}` and keep $${literal} as is.
+events:
+  build.done:
+    description: "Done with ${UNSET}"
+"#;
+        let mut layers =
+            stack().with_presets(move |name| (name == "shell").then(|| preset.to_string()));
+        layers
+            .add_yaml(
+                "ralph.yml",
+                "extends: builtin:shell
+cli:
+  backend: ${BACKEND}
+",
+                None,
+            )
+            .unwrap();
+
+        let config = layers.to_config().unwrap();
+        assert_eq!(config.cli.backend, "kiro");
+        assert_eq!(
+            config.event_loop.prompt.as_deref(),
+            Some("Deploy to ${TARGET}")
+        );
+        assert_eq!(config.core.guardrails, vec!["Never print ${API_KEY}"]);
+        assert_eq!(
+            config.hats["builder"].instructions,
+            "Run `echo ${HOME:-/root}` and keep $${literal} as is.\n"
+        );
+        assert_eq!(
+            config.hats["builder"].description.as_deref(),
+            Some("Uses ${BACKEND}")
+        );
+        assert_eq!(
+            config.events["build.done"].description,
+            "Done with ${UNSET}"
+        );
+    }
+
+    #[test]
+    fn test_overrides_reach_any_field() {
+        let mut layers = stack();
+        layers.add_preset("feature").unwrap();
+        layers.add_override("event_loop.max_iterations=5").unwrap();
+        layers
+            .add_override("hats.builder.max_activations=2")
+            .unwrap();
+        layers
+            .add_override("features.preflight.skip=[git, tools]")
+            .unwrap();
+        layers
+            .add_override("events.\"build.done\".description=Done: built")
+            .unwrap();
+        layers.add_override("core.nonexistent=1").unwrap();
+
+        let config = layers.to_config().unwrap();
+        assert_eq!(config.event_loop.max_iterations, 5);
+        assert_eq!(config.hats["builder"].max_activations, Some(2));
+        assert_eq!(config.features.preflight.skip, vec!["git", "tools"]);
+        assert_eq!(config.events["build.done"].description, "Done: built");
+        assert_eq!(
+            layers.origin(&path("event_loop.max_iterations")),
+            "-c event_loop.max_iterations=5"
+        );
+        assert_eq!(layers.unknown_overrides(&config), vec!["core.nonexistent"]);
+    }
+
+    #[test]
+    fn test_parse_override_errors() {
+        assert!(parse_override("no-equals").is_err());
+        assert!(parse_override("a..b=1").is_err());
+        assert!(parse_override("\"unterminated=1").is_err());
+
+        let mut layers = stack();
+        layers
+            .add_yaml("x", "core:\n  scratchpad: a\n", None)
+            .unwrap();
+        let err = layers.add_override("core.scratchpad.deep=1").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("'core.scratchpad' is not a section")
+        );
+    }
+
+    #[test]
+    fn test_later_layers_replace_lists_and_origins() {
+        let mut layers = stack();
+        layers
+            .add_yaml("user", "features:\n  preflight:\n    skip: [git]\n", None)
+            .unwrap();
+        layers
+            .add_yaml(
+                "project",
+                "features:\n  preflight:\n    skip: [tools]\n",
+                None,
+            )
+            .unwrap();
+
+        let config = layers.to_config().unwrap();
+        assert_eq!(config.features.preflight.skip, vec!["tools"]);
+        assert_eq!(layers.origin(&path("features.preflight.skip")), "project");
+    }
+}
@@ -78,7 +78,7 @@ impl AgentOutputLogger {
             content,
         };
 
-        let json = serde_json::to_string(&entry)?;
+        let json = crate::redaction::redacted_json(&entry)?;
         writeln!(self.file, "{}", json)?;
         self.file.flush()?;
 
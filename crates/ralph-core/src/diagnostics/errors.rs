@@ -150,7 +150,7 @@ impl ErrorLogger {
             context: error.context(),
         };
 
-        if let Ok(json) = serde_json::to_string(&entry) {
+        if let Ok(json) = crate::redaction::redacted_json(&entry) {
             let _ = writeln!(self.file, "{}", json);
             let _ = self.file.flush();
         }
@@ -49,8 +49,8 @@ impl OrchestrationLogger {
             hat: hat.to_string(),
             event,
         };
-        serde_json::to_writer(&mut self.writer, &entry)?;
-        self.writer.write_all(b"\n")?;
+        let json = crate::redaction::redacted_json(&entry)?;
+        writeln!(self.writer, "{json}")?;
         self.writer.flush()?;
         Ok(())
     }
@@ -4,8 +4,11 @@
 //! state of the orchestration loop including iteration count, failures,
 //! timing, and hat activation tracking.
 
+use crate::approvals::ApprovalQueue;
+use crate::skill_registry::TriggeredSkill;
+use crate::timers::TimerSet;
 use ralph_proto::HatId;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::time::{Duration, Instant};
 
 /// Current state of the event loop.
@@ -17,6 +20,9 @@ pub struct LoopState {
     pub consecutive_failures: u32,
     /// Cumulative cost in USD (if tracked).
     pub cumulative_cost: f64,
+    /// Cost in USD per `backend/model` (just `backend` when no model was
+    /// requested), for the cost breakdown.
+    pub cost_by_model: BTreeMap<String, f64>,
     /// When the loop started.
     pub started_at: Instant,
     /// The last hat that executed.
@@ -49,6 +55,15 @@ pub struct LoopState {
     /// Hat IDs that were active in the last iteration.
     /// Used to inject `default_publishes` when agent writes no events.
     pub last_active_hat_ids: Vec<HatId>,
+
+    /// Skills injected by their `triggers:` into the last prompt.
+    pub last_triggered_skills: Vec<TriggeredSkill>,
+
+    /// Delayed events and armed watchdogs waiting to fire.
+    pub timers: TimerSet,
+
+    /// Events held until a human approves them.
+    pub approvals: ApprovalQueue,
 }
 
 impl Default for LoopState {
@@ -57,6 +72,7 @@ impl Default for LoopState {
             iteration: 0,
             consecutive_failures: 0,
             cumulative_cost: 0.0,
+            cost_by_model: BTreeMap::new(),
             started_at: Instant::now(),
             last_hat: None,
             consecutive_blocked: 0,
@@ -70,6 +86,9 @@ impl Default for LoopState {
             exhausted_hats: HashSet::new(),
             last_checkin_at: None,
             last_active_hat_ids: Vec::new(),
+            last_triggered_skills: Vec::new(),
+            timers: TimerSet::new(),
+            approvals: ApprovalQueue::new(),
         }
     }
 }
@@ -8,17 +8,25 @@ mod tests;
 
 pub use loop_state::LoopState;
 
+use crate::approvals::{APPROVAL_RESPONSE_TOPIC, ApprovalDecision, ApprovalQueue};
 use crate::config::{HatBackend, InjectMode, RalphConfig};
 use crate::event_parser::{EventParser, MutationEvidence, MutationStatus};
-use crate::event_reader::EventReader;
+use crate::event_reader::{EventReader, ParseResult};
 use crate::hat_registry::HatRegistry;
 use crate::hatless_ralph::HatlessRalph;
 use crate::instructions::InstructionBuilder;
 use crate::loop_context::LoopContext;
 use crate::memory_store::{MarkdownMemoryStore, format_memories_as_markdown, truncate_to_budget};
-use crate::skill_registry::SkillRegistry;
+use crate::prompt_assembly::{
+    PromptAssembly, PromptSection, SECTION_HAT_INSTRUCTIONS, SECTION_MEMORIES, SECTION_READY_TASKS,
+    SECTION_SCRATCHPAD,
+};
+use crate::skill_registry::{SkillRegistry, TriggeredSkill};
+use crate::skill_triggers::TriggerContext;
+use crate::skill_usage::{SKILL_USAGE_FILE, SkillUsageLog, SkillUsageRecord};
 use crate::text::floor_char_boundary;
-use ralph_proto::{CheckinContext, Event, EventBus, Hat, HatId, RobotService};
+use crate::timers::TimerSet;
+use ralph_proto::{CheckinContext, Event, EventBus, Hat, HatId, RobotService, Topic};
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
@@ -97,6 +105,36 @@ impl TerminationReason {
     pub fn is_success(&self) -> bool {
         matches!(self, TerminationReason::CompletionPromise)
     }
+
+    /// Every termination reason, in declaration order.
+    pub const ALL: [TerminationReason; 10] = [
+        TerminationReason::CompletionPromise,
+        TerminationReason::MaxIterations,
+        TerminationReason::MaxRuntime,
+        TerminationReason::MaxCost,
+        TerminationReason::ConsecutiveFailures,
+        TerminationReason::LoopThrashing,
+        TerminationReason::ValidationFailure,
+        TerminationReason::Stopped,
+        TerminationReason::Interrupted,
+        TerminationReason::RestartRequested,
+    ];
+
+    /// Parses a termination reason from its payload name (`completed`,
+    /// `max_iterations`), its snake_case variant name (`completion_promise`),
+    /// or its Debug form (`MaxIterations`). Case and `_`/`-` are ignored.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let normalize = |s: &str| {
+            s.chars()
+                .filter(|c| *c != '_' && *c != '-')
+                .collect::<String>()
+                .to_lowercase()
+        };
+        let wanted = normalize(name.trim());
+        Self::ALL.into_iter().find(|reason| {
+            normalize(reason.as_str()) == wanted || normalize(&format!("{reason:?}")) == wanted
+        })
+    }
 }
 
 /// The main event loop orchestrator.
@@ -112,6 +150,9 @@ pub struct EventLoop {
     /// Event reader for consuming events from JSONL file.
     /// Made pub(crate) to allow tests to override the path.
     pub(crate) event_reader: EventReader,
+    /// Reader for human approval decisions, written by the TUI, the robot
+    /// service or `ralph loops approve`, never by the agent.
+    pub(crate) decision_reader: EventReader,
     diagnostics: crate::diagnostics::DiagnosticsCollector,
     /// Loop context for path resolution (None for legacy single-loop mode).
     loop_context: Option<LoopContext>,
@@ -120,6 +161,12 @@ pub struct EventLoop {
     /// Robot service for human-in-the-loop communication.
     /// Injected externally when `human.enabled` is true and this is the primary loop.
     robot_service: Option<Box<dyn RobotService>>,
+    /// Timers changed outside `tick_timers` and not yet written to disk.
+    timers_unsaved: bool,
+    /// Whether timers and held approvals are written to the loop context.
+    persist_state: bool,
+    /// Time pinned by `set_clock` for replays; the wall clock when `None`.
+    pinned_now: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 impl EventLoop {
@@ -235,6 +282,9 @@ impl EventLoop {
             })
             .unwrap_or_else(|_| context.events_path());
         let event_reader = EventReader::new(&events_path);
+        // Decisions left from before this process started answer nothing held now
+        let mut decision_reader = EventReader::new(context.approval_decisions_path());
+        decision_reader.skip_existing();
 
         Self {
             config,
@@ -245,10 +295,14 @@ impl EventLoop {
             ralph,
             robot_guidance: Vec::new(),
             event_reader,
+            decision_reader,
             diagnostics,
             loop_context: Some(context),
             skill_registry,
             robot_service: None,
+            timers_unsaved: false,
+            persist_state: true,
+            pinned_now: None,
         }
     }
 
@@ -326,6 +380,10 @@ impl EventLoop {
             .map(|s| s.trim().to_string())
             .unwrap_or_else(|_| ".ralph/events.jsonl".to_string());
         let event_reader = EventReader::new(&events_path);
+        let mut decision_reader = EventReader::new(
+            std::path::Path::new(".ralph").join(crate::approvals::APPROVAL_DECISIONS_FILE),
+        );
+        decision_reader.skip_existing();
 
         Self {
             config,
@@ -336,10 +394,14 @@ impl EventLoop {
             ralph,
             robot_guidance: Vec::new(),
             event_reader,
+            decision_reader,
             diagnostics,
             loop_context: None,
             skill_registry,
             robot_service: None,
+            timers_unsaved: false,
+            persist_state: true,
+            pinned_now: None,
         }
     }
 
@@ -354,6 +416,28 @@ impl EventLoop {
         self.robot_service = Some(service);
     }
 
+    /// Stops timers and held approvals from being written to (or read from)
+    /// the loop context.
+    ///
+    /// For loops built only to inspect a run, such as `ralph prompt preview`,
+    /// which must not disturb the state of a loop running in the same workspace.
+    pub fn disable_state_persistence(&mut self) {
+        self.persist_state = false;
+    }
+
+    /// Pins the time that timers and approval timeouts are measured against.
+    ///
+    /// Used by `LoopReplayer` to re-run deadlines at the times they were
+    /// recorded rather than at the speed of the replay.
+    pub fn set_clock(&mut self, now: chrono::DateTime<chrono::Utc>) {
+        self.pinned_now = Some(now);
+    }
+
+    /// Current time for timers and approvals.
+    fn now(&self) -> chrono::DateTime<chrono::Utc> {
+        self.pinned_now.unwrap_or_else(chrono::Utc::now)
+    }
+
     /// Returns the loop context, if one was provided.
     pub fn loop_context(&self) -> Option<&LoopContext> {
         self.loop_context.as_ref()
@@ -375,6 +459,43 @@ impl EventLoop {
             .unwrap_or_else(|| PathBuf::from(&self.config.core.scratchpad))
     }
 
+    /// Returns the timers path, or `None` when timers are not persisted.
+    fn timers_path(&self) -> Option<PathBuf> {
+        self.loop_context
+            .as_ref()
+            .filter(|_| self.persist_state)
+            .map(LoopContext::timers_path)
+    }
+
+    /// Persists pending timers so a resumed loop can pick them up.
+    fn save_timers(&mut self) {
+        let Some(path) = self.timers_path() else {
+            return;
+        };
+        if let Err(e) = self.state.timers.save(&path, self.state.iteration) {
+            warn!(error = %e, path = ?path, "Failed to save timers");
+        }
+        self.timers_unsaved = false;
+    }
+
+    /// Returns the held-approvals path, or `None` when they are not persisted.
+    fn approvals_path(&self) -> Option<PathBuf> {
+        self.loop_context
+            .as_ref()
+            .filter(|_| self.persist_state)
+            .map(LoopContext::approvals_path)
+    }
+
+    /// Persists held events so a resumed loop asks for them again.
+    fn save_approvals(&self) {
+        let Some(path) = self.approvals_path() else {
+            return;
+        };
+        if let Err(e) = self.state.approvals.save(&path) {
+            warn!(error = %e, path = ?path, "Failed to save held approvals");
+        }
+    }
+
     /// Returns the current loop state.
     pub fn state(&self) -> &LoopState {
         &self.state
@@ -544,6 +665,7 @@ impl EventLoop {
             .clone()
             .unwrap_or_else(|| "task.start".to_string());
         self.initialize_with_topic(&topic, prompt_content);
+        self.arm_start_watchdogs();
     }
 
     /// Initializes the loop for resume mode by publishing task.resume.
@@ -551,8 +673,319 @@ impl EventLoop {
     /// Per spec: "User can run `ralph resume` to restart reading existing scratchpad."
     /// The planner should read the existing scratchpad rather than doing fresh gap analysis.
     pub fn initialize_resume(&mut self, prompt_content: &str) {
+        // Pick up delayed events and watchdogs left by the interrupted run
+        if let Some(path) = self.timers_path() {
+            match TimerSet::load(&path, self.state.iteration) {
+                Ok(timers) => self.state.timers = timers,
+                Err(e) => warn!(error = %e, path = ?path, "Failed to load timers"),
+            }
+        }
+
+        // Events still awaiting approval are asked for again
+        if let Some(path) = self.approvals_path() {
+            match ApprovalQueue::load(&path) {
+                Ok(approvals) => self.state.approvals = approvals,
+                Err(e) => warn!(error = %e, path = ?path, "Failed to load held approvals"),
+            }
+        }
+
         // Resume always uses task.resume regardless of starting_event config
         self.initialize_with_topic("task.resume", prompt_content);
+        self.arm_start_watchdogs();
+        self.ask_next_approval();
+    }
+
+    /// Arms watchdogs without an `after:` topic, unless already armed.
+    ///
+    /// They are persisted by the first `tick_timers`, so building a loop
+    /// (e.g. for a prompt preview) never writes the run's timer state.
+    fn arm_start_watchdogs(&mut self) {
+        let watchdogs: Vec<_> = self
+            .config
+            .event_loop
+            .watchdogs
+            .iter()
+            .filter(|w| w.after.is_none() && !self.state.timers.has_watchdog(w))
+            .cloned()
+            .collect();
+        if watchdogs.is_empty() {
+            return;
+        }
+        self.state
+            .timers
+            .arm_watchdogs(&watchdogs, None, self.now(), self.state.iteration);
+        self.timers_unsaved = true;
+    }
+
+    /// Advances timers for the coming iteration.
+    ///
+    /// Watchdogs are disarmed or armed by the topics of pending events, then
+    /// delayed events and expired watchdogs that have fallen due are published.
+    /// Call once per iteration before `next_hat()`. Returns the fired events.
+    pub fn tick_timers(&mut self) -> Vec<Event> {
+        let watchdogs = &self.config.event_loop.watchdogs;
+        if watchdogs.is_empty() && self.state.timers.is_empty() {
+            return Vec::new();
+        }
+
+        let now = self.now();
+        let iteration = self.state.iteration;
+        let topics: Vec<String> = self
+            .bus
+            .hat_ids()
+            .filter_map(|id| self.bus.peek_pending(id))
+            .flatten()
+            .chain(self.bus.peek_human_pending())
+            .map(|event| event.topic.to_string())
+            .collect();
+
+        let before = self.state.timers.clone();
+        for topic in &topics {
+            self.state.timers.observe(topic);
+            self.state
+                .timers
+                .arm_watchdogs(watchdogs, Some(topic), now, iteration);
+        }
+
+        let fired = self.state.timers.take_due(now, iteration);
+        for event in &fired {
+            info!(topic = %event.topic, "Timer fired");
+            self.publish_or_hold(event.clone());
+        }
+
+        if self.timers_unsaved || self.state.timers != before {
+            self.save_timers();
+        }
+        fired
+    }
+
+    /// Describes pending timers for status displays.
+    pub fn pending_timers(&self) -> Vec<String> {
+        let now = self.now();
+        self.state
+            .timers
+            .iter()
+            .map(|timer| timer.describe(now, self.state.iteration))
+            .collect()
+    }
+
+    /// Time until the next wall-clock timer falls due, if any.
+    pub fn next_timer_due_in(&self) -> Option<Duration> {
+        self.state.timers.next_due_in(self.now())
+    }
+
+    /// Settles held events with new human decisions, then rejects those whose
+    /// approval timeout has passed.
+    ///
+    /// Call once per iteration before `next_hat()`, and while waiting for a
+    /// decision. Returns the published events: approved events and
+    /// `<topic>.rejected` events.
+    pub fn tick_approvals(&mut self) -> Vec<Event> {
+        let decisions = self.read_approval_decisions();
+        self.tick_approvals_with(&decisions)
+    }
+
+    /// Reads the human decisions written since the last read, oldest first.
+    ///
+    /// Callers that record a session read decisions with this and pass them
+    /// to `tick_approvals_with`, so a replay can settle the same events.
+    pub fn read_approval_decisions(&mut self) -> Vec<String> {
+        match self.decision_reader.read_new_events() {
+            Ok(parsed) => parsed
+                .events
+                .into_iter()
+                .filter(|decision| decision.topic == APPROVAL_RESPONSE_TOPIC)
+                .map(|decision| decision.payload.unwrap_or_default())
+                .collect(),
+            Err(e) => {
+                warn!(error = %e, "Failed to read approval decisions");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Like `tick_approvals`, with decisions already read by the caller.
+    pub fn tick_approvals_with(&mut self, decisions: &[String]) -> Vec<Event> {
+        let mut settled = Vec::new();
+        for reply in decisions {
+            match self.settle_approval(reply) {
+                Some(event) => {
+                    self.bus.publish(event.clone());
+                    settled.push(event);
+                }
+                None => warn!("Approval decision with no event awaiting approval — ignoring"),
+            }
+        }
+
+        let expired = self.state.approvals.take_expired(self.now());
+        if expired.is_empty() {
+            return settled;
+        }
+
+        for event in &expired {
+            warn!(topic = %event.topic, "Approval timed out");
+            if let Some(ref robot_service) = self.robot_service
+                && let Err(e) = robot_service
+                    .send_notification(&format!("⏰ `{}`: {}", event.topic, event.payload))
+            {
+                warn!(error = %e, "Failed to send approval timeout notification");
+            }
+            self.bus.publish(event.clone());
+        }
+        self.save_approvals();
+        self.ask_next_approval();
+        settled.extend(expired);
+        settled
+    }
+
+    /// Describes held events for status displays, oldest first.
+    pub fn pending_approvals(&self) -> Vec<String> {
+        let now = self.now();
+        self.state
+            .approvals
+            .iter()
+            .map(|approval| approval.describe(now))
+            .collect()
+    }
+
+    /// Returns true while any event is held for approval.
+    pub fn awaiting_approval(&self) -> bool {
+        !self.state.approvals.is_empty()
+    }
+
+    /// Time until the earliest approval timeout, if any.
+    pub fn next_approval_due_in(&self) -> Option<Duration> {
+        self.state.approvals.next_due_in(self.now())
+    }
+
+    /// Publishes a hat- or agent-originated event, or holds it if its topic
+    /// requires approval. Returns true if the event was published.
+    ///
+    /// Every path that delivers such events goes through here, so a gated
+    /// topic cannot reach a hat without a decision.
+    pub fn publish_or_hold(&mut self, event: Event) -> bool {
+        if self.hold_for_approval(&event) {
+            return false;
+        }
+        self.bus.publish(event);
+        true
+    }
+
+    /// Holds `event` if its topic requires approval. Returns true if held.
+    ///
+    /// An `approval_timeout` that does not parse (config validation rejects
+    /// it, so only an unvalidated config gets here) rejects the event at once
+    /// rather than waiting forever.
+    fn hold_for_approval(&mut self, event: &Event) -> bool {
+        let topic = event.topic.as_str();
+        let Some(metadata) = self
+            .config
+            .events
+            .iter()
+            .find(|(pattern, metadata)| {
+                metadata.requires_approval && Topic::new(pattern.as_str()).matches_str(topic)
+            })
+            .map(|(_, metadata)| metadata)
+        else {
+            return false;
+        };
+        let timeout = match metadata
+            .approval_timeout
+            .as_deref()
+            .map(|t| crate::timers::parse_duration(t).map_err(|e| format!("'{t}': {e}")))
+            .transpose()
+        {
+            Ok(timeout) => timeout,
+            Err(reason) => {
+                warn!(topic = %topic, "Invalid approval_timeout {} — rejecting", reason);
+                let rejection = Event::new(
+                    crate::approvals::rejected_topic(topic).as_str(),
+                    format!("Invalid approval_timeout {reason}"),
+                );
+                self.bus.publish(rejection);
+                return true;
+            }
+        };
+
+        info!(topic = %topic, "Holding event for human approval");
+        self.state.approvals.hold(event, timeout, self.now());
+        self.save_approvals();
+        self.ask_next_approval();
+        true
+    }
+
+    /// Settles the oldest held event with an `approval.response`, returning
+    /// the event to publish in its place.
+    fn settle_approval(&mut self, response: &str) -> Option<Event> {
+        let decision = ApprovalDecision::parse(response);
+        let event = self.state.approvals.decide(decision)?;
+        info!(topic = %event.topic, "Approval settled");
+        self.save_approvals();
+        self.ask_next_approval();
+        Some(event)
+    }
+
+    /// Asks the human about the oldest held event, unless already asked.
+    fn ask_next_approval(&mut self) {
+        let Some(approval) = self.state.approvals.next_to_ask() else {
+            return;
+        };
+        approval.asked = true;
+
+        match self.robot_service {
+            Some(ref robot_service) => {
+                if let Err(e) = robot_service.send_question(&approval.question()) {
+                    warn!(
+                        topic = %approval.topic,
+                        error = %e,
+                        "Failed to send approval request — answer with `ralph loops approve` or `ralph loops reject`"
+                    );
+                }
+            }
+            None => info!(
+                topic = %approval.topic,
+                "Awaiting approval — answer in the TUI or with `ralph loops approve` (or `ralph loops reject --reason <reason>`)"
+            ),
+        }
+    }
+
+    /// Schedules an event written with `ralph emit --delay/--after-iterations`.
+    fn defer_event(&mut self, event: &crate::event_reader::Event) {
+        let due_at = event.deliver_at.as_deref().and_then(|at| {
+            match chrono::DateTime::parse_from_rfc3339(at) {
+                Ok(at) => Some(at.with_timezone(&chrono::Utc)),
+                Err(e) => {
+                    warn!(topic = %event.topic, deliver_at = at, error = %e, "Invalid deliver_at");
+                    None
+                }
+            }
+        });
+        let due_iteration = event
+            .after_iterations
+            .map(|n| self.state.iteration.saturating_add(n));
+        // A malformed deadline delivers on the next iteration rather than never
+        let due_at = if due_at.is_none() && due_iteration.is_none() {
+            Some(self.now())
+        } else {
+            due_at
+        };
+
+        debug!(topic = %event.topic, ?due_at, ?due_iteration, "Deferring event");
+        self.state.timers.schedule_event(
+            &event.topic,
+            event.payload.as_deref().unwrap_or_default(),
+            due_at,
+            due_iteration,
+        );
+        self.save_timers();
+    }
+
+    /// Sets the objective without publishing a start event.
+    ///
+    /// Used to rebuild a mid-loop iteration (e.g. `ralph prompt preview
+    /// --iteration-state`), where the start event has long been consumed.
+    pub fn set_objective(&mut self, prompt_content: &str) {
+        self.ralph.set_objective(prompt_content.to_string());
     }
 
     /// Common initialization logic with configurable topic.
@@ -671,6 +1104,18 @@ impl EventLoop {
     /// primed memories to the prompt context. If a scratchpad file exists and is
     /// non-empty, its content is also prepended (before memories).
     pub fn build_prompt(&mut self, hat_id: &HatId) -> Option<String> {
+        let prompt = self
+            .build_prompt_assembly(hat_id)
+            .map(|assembly| assembly.render());
+        self.record_skill_injections();
+        prompt
+    }
+
+    /// Builds the prompt for a hat as named sections, trimmed to `event_loop.prompt_budget`.
+    ///
+    /// This is what [`build_prompt`](Self::build_prompt) renders; `ralph prompt
+    /// preview` uses it to report per-section token counts.
+    pub fn build_prompt_assembly(&mut self, hat_id: &HatId) -> Option<PromptAssembly> {
         // Handle "ralph" hat - the constant coordinator
         // Per spec: "Hatless Ralph is constant — Cannot be replaced, overwritten, or configured away"
         if hat_id.as_str() == "ralph" {
@@ -696,11 +1141,9 @@ impl EventLoop {
                 self.apply_robot_guidance();
 
                 // Build base prompt and prepend memories + scratchpad + ready tasks
-                let base_prompt = self.ralph.build_prompt(&events_context, &[]);
+                let base_prompt = self.ralph.build_prompt_sections(&events_context, &[]);
                 self.ralph.clear_robot_guidance();
-                let with_skills = self.prepend_auto_inject_skills(base_prompt);
-                let with_scratchpad = self.prepend_scratchpad(with_skills);
-                let final_prompt = self.prepend_ready_tasks(with_scratchpad);
+                let final_prompt = self.assemble_prompt(base_prompt, &regular_events);
 
                 debug!("build_prompt: routing to HatlessRalph (solo mode)");
                 return Some(final_prompt);
@@ -765,7 +1208,9 @@ impl EventLoop {
                     .join("\n");
 
                 // Build base prompt and prepend memories + scratchpad if available
-                let base_prompt = self.ralph.build_prompt(&events_context, &active_hats);
+                let base_prompt = self
+                    .ralph
+                    .build_prompt_sections(&events_context, &active_hats);
 
                 // Build prompt with active hats - filters instructions to only active hats
                 debug!(
@@ -778,9 +1223,7 @@ impl EventLoop {
 
                 // Clear guidance after active_hats references are no longer needed
                 self.ralph.clear_robot_guidance();
-                let with_skills = self.prepend_auto_inject_skills(base_prompt);
-                let with_scratchpad = self.prepend_scratchpad(with_skills);
-                let final_prompt = self.prepend_ready_tasks(with_scratchpad);
+                let final_prompt = self.assemble_prompt(base_prompt, &regular_events);
 
                 return Some(final_prompt);
             }
@@ -810,10 +1253,14 @@ impl EventLoop {
             "build_prompt: routing to build_custom_hat() for '{}'",
             hat_id.as_str()
         );
-        Some(
+        let mut assembly = PromptAssembly::new();
+        assembly.push(PromptSection::new(
+            SECTION_HAT_INSTRUCTIONS,
             self.instruction_builder
                 .build_custom_hat(hat, &events_context),
-        )
+        ));
+        assembly.fit_to_budget(self.config.event_loop.prompt_budget);
+        Some(assembly)
     }
 
     /// Stores guidance payloads, persists them to scratchpad, and prepares them for prompt injection.
@@ -894,7 +1341,45 @@ impl EventLoop {
         self.ralph.set_robot_guidance(self.robot_guidance.clone());
     }
 
-    /// Prepends auto-injected skill content to the prompt.
+    /// Surrounds Ralph's prompt sections with injected context and applies the prompt budget.
+    ///
+    /// Final order: ready tasks, scratchpad, auto-injected skills, triggered skills,
+    /// then Ralph's own sections.
+    fn assemble_prompt(&mut self, base: PromptAssembly, events: &[Event]) -> PromptAssembly {
+        let triggered = self.triggered_skills(events);
+
+        let mut assembly = PromptAssembly::new();
+        if let Some(section) = self.ready_tasks_section() {
+            assembly.push(section);
+        }
+        if let Some(section) = self.scratchpad_section() {
+            assembly.push(section);
+        }
+        self.push_auto_inject_skills(&mut assembly);
+        self.inject_triggered_skills(&mut assembly, &triggered);
+        assembly.extend(base);
+        self.state.last_triggered_skills = triggered;
+
+        let budget = self.config.event_loop.prompt_budget;
+        let before = assembly.total_tokens();
+        if !assembly.fit_to_budget(budget) {
+            warn!(
+                "Prompt is ~{} tokens after trimming, over prompt_budget of {}",
+                assembly.total_tokens(),
+                budget
+            );
+        } else if assembly.total_tokens() < before {
+            info!(
+                "Trimmed prompt from ~{} to ~{} tokens (prompt_budget: {})",
+                before,
+                assembly.total_tokens(),
+                budget
+            );
+        }
+        assembly
+    }
+
+    /// Adds auto-injected skill content to the prompt.
     ///
     /// This generalizes the former `prepend_memories()` into a skill auto-injection
     /// pipeline that handles memories, tools, and any other auto-inject skills.
@@ -903,35 +1388,25 @@ impl EventLoop {
     /// 1. Memory data + ralph-tools skill (special case: loads memory data from store, applies budget)
     /// 2. RObot interaction skill (gated by `robot.enabled`)
     /// 3. Other auto-inject skills from the registry (wrapped in XML tags)
-    fn prepend_auto_inject_skills(&self, prompt: String) -> String {
-        let mut prefix = String::new();
-
+    fn push_auto_inject_skills(&self, assembly: &mut PromptAssembly) {
         // 1. Memory data + ralph-tools skill — special case with data loading
-        self.inject_memories_and_tools_skill(&mut prefix);
+        self.inject_memories_and_tools_skill(assembly);
 
         // 2. RObot interaction skill — gated by robot.enabled
-        self.inject_robot_skill(&mut prefix);
+        self.inject_robot_skill(assembly);
 
         // 3. Other auto-inject skills from the registry
-        self.inject_custom_auto_skills(&mut prefix);
-
-        if prefix.is_empty() {
-            return prompt;
-        }
-
-        prefix.push_str("\n\n");
-        prefix.push_str(&prompt);
-        prefix
+        self.inject_custom_auto_skills(assembly);
     }
 
-    /// Injects memory data and the ralph-tools skill into the prefix.
+    /// Injects memory data and the ralph-tools skill into the prompt.
     ///
     /// Special case: loads memory entries from the store, applies budget
     /// truncation, then appends the ralph-tools skill content (which covers
     /// both tasks and memories CLI usage).
     /// Memory data is gated by `memories.enabled && memories.inject == Auto`.
     /// The ralph-tools skill is injected when either memories or tasks are enabled.
-    fn inject_memories_and_tools_skill(&self, prefix: &mut String) {
+    fn inject_memories_and_tools_skill(&self, assembly: &mut PromptAssembly) {
         let memories_config = &self.config.memories;
 
         // Inject memory DATA if memories are enabled with auto-inject
@@ -988,19 +1463,22 @@ impl EventLoop {
                     memories_content.len()
                 );
 
-                prefix.push_str(&memories_content);
+                assembly.push(PromptSection::new(
+                    SECTION_MEMORIES,
+                    format!("{memories_content}\n\n"),
+                ));
             }
         }
 
         // Inject the ralph-tools skill when either memories or tasks are enabled
         if memories_config.enabled || self.config.tasks.enabled {
             if let Some(skill) = self.skill_registry.get("ralph-tools") {
-                if !prefix.is_empty() {
-                    prefix.push_str("\n\n");
-                }
-                prefix.push_str(&format!(
-                    "<ralph-tools-skill>\n{}\n</ralph-tools-skill>",
-                    skill.content.trim()
+                assembly.push(PromptSection::new(
+                    "ralph-tools-skill",
+                    format!(
+                        "<ralph-tools-skill>\n{}\n</ralph-tools-skill>\n\n",
+                        skill.content.trim()
+                    ),
                 ));
                 debug!("Injected ralph-tools skill from registry");
             } else {
@@ -1009,53 +1487,129 @@ impl EventLoop {
         }
     }
 
-    /// Injects the RObot interaction skill content into the prefix.
+    /// Injects the RObot interaction skill content into the prompt.
     ///
     /// Gated by `robot.enabled`. Teaches agents how and when to interact
     /// with humans via `human.interact` events.
-    fn inject_robot_skill(&self, prefix: &mut String) {
+    fn inject_robot_skill(&self, assembly: &mut PromptAssembly) {
         if !self.config.robot.enabled {
             return;
         }
 
         if let Some(skill) = self.skill_registry.get("robot-interaction") {
-            if !prefix.is_empty() {
-                prefix.push_str("\n\n");
-            }
-            prefix.push_str(&format!(
-                "<robot-skill>\n{}\n</robot-skill>",
-                skill.content.trim()
+            assembly.push(PromptSection::new(
+                "robot-skill",
+                format!(
+                    "<robot-skill>\n{}\n</robot-skill>\n\n",
+                    skill.content.trim()
+                ),
             ));
             debug!("Injected robot interaction skill from registry");
         }
     }
 
     /// Injects any user-configured auto-inject skills (excluding built-in ralph-tools/robot-interaction).
-    fn inject_custom_auto_skills(&self, prefix: &mut String) {
+    fn inject_custom_auto_skills(&self, assembly: &mut PromptAssembly) {
         for skill in self.skill_registry.auto_inject_skills(None) {
             // Skip built-in skills handled above
             if skill.name == "ralph-tools" || skill.name == "robot-interaction" {
                 continue;
             }
 
-            if !prefix.is_empty() {
-                prefix.push_str("\n\n");
-            }
-            prefix.push_str(&format!(
-                "<{name}-skill>\n{content}\n</{name}-skill>",
-                name = skill.name,
-                content = skill.content.trim()
+            assembly.push(PromptSection::new(
+                format!("{}-skill", skill.name),
+                format!(
+                    "<{name}-skill>\n{content}\n</{name}-skill>\n\n",
+                    name = skill.name,
+                    content = skill.content.trim()
+                ),
             ));
             debug!("Injected auto-inject skill: {}", skill.name);
         }
     }
 
-    /// Prepends scratchpad content to the prompt if the file exists and is non-empty.
+    /// Selects the skills whose `triggers:` match this iteration's events,
+    /// objective, and touched files.
+    fn triggered_skills(&self, events: &[Event]) -> Vec<TriggeredSkill> {
+        let mut context = TriggerContext {
+            topics: events.iter().map(|e| e.topic.to_string()).collect(),
+            texts: events.iter().map(|e| e.payload.clone()).collect(),
+            files: Vec::new(),
+        };
+        if let Some(objective) = self.ralph.objective() {
+            context.texts.push(objective.to_string());
+        }
+        if self.skill_registry.needs_touched_files(None) {
+            let workspace = self
+                .loop_context
+                .as_ref()
+                .map_or(self.config.core.workspace_root.as_path(), |ctx| {
+                    ctx.workspace()
+                });
+            context.files = crate::git_ops::get_touched_files(workspace).unwrap_or_else(|e| {
+                debug!("Could not list touched files for skill triggers: {}", e);
+                Vec::new()
+            });
+        }
+
+        self.skill_registry.triggered_skills(None, &context)
+    }
+
+    /// Injects the full content of skills selected by their `triggers:`.
+    fn inject_triggered_skills(&self, assembly: &mut PromptAssembly, triggered: &[TriggeredSkill]) {
+        for triggered_skill in triggered {
+            let Some(skill) = self.skill_registry.get(&triggered_skill.name) else {
+                continue;
+            };
+            assembly.push(PromptSection::new(
+                format!("{}-skill", skill.name),
+                format!(
+                    "<{name}-skill>\n{content}\n</{name}-skill>\n\n",
+                    name = skill.name,
+                    content = skill.content.trim()
+                ),
+            ));
+            info!(
+                "Injected skill '{}' ({})",
+                triggered_skill.name, triggered_skill.reason
+            );
+        }
+    }
+
+    /// Appends the skills injected into the last prompt to the skill usage log.
+    fn record_skill_injections(&self) {
+        if self.state.last_triggered_skills.is_empty() {
+            return;
+        }
+
+        let records: Vec<SkillUsageRecord> = self
+            .state
+            .last_triggered_skills
+            .iter()
+            .map(|skill| {
+                SkillUsageRecord::injected(&skill.name, self.state.iteration + 1, &skill.reason)
+            })
+            .collect();
+        let log = SkillUsageLog::new(self.skill_usage_path());
+        if let Err(e) = log.append(&records) {
+            warn!("Failed to record skill usage: {}", e);
+        }
+    }
+
+    /// Returns the skill usage log path based on loop context or default.
+    fn skill_usage_path(&self) -> PathBuf {
+        self.loop_context
+            .as_ref()
+            .map(|ctx| ctx.ralph_dir().join(SKILL_USAGE_FILE))
+            .unwrap_or_else(|| PathBuf::from(".ralph").join(SKILL_USAGE_FILE))
+    }
+
+    /// Builds the scratchpad section if the file exists and is non-empty.
     ///
     /// The scratchpad is the agent's working memory for the current objective.
     /// Auto-injecting saves one tool call per iteration.
     /// When the file exceeds the budget, the TAIL is kept (most recent entries).
-    fn prepend_scratchpad(&self, prompt: String) -> String {
+    fn scratchpad_section(&self) -> Option<PromptSection> {
         let scratchpad_path = self.scratchpad_path();
 
         let resolved_path = if scratchpad_path.is_relative() {
@@ -1069,20 +1623,20 @@ impl EventLoop {
                 "Scratchpad not found at {:?}, skipping injection",
                 resolved_path
             );
-            return prompt;
+            return None;
         }
 
         let content = match std::fs::read_to_string(&resolved_path) {
             Ok(c) => c,
             Err(e) => {
                 info!("Failed to read scratchpad for injection: {}", e);
-                return prompt;
+                return None;
             }
         };
 
         if content.trim().is_empty() {
             debug!("Scratchpad is empty, skipping injection");
-            return prompt;
+            return None;
         }
 
         // Budget: 4000 tokens ~16000 chars. Keep the TAIL (most recent content).
@@ -1121,22 +1675,22 @@ impl EventLoop {
 
         info!("Injecting scratchpad ({} chars) into prompt", content.len());
 
-        let mut final_prompt = format!(
-            "<scratchpad path=\"{}\">\n{}\n</scratchpad>\n\n",
-            self.config.core.scratchpad, content
-        );
-        final_prompt.push_str(&prompt);
-        final_prompt
+        Some(PromptSection::wrapped(
+            SECTION_SCRATCHPAD,
+            format!("<scratchpad path=\"{}\">\n", self.config.core.scratchpad),
+            content,
+            "\n</scratchpad>\n\n",
+        ))
     }
 
-    /// Prepends ready tasks to the prompt if tasks are enabled and any exist.
+    /// Builds the ready tasks section if tasks are enabled and any exist.
     ///
     /// Loads the task store and formats ready (unblocked, open) tasks into
     /// a `<ready-tasks>` XML block. This saves the agent a tool call per
     /// iteration and puts tasks at the same prominence as the scratchpad.
-    fn prepend_ready_tasks(&self, prompt: String) -> String {
+    fn ready_tasks_section(&self) -> Option<PromptSection> {
         if !self.config.tasks.enabled {
-            return prompt;
+            return None;
         }
 
         use crate::task::TaskStatus;
@@ -1150,14 +1704,14 @@ impl EventLoop {
         };
 
         if !resolved_path.exists() {
-            return prompt;
+            return None;
         }
 
         let store = match TaskStore::load(&resolved_path) {
             Ok(s) => s,
             Err(e) => {
                 info!("Failed to load task store for injection: {}", e);
-                return prompt;
+                return None;
             }
         };
 
@@ -1166,7 +1720,7 @@ impl EventLoop {
         let closed_count = store.all().len() - open.len();
 
         if open.is_empty() && closed_count == 0 {
-            return prompt;
+            return None;
         }
 
         let mut section = String::from("<ready-tasks>\n");
@@ -1218,9 +1772,7 @@ impl EventLoop {
             closed_count
         );
 
-        let mut final_prompt = section;
-        final_prompt.push_str(&prompt);
-        final_prompt
+        Some(PromptSection::new(SECTION_READY_TASKS, section))
     }
 
     /// Builds the Ralph prompt (coordination mode).
@@ -1243,7 +1795,7 @@ impl EventLoop {
     fn determine_active_hat_ids(&self, events: &[Event]) -> Vec<HatId> {
         let mut active_hat_ids = Vec::new();
         for event in events {
-            if let Some(hat) = self.registry.get_for_topic(event.topic.as_str()) {
+            if let Some(hat) = self.registry.get_for_event(event) {
                 // Avoid duplicates
                 if !active_hat_ids.iter().any(|id| id == &hat.id) {
                     active_hat_ids.push(hat.id.clone());
@@ -1341,7 +1893,7 @@ impl EventLoop {
             let Some(event) = events.first() else {
                 continue;
             };
-            if let Some(active_hat) = self.registry.get_for_topic(event.topic.as_str()) {
+            if let Some(active_hat) = self.registry.get_for_event(event) {
                 return active_hat.id.clone();
             }
         }
@@ -1364,7 +1916,7 @@ impl EventLoop {
                 "No events written by hat, injecting default_publishes event"
             );
 
-            self.bus.publish(default_event);
+            self.publish_or_hold(default_event);
         }
     }
 
@@ -1464,6 +2016,17 @@ impl EventLoop {
         self.state.cumulative_cost += cost;
     }
 
+    /// Adds cost to the cumulative total and to the breakdown for the
+    /// backend and model that incurred it.
+    pub fn add_model_cost(&mut self, backend: &str, model: Option<&str>, cost: f64) {
+        self.add_cost(cost);
+        let label = match model {
+            Some(model) => format!("{backend}/{model}"),
+            None => backend.to_string(),
+        };
+        *self.state.cost_by_model.entry(label).or_default() += cost;
+    }
+
     /// Verifies all tasks in scratchpad are complete or cancelled.
     ///
     /// Returns:
@@ -1633,8 +2196,22 @@ impl EventLoop {
     ///
     /// Returns true if Ralph should be invoked to handle orphaned events.
     pub fn process_events_from_jsonl(&mut self) -> std::io::Result<bool> {
-        let result = self.event_reader.read_new_events()?;
+        let result = self.read_events_from_jsonl()?;
+        Ok(self.process_parsed_events(result))
+    }
+
+    /// Reads new events from JSONL without processing them.
+    ///
+    /// Pair with `process_parsed_events` when the raw events are needed as
+    /// well, e.g. to record them for deterministic replay.
+    pub fn read_events_from_jsonl(&mut self) -> std::io::Result<ParseResult> {
+        self.event_reader.read_new_events()
+    }
 
+    /// Processes events already read from JSONL (see `process_events_from_jsonl`).
+    ///
+    /// Returns true if Ralph should be invoked to handle orphaned events.
+    pub fn process_parsed_events(&mut self, result: ParseResult) -> bool {
         // Handle malformed lines with backpressure
         for malformed in &result.malformed {
             let payload = format!(
@@ -1657,16 +2234,21 @@ impl EventLoop {
         }
 
         if result.events.is_empty() && result.malformed.is_empty() {
-            return Ok(false);
+            return false;
         }
 
         let mut has_orphans = false;
 
         // Validate and transform events (apply backpressure for build.done)
         let mut validated_events = Vec::new();
-        let completion_topic = self.config.event_loop.completion_promise.as_str();
+        let completion_topic = self.config.event_loop.completion_promise.clone();
         let total_events = result.events.len();
         for (index, event) in result.events.into_iter().enumerate() {
+            if event.is_deferred() {
+                self.defer_event(&event);
+                continue;
+            }
+
             let payload = event.payload.clone().unwrap_or_default();
 
             if event.topic == completion_topic {
@@ -1943,6 +2525,7 @@ impl EventLoop {
         // When a human.interact event is detected and robot service is active,
         // send the question and block until human.response or timeout.
         let mut response_event = None;
+        let mut question_sent = false;
         let ask_human_idx = validated_events
             .iter()
             .position(|e| e.topic == "human.interact".into());
@@ -1981,6 +2564,7 @@ impl EventLoop {
 
                 // Block: poll events file for human.response
                 // Per spec, even on send failure we treat as timeout (continue without blocking)
+                question_sent = send_ok;
                 if send_ok {
                     // Read the active events path from the current-events marker,
                     // falling back to the default events.jsonl if not available.
@@ -2034,11 +2618,29 @@ impl EventLoop {
             }
         }
 
+        // The robot tracks one open question per loop, so a pending approval
+        // request was replaced by the human.interact question: ask it again
+        if question_sent && self.awaiting_approval() {
+            self.state.approvals.ask_again();
+            self.ask_next_approval();
+        }
+
         // Publish validated events to the bus.
         // Ralph is always registered with subscribe("*"), so every event has at least
         // one subscriber. Events without a specific hat subscriber are "orphaned" —
         // Ralph handles them as the universal fallback.
         for event in validated_events {
+            // Decisions only come from the human's channel (see tick_approvals),
+            // so a hat cannot approve what it emitted
+            if event.topic == APPROVAL_RESPONSE_TOPIC.into() {
+                warn!("approval.response in the events file is ignored — only a human can decide");
+                continue;
+            }
+            // Held events wait for a human
+            if self.hold_for_approval(&event) {
+                continue;
+            }
+
             self.diagnostics.log_orchestration(
                 self.state.iteration,
                 "jsonl",
@@ -2047,7 +2649,7 @@ impl EventLoop {
                 },
             );
 
-            if !self.registry.has_subscriber(event.topic.as_str()) {
+            if self.registry.get_for_event(&event).is_none() {
                 has_orphans = true;
             }
 
@@ -2067,7 +2669,7 @@ impl EventLoop {
             self.bus.publish(response);
         }
 
-        Ok(has_orphans)
+        has_orphans
     }
 
     /// Checks if output contains a completion event from Ralph.
@@ -1042,7 +1042,11 @@ fn test_default_publishes_injects_when_no_events() {
             instructions: "Test hat".to_string(),
             extra_instructions: vec![],
             backend: None,
+            model: None,
+            effort: None,
             default_publishes: Some("task.done".to_string()),
+            when: None,
+            write_scope: None,
             max_activations: None,
         },
     );
@@ -1088,7 +1092,11 @@ fn test_default_publishes_not_injected_when_events_written() {
             instructions: "Test hat".to_string(),
             extra_instructions: vec![],
             backend: None,
+            model: None,
+            effort: None,
             default_publishes: Some("task.done".to_string()),
+            when: None,
+            write_scope: None,
             max_activations: None,
         },
     );
@@ -1142,7 +1150,11 @@ fn test_default_publishes_not_injected_when_not_configured() {
             instructions: "Test hat".to_string(),
             extra_instructions: vec![],
             backend: None,
+            model: None,
+            effort: None,
             default_publishes: None, // No default configured
+            when: None,
+            write_scope: None,
             max_activations: None,
         },
     );
@@ -1828,6 +1840,25 @@ event_loop:
     );
 }
 
+#[test]
+fn test_add_model_cost_tracks_breakdown() {
+    let mut event_loop = EventLoop::new(RalphConfig::default());
+
+    event_loop.add_model_cost("claude", Some("opus"), 0.5);
+    event_loop.add_model_cost("claude", Some("opus"), 0.25);
+    event_loop.add_model_cost("gemini", None, 0.5);
+
+    assert!((event_loop.state.cumulative_cost - 1.25).abs() < f64::EPSILON);
+    assert_eq!(
+        event_loop.state.cost_by_model.get("claude/opus").copied(),
+        Some(0.75)
+    );
+    assert_eq!(
+        event_loop.state.cost_by_model.get("gemini").copied(),
+        Some(0.5)
+    );
+}
+
 #[test]
 fn test_malformed_events_increment_counter() {
     // Kills: line 1063 `+= 1` → `-=` / `*=`
@@ -2782,7 +2813,7 @@ fn test_format_duration_variants() {
 
     assert_eq!(format_duration(Duration::from_secs(45)), "45s");
     assert_eq!(format_duration(Duration::from_secs(61)), "1m 1s");
-    assert_eq!(format_duration(Duration::from_secs(3600)), "1h 0m 0s");
+    assert_eq!(format_duration(Duration::from_hours(1)), "1h 0m 0s");
     assert_eq!(format_duration(Duration::from_secs(3661)), "1h 1m 1s");
 }
 
@@ -3016,6 +3047,29 @@ fn test_termination_reason_strings_and_flags() {
     }
 }
 
+#[test]
+fn test_termination_reason_from_name() {
+    for reason in TerminationReason::ALL {
+        assert_eq!(
+            TerminationReason::from_name(reason.as_str()),
+            Some(reason.clone())
+        );
+        assert_eq!(
+            TerminationReason::from_name(&format!("{reason:?}")),
+            Some(reason)
+        );
+    }
+    assert_eq!(
+        TerminationReason::from_name("completion_promise"),
+        Some(TerminationReason::CompletionPromise)
+    );
+    assert_eq!(
+        TerminationReason::from_name("Max-Iterations"),
+        Some(TerminationReason::MaxIterations)
+    );
+    assert_eq!(TerminationReason::from_name("error: no backend"), None);
+}
+
 #[test]
 fn test_has_pending_human_events_detects_guidance() {
     let mut event_loop = EventLoop::new(RalphConfig::default());
@@ -3199,3 +3253,458 @@ hats:
     assert!(drop_again);
     assert!(event_again.is_none());
 }
+
+#[test]
+fn test_prompt_budget_trims_scratchpad_head() {
+    let dir = tempfile::tempdir().unwrap();
+    let scratchpad_path = dir.path().join("scratchpad.md");
+    let notes = (0..300)
+        .map(|i| format!("- note {i:03}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    std::fs::write(&scratchpad_path, &notes).unwrap();
+
+    let yaml = format!("core:\n  scratchpad: \"{}\"\n", scratchpad_path.display());
+    let config: RalphConfig = serde_yaml::from_str(&yaml).unwrap();
+    let ralph_id = HatId::new("ralph");
+
+    let mut unbounded = EventLoop::new(config.clone());
+    unbounded.initialize("Test prompt");
+    let full = unbounded.build_prompt_assembly(&ralph_id).unwrap();
+    assert!(!full.sections().iter().any(PromptSection::is_trimmed));
+
+    let mut config = config;
+    config.event_loop.prompt_budget = full.total_tokens() - 200;
+    let mut event_loop = EventLoop::new(config);
+    event_loop.initialize("Test prompt");
+    let assembly = event_loop.build_prompt_assembly(&ralph_id).unwrap();
+
+    assert!(assembly.total_tokens() <= assembly.budget());
+    let scratchpad = assembly.section(SECTION_SCRATCHPAD).unwrap();
+    assert!(scratchpad.is_trimmed());
+    let prompt = assembly.render();
+    assert!(prompt.contains("tokens trimmed to fit prompt_budget"));
+    assert!(prompt.contains("- note 299"), "most recent notes are kept");
+    assert!(!prompt.contains("- note 000"));
+    assert!(prompt.contains("Test prompt"), "objective is never trimmed");
+}
+
+#[test]
+fn test_triggered_skills_are_injected_and_recorded() {
+    let dir = tempfile::tempdir().unwrap();
+    let skills_dir = dir.path().join("skills");
+    std::fs::create_dir(&skills_dir).unwrap();
+    std::fs::write(
+        skills_dir.join("postgres.md"),
+        "---\nname: postgres\ndescription: Postgres tips\ntriggers:\n  keywords: [postgres]\n---\nPOSTGRES SKILL BODY\n",
+    )
+    .unwrap();
+    std::fs::write(
+        skills_dir.join("deploy.md"),
+        "---\nname: deploy\ndescription: Deploy tips\ntriggers:\n  topics: [\"deploy.*\"]\n---\nDEPLOY SKILL BODY\n",
+    )
+    .unwrap();
+
+    let yaml = format!("skills:\n  dirs: [\"{}\"]\n", skills_dir.display());
+    let config: RalphConfig = serde_yaml::from_str(&yaml).unwrap();
+    let context = LoopContext::primary(dir.path().to_path_buf());
+    let mut event_loop = EventLoop::with_context_and_diagnostics(
+        config,
+        context,
+        crate::diagnostics::DiagnosticsCollector::disabled(),
+    );
+    event_loop.initialize("Migrate the Postgres schema");
+
+    let prompt = event_loop.build_prompt(&HatId::new("ralph")).unwrap();
+    assert!(prompt.contains("<postgres-skill>\nPOSTGRES SKILL BODY\n</postgres-skill>"));
+    assert!(!prompt.contains("DEPLOY SKILL BODY"));
+    assert_eq!(event_loop.state().last_triggered_skills.len(), 1);
+
+    let log = SkillUsageLog::new(dir.path().join(".ralph").join(SKILL_USAGE_FILE));
+    let records = log.read().unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].skill, "postgres");
+    assert_eq!(records[0].iteration, Some(1));
+    assert_eq!(records[0].reason.as_deref(), Some("keyword 'postgres'"));
+}
+
+#[test]
+fn test_deferred_jsonl_event_is_delivered_after_iterations() {
+    use std::io::Write;
+
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let loop_context = LoopContext::primary(temp_dir.path().to_path_buf());
+    let events_path = temp_dir.path().join("events.jsonl");
+    let mut event_loop = EventLoop::with_context(RalphConfig::default(), loop_context);
+    event_loop.event_reader = crate::event_reader::EventReader::new(&events_path);
+
+    let mut file = std::fs::File::create(&events_path).unwrap();
+    writeln!(
+        file,
+        r#"{{"topic":"review.later","payload":"check again","ts":"2026-01-01T00:00:00Z","after_iterations":2}}"#
+    )
+    .unwrap();
+    let _ = event_loop.process_events_from_jsonl();
+
+    // Parked rather than published, and persisted for resume
+    assert!(!event_loop.has_pending_events());
+    assert_eq!(event_loop.state().timers.len(), 1);
+    assert!(temp_dir.path().join(".ralph/agent/timers.json").exists());
+
+    event_loop.state.iteration = 1;
+    assert!(event_loop.tick_timers().is_empty());
+
+    event_loop.state.iteration = 2;
+    let fired = event_loop.tick_timers();
+    assert_eq!(fired.len(), 1);
+    assert_eq!(fired[0].payload, "check again");
+    assert!(event_loop.has_pending_events());
+    assert!(!temp_dir.path().join(".ralph/agent/timers.json").exists());
+}
+
+#[test]
+fn test_watchdog_fires_unless_expected_event_arrives() {
+    let yaml = r"
+event_loop:
+  watchdogs:
+    - expect: build.done
+      after: build.task
+      within_iterations: 2
+      else: build.timeout
+";
+    let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
+    let mut event_loop = EventLoop::new(config);
+    event_loop.initialize("Test");
+    let ralph = HatId::new("ralph");
+
+    // build.task arms the watchdog when it is seen pending
+    event_loop.bus.publish(Event::new("build.task", "do it"));
+    assert!(event_loop.tick_timers().is_empty());
+    assert_eq!(event_loop.pending_timers().len(), 1);
+    event_loop.bus.take_pending(&ralph);
+
+    event_loop.state.iteration = 2;
+    let fired = event_loop.tick_timers();
+    assert_eq!(fired.len(), 1);
+    assert_eq!(fired[0].topic.as_str(), "build.timeout");
+    event_loop.bus.take_pending(&ralph);
+
+    // Re-armed, then satisfied by build.done before the deadline
+    event_loop.bus.publish(Event::new("build.task", "again"));
+    event_loop.tick_timers();
+    event_loop.bus.take_pending(&ralph);
+    event_loop.bus.publish(Event::new("build.done", "ok"));
+    event_loop.tick_timers();
+    assert!(event_loop.pending_timers().is_empty());
+}
+
+#[test]
+fn test_resume_restores_persisted_timers() {
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let yaml = r"
+event_loop:
+  watchdogs:
+    - expect: build.done
+      within: 20m
+      else: build.timeout
+";
+    let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
+
+    let mut first = EventLoop::with_context(
+        config.clone(),
+        LoopContext::primary(temp_dir.path().to_path_buf()),
+    );
+    first.initialize("Test");
+    let armed = first.state().timers.clone();
+    assert_eq!(armed.len(), 1);
+
+    // Start watchdogs are written at the first iteration boundary, not on initialize
+    let timers_path = temp_dir.path().join(".ralph/agent/timers.json");
+    assert!(!timers_path.exists());
+    first.tick_timers();
+    assert!(timers_path.exists());
+
+    // The resumed loop keeps the original deadline instead of re-arming
+    let mut resumed =
+        EventLoop::with_context(config, LoopContext::primary(temp_dir.path().to_path_buf()));
+    resumed.initialize_resume("Test");
+    assert_eq!(
+        resumed.state().timers.iter().collect::<Vec<_>>(),
+        armed.iter().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_gated_event_is_held_until_approved_or_rejected() {
+    use std::io::Write;
+
+    let yaml = r"
+events:
+  deploy.*:
+    requires_approval: true
+";
+    let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let loop_context = LoopContext::primary(temp_dir.path().to_path_buf());
+    let events_path = temp_dir.path().join("events.jsonl");
+    let mut event_loop = EventLoop::with_context(config, loop_context);
+    event_loop.event_reader = crate::event_reader::EventReader::new(&events_path);
+    let approvals_path = temp_dir.path().join(".ralph/agent/approvals.json");
+    let ralph = HatId::new("ralph");
+    let pending_topics = |event_loop: &EventLoop| -> Vec<String> {
+        event_loop
+            .bus
+            .peek_pending(&ralph)
+            .into_iter()
+            .flatten()
+            .map(|e| e.topic.to_string())
+            .collect()
+    };
+
+    let mut file = std::fs::File::create(&events_path).unwrap();
+    writeln!(
+        file,
+        r#"{{"topic":"deploy.start","payload":"v1.2","ts":"2026-01-01T00:00:00Z"}}"#
+    )
+    .unwrap();
+    writeln!(
+        file,
+        r#"{{"topic":"plan.ready","payload":"ok","ts":"2026-01-01T00:00:00Z"}}"#
+    )
+    .unwrap();
+    let _ = event_loop.process_events_from_jsonl();
+
+    // Only the ungated event is delivered
+    assert_eq!(pending_topics(&event_loop), vec!["plan.ready"]);
+    assert!(event_loop.awaiting_approval());
+    assert_eq!(event_loop.pending_approvals(), vec!["deploy.start"]);
+    assert!(approvals_path.exists());
+    event_loop.bus.take_pending(&ralph);
+
+    // The human's decision arrives through the decisions file
+    let decisions_path = temp_dir.path().join(".ralph/approval-decisions.jsonl");
+    crate::approvals::record_decision(&decisions_path, "approve").unwrap();
+    let settled = event_loop.tick_approvals();
+    assert_eq!(settled.len(), 1);
+    let delivered = event_loop.bus.take_pending(&ralph);
+    assert_eq!(delivered.len(), 1);
+    assert_eq!(delivered[0].topic.as_str(), "deploy.start");
+    assert_eq!(delivered[0].payload, "v1.2");
+    assert!(!event_loop.awaiting_approval());
+    assert!(!approvals_path.exists());
+
+    writeln!(
+        file,
+        r#"{{"topic":"deploy.rollback","payload":"","ts":"2026-01-01T00:00:00Z"}}"#
+    )
+    .unwrap();
+    let _ = event_loop.process_events_from_jsonl();
+    crate::approvals::record_decision(&decisions_path, "reject: not during the freeze").unwrap();
+    event_loop.tick_approvals();
+    let delivered = event_loop.bus.take_pending(&ralph);
+    assert_eq!(delivered.len(), 1);
+    assert_eq!(delivered[0].topic.as_str(), "deploy.rollback.rejected");
+    assert_eq!(delivered[0].payload, "not during the freeze");
+}
+
+#[test]
+fn test_agent_cannot_approve_its_own_held_event() {
+    use std::io::Write;
+
+    let yaml = r"
+events:
+  deploy.*:
+    requires_approval: true
+";
+    let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let decisions_path = temp_dir.path().join(".ralph/approval-decisions.jsonl");
+    // A decision written before the loop started answers nothing held now
+    crate::approvals::record_decision(&decisions_path, "approve").unwrap();
+    let loop_context = LoopContext::primary(temp_dir.path().to_path_buf());
+    let events_path = temp_dir.path().join("events.jsonl");
+    let mut event_loop = EventLoop::with_context(config, loop_context);
+    event_loop.event_reader = crate::event_reader::EventReader::new(&events_path);
+    let ralph = HatId::new("ralph");
+
+    // The hat emits the gated event and approves it in the same breath
+    let mut file = std::fs::File::create(&events_path).unwrap();
+    writeln!(
+        file,
+        r#"{{"topic":"deploy.start","payload":"v1.2","ts":"2026-01-01T00:00:00Z"}}"#
+    )
+    .unwrap();
+    writeln!(
+        file,
+        r#"{{"topic":"approval.response","payload":"approve","ts":"2026-01-01T00:00:00Z"}}"#
+    )
+    .unwrap();
+    let _ = event_loop.process_events_from_jsonl();
+    assert!(event_loop.tick_approvals().is_empty());
+
+    assert!(event_loop.awaiting_approval());
+    assert_eq!(event_loop.pending_approvals(), vec!["deploy.start"]);
+    assert!(
+        event_loop
+            .bus
+            .peek_pending(&ralph)
+            .into_iter()
+            .flatten()
+            .all(|e| e.topic.as_str() != "deploy.start" && e.topic.as_str() != "approval.response")
+    );
+}
+
+#[test]
+fn test_invalid_approval_timeout_rejects_instead_of_waiting() {
+    // Validation rejects this config; an unvalidated one must not wait forever
+    let yaml = r"
+events:
+  deploy.start:
+    requires_approval: true
+    approval_timeout: 10 minutes
+";
+    let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
+    assert!(config.validate().is_err());
+    let mut event_loop = EventLoop::new(config);
+
+    assert!(!event_loop.publish_or_hold(Event::new("deploy.start", "v1.2")));
+
+    assert!(!event_loop.awaiting_approval());
+    let delivered = event_loop.bus.take_pending(&HatId::new("ralph"));
+    assert_eq!(delivered.len(), 1);
+    assert_eq!(delivered[0].topic.as_str(), "deploy.start.rejected");
+    assert!(
+        delivered[0].payload.contains("Invalid approval_timeout"),
+        "{}",
+        delivered[0].payload
+    );
+}
+
+#[test]
+fn test_approved_event_keeps_its_source() {
+    let yaml = r"
+events:
+  deploy.start:
+    requires_approval: true
+";
+    let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
+    let mut event_loop = EventLoop::new(config);
+
+    event_loop.publish_or_hold(Event::new("deploy.start", "v1.2").with_source("deployer"));
+    assert!(event_loop.awaiting_approval());
+
+    let settled = event_loop.settle_approval("approve").unwrap();
+    assert_eq!(settled.topic.as_str(), "deploy.start");
+    assert_eq!(settled.source, Some(HatId::new("deployer")));
+}
+
+#[test]
+fn test_gated_default_publishes_event_is_held() {
+    let yaml = r#"
+hats:
+  deployer:
+    name: "Deployer"
+    triggers: ["plan.ready"]
+    publishes: ["deploy.start"]
+    default_publishes: "deploy.start"
+events:
+  deploy.start:
+    requires_approval: true
+"#;
+    let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
+    let mut event_loop = EventLoop::new(config);
+
+    event_loop.check_default_publishes(&HatId::new("deployer"));
+
+    assert!(!event_loop.bus.has_pending());
+    assert_eq!(event_loop.pending_approvals(), vec!["deploy.start"]);
+}
+
+#[test]
+fn test_human_interact_reply_passes_through_while_approval_is_pending() {
+    use std::io::Write;
+
+    let yaml = r"
+events:
+  deploy.*:
+    requires_approval: true
+";
+    let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
+    let temp_dir = tempfile::TempDir::new().unwrap();
+    let loop_context = LoopContext::primary(temp_dir.path().to_path_buf());
+    let events_path = temp_dir.path().join("events.jsonl");
+    let mut event_loop = EventLoop::with_context(config, loop_context);
+    event_loop.event_reader = crate::event_reader::EventReader::new(&events_path);
+    let ralph = HatId::new("ralph");
+
+    let mut file = std::fs::File::create(&events_path).unwrap();
+    writeln!(
+        file,
+        r#"{{"topic":"deploy.start","payload":"v1.2","ts":"2026-01-01T00:00:00Z"}}"#
+    )
+    .unwrap();
+    let _ = event_loop.process_events_from_jsonl();
+    assert!(event_loop.awaiting_approval());
+
+    // A question asked while the deploy is held is answered on its own
+    writeln!(
+        file,
+        r#"{{"topic":"human.interact","payload":"Which DB?","ts":"2026-01-01T00:00:00Z"}}"#
+    )
+    .unwrap();
+    writeln!(
+        file,
+        r#"{{"topic":"human.response","payload":"Postgres","ts":"2026-01-01T00:00:00Z"}}"#
+    )
+    .unwrap();
+    let _ = event_loop.process_events_from_jsonl();
+
+    let responses: Vec<_> = event_loop
+        .bus
+        .take_human_pending()
+        .into_iter()
+        .filter(|e| e.topic.as_str() == "human.response")
+        .collect();
+    assert_eq!(responses.len(), 1);
+    assert_eq!(responses[0].payload, "Postgres");
+    assert_eq!(event_loop.pending_approvals(), vec!["deploy.start"]);
+
+    let decisions_path = temp_dir.path().join(".ralph/approval-decisions.jsonl");
+    crate::approvals::record_decision(&decisions_path, "approve").unwrap();
+    event_loop.tick_approvals();
+    let delivered = event_loop.bus.take_pending(&ralph);
+    assert_eq!(delivered.len(), 1);
+    assert_eq!(delivered[0].topic.as_str(), "deploy.start");
+    assert!(!event_loop.awaiting_approval());
+}
+
+#[test]
+fn test_gated_event_is_rejected_after_approval_timeout() {
+    let yaml = r"
+events:
+  deploy.start:
+    requires_approval: true
+    approval_timeout: 0s
+";
+    let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
+    let mut event_loop = EventLoop::new(config);
+    event_loop.initialize("Test");
+    let ralph = HatId::new("ralph");
+    event_loop.bus.take_pending(&ralph);
+
+    // Delayed events are gated too when they fall due
+    event_loop
+        .state
+        .timers
+        .schedule_event("deploy.start", "v1.2", Some(chrono::Utc::now()), None);
+    assert_eq!(event_loop.tick_timers().len(), 1);
+    assert!(!event_loop.has_pending_events());
+    assert!(event_loop.awaiting_approval());
+
+    let rejected = event_loop.tick_approvals();
+    assert_eq!(rejected.len(), 1);
+    assert_eq!(rejected[0].topic.as_str(), "deploy.start.rejected");
+    assert_eq!(rejected[0].payload, "No approval within 0s");
+    assert!(!event_loop.awaiting_approval());
+    assert!(event_loop.has_pending_events());
+}
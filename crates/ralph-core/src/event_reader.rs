@@ -11,7 +11,7 @@ use tracing::warn;
 /// Contains both successfully parsed events and information about lines
 /// that failed to parse. This supports backpressure validation by allowing
 /// the caller to respond to malformed events.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ParseResult {
     /// Successfully parsed events.
     pub events: Vec<Event>,
@@ -23,7 +23,7 @@ pub struct ParseResult {
 ///
 /// Used for backpressure feedback - when agents write invalid JSONL,
 /// this provides details for the `event.malformed` system event.
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MalformedLine {
     /// Line number in the file (1-indexed).
     pub line_number: u64,
@@ -92,6 +92,19 @@ pub struct Event {
     )]
     pub payload: Option<String>,
     pub ts: String,
+    /// Deliver no earlier than this RFC 3339 time (`ralph emit --delay`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deliver_at: Option<String>,
+    /// Deliver after this many more iterations (`ralph emit --after-iterations`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub after_iterations: Option<u32>,
+}
+
+impl Event {
+    /// Returns true if the event asks for deferred delivery.
+    pub fn is_deferred(&self) -> bool {
+        self.deliver_at.is_some() || self.after_iterations.is_some()
+    }
 }
 
 /// Reads new events from `.ralph/events.jsonl` since last read.
@@ -195,6 +208,12 @@ impl EventReader {
     pub fn reset(&mut self) {
         self.position = 0;
     }
+
+    /// Moves the position to the end of the file, so only lines appended
+    /// from now on are read.
+    pub fn skip_existing(&mut self) {
+        self.position = std::fs::metadata(&self.path).map_or(0, |m| m.len());
+    }
 }
 
 #[cfg(test)]
@@ -408,6 +408,53 @@ pub fn get_recent_files(path: impl AsRef<Path>, limit: usize) -> Result<Vec<Stri
     Ok(files)
 }
 
+/// Get the files touched in the working tree and the most recent commit.
+///
+/// Includes staged, unstaged, and untracked changes plus the files changed by
+/// `HEAD`. Paths are relative to the repository root and deduplicated.
+///
+/// # Arguments
+///
+/// * `path` - Path to the git repository (or worktree)
+pub fn get_touched_files(path: impl AsRef<Path>) -> Result<Vec<String>, GitOpsError> {
+    let path = path.as_ref();
+    let output = Command::new("git")
+        .args(["status", "--porcelain", "--untracked-files=all"])
+        .current_dir(path)
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(GitOpsError::Git(stderr.to_string()));
+    }
+
+    let mut files: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.get(3..))
+        // Renames are reported as "old -> new"
+        .map(|entry| entry.rsplit(" -> ").next().unwrap_or(entry))
+        .map(|entry| entry.trim_matches('"').to_string())
+        .collect();
+
+    // The last commit fails on a repository with a single commit; that's fine.
+    let output = Command::new("git")
+        .args(["diff", "--name-only", "HEAD~1..HEAD", "--"])
+        .current_dir(path)
+        .output()?;
+    if output.status.success() {
+        files.extend(
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter(|line| !line.is_empty())
+                .map(String::from),
+        );
+    }
+
+    files.sort();
+    files.dedup();
+    Ok(files)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -766,4 +813,29 @@ mod tests {
             files
         );
     }
+
+    #[test]
+    fn test_get_touched_files() {
+        let temp = TempDir::new().unwrap();
+        init_git_repo(temp.path());
+
+        fs::write(temp.path().join("committed.txt"), "content").unwrap();
+        Command::new("git")
+            .args(["add", "committed.txt"])
+            .current_dir(temp.path())
+            .output()
+            .unwrap();
+        Command::new("git")
+            .args(["commit", "-m", "Add committed"])
+            .current_dir(temp.path())
+            .output()
+            .unwrap();
+
+        fs::create_dir(temp.path().join("db")).unwrap();
+        fs::write(temp.path().join("db/init.sql"), "select 1;").unwrap();
+        fs::write(temp.path().join("README.md"), "# Changed").unwrap();
+
+        let files = get_touched_files(temp.path()).unwrap();
+        assert_eq!(files, vec!["README.md", "committed.txt", "db/init.sql"]);
+    }
 }
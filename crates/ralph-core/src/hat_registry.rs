@@ -1,7 +1,7 @@
 //! Hat registry for managing agent personas.
 
-use crate::config::{HatConfig, RalphConfig};
-use ralph_proto::{Hat, HatId, Topic};
+use crate::config::{ConfigError, HatConfig, RalphConfig};
+use ralph_proto::{Event, Hat, HatId, Topic};
 use std::collections::{BTreeMap, HashSet};
 
 /// Registry for managing and creating hats from configuration.
@@ -21,28 +21,41 @@ impl HatRegistry {
         Self::default()
     }
 
-    /// Creates a registry from configuration.
+    /// Creates a registry from a validated configuration.
     ///
     /// Empty config → empty registry (HatlessRalph is the fallback, not default hats).
+    ///
+    /// # Panics
+    ///
+    /// Panics if a hat's `when:` does not parse. [`RalphConfig::validate`]
+    /// rejects those; use [`HatRegistry::try_from_config`] for configs that
+    /// have not been validated.
     pub fn from_config(config: &RalphConfig) -> Self {
+        Self::try_from_config(config).unwrap_or_else(|e| panic!("{e}"))
+    }
+
+    /// Creates a registry from configuration, failing on a `when:` that does
+    /// not parse.
+    pub fn try_from_config(config: &RalphConfig) -> Result<Self, ConfigError> {
         let mut registry = Self::new();
 
         for (id, hat_config) in &config.hats {
-            let hat = Self::hat_from_config(id, hat_config);
+            let hat = Self::hat_from_config(id, hat_config)?;
             registry.register_with_config(hat, hat_config.clone());
         }
 
-        registry
+        Ok(registry)
     }
 
     /// Creates a Hat from HatConfig.
-    fn hat_from_config(id: &str, config: &HatConfig) -> Hat {
+    fn hat_from_config(id: &str, config: &HatConfig) -> Result<Hat, ConfigError> {
         let mut hat = Hat::new(id, &config.name);
         hat.description = config.description.clone().unwrap_or_default();
         hat.subscriptions = config.trigger_topics();
         hat.publishes = config.publish_topics();
         hat.instructions = config.instructions.clone();
-        hat
+        hat.conditions = config.parse_conditions(id)?;
+        Ok(hat)
     }
 
     /// Registers a hat with the registry.
@@ -136,20 +149,36 @@ impl HatRegistry {
     /// Uses prefix index for O(1) early-exit when the topic prefix doesn't match
     /// any subscription pattern.
     pub fn get_for_topic(&self, topic: &str) -> Option<&Hat> {
-        // Fast path: Check if any subscription could possibly match this topic
-        // If we have a global wildcard "*", we must do the full scan
-        if !self.prefix_index.contains("*") {
-            // Extract prefix from topic (e.g., "task" from "task.start")
-            let topic_prefix = topic.split('.').next().unwrap_or(topic);
-            if !self.prefix_index.contains(topic_prefix) {
-                // No subscription has this prefix - early exit
-                return None;
-            }
+        if !self.may_match(topic) {
+            return None;
         }
 
         // Fall back to full linear scan (BTreeMap is already sorted by key)
         self.hats.values().find(|hat| hat.is_subscribed_str(topic))
     }
+
+    /// Returns the first hat that accepts the event.
+    ///
+    /// Like `get_for_topic()`, but also evaluates trigger `when:` conditions
+    /// against the event payload.
+    pub fn get_for_event(&self, event: &Event) -> Option<&Hat> {
+        if !self.may_match(event.topic.as_str()) {
+            return None;
+        }
+
+        self.hats.values().find(|hat| hat.accepts(event))
+    }
+
+    /// Checks the prefix index to see if any subscription could match the topic.
+    fn may_match(&self, topic: &str) -> bool {
+        // If we have a global wildcard "*", we must do the full scan
+        if self.prefix_index.contains("*") {
+            return true;
+        }
+        // Extract prefix from topic (e.g., "task" from "task.start")
+        let topic_prefix = topic.split('.').next().unwrap_or(topic);
+        self.prefix_index.contains(topic_prefix)
+    }
 }
 
 #[cfg(test)]
@@ -225,6 +254,47 @@ hats:
         assert!(no_hat.is_none());
     }
 
+    #[test]
+    fn test_get_for_event_evaluates_conditions() {
+        let yaml = r#"
+hats:
+  fixer:
+    name: "Fixer"
+    triggers: ["review.done"]
+    when: "$.failures > 0"
+"#;
+        let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
+        let registry = HatRegistry::from_config(&config);
+
+        let failing = Event::new("review.done", r#"{"failures": 2}"#);
+        assert_eq!(
+            registry.get_for_event(&failing).unwrap().id.as_str(),
+            "fixer"
+        );
+
+        let passing = Event::new("review.done", r#"{"failures": 0}"#);
+        assert!(registry.get_for_event(&passing).is_none());
+        // Topic-only lookups ignore conditions.
+        assert!(registry.get_for_topic("review.done").is_some());
+    }
+
+    #[test]
+    fn test_invalid_condition_fails_registry_construction() {
+        let yaml = r#"
+hats:
+  fixer:
+    name: "Fixer"
+    triggers: ["review.done"]
+    when: "$.failures > > 0"
+"#;
+        let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(matches!(
+            HatRegistry::try_from_config(&config),
+            Err(ConfigError::InvalidCondition { hat, trigger, .. })
+                if hat == "fixer" && trigger == "review.done"
+        ));
+    }
+
     #[test]
     fn test_empty_registry_has_no_subscribers() {
         let config = RalphConfig::default();
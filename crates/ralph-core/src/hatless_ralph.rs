@@ -4,6 +4,11 @@
 
 use crate::config::CoreConfig;
 use crate::hat_registry::HatRegistry;
+use crate::prompt_assembly::{
+    PromptAssembly, PromptSection, SECTION_CORE, SECTION_DONE, SECTION_EVENT_WRITING,
+    SECTION_GUIDANCE, SECTION_HATS, SECTION_OBJECTIVE, SECTION_PENDING_EVENTS, SECTION_SKILL_INDEX,
+    SECTION_WORKFLOW,
+};
 use ralph_proto::Topic;
 use std::collections::HashMap;
 use std::path::Path;
@@ -223,6 +228,11 @@ impl HatlessRalph {
         self.objective = Some(objective);
     }
 
+    /// Returns the stored objective, if any.
+    pub fn objective(&self) -> Option<&str> {
+        self.objective.as_deref()
+    }
+
     /// Sets robot guidance messages collected from `human.guidance` events.
     ///
     /// Called by `EventLoop::build_prompt()` before `HatlessRalph::build_prompt()`.
@@ -271,31 +281,48 @@ impl HatlessRalph {
     ///
     /// For solo mode (no hats), pass an empty slice: `&[]`
     pub fn build_prompt(&self, context: &str, active_hats: &[&ralph_proto::Hat]) -> String {
-        let mut prompt = self.core_prompt();
+        self.build_prompt_sections(context, active_hats).render()
+    }
+
+    /// Builds Ralph's prompt as named sections (see [`build_prompt`](Self::build_prompt)).
+    pub fn build_prompt_sections(
+        &self,
+        context: &str,
+        active_hats: &[&ralph_proto::Hat],
+    ) -> PromptAssembly {
+        let mut prompt = PromptAssembly::new();
+        prompt.push(PromptSection::new(SECTION_CORE, self.core_prompt()));
 
         // Inject skill index between GUARDRAILS and OBJECTIVE
         if !self.skill_index.is_empty() {
-            prompt.push_str(&self.skill_index);
-            prompt.push('\n');
+            prompt.push(PromptSection::new(
+                SECTION_SKILL_INDEX,
+                format!("{}\n", self.skill_index),
+            ));
         }
 
         // Add prominent OBJECTIVE section first (stored at initialization, persists across all iterations)
         if let Some(ref obj) = self.objective {
-            prompt.push_str(&self.objective_section(obj));
+            prompt.push(PromptSection::new(
+                SECTION_OBJECTIVE,
+                self.objective_section(obj),
+            ));
         }
 
         // Inject robot guidance (collected from human.guidance events, cleared after injection)
-        let guidance = self.collect_robot_guidance();
-        if !guidance.is_empty() {
-            prompt.push_str(&guidance);
-        }
+        prompt.push(PromptSection::new(
+            SECTION_GUIDANCE,
+            self.collect_robot_guidance(),
+        ));
 
         // Include pending events BEFORE workflow so Ralph sees the task first
         if !context.trim().is_empty() {
-            prompt.push_str("## PENDING EVENTS\n\n");
-            prompt.push_str("You MUST handle these events in this iteration:\n\n");
-            prompt.push_str(context);
-            prompt.push_str("\n\n");
+            prompt.push(PromptSection::new(
+                SECTION_PENDING_EVENTS,
+                format!(
+                    "## PENDING EVENTS\n\nYou MUST handle these events in this iteration:\n\n{context}\n\n"
+                ),
+            ));
         }
 
         // Check if any active hat has custom instructions
@@ -305,19 +332,31 @@ impl HatlessRalph {
             .any(|h| !h.instructions.trim().is_empty());
 
         if !has_custom_workflow {
-            prompt.push_str(&self.workflow_section());
+            prompt.push(PromptSection::new(
+                SECTION_WORKFLOW,
+                self.workflow_section(),
+            ));
         }
 
         if let Some(topology) = &self.hat_topology {
-            prompt.push_str(&self.hats_section(topology, active_hats));
+            prompt.push(PromptSection::new(
+                SECTION_HATS,
+                self.hats_section(topology, active_hats),
+            ));
         }
 
-        prompt.push_str(&self.event_writing_section());
+        prompt.push(PromptSection::new(
+            SECTION_EVENT_WRITING,
+            self.event_writing_section(),
+        ));
 
         // Only show completion instructions when Ralph is coordinating (no active hat).
         // Hats should publish events and stop — only Ralph decides when the loop is done.
         if active_hats.is_empty() {
-            prompt.push_str(&self.done_section(self.objective.as_deref()));
+            prompt.push(PromptSection::new(
+                SECTION_DONE,
+                self.done_section(self.objective.as_deref()),
+            ));
         }
 
         prompt
@@ -907,14 +946,14 @@ mod tests {
     // Helper function to create HatlessRalph with custom template for testing
     fn create_hatless_ralph_with_template(template: Option<String>) -> HatlessRalph {
         let config = RalphConfig::default();
-        let ralph = HatlessRalph::new(
+
+        HatlessRalph::new(
             "LOOP_COMPLETE",
             config.core.clone(),
             &HatRegistry::new(),
             None,
             template,
-        );
-        ralph
+        )
     }
 
     #[test]
@@ -1115,7 +1154,13 @@ hats:
 "#;
         let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
         let registry = HatRegistry::from_config(&config);
-        let ralph = HatlessRalph::new("LOOP_COMPLETE", config.core.clone(), &registry, Some("tdd.start".to_string()), None);
+        let ralph = HatlessRalph::new(
+            "LOOP_COMPLETE",
+            config.core.clone(),
+            &registry,
+            Some("tdd.start".to_string()),
+            None,
+        );
 
         let prompt = ralph.build_prompt("", &[]);
 
@@ -1686,7 +1731,8 @@ hats:
         // When objective is set via set_objective(), OBJECTIVE section should appear
         let config = RalphConfig::default();
         let registry = HatRegistry::new();
-        let mut ralph = HatlessRalph::new("LOOP_COMPLETE", config.core.clone(), &registry, None, None);
+        let mut ralph =
+            HatlessRalph::new("LOOP_COMPLETE", config.core.clone(), &registry, None, None);
         ralph.set_objective("Implement user authentication with JWT tokens".to_string());
 
         let prompt = ralph.build_prompt("", &[]);
@@ -1711,7 +1757,8 @@ hats:
         // when Ralph is coordinating (no active hats)
         let config = RalphConfig::default();
         let registry = HatRegistry::new();
-        let mut ralph = HatlessRalph::new("LOOP_COMPLETE", config.core.clone(), &registry, None, None);
+        let mut ralph =
+            HatlessRalph::new("LOOP_COMPLETE", config.core.clone(), &registry, None, None);
         ralph.set_objective("Fix the login bug in auth module".to_string());
 
         let prompt = ralph.build_prompt("", &[]);
@@ -1735,7 +1782,8 @@ hats:
         // OBJECTIVE should appear BEFORE PENDING EVENTS for prominence
         let config = RalphConfig::default();
         let registry = HatRegistry::new();
-        let mut ralph = HatlessRalph::new("LOOP_COMPLETE", config.core.clone(), &registry, None, None);
+        let mut ralph =
+            HatlessRalph::new("LOOP_COMPLETE", config.core.clone(), &registry, None, None);
         ralph.set_objective("Build feature X".to_string());
 
         let context = "Event: task.start - Build feature X";
@@ -1775,7 +1823,8 @@ hats:
         // Test that set_objective stores the objective and it appears in prompt
         let config = RalphConfig::default();
         let registry = HatRegistry::new();
-        let mut ralph = HatlessRalph::new("LOOP_COMPLETE", config.core.clone(), &registry, None, None);
+        let mut ralph =
+            HatlessRalph::new("LOOP_COMPLETE", config.core.clone(), &registry, None, None);
         ralph.set_objective("Review this PR for security issues".to_string());
 
         let prompt = ralph.build_prompt("", &[]);
@@ -1791,7 +1840,8 @@ hats:
         // Objective should appear even when context has other events (not task.start)
         let config = RalphConfig::default();
         let registry = HatRegistry::new();
-        let mut ralph = HatlessRalph::new("LOOP_COMPLETE", config.core.clone(), &registry, None, None);
+        let mut ralph =
+            HatlessRalph::new("LOOP_COMPLETE", config.core.clone(), &registry, None, None);
         ralph.set_objective("Implement feature Y".to_string());
 
         let context =
@@ -1834,7 +1884,8 @@ hats:
         // (simulating iteration 2+ where the start event has been consumed)
         let config = RalphConfig::default();
         let registry = HatRegistry::new();
-        let mut ralph = HatlessRalph::new("LOOP_COMPLETE", config.core.clone(), &registry, None, None);
+        let mut ralph =
+            HatlessRalph::new("LOOP_COMPLETE", config.core.clone(), &registry, None, None);
         ralph.set_objective("Build a REST API with authentication".to_string());
 
         // Simulate iteration 2: only non-start events present
@@ -1864,7 +1915,8 @@ hats:
 "#;
         let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
         let registry = HatRegistry::from_config(&config);
-        let mut ralph = HatlessRalph::new("LOOP_COMPLETE", config.core.clone(), &registry, None, None);
+        let mut ralph =
+            HatlessRalph::new("LOOP_COMPLETE", config.core.clone(), &registry, None, None);
         ralph.set_objective("Implement feature X".to_string());
 
         let builder = registry.get(&ralph_proto::HatId::new("builder")).unwrap();
@@ -1901,7 +1953,8 @@ hats:
 "#;
         let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
         let registry = HatRegistry::from_config(&config);
-        let mut ralph = HatlessRalph::new("LOOP_COMPLETE", config.core.clone(), &registry, None, None);
+        let mut ralph =
+            HatlessRalph::new("LOOP_COMPLETE", config.core.clone(), &registry, None, None);
         ralph.set_objective("Complete the TDD cycle".to_string());
 
         // No active hats - Ralph is coordinating
@@ -1922,7 +1975,8 @@ hats:
         // DONE section includes "Remember your objective" when Ralph is coordinating
         let config = RalphConfig::default();
         let registry = HatRegistry::new();
-        let mut ralph = HatlessRalph::new("LOOP_COMPLETE", config.core.clone(), &registry, None, None);
+        let mut ralph =
+            HatlessRalph::new("LOOP_COMPLETE", config.core.clone(), &registry, None, None);
         ralph.set_objective("Deploy the application".to_string());
 
         let prompt = ralph.build_prompt("", &[]);
@@ -2247,7 +2301,8 @@ hats:
         // Single human.guidance message should be injected as-is (no numbered list)
         let config = RalphConfig::default();
         let registry = HatRegistry::new();
-        let mut ralph = HatlessRalph::new("LOOP_COMPLETE", config.core.clone(), &registry, None, None);
+        let mut ralph =
+            HatlessRalph::new("LOOP_COMPLETE", config.core.clone(), &registry, None, None);
         ralph.set_robot_guidance(vec!["Focus on error handling first".to_string()]);
 
         let prompt = ralph.build_prompt("", &[]);
@@ -2272,7 +2327,8 @@ hats:
         // Multiple human.guidance messages should be squashed into a numbered list
         let config = RalphConfig::default();
         let registry = HatRegistry::new();
-        let mut ralph = HatlessRalph::new("LOOP_COMPLETE", config.core.clone(), &registry, None, None);
+        let mut ralph =
+            HatlessRalph::new("LOOP_COMPLETE", config.core.clone(), &registry, None, None);
         ralph.set_robot_guidance(vec![
             "Focus on error handling".to_string(),
             "Use the existing retry pattern".to_string(),
@@ -2304,7 +2360,8 @@ hats:
         // ROBOT GUIDANCE should appear after OBJECTIVE but before PENDING EVENTS
         let config = RalphConfig::default();
         let registry = HatRegistry::new();
-        let mut ralph = HatlessRalph::new("LOOP_COMPLETE", config.core.clone(), &registry, None, None);
+        let mut ralph =
+            HatlessRalph::new("LOOP_COMPLETE", config.core.clone(), &registry, None, None);
         ralph.set_objective("Build feature X".to_string());
         ralph.set_robot_guidance(vec!["Use the new API".to_string()]);
 
@@ -2337,7 +2394,8 @@ hats:
         // After build_prompt consumes guidance, clear_robot_guidance should leave it empty
         let config = RalphConfig::default();
         let registry = HatRegistry::new();
-        let mut ralph = HatlessRalph::new("LOOP_COMPLETE", config.core.clone(), &registry, None, None);
+        let mut ralph =
+            HatlessRalph::new("LOOP_COMPLETE", config.core.clone(), &registry, None, None);
         ralph.set_robot_guidance(vec!["First guidance".to_string()]);
 
         // First prompt should include guidance
@@ -2418,8 +2476,10 @@ hats:
 
         // Since substitution logic is not implemented yet, this should still return the default prompt
         // This test will fail initially (red phase) until substitution is implemented
-        assert!(prompt.contains(&ralph.core.scratchpad), 
-                "Should substitute {{scratchpad}} with actual scratchpad path, but substitution not implemented yet");
+        assert!(
+            prompt.contains(&ralph.core.scratchpad),
+            "Should substitute {{scratchpad}} with actual scratchpad path, but substitution not implemented yet"
+        );
     }
 
     #[test]
@@ -2433,8 +2493,10 @@ hats:
 
         // Since substitution logic is not implemented yet, this should still return the default prompt
         // This test will fail initially (red phase) until substitution is implemented
-        assert!(prompt.contains("Guardrails:"), 
-                "Should substitute {{guardrails}} with actual guardrails, but substitution not implemented yet");
+        assert!(
+            prompt.contains("Guardrails:"),
+            "Should substitute {{guardrails}} with actual guardrails, but substitution not implemented yet"
+        );
     }
 
     #[test]
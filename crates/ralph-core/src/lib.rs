@@ -10,9 +10,12 @@
 //! - Terminal capture for session recording
 //! - Benchmark task definitions and workspace isolation
 
+pub mod approvals;
+pub mod backend_definition;
 #[cfg(feature = "recording")]
 mod cli_capture;
 mod config;
+pub mod config_layers;
 pub mod diagnostics;
 mod event_logger;
 mod event_loop;
@@ -35,32 +38,54 @@ mod memory;
 pub mod memory_parser;
 mod memory_store;
 pub mod merge_queue;
+pub mod path_glob;
+pub mod pipeline;
 pub mod planning_session;
 pub mod preflight;
+pub mod prompt_assembly;
+pub mod redaction;
+pub mod sandbox;
+pub mod schedule;
+#[cfg(feature = "recording")]
+mod session_export;
 #[cfg(feature = "recording")]
 mod session_player;
 #[cfg(feature = "recording")]
 mod session_recorder;
 pub mod skill;
+pub mod skill_pack;
 pub mod skill_registry;
+pub mod skill_triggers;
+pub mod skill_usage;
 mod summary_writer;
 pub mod task;
 pub mod task_definition;
 pub mod task_store;
 pub mod testing;
 mod text;
+pub mod timers;
+mod topology_analysis;
 pub mod utils;
 pub mod workspace;
 pub mod worktree;
+pub mod write_scope;
 
+pub use approvals::{
+    APPROVAL_DECISIONS_FILE, APPROVAL_RESPONSE_TOPIC, ApprovalDecision, ApprovalQueue,
+    PendingApproval, record_decision,
+};
+pub use backend_definition::{
+    BackendDefinition, BackendOutputFormat, LineParser, OutputRules, ParsedLine, UsageExtractor,
+};
 #[cfg(feature = "recording")]
 pub use cli_capture::{CliCapture, CliCapturePair};
 pub use config::{
-    CliConfig, ConfigError, CoreConfig, EventLoopConfig, EventMetadata, FeaturesConfig, HatBackend,
-    HatConfig, InjectMode, MemoriesConfig, MemoriesFilter, RalphConfig, SkillOverride,
-    SkillsConfig,
+    ApiBackendConfig, BackendRecoveryConfig, CliConfig, ConfigError, CoreConfig, EFFORT_LEVELS,
+    EventLoopConfig, EventMetadata, FeaturesConfig, HatBackend, HatConfig, InjectMode,
+    MemoriesConfig, MemoriesFilter, RalphConfig, SkillOverride, SkillsConfig, TriggerWhen,
 };
 // Re-export loop_name types (also available via FeaturesConfig.loop_naming)
+pub use config_layers::LayeredConfig;
 pub use diagnostics::DiagnosticsCollector;
 pub use event_logger::{EventHistory, EventLogger, EventRecord};
 pub use event_loop::{EventLoop, LoopState, TerminationReason, UserPrompt};
@@ -69,7 +94,7 @@ pub use event_reader::{Event, EventReader, MalformedLine, ParseResult};
 pub use file_lock::{FileLock, LockGuard as FileLockGuard, LockedFile};
 pub use git_ops::{
     AutoCommitResult, GitOpsError, auto_commit_changes, clean_stashes, get_commit_summary,
-    get_current_branch, get_head_sha, get_recent_files, has_uncommitted_changes,
+    get_current_branch, get_head_sha, get_recent_files, get_touched_files, has_uncommitted_changes,
     is_working_tree_clean, prune_remote_refs,
 };
 pub use handoff::{HandoffError, HandoffResult, HandoffWriter};
@@ -92,6 +117,10 @@ pub use merge_queue::{
     MergeQueueError, MergeState, SteeringDecision, merge_button_state, merge_execution_summary,
     merge_needs_steering, smart_merge_summary,
 };
+pub use pipeline::{
+    PipelineDefinition, PipelineError, PipelineRun, PipelineState, PipelineStatus, StageDefinition,
+    StageRecord,
+};
 pub use planning_session::{
     ConversationEntry, ConversationType, PlanningSession, PlanningSessionError, SessionMetadata,
     SessionStatus,
@@ -100,12 +129,27 @@ pub use preflight::{
     AcceptanceCriterion, CheckResult, CheckStatus, PreflightCheck, PreflightReport,
     PreflightRunner, extract_acceptance_criteria, extract_all_criteria, extract_criteria_from_file,
 };
+pub use prompt_assembly::{PromptAssembly, PromptSection, estimate_tokens};
+pub use redaction::{ArtifactLeak, RedactionConfig, Redactor};
+pub use sandbox::{
+    Sandbox, SandboxBind, SandboxConfig, SandboxEngine, SandboxError, SandboxLimits,
+    SandboxedCommand,
+};
+pub use schedule::{
+    CronSchedule, ScheduleEntry, ScheduleError, ScheduleFile, ScheduledRun, Scheduler, Trigger,
+    load_schedules,
+};
+#[cfg(feature = "recording")]
+pub use session_export::{ExportConfig, SessionExporter, TimelineEntry};
 #[cfg(feature = "recording")]
 pub use session_player::{PlayerConfig, ReplayMode, SessionPlayer, TimestampedRecord};
 #[cfg(feature = "recording")]
 pub use session_recorder::{Record, SessionRecorder};
 pub use skill::{SkillEntry, SkillFrontmatter, SkillSource, parse_frontmatter};
-pub use skill_registry::SkillRegistry;
+pub use skill_pack::{PackSource, SkillLock, SkillPack, SkillPackError};
+pub use skill_registry::{SkillRegistry, TriggeredSkill};
+pub use skill_triggers::{SkillTriggers, TriggerContext};
+pub use skill_usage::{SkillUsageKind, SkillUsageLog, SkillUsageRecord, SkillUsageStats};
 pub use summary_writer::SummaryWriter;
 pub use task::{Task, TaskStatus};
 pub use task_definition::{
@@ -113,6 +157,8 @@ pub use task_definition::{
 };
 pub use task_store::TaskStore;
 pub use text::{floor_char_boundary, truncate_with_ellipsis};
+pub use timers::{Timer, TimerKind, TimerSet, WatchdogConfig};
+pub use topology_analysis::{IssueSeverity, TopologyAnalyzer, TopologyIssue, TopologyReport};
 pub use workspace::{
     CleanupPolicy, TaskWorkspace, VerificationResult, WorkspaceError, WorkspaceInfo,
     WorkspaceManager,
@@ -122,3 +168,6 @@ pub use worktree::{
     list_ralph_worktrees, list_worktrees, remove_worktree, sync_working_directory_to_worktree,
     worktree_exists,
 };
+pub use write_scope::{
+    SCOPE_VIOLATION_TOPIC, ScopeViolation, ScopeViolationAction, WorkspaceSnapshot, WriteScope,
+};
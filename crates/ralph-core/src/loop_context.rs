@@ -188,6 +188,29 @@ impl LoopContext {
         self.agent_dir().join("scratchpad.md")
     }
 
+    /// Path to the pending timers file.
+    ///
+    /// Holds delayed events and armed watchdogs so they survive a resume.
+    pub fn timers_path(&self) -> PathBuf {
+        self.agent_dir().join("timers.json")
+    }
+
+    /// Path to the held-approvals file.
+    ///
+    /// Holds events awaiting human approval so they survive a resume.
+    pub fn approvals_path(&self) -> PathBuf {
+        self.agent_dir().join("approvals.json")
+    }
+
+    /// Path to the file human approval decisions are appended to.
+    ///
+    /// Kept outside `.ralph/agent/` and away from the events file, which the
+    /// agent writes, so a hat cannot approve its own held events.
+    pub fn approval_decisions_path(&self) -> PathBuf {
+        self.ralph_dir()
+            .join(crate::approvals::APPROVAL_DECISIONS_FILE)
+    }
+
     /// Path to the memories markdown file.
     ///
     /// For primary loops, this is the actual memories file.
@@ -7,6 +7,7 @@
 //! - **Auditing**: Complete trace of what happened and when
 //! - **Source of truth**: Registry state can be derived from history
 
+use std::collections::BTreeMap;
 use std::fs::{File, OpenOptions};
 use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
@@ -64,7 +65,7 @@ impl HistoryEvent {
 }
 
 /// Types of events that can be recorded in loop history.
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(tag = "kind", rename_all = "snake_case")]
 pub enum HistoryEventType {
     /// Loop started with given prompt.
@@ -102,6 +103,50 @@ pub enum HistoryEventType {
 
     /// Loop was discarded.
     LoopDiscarded { reason: String },
+
+    /// A scheduled run was started.
+    ScheduledRunStarted { schedule: String },
+
+    /// A scheduled run finished (or failed to start).
+    ScheduledRunFinished {
+        schedule: String,
+        loop_id: Option<String>,
+        outcome: String,
+        success: bool,
+    },
+
+    /// A scheduled run was skipped.
+    ScheduledRunSkipped { schedule: String, reason: String },
+
+    /// A pipeline run was created.
+    PipelineStarted { pipeline: String },
+
+    /// A pipeline stage started.
+    StageStarted { stage: String },
+
+    /// A pipeline stage finished (or failed to start).
+    StageFinished {
+        stage: String,
+        loop_id: Option<String>,
+        outcome: String,
+    },
+
+    /// The loop failed over from one backend to another.
+    BackendSwitched {
+        from: String,
+        to: String,
+        reason: String,
+    },
+
+    /// The backend, model, and cost of one iteration.
+    IterationUsage {
+        iteration: u32,
+        hat: String,
+        backend: String,
+        model: Option<String>,
+        effort: Option<String>,
+        cost_usd: f64,
+    },
 }
 
 /// Loop history manager for a single loop.
@@ -269,6 +314,19 @@ impl LoopHistory {
                     summary.merge_failed = true;
                     summary.merge_failure_reason = Some(reason.clone());
                 }
+                HistoryEventType::IterationUsage {
+                    backend,
+                    model,
+                    cost_usd,
+                    ..
+                } => {
+                    summary.cost_usd += cost_usd;
+                    let label = match model {
+                        Some(model) => format!("{backend}/{model}"),
+                        None => backend.clone(),
+                    };
+                    *summary.cost_by_model.entry(label).or_default() += cost_usd;
+                }
                 _ => {}
             }
         }
@@ -361,6 +419,103 @@ impl LoopHistory {
             reason: reason.to_string(),
         }))
     }
+
+    /// Record scheduled run started event.
+    pub fn record_scheduled_run_started(&self, schedule: &str) -> Result<(), HistoryError> {
+        self.append(HistoryEvent::new(HistoryEventType::ScheduledRunStarted {
+            schedule: schedule.to_string(),
+        }))
+    }
+
+    /// Record scheduled run finished event.
+    pub fn record_scheduled_run_finished(
+        &self,
+        schedule: &str,
+        loop_id: Option<&str>,
+        outcome: &str,
+        success: bool,
+    ) -> Result<(), HistoryError> {
+        self.append(HistoryEvent::new(HistoryEventType::ScheduledRunFinished {
+            schedule: schedule.to_string(),
+            loop_id: loop_id.map(String::from),
+            outcome: outcome.to_string(),
+            success,
+        }))
+    }
+
+    /// Record scheduled run skipped event.
+    pub fn record_scheduled_run_skipped(
+        &self,
+        schedule: &str,
+        reason: &str,
+    ) -> Result<(), HistoryError> {
+        self.append(HistoryEvent::new(HistoryEventType::ScheduledRunSkipped {
+            schedule: schedule.to_string(),
+            reason: reason.to_string(),
+        }))
+    }
+
+    /// Record pipeline started event.
+    pub fn record_pipeline_started(&self, pipeline: &str) -> Result<(), HistoryError> {
+        self.append(HistoryEvent::new(HistoryEventType::PipelineStarted {
+            pipeline: pipeline.to_string(),
+        }))
+    }
+
+    /// Record pipeline stage started event.
+    pub fn record_stage_started(&self, stage: &str) -> Result<(), HistoryError> {
+        self.append(HistoryEvent::new(HistoryEventType::StageStarted {
+            stage: stage.to_string(),
+        }))
+    }
+
+    /// Record pipeline stage finished event.
+    pub fn record_stage_finished(
+        &self,
+        stage: &str,
+        loop_id: Option<&str>,
+        outcome: &str,
+    ) -> Result<(), HistoryError> {
+        self.append(HistoryEvent::new(HistoryEventType::StageFinished {
+            stage: stage.to_string(),
+            loop_id: loop_id.map(String::from),
+            outcome: outcome.to_string(),
+        }))
+    }
+
+    /// Record backend failover event.
+    pub fn record_backend_switched(
+        &self,
+        from: &str,
+        to: &str,
+        reason: &str,
+    ) -> Result<(), HistoryError> {
+        self.append(HistoryEvent::new(HistoryEventType::BackendSwitched {
+            from: from.to_string(),
+            to: to.to_string(),
+            reason: reason.to_string(),
+        }))
+    }
+
+    /// Record which backend and model ran an iteration and what it cost.
+    pub fn record_iteration_usage(
+        &self,
+        iteration: u32,
+        hat: &str,
+        backend: &str,
+        model: Option<&str>,
+        effort: Option<&str>,
+        cost_usd: f64,
+    ) -> Result<(), HistoryError> {
+        self.append(HistoryEvent::new(HistoryEventType::IterationUsage {
+            iteration,
+            hat: hat.to_string(),
+            backend: backend.to_string(),
+            model: model.map(String::from),
+            effort: effort.map(String::from),
+            cost_usd,
+        }))
+    }
 }
 
 /// Summary statistics for a loop history.
@@ -404,6 +559,13 @@ pub struct HistorySummary {
 
     /// Merge failure reason (if failed).
     pub merge_failure_reason: Option<String>,
+
+    /// Estimated cost in USD over all recorded iterations.
+    pub cost_usd: f64,
+
+    /// Estimated cost per `backend/model` (just `backend` when the
+    /// iteration requested no model).
+    pub cost_by_model: BTreeMap<String, f64>,
 }
 
 #[cfg(test)]
@@ -533,6 +695,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_summary_cost_by_model() {
+        let (_dir, history) = temp_history();
+
+        history
+            .record_iteration_usage(1, "planner", "claude", Some("opus"), Some("high"), 0.5)
+            .unwrap();
+        history
+            .record_iteration_usage(2, "builder", "claude", Some("sonnet"), None, 0.25)
+            .unwrap();
+        history
+            .record_iteration_usage(3, "planner", "claude", Some("opus"), Some("high"), 0.5)
+            .unwrap();
+
+        let events = history.read_all().unwrap();
+        assert!(matches!(
+            &events[0].event_type,
+            HistoryEventType::IterationUsage { hat, effort, .. }
+                if hat == "planner" && effort.as_deref() == Some("high")
+        ));
+
+        let summary = history.summary().unwrap();
+        assert!((summary.cost_usd - 1.25).abs() < f64::EPSILON);
+        assert_eq!(summary.cost_by_model.get("claude/opus").copied(), Some(1.0));
+        assert_eq!(
+            summary.cost_by_model.get("claude/sonnet").copied(),
+            Some(0.25)
+        );
+    }
+
     #[test]
     fn test_empty_file() {
         let (_dir, history) = temp_history();
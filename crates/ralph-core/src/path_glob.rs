@@ -0,0 +1,81 @@
+//! Path globs shared by skill triggers and hat write scopes.
+//!
+//! Patterns are matched against workspace-relative paths with `/`
+//! separators. `*` and `?` stay within a path segment, `**` spans segments,
+//! and a pattern without a `/` also matches the file name alone (`*.sql`
+//! matches `db/init.sql`).
+
+/// Matches a path glob against a workspace-relative path, falling back to
+/// the file name for patterns without a directory component.
+pub fn file_matches(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.strip_prefix("./").unwrap_or(pattern);
+    if glob_match(pattern, path) {
+        return true;
+    }
+    !pattern.contains('/')
+        && path
+            .rsplit('/')
+            .next()
+            .is_some_and(|name| glob_match(pattern, name))
+}
+
+/// Minimal glob matcher: `*` and `?` stay within a path segment, `**` spans segments.
+pub fn glob_match(pattern: &str, path: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let path: Vec<char> = path.chars().collect();
+    glob_match_from(&pattern, &path)
+}
+
+fn glob_match_from(pattern: &[char], path: &[char]) -> bool {
+    match pattern.first() {
+        None => path.is_empty(),
+        Some('*') if pattern.get(1) == Some(&'*') => {
+            // `**/` also matches zero directories.
+            let rest = &pattern[2..];
+            let rest_after_slash = rest.strip_prefix(&['/']).unwrap_or(rest);
+            if glob_match_from(rest_after_slash, path) {
+                return true;
+            }
+            (0..path.len()).any(|i| glob_match_from(rest, &path[i + 1..]))
+        }
+        Some('*') => {
+            let rest = &pattern[1..];
+            for i in 0..=path.len() {
+                if glob_match_from(rest, &path[i..]) {
+                    return true;
+                }
+                if path.get(i) == Some(&'/') {
+                    break;
+                }
+            }
+            false
+        }
+        Some('?') => path
+            .first()
+            .is_some_and(|c| *c != '/' && glob_match_from(&pattern[1..], &path[1..])),
+        Some(c) => path.first() == Some(c) && glob_match_from(&pattern[1..], &path[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn glob_supports_single_and_double_star() {
+        assert!(glob_match("src/*.rs", "src/main.rs"));
+        assert!(!glob_match("src/*.rs", "src/bin/main.rs"));
+        assert!(glob_match("src/**/*.rs", "src/main.rs"));
+        assert!(glob_match("src/**/*.rs", "src/bin/deep/main.rs"));
+        assert!(glob_match("migrations/**", "migrations/001/up.sql"));
+        assert!(glob_match("file?.txt", "file1.txt"));
+        assert!(!glob_match("file?.txt", "file10.txt"));
+    }
+
+    #[test]
+    fn file_patterns_without_slash_match_file_name() {
+        assert!(file_matches("*.sql", "db/migrations/init.sql"));
+        assert!(file_matches("./Cargo.toml", "Cargo.toml"));
+        assert!(!file_matches("db/*.sql", "other/db/init.sql"));
+    }
+}
@@ -0,0 +1,849 @@
+//! Multi-stage loop pipelines.
+//!
+//! A pipeline file lists stages that run one after another in the same
+//! workspace, each with its own preset or config:
+//!
+//! ```yaml
+//! name: ship-feature
+//! prompt: Add rate limiting to the public API
+//! stages:
+//!   - name: spec
+//!     preset: spec-driven
+//!   - name: build
+//!     preset: feature
+//!   - name: review
+//!     preset: pr-review
+//!     requires: [completed, max_iterations]
+//! ```
+//!
+//! Each stage's prompt is built from the pipeline goal, the stage's own
+//! instructions, and what the previous stage left behind: its handoff,
+//! summary, and open tasks. A stage only starts if the previous stage ended
+//! with one of the termination reasons listed in its `requires` (default:
+//! the completion promise); otherwise the pipeline halts there.
+//!
+//! Every run gets a directory under `.ralph/pipelines/<run-id>/` holding a
+//! copy of the pipeline definition, a `history.jsonl` of stage events, and
+//! the handoff and summary each stage produced. The run's state is derived
+//! from that history, so an interrupted or halted run can be resumed.
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use chrono::{DateTime, Local, Utc};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::event_loop::TerminationReason;
+use crate::loop_context::LoopContext;
+use crate::loop_history::{HistoryError, HistoryEventType, LoopHistory};
+use crate::task_store::TaskStore;
+
+/// Pipeline run directories, relative to the workspace root.
+pub const PIPELINE_RUNS_DIR: &str = ".ralph/pipelines";
+
+/// Copy of the pipeline definition inside a run directory.
+const DEFINITION_FILE: &str = "pipeline.yml";
+
+/// Stage history inside a run directory.
+const HISTORY_FILE: &str = "history.jsonl";
+
+/// `requires` value that accepts any termination reason.
+const ANY_REASON: &str = "any";
+
+/// Errors that can occur while loading or running pipelines.
+#[derive(Debug, Error)]
+pub enum PipelineError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Failed to parse {path}: {source}")]
+    Yaml {
+        path: PathBuf,
+        source: serde_yaml::Error,
+    },
+
+    #[error("Invalid pipeline '{name}': {reason}")]
+    InvalidPipeline { name: String, reason: String },
+
+    #[error("Invalid stage '{stage}': {reason}")]
+    InvalidStage { stage: String, reason: String },
+
+    #[error("No pipeline run '{0}'")]
+    RunNotFound(String),
+
+    #[error(transparent)]
+    History(#[from] HistoryError),
+}
+
+/// A pipeline: an overall goal and the stages that work towards it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PipelineDefinition {
+    /// Pipeline name, used in run IDs. Defaults to the file name.
+    #[serde(default)]
+    pub name: String,
+
+    /// Overall goal, included in every stage's prompt.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+
+    /// Stages, in the order they run.
+    pub stages: Vec<StageDefinition>,
+}
+
+/// One stage of a pipeline: a loop run with its own preset and instructions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StageDefinition {
+    /// Unique name within the pipeline.
+    pub name: String,
+
+    /// Builtin preset to run with (e.g. `feature`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<String>,
+
+    /// Config source to run with, layered over the preset (file path or URL).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config: Option<String>,
+
+    /// Instructions for this stage.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+
+    /// Instructions file, relative to the workspace root.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_file: Option<String>,
+
+    /// Termination reasons of the previous stage that let this stage start
+    /// (e.g. `completed`, `max_iterations`, or `any`). Empty means
+    /// `completed`. Ignored on the first stage.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub requires: Vec<String>,
+}
+
+impl PipelineDefinition {
+    /// Loads and validates a pipeline file. A missing `name` defaults to the
+    /// file stem.
+    pub fn load(path: &Path) -> Result<Self, PipelineError> {
+        let content = std::fs::read_to_string(path)?;
+        let mut definition: Self =
+            serde_yaml::from_str(&content).map_err(|source| PipelineError::Yaml {
+                path: path.to_path_buf(),
+                source,
+            })?;
+        if definition.name.trim().is_empty() {
+            definition.name = path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
+        }
+        definition.validate()?;
+        Ok(definition)
+    }
+
+    /// Checks the pipeline is runnable: a usable name, at least one stage,
+    /// unique stage names, valid `requires` lists, and a prompt for the first
+    /// stage (its own or the pipeline goal).
+    pub fn validate(&self) -> Result<(), PipelineError> {
+        let invalid = |reason: &str| PipelineError::InvalidPipeline {
+            name: self.name.clone(),
+            reason: reason.to_string(),
+        };
+        if !is_valid_name(&self.name) {
+            return Err(invalid(
+                "name must be non-empty and use only letters, digits, '-' and '_'",
+            ));
+        }
+        let Some(first) = self.stages.first() else {
+            return Err(invalid("needs at least one stage"));
+        };
+
+        let mut seen = std::collections::HashSet::new();
+        for stage in &self.stages {
+            stage.validate()?;
+            if !seen.insert(stage.name.as_str()) {
+                return Err(invalid(&format!(
+                    "stage '{}' is defined more than once",
+                    stage.name
+                )));
+            }
+        }
+
+        if self.prompt.is_none() && first.prompt.is_none() && first.prompt_file.is_none() {
+            return Err(invalid(
+                "needs a pipeline prompt or a prompt for the first stage",
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl StageDefinition {
+    fn validate(&self) -> Result<(), PipelineError> {
+        let invalid = |reason: String| PipelineError::InvalidStage {
+            stage: self.name.clone(),
+            reason,
+        };
+        if !is_valid_name(&self.name) {
+            return Err(invalid(
+                "name must be non-empty and use only letters, digits, '-' and '_'".to_string(),
+            ));
+        }
+        if self.prompt.is_some() && self.prompt_file.is_some() {
+            return Err(invalid(
+                "set either prompt or prompt_file, not both".to_string(),
+            ));
+        }
+        for name in &self.requires {
+            if name != ANY_REASON && TerminationReason::from_name(name).is_none() {
+                return Err(invalid(format!("unknown termination reason '{name}'")));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns true if this stage may start after the previous stage ended
+    /// with `reason`.
+    pub fn accepts(&self, reason: &TerminationReason) -> bool {
+        if self.requires.is_empty() {
+            return reason.is_success();
+        }
+        self.requires.iter().any(|name| {
+            name == ANY_REASON || TerminationReason::from_name(name).as_ref() == Some(reason)
+        })
+    }
+
+    /// Returns the stage's own instructions, reading `prompt_file` relative to
+    /// the workspace.
+    pub fn resolve_prompt(&self, workspace_root: &Path) -> Result<Option<String>, PipelineError> {
+        if let Some(prompt) = &self.prompt {
+            return Ok(Some(prompt.clone()));
+        }
+        match &self.prompt_file {
+            Some(file) => Ok(Some(std::fs::read_to_string(workspace_root.join(file))?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Config sources (`-c` arguments) the loop runs with: the preset, then
+    /// the config file. Empty when the stage uses the workspace default.
+    pub fn config_sources(&self) -> Vec<String> {
+        self.preset
+            .iter()
+            .map(|preset| format!("builtin:{preset}"))
+            .chain(self.config.iter().cloned())
+            .collect()
+    }
+}
+
+/// Names end up in paths, so keep them to a safe character set.
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// What happened to one stage in a pipeline run, from the run's history.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StageRecord {
+    pub name: String,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub loop_id: Option<String>,
+    /// Termination reason (Debug form, e.g. `CompletionPromise`) or an
+    /// `error: ...` message if the stage failed to run.
+    pub outcome: Option<String>,
+}
+
+impl StageRecord {
+    /// Started but never finished: running now, or interrupted.
+    pub fn is_unfinished(&self) -> bool {
+        self.started_at.is_some() && self.finished_at.is_none()
+    }
+}
+
+/// Where a pipeline run stands.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "state", rename_all = "snake_case")]
+pub enum PipelineState {
+    /// The stage at this index runs next (it has not run, or did not finish).
+    Pending { stage: usize },
+
+    /// The stage at this index failed to run, or ended with a reason the
+    /// next stage does not accept.
+    Halted { stage: usize, outcome: String },
+
+    /// Every stage ran; `outcome` is how the last one ended.
+    Completed { outcome: String },
+}
+
+/// A pipeline run's stages and overall state.
+#[derive(Debug, Clone, Serialize)]
+pub struct PipelineStatus {
+    pub id: String,
+    pub pipeline: String,
+    pub started_at: Option<DateTime<Utc>>,
+    pub stages: Vec<StageRecord>,
+    #[serde(flatten)]
+    pub state: PipelineState,
+}
+
+/// One run of a pipeline, stored under `.ralph/pipelines/<run-id>/`.
+pub struct PipelineRun {
+    id: String,
+    dir: PathBuf,
+    definition: PipelineDefinition,
+    history: LoopHistory,
+}
+
+impl PipelineRun {
+    /// Creates a run directory for the definition and records its start.
+    pub fn create(
+        workspace_root: &Path,
+        definition: PipelineDefinition,
+    ) -> Result<Self, PipelineError> {
+        definition.validate()?;
+        let runs_dir = workspace_root.join(PIPELINE_RUNS_DIR);
+        std::fs::create_dir_all(&runs_dir)?;
+
+        let base = format!(
+            "{}-{}",
+            definition.name,
+            Local::now().format("%Y%m%d-%H%M%S")
+        );
+        let mut id = base.clone();
+        let mut suffix = 2;
+        while runs_dir.join(&id).exists() {
+            id = format!("{base}-{suffix}");
+            suffix += 1;
+        }
+
+        let dir = runs_dir.join(&id);
+        std::fs::create_dir_all(&dir)?;
+        let yaml = serde_yaml::to_string(&definition).map_err(|source| PipelineError::Yaml {
+            path: dir.join(DEFINITION_FILE),
+            source,
+        })?;
+        std::fs::write(dir.join(DEFINITION_FILE), yaml)?;
+
+        let history = LoopHistory::new(dir.join(HISTORY_FILE));
+        history.record_pipeline_started(&definition.name)?;
+        Ok(Self {
+            id,
+            dir,
+            definition,
+            history,
+        })
+    }
+
+    /// Opens an existing run by ID.
+    pub fn open(workspace_root: &Path, id: &str) -> Result<Self, PipelineError> {
+        let dir = workspace_root.join(PIPELINE_RUNS_DIR).join(id);
+        let definition_path = dir.join(DEFINITION_FILE);
+        if !is_valid_name(id) || !definition_path.exists() {
+            return Err(PipelineError::RunNotFound(id.to_string()));
+        }
+        let content = std::fs::read_to_string(&definition_path)?;
+        let definition: PipelineDefinition =
+            serde_yaml::from_str(&content).map_err(|source| PipelineError::Yaml {
+                path: definition_path,
+                source,
+            })?;
+        Ok(Self {
+            id: id.to_string(),
+            history: LoopHistory::new(dir.join(HISTORY_FILE)),
+            dir,
+            definition,
+        })
+    }
+
+    /// Opens every run in the workspace, oldest first.
+    pub fn list(workspace_root: &Path) -> Result<Vec<Self>, PipelineError> {
+        let runs_dir = workspace_root.join(PIPELINE_RUNS_DIR);
+        if !runs_dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut runs = Vec::new();
+        for entry in std::fs::read_dir(&runs_dir)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+            let id = entry.file_name().to_string_lossy().into_owned();
+            if let Ok(run) = Self::open(workspace_root, &id) {
+                let started_at = run.started_at()?;
+                runs.push((started_at, run));
+            }
+        }
+        runs.sort_by(|(a, run_a), (b, run_b)| a.cmp(b).then_with(|| run_a.id.cmp(&run_b.id)));
+        Ok(runs.into_iter().map(|(_, run)| run).collect())
+    }
+
+    /// Opens the most recently started run, if any.
+    pub fn latest(workspace_root: &Path) -> Result<Option<Self>, PipelineError> {
+        Ok(Self::list(workspace_root)?.pop())
+    }
+
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub fn definition(&self) -> &PipelineDefinition {
+        &self.definition
+    }
+
+    /// Directory holding the handoff and summary a stage produced.
+    pub fn stage_dir(&self, stage: &str) -> PathBuf {
+        self.dir.join(stage)
+    }
+
+    fn started_at(&self) -> Result<Option<DateTime<Utc>>, PipelineError> {
+        Ok(self
+            .history
+            .read_all()?
+            .into_iter()
+            .find(|event| matches!(event.event_type, HistoryEventType::PipelineStarted { .. }))
+            .map(|event| event.timestamp))
+    }
+
+    /// Replays the run's history into per-stage records and the run's state.
+    pub fn status(&self) -> Result<PipelineStatus, PipelineError> {
+        let mut stages: Vec<StageRecord> = self
+            .definition
+            .stages
+            .iter()
+            .map(|stage| StageRecord {
+                name: stage.name.clone(),
+                started_at: None,
+                finished_at: None,
+                loop_id: None,
+                outcome: None,
+            })
+            .collect();
+        let mut started_at = None;
+
+        for event in self.history.read_all()? {
+            match event.event_type {
+                HistoryEventType::PipelineStarted { .. } => started_at = Some(event.timestamp),
+                HistoryEventType::StageStarted { stage } => {
+                    let Some(index) = stages.iter().position(|record| record.name == stage) else {
+                        continue;
+                    };
+                    // Re-running a stage invalidates everything after it.
+                    for record in &mut stages[index..] {
+                        record.started_at = None;
+                        record.finished_at = None;
+                        record.loop_id = None;
+                        record.outcome = None;
+                    }
+                    stages[index].started_at = Some(event.timestamp);
+                }
+                HistoryEventType::StageFinished {
+                    stage,
+                    loop_id,
+                    outcome,
+                } => {
+                    if let Some(record) = stages.iter_mut().find(|record| record.name == stage) {
+                        record.finished_at = Some(event.timestamp);
+                        record.loop_id = loop_id;
+                        record.outcome = Some(outcome);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let state = self.state(&stages);
+        Ok(PipelineStatus {
+            id: self.id.clone(),
+            pipeline: self.definition.name.clone(),
+            started_at,
+            stages,
+            state,
+        })
+    }
+
+    fn state(&self, stages: &[StageRecord]) -> PipelineState {
+        let definitions = &self.definition.stages;
+        for (index, record) in stages.iter().enumerate() {
+            let Some(outcome) = &record.outcome else {
+                return PipelineState::Pending { stage: index };
+            };
+            let halted = || PipelineState::Halted {
+                stage: index,
+                outcome: outcome.clone(),
+            };
+            let Some(reason) = TerminationReason::from_name(outcome) else {
+                return halted();
+            };
+            match definitions.get(index + 1) {
+                None => {
+                    return PipelineState::Completed {
+                        outcome: outcome.clone(),
+                    };
+                }
+                // A stage that already started was let through, by its gate
+                // or by an explicit `resume --from`.
+                Some(next) if !next.accepts(&reason) && stages[index + 1].started_at.is_none() => {
+                    return halted();
+                }
+                Some(_) => {}
+            }
+        }
+        // Only reachable for a pipeline without stages, which validation rejects.
+        PipelineState::Completed {
+            outcome: String::new(),
+        }
+    }
+
+    /// Records that a stage is starting.
+    pub fn record_stage_started(&self, stage: &str) -> Result<(), PipelineError> {
+        Ok(self.history.record_stage_started(stage)?)
+    }
+
+    /// Records how a stage ended.
+    pub fn record_stage_finished(
+        &self,
+        stage: &str,
+        loop_id: Option<&str>,
+        outcome: &str,
+    ) -> Result<(), PipelineError> {
+        Ok(self
+            .history
+            .record_stage_finished(stage, loop_id, outcome)?)
+    }
+
+    /// Copies the handoff and summary the stage's loop wrote into the run
+    /// directory. Files older than `since` were left by an earlier loop and
+    /// are ignored. Stale copies from a previous attempt are removed.
+    pub fn capture_artifacts(
+        &self,
+        stage: &str,
+        workspace_root: &Path,
+        since: SystemTime,
+    ) -> Result<(), PipelineError> {
+        let context = LoopContext::primary(workspace_root.to_path_buf());
+        let stage_dir = self.stage_dir(stage);
+        std::fs::create_dir_all(&stage_dir)?;
+        for source in [context.handoff_path(), context.summary_path()] {
+            let Some(file_name) = source.file_name() else {
+                continue;
+            };
+            let target = stage_dir.join(file_name);
+            let fresh = std::fs::metadata(&source)
+                .and_then(|meta| meta.modified())
+                .is_ok_and(|modified| modified >= since);
+            if fresh {
+                std::fs::copy(&source, &target)?;
+            } else if target.exists() {
+                std::fs::remove_file(&target)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Builds the prompt for the stage at `index`: the pipeline goal, the
+    /// stage's instructions, and what the previous stage left behind.
+    pub fn stage_prompt(
+        &self,
+        index: usize,
+        workspace_root: &Path,
+        status: &PipelineStatus,
+    ) -> Result<String, PipelineError> {
+        let stages = &self.definition.stages;
+        let stage = &stages[index];
+        let mut sections = vec![format!(
+            "# Pipeline `{}` — stage {} of {}: `{}`",
+            self.definition.name,
+            index + 1,
+            stages.len(),
+            stage.name
+        )];
+
+        if let Some(goal) = &self.definition.prompt {
+            sections.push(format!("## Goal\n\n{}", goal.trim()));
+        }
+        let instructions = stage.resolve_prompt(workspace_root)?;
+        if let Some(instructions) = &instructions {
+            sections.push(format!("## This Stage\n\n{}", instructions.trim()));
+        }
+
+        if index > 0 {
+            let previous = &status.stages[index - 1];
+            let mut context = format!(
+                "## Previous Stage\n\nThe `{}` stage ended with `{}`.",
+                previous.name,
+                previous.outcome.as_deref().unwrap_or("unknown")
+            );
+            if instructions.is_none() {
+                context.push_str(" Continue the work it started.");
+            }
+            sections.push(context);
+
+            let previous_dir = self.stage_dir(&previous.name);
+            let handoff = read_optional(&previous_dir.join("handoff.md"))?;
+            if let Some(handoff) = &handoff {
+                sections.push(format!("### Handoff\n\n{}", handoff.trim()));
+            }
+            if let Some(summary) = read_optional(&previous_dir.join("summary.md"))? {
+                sections.push(format!("### Summary\n\n{}", summary.trim()));
+            }
+            // The handoff already lists remaining work; fall back to the task
+            // store when the previous stage did not write one.
+            if handoff.is_none()
+                && let Some(tasks) = open_tasks(workspace_root)?
+            {
+                sections.push(format!("### Open Tasks\n\n{tasks}"));
+            }
+        }
+
+        Ok(sections.join("\n\n"))
+    }
+}
+
+fn read_optional(path: &Path) -> Result<Option<String>, PipelineError> {
+    match std::fs::read_to_string(path) {
+        Ok(content) if content.trim().is_empty() => Ok(None),
+        Ok(content) => Ok(Some(content)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Lists the workspace's unfinished tasks as a markdown checklist.
+fn open_tasks(workspace_root: &Path) -> Result<Option<String>, PipelineError> {
+    let context = LoopContext::primary(workspace_root.to_path_buf());
+    let store = TaskStore::load(&context.tasks_path())?;
+    let lines: Vec<String> = store
+        .all()
+        .iter()
+        .filter(|task| !task.status.is_terminal())
+        .map(|task| format!("- [ ] {} ({})", task.title, task.id))
+        .collect();
+    Ok((!lines.is_empty()).then(|| lines.join("\n")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::task::Task;
+    use tempfile::TempDir;
+
+    const PIPELINE: &str = r"
+prompt: Add rate limiting
+stages:
+  - name: spec
+    preset: spec-driven
+    prompt: Write the spec
+  - name: build
+    preset: feature
+  - name: review
+    preset: pr-review
+    requires: [completed, max_iterations]
+";
+
+    fn definition(dir: &Path) -> PipelineDefinition {
+        let path = dir.join("ship.yml");
+        std::fs::write(&path, PIPELINE).unwrap();
+        PipelineDefinition::load(&path).unwrap()
+    }
+
+    fn finish(run: &PipelineRun, stage: &str, outcome: &str) {
+        run.record_stage_started(stage).unwrap();
+        run.record_stage_finished(stage, Some("primary"), outcome)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_load_defaults_name_and_validates() {
+        let dir = TempDir::new().unwrap();
+        let definition = definition(dir.path());
+        assert_eq!(definition.name, "ship");
+        assert_eq!(definition.stages.len(), 3);
+        assert_eq!(
+            definition.stages[1].config_sources(),
+            vec!["builtin:feature".to_string()]
+        );
+
+        let invalid = [
+            ("stages: []", "at least one stage"),
+            (
+                "stages:\n  - name: a\n    prompt: x\n  - name: a\n",
+                "more than once",
+            ),
+            ("stages:\n  - name: a\n", "prompt for the first stage"),
+            (
+                "stages:\n  - name: a\n    prompt: x\n    requires: [finished]\n",
+                "unknown termination reason 'finished'",
+            ),
+            ("stages:\n  - name: a/b\n    prompt: x\n", "name must be"),
+        ];
+        for (yaml, expected) in invalid {
+            let path = dir.path().join("bad.yml");
+            std::fs::write(&path, yaml).unwrap();
+            let err = PipelineDefinition::load(&path).unwrap_err().to_string();
+            assert!(err.contains(expected), "{yaml}: {err}");
+        }
+    }
+
+    #[test]
+    fn test_stage_gates() {
+        let dir = TempDir::new().unwrap();
+        let definition = definition(dir.path());
+        let build = &definition.stages[1];
+        assert!(build.accepts(&TerminationReason::CompletionPromise));
+        assert!(!build.accepts(&TerminationReason::MaxIterations));
+
+        let review = &definition.stages[2];
+        assert!(review.accepts(&TerminationReason::MaxIterations));
+        assert!(!review.accepts(&TerminationReason::MaxCost));
+
+        let any = StageDefinition {
+            requires: vec!["any".to_string()],
+            ..build.clone()
+        };
+        assert!(any.accepts(&TerminationReason::Interrupted));
+    }
+
+    #[test]
+    fn test_status_follows_history() {
+        let dir = TempDir::new().unwrap();
+        let run = PipelineRun::create(dir.path(), definition(dir.path())).unwrap();
+        assert_eq!(
+            run.status().unwrap().state,
+            PipelineState::Pending { stage: 0 }
+        );
+
+        // Interrupted mid-stage: the stage runs again.
+        run.record_stage_started("spec").unwrap();
+        let status = run.status().unwrap();
+        assert!(status.stages[0].is_unfinished());
+        assert_eq!(status.state, PipelineState::Pending { stage: 0 });
+
+        run.record_stage_finished("spec", Some("primary"), "CompletionPromise")
+            .unwrap();
+        assert_eq!(
+            run.status().unwrap().state,
+            PipelineState::Pending { stage: 1 }
+        );
+
+        // `build` hit its iteration limit; `review` accepts that.
+        finish(&run, "build", "MaxIterations");
+        assert_eq!(
+            run.status().unwrap().state,
+            PipelineState::Pending { stage: 2 }
+        );
+
+        finish(&run, "review", "CompletionPromise");
+        let status = run.status().unwrap();
+        assert_eq!(
+            status.state,
+            PipelineState::Completed {
+                outcome: "CompletionPromise".to_string()
+            }
+        );
+        assert_eq!(status.stages[2].loop_id.as_deref(), Some("primary"));
+
+        // Re-running an earlier stage resets the stages after it.
+        finish(&run, "spec", "MaxIterations");
+        let status = run.status().unwrap();
+        assert_eq!(
+            status.state,
+            PipelineState::Halted {
+                stage: 0,
+                outcome: "MaxIterations".to_string()
+            }
+        );
+        assert!(status.stages[2].outcome.is_none());
+
+        // Starting the next stage anyway overrides the gate.
+        run.record_stage_started("build").unwrap();
+        assert_eq!(
+            run.status().unwrap().state,
+            PipelineState::Pending { stage: 1 }
+        );
+
+        finish(&run, "spec", "error: no backend");
+        assert!(matches!(
+            run.status().unwrap().state,
+            PipelineState::Halted { stage: 0, .. }
+        ));
+    }
+
+    #[test]
+    fn test_runs_are_listed_and_reopened() {
+        let dir = TempDir::new().unwrap();
+        assert!(PipelineRun::latest(dir.path()).unwrap().is_none());
+
+        let first = PipelineRun::create(dir.path(), definition(dir.path())).unwrap();
+        let second = PipelineRun::create(dir.path(), definition(dir.path())).unwrap();
+        assert_ne!(first.id(), second.id());
+        assert!(first.id().starts_with("ship-"));
+
+        let latest = PipelineRun::latest(dir.path()).unwrap().unwrap();
+        assert_eq!(latest.id(), second.id());
+        assert_eq!(latest.definition(), second.definition());
+        assert!(matches!(
+            PipelineRun::open(dir.path(), "missing"),
+            Err(PipelineError::RunNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_stage_prompt_carries_previous_stage_context() {
+        let dir = TempDir::new().unwrap();
+        let run = PipelineRun::create(dir.path(), definition(dir.path())).unwrap();
+
+        let status = run.status().unwrap();
+        let prompt = run.stage_prompt(0, dir.path(), &status).unwrap();
+        assert!(prompt.starts_with("# Pipeline `ship` — stage 1 of 3: `spec`"));
+        assert!(prompt.contains("## Goal\n\nAdd rate limiting"));
+        assert!(prompt.contains("## This Stage\n\nWrite the spec"));
+        assert!(!prompt.contains("Previous Stage"));
+
+        // The spec stage wrote a summary and left a task open, but no handoff.
+        let context = LoopContext::primary(dir.path().to_path_buf());
+        std::fs::create_dir_all(context.agent_dir()).unwrap();
+        let since = SystemTime::now() - std::time::Duration::from_secs(1);
+        std::fs::write(context.summary_path(), "Spec written.\n").unwrap();
+        let mut store = TaskStore::load(&context.tasks_path()).unwrap();
+        store.add(Task::new("Implement limiter".to_string(), 1));
+        store.save().unwrap();
+        run.record_stage_started("spec").unwrap();
+        run.capture_artifacts("spec", dir.path(), since).unwrap();
+        run.record_stage_finished("spec", None, "CompletionPromise")
+            .unwrap();
+
+        let status = run.status().unwrap();
+        let prompt = run.stage_prompt(1, dir.path(), &status).unwrap();
+        assert!(prompt.contains(
+            "The `spec` stage ended with `CompletionPromise`. Continue the work it started."
+        ));
+        assert!(prompt.contains("### Summary\n\nSpec written."));
+        assert!(prompt.contains("### Open Tasks\n\n- [ ] Implement limiter"));
+        assert!(!prompt.contains("### Handoff"));
+
+        // With a handoff, the handoff replaces the task list.
+        std::fs::write(run.stage_dir("spec").join("handoff.md"), "# Handoff\n").unwrap();
+        let prompt = run.stage_prompt(1, dir.path(), &status).unwrap();
+        assert!(prompt.contains("### Handoff\n\n# Handoff"));
+        assert!(!prompt.contains("### Open Tasks"));
+    }
+
+    #[test]
+    fn test_capture_ignores_stale_artifacts() {
+        let dir = TempDir::new().unwrap();
+        let run = PipelineRun::create(dir.path(), definition(dir.path())).unwrap();
+        let context = LoopContext::primary(dir.path().to_path_buf());
+        std::fs::create_dir_all(context.agent_dir()).unwrap();
+        std::fs::write(context.handoff_path(), "old handoff").unwrap();
+
+        let future = SystemTime::now() + std::time::Duration::from_mins(1);
+        run.capture_artifacts("spec", dir.path(), future).unwrap();
+        assert!(!run.stage_dir("spec").join("handoff.md").exists());
+
+        let past = SystemTime::now() - std::time::Duration::from_mins(1);
+        run.capture_artifacts("spec", dir.path(), past).unwrap();
+        assert!(run.stage_dir("spec").join("handoff.md").exists());
+    }
+}
@@ -1,9 +1,12 @@
 //! Preflight checks for validating environment and configuration before running.
 
+use crate::backend_definition::BackendDefinition;
 use crate::config::ConfigWarning;
-use crate::{RalphConfig, git_ops};
+use crate::sandbox::Sandbox;
+use crate::{HatRegistry, RalphConfig, SkillRegistry, TopologyAnalyzer, git_ops};
 use async_trait::async_trait;
 use serde::Serialize;
+use std::collections::BTreeMap;
 use std::env;
 use std::ffi::OsString;
 use std::path::{Path, PathBuf};
@@ -105,12 +108,14 @@ impl PreflightRunner {
         Self {
             checks: vec![
                 Box::new(ConfigValidCheck),
+                Box::new(HatTopologyCheck),
                 Box::new(BackendAvailableCheck),
                 Box::new(TelegramTokenCheck),
                 Box::new(GitCleanCheck),
                 Box::new(PathsExistCheck),
                 Box::new(ToolsInPathCheck::default()),
                 Box::new(SpecCompletenessCheck),
+                Box::new(SandboxCheck),
             ],
         }
     }
@@ -173,6 +178,50 @@ impl PreflightCheck for ConfigValidCheck {
     }
 }
 
+struct HatTopologyCheck;
+
+#[async_trait]
+impl PreflightCheck for HatTopologyCheck {
+    fn name(&self) -> &'static str {
+        "hats"
+    }
+
+    async fn run(&self, config: &RalphConfig) -> CheckResult {
+        let registry = match HatRegistry::try_from_config(config) {
+            Ok(registry) => registry,
+            Err(e) => {
+                return CheckResult::fail(self.name(), "Hat conditions invalid", e.to_string());
+            }
+        };
+        if registry.is_empty() {
+            return CheckResult::pass(self.name(), "No hats configured (solo mode)");
+        }
+
+        let report = TopologyAnalyzer::new(config, &registry).analyze();
+        let details = report
+            .issues
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        if report.has_errors() {
+            CheckResult::fail(self.name(), "Hat topology invalid", details)
+        } else if report.issues.is_empty() {
+            CheckResult::pass(
+                self.name(),
+                format!("Hat topology valid ({} hats)", registry.len()),
+            )
+        } else {
+            CheckResult::warn(
+                self.name(),
+                format!("Hat topology valid ({} warning(s))", report.issues.len()),
+                details,
+            )
+        }
+    }
+}
+
 struct BackendAvailableCheck;
 
 #[async_trait]
@@ -186,6 +235,13 @@ impl PreflightCheck for BackendAvailableCheck {
         if backend.eq_ignore_ascii_case("auto") {
             return check_auto_backend(self.name(), config);
         }
+        if backend.eq_ignore_ascii_case("api") {
+            return check_api_backend(self.name(), config);
+        }
+
+        if let Some(definition) = config.backends.get(backend) {
+            return check_defined_backend(self.name(), backend, definition);
+        }
 
         check_named_backend(self.name(), config, backend)
     }
@@ -221,6 +277,35 @@ impl PreflightCheck for TelegramTokenCheck {
     }
 }
 
+struct SandboxCheck;
+
+#[async_trait]
+impl PreflightCheck for SandboxCheck {
+    fn name(&self) -> &'static str {
+        "sandbox"
+    }
+
+    async fn run(&self, config: &RalphConfig) -> CheckResult {
+        if !config.sandbox.enabled {
+            return CheckResult::pass(self.name(), "Sandbox disabled (skipping)");
+        }
+
+        let sandbox = match Sandbox::new(&config.sandbox, &config.core.workspace_root) {
+            Ok(sandbox) => sandbox,
+            Err(err) => {
+                return CheckResult::fail(self.name(), "Sandbox unavailable", err.to_string());
+            }
+        };
+        match sandbox.check() {
+            Ok(()) => CheckResult::pass(
+                self.name(),
+                format!("Sandbox ready ({})", sandbox.engine_name()),
+            ),
+            Err(err) => CheckResult::fail(self.name(), "Sandbox setup failed", err.to_string()),
+        }
+    }
+}
+
 struct GitCleanCheck;
 
 #[async_trait]
@@ -336,27 +421,43 @@ impl PreflightCheck for ToolsInPathCheck {
     }
 
     async fn run(&self, config: &RalphConfig) -> CheckResult {
-        if !is_git_workspace(&config.core.workspace_root) {
+        let skill_tools = skill_required_tools(config);
+        let in_git = is_git_workspace(&config.core.workspace_root);
+        if !in_git && skill_tools.is_empty() {
             return CheckResult::pass(self.name(), "Not a git repository (skipping)");
         }
 
-        let missing_required: Vec<String> = self
-            .required
+        // Git tooling only matters inside a repository; skill tools always do.
+        let (mut required, optional) = if in_git {
+            (self.required.clone(), self.optional.clone())
+        } else {
+            (Vec::new(), Vec::new())
+        };
+        for tool in skill_tools.keys() {
+            if !required.contains(tool) {
+                required.push(tool.clone());
+            }
+        }
+        let describe = |tool: &String| match skill_tools.get(tool) {
+            Some(skills) => format!("{tool} (skill: {})", skills.join(", ")),
+            None => tool.clone(),
+        };
+
+        let missing_required: Vec<String> = required
             .iter()
             .filter(|tool| find_executable(tool).is_none())
-            .cloned()
+            .map(describe)
             .collect();
 
-        let missing_optional: Vec<String> = self
-            .optional
+        let missing_optional: Vec<String> = optional
             .iter()
             .filter(|tool| find_executable(tool).is_none())
             .cloned()
             .collect();
 
         if missing_required.is_empty() && missing_optional.is_empty() {
-            let mut tools = self.required.clone();
-            tools.extend(self.optional.clone());
+            let mut tools = required;
+            tools.extend(optional);
             CheckResult::pass(
                 self.name(),
                 format!("Required tools available ({})", tools.join(", ")),
@@ -381,6 +482,36 @@ impl PreflightCheck for ToolsInPathCheck {
     }
 }
 
+/// Maps each tool declared via `requires_tools` by an available skill to the
+/// skills that need it.
+fn skill_required_tools(config: &RalphConfig) -> BTreeMap<String, Vec<String>> {
+    let mut tools: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    if !config.skills.enabled {
+        return tools;
+    }
+
+    let Ok(registry) = SkillRegistry::from_config(
+        &config.skills,
+        &config.core.workspace_root,
+        Some(config.cli.backend.as_str()),
+    ) else {
+        return tools;
+    };
+
+    for skill in registry.skills_for_hat(None) {
+        for tool in &skill.requires_tools {
+            tools
+                .entry(tool.clone())
+                .or_default()
+                .push(skill.name.clone());
+        }
+    }
+    for skills in tools.values_mut() {
+        skills.sort();
+    }
+    tools
+}
+
 struct SpecCompletenessCheck;
 
 #[async_trait]
@@ -737,6 +868,14 @@ fn check_auto_backend(name: &str, config: &RalphConfig) -> CheckResult {
             continue;
         }
 
+        if let Some(definition) = config.backends.get(backend) {
+            checked.push(format!("{backend} ({})", definition.command));
+            if version_check_passes(&definition.version_check()) {
+                return CheckResult::pass(name, format!("Auto backend available ({backend})"));
+            }
+            continue;
+        }
+
         let Some(command) = backend_command(backend, None) else {
             continue;
         };
@@ -765,6 +904,21 @@ fn check_auto_backend(name: &str, config: &RalphConfig) -> CheckResult {
     )
 }
 
+fn check_api_backend(name: &str, config: &RalphConfig) -> CheckResult {
+    let api = &config.cli.api;
+    match api.model.as_deref().filter(|model| !model.is_empty()) {
+        Some(model) => CheckResult::pass(
+            name,
+            format!("API backend configured ({model} at {})", api.base_url),
+        ),
+        None => CheckResult::fail(
+            name,
+            "API backend model missing",
+            "Set cli.api.model in ralph.yml",
+        ),
+    }
+}
+
 fn check_named_backend(name: &str, config: &RalphConfig, backend: &str) -> CheckResult {
     let command_override = config.cli.command.as_deref();
     let Some(command) = backend_command(backend, command_override) else {
@@ -798,6 +952,33 @@ fn check_named_backend(name: &str, config: &RalphConfig, backend: &str) -> Check
     }
 }
 
+fn check_defined_backend(name: &str, backend: &str, definition: &BackendDefinition) -> CheckResult {
+    let argv = definition.version_check();
+    if version_check_passes(&argv) {
+        CheckResult::pass(
+            name,
+            format!("Backend CLI available ({backend}: {})", definition.command),
+        )
+    } else {
+        CheckResult::fail(
+            name,
+            "Backend CLI not available",
+            format!("Version check failed: {}", argv.join(" ")),
+        )
+    }
+}
+
+fn version_check_passes(argv: &[String]) -> bool {
+    let Some((command, args)) = argv.split_first() else {
+        return false;
+    };
+    Command::new(command)
+        .args(args)
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
 fn backend_command(backend: &str, override_cmd: Option<&str>) -> Option<String> {
     if let Some(command) = override_cmd {
         let trimmed = command.trim();
@@ -841,7 +1022,7 @@ fn ensure_directory(path: &Path, created: &mut Vec<String>) -> anyhow::Result<()
     Ok(())
 }
 
-fn find_executable(command: &str) -> Option<PathBuf> {
+pub(crate) fn find_executable(command: &str) -> Option<PathBuf> {
     let path = Path::new(command);
     if path.components().count() > 1 {
         return if path.is_file() {
@@ -928,6 +1109,41 @@ mod tests {
         assert!(message.contains("archive_prompts"));
     }
 
+    #[tokio::test]
+    async fn hats_check_skips_solo_mode() {
+        let config = RalphConfig::default();
+        let result = HatTopologyCheck.run(&config).await;
+
+        assert_eq!(result.status, CheckStatus::Pass);
+        assert!(result.label.contains("solo mode"));
+    }
+
+    #[tokio::test]
+    async fn hats_check_reports_topology_issues() {
+        let yaml = r#"
+hats:
+  builder:
+    name: "Builder"
+    triggers: ["build.task"]
+    publishes: ["build.done"]
+"#;
+        let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
+        let result = HatTopologyCheck.run(&config).await;
+        assert_eq!(result.status, CheckStatus::Warn);
+        assert!(result.message.unwrap_or_default().contains("build.done"));
+
+        let mut config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
+        config.event_loop.starting_event = Some("plan.start".to_string());
+        let result = HatTopologyCheck.run(&config).await;
+        assert_eq!(result.status, CheckStatus::Fail);
+        assert!(
+            result
+                .message
+                .unwrap_or_default()
+                .contains("starting_event 'plan.start' has no subscribers")
+        );
+    }
+
     #[tokio::test]
     async fn tools_check_reports_missing_tools() {
         let temp = tempfile::tempdir().expect("tempdir");
@@ -959,6 +1175,35 @@ mod tests {
         assert!(result.message.unwrap_or_default().contains("Missing"));
     }
 
+    #[tokio::test]
+    async fn tools_check_requires_skill_tools_outside_repo() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let skills = temp.path().join("skills");
+        std::fs::create_dir_all(&skills).expect("create skills dir");
+        std::fs::write(
+            skills.join("deploy.md"),
+            "---\nname: deploy\nrequires_tools: [definitely-not-a-tool]\n---\nDeploy.\n",
+        )
+        .expect("write skill");
+        let mut config = RalphConfig::default();
+        config.core.workspace_root = temp.path().to_path_buf();
+        config.skills.dirs = vec![skills];
+
+        let check = ToolsInPathCheck::new(vec!["git".to_string()]);
+        let result = check.run(&config).await;
+
+        assert_eq!(result.status, CheckStatus::Fail);
+        let message = result.message.unwrap_or_default();
+        assert!(
+            message.contains("definitely-not-a-tool (skill: deploy)"),
+            "{message}"
+        );
+        assert!(
+            !message.contains("git"),
+            "git is not required outside a repo"
+        );
+    }
+
     #[tokio::test]
     async fn paths_check_creates_missing_dirs() {
         let temp = tempfile::tempdir().expect("tempdir");
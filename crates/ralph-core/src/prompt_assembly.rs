@@ -0,0 +1,363 @@
+//! Section-level prompt assembly with approximate token accounting.
+//!
+//! Every iteration prompt is stitched together from named sections (ready
+//! tasks, scratchpad, memories, core prompt, skill index, objective, ...).
+//! Keeping them apart until the final render lets `ralph prompt preview`
+//! report where the tokens go, and lets `event_loop.prompt_budget` trim the
+//! lowest-priority sections when the prompt grows too large.
+
+use crate::text::floor_char_boundary;
+
+/// `<ready-tasks>` block injected when tasks are enabled.
+pub const SECTION_READY_TASKS: &str = "ready-tasks";
+/// `<scratchpad>` block with the scratchpad tail.
+pub const SECTION_SCRATCHPAD: &str = "scratchpad";
+/// Auto-injected memory data.
+pub const SECTION_MEMORIES: &str = "memories";
+/// Compact table of available skills.
+pub const SECTION_SKILL_INDEX: &str = "skill-index";
+/// Core prompt (orientation, scratchpad, state management, guardrails).
+pub const SECTION_CORE: &str = "core";
+/// The user's objective.
+pub const SECTION_OBJECTIVE: &str = "objective";
+/// Squashed `human.guidance` messages.
+pub const SECTION_GUIDANCE: &str = "guidance";
+/// Events Ralph must handle this iteration.
+pub const SECTION_PENDING_EVENTS: &str = "pending-events";
+/// Generic workflow steps (omitted when an active hat has instructions).
+pub const SECTION_WORKFLOW: &str = "workflow";
+/// Hat topology table and active hat instructions.
+pub const SECTION_HATS: &str = "hats";
+/// Event writing instructions.
+pub const SECTION_EVENT_WRITING: &str = "event-writing";
+/// Completion instructions (only while Ralph is coordinating).
+pub const SECTION_DONE: &str = "done";
+/// Instructions for a directly executed hat (legacy non-Ralph path).
+pub const SECTION_HAT_INSTRUCTIONS: &str = "hat-instructions";
+
+/// How a section may shrink when the prompt exceeds its budget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Trim {
+    /// Keep the beginning, cut the end.
+    KeepHead,
+    /// Keep the end (most recent content), cut the beginning.
+    KeepTail,
+    /// Remove the section entirely.
+    Drop,
+}
+
+/// Sections that may be trimmed, lowest priority first.
+const TRIM_ORDER: &[(&str, Trim)] = &[
+    (SECTION_MEMORIES, Trim::KeepHead),
+    (SECTION_SKILL_INDEX, Trim::Drop),
+    (SECTION_SCRATCHPAD, Trim::KeepTail),
+];
+
+/// Estimates the token count of `text` (roughly 4 characters per token).
+#[must_use]
+pub fn estimate_tokens(text: &str) -> usize {
+    text.len().div_ceil(4)
+}
+
+/// A named chunk of the prompt.
+///
+/// The body may be wrapped in a fixed prefix and suffix (e.g. XML tags) that
+/// survive trimming.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptSection {
+    name: String,
+    prefix: String,
+    body: String,
+    suffix: String,
+    original_tokens: usize,
+    dropped: bool,
+}
+
+impl PromptSection {
+    /// Creates a section from its full text.
+    pub fn new(name: impl Into<String>, content: impl Into<String>) -> Self {
+        Self::wrapped(name, "", content, "")
+    }
+
+    /// Creates a section whose `body` sits between a fixed `prefix` and `suffix`.
+    pub fn wrapped(
+        name: impl Into<String>,
+        prefix: impl Into<String>,
+        body: impl Into<String>,
+        suffix: impl Into<String>,
+    ) -> Self {
+        let mut section = Self {
+            name: name.into(),
+            prefix: prefix.into(),
+            body: body.into(),
+            suffix: suffix.into(),
+            original_tokens: 0,
+            dropped: false,
+        };
+        section.original_tokens = section.tokens();
+        section
+    }
+
+    /// Section name (one of the `SECTION_*` constants or `<skill>-skill`).
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Rendered text of the section (empty once dropped).
+    pub fn text(&self) -> String {
+        if self.dropped {
+            return String::new();
+        }
+        format!("{}{}{}", self.prefix, self.body, self.suffix)
+    }
+
+    /// Approximate token count of the rendered section.
+    pub fn tokens(&self) -> usize {
+        if self.dropped {
+            return 0;
+        }
+        estimate_tokens(&self.prefix) + estimate_tokens(&self.body) + estimate_tokens(&self.suffix)
+    }
+
+    /// Approximate token count before any budget trimming.
+    pub fn original_tokens(&self) -> usize {
+        self.original_tokens
+    }
+
+    /// Whether the budget removed or shortened this section.
+    pub fn is_trimmed(&self) -> bool {
+        self.dropped || self.tokens() < self.original_tokens
+    }
+
+    /// Whether the budget removed this section entirely.
+    pub fn is_dropped(&self) -> bool {
+        self.dropped
+    }
+
+    /// Shrinks the section by at least `excess` tokens, dropping it if needed.
+    fn shrink(&mut self, excess: usize, trim: Trim) {
+        let body_tokens = estimate_tokens(&self.body);
+
+        // The marker and its line breaks count against the kept body.
+        let marker = format!("<!-- ~{excess} tokens trimmed to fit prompt_budget -->");
+        let keep_tokens = match trim {
+            Trim::Drop => 0,
+            Trim::KeepHead | Trim::KeepTail => {
+                body_tokens.saturating_sub(excess + estimate_tokens(&marker) + 1)
+            }
+        };
+
+        if keep_tokens == 0 {
+            self.dropped = true;
+            return;
+        }
+
+        let keep_bytes = keep_tokens * 4;
+        self.body = match trim {
+            Trim::KeepHead => {
+                let end = floor_char_boundary(&self.body, keep_bytes);
+                let end = self.body[..end].rfind('\n').map_or(end, |n| n + 1);
+                format!("{}\n{marker}\n", &self.body[..end])
+            }
+            Trim::KeepTail => {
+                let start = floor_char_boundary(&self.body, self.body.len() - keep_bytes);
+                let start = self.body[start..]
+                    .find('\n')
+                    .map_or(start, |n| start + n + 1);
+                format!("{marker}\n\n{}", &self.body[start..])
+            }
+            Trim::Drop => unreachable!("drop handled above"),
+        };
+    }
+}
+
+/// An ordered collection of prompt sections.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PromptAssembly {
+    sections: Vec<PromptSection>,
+    budget: usize,
+}
+
+impl PromptAssembly {
+    /// Creates an empty assembly.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a section. Empty sections are skipped.
+    pub fn push(&mut self, section: PromptSection) {
+        if !section.text().is_empty() {
+            self.sections.push(section);
+        }
+    }
+
+    /// Appends every section of `other`.
+    pub fn extend(&mut self, other: PromptAssembly) {
+        for section in other.sections {
+            self.push(section);
+        }
+    }
+
+    /// Sections in prompt order, including dropped ones.
+    pub fn sections(&self) -> &[PromptSection] {
+        &self.sections
+    }
+
+    /// Returns the section with the given name.
+    pub fn section(&self, name: &str) -> Option<&PromptSection> {
+        self.sections.iter().find(|s| s.name == name)
+    }
+
+    /// Approximate token count of the rendered prompt.
+    pub fn total_tokens(&self) -> usize {
+        self.sections.iter().map(PromptSection::tokens).sum()
+    }
+
+    /// Token budget applied by [`fit_to_budget`](Self::fit_to_budget) (0 = unlimited).
+    pub fn budget(&self) -> usize {
+        self.budget
+    }
+
+    /// Trims low-priority sections until the prompt fits `budget` tokens.
+    ///
+    /// Memories lose their tail first, then the skill index is dropped, then
+    /// the scratchpad loses its oldest content. Other sections are never
+    /// touched, so the result can still exceed the budget. A budget of 0
+    /// disables trimming. Returns `true` when the prompt fits.
+    pub fn fit_to_budget(&mut self, budget: usize) -> bool {
+        self.budget = budget;
+        if budget == 0 {
+            return true;
+        }
+
+        for (name, trim) in TRIM_ORDER {
+            let total = self.total_tokens();
+            if total <= budget {
+                return true;
+            }
+            if let Some(section) = self.sections.iter_mut().find(|s| s.name == *name) {
+                section.shrink(total - budget, *trim);
+            }
+        }
+
+        self.total_tokens() <= budget
+    }
+
+    /// Renders the final prompt text.
+    pub fn render(&self) -> String {
+        self.sections.iter().map(PromptSection::text).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(prefix: &str, count: usize) -> String {
+        (0..count)
+            .map(|i| format!("{prefix} line {i:03}\n"))
+            .collect::<Vec<_>>()
+            .concat()
+    }
+
+    fn sample() -> PromptAssembly {
+        let mut assembly = PromptAssembly::new();
+        assembly.push(PromptSection::wrapped(
+            SECTION_SCRATCHPAD,
+            "<scratchpad>\n",
+            lines("scratch", 50),
+            "\n</scratchpad>\n\n",
+        ));
+        assembly.push(PromptSection::new(SECTION_MEMORIES, lines("memory", 50)));
+        assembly.push(PromptSection::new(SECTION_CORE, "## CORE\n\n"));
+        assembly.push(PromptSection::new(SECTION_SKILL_INDEX, lines("skill", 20)));
+        assembly.push(PromptSection::new(SECTION_OBJECTIVE, "## OBJECTIVE\n\n"));
+        assembly
+    }
+
+    #[test]
+    fn render_concatenates_sections_in_order() {
+        let mut assembly = PromptAssembly::new();
+        assembly.push(PromptSection::new(SECTION_CORE, "core\n"));
+        assembly.push(PromptSection::new(SECTION_GUIDANCE, ""));
+        assembly.push(PromptSection::wrapped(
+            SECTION_SCRATCHPAD,
+            "<s>",
+            "body",
+            "</s>",
+        ));
+
+        assert_eq!(assembly.render(), "core\n<s>body</s>");
+        assert_eq!(assembly.sections().len(), 2, "empty sections are skipped");
+        assert_eq!(estimate_tokens("12345"), 2);
+    }
+
+    #[test]
+    fn no_budget_leaves_prompt_untouched() {
+        let mut assembly = sample();
+        let before = assembly.render();
+
+        assert!(assembly.fit_to_budget(0));
+        assert_eq!(assembly.render(), before);
+        assert!(assembly.sections().iter().all(|s| !s.is_trimmed()));
+    }
+
+    #[test]
+    fn memories_are_trimmed_first() {
+        let mut assembly = sample();
+        let total = assembly.total_tokens();
+
+        assert!(assembly.fit_to_budget(total - 50));
+
+        let memories = assembly.section(SECTION_MEMORIES).unwrap();
+        assert!(memories.is_trimmed());
+        assert!(!memories.is_dropped());
+        let text = memories.text();
+        assert!(text.starts_with("memory line 000\n"));
+        assert!(text.contains("tokens trimmed to fit prompt_budget"));
+        assert!(!assembly.section(SECTION_SKILL_INDEX).unwrap().is_trimmed());
+        assert!(!assembly.section(SECTION_SCRATCHPAD).unwrap().is_trimmed());
+    }
+
+    #[test]
+    fn skill_index_dropped_before_scratchpad_is_cut() {
+        let mut assembly = sample();
+        let memories = assembly.section(SECTION_MEMORIES).unwrap().tokens();
+        let skills = assembly.section(SECTION_SKILL_INDEX).unwrap().tokens();
+        let budget = assembly.total_tokens() - memories - skills;
+
+        assert!(assembly.fit_to_budget(budget));
+
+        assert!(assembly.section(SECTION_MEMORIES).unwrap().is_dropped());
+        assert!(assembly.section(SECTION_SKILL_INDEX).unwrap().is_dropped());
+        assert!(!assembly.render().contains("skill line"));
+        assert!(!assembly.section(SECTION_SCRATCHPAD).unwrap().is_trimmed());
+    }
+
+    #[test]
+    fn scratchpad_keeps_tail_and_wrapper() {
+        let mut assembly = sample();
+        let fits = assembly.fit_to_budget(150);
+
+        assert!(fits, "total {} > 150", assembly.total_tokens());
+        let scratchpad = assembly.section(SECTION_SCRATCHPAD).unwrap().text();
+        assert!(scratchpad.starts_with("<scratchpad>\n<!-- ~"));
+        assert!(scratchpad.ends_with("scratch line 049\n\n</scratchpad>\n\n"));
+        assert!(!scratchpad.contains("scratch line 000"));
+        assert!(assembly.render().contains("## CORE"));
+    }
+
+    #[test]
+    fn fixed_sections_are_never_trimmed() {
+        let mut assembly = sample();
+
+        assert!(!assembly.fit_to_budget(1));
+        assert!(
+            assembly
+                .section(SECTION_CORE)
+                .is_some_and(|s| !s.is_trimmed())
+        );
+        assert!(assembly.render().contains("## OBJECTIVE"));
+        assert_eq!(assembly.budget(), 1);
+    }
+}
@@ -0,0 +1,573 @@
+//! Sandboxed agent execution.
+//!
+//! With `sandbox.enabled`, backends are launched inside Linux namespaces
+//! instead of with the user's full privileges. The workspace is mounted
+//! read-write and the rest of the filesystem read-only. Extra binds, network
+//! isolation and resource limits are configurable.
+//!
+//! Two engines are supported:
+//! - **bubblewrap** (`bwrap`): unprivileged, keeps the user's uid
+//! - **unshare** (util-linux): a user namespace where the agent runs as the
+//!   namespace's root, with the mounts set up by a small shell script
+//!
+//! Resource limits are applied with `prlimit` inside the sandbox. Writable
+//! temporary files go to `.ralph/tmp` in the workspace (exported as `TMPDIR`),
+//! since `/tmp` is read-only like the rest of the system.
+
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use serde::{Deserialize, Serialize};
+
+use crate::preflight::find_executable;
+
+/// Workspace-relative directory exported as `TMPDIR` inside the sandbox.
+const SANDBOX_TMP_DIR: &str = ".ralph/tmp";
+
+/// Sandbox configuration (`sandbox:` in ralph.yml).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SandboxConfig {
+    /// Launch backends inside the sandbox.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Engine used to create the namespaces.
+    #[serde(default)]
+    pub engine: SandboxEngine,
+
+    /// Allow network access. Set to false to give the agent an empty
+    /// network namespace.
+    #[serde(default = "default_true")]
+    pub network: bool,
+
+    /// Extra paths mounted into the sandbox (read-only unless `writable`).
+    #[serde(default)]
+    pub binds: Vec<SandboxBind>,
+
+    /// Resource limits for the backend process.
+    #[serde(default)]
+    pub limits: SandboxLimits,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for SandboxConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            engine: SandboxEngine::default(),
+            network: true,
+            binds: Vec::new(),
+            limits: SandboxLimits::default(),
+        }
+    }
+}
+
+/// Namespace engine for the sandbox.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SandboxEngine {
+    /// bubblewrap when installed, otherwise unshare.
+    #[default]
+    Auto,
+    /// bubblewrap (`bwrap`).
+    Bubblewrap,
+    /// util-linux `unshare`.
+    Unshare,
+}
+
+/// An extra path mounted into the sandbox.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SandboxBind {
+    /// Host path. `~/` expands to the home directory; relative paths are
+    /// resolved against the workspace.
+    pub path: String,
+
+    /// Mount point inside the sandbox (defaults to `path`).
+    #[serde(default)]
+    pub target: Option<String>,
+
+    /// Mount read-write instead of read-only.
+    #[serde(default)]
+    pub writable: bool,
+}
+
+/// Resource limits applied to the sandboxed backend.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SandboxLimits {
+    /// Address-space limit in MiB (`RLIMIT_AS`).
+    #[serde(default)]
+    pub memory_mb: Option<u64>,
+
+    /// CPU time limit in seconds (`RLIMIT_CPU`).
+    #[serde(default)]
+    pub cpu_time_secs: Option<u64>,
+
+    /// Per-user process limit (`RLIMIT_NPROC`).
+    ///
+    /// The kernel counts every process owned by the user running Ralph, not
+    /// only the sandboxed ones, so leave headroom for the rest of the session.
+    #[serde(default)]
+    pub max_user_processes: Option<u64>,
+}
+
+impl SandboxLimits {
+    fn is_empty(&self) -> bool {
+        self.memory_mb.is_none()
+            && self.cpu_time_secs.is_none()
+            && self.max_user_processes.is_none()
+    }
+}
+
+/// Errors setting up the sandbox.
+#[derive(Debug, thiserror::Error)]
+pub enum SandboxError {
+    #[error("sandboxing requires Linux namespaces; disable `sandbox.enabled` on this platform")]
+    Unsupported,
+
+    #[error("sandbox engine '{0}' was not found on PATH")]
+    EngineNotFound(&'static str),
+
+    #[error("no sandbox engine found: install bubblewrap (bwrap) or util-linux (unshare)")]
+    NoEngine,
+
+    #[error("sandbox resource limits need `prlimit` (util-linux) on PATH")]
+    PrlimitNotFound,
+
+    #[error("sandbox bind path does not exist: {0}")]
+    BindNotFound(String),
+
+    #[error("sandbox could not be set up with {engine}: {message}")]
+    Setup {
+        engine: &'static str,
+        message: String,
+    },
+
+    #[error("sandbox I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// The engine a sandbox resolved to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Engine {
+    Bubblewrap(PathBuf),
+    Unshare(PathBuf),
+}
+
+/// A bind with resolved host and sandbox paths.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Bind {
+    source: PathBuf,
+    target: PathBuf,
+    writable: bool,
+}
+
+/// A command rewritten to run inside the sandbox.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SandboxedCommand {
+    /// Program to spawn (the engine).
+    pub program: String,
+    /// Arguments, ending with the original command and its arguments.
+    pub args: Vec<String>,
+    /// Environment variables to set on the spawned process.
+    pub env: Vec<(String, String)>,
+}
+
+/// A resolved sandbox that wraps backend commands.
+#[derive(Debug, Clone)]
+pub struct Sandbox {
+    engine: Engine,
+    workspace: PathBuf,
+    binds: Vec<Bind>,
+    network: bool,
+    limits: SandboxLimits,
+    prlimit: Option<PathBuf>,
+}
+
+impl Sandbox {
+    /// Resolves the engine, binds and limits for `workspace`, and creates
+    /// the sandbox's temporary directory.
+    ///
+    /// # Errors
+    /// Returns `SandboxError` when not on Linux, when the engine (or
+    /// `prlimit` for limits) is missing, or when a bind path does not exist.
+    pub fn new(config: &SandboxConfig, workspace: &Path) -> Result<Self, SandboxError> {
+        if !cfg!(target_os = "linux") {
+            return Err(SandboxError::Unsupported);
+        }
+
+        let engine = match config.engine {
+            SandboxEngine::Bubblewrap => Engine::Bubblewrap(
+                find_executable("bwrap").ok_or(SandboxError::EngineNotFound("bwrap"))?,
+            ),
+            SandboxEngine::Unshare => Engine::Unshare(
+                find_executable("unshare").ok_or(SandboxError::EngineNotFound("unshare"))?,
+            ),
+            SandboxEngine::Auto => find_executable("bwrap")
+                .map(Engine::Bubblewrap)
+                .or_else(|| find_executable("unshare").map(Engine::Unshare))
+                .ok_or(SandboxError::NoEngine)?,
+        };
+
+        let prlimit = if config.limits.is_empty() {
+            None
+        } else {
+            Some(find_executable("prlimit").ok_or(SandboxError::PrlimitNotFound)?)
+        };
+
+        let workspace = std::fs::canonicalize(workspace)?;
+        std::fs::create_dir_all(workspace.join(SANDBOX_TMP_DIR))?;
+        let binds = config
+            .binds
+            .iter()
+            .map(|bind| {
+                let source = resolve_bind_path(&bind.path, &workspace);
+                let source = std::fs::canonicalize(&source)
+                    .map_err(|_| SandboxError::BindNotFound(source.display().to_string()))?;
+                let target = bind
+                    .target
+                    .as_deref()
+                    .map_or_else(|| source.clone(), |t| resolve_bind_path(t, &workspace));
+                Ok(Bind {
+                    source,
+                    target,
+                    writable: bind.writable,
+                })
+            })
+            .collect::<Result<Vec<_>, SandboxError>>()?;
+
+        Ok(Self {
+            engine,
+            workspace,
+            binds,
+            network: config.network,
+            limits: config.limits,
+            prlimit,
+        })
+    }
+
+    /// Name of the engine in use ("bubblewrap" or "unshare").
+    pub fn engine_name(&self) -> &'static str {
+        match self.engine {
+            Engine::Bubblewrap(_) => "bubblewrap",
+            Engine::Unshare(_) => "unshare",
+        }
+    }
+
+    /// Directory exported as `TMPDIR` inside the sandbox.
+    pub fn tmp_dir(&self) -> PathBuf {
+        self.workspace.join(SANDBOX_TMP_DIR)
+    }
+
+    /// Rewrites `program args...` to run inside the sandbox.
+    pub fn wrap(&self, program: &str, args: &[String]) -> SandboxedCommand {
+        let mut inner = Vec::new();
+        if let Some(prlimit) = &self.prlimit {
+            inner.push(prlimit.display().to_string());
+            if let Some(mb) = self.limits.memory_mb {
+                inner.push(format!("--as={}", mb.saturating_mul(1024 * 1024)));
+            }
+            if let Some(secs) = self.limits.cpu_time_secs {
+                inner.push(format!("--cpu={secs}"));
+            }
+            if let Some(processes) = self.limits.max_user_processes {
+                inner.push(format!("--nproc={processes}"));
+            }
+            inner.push("--".to_string());
+        }
+        inner.push(program.to_string());
+        inner.extend(args.iter().cloned());
+
+        let (engine, mut wrapped) = match &self.engine {
+            Engine::Bubblewrap(bwrap) => (bwrap, self.bubblewrap_args()),
+            Engine::Unshare(unshare) => (unshare, self.unshare_args()),
+        };
+        wrapped.extend(inner);
+
+        SandboxedCommand {
+            program: engine.display().to_string(),
+            args: wrapped,
+            env: vec![("TMPDIR".to_string(), self.tmp_dir().display().to_string())],
+        }
+    }
+
+    /// Runs `true` inside the sandbox to confirm it can be set up.
+    ///
+    /// # Errors
+    /// Returns `SandboxError::Setup` with the engine's stderr when the trial
+    /// run fails.
+    pub fn check(&self) -> Result<(), SandboxError> {
+        let command = self.wrap("true", &[]);
+        let output = Command::new(&command.program)
+            .args(&command.args)
+            .envs(command.env)
+            .current_dir(&self.workspace)
+            .stdin(Stdio::null())
+            .output()
+            .map_err(|e| SandboxError::Setup {
+                engine: self.engine_name(),
+                message: e.to_string(),
+            })?;
+        if output.status.success() {
+            return Ok(());
+        }
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        Err(SandboxError::Setup {
+            engine: self.engine_name(),
+            message: if stderr.is_empty() {
+                format!("exited with {}", output.status)
+            } else {
+                stderr
+            },
+        })
+    }
+
+    fn bubblewrap_args(&self) -> Vec<String> {
+        let workspace = self.workspace.display().to_string();
+        let mut args: Vec<String> = [
+            "--ro-bind",
+            "/",
+            "/",
+            "--dev",
+            "/dev",
+            "--proc",
+            "/proc",
+            "--bind",
+        ]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+        args.extend([workspace.clone(), workspace.clone()]);
+        for bind in &self.binds {
+            let flag = if bind.writable { "--bind" } else { "--ro-bind" };
+            args.extend([
+                flag.to_string(),
+                bind.source.display().to_string(),
+                bind.target.display().to_string(),
+            ]);
+        }
+        if !self.network {
+            args.push("--unshare-net".to_string());
+        }
+        args.extend([
+            "--unshare-pid".to_string(),
+            "--die-with-parent".to_string(),
+            "--chdir".to_string(),
+            workspace,
+            "--".to_string(),
+        ]);
+        args
+    }
+
+    fn unshare_args(&self) -> Vec<String> {
+        let mut args: Vec<String> = [
+            "--user",
+            "--map-root-user",
+            "--mount",
+            "--pid",
+            "--fork",
+            "--kill-child",
+            "--mount-proc",
+        ]
+        .iter()
+        .map(ToString::to_string)
+        .collect();
+        if !self.network {
+            args.push("--net".to_string());
+        }
+        args.extend([
+            "--".to_string(),
+            "/bin/sh".to_string(),
+            "-c".to_string(),
+            self.unshare_script(),
+            "ralph-sandbox".to_string(),
+        ]);
+        args
+    }
+
+    /// Shell script run inside the new namespaces: bind the writable paths
+    /// onto themselves, remount every other mount read-only, then exec the
+    /// backend.
+    fn unshare_script(&self) -> String {
+        let workspace = quote(&self.workspace);
+        let mut script = format!("set -e\nmount --bind {workspace} {workspace}\n");
+        for bind in &self.binds {
+            script.push_str(&format!(
+                "mount --bind {} {}\n",
+                quote(&bind.source),
+                quote(&bind.target)
+            ));
+        }
+
+        let mut keep_writable = vec![workspace.clone()];
+        keep_writable.extend(
+            self.binds
+                .iter()
+                .filter(|bind| bind.writable)
+                .map(|bind| quote(&bind.target)),
+        );
+        // A remount must keep the flags the kernel locked on the mount
+        // (nosuid, nodev, ...), and any mount left writable aborts the launch.
+        script.push_str(&format!(
+            "awk '{{f=\"\"; n=split($4,o,\",\"); for(i=1;i<=n;i++) \
+             if(o[i]~/^(nosuid|nodev|noexec|noatime|nodiratime|relatime)$/) f=f\",\"o[i]; \
+             print $2, f}}' /proc/self/mounts | while read -r m f; do\n  \
+             m=$(printf '%b' \"$m\")\n  \
+             case \"$m\" in {}|/proc|/proc/*|/dev|/dev/*|/sys|/sys/*) ;;\n  \
+             *) mount -o \"remount,bind,ro$f\" \"$m\" || \
+             {{ echo \"ralph-sandbox: cannot make $m read-only\" >&2; exit 1; }} ;;\n  \
+             esac\ndone\n",
+            keep_writable.join("|")
+        ));
+        for bind in self.binds.iter().filter(|bind| !bind.writable) {
+            script.push_str(&format!(
+                "mount -o remount,bind,ro {}\n",
+                quote(&bind.target)
+            ));
+        }
+        script.push_str(&format!("cd {workspace}\nexec \"$@\"\n"));
+        script
+    }
+}
+
+/// Expands `~/` and resolves relative paths against the workspace.
+fn resolve_bind_path(path: &str, workspace: &Path) -> PathBuf {
+    if let Some(rest) = path.strip_prefix("~/")
+        && let Some(home) = std::env::var_os("HOME")
+    {
+        return PathBuf::from(home).join(rest);
+    }
+    let path = PathBuf::from(path);
+    if path.is_absolute() {
+        path
+    } else {
+        workspace.join(path)
+    }
+}
+
+/// Single-quotes a path for the unshare setup script.
+fn quote(path: &Path) -> String {
+    format!("'{}'", path.display().to_string().replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn sandbox(engine: Engine, workspace: &Path) -> Sandbox {
+        Sandbox {
+            engine,
+            workspace: workspace.to_path_buf(),
+            binds: vec![Bind {
+                source: PathBuf::from("/home/me/.claude"),
+                target: PathBuf::from("/home/me/.claude"),
+                writable: true,
+            }],
+            network: false,
+            limits: SandboxLimits {
+                memory_mb: Some(1),
+                cpu_time_secs: Some(60),
+                max_user_processes: None,
+            },
+            prlimit: Some(PathBuf::from("/usr/bin/prlimit")),
+        }
+    }
+
+    #[test]
+    fn test_config_defaults() {
+        let config: SandboxConfig = serde_yaml::from_str("enabled: true").unwrap();
+        assert!(config.enabled);
+        assert!(config.network);
+        assert_eq!(config.engine, SandboxEngine::Auto);
+        assert!(config.limits.is_empty());
+    }
+
+    #[test]
+    fn test_bubblewrap_wrap() {
+        let sandbox = sandbox(
+            Engine::Bubblewrap(PathBuf::from("/usr/bin/bwrap")),
+            Path::new("/ws"),
+        );
+        let command = sandbox.wrap("claude", &["-p".to_string(), "hi".to_string()]);
+
+        assert_eq!(command.program, "/usr/bin/bwrap");
+        let args = command.args.join(" ");
+        assert!(
+            args.starts_with("--ro-bind / / --dev /dev --proc /proc --bind /ws /ws"),
+            "{args}"
+        );
+        assert!(
+            args.contains("--bind /home/me/.claude /home/me/.claude"),
+            "{args}"
+        );
+        assert!(args.contains("--unshare-net"), "{args}");
+        assert!(
+            args.ends_with("-- /usr/bin/prlimit --as=1048576 --cpu=60 -- claude -p hi"),
+            "{args}"
+        );
+        assert_eq!(
+            command.env,
+            vec![("TMPDIR".to_string(), "/ws/.ralph/tmp".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_unshare_wrap() {
+        let sandbox = sandbox(
+            Engine::Unshare(PathBuf::from("/usr/bin/unshare")),
+            Path::new("/w s"),
+        );
+        let command = sandbox.wrap("claude", &[]);
+
+        assert_eq!(command.program, "/usr/bin/unshare");
+        assert!(command.args.contains(&"--net".to_string()));
+        let script = command
+            .args
+            .iter()
+            .find(|arg| arg.starts_with("set -e"))
+            .unwrap();
+        assert!(script.contains("mount --bind '/w s' '/w s'"), "{script}");
+        assert!(
+            script.contains("'/w s'|'/home/me/.claude'|/proc"),
+            "{script}"
+        );
+        assert!(
+            script.contains("|| { echo \"ralph-sandbox: cannot make $m read-only\" >&2; exit 1; }"),
+            "{script}"
+        );
+        assert!(!script.contains("|| true"), "{script}");
+        assert!(script.ends_with("exec \"$@\"\n"), "{script}");
+        assert_eq!(command.args.last().map(String::as_str), Some("claude"));
+    }
+
+    #[test]
+    fn test_missing_bind_is_an_error() {
+        let workspace = TempDir::new().unwrap();
+        let config = SandboxConfig {
+            enabled: true,
+            engine: SandboxEngine::Unshare,
+            binds: vec![SandboxBind {
+                path: "does-not-exist".to_string(),
+                target: None,
+                writable: false,
+            }],
+            ..SandboxConfig::default()
+        };
+        if find_executable("unshare").is_none() {
+            return;
+        }
+
+        let err = Sandbox::new(&config, workspace.path()).unwrap_err();
+        assert!(
+            matches!(err, SandboxError::BindNotFound(path) if path.ends_with("does-not-exist"))
+        );
+    }
+
+    #[test]
+    fn test_quote_escapes_single_quotes() {
+        assert_eq!(quote(Path::new("/a'b")), r"'/a'\''b'");
+    }
+}
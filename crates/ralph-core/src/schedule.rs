@@ -0,0 +1,701 @@
+//! Cron-style schedules for recurring loops.
+//!
+//! Schedule entries come from the `schedules:` section of the config and from
+//! `.ralph/schedules.yml`. Each entry names a cron expression, a prompt, and
+//! optionally a preset or config file to run it with. The [`Scheduler`]
+//! decides which entries are due and refuses to start an entry while its
+//! previous run is still going; the `ralph schedule daemon` command does the
+//! actual running.
+//!
+//! Cron expressions use the standard five fields — minute, hour, day of
+//! month, month, day of week — evaluated in the caller's time zone:
+//!
+//! ```text
+//! 0 3 * * 1-5      03:00 on weekdays
+//! */30 * * * *     every 30 minutes
+//! 0 9 1 * *        09:00 on the first of every month
+//! @daily           shorthand for "0 0 * * *"
+//! ```
+
+use std::collections::HashSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Timelike};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use crate::loop_history::{HistoryError, HistoryEventType, LoopHistory};
+
+/// Schedule file, relative to the workspace root.
+pub const SCHEDULES_FILE: &str = ".ralph/schedules.yml";
+
+/// History of scheduled runs, relative to the workspace root.
+pub const SCHEDULE_HISTORY_FILE: &str = ".ralph/schedule-history.jsonl";
+
+/// How far ahead [`CronSchedule::next_after`] searches before giving up
+/// (covers "29 February" style expressions).
+const SEARCH_DAYS: i64 = 366 * 5;
+
+/// Errors that can occur while loading or evaluating schedules.
+#[derive(Debug, Error)]
+pub enum ScheduleError {
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+
+    #[error("Failed to parse {path}: {source}")]
+    Yaml {
+        path: PathBuf,
+        source: serde_yaml::Error,
+    },
+
+    #[error("Invalid cron expression '{expr}': {reason}")]
+    InvalidCron { expr: String, reason: String },
+
+    #[error("Invalid schedule '{name}': {reason}")]
+    InvalidEntry { name: String, reason: String },
+
+    #[error("Schedule '{0}' is defined more than once")]
+    DuplicateName(String),
+
+    #[error("No schedule named '{0}'")]
+    NotFound(String),
+
+    #[error(transparent)]
+    History(#[from] HistoryError),
+}
+
+/// A parsed five-field cron expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CronSchedule {
+    expr: String,
+    minutes: u64,
+    hours: u64,
+    days_of_month: u64,
+    months: u64,
+    days_of_week: u64,
+    /// Whether day-of-month / day-of-week were restricted (not `*`).
+    /// When both are, a day matches if either field does, as in cron.
+    dom_restricted: bool,
+    dow_restricted: bool,
+}
+
+const MONTH_NAMES: [&str; 12] = [
+    "jan", "feb", "mar", "apr", "may", "jun", "jul", "aug", "sep", "oct", "nov", "dec",
+];
+const DAY_NAMES: [&str; 7] = ["sun", "mon", "tue", "wed", "thu", "fri", "sat"];
+
+impl CronSchedule {
+    /// Parses a cron expression (five fields, or an `@daily`-style macro).
+    pub fn parse(expr: &str) -> Result<Self, ScheduleError> {
+        let trimmed = expr.trim();
+        let expanded = match trimmed {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            "@yearly" | "@annually" => "0 0 1 1 *",
+            other => other,
+        };
+        let invalid = |reason: String| ScheduleError::InvalidCron {
+            expr: trimmed.to_string(),
+            reason,
+        };
+
+        let fields: Vec<&str> = expanded.split_whitespace().collect();
+        let [minute, hour, dom, month, dow] = fields[..] else {
+            return Err(invalid(format!(
+                "expected 5 fields (minute hour day-of-month month day-of-week), found {}",
+                fields.len()
+            )));
+        };
+
+        let mut days_of_week = parse_field(dow, 0, 7, &DAY_NAMES).map_err(&invalid)?;
+        // Both 0 and 7 mean Sunday.
+        if days_of_week & (1 << 7) != 0 {
+            days_of_week = (days_of_week | 1) & !(1 << 7);
+        }
+
+        Ok(Self {
+            expr: trimmed.to_string(),
+            minutes: parse_field(minute, 0, 59, &[]).map_err(&invalid)?,
+            hours: parse_field(hour, 0, 23, &[]).map_err(&invalid)?,
+            days_of_month: parse_field(dom, 1, 31, &[]).map_err(&invalid)?,
+            months: parse_field(month, 1, 12, &MONTH_NAMES).map_err(&invalid)?,
+            days_of_week,
+            dom_restricted: dom != "*",
+            dow_restricted: dow != "*",
+        })
+    }
+
+    /// The expression as written.
+    pub fn expr(&self) -> &str {
+        &self.expr
+    }
+
+    /// Returns true if the schedule fires at the given local minute.
+    pub fn matches(&self, time: &NaiveDateTime) -> bool {
+        bit(self.minutes, time.minute())
+            && bit(self.hours, time.hour())
+            && bit(self.months, time.month())
+            && self.matches_day(time.date())
+    }
+
+    fn matches_day(&self, date: NaiveDate) -> bool {
+        let dom = bit(self.days_of_month, date.day());
+        let dow = bit(self.days_of_week, date.weekday().num_days_from_sunday());
+        if self.dom_restricted && self.dow_restricted {
+            dom || dow
+        } else {
+            dom && dow
+        }
+    }
+
+    /// Returns the first time strictly after `after` at which the schedule fires.
+    ///
+    /// Local times skipped by a DST transition never fire. Returns `None` if
+    /// the expression cannot fire within the next five years (e.g. `0 0 31 2 *`).
+    pub fn next_after<Tz: TimeZone>(&self, after: &DateTime<Tz>) -> Option<DateTime<Tz>> {
+        let tz = after.timezone();
+        let start = after.naive_local().with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+        let limit = start + Duration::days(SEARCH_DAYS);
+
+        let mut time = start;
+        while time <= limit {
+            if !bit(self.months, time.month()) {
+                let (year, month) = if time.month() == 12 {
+                    (time.year() + 1, 1)
+                } else {
+                    (time.year(), time.month() + 1)
+                };
+                time = NaiveDate::from_ymd_opt(year, month, 1)?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !self.matches_day(time.date()) {
+                time = time.date().succ_opt()?.and_hms_opt(0, 0, 0)?;
+                continue;
+            }
+            if !bit(self.hours, time.hour()) {
+                time = time.with_minute(0)? + Duration::hours(1);
+                continue;
+            }
+            if !bit(self.minutes, time.minute()) {
+                time += Duration::minutes(1);
+                continue;
+            }
+            if let Some(found) = tz.from_local_datetime(&time).earliest()
+                && found > *after
+            {
+                return Some(found);
+            }
+            time += Duration::minutes(1);
+        }
+        None
+    }
+}
+
+impl FromStr for CronSchedule {
+    type Err = ScheduleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for CronSchedule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.expr)
+    }
+}
+
+fn bit(mask: u64, value: u32) -> bool {
+    mask & (1 << value) != 0
+}
+
+/// Parses one cron field (`*`, `5`, `1-5`, `*/15`, `1-10/2`, `mon,wed`) into a bitmask.
+fn parse_field(field: &str, min: u32, max: u32, names: &[&str]) -> Result<u64, String> {
+    let value = |token: &str| -> Result<u32, String> {
+        let lower = token.to_ascii_lowercase();
+        // Named months start at 1, named weekdays at 0.
+        if let Some(index) = names.iter().position(|name| *name == lower) {
+            return Ok(index as u32 + min);
+        }
+        let n: u32 = token
+            .parse()
+            .map_err(|_| format!("'{token}' is not a number"))?;
+        if n < min || n > max {
+            return Err(format!("{n} is outside {min}-{max}"));
+        }
+        Ok(n)
+    };
+
+    let mut mask = 0u64;
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => {
+                let step: u32 = step
+                    .parse()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or_else(|| format!("invalid step in '{item}'"))?;
+                (range, step)
+            }
+            None => (item, 1),
+        };
+        let (start, end) = if range == "*" {
+            (min, max)
+        } else if let Some((start, end)) = range.split_once('-') {
+            (value(start)?, value(end)?)
+        } else {
+            let start = value(range)?;
+            // "5/10" means "from 5 to the end, every 10".
+            (start, if step > 1 { max } else { start })
+        };
+        if start > end {
+            return Err(format!("range '{range}' is backwards"));
+        }
+        for n in (start..=end).step_by(step as usize) {
+            mask |= 1 << n;
+        }
+    }
+    Ok(mask)
+}
+
+/// A recurring loop: when to run it, what to ask, and which config to use.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScheduleEntry {
+    /// Unique name, used in history and notifications.
+    pub name: String,
+
+    /// Cron expression (five fields or `@hourly`/`@daily`/`@weekly`/`@monthly`).
+    pub cron: String,
+
+    /// Inline prompt for the loop.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<String>,
+
+    /// Prompt file, relative to the workspace root.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_file: Option<String>,
+
+    /// Builtin preset to run with (e.g. `code-assist`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preset: Option<String>,
+
+    /// Config source to run with, layered over the preset (file path or URL).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config: Option<String>,
+
+    /// Disabled entries are listed but never run by the daemon.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl ScheduleEntry {
+    /// Parses the entry's cron expression.
+    pub fn cron_schedule(&self) -> Result<CronSchedule, ScheduleError> {
+        CronSchedule::parse(&self.cron)
+    }
+
+    /// Checks the entry is runnable: a name, a valid cron expression, and
+    /// exactly one of `prompt` / `prompt_file`.
+    pub fn validate(&self) -> Result<(), ScheduleError> {
+        let invalid = |reason: &str| ScheduleError::InvalidEntry {
+            name: self.name.clone(),
+            reason: reason.to_string(),
+        };
+        if self.name.trim().is_empty() {
+            return Err(invalid("name must not be empty"));
+        }
+        self.cron_schedule()?;
+        match (&self.prompt, &self.prompt_file) {
+            (Some(_), Some(_)) => Err(invalid("set either prompt or prompt_file, not both")),
+            (None, None) => Err(invalid("needs a prompt or prompt_file")),
+            _ => Ok(()),
+        }
+    }
+
+    /// Returns the prompt text, reading `prompt_file` relative to the workspace.
+    pub fn resolve_prompt(&self, workspace_root: &Path) -> Result<String, ScheduleError> {
+        if let Some(prompt) = &self.prompt {
+            return Ok(prompt.clone());
+        }
+        let file = self
+            .prompt_file
+            .as_ref()
+            .ok_or_else(|| ScheduleError::InvalidEntry {
+                name: self.name.clone(),
+                reason: "needs a prompt or prompt_file".to_string(),
+            })?;
+        Ok(std::fs::read_to_string(workspace_root.join(file))?)
+    }
+
+    /// Config sources (`-c` arguments) the loop runs with: the preset, then
+    /// the config file. Empty when the entry uses the workspace default.
+    pub fn config_sources(&self) -> Vec<String> {
+        self.preset
+            .iter()
+            .map(|preset| format!("builtin:{preset}"))
+            .chain(self.config.iter().cloned())
+            .collect()
+    }
+}
+
+/// Contents of `.ralph/schedules.yml`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ScheduleFile {
+    #[serde(default)]
+    pub schedules: Vec<ScheduleEntry>,
+}
+
+impl ScheduleFile {
+    /// Loads the schedule file, returning an empty one if it does not exist.
+    pub fn load(path: &Path) -> Result<Self, ScheduleError> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(path)?;
+        serde_yaml::from_str(&content).map_err(|source| ScheduleError::Yaml {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+}
+
+/// Collects the schedules from the config and `.ralph/schedules.yml`, in
+/// that order, validating each entry and rejecting duplicate names.
+pub fn load_schedules(
+    workspace_root: &Path,
+    config_entries: &[ScheduleEntry],
+) -> Result<Vec<ScheduleEntry>, ScheduleError> {
+    let file = ScheduleFile::load(&workspace_root.join(SCHEDULES_FILE))?;
+    let mut seen = HashSet::new();
+    let mut entries = Vec::new();
+    for entry in config_entries.iter().cloned().chain(file.schedules) {
+        entry.validate()?;
+        if !seen.insert(entry.name.clone()) {
+            return Err(ScheduleError::DuplicateName(entry.name));
+        }
+        entries.push(entry);
+    }
+    Ok(entries)
+}
+
+/// What the scheduler wants done with a due entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Trigger {
+    /// Start a run of this entry.
+    Run(ScheduleEntry),
+    /// The entry is due, but its previous run has not finished.
+    Skip { name: String, reason: String },
+}
+
+#[derive(Debug)]
+struct Slot<Tz: TimeZone> {
+    entry: ScheduleEntry,
+    cron: CronSchedule,
+    next: Option<DateTime<Tz>>,
+    running: bool,
+}
+
+/// Decides which schedule entries are due, one run per entry at a time.
+///
+/// Occurrences missed while the process was asleep collapse into a single
+/// run; an occurrence that comes due while the previous run is still going
+/// is skipped rather than queued.
+#[derive(Debug)]
+pub struct Scheduler<Tz: TimeZone> {
+    slots: Vec<Slot<Tz>>,
+}
+
+impl<Tz: TimeZone> Scheduler<Tz> {
+    /// Creates a scheduler for the enabled entries, with first runs after `now`.
+    pub fn new(entries: Vec<ScheduleEntry>, now: &DateTime<Tz>) -> Result<Self, ScheduleError> {
+        let slots = entries
+            .into_iter()
+            .filter(|entry| entry.enabled)
+            .map(|entry| {
+                let cron = entry.cron_schedule()?;
+                let next = cron.next_after(now);
+                Ok(Slot {
+                    entry,
+                    cron,
+                    next,
+                    running: false,
+                })
+            })
+            .collect::<Result<_, ScheduleError>>()?;
+        Ok(Self { slots })
+    }
+
+    /// Returns the entries due at `now`, advancing each to its next occurrence.
+    pub fn poll(&mut self, now: &DateTime<Tz>) -> Vec<Trigger> {
+        let mut triggers = Vec::new();
+        for slot in &mut self.slots {
+            if slot.next.as_ref().is_none_or(|next| next > now) {
+                continue;
+            }
+            slot.next = slot.cron.next_after(now);
+            if slot.running {
+                triggers.push(Trigger::Skip {
+                    name: slot.entry.name.clone(),
+                    reason: "previous run still in progress".to_string(),
+                });
+            } else {
+                slot.running = true;
+                triggers.push(Trigger::Run(slot.entry.clone()));
+            }
+        }
+        triggers
+    }
+
+    /// Marks the entry's run as finished so it can run again.
+    pub fn finish(&mut self, name: &str) {
+        if let Some(slot) = self.slots.iter_mut().find(|slot| slot.entry.name == name) {
+            slot.running = false;
+        }
+    }
+
+    /// Returns true if the entry has a run in progress.
+    pub fn is_running(&self, name: &str) -> bool {
+        self.slots
+            .iter()
+            .any(|slot| slot.entry.name == name && slot.running)
+    }
+
+    /// The earliest upcoming occurrence across all entries.
+    pub fn next_due(&self) -> Option<DateTime<Tz>> {
+        self.slots.iter().filter_map(|slot| slot.next.clone()).min()
+    }
+
+    /// Each scheduled entry with its next occurrence.
+    pub fn upcoming(&self) -> impl Iterator<Item = (&ScheduleEntry, Option<&DateTime<Tz>>)> {
+        self.slots
+            .iter()
+            .map(|slot| (&slot.entry, slot.next.as_ref()))
+    }
+}
+
+/// The most recent run of a schedule, read from the schedule history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduledRun {
+    pub schedule: String,
+    pub started_at: DateTime<chrono::Utc>,
+    /// `None` while the run is in progress (or if the daemon died mid-run).
+    pub outcome: Option<String>,
+    pub success: Option<bool>,
+    pub loop_id: Option<String>,
+}
+
+/// Returns the schedule history for a workspace.
+pub fn schedule_history(workspace_root: &Path) -> LoopHistory {
+    LoopHistory::new(workspace_root.join(SCHEDULE_HISTORY_FILE))
+}
+
+/// Returns the most recent run of each schedule, in order of first appearance.
+pub fn last_runs(history: &LoopHistory) -> Result<Vec<ScheduledRun>, ScheduleError> {
+    let mut runs: Vec<ScheduledRun> = Vec::new();
+    for event in history.read_all()? {
+        match event.event_type {
+            HistoryEventType::ScheduledRunStarted { schedule } => {
+                let run = ScheduledRun {
+                    schedule: schedule.clone(),
+                    started_at: event.timestamp,
+                    outcome: None,
+                    success: None,
+                    loop_id: None,
+                };
+                match runs.iter_mut().find(|run| run.schedule == schedule) {
+                    Some(existing) => *existing = run,
+                    None => runs.push(run),
+                }
+            }
+            HistoryEventType::ScheduledRunFinished {
+                schedule,
+                loop_id,
+                outcome,
+                success,
+            } => {
+                if let Some(run) = runs.iter_mut().find(|run| run.schedule == schedule) {
+                    run.outcome = Some(outcome);
+                    run.success = Some(success);
+                    run.loop_id = loop_id;
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(runs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use tempfile::TempDir;
+
+    fn at(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, mo, d, h, mi, 0).unwrap()
+    }
+
+    fn entry(name: &str, cron: &str) -> ScheduleEntry {
+        ScheduleEntry {
+            name: name.to_string(),
+            cron: cron.to_string(),
+            prompt: Some("audit dependencies".to_string()),
+            prompt_file: None,
+            preset: None,
+            config: None,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn parses_fields_ranges_steps_and_names() {
+        let cron = CronSchedule::parse("*/15 9-17 * jan,jul mon-fri").unwrap();
+        // 2026-01-05 is a Monday.
+        assert!(cron.matches(&at(2026, 1, 5, 9, 45).naive_utc()));
+        assert!(!cron.matches(&at(2026, 1, 5, 9, 50).naive_utc()));
+        assert!(!cron.matches(&at(2026, 1, 4, 9, 45).naive_utc()));
+        assert!(!cron.matches(&at(2026, 2, 2, 9, 45).naive_utc()));
+
+        let sunday = CronSchedule::parse("0 0 * * 7").unwrap();
+        assert!(sunday.matches(&at(2026, 1, 4, 0, 0).naive_utc()));
+    }
+
+    #[test]
+    fn rejects_malformed_expressions() {
+        for expr in [
+            "* * * *",
+            "60 * * * *",
+            "*/0 * * * *",
+            "5-1 * * * *",
+            "x * * * *",
+        ] {
+            assert!(
+                matches!(
+                    CronSchedule::parse(expr),
+                    Err(ScheduleError::InvalidCron { .. })
+                ),
+                "{expr} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn next_after_finds_following_occurrence() {
+        let daily = CronSchedule::parse("@daily").unwrap();
+        assert_eq!(
+            daily.next_after(&at(2026, 3, 10, 0, 0)),
+            Some(at(2026, 3, 11, 0, 0))
+        );
+
+        let weekdays = CronSchedule::parse("30 3 * * 1-5").unwrap();
+        // Friday 2026-01-09 after 03:30 → Monday 2026-01-12.
+        assert_eq!(
+            weekdays.next_after(&at(2026, 1, 9, 4, 0)),
+            Some(at(2026, 1, 12, 3, 30))
+        );
+
+        let leap = CronSchedule::parse("0 12 29 2 *").unwrap();
+        assert_eq!(
+            leap.next_after(&at(2026, 3, 1, 0, 0)),
+            Some(at(2028, 2, 29, 12, 0))
+        );
+
+        assert_eq!(
+            CronSchedule::parse("0 0 31 2 *")
+                .unwrap()
+                .next_after(&at(2026, 1, 1, 0, 0)),
+            None
+        );
+    }
+
+    #[test]
+    fn day_of_month_and_week_match_either_when_both_set() {
+        let cron = CronSchedule::parse("0 0 1 * mon").unwrap();
+        // 2026-01-01 is a Thursday; 2026-01-05 is a Monday.
+        assert!(cron.matches(&at(2026, 1, 1, 0, 0).naive_utc()));
+        assert!(cron.matches(&at(2026, 1, 5, 0, 0).naive_utc()));
+        assert!(!cron.matches(&at(2026, 1, 6, 0, 0).naive_utc()));
+    }
+
+    #[test]
+    fn scheduler_runs_due_entries_and_skips_overlaps() {
+        let start = at(2026, 1, 1, 0, 0);
+        let mut scheduler = Scheduler::new(
+            vec![entry("audit", "*/10 * * * *"), {
+                let mut off = entry("off", "* * * * *");
+                off.enabled = false;
+                off
+            }],
+            &start,
+        )
+        .unwrap();
+        assert_eq!(scheduler.next_due(), Some(at(2026, 1, 1, 0, 10)));
+        assert!(scheduler.poll(&at(2026, 1, 1, 0, 5)).is_empty());
+
+        let triggers = scheduler.poll(&at(2026, 1, 1, 0, 10));
+        assert!(matches!(&triggers[..], [Trigger::Run(e)] if e.name == "audit"));
+        assert!(scheduler.is_running("audit"));
+
+        let triggers = scheduler.poll(&at(2026, 1, 1, 0, 20));
+        assert!(matches!(&triggers[..], [Trigger::Skip { name, .. }] if name == "audit"));
+
+        scheduler.finish("audit");
+        // Missed occurrences collapse into one run.
+        let triggers = scheduler.poll(&at(2026, 1, 1, 1, 5));
+        assert_eq!(triggers.len(), 1);
+        assert_eq!(scheduler.next_due(), Some(at(2026, 1, 1, 1, 10)));
+    }
+
+    #[test]
+    fn load_schedules_merges_config_and_file() {
+        let dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(dir.path().join(".ralph")).unwrap();
+        std::fs::write(
+            dir.path().join(SCHEDULES_FILE),
+            "schedules:\n  - name: docs\n    cron: \"@weekly\"\n    prompt_file: prompts/docs.md\n    preset: docs\n",
+        )
+        .unwrap();
+
+        let entries = load_schedules(dir.path(), &[entry("audit", "@daily")]).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].name, "docs");
+        assert_eq!(entries[1].config_sources(), vec!["builtin:docs"]);
+
+        let err = load_schedules(dir.path(), &[entry("docs", "@daily")]).unwrap_err();
+        assert!(matches!(err, ScheduleError::DuplicateName(name) if name == "docs"));
+
+        let mut no_prompt = entry("empty", "@daily");
+        no_prompt.prompt = None;
+        let err = load_schedules(dir.path(), &[no_prompt]).unwrap_err();
+        assert!(matches!(err, ScheduleError::InvalidEntry { .. }));
+    }
+
+    #[test]
+    fn last_runs_reads_schedule_history() {
+        let dir = TempDir::new().unwrap();
+        let history = schedule_history(dir.path());
+        history.record_scheduled_run_started("audit").unwrap();
+        history
+            .record_scheduled_run_finished("audit", Some("loop-1"), "CompletionPromise", true)
+            .unwrap();
+        history
+            .record_scheduled_run_skipped("audit", "previous run still in progress")
+            .unwrap();
+        history.record_scheduled_run_started("docs").unwrap();
+
+        let runs = last_runs(&history).unwrap();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].schedule, "audit");
+        assert_eq!(runs[0].success, Some(true));
+        assert_eq!(runs[0].loop_id.as_deref(), Some("loop-1"));
+        assert_eq!(runs[1].outcome, None);
+    }
+}
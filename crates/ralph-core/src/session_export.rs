@@ -0,0 +1,967 @@
+//! Session export to shareable formats.
+//!
+//! `SessionExporter` converts a recorded JSONL session into either an
+//! asciinema v2 cast file (terminal writes and resizes only) or a
+//! self-contained HTML report that interleaves orchestration events (hat
+//! switches, published topics, termination) with the terminal output
+//! timeline. Both formats are derived from the same records that
+//! `SessionPlayer` replays.
+
+use ralph_proto::UxEvent;
+use serde::Serialize;
+use serde_json::Value;
+use std::io::{self, Write};
+
+use crate::session_player::{SessionPlayer, TimestampedRecord};
+
+/// Default terminal width used when the recording has no resize event.
+const DEFAULT_WIDTH: u16 = 80;
+
+/// Default terminal height used when the recording has no resize event.
+const DEFAULT_HEIGHT: u16 = 24;
+
+/// Maximum payload characters shown inline in the HTML report.
+const PAYLOAD_PREVIEW_CHARS: usize = 2000;
+
+/// Configuration for session export.
+#[derive(Debug, Clone)]
+pub struct ExportConfig {
+    /// Terminal width used until the first recorded resize.
+    pub width: u16,
+
+    /// Terminal height used until the first recorded resize.
+    pub height: u16,
+
+    /// Optional title for the cast header and HTML report.
+    pub title: Option<String>,
+}
+
+impl Default for ExportConfig {
+    fn default() -> Self {
+        Self {
+            width: DEFAULT_WIDTH,
+            height: DEFAULT_HEIGHT,
+            title: None,
+        }
+    }
+}
+
+impl ExportConfig {
+    /// Sets the initial terminal dimensions.
+    pub fn with_size(mut self, width: u16, height: u16) -> Self {
+        self.width = width.max(1);
+        self.height = height.max(1);
+        self
+    }
+
+    /// Sets the title.
+    pub fn with_title(mut self, title: impl Into<String>) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+}
+
+/// A single entry in the exported session timeline.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TimelineEntry {
+    /// An iteration started with the given hat.
+    Iteration {
+        /// Offset from session start in milliseconds.
+        offset_ms: u64,
+        /// Iteration number (1-based).
+        iteration: u64,
+        /// Hat that was active for the iteration.
+        hat: String,
+    },
+
+    /// An event was published on the bus.
+    Publish {
+        /// Offset from session start in milliseconds.
+        offset_ms: u64,
+        /// Event topic.
+        topic: String,
+        /// Event payload.
+        payload: String,
+        /// Hat that published the event, if any.
+        source: Option<String>,
+        /// Target hat for direct handoff, if any.
+        target: Option<String>,
+    },
+
+    /// The loop terminated.
+    Termination {
+        /// Offset from session start in milliseconds.
+        offset_ms: u64,
+        /// Termination reason as recorded.
+        reason: String,
+    },
+
+    /// Consecutive terminal writes, merged into one block.
+    Output {
+        /// Offset of the first write in the block, in milliseconds.
+        offset_ms: u64,
+        /// Raw bytes written to the terminal (ANSI sequences preserved).
+        bytes: Vec<u8>,
+    },
+}
+
+impl TimelineEntry {
+    /// Returns the offset from session start in milliseconds.
+    pub fn offset_ms(&self) -> u64 {
+        match self {
+            TimelineEntry::Iteration { offset_ms, .. }
+            | TimelineEntry::Publish { offset_ms, .. }
+            | TimelineEntry::Termination { offset_ms, .. }
+            | TimelineEntry::Output { offset_ms, .. } => *offset_ms,
+        }
+    }
+}
+
+/// Exports recorded sessions to asciinema casts and HTML reports.
+///
+/// # Example
+///
+/// ```
+/// use ralph_core::{ExportConfig, SessionExporter, SessionPlayer};
+///
+/// let jsonl = r#"{"ts":1000,"event":"ux.terminal.write","data":{"bytes":"SGVsbG8=","stdout":true,"offset_ms":0}}"#;
+/// let player = SessionPlayer::from_bytes(jsonl.as_bytes()).unwrap();
+///
+/// let mut cast = Vec::new();
+/// SessionExporter::new(&player)
+///     .with_config(ExportConfig::default().with_size(100, 30))
+///     .write_asciicast(&mut cast)
+///     .unwrap();
+///
+/// let cast = String::from_utf8(cast).unwrap();
+/// assert!(cast.starts_with(r#"{"version":2"#));
+/// assert!(cast.contains(r#"[0.0,"o","Hello"]"#));
+/// ```
+#[derive(Debug)]
+pub struct SessionExporter<'a> {
+    /// The player holding the parsed session records.
+    player: &'a SessionPlayer,
+
+    /// Export configuration.
+    config: ExportConfig,
+}
+
+impl<'a> SessionExporter<'a> {
+    /// Creates an exporter for the given session.
+    pub fn new(player: &'a SessionPlayer) -> Self {
+        Self {
+            player,
+            config: ExportConfig::default(),
+        }
+    }
+
+    /// Sets the export configuration.
+    pub fn with_config(mut self, config: ExportConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Writes the session as an asciinema v2 cast file.
+    ///
+    /// The header uses the last resize recorded before any output, or the
+    /// configured dimensions when there is none. Terminal writes become `"o"` events and
+    /// later resizes become `"r"` events, timed relative to session start.
+    pub fn write_asciicast<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let ux_events: Vec<(u64, UxEvent)> = self
+            .player
+            .records()
+            .iter()
+            .filter(|r| r.record.event.starts_with("ux.terminal."))
+            .filter_map(|r| {
+                SessionPlayer::parse_ux_event(&r.record)
+                    .ok()
+                    .map(|ux| (r.offset_ms, ux))
+            })
+            .collect();
+
+        // The last resize before the first write defines the initial size
+        let (mut width, mut height) = (self.config.width, self.config.height);
+        for (_, ux) in &ux_events {
+            match ux {
+                UxEvent::TerminalResize(resize) => {
+                    width = resize.width;
+                    height = resize.height;
+                }
+                UxEvent::TerminalWrite(_) => break,
+                _ => {}
+            }
+        }
+
+        let header = CastHeader {
+            version: 2,
+            width,
+            height,
+            timestamp: self.player.records().first().map(|r| r.record.ts / 1000),
+            title: self.config.title.as_deref(),
+            env: CastEnv {
+                term: "xterm-256color",
+            },
+        };
+        let header = serde_json::to_string(&header)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        writeln!(writer, "{}", header)?;
+
+        let mut wrote_output = false;
+        // Bytes of a multi-byte character split across PTY reads
+        let mut pending: Vec<u8> = Vec::new();
+        for (offset_ms, ux) in ux_events {
+            let time = offset_ms as f64 / 1000.0;
+            let line = match ux {
+                UxEvent::TerminalWrite(write) => {
+                    let bytes = write.decode_bytes().map_err(|e| {
+                        io::Error::new(
+                            io::ErrorKind::InvalidData,
+                            format!("Failed to decode base64: {}", e),
+                        )
+                    })?;
+                    wrote_output = true;
+                    pending.extend_from_slice(&bytes);
+                    let text = take_complete_utf8(&mut pending);
+                    if text.is_empty() {
+                        continue;
+                    }
+                    serde_json::json!([time, "o", text])
+                }
+                // The header already reflects the leading resizes
+                UxEvent::TerminalResize(resize) if wrote_output => {
+                    serde_json::json!([time, "r", format!("{}x{}", resize.width, resize.height)])
+                }
+                _ => continue,
+            };
+            writeln!(writer, "{}", line)?;
+        }
+        if !pending.is_empty() {
+            // A truncated trailing character can never complete
+            let time = self.player.records().last().map_or(0, |r| r.offset_ms) as f64 / 1000.0;
+            let line = serde_json::json!([time, "o", String::from_utf8_lossy(&pending)]);
+            writeln!(writer, "{}", line)?;
+        }
+
+        writer.flush()
+    }
+
+    /// Builds the interleaved timeline of orchestration events and output.
+    ///
+    /// Iterations come from `_meta.iteration` records, publishes from
+    /// `bus.publish` records (with `loop.terminate` mapped to a termination
+    /// entry), and adjacent terminal writes are merged into one output block.
+    pub fn timeline(&self) -> Vec<TimelineEntry> {
+        let mut entries: Vec<TimelineEntry> = Vec::new();
+
+        for record in self.player.records() {
+            let offset_ms = record.offset_ms;
+            let data = &record.record.data;
+
+            match record.record.event.as_str() {
+                "_meta.iteration" => entries.push(TimelineEntry::Iteration {
+                    offset_ms,
+                    iteration: data.get("n").and_then(Value::as_u64).unwrap_or(0),
+                    hat: json_str(data, "hat").unwrap_or_default(),
+                }),
+                "_meta.termination" => {
+                    // loop.terminate on the bus already covers this termination
+                    if !entries
+                        .iter()
+                        .any(|e| matches!(e, TimelineEntry::Termination { .. }))
+                    {
+                        entries.push(TimelineEntry::Termination {
+                            offset_ms,
+                            reason: json_str(data, "reason").unwrap_or_default(),
+                        });
+                    }
+                }
+                "bus.publish" => {
+                    let topic = json_str(data, "topic").unwrap_or_default();
+                    let payload = json_str(data, "payload").unwrap_or_default();
+                    if topic == "loop.terminate" {
+                        entries.push(TimelineEntry::Termination {
+                            offset_ms,
+                            reason: termination_reason_from_payload(&payload),
+                        });
+                    } else {
+                        entries.push(TimelineEntry::Publish {
+                            offset_ms,
+                            topic,
+                            payload,
+                            source: json_str(data, "source"),
+                            target: json_str(data, "target"),
+                        });
+                    }
+                }
+                "ux.terminal.write" => {
+                    if let Some(bytes) = decode_write(record) {
+                        if let Some(TimelineEntry::Output { bytes: prev, .. }) = entries.last_mut()
+                        {
+                            prev.extend_from_slice(&bytes);
+                        } else {
+                            entries.push(TimelineEntry::Output { offset_ms, bytes });
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        entries
+    }
+
+    /// Writes the session as a self-contained HTML report.
+    ///
+    /// The report has no external assets: styles are inlined and terminal
+    /// output is rendered with basic SGR colors translated to spans.
+    pub fn write_html<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        let timeline = self.timeline();
+        let title = self
+            .config
+            .title
+            .clone()
+            .unwrap_or_else(|| "Ralph session".to_string());
+
+        let iterations = timeline
+            .iter()
+            .filter(|e| matches!(e, TimelineEntry::Iteration { .. }))
+            .count();
+        let publishes = timeline
+            .iter()
+            .filter(|e| matches!(e, TimelineEntry::Publish { .. }))
+            .count();
+        let termination = timeline.iter().find_map(|e| match e {
+            TimelineEntry::Termination { reason, .. } => Some(reason.as_str()),
+            _ => None,
+        });
+        let duration_ms = self
+            .player
+            .records()
+            .last()
+            .map(|r| r.offset_ms)
+            .unwrap_or(0);
+
+        writeln!(writer, "<!DOCTYPE html>")?;
+        writeln!(
+            writer,
+            "<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">"
+        )?;
+        writeln!(writer, "<title>{}</title>", escape_html(&title))?;
+        writeln!(writer, "<style>{}</style>\n</head>\n<body>", HTML_STYLE)?;
+        writeln!(writer, "<h1>{}</h1>", escape_html(&title))?;
+
+        writeln!(writer, "<table class=\"summary\">")?;
+        writeln!(
+            writer,
+            "<tr><th>Duration</th><td>{}</td></tr>",
+            format_offset(duration_ms)
+        )?;
+        writeln!(
+            writer,
+            "<tr><th>Iterations</th><td>{}</td></tr>",
+            iterations
+        )?;
+        writeln!(
+            writer,
+            "<tr><th>Events published</th><td>{}</td></tr>",
+            publishes
+        )?;
+        writeln!(
+            writer,
+            "<tr><th>Termination</th><td>{}</td></tr>",
+            escape_html(termination.unwrap_or("not recorded"))
+        )?;
+        writeln!(writer, "</table>")?;
+
+        writeln!(writer, "<ol class=\"timeline\">")?;
+        for entry in &timeline {
+            let time = format_offset(entry.offset_ms());
+            match entry {
+                TimelineEntry::Iteration { iteration, hat, .. } => writeln!(
+                    writer,
+                    "<li class=\"iteration\"><span class=\"time\">{}</span> \
+                     <strong>Iteration {}</strong> &middot; hat <code>{}</code></li>",
+                    time,
+                    iteration,
+                    escape_html(hat)
+                )?,
+                TimelineEntry::Publish {
+                    topic,
+                    payload,
+                    source,
+                    target,
+                    ..
+                } => {
+                    let mut route = String::new();
+                    if let Some(source) = source {
+                        route.push_str(&format!(" from <code>{}</code>", escape_html(source)));
+                    }
+                    if let Some(target) = target {
+                        route.push_str(&format!(" to <code>{}</code>", escape_html(target)));
+                    }
+                    writeln!(
+                        writer,
+                        "<li class=\"publish\"><span class=\"time\">{}</span> \
+                         published <code class=\"topic\">{}</code>{}",
+                        time,
+                        escape_html(topic),
+                        route
+                    )?;
+                    if !payload.trim().is_empty() {
+                        writeln!(
+                            writer,
+                            "<pre class=\"payload\">{}</pre>",
+                            escape_html(&preview(payload))
+                        )?;
+                    }
+                    writeln!(writer, "</li>")?;
+                }
+                TimelineEntry::Termination { reason, .. } => writeln!(
+                    writer,
+                    "<li class=\"termination\"><span class=\"time\">{}</span> \
+                     loop terminated: <strong>{}</strong></li>",
+                    time,
+                    escape_html(reason)
+                )?,
+                TimelineEntry::Output { bytes, .. } => writeln!(
+                    writer,
+                    "<li class=\"output\"><span class=\"time\">{}</span>\
+                     <pre class=\"terminal\">{}</pre></li>",
+                    time,
+                    ansi_to_html(bytes)
+                )?,
+            }
+        }
+        writeln!(writer, "</ol>\n</body>\n</html>")?;
+
+        writer.flush()
+    }
+}
+
+/// Header line of an asciinema v2 cast file.
+#[derive(Serialize)]
+struct CastHeader<'a> {
+    version: u8,
+    width: u16,
+    height: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    timestamp: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    title: Option<&'a str>,
+    env: CastEnv<'a>,
+}
+
+/// Environment recorded in the cast header.
+#[derive(Serialize)]
+struct CastEnv<'a> {
+    #[serde(rename = "TERM")]
+    term: &'a str,
+}
+
+/// Inline stylesheet for HTML reports.
+const HTML_STYLE: &str = "body{font-family:system-ui,sans-serif;margin:2rem;color:#222}\
+table.summary{border-collapse:collapse;margin-bottom:1.5rem}\
+table.summary th{text-align:left;padding:.2rem 1rem .2rem 0;color:#555}\
+ol.timeline{list-style:none;padding:0}\
+ol.timeline li{margin:.4rem 0;padding:.3rem .6rem;border-left:3px solid #ccc}\
+li.iteration{border-color:#3465a4!important;background:#eef3fb}\
+li.publish{border-color:#4e9a06!important}\
+li.termination{border-color:#cc0000!important;background:#fbeeee}\
+.time{color:#888;font-family:monospace;margin-right:.5rem}\
+pre{margin:.3rem 0;white-space:pre-wrap;word-break:break-word}\
+pre.payload{background:#f6f6f6;padding:.4rem}\
+pre.terminal{background:#1e1e1e;color:#ddd;padding:.6rem}\
+.b{font-weight:bold}.c0{color:#555}.c1{color:#ef2929}.c2{color:#8ae234}.c3{color:#fce94f}\
+.c4{color:#729fcf}.c5{color:#ad7fa8}.c6{color:#34e2e2}.c7{color:#eee}";
+
+/// Reads a string field from a JSON object.
+fn json_str(data: &Value, key: &str) -> Option<String> {
+    data.get(key).and_then(Value::as_str).map(str::to_string)
+}
+
+/// Extracts the reason from a `loop.terminate` payload (the line after `## Reason`).
+pub(crate) fn termination_reason_from_payload(payload: &str) -> String {
+    let mut lines = payload.lines().map(str::trim);
+    lines
+        .by_ref()
+        .find(|line| *line == "## Reason")
+        .and_then(|_| lines.find(|line| !line.is_empty()))
+        .unwrap_or_else(|| payload.trim())
+        .to_string()
+}
+
+/// Decodes the bytes of a terminal write record.
+fn decode_write(record: &TimestampedRecord) -> Option<Vec<u8>> {
+    match SessionPlayer::parse_ux_event(&record.record).ok()? {
+        UxEvent::TerminalWrite(write) => write.decode_bytes().ok(),
+        _ => None,
+    }
+}
+
+/// Formats a millisecond offset as `mm:ss.mmm`.
+fn format_offset(offset_ms: u64) -> String {
+    let minutes = offset_ms / 60_000;
+    let seconds = (offset_ms % 60_000) / 1000;
+    let millis = offset_ms % 1000;
+    format!("{:02}:{:02}.{:03}", minutes, seconds, millis)
+}
+
+/// Truncates long payloads for inline display.
+fn preview(payload: &str) -> String {
+    if payload.chars().count() <= PAYLOAD_PREVIEW_CHARS {
+        payload.to_string()
+    } else {
+        let truncated: String = payload.chars().take(PAYLOAD_PREVIEW_CHARS).collect();
+        format!("{}\n…", truncated)
+    }
+}
+
+/// Decodes the complete UTF-8 prefix of `buf`, leaving an incomplete trailing
+/// character in place so the next chunk can finish it.
+///
+/// Invalid sequences are replaced with U+FFFD as `String::from_utf8_lossy` does.
+fn take_complete_utf8(buf: &mut Vec<u8>) -> String {
+    let mut text = String::with_capacity(buf.len());
+    let mut rest = buf.as_slice();
+    loop {
+        match std::str::from_utf8(rest) {
+            Ok(valid) => {
+                text.push_str(valid);
+                rest = &[];
+                break;
+            }
+            Err(e) => {
+                text.push_str(&String::from_utf8_lossy(&rest[..e.valid_up_to()]));
+                match e.error_len() {
+                    // The input ended mid-character: keep the tail for later
+                    None => {
+                        rest = &rest[e.valid_up_to()..];
+                        break;
+                    }
+                    Some(len) => {
+                        text.push(char::REPLACEMENT_CHARACTER);
+                        rest = &rest[e.valid_up_to() + len..];
+                    }
+                }
+            }
+        }
+    }
+    *buf = rest.to_vec();
+    text
+}
+
+/// Escapes text for inclusion in HTML.
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Renders terminal bytes as HTML, translating basic SGR styling to spans.
+///
+/// Bold and the 16 standard foreground colors are kept; all other escape
+/// sequences (cursor movement, OSC, 256-color and truecolor) are dropped.
+/// Unknown SGR parameters leave the current style unchanged.
+fn ansi_to_html(bytes: &[u8]) -> String {
+    let text = String::from_utf8_lossy(bytes);
+    let mut html = String::with_capacity(text.len());
+    let mut bold = false;
+    let mut color: Option<u8> = None;
+    let mut span_open = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            if c != '\r' {
+                html.push_str(&escape_html(c.encode_utf8(&mut [0; 4])));
+            }
+            continue;
+        }
+
+        match chars.peek() {
+            Some('[') => {}
+            Some(']') => {
+                // OSC sequence (titles, hyperlinks): drop everything up to BEL or ST
+                chars.next();
+                while let Some(p) = chars.next() {
+                    if p == '\x07' || (p == '\x1b' && chars.next_if_eq(&'\\').is_some()) {
+                        break;
+                    }
+                }
+                continue;
+            }
+            _ => {
+                // Simple escape sequence: ESC + single char
+                chars.next();
+                continue;
+            }
+        }
+        chars.next();
+
+        let mut params = String::new();
+        let mut final_byte = None;
+        for p in chars.by_ref() {
+            if ('\x40'..='\x7e').contains(&p) {
+                final_byte = Some(p);
+                break;
+            }
+            params.push(p);
+        }
+        if final_byte != Some('m') {
+            continue;
+        }
+
+        let mut codes = params.split(';');
+        while let Some(code) = codes.next() {
+            // An empty parameter (`ESC[m`, `ESC[;1m`) means reset
+            let code = if code.is_empty() {
+                0
+            } else {
+                match code.parse::<u8>() {
+                    Ok(code) => code,
+                    Err(_) => continue,
+                }
+            };
+            match code {
+                0 => {
+                    bold = false;
+                    color = None;
+                }
+                1 => bold = true,
+                22 => bold = false,
+                n @ 30..=37 => color = Some(n - 30),
+                n @ 90..=97 => color = Some(n - 90),
+                39 => color = None,
+                // Extended colors are dropped along with their arguments:
+                // `38;5;n` / `38;2;r;g;b` (and 48 for the background)
+                38 | 48 => {
+                    let args = match codes.next() {
+                        Some("5") => 1,
+                        Some("2") => 3,
+                        _ => 0,
+                    };
+                    if args > 0 {
+                        codes.nth(args - 1);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if span_open {
+            html.push_str("</span>");
+            span_open = false;
+        }
+        if bold || color.is_some() {
+            let mut classes = Vec::new();
+            if bold {
+                classes.push("b".to_string());
+            }
+            if let Some(c) = color {
+                classes.push(format!("c{}", c));
+            }
+            html.push_str(&format!("<span class=\"{}\">", classes.join(" ")));
+            span_open = true;
+        }
+    }
+
+    if span_open {
+        html.push_str("</span>");
+    }
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session_recorder::Record;
+    use ralph_proto::{Event, TerminalResize, TerminalWrite};
+
+    fn line(ts: u64, mut record: Record) -> String {
+        record.ts = ts;
+        serde_json::to_string(&record).unwrap()
+    }
+
+    fn sample_session() -> SessionPlayer {
+        let lines = [
+            line(
+                1000,
+                Record::from_ux_event(&UxEvent::TerminalResize(TerminalResize::new(120, 40, 0))),
+            ),
+            line(1000, Record::meta_iteration(1, 0, "builder")),
+            line(
+                1100,
+                Record::from_ux_event(&UxEvent::TerminalWrite(TerminalWrite::new(
+                    b"\x1b[32mok\x1b[0m <b>",
+                    true,
+                    100,
+                ))),
+            ),
+            line(
+                1200,
+                Record::from_ux_event(&UxEvent::TerminalWrite(TerminalWrite::new(
+                    b" done\r\n",
+                    true,
+                    200,
+                ))),
+            ),
+            line(
+                1300,
+                Record::from_bus_event(
+                    &Event::new("build.done", "tests: pass").with_source("builder"),
+                ),
+            ),
+            line(
+                1400,
+                Record::from_ux_event(&UxEvent::TerminalResize(TerminalResize::new(100, 30, 400))),
+            ),
+            line(
+                2500,
+                Record::from_bus_event(&Event::new(
+                    "loop.terminate",
+                    "## Reason\ncompleted\n\n## Status\nAll tasks completed successfully.",
+                )),
+            ),
+        ];
+        SessionPlayer::from_bytes(lines.join("\n").as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_asciicast_header_uses_leading_resize() {
+        let player = sample_session();
+        let mut output = Vec::new();
+        SessionExporter::new(&player)
+            .with_config(ExportConfig::default().with_title("demo"))
+            .write_asciicast(&mut output)
+            .unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let header: Value = serde_json::from_str(output.lines().next().unwrap()).unwrap();
+        assert_eq!(header["version"], 2);
+        assert_eq!(header["width"], 120);
+        assert_eq!(header["height"], 40);
+        assert_eq!(header["title"], "demo");
+        assert_eq!(header["timestamp"], 1);
+    }
+
+    #[test]
+    fn test_asciicast_events() {
+        let player = sample_session();
+        let mut output = Vec::new();
+        SessionExporter::new(&player)
+            .write_asciicast(&mut output)
+            .unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let events: Vec<Value> = output
+            .lines()
+            .skip(1)
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+
+        assert_eq!(events.len(), 3);
+        assert_eq!(
+            events[0],
+            serde_json::json!([0.1, "o", "\x1b[32mok\x1b[0m <b>"])
+        );
+        assert_eq!(events[1], serde_json::json!([0.2, "o", " done\r\n"]));
+        assert_eq!(events[2], serde_json::json!([0.4, "r", "100x30"]));
+    }
+
+    #[test]
+    fn test_asciicast_header_uses_last_leading_resize() {
+        let lines = [
+            line(
+                1000,
+                Record::from_ux_event(&UxEvent::TerminalResize(TerminalResize::new(80, 24, 0))),
+            ),
+            line(
+                1050,
+                Record::from_ux_event(&UxEvent::TerminalResize(TerminalResize::new(132, 50, 50))),
+            ),
+            line(
+                1100,
+                Record::from_ux_event(&UxEvent::TerminalWrite(TerminalWrite::new(
+                    b"hi", true, 100,
+                ))),
+            ),
+        ];
+        let player = SessionPlayer::from_bytes(lines.join("\n").as_bytes()).unwrap();
+        let mut output = Vec::new();
+        SessionExporter::new(&player)
+            .write_asciicast(&mut output)
+            .unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let mut lines = output.lines();
+        let header: Value = serde_json::from_str(lines.next().unwrap()).unwrap();
+        assert_eq!(header["width"], 132);
+        assert_eq!(header["height"], 50);
+        // Leading resizes are folded into the header, not replayed
+        assert_eq!(lines.count(), 1);
+    }
+
+    #[test]
+    fn test_asciicast_joins_characters_split_across_writes() {
+        // "é" is 0xC3 0xA9; the PTY read boundary falls between the two bytes
+        let lines = [
+            line(
+                1000,
+                Record::from_ux_event(&UxEvent::TerminalWrite(TerminalWrite::new(
+                    b"caf\xc3", true, 0,
+                ))),
+            ),
+            line(
+                1100,
+                Record::from_ux_event(&UxEvent::TerminalWrite(TerminalWrite::new(
+                    b"\xa9!", true, 100,
+                ))),
+            ),
+        ];
+        let player = SessionPlayer::from_bytes(lines.join("\n").as_bytes()).unwrap();
+        let mut output = Vec::new();
+        SessionExporter::new(&player)
+            .write_asciicast(&mut output)
+            .unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let frames: Vec<Value> = output
+            .lines()
+            .skip(1)
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0][2], "caf");
+        assert_eq!(frames[1][2], "\u{e9}!");
+        assert!(!output.contains('\u{fffd}'));
+    }
+
+    #[test]
+    fn test_take_complete_utf8_replaces_invalid_bytes_but_keeps_incomplete_tail() {
+        let mut buf = b"a\xffb\xe2\x82".to_vec();
+        assert_eq!(take_complete_utf8(&mut buf), "a\u{fffd}b");
+        assert_eq!(buf, b"\xe2\x82");
+        buf.push(0xac);
+        assert_eq!(take_complete_utf8(&mut buf), "\u{20ac}");
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn test_asciicast_default_size_without_resize() {
+        let jsonl = r#"{"ts":1000,"event":"ux.terminal.write","data":{"bytes":"SGVsbG8=","stdout":true,"offset_ms":0}}"#;
+        let player = SessionPlayer::from_bytes(jsonl.as_bytes()).unwrap();
+        let mut output = Vec::new();
+        SessionExporter::new(&player)
+            .write_asciicast(&mut output)
+            .unwrap();
+
+        let output = String::from_utf8(output).unwrap();
+        let header: Value = serde_json::from_str(output.lines().next().unwrap()).unwrap();
+        assert_eq!(header["width"], 80);
+        assert_eq!(header["height"], 24);
+    }
+
+    #[test]
+    fn test_timeline_interleaves_events_and_output() {
+        let player = sample_session();
+        let timeline = SessionExporter::new(&player).timeline();
+
+        assert_eq!(timeline.len(), 4);
+        assert_eq!(
+            timeline[0],
+            TimelineEntry::Iteration {
+                offset_ms: 0,
+                iteration: 1,
+                hat: "builder".to_string(),
+            }
+        );
+        assert_eq!(
+            timeline[1],
+            TimelineEntry::Output {
+                offset_ms: 100,
+                bytes: b"\x1b[32mok\x1b[0m <b> done\r\n".to_vec(),
+            }
+        );
+        assert!(matches!(
+            &timeline[2],
+            TimelineEntry::Publish { topic, source, .. }
+                if topic == "build.done" && source.as_deref() == Some("builder")
+        ));
+        assert_eq!(
+            timeline[3],
+            TimelineEntry::Termination {
+                offset_ms: 1500,
+                reason: "completed".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_html_report_is_escaped_and_colored() {
+        let player = sample_session();
+        let mut output = Vec::new();
+        SessionExporter::new(&player)
+            .with_config(ExportConfig::default().with_title("Run <1>"))
+            .write_html(&mut output)
+            .unwrap();
+
+        let html = String::from_utf8(output).unwrap();
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Run &lt;1&gt;</title>"));
+        assert!(html.contains("<span class=\"c2\">ok</span> &lt;b&gt; done"));
+        assert!(html.contains("<code class=\"topic\">build.done</code> from <code>builder</code>"));
+        assert!(html.contains("loop terminated: <strong>completed</strong>"));
+        assert!(html.contains("<tr><th>Iterations</th><td>1</td></tr>"));
+        assert!(!html.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_ansi_to_html_bold_and_reset() {
+        assert_eq!(
+            ansi_to_html(b"\x1b[1;31merr\x1b[0m plain"),
+            "<span class=\"b c1\">err</span> plain"
+        );
+        assert_eq!(ansi_to_html(b"\x1b[2Kclear"), "clear");
+    }
+
+    #[test]
+    fn test_ansi_to_html_keeps_style_across_extended_and_unknown_codes() {
+        // Truecolor and 256-color arguments must not be read as SGR codes
+        // (the 0 in `38;2;0;0;0` is not a reset)
+        assert_eq!(
+            ansi_to_html(b"\x1b[1;38;2;0;0;0mbold\x1b[48;5;0m still\x1b[0m"),
+            "<span class=\"b\">bold</span><span class=\"b\"> still</span>"
+        );
+        // Unparsable parameters are skipped rather than treated as a reset
+        assert_eq!(
+            ansi_to_html(b"\x1b[32mok\x1b[999;4:3;1m!"),
+            "<span class=\"c2\">ok</span><span class=\"b c2\">!</span>"
+        );
+    }
+
+    #[test]
+    fn test_ansi_to_html_drops_osc_sequences() {
+        // OSC 8 hyperlink terminated by ST, then a BEL-terminated window title
+        assert_eq!(
+            ansi_to_html(
+                b"\x1b]8;;https://example.com\x1b\\link\x1b]8;;\x1b\\ \x1b]0;title\x07done"
+            ),
+            "link done"
+        );
+    }
+
+    #[test]
+    fn test_format_offset() {
+        assert_eq!(format_offset(0), "00:00.000");
+        assert_eq!(format_offset(61_234), "01:01.234");
+    }
+}
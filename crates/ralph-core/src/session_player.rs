@@ -271,9 +271,14 @@ impl SessionPlayer {
     }
 
     /// Parses a Record's data field as a UxEvent.
-    fn parse_ux_event(record: &Record) -> Result<UxEvent, serde_json::Error> {
-        // The record stores data without the event tag, so we need to reconstruct
-        // the tagged format for UxEvent deserialization
+    pub(crate) fn parse_ux_event(record: &Record) -> Result<UxEvent, serde_json::Error> {
+        // `SessionRecorder` stores the full tagged UxEvent in `data`
+        if record.data.get("event").is_some() && record.data.get("data").is_some() {
+            return serde_json::from_value(record.data.clone());
+        }
+
+        // Hand-written fixtures store data without the event tag, so we need to
+        // reconstruct the tagged format for UxEvent deserialization
         let tagged = serde_json::json!({
             "event": record.event,
             "data": record.data,
@@ -322,7 +327,7 @@ impl SessionPlayer {
 ///
 /// Handles CSI sequences (\x1b[...m), OSC sequences (\x1b]...\x07),
 /// and simple escape sequences (\x1b followed by a single char).
-fn strip_ansi(bytes: &[u8]) -> Vec<u8> {
+pub(crate) fn strip_ansi(bytes: &[u8]) -> Vec<u8> {
     let mut result = Vec::with_capacity(bytes.len());
     let mut i = 0;
 
@@ -515,6 +520,15 @@ mod tests {
         assert_eq!(config.event_filter, vec!["ux."]);
     }
 
+    #[test]
+    fn test_collect_output_from_recorder_records() {
+        let ux_event = UxEvent::TerminalWrite(TerminalWrite::new(b"Recorded", true, 0));
+        let line = serde_json::to_string(&Record::from_ux_event(&ux_event)).unwrap();
+        let player = SessionPlayer::from_bytes(line.as_bytes()).unwrap();
+
+        assert_eq!(player.collect_terminal_output().unwrap(), "Recorded");
+    }
+
     #[test]
     fn test_empty_input() {
         let player = SessionPlayer::from_bytes(b"").unwrap();
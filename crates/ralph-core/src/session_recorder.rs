@@ -4,6 +4,7 @@
 //! and UX captures (terminal output) into a unified JSONL format for replay
 //! and analysis.
 
+use crate::event_reader::ParseResult;
 use ralph_proto::{Event, UxEvent};
 use serde::{Deserialize, Serialize};
 use std::io::{self, Write};
@@ -82,6 +83,58 @@ impl Record {
         )
     }
 
+    /// Creates a metadata record for the raw backend output of an iteration.
+    ///
+    /// Together with `meta_agent_events`, this lets `LoopReplayer` substitute
+    /// the recorded output when re-driving the event loop.
+    pub fn meta_agent_output(iteration: u32, hat: &str, output: &str, success: bool) -> Self {
+        Self::new(
+            "_meta.agent_output",
+            serde_json::json!({
+                "n": iteration,
+                "hat": hat,
+                "output": output,
+                "success": success,
+            }),
+        )
+    }
+
+    /// Creates a metadata record for the events an agent wrote to the events
+    /// JSONL during an iteration (before validation).
+    pub fn meta_agent_events(iteration: u32, events: &ParseResult) -> Self {
+        Self::new(
+            "_meta.agent_events",
+            serde_json::json!({
+                "n": iteration,
+                "events": events,
+            }),
+        )
+    }
+
+    /// Creates a metadata record for human approval decisions read after
+    /// `iteration` completed.
+    pub fn meta_approval_decisions(iteration: u32, decisions: &[String]) -> Self {
+        Self::new(
+            "_meta.approval_decisions",
+            serde_json::json!({
+                "n": iteration,
+                "decisions": decisions,
+            }),
+        )
+    }
+
+    /// Creates a metadata record for the files a hat with a `write_scope`
+    /// changed during an iteration, so a replay can check them again.
+    pub fn meta_changed_files(iteration: u32, files: &[String]) -> Self {
+        Self::new(
+            "_meta.changed_files",
+            serde_json::json!({
+                "n": iteration,
+                "files": files,
+            }),
+        )
+    }
+
     /// Creates a metadata record for termination.
     pub fn meta_termination(
         reason: &str,
@@ -188,12 +241,19 @@ impl<W: Write> SessionRecorder<W> {
     fn write_record(&self, record: &Record) {
         if let Ok(mut writer) = self.writer.lock() {
             // Ignore write errors - recording should not interrupt execution
-            if let Ok(json) = serde_json::to_string(record) {
+            if let Ok(json) = crate::redaction::redacted_json(record) {
                 let _ = writeln!(writer, "{}", json);
             }
         }
     }
 
+    /// Consumes the recorder and returns the underlying writer.
+    pub fn into_inner(self) -> W {
+        self.writer
+            .into_inner()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
     /// Flushes the underlying writer.
     pub fn flush(&self) -> io::Result<()> {
         self.writer
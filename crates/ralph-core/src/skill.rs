@@ -3,6 +3,7 @@
 //! Skills are markdown documents with YAML frontmatter that provide knowledge
 //! and tool instructions to agents during orchestration loops.
 
+use crate::skill_triggers::SkillTriggers;
 use serde::Deserialize;
 use std::path::PathBuf;
 
@@ -25,6 +26,10 @@ pub struct SkillEntry {
     pub tags: Vec<String>,
     /// Whether to inject full content into every prompt (not just index entry).
     pub auto_inject: bool,
+    /// Executables that must be on PATH for the skill to work.
+    pub requires_tools: Vec<String>,
+    /// Conditions that inject the full skill into a prompt.
+    pub triggers: SkillTriggers,
 }
 
 /// Where a skill was loaded from.
@@ -34,6 +39,15 @@ pub enum SkillSource {
     BuiltIn,
     /// Loaded from a filesystem path.
     File(PathBuf),
+    /// Loaded from an installed skill pack.
+    Pack {
+        /// Pack name from its manifest.
+        name: String,
+        /// Pack version from its manifest.
+        version: String,
+        /// Path of the skill file inside `.ralph/skills/<pack>/`.
+        path: PathBuf,
+    },
 }
 
 /// Parsed YAML frontmatter from a skill file.
@@ -47,6 +61,10 @@ pub struct SkillFrontmatter {
     pub backends: Vec<String>,
     #[serde(default)]
     pub tags: Vec<String>,
+    #[serde(default)]
+    pub requires_tools: Vec<String>,
+    #[serde(default)]
+    pub triggers: SkillTriggers,
 }
 
 /// Parse YAML frontmatter from a markdown document.
@@ -107,6 +125,10 @@ description: A useful skill
 hats: [builder, reviewer]
 backends: [claude, gemini]
 tags: [testing, tdd]
+requires_tools: [cargo, rg]
+triggers:
+  files: ['*.rs']
+  keywords: [clippy]
 ---
 
 # My Skill
@@ -120,6 +142,10 @@ Body content here.
         assert_eq!(fm.hats, vec!["builder", "reviewer"]);
         assert_eq!(fm.backends, vec!["claude", "gemini"]);
         assert_eq!(fm.tags, vec!["testing", "tdd"]);
+        assert_eq!(fm.requires_tools, vec!["cargo", "rg"]);
+        assert_eq!(fm.triggers.files, vec!["*.rs"]);
+        assert_eq!(fm.triggers.keywords, vec!["clippy"]);
+        assert!(fm.triggers.topics.is_empty());
         assert!(body.contains("# My Skill"));
         assert!(body.contains("Body content here."));
         // Frontmatter delimiters should be stripped
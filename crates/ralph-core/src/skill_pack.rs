@@ -0,0 +1,606 @@
+//! Skill packs: versioned skill bundles vendored into `.ralph/skills/`.
+//!
+//! A pack is a directory (local or in a git repository) with a
+//! `skill-pack.yml` manifest:
+//!
+//! ```yaml
+//! name: rust-tools
+//! version: 1.2.0
+//! description: Cargo and clippy workflows
+//! skills:                # paths relative to the pack root
+//!   - cargo.md
+//!   - clippy/SKILL.md
+//! requires_tools: [cargo] # checked by preflight for every skill in the pack
+//! ```
+//!
+//! [`install`] copies a pack into `.ralph/skills/<name>/` and records where it
+//! came from in `.ralph/skills/skills.lock`. Installed packs are discovered by
+//! [`SkillRegistry::from_config`](crate::SkillRegistry::from_config).
+
+use crate::skill::parse_frontmatter;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::warn;
+
+/// Manifest file name at the root of every pack.
+pub const MANIFEST_FILE: &str = "skill-pack.yml";
+
+/// Directory (relative to the workspace root) packs are installed into.
+pub const PACKS_DIR: &str = ".ralph/skills";
+
+/// Lockfile name inside [`PACKS_DIR`].
+pub const LOCK_FILE: &str = "skills.lock";
+
+/// Errors that can occur while loading or installing skill packs.
+#[derive(Debug, thiserror::Error)]
+pub enum SkillPackError {
+    /// The directory has no manifest.
+    #[error("No skill-pack.yml found in {}", .0.display())]
+    MissingManifest(PathBuf),
+
+    /// The manifest could not be parsed or is incomplete.
+    #[error("Invalid skill pack manifest {}: {reason}", path.display())]
+    InvalidManifest { path: PathBuf, reason: String },
+
+    /// A skill listed in the manifest does not exist.
+    #[error("Skill '{}' listed by pack '{pack}' does not exist", skill.display())]
+    MissingSkill { pack: String, skill: PathBuf },
+
+    /// Cloning a git source failed.
+    #[error("Failed to fetch skill pack from {url}: {reason}")]
+    Git { url: String, reason: String },
+
+    /// The lockfile could not be parsed.
+    #[error("Invalid skill lockfile {}: {reason}", path.display())]
+    InvalidLock { path: PathBuf, reason: String },
+
+    /// IO error.
+    #[error("IO error: {0}")]
+    Io(#[from] io::Error),
+}
+
+/// Parsed `skill-pack.yml`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SkillPackManifest {
+    /// Pack name; also its directory name under `.ralph/skills/`.
+    pub name: String,
+    /// Pack version (free-form, typically semver).
+    pub version: String,
+    /// Human-readable description.
+    #[serde(default)]
+    pub description: String,
+    /// Skill files relative to the pack root (`*.md` or `<dir>/SKILL.md`).
+    pub skills: Vec<PathBuf>,
+    /// Executables every skill in the pack needs on PATH.
+    #[serde(default)]
+    pub requires_tools: Vec<String>,
+}
+
+/// A pack directory with a validated manifest.
+#[derive(Debug, Clone)]
+pub struct SkillPack {
+    /// Pack root directory.
+    pub root: PathBuf,
+    /// The pack's manifest.
+    pub manifest: SkillPackManifest,
+}
+
+impl SkillPack {
+    /// Loads and validates the pack at `root`.
+    pub fn load(root: &Path) -> Result<Self, SkillPackError> {
+        let manifest_path = root.join(MANIFEST_FILE);
+        if !manifest_path.is_file() {
+            return Err(SkillPackError::MissingManifest(root.to_path_buf()));
+        }
+
+        let invalid = |reason: String| SkillPackError::InvalidManifest {
+            path: manifest_path.clone(),
+            reason,
+        };
+        let content = fs::read_to_string(&manifest_path)?;
+        let manifest: SkillPackManifest =
+            serde_yaml::from_str(&content).map_err(|e| invalid(e.to_string()))?;
+
+        let valid_name = !manifest.name.is_empty()
+            && manifest
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+            && !manifest.name.starts_with('.');
+        if !valid_name {
+            return Err(invalid(format!(
+                "name '{}' must use only letters, digits, '-', '_' and '.'",
+                manifest.name
+            )));
+        }
+        if manifest.version.trim().is_empty() {
+            return Err(invalid("version must not be empty".to_string()));
+        }
+        if manifest.skills.is_empty() {
+            return Err(invalid(
+                "skills must list at least one skill file".to_string(),
+            ));
+        }
+
+        for skill in &manifest.skills {
+            let escapes = skill.is_absolute()
+                || skill
+                    .components()
+                    .any(|c| matches!(c, std::path::Component::ParentDir));
+            if escapes || !root.join(skill).is_file() {
+                return Err(SkillPackError::MissingSkill {
+                    pack: manifest.name.clone(),
+                    skill: skill.clone(),
+                });
+            }
+        }
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            manifest,
+        })
+    }
+
+    /// Absolute paths of the pack's skill files with their fallback names.
+    ///
+    /// The fallback name follows the same rules as skill directories: the
+    /// parent directory for `SKILL.md`, the file stem otherwise.
+    pub fn skill_files(&self) -> Vec<(PathBuf, String)> {
+        self.manifest
+            .skills
+            .iter()
+            .map(|relative| {
+                let path = self.root.join(relative);
+                let fallback = if path.file_name().is_some_and(|n| n == "SKILL.md") {
+                    relative.parent().and_then(Path::file_name)
+                } else {
+                    relative.file_stem()
+                };
+                let fallback = fallback
+                    .and_then(|s| s.to_str())
+                    .unwrap_or("unknown")
+                    .to_string();
+                (path, fallback)
+            })
+            .collect()
+    }
+
+    /// Skill names the pack provides (frontmatter `name`, else the fallback).
+    pub fn skill_names(&self) -> Vec<String> {
+        self.skill_files()
+            .into_iter()
+            .map(|(path, fallback)| {
+                fs::read_to_string(&path)
+                    .ok()
+                    .and_then(|raw| parse_frontmatter(&raw).0)
+                    .and_then(|fm| fm.name)
+                    .unwrap_or(fallback)
+            })
+            .collect()
+    }
+}
+
+/// Where to install a pack from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PackSource {
+    /// A local directory.
+    Path(PathBuf),
+    /// A git repository, optionally pinned with `url#rev`.
+    Git { url: String, rev: Option<String> },
+}
+
+impl PackSource {
+    /// Parses a CLI argument: git URLs (`https://`, `ssh://`, `git@`, `*.git`)
+    /// become [`PackSource::Git`], anything else is a local path.
+    pub fn parse(input: &str) -> Self {
+        let (base, rev) = match input.split_once('#') {
+            Some((base, rev)) if !rev.is_empty() => (base, Some(rev.to_string())),
+            _ => (input, None),
+        };
+        let is_git = ["https://", "http://", "ssh://", "git://", "git@"]
+            .iter()
+            .any(|prefix| base.starts_with(prefix))
+            || Path::new(base)
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("git"));
+
+        if is_git && !Path::new(base).exists() {
+            Self::Git {
+                url: base.to_string(),
+                rev,
+            }
+        } else {
+            Self::Path(PathBuf::from(input))
+        }
+    }
+}
+
+/// Lockfile entry for an installed pack.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedPack {
+    /// Installed version.
+    pub version: String,
+    /// Local path or git URL the pack was installed from.
+    pub source: String,
+    /// Resolved git commit, for git sources.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rev: Option<String>,
+    /// Skill names the pack provides.
+    #[serde(default)]
+    pub skills: Vec<String>,
+    /// Tools the pack requires.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub requires_tools: Vec<String>,
+}
+
+/// `.ralph/skills/skills.lock`: provenance of every installed pack.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SkillLock {
+    /// Installed packs keyed by name.
+    #[serde(default)]
+    pub packs: BTreeMap<String, LockedPack>,
+}
+
+impl SkillLock {
+    /// Lockfile path for a workspace.
+    pub fn path(workspace_root: &Path) -> PathBuf {
+        workspace_root.join(PACKS_DIR).join(LOCK_FILE)
+    }
+
+    /// Loads the lockfile, returning an empty lock when none exists.
+    pub fn load(workspace_root: &Path) -> Result<Self, SkillPackError> {
+        let path = Self::path(workspace_root);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = fs::read_to_string(&path)?;
+        serde_yaml::from_str(&content).map_err(|e| SkillPackError::InvalidLock {
+            path,
+            reason: e.to_string(),
+        })
+    }
+
+    /// Writes the lockfile.
+    pub fn save(&self, workspace_root: &Path) -> Result<(), SkillPackError> {
+        let path = Self::path(workspace_root);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let yaml = serde_yaml::to_string(self).map_err(|e| SkillPackError::InvalidLock {
+            path: path.clone(),
+            reason: e.to_string(),
+        })?;
+        fs::write(
+            &path,
+            format!("# Generated by `ralph tools skill install`. Do not edit.\n{yaml}"),
+        )?;
+        Ok(())
+    }
+}
+
+/// Installs a pack into `<workspace_root>/.ralph/skills/<name>/`.
+///
+/// An already installed pack with the same name is replaced. Returns the pack
+/// name and its new lockfile entry.
+pub fn install(
+    source: &PackSource,
+    workspace_root: &Path,
+) -> Result<(String, LockedPack), SkillPackError> {
+    let packs_dir = workspace_root.join(PACKS_DIR);
+    fs::create_dir_all(&packs_dir)?;
+
+    match source {
+        PackSource::Path(path) => {
+            let path = if path.is_relative() {
+                workspace_root.join(path)
+            } else {
+                path.clone()
+            };
+            let source = fs::canonicalize(&path).unwrap_or(path);
+            install_from_dir(&source, workspace_root, source.display().to_string(), None)
+        }
+        PackSource::Git { url, rev } => {
+            let checkout = packs_dir.join(format!(".fetch-{}", std::process::id()));
+            let result = clone(url, rev.as_deref(), &checkout).and_then(|commit| {
+                install_from_dir(&checkout, workspace_root, url.clone(), Some(commit))
+            });
+            let _ = fs::remove_dir_all(&checkout);
+            result
+        }
+    }
+}
+
+/// Removes an installed pack and its lockfile entry. Returns `false` if the
+/// pack was not installed.
+pub fn uninstall(name: &str, workspace_root: &Path) -> Result<bool, SkillPackError> {
+    let mut lock = SkillLock::load(workspace_root)?;
+    let dir = workspace_root.join(PACKS_DIR).join(name);
+    let was_locked = lock.packs.remove(name).is_some();
+    let had_dir = dir.join(MANIFEST_FILE).is_file();
+    if had_dir {
+        fs::remove_dir_all(&dir)?;
+    }
+    if was_locked {
+        lock.save(workspace_root)?;
+    }
+    Ok(was_locked || had_dir)
+}
+
+/// Returns every valid pack installed in the workspace, sorted by name.
+///
+/// Invalid packs are skipped with a warning.
+pub fn installed_packs(workspace_root: &Path) -> Vec<SkillPack> {
+    let Ok(entries) = fs::read_dir(workspace_root.join(PACKS_DIR)) else {
+        return Vec::new();
+    };
+
+    let mut packs: Vec<SkillPack> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.join(MANIFEST_FILE).is_file())
+        .filter_map(|path| match SkillPack::load(&path) {
+            Ok(pack) => Some(pack),
+            Err(e) => {
+                warn!("Skipping installed skill pack {}: {}", path.display(), e);
+                None
+            }
+        })
+        .collect();
+    packs.sort_by(|a, b| a.manifest.name.cmp(&b.manifest.name));
+    packs
+}
+
+fn install_from_dir(
+    source_dir: &Path,
+    workspace_root: &Path,
+    source: String,
+    rev: Option<String>,
+) -> Result<(String, LockedPack), SkillPackError> {
+    let pack = SkillPack::load(source_dir)?;
+    let name = pack.manifest.name.clone();
+
+    let dest = workspace_root.join(PACKS_DIR).join(&name);
+    if dest.exists() {
+        fs::remove_dir_all(&dest)?;
+    }
+    copy_pack(source_dir, &dest)?;
+
+    let locked = LockedPack {
+        version: pack.manifest.version.clone(),
+        source,
+        rev,
+        skills: pack.skill_names(),
+        requires_tools: pack.manifest.requires_tools.clone(),
+    };
+
+    let mut lock = SkillLock::load(workspace_root)?;
+    lock.packs.insert(name.clone(), locked.clone());
+    lock.save(workspace_root)?;
+
+    Ok((name, locked))
+}
+
+/// Recursively copies a pack, skipping VCS metadata.
+fn copy_pack(src: &Path, dst: &Path) -> io::Result<()> {
+    fs::create_dir_all(dst)?;
+
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        if entry.file_name() == ".git" {
+            continue;
+        }
+        let src_path = entry.path();
+        let dst_path = dst.join(entry.file_name());
+
+        if src_path.is_dir() {
+            copy_pack(&src_path, &dst_path)?;
+        } else {
+            fs::copy(&src_path, &dst_path)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Clones `url` into `dest` (checking out `rev` if given) and returns the commit SHA.
+fn clone(url: &str, rev: Option<&str>, dest: &Path) -> Result<String, SkillPackError> {
+    let git = |args: &[&str], cwd: Option<&Path>| -> Result<String, SkillPackError> {
+        let mut command = Command::new("git");
+        command.args(args);
+        if let Some(cwd) = cwd {
+            command.current_dir(cwd);
+        }
+        let output = command.output().map_err(|e| SkillPackError::Git {
+            url: url.to_string(),
+            reason: e.to_string(),
+        })?;
+        if !output.status.success() {
+            return Err(SkillPackError::Git {
+                url: url.to_string(),
+                reason: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+            });
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    let dest_str = dest.to_string_lossy();
+    match rev {
+        None => git(&["clone", "--quiet", "--depth", "1", url, &dest_str], None)?,
+        Some(rev) => {
+            git(&["clone", "--quiet", url, &dest_str], None)?;
+            git(&["checkout", "--quiet", rev], Some(dest))?
+        }
+    };
+    git(&["rev-parse", "HEAD"], Some(dest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_pack(dir: &Path, version: &str) {
+        fs::create_dir_all(dir.join("clippy")).unwrap();
+        fs::write(
+            dir.join(MANIFEST_FILE),
+            format!(
+                "name: rust-tools\nversion: {version}\nskills: [cargo.md, clippy/SKILL.md]\nrequires_tools: [cargo]\n"
+            ),
+        )
+        .unwrap();
+        fs::write(
+            dir.join("cargo.md"),
+            "---\nname: cargo-expert\ndescription: Cargo tips\n---\nUse cargo.\n",
+        )
+        .unwrap();
+        fs::write(dir.join("clippy/SKILL.md"), "Run clippy.\n").unwrap();
+        fs::create_dir_all(dir.join(".git")).unwrap();
+        fs::write(dir.join(".git/HEAD"), "ref: refs/heads/main\n").unwrap();
+    }
+
+    #[test]
+    fn load_validates_manifest() {
+        let temp = TempDir::new().unwrap();
+        write_pack(temp.path(), "1.0.0");
+
+        let pack = SkillPack::load(temp.path()).unwrap();
+        assert_eq!(pack.manifest.name, "rust-tools");
+        assert_eq!(pack.skill_names(), vec!["cargo-expert", "clippy"]);
+
+        fs::write(
+            temp.path().join(MANIFEST_FILE),
+            "name: rust-tools\nversion: 1.0.0\nskills: [missing.md]\n",
+        )
+        .unwrap();
+        let err = SkillPack::load(temp.path()).unwrap_err();
+        assert!(matches!(err, SkillPackError::MissingSkill { .. }), "{err}");
+
+        fs::write(
+            temp.path().join(MANIFEST_FILE),
+            "name: ../evil\nversion: 1.0.0\nskills: [cargo.md]\n",
+        )
+        .unwrap();
+        let err = SkillPack::load(temp.path()).unwrap_err();
+        assert!(err.to_string().contains("must use only letters"), "{err}");
+
+        let err = SkillPack::load(&temp.path().join("clippy")).unwrap_err();
+        assert!(matches!(err, SkillPackError::MissingManifest(_)));
+    }
+
+    #[test]
+    fn install_vendors_pack_and_writes_lock() {
+        let source = TempDir::new().unwrap();
+        let workspace = TempDir::new().unwrap();
+        write_pack(source.path(), "1.0.0");
+
+        let (name, locked) = install(
+            &PackSource::Path(source.path().to_path_buf()),
+            workspace.path(),
+        )
+        .unwrap();
+
+        assert_eq!(name, "rust-tools");
+        assert_eq!(locked.version, "1.0.0");
+        assert_eq!(locked.skills, vec!["cargo-expert", "clippy"]);
+        let dest = workspace.path().join(PACKS_DIR).join("rust-tools");
+        assert!(dest.join("clippy/SKILL.md").is_file());
+        assert!(!dest.join(".git").exists(), "VCS metadata is not vendored");
+
+        let lock = SkillLock::load(workspace.path()).unwrap();
+        assert_eq!(lock.packs["rust-tools"], locked);
+        assert_eq!(installed_packs(workspace.path()).len(), 1);
+
+        // Reinstalling upgrades in place
+        write_pack(source.path(), "1.1.0");
+        fs::remove_dir_all(source.path().join("clippy")).unwrap();
+        fs::write(
+            source.path().join(MANIFEST_FILE),
+            "name: rust-tools\nversion: 1.1.0\nskills: [cargo.md]\n",
+        )
+        .unwrap();
+        install(
+            &PackSource::Path(source.path().to_path_buf()),
+            workspace.path(),
+        )
+        .unwrap();
+        assert!(!dest.join("clippy").exists());
+        let lock = SkillLock::load(workspace.path()).unwrap();
+        assert_eq!(lock.packs["rust-tools"].version, "1.1.0");
+
+        assert!(uninstall("rust-tools", workspace.path()).unwrap());
+        assert!(!dest.exists());
+        assert!(SkillLock::load(workspace.path()).unwrap().packs.is_empty());
+        assert!(!uninstall("rust-tools", workspace.path()).unwrap());
+    }
+
+    #[test]
+    fn install_from_git_records_commit() {
+        let repo = TempDir::new().unwrap();
+        let workspace = TempDir::new().unwrap();
+        write_pack(repo.path(), "2.0.0");
+        fs::remove_dir_all(repo.path().join(".git")).unwrap();
+
+        let git = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(args)
+                .current_dir(repo.path())
+                .output()
+                .unwrap();
+            assert!(status.status.success(), "git {args:?} failed");
+            String::from_utf8_lossy(&status.stdout).trim().to_string()
+        };
+        git(&["init", "--quiet"]);
+        git(&["add", "."]);
+        git(&[
+            "-c",
+            "user.name=test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "--quiet",
+            "-m",
+            "pack",
+        ]);
+        let head = git(&["rev-parse", "HEAD"]);
+
+        let url = format!("file://{}", repo.path().display());
+        let source = PackSource::Git {
+            url: url.clone(),
+            rev: None,
+        };
+        let (_, locked) = install(&source, workspace.path()).unwrap();
+
+        assert_eq!(locked.source, url);
+        assert_eq!(locked.rev.as_deref(), Some(head.as_str()));
+        let packs_dir = workspace.path().join(PACKS_DIR);
+        let leftovers: Vec<_> = fs::read_dir(&packs_dir)
+            .unwrap()
+            .flatten()
+            .filter(|e| e.file_name().to_string_lossy().starts_with(".fetch-"))
+            .collect();
+        assert!(leftovers.is_empty(), "temporary checkout is removed");
+    }
+
+    #[test]
+    fn parse_distinguishes_git_urls_from_paths() {
+        assert_eq!(
+            PackSource::parse("https://github.com/acme/skills.git#v1.2.0"),
+            PackSource::Git {
+                url: "https://github.com/acme/skills.git".to_string(),
+                rev: Some("v1.2.0".to_string()),
+            }
+        );
+        assert!(matches!(
+            PackSource::parse("git@github.com:acme/skills"),
+            PackSource::Git { rev: None, .. }
+        ));
+        assert_eq!(
+            PackSource::parse("./packs/rust"),
+            PackSource::Path(PathBuf::from("./packs/rust"))
+        );
+    }
+}
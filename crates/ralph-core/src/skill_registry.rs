@@ -1,10 +1,13 @@
 //! Skill registry for discovering, storing, and providing access to skills.
 //!
-//! The registry manages both built-in skills (compiled into the binary) and
-//! user-defined skills (discovered from configured directories).
+//! The registry manages built-in skills (compiled into the binary), skills from
+//! installed skill packs (`.ralph/skills/`), and user-defined skills (discovered
+//! from configured directories).
 
 use crate::config::{SkillOverride, SkillsConfig};
 use crate::skill::{SkillEntry, SkillSource, parse_frontmatter};
+use crate::skill_pack;
+use crate::skill_triggers::TriggerContext;
 use anyhow::Result;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
@@ -16,6 +19,15 @@ const RALPH_TOOLS_SKILL_RAW: &str = include_str!("../data/ralph-tools.md");
 /// Built-in RObot interaction skill content.
 const ROBOT_INTERACTION_SKILL_RAW: &str = include_str!("../data/robot-interaction-skill.md");
 
+/// A skill selected by its `triggers:` for the current iteration.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TriggeredSkill {
+    /// Skill name.
+    pub name: String,
+    /// Which trigger matched (e.g. `keyword 'postgres'`).
+    pub reason: String,
+}
+
 /// Registry of all available skills for the current loop.
 pub struct SkillRegistry {
     /// All skills indexed by name.
@@ -52,6 +64,8 @@ impl SkillRegistry {
                 backends: fm.backends,
                 tags: fm.tags,
                 auto_inject: false, // Built-ins default to false; overridden by config
+                requires_tools: fm.requires_tools,
+                triggers: fm.triggers,
             },
         );
 
@@ -113,8 +127,40 @@ impl SkillRegistry {
         Ok(())
     }
 
+    /// Register skills from every pack installed in `.ralph/skills/`.
+    ///
+    /// Pack-level `requires_tools` are added to each skill's own requirements.
+    pub fn scan_installed_packs(&mut self, workspace_root: &Path) -> Result<()> {
+        for pack in skill_pack::installed_packs(workspace_root) {
+            for (path, fallback_name) in pack.skill_files() {
+                let source = SkillSource::Pack {
+                    name: pack.manifest.name.clone(),
+                    version: pack.manifest.version.clone(),
+                    path: path.clone(),
+                };
+                self.register_file(&path, &fallback_name, source, &pack.manifest.requires_tools)?;
+            }
+        }
+        Ok(())
+    }
+
     /// Register a skill from a file path.
     fn register_from_file(&mut self, path: &Path, fallback_name: &str) -> Result<()> {
+        self.register_file(
+            path,
+            fallback_name,
+            SkillSource::File(path.to_path_buf()),
+            &[],
+        )
+    }
+
+    fn register_file(
+        &mut self,
+        path: &Path,
+        fallback_name: &str,
+        source: SkillSource,
+        extra_tools: &[String],
+    ) -> Result<()> {
         let raw = match std::fs::read_to_string(path) {
             Ok(content) => content,
             Err(e) => {
@@ -129,17 +175,26 @@ impl SkillRegistry {
         let name = fm.name.unwrap_or_else(|| fallback_name.to_string());
         let description = fm.description.unwrap_or_default();
 
+        let mut requires_tools = fm.requires_tools;
+        for tool in extra_tools {
+            if !requires_tools.contains(tool) {
+                requires_tools.push(tool.clone());
+            }
+        }
+
         self.skills.insert(
             name.clone(),
             SkillEntry {
                 name,
                 description,
                 content,
-                source: SkillSource::File(path.to_path_buf()),
+                source,
                 hats: fm.hats,
                 backends: fm.backends,
                 tags: fm.tags,
                 auto_inject: false,
+                requires_tools,
+                triggers: fm.triggers,
             },
         );
 
@@ -177,6 +232,9 @@ impl SkillRegistry {
                 if let Some(auto_inject) = override_.auto_inject {
                     skill.auto_inject = auto_inject;
                 }
+                if let Some(triggers) = &override_.triggers {
+                    skill.triggers = triggers.clone();
+                }
             }
         }
     }
@@ -192,13 +250,16 @@ impl SkillRegistry {
         // 1. Register built-in skills
         registry.register_builtins()?;
 
-        // 2. Scan configured directories
+        // 2. Register installed skill packs
+        registry.scan_installed_packs(workspace_root)?;
+
+        // 3. Scan configured directories (local skills replace pack skills)
         for dir in &config.dirs {
             let resolved = Self::resolve_skill_dir(workspace_root, dir);
             registry.scan_directory(&resolved)?;
         }
 
-        // 3. Apply config overrides
+        // 4. Apply config overrides
         registry.apply_overrides(&config.overrides);
 
         Ok(registry)
@@ -247,6 +308,38 @@ impl SkillRegistry {
             .collect()
     }
 
+    /// Returns true if any visible skill has a `files:` trigger.
+    ///
+    /// Lets callers skip collecting touched files when nothing would use them.
+    pub fn needs_touched_files(&self, hat_id: Option<&str>) -> bool {
+        self.skills
+            .values()
+            .any(|s| s.triggers.needs_files() && self.is_visible(s, hat_id))
+    }
+
+    /// Get the skills whose `triggers:` match the current iteration, sorted by name.
+    ///
+    /// Auto-inject skills are excluded since they are already injected every iteration.
+    pub fn triggered_skills(
+        &self,
+        hat_id: Option<&str>,
+        context: &TriggerContext,
+    ) -> Vec<TriggeredSkill> {
+        let mut triggered: Vec<TriggeredSkill> = self
+            .skills
+            .values()
+            .filter(|s| !s.auto_inject && self.is_visible(s, hat_id))
+            .filter_map(|s| {
+                s.triggers.matches(context).map(|reason| TriggeredSkill {
+                    name: s.name.clone(),
+                    reason,
+                })
+            })
+            .collect();
+        triggered.sort_by(|a, b| a.name.cmp(&b.name));
+        triggered
+    }
+
     /// Check if a skill is visible given the current hat and backend.
     fn is_visible(&self, skill: &SkillEntry, hat_id: Option<&str>) -> bool {
         // Backend filtering
@@ -686,6 +779,46 @@ mod tests {
         assert!(registry.get("ralph-tools").unwrap().auto_inject);
     }
 
+    #[test]
+    fn test_from_config_discovers_installed_packs() {
+        let tmp = TempDir::new().unwrap();
+        let pack_dir = tmp.path().join(".ralph/skills/rust-tools");
+        fs::create_dir_all(&pack_dir).unwrap();
+        fs::write(
+            pack_dir.join("skill-pack.yml"),
+            "name: rust-tools\nversion: 1.2.0\nskills: [cargo.md, fmt.md]\nrequires_tools: [cargo]\n",
+        )
+        .unwrap();
+        fs::write(
+            pack_dir.join("cargo.md"),
+            "---\ndescription: Cargo tips\nrequires_tools: [cargo-nextest, cargo]\n---\nUse cargo.\n",
+        )
+        .unwrap();
+        fs::write(pack_dir.join("fmt.md"), "Pack fmt.\n").unwrap();
+
+        // A local skill with the same name replaces the pack's skill
+        let skill_dir = tmp.path().join("skills");
+        fs::create_dir(&skill_dir).unwrap();
+        fs::write(skill_dir.join("fmt.md"), "Local fmt.\n").unwrap();
+
+        let config = SkillsConfig {
+            enabled: true,
+            dirs: vec![skill_dir],
+            overrides: HashMap::new(),
+        };
+        let registry = SkillRegistry::from_config(&config, tmp.path(), None).unwrap();
+
+        let cargo = registry.get("cargo").expect("pack skill registered");
+        assert!(matches!(
+            &cargo.source,
+            SkillSource::Pack { name, version, .. } if name == "rust-tools" && version == "1.2.0"
+        ));
+        assert_eq!(cargo.requires_tools, vec!["cargo-nextest", "cargo"]);
+        let fmt = registry.get("fmt").unwrap();
+        assert!(matches!(fmt.source, SkillSource::File(_)));
+        assert_eq!(fmt.content.trim(), "Local fmt.");
+    }
+
     #[test]
     fn test_from_config_resolves_parent_skills_dir_for_relative_path() {
         let tmp = TempDir::new().unwrap();
@@ -713,4 +846,55 @@ mod tests {
         let registry = SkillRegistry::from_config(&config, &workspace_dir, None).unwrap();
         assert!(registry.get("test-driven-development").is_some());
     }
+
+    #[test]
+    fn test_triggered_skills_match_context() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(
+            tmp.path().join("postgres.md"),
+            "---\nname: postgres\ntriggers:\n  keywords: [postgres]\n  files: ['*.sql']\n---\nUse psql.\n",
+        )
+        .unwrap();
+        fs::write(
+            tmp.path().join("docker.md"),
+            "---\nname: docker\ntriggers:\n  topics: ['deploy.*']\n---\nUse compose.\n",
+        )
+        .unwrap();
+
+        let mut registry = SkillRegistry::new(None);
+        registry.scan_directory(tmp.path()).unwrap();
+        assert!(registry.needs_touched_files(None));
+
+        let context = TriggerContext {
+            topics: vec!["deploy.start".to_string()],
+            texts: vec!["Migrate Postgres".to_string()],
+            files: Vec::new(),
+        };
+        let triggered = registry.triggered_skills(None, &context);
+        assert_eq!(
+            triggered,
+            vec![
+                TriggeredSkill {
+                    name: "docker".to_string(),
+                    reason: "topic 'deploy.start' matches 'deploy.*'".to_string(),
+                },
+                TriggeredSkill {
+                    name: "postgres".to_string(),
+                    reason: "keyword 'postgres'".to_string(),
+                },
+            ]
+        );
+
+        // Auto-inject skills are already in every prompt.
+        registry.skills.get_mut("docker").unwrap().auto_inject = true;
+        let triggered = registry.triggered_skills(None, &context);
+        assert_eq!(triggered.len(), 1);
+        assert_eq!(triggered[0].name, "postgres");
+
+        assert!(
+            registry
+                .triggered_skills(None, &TriggerContext::default())
+                .is_empty()
+        );
+    }
 }
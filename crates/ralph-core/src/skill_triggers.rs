@@ -0,0 +1,157 @@
+//! Context-aware skill selection via `triggers:` frontmatter.
+//!
+//! A skill with triggers is injected in full only when the current iteration
+//! matches one of them:
+//!
+//! ```yaml
+//! triggers:
+//!   files: ["migrations/**", "*.sql"]   # globs over files touched in the workspace
+//!   keywords: [postgres, schema]        # words in pending event payloads or the objective
+//!   topics: ["db.*"]                    # pending event topics
+//! ```
+
+use crate::path_glob::file_matches;
+use ralph_proto::Topic;
+use serde::{Deserialize, Serialize};
+
+/// Conditions under which a skill is auto-injected.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct SkillTriggers {
+    /// Glob patterns matched against touched file paths. Patterns without a
+    /// `/` also match the file name alone (`*.sql` matches `db/init.sql`).
+    pub files: Vec<String>,
+    /// Case-insensitive whole words matched against event payloads and the objective.
+    pub keywords: Vec<String>,
+    /// Topic patterns matched against pending event topics (`db.*` style).
+    pub topics: Vec<String>,
+}
+
+impl SkillTriggers {
+    /// Returns true if no trigger is configured.
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty() && self.keywords.is_empty() && self.topics.is_empty()
+    }
+
+    /// Returns true if any trigger needs the list of touched files.
+    pub fn needs_files(&self) -> bool {
+        !self.files.is_empty()
+    }
+
+    /// Returns a human-readable reason for the first matching trigger, if any.
+    ///
+    /// Topics are checked first, then keywords, then files.
+    pub fn matches(&self, context: &TriggerContext) -> Option<String> {
+        for pattern in &self.topics {
+            let pattern_topic = Topic::new(pattern.as_str());
+            if let Some(topic) = context
+                .topics
+                .iter()
+                .find(|topic| pattern_topic.matches_str(topic))
+            {
+                return Some(format!("topic '{topic}' matches '{pattern}'"));
+            }
+        }
+
+        for keyword in &self.keywords {
+            if context
+                .texts
+                .iter()
+                .any(|text| contains_word(text, keyword))
+            {
+                return Some(format!("keyword '{keyword}'"));
+            }
+        }
+
+        for pattern in &self.files {
+            if let Some(file) = context
+                .files
+                .iter()
+                .find(|file| file_matches(pattern, file))
+            {
+                return Some(format!("file '{file}' matches '{pattern}'"));
+            }
+        }
+
+        None
+    }
+}
+
+/// What the current iteration is about, as seen by skill triggers.
+#[derive(Debug, Clone, Default)]
+pub struct TriggerContext {
+    /// Topics of the pending events.
+    pub topics: Vec<String>,
+    /// Pending event payloads and the objective.
+    pub texts: Vec<String>,
+    /// Workspace-relative paths of touched files.
+    pub files: Vec<String>,
+}
+
+/// Returns true if `text` contains `word` on word boundaries, ignoring case.
+fn contains_word(text: &str, word: &str) -> bool {
+    let word = word.trim().to_lowercase();
+    if word.is_empty() {
+        return false;
+    }
+    let text = text.to_lowercase();
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+
+    text.match_indices(&word).any(|(start, _)| {
+        let before = text[..start].chars().next_back();
+        let after = text[start + word.len()..].chars().next();
+        !before.is_some_and(is_word_char) && !after.is_some_and(is_word_char)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keywords_match_whole_words_case_insensitively() {
+        assert!(contains_word("Migrate the Postgres schema", "postgres"));
+        assert!(contains_word("use db_pool, please", "db_pool"));
+        assert!(!contains_word("postgresql only", "postgres"));
+        assert!(!contains_word("anything", "  "));
+    }
+
+    #[test]
+    fn matches_reports_first_matching_trigger() {
+        let triggers: SkillTriggers =
+            serde_yaml::from_str("files: ['*.sql']\nkeywords: [postgres]\ntopics: ['db.*']\n")
+                .unwrap();
+        assert!(!triggers.is_empty());
+        assert!(triggers.needs_files());
+
+        let none = TriggerContext::default();
+        assert_eq!(triggers.matches(&none), None);
+
+        let topic = TriggerContext {
+            topics: vec!["db.migrate".to_string()],
+            ..TriggerContext::default()
+        };
+        assert_eq!(
+            triggers.matches(&topic).as_deref(),
+            Some("topic 'db.migrate' matches 'db.*'")
+        );
+
+        let keyword = TriggerContext {
+            texts: vec!["Tune Postgres indexes".to_string()],
+            ..TriggerContext::default()
+        };
+        assert_eq!(
+            triggers.matches(&keyword).as_deref(),
+            Some("keyword 'postgres'")
+        );
+
+        let file = TriggerContext {
+            files: vec!["db/init.sql".to_string()],
+            ..TriggerContext::default()
+        };
+        assert_eq!(
+            triggers.matches(&file).as_deref(),
+            Some("file 'db/init.sql' matches '*.sql'")
+        );
+    }
+}
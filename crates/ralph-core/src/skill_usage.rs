@@ -0,0 +1,215 @@
+//! Skill usage telemetry.
+//!
+//! Records which skills were injected into prompts by their `triggers:` and
+//! which ones the agent loaded on demand with `ralph tools skill load`, so
+//! triggers can be tuned. Records are appended to `.ralph/skill-usage.jsonl`.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
+
+/// File name of the usage log inside `.ralph/`.
+pub const SKILL_USAGE_FILE: &str = "skill-usage.jsonl";
+
+/// How a skill reached the agent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SkillUsageKind {
+    /// Injected in full because one of its triggers matched.
+    Injected,
+    /// Loaded by the agent via `ralph tools skill load`.
+    Loaded,
+}
+
+/// A single usage record.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillUsageRecord {
+    pub ts: DateTime<Utc>,
+    pub skill: String,
+    pub kind: SkillUsageKind,
+    /// Loop iteration, for injections.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iteration: Option<u32>,
+    /// Trigger that caused the injection.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl SkillUsageRecord {
+    /// Creates an injection record.
+    pub fn injected(skill: impl Into<String>, iteration: u32, reason: impl Into<String>) -> Self {
+        Self {
+            ts: Utc::now(),
+            skill: skill.into(),
+            kind: SkillUsageKind::Injected,
+            iteration: Some(iteration),
+            reason: Some(reason.into()),
+        }
+    }
+
+    /// Creates an on-demand load record.
+    pub fn loaded(skill: impl Into<String>) -> Self {
+        Self {
+            ts: Utc::now(),
+            skill: skill.into(),
+            kind: SkillUsageKind::Loaded,
+            iteration: None,
+            reason: None,
+        }
+    }
+}
+
+/// Aggregated usage of one skill.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SkillUsageStats {
+    pub skill: String,
+    pub injected: usize,
+    pub loaded: usize,
+    /// Loads that happened after the skill had already been injected.
+    pub loaded_after_injection: usize,
+    /// Trigger reasons with how often each fired.
+    pub reasons: BTreeMap<String, usize>,
+}
+
+/// Append-only JSONL log of skill usage.
+#[derive(Debug, Clone)]
+pub struct SkillUsageLog {
+    path: PathBuf,
+}
+
+impl SkillUsageLog {
+    /// Creates a log at an explicit path.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Creates the log for a workspace (`<root>/.ralph/skill-usage.jsonl`).
+    pub fn for_workspace(root: &Path) -> Self {
+        Self::new(root.join(".ralph").join(SKILL_USAGE_FILE))
+    }
+
+    /// Returns the log path.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Appends records to the log, creating it if needed.
+    pub fn append(&self, records: &[SkillUsageRecord]) -> io::Result<()> {
+        if records.is_empty() {
+            return Ok(());
+        }
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        for record in records {
+            let line = serde_json::to_string(record).map_err(io::Error::other)?;
+            writeln!(file, "{line}")?;
+        }
+        Ok(())
+    }
+
+    /// Reads all records, skipping malformed lines. A missing log is empty.
+    pub fn read(&self) -> io::Result<Vec<SkillUsageRecord>> {
+        let file = match std::fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        let mut records = Vec::new();
+        for line in io::BufReader::new(file).lines() {
+            let line = line?;
+            if let Ok(record) = serde_json::from_str(&line) {
+                records.push(record);
+            }
+        }
+        Ok(records)
+    }
+
+    /// Aggregates records per skill, sorted by skill name.
+    pub fn stats(records: &[SkillUsageRecord]) -> Vec<SkillUsageStats> {
+        let mut stats: BTreeMap<&str, SkillUsageStats> = BTreeMap::new();
+        for record in records {
+            let entry = stats
+                .entry(record.skill.as_str())
+                .or_insert_with(|| SkillUsageStats {
+                    skill: record.skill.clone(),
+                    ..SkillUsageStats::default()
+                });
+            match record.kind {
+                SkillUsageKind::Injected => {
+                    entry.injected += 1;
+                    if let Some(reason) = &record.reason {
+                        *entry.reasons.entry(reason.clone()).or_default() += 1;
+                    }
+                }
+                SkillUsageKind::Loaded => {
+                    entry.loaded += 1;
+                    if entry.injected > 0 {
+                        entry.loaded_after_injection += 1;
+                    }
+                }
+            }
+        }
+        stats.into_values().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn append_and_read_round_trip() {
+        let tmp = TempDir::new().unwrap();
+        let log = SkillUsageLog::for_workspace(tmp.path());
+        assert!(log.read().unwrap().is_empty());
+
+        log.append(&[
+            SkillUsageRecord::injected("postgres", 1, "keyword 'postgres'"),
+            SkillUsageRecord::loaded("docker"),
+        ])
+        .unwrap();
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(log.path())
+            .unwrap()
+            .write_all(b"not json\n")
+            .unwrap();
+
+        let records = log.read().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].kind, SkillUsageKind::Injected);
+        assert_eq!(records[0].iteration, Some(1));
+        assert_eq!(records[1].skill, "docker");
+        assert_eq!(records[1].reason, None);
+    }
+
+    #[test]
+    fn stats_count_injections_and_loads() {
+        let records = vec![
+            SkillUsageRecord::loaded("postgres"),
+            SkillUsageRecord::injected("postgres", 1, "keyword 'postgres'"),
+            SkillUsageRecord::injected("postgres", 2, "keyword 'postgres'"),
+            SkillUsageRecord::loaded("postgres"),
+            SkillUsageRecord::loaded("docker"),
+        ];
+        let stats = SkillUsageLog::stats(&records);
+
+        assert_eq!(stats.len(), 2);
+        assert_eq!(stats[0].skill, "docker");
+        assert_eq!(stats[0].loaded, 1);
+        assert_eq!(stats[0].loaded_after_injection, 0);
+
+        assert_eq!(stats[1].injected, 2);
+        assert_eq!(stats[1].loaded, 2);
+        assert_eq!(stats[1].loaded_after_injection, 1);
+        assert_eq!(stats[1].reasons.get("keyword 'postgres'"), Some(&2));
+    }
+}
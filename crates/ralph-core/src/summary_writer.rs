@@ -138,6 +138,14 @@ impl SummaryWriter {
         if state.cumulative_cost > 0.0 {
             content.push_str(&format!("**Est. cost:** ${:.2}\n", state.cumulative_cost));
         }
+        if !state.cost_by_model.is_empty() {
+            let breakdown: Vec<String> = state
+                .cost_by_model
+                .iter()
+                .map(|(model, cost)| format!("{model} ${cost:.2}"))
+                .collect();
+            content.push_str(&format!("**Cost by model:** {}\n", breakdown.join(", ")));
+        }
 
         // Tasks section (read from scratchpad if available)
         content.push('\n');
@@ -307,6 +315,7 @@ mod tests {
             iteration: 12,
             consecutive_failures: 0,
             cumulative_cost: 1.50,
+            cost_by_model: std::collections::BTreeMap::new(),
             started_at: Instant::now(),
             last_hat: None,
             consecutive_blocked: 0,
@@ -320,6 +329,9 @@ mod tests {
             exhausted_hats: std::collections::HashSet::new(),
             last_checkin_at: None,
             last_active_hat_ids: Vec::new(),
+            last_triggered_skills: Vec::new(),
+            timers: crate::timers::TimerSet::new(),
+            approvals: crate::approvals::ApprovalQueue::new(),
         }
     }
 
@@ -400,6 +412,27 @@ More text here.
         assert!(content.contains("## Events"));
         assert!(content.contains("## Final Commit"));
         assert!(content.contains("abc1234: feat(auth): add tokens"));
+        assert!(!content.contains("**Cost by model:**"));
+    }
+
+    #[test]
+    fn test_generate_content_cost_by_model() {
+        let writer = SummaryWriter::default();
+        let mut state = test_state();
+        state.cost_by_model.insert("claude/opus".to_string(), 1.25);
+        state
+            .cost_by_model
+            .insert("claude/sonnet".to_string(), 0.25);
+
+        let content = writer.generate_content_with_landing(
+            &TerminationReason::CompletionPromise,
+            &state,
+            None,
+            None,
+            None,
+        );
+
+        assert!(content.contains("**Cost by model:** claude/opus $1.25, claude/sonnet $0.25"));
     }
 
     #[test]
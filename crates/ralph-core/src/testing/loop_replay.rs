@@ -0,0 +1,945 @@
+//! Deterministic replay of a full orchestration loop from a session recording.
+//!
+//! `LoopReplayer` re-drives the real `EventLoop` with the current configuration,
+//! substituting the backend output and agent-written events recorded by
+//! `ralph run --record-session` for each iteration instead of calling a model.
+//! The replay is compared against the recording and every divergence (different
+//! hat selected, event rejected or added, different termination) is reported.
+//!
+//! Timers and approval timeouts run on the recorded clock, human approval
+//! decisions are fed back at the times they were read, and hats with a
+//! `write_scope` are checked against the files they were recorded to change.
+//!
+//! This makes it possible to regression-test changes to hat topologies and
+//! presets without spending model calls.
+//!
+//! # Example
+//!
+//! ```ignore
+//! use ralph_core::testing::{LoopRecording, LoopReplayer};
+//!
+//! let recording = LoopRecording::from_file("session.jsonl")?;
+//! let report = LoopReplayer::new(config).run(&recording);
+//!
+//! assert!(report.is_faithful(), "{:?}", report.divergences);
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io::{self, BufReader};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use chrono::{DateTime, Utc};
+use ralph_proto::HatId;
+use serde_json::Value;
+
+use crate::config::RalphConfig;
+use crate::diagnostics::DiagnosticsCollector;
+use crate::event_loop::{EventLoop, TerminationReason};
+use crate::event_reader::ParseResult;
+use crate::session_export::termination_reason_from_payload;
+use crate::session_player::SessionPlayer;
+use crate::write_scope::ScopeViolationAction;
+
+/// Maximum consecutive fallback injections before the replay stops,
+/// matching the loop runner.
+const MAX_FALLBACK_ATTEMPTS: u32 = 3;
+
+/// Topic published by the orchestrator on exit; compared separately.
+const TERMINATE_TOPIC: &str = "loop.terminate";
+
+/// One iteration captured in a session recording.
+#[derive(Debug, Clone)]
+pub struct RecordedIteration {
+    /// Iteration number (1-based).
+    pub iteration: u32,
+    /// Hat that was active for the iteration.
+    pub hat: String,
+    /// Raw backend output, if recorded.
+    pub output: Option<String>,
+    /// Whether the backend reported success.
+    pub success: bool,
+    /// Events the agent wrote to the events JSONL (before validation).
+    pub events: ParseResult,
+    /// Topics published on the bus during the iteration, in order.
+    pub published: Vec<String>,
+    /// Unix timestamp (ms) at which the iteration started.
+    pub started_at: u64,
+    /// Unix timestamp (ms) at which the agent's events were read.
+    pub events_at: u64,
+    /// Files the hat changed, recorded when it had a `write_scope`.
+    pub changed_files: Option<Vec<String>>,
+}
+
+/// A human approval decision read between iterations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordedDecision {
+    /// Number of iterations completed when the decision was read.
+    pub after_iteration: u32,
+    /// Unix timestamp (ms) at which it was read.
+    pub ts: u64,
+    /// The reply, e.g. `approve` or `reject: not now`.
+    pub reply: String,
+}
+
+/// A loop recording parsed from a `SessionRecorder` JSONL file.
+#[derive(Debug, Clone)]
+pub struct LoopRecording {
+    /// Topic of the event that started the loop (e.g. `task.start`).
+    pub start_topic: String,
+    /// The prompt the loop was started with.
+    pub prompt: String,
+    /// Topics published before the first iteration.
+    pub initial_published: Vec<String>,
+    /// Recorded iterations in order.
+    pub iterations: Vec<RecordedIteration>,
+    /// Human approval decisions in the order they were read.
+    pub decisions: Vec<RecordedDecision>,
+    /// Termination reason from the `loop.terminate` event, if recorded.
+    pub termination: Option<String>,
+    /// Unix timestamp (ms) of the first record.
+    pub started_at: u64,
+    /// Unix timestamp (ms) of the last record.
+    pub ended_at: u64,
+}
+
+impl LoopRecording {
+    /// Loads a recording from a JSONL session file.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be read or is not a replayable
+    /// loop recording.
+    pub fn from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let file = std::fs::File::open(path.as_ref())?;
+        let player = SessionPlayer::from_reader(BufReader::new(file))?;
+        Self::from_player(&player)
+    }
+
+    /// Builds a recording from parsed session records.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidData` if the session has no start event or no recorded
+    /// backend output (sessions recorded before output capture was added).
+    pub fn from_player(player: &SessionPlayer) -> io::Result<Self> {
+        let mut start: Option<(String, String)> = None;
+        let mut initial_published = Vec::new();
+        let mut iterations: Vec<RecordedIteration> = Vec::new();
+        let mut decisions = Vec::new();
+        let mut termination = None;
+        let mut has_output = false;
+        let started_at = player.records().first().map_or(0, |r| r.record.ts);
+        let ended_at = player.records().last().map_or(0, |r| r.record.ts);
+
+        for record in player.records() {
+            let data = &record.record.data;
+            let ts = record.record.ts;
+            let n = data.get("n").and_then(Value::as_u64).unwrap_or(0) as u32;
+
+            match record.record.event.as_str() {
+                "_meta.iteration" => iterations.push(RecordedIteration {
+                    iteration: n,
+                    hat: json_str(data, "hat"),
+                    output: None,
+                    success: true,
+                    events: ParseResult::default(),
+                    published: Vec::new(),
+                    started_at: ts,
+                    events_at: ts,
+                    changed_files: None,
+                }),
+                "_meta.agent_output" => {
+                    if let Some(current) = iterations.last_mut().filter(|it| it.iteration == n) {
+                        current.output = Some(json_str(data, "output"));
+                        current.success =
+                            data.get("success").and_then(Value::as_bool) == Some(true);
+                        current.events_at = ts;
+                        has_output = true;
+                    }
+                }
+                "_meta.agent_events" => {
+                    if let Some(current) = iterations.last_mut().filter(|it| it.iteration == n) {
+                        current.events = data
+                            .get("events")
+                            .cloned()
+                            .and_then(|events| serde_json::from_value(events).ok())
+                            .unwrap_or_default();
+                        current.events_at = ts;
+                    }
+                }
+                "_meta.changed_files" => {
+                    if let Some(current) = iterations.last_mut().filter(|it| it.iteration == n) {
+                        current.changed_files = Some(json_strings(data, "files"));
+                    }
+                }
+                "_meta.approval_decisions" => {
+                    decisions.extend(json_strings(data, "decisions").into_iter().map(|reply| {
+                        RecordedDecision {
+                            after_iteration: n,
+                            ts,
+                            reply,
+                        }
+                    }));
+                }
+                "bus.publish" => {
+                    let topic = json_str(data, "topic");
+                    if start.is_none() {
+                        start = Some((topic.clone(), json_str(data, "payload")));
+                    }
+                    if topic == TERMINATE_TOPIC {
+                        termination =
+                            Some(termination_reason_from_payload(&json_str(data, "payload")));
+                    } else if let Some(current) = iterations.last_mut() {
+                        current.published.push(topic);
+                    } else {
+                        initial_published.push(topic);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let (start_topic, prompt) = start.ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Recording has no start event; was it made with `ralph run --record-session`?",
+            )
+        })?;
+
+        if !has_output {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Recording has no backend output records; re-record it with `ralph run --record-session`",
+            ));
+        }
+
+        Ok(Self {
+            start_topic,
+            prompt,
+            initial_published,
+            iterations,
+            decisions,
+            termination,
+            started_at,
+            ended_at,
+        })
+    }
+}
+
+/// A difference between the recording and its replay.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Divergence {
+    /// The replay selected a different hat for an iteration.
+    HatMismatch {
+        iteration: u32,
+        recorded: String,
+        replayed: String,
+    },
+    /// An event published in the recording was not published in the replay.
+    EventRejected { iteration: u32, topic: String },
+    /// The replay published an event the recording did not.
+    UnexpectedEvent { iteration: u32, topic: String },
+    /// The replay needed an iteration the recording does not contain.
+    RecordingExhausted { iteration: u32 },
+    /// The replay was still waiting for a timer or an approval decision
+    /// when the recording went on to the next iteration.
+    StillWaiting { iteration: u32 },
+    /// The loop terminated for a different reason.
+    TerminationMismatch {
+        recorded: Option<String>,
+        replayed: String,
+    },
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Divergence::HatMismatch {
+                iteration,
+                recorded,
+                replayed,
+            } => write!(
+                f,
+                "iteration {}: recorded hat '{}', replay selected '{}'",
+                iteration, recorded, replayed
+            ),
+            Divergence::EventRejected { iteration, topic } => write!(
+                f,
+                "iteration {}: '{}' was published in the recording but not in the replay",
+                iteration, topic
+            ),
+            Divergence::UnexpectedEvent { iteration, topic } => write!(
+                f,
+                "iteration {}: replay published '{}' which the recording did not",
+                iteration, topic
+            ),
+            Divergence::RecordingExhausted { iteration } => write!(
+                f,
+                "iteration {}: replay continued past the end of the recording",
+                iteration
+            ),
+            Divergence::StillWaiting { iteration } => write!(
+                f,
+                "iteration {}: replay was still waiting for a timer or approval, the recording was not",
+                iteration
+            ),
+            Divergence::TerminationMismatch { recorded, replayed } => write!(
+                f,
+                "termination: recorded '{}', replay ended with '{}'",
+                recorded.as_deref().unwrap_or("none"),
+                replayed
+            ),
+        }
+    }
+}
+
+/// Result of replaying a recording.
+#[derive(Debug, Clone)]
+pub struct LoopReplayReport {
+    /// Number of iterations replayed.
+    pub iterations: u32,
+    /// Hat selected for each replayed iteration.
+    pub hats: Vec<String>,
+    /// Termination reason of the replay (`None` if the recording ran out first).
+    pub termination: Option<TerminationReason>,
+    /// Differences from the recording, in the order they were found.
+    pub divergences: Vec<Divergence>,
+}
+
+impl LoopReplayReport {
+    /// Returns true if the replay matched the recording exactly.
+    pub fn is_faithful(&self) -> bool {
+        self.divergences.is_empty()
+    }
+}
+
+/// Re-drives the event loop from a recording with the given configuration.
+pub struct LoopReplayer {
+    config: RalphConfig,
+}
+
+impl LoopReplayer {
+    /// Creates a replayer that routes with the given configuration.
+    pub fn new(config: RalphConfig) -> Self {
+        Self { config }
+    }
+
+    /// Replays the recording and reports divergences.
+    ///
+    /// Each iteration mirrors the loop runner: fire due timers and settle held
+    /// events with the recorded decisions, select the next hat (waiting, or
+    /// with fallback recovery), build the prompt, process the recorded output,
+    /// check the recorded changes against the hat's write scope, process the
+    /// recorded agent events, apply default publishes and check for the
+    /// completion event.
+    pub fn run(&self, recording: &LoopRecording) -> LoopReplayReport {
+        let mut event_loop =
+            EventLoop::with_diagnostics(self.config.clone(), DiagnosticsCollector::disabled());
+        event_loop.set_clock(recorded_time(recording.started_at));
+
+        // Bucket published topics by iteration, the same way the recording does
+        let published: Arc<Mutex<Vec<(u32, String)>>> = Arc::new(Mutex::new(Vec::new()));
+        let current_iteration = Arc::new(Mutex::new(0u32));
+        {
+            let published = Arc::clone(&published);
+            let current_iteration = Arc::clone(&current_iteration);
+            event_loop.add_observer(move |event| {
+                let iteration = current_iteration.lock().map(|n| *n).unwrap_or(0);
+                if let Ok(mut published) = published.lock() {
+                    published.push((iteration, event.topic.to_string()));
+                }
+            });
+        }
+
+        if recording.start_topic == "task.resume" {
+            event_loop.initialize_resume(&recording.prompt);
+        } else {
+            event_loop.initialize(&recording.prompt);
+        }
+
+        let mut divergences = Vec::new();
+        let mut hats = Vec::new();
+        let mut recorded = recording.iterations.iter().peekable();
+        let mut decisions = recording.decisions.iter().peekable();
+        let mut consecutive_fallbacks = 0u32;
+
+        let termination = loop {
+            if let Some(reason) = event_loop.check_termination() {
+                break Some(reason);
+            }
+
+            // Decisions at the times they were read, then everything that fell
+            // due by the time the next recorded iteration started
+            let completed = event_loop.state().iteration;
+            while let Some(decision) = decisions.next_if(|d| d.after_iteration <= completed) {
+                event_loop.set_clock(recorded_time(decision.ts));
+                event_loop.tick_timers();
+                event_loop.tick_approvals_with(std::slice::from_ref(&decision.reply));
+            }
+            let next_started_at = recorded
+                .peek()
+                .map_or(recording.ended_at, |step| step.started_at);
+            event_loop.set_clock(recorded_time(next_started_at));
+            event_loop.tick_timers();
+            event_loop.tick_approvals_with(&[]);
+
+            let hat_id = match event_loop.next_hat() {
+                Some(id) => {
+                    consecutive_fallbacks = 0;
+                    id.clone()
+                }
+                None => {
+                    // The runner waits here; by now the wait would have ended
+                    if event_loop.awaiting_approval() || event_loop.next_timer_due_in().is_some() {
+                        if recorded.peek().is_some() {
+                            divergences.push(Divergence::StillWaiting {
+                                iteration: completed + 1,
+                            });
+                        }
+                        break None;
+                    }
+
+                    consecutive_fallbacks += 1;
+                    if consecutive_fallbacks > MAX_FALLBACK_ATTEMPTS
+                        || !event_loop.inject_fallback_event()
+                    {
+                        break Some(TerminationReason::Stopped);
+                    }
+                    continue;
+                }
+            };
+
+            let iteration = event_loop.state().iteration + 1;
+            if let Ok(mut current) = current_iteration.lock() {
+                *current = iteration;
+            }
+
+            let display_hat: HatId = if hat_id.as_str() == "ralph" {
+                event_loop.get_active_hat_id()
+            } else {
+                hat_id.clone()
+            };
+            hats.push(display_hat.as_str().to_string());
+
+            let Some(step) = recorded.next().filter(|step| step.output.is_some()) else {
+                divergences.push(Divergence::RecordingExhausted { iteration });
+                break None;
+            };
+
+            if step.hat != display_hat.as_str() {
+                divergences.push(Divergence::HatMismatch {
+                    iteration,
+                    recorded: step.hat.clone(),
+                    replayed: display_hat.as_str().to_string(),
+                });
+            }
+
+            event_loop.set_clock(recorded_time(step.events_at));
+
+            // The prompt is not needed, but building it consumes pending events
+            let _ = event_loop.build_prompt(&hat_id);
+
+            let output = step.output.as_deref().unwrap_or_default();
+            if let Some(reason) = event_loop.process_output(&hat_id, output, step.success) {
+                break Some(reason);
+            }
+
+            let scope_violation = event_loop
+                .registry()
+                .get_config(&display_hat)
+                .and_then(|hat| hat.write_scope.as_ref())
+                .zip(step.changed_files.as_deref())
+                .and_then(|(scope, changed)| scope.check(display_hat.as_str(), changed));
+            let mut events = step.events.clone();
+            if scope_violation
+                .as_ref()
+                .is_some_and(|v| v.action == ScopeViolationAction::Reject)
+            {
+                events.events.clear();
+            }
+            let mut agent_wrote_events = event_loop.process_parsed_events(events);
+            if let Some(violation) = scope_violation {
+                event_loop.publish_or_hold(violation.to_event());
+                agent_wrote_events = true;
+            }
+
+            if !agent_wrote_events {
+                let active_hats = event_loop.state().last_active_hat_ids.clone();
+                for active_hat_id in &active_hats {
+                    event_loop.check_default_publishes(active_hat_id);
+                    if event_loop.has_pending_events() {
+                        break;
+                    }
+                }
+            }
+
+            if let Some(reason) = event_loop.check_completion_event() {
+                break Some(reason);
+            }
+        };
+
+        let iterations = event_loop.state().iteration;
+        let replayed = published.lock().map(|p| p.clone()).unwrap_or_default();
+        divergences.extend(compare_published(recording, &replayed, iterations));
+
+        if let Some(ref reason) = termination
+            && recording.termination.as_deref() != Some(reason.as_str())
+        {
+            divergences.push(Divergence::TerminationMismatch {
+                recorded: recording.termination.clone(),
+                replayed: reason.as_str().to_string(),
+            });
+        }
+
+        LoopReplayReport {
+            iterations,
+            hats,
+            termination,
+            divergences,
+        }
+    }
+}
+
+/// Compares published topics per iteration, ignoring `loop.terminate`.
+///
+/// Only iterations the replay actually reached are compared so a shorter
+/// replay is reported once (via termination or exhaustion) rather than as
+/// a rejection of every later event.
+fn compare_published(
+    recording: &LoopRecording,
+    replayed: &[(u32, String)],
+    replayed_iterations: u32,
+) -> Vec<Divergence> {
+    let mut recorded_by_iteration: BTreeMap<u32, Vec<&str>> = BTreeMap::new();
+    recorded_by_iteration.insert(
+        0,
+        recording
+            .initial_published
+            .iter()
+            .map(String::as_str)
+            .collect(),
+    );
+    for step in &recording.iterations {
+        recorded_by_iteration
+            .entry(step.iteration)
+            .or_default()
+            .extend(step.published.iter().map(String::as_str));
+    }
+
+    let mut replayed_by_iteration: BTreeMap<u32, Vec<&str>> = BTreeMap::new();
+    for (iteration, topic) in replayed {
+        if topic != TERMINATE_TOPIC {
+            replayed_by_iteration
+                .entry(*iteration)
+                .or_default()
+                .push(topic.as_str());
+        }
+    }
+
+    let mut divergences = Vec::new();
+    for iteration in 0..=replayed_iterations {
+        let mut unmatched: Vec<&str> = replayed_by_iteration
+            .get(&iteration)
+            .cloned()
+            .unwrap_or_default();
+
+        for topic in recorded_by_iteration
+            .get(&iteration)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+        {
+            if let Some(pos) = unmatched.iter().position(|t| t == topic) {
+                unmatched.remove(pos);
+            } else {
+                divergences.push(Divergence::EventRejected {
+                    iteration,
+                    topic: (*topic).to_string(),
+                });
+            }
+        }
+
+        divergences.extend(
+            unmatched
+                .into_iter()
+                .map(|topic| Divergence::UnexpectedEvent {
+                    iteration,
+                    topic: topic.to_string(),
+                }),
+        );
+    }
+
+    divergences
+}
+
+/// Reads a string field from a JSON object.
+fn json_str(data: &Value, key: &str) -> String {
+    data.get(key)
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+/// Reads an array of strings from a JSON object.
+fn json_strings(data: &Value, key: &str) -> Vec<String> {
+    data.get(key)
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|value| value.as_str().map(ToString::to_string))
+        .collect()
+}
+
+/// Converts a recorded Unix timestamp in milliseconds to a time.
+fn recorded_time(ts: u64) -> DateTime<Utc> {
+    DateTime::from_timestamp_millis(i64::try_from(ts).unwrap_or(i64::MAX)).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::event_reader::Event as JsonlEvent;
+    use crate::session_recorder::{Record, SessionRecorder};
+    use ralph_proto::Event;
+
+    const HATS_YAML: &str = r#"
+event_loop:
+  completion_promise: "LOOP_COMPLETE"
+  max_iterations: 10
+hats:
+  builder:
+    name: "Builder"
+    triggers: ["build.task"]
+    publishes: ["build.done"]
+  reviewer:
+    name: "Reviewer"
+    triggers: ["build.done"]
+    publishes: ["review.approved"]
+"#;
+
+    fn jsonl_event(topic: &str, payload: &str) -> ParseResult {
+        ParseResult {
+            events: vec![JsonlEvent {
+                topic: topic.to_string(),
+                payload: Some(payload.to_string()),
+                ts: "2026-01-01T00:00:00Z".to_string(),
+                deliver_at: None,
+                after_iterations: None,
+            }],
+            malformed: Vec::new(),
+        }
+    }
+
+    /// What happened outside the agent while a loop was recorded.
+    #[derive(Default)]
+    struct Outside<'a> {
+        /// Approval replies, keyed by the number of completed iterations.
+        decisions: &'a [(u32, &'a str)],
+        /// Files changed by the hat in an iteration.
+        changed_files: &'a [(u32, &'a [&'a str])],
+    }
+
+    /// Records a loop by running it with scripted per-iteration agent events,
+    /// the same way `ralph run --record-session` does.
+    fn record_loop(config: &RalphConfig, script: &[(&str, ParseResult)]) -> LoopRecording {
+        record_loop_with(config, script, &Outside::default())
+    }
+
+    fn record_loop_with(
+        config: &RalphConfig,
+        script: &[(&str, ParseResult)],
+        outside: &Outside<'_>,
+    ) -> LoopRecording {
+        let recorder = Arc::new(SessionRecorder::new(Vec::new()));
+        let mut event_loop =
+            EventLoop::with_diagnostics(config.clone(), DiagnosticsCollector::disabled());
+        event_loop.add_observer(SessionRecorder::make_observer(Arc::clone(&recorder)));
+        event_loop.initialize("Build the thing");
+
+        let mut script = script.iter();
+        let reason = loop {
+            if let Some(reason) = event_loop.check_termination() {
+                break reason;
+            }
+            event_loop.tick_timers();
+            let completed = event_loop.state().iteration;
+            let decisions: Vec<String> = outside
+                .decisions
+                .iter()
+                .filter(|(n, _)| *n == completed)
+                .map(|(_, reply)| (*reply).to_string())
+                .collect();
+            if !decisions.is_empty() {
+                recorder.record_meta(Record::meta_approval_decisions(completed, &decisions));
+            }
+            event_loop.tick_approvals_with(&decisions);
+            let Some(hat_id) = event_loop.next_hat().cloned() else {
+                break TerminationReason::Stopped;
+            };
+            let iteration = event_loop.state().iteration + 1;
+            let display_hat = event_loop.get_active_hat_id();
+            recorder.record_meta(Record::meta_iteration(iteration, 0, display_hat.as_str()));
+            let _ = event_loop.build_prompt(&hat_id);
+
+            let Some((output, events)) = script.next() else {
+                break TerminationReason::Stopped;
+            };
+            recorder.record_meta(Record::meta_agent_output(
+                iteration,
+                display_hat.as_str(),
+                output,
+                true,
+            ));
+            if let Some(reason) = event_loop.process_output(&hat_id, output, true) {
+                break reason;
+            }
+            let violation = event_loop
+                .registry()
+                .get_config(&display_hat)
+                .and_then(|hat| hat.write_scope.clone())
+                .zip(outside.changed_files.iter().find(|(n, _)| *n == iteration))
+                .and_then(|(scope, (_, files))| {
+                    let files: Vec<String> = files.iter().map(ToString::to_string).collect();
+                    recorder.record_meta(Record::meta_changed_files(iteration, &files));
+                    scope.check(display_hat.as_str(), &files)
+                });
+            recorder.record_meta(Record::meta_agent_events(iteration, events));
+            let mut events = events.clone();
+            if violation
+                .as_ref()
+                .is_some_and(|v| v.action == ScopeViolationAction::Reject)
+            {
+                events.events.clear();
+            }
+            event_loop.process_parsed_events(events);
+            if let Some(violation) = violation {
+                event_loop.publish_or_hold(violation.to_event());
+            }
+            if let Some(reason) = event_loop.check_completion_event() {
+                break reason;
+            }
+        };
+        event_loop.publish_terminate_event(&reason);
+        drop(event_loop);
+
+        let bytes = Arc::try_unwrap(recorder).ok().unwrap().into_inner();
+        let player = SessionPlayer::from_bytes(&bytes).unwrap();
+        LoopRecording::from_player(&player).unwrap()
+    }
+
+    fn script() -> Vec<(&'static str, ParseResult)> {
+        vec![
+            ("Planning", jsonl_event("build.task", "Implement feature")),
+            (
+                "Building",
+                jsonl_event(
+                    "build.done",
+                    "tests: pass\nlint: pass\ntypecheck: pass\naudit: pass\ncoverage: pass\ncomplexity: 5\nduplication: pass",
+                ),
+            ),
+            ("Reviewing", jsonl_event("review.approved", "Looks good")),
+            ("Done", jsonl_event("LOOP_COMPLETE", "All done")),
+        ]
+    }
+
+    #[test]
+    fn test_replay_with_same_config_is_faithful() {
+        let config = RalphConfig::parse_yaml(HATS_YAML).unwrap();
+        let recording = record_loop(&config, &script());
+
+        assert_eq!(recording.start_topic, "task.start");
+        assert_eq!(recording.prompt, "Build the thing");
+        assert_eq!(recording.iterations.len(), 4);
+        assert_eq!(recording.termination.as_deref(), Some("completed"));
+
+        let report = LoopReplayer::new(config).run(&recording);
+
+        assert!(report.is_faithful(), "{:?}", report.divergences);
+        assert_eq!(report.iterations, 4);
+        assert_eq!(report.hats, vec!["ralph", "builder", "reviewer", "ralph"]);
+        assert_eq!(
+            report.termination,
+            Some(TerminationReason::CompletionPromise)
+        );
+    }
+
+    #[test]
+    fn test_replay_reports_hat_and_event_divergences() {
+        let config = RalphConfig::parse_yaml(HATS_YAML).unwrap();
+        let recording = record_loop(&config, &script());
+
+        // Reviewer no longer triggers on build.done, so Ralph picks it up instead
+        let changed = HATS_YAML.replace(r#"triggers: ["build.done"]"#, r#"triggers: ["qa.done"]"#);
+        let report = LoopReplayer::new(RalphConfig::parse_yaml(&changed).unwrap()).run(&recording);
+
+        assert!(!report.is_faithful());
+        assert!(report.divergences.contains(&Divergence::HatMismatch {
+            iteration: 3,
+            recorded: "reviewer".to_string(),
+            replayed: "ralph".to_string(),
+        }));
+    }
+
+    #[test]
+    fn test_replay_reports_rejected_event() {
+        let config = RalphConfig::parse_yaml(HATS_YAML).unwrap();
+        let mut steps = script();
+        // build.done without backpressure evidence is rejected as build.blocked
+        steps[1].1 = jsonl_event("build.done", "done");
+        let recording = record_loop(&config, &steps);
+        let mut tampered = recording.clone();
+        tampered.iterations[1].published = vec!["build.done".to_string()];
+
+        let report = LoopReplayer::new(config).run(&tampered);
+
+        assert!(report.divergences.contains(&Divergence::EventRejected {
+            iteration: 2,
+            topic: "build.done".to_string(),
+        }));
+        assert!(report.divergences.contains(&Divergence::UnexpectedEvent {
+            iteration: 2,
+            topic: "build.blocked".to_string(),
+        }));
+    }
+
+    #[test]
+    fn test_replay_reports_termination_mismatch() {
+        let config = RalphConfig::parse_yaml(HATS_YAML).unwrap();
+        let recording = record_loop(&config, &script());
+
+        let limited = HATS_YAML.replace("max_iterations: 10", "max_iterations: 2");
+        let report = LoopReplayer::new(RalphConfig::parse_yaml(&limited).unwrap()).run(&recording);
+
+        assert_eq!(report.termination, Some(TerminationReason::MaxIterations));
+        assert!(
+            report
+                .divergences
+                .contains(&Divergence::TerminationMismatch {
+                    recorded: Some("completed".to_string()),
+                    replayed: "max_iterations".to_string(),
+                })
+        );
+    }
+
+    #[test]
+    fn test_replay_reports_exhausted_recording() {
+        let config = RalphConfig::parse_yaml(HATS_YAML).unwrap();
+        let mut recording = record_loop(&config, &script());
+        recording.iterations.truncate(2);
+
+        let report = LoopReplayer::new(config).run(&recording);
+
+        assert_eq!(report.termination, None);
+        assert!(
+            report
+                .divergences
+                .contains(&Divergence::RecordingExhausted { iteration: 3 })
+        );
+    }
+
+    #[test]
+    fn test_replay_mirrors_watchdogs_and_approvals() {
+        let yaml = HATS_YAML.replace(
+            "  max_iterations: 10\n",
+            "  max_iterations: 10\n  watchdogs:\n    - expect: deploy.done\n      within_iterations: 2\n      else: deploy.overdue\n",
+        ) + "events:\n  build.done:\n    requires_approval: true\n";
+        let config = RalphConfig::parse_yaml(&yaml).unwrap();
+        let outside = Outside {
+            decisions: &[(2, "approve")],
+            ..Outside::default()
+        };
+        let recording = record_loop_with(&config, &script(), &outside);
+
+        assert_eq!(recording.decisions.len(), 1);
+        assert!(
+            recording.iterations[1]
+                .published
+                .iter()
+                .any(|t| t == "deploy.overdue")
+        );
+        assert!(
+            recording.iterations[1]
+                .published
+                .iter()
+                .any(|t| t == "build.done")
+        );
+
+        let report = LoopReplayer::new(config.clone()).run(&recording);
+        assert!(report.is_faithful(), "{:?}", report.divergences);
+
+        // Without the human's decision the build stays held
+        let mut undecided = recording.clone();
+        undecided.decisions.clear();
+        let report = LoopReplayer::new(config).run(&undecided);
+        assert!(
+            report.divergences.contains(&Divergence::EventRejected {
+                iteration: 2,
+                topic: "build.done".to_string(),
+            }),
+            "{:?}",
+            report.divergences
+        );
+    }
+
+    #[test]
+    fn test_replay_rejects_events_of_hat_outside_its_write_scope() {
+        let yaml = HATS_YAML.replace(
+            "    publishes: [\"build.done\"]\n",
+            "    publishes: [\"build.done\"]\n    write_scope:\n      allow: [\"src/**\"]\n      on_violation: reject\n",
+        );
+        let config = RalphConfig::parse_yaml(&yaml).unwrap();
+        let outside = Outside {
+            changed_files: &[(2, &["src/lib.rs", "Cargo.toml"])],
+            ..Outside::default()
+        };
+        let recording = record_loop_with(&config, &script(), &outside);
+        assert_eq!(
+            recording.iterations[1].changed_files,
+            Some(vec!["src/lib.rs".to_string(), "Cargo.toml".to_string()])
+        );
+        assert!(
+            !recording.iterations[1]
+                .published
+                .iter()
+                .any(|t| t == "build.done")
+        );
+
+        let report = LoopReplayer::new(config).run(&recording);
+        assert!(report.is_faithful(), "{:?}", report.divergences);
+
+        // Widening the scope lets the build through, which the replay reports
+        let widened = yaml.replace("allow: [\"src/**\"]", "allow: [\"**\"]");
+        let report = LoopReplayer::new(RalphConfig::parse_yaml(&widened).unwrap()).run(&recording);
+        assert!(report.divergences.contains(&Divergence::UnexpectedEvent {
+            iteration: 2,
+            topic: "build.done".to_string(),
+        }));
+    }
+
+    #[test]
+    fn test_recording_without_output_is_rejected() {
+        let line = serde_json::to_string(&Record::from_bus_event(&Event::new(
+            "task.start",
+            "Build the thing",
+        )))
+        .unwrap();
+        let player = SessionPlayer::from_bytes(line.as_bytes()).unwrap();
+
+        let err = LoopRecording::from_player(&player).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_divergence_display() {
+        let divergence = Divergence::HatMismatch {
+            iteration: 2,
+            recorded: "builder".to_string(),
+            replayed: "ralph".to_string(),
+        };
+        assert_eq!(
+            divergence.to_string(),
+            "iteration 2: recorded hat 'builder', replay selected 'ralph'"
+        );
+    }
+}
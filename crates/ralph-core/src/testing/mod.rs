@@ -1,15 +1,23 @@
 //! Testing utilities for deterministic E2E tests.
 
+#[cfg(feature = "recording")]
+pub mod loop_replay;
 pub mod mock_backend;
 #[cfg(feature = "recording")]
 pub mod replay_backend;
+pub mod report;
 pub mod scenario;
 #[cfg(feature = "recording")]
 pub mod smoke_runner;
 
+#[cfg(feature = "recording")]
+pub use loop_replay::{
+    Divergence, LoopRecording, LoopReplayReport, LoopReplayer, RecordedDecision, RecordedIteration,
+};
 pub use mock_backend::{ExecutionRecord, MockBackend};
 #[cfg(feature = "recording")]
 pub use replay_backend::{ReplayBackend, ReplayTimingMode};
+pub use report::{TestCase, TestFailure};
 pub use scenario::{ExecutionTrace, Scenario, ScenarioRunner};
 #[cfg(feature = "recording")]
 pub use smoke_runner::{
@@ -0,0 +1,278 @@
+//! JUnit XML and TAP output for test results.
+//!
+//! Both the e2e harness and the smoke fixture runner map their results onto
+//! [`TestCase`] and render them here, so CI sees the same formats from either.
+//!
+//! # Example
+//!
+//! ```
+//! use ralph_core::testing::report::{TestCase, junit_xml, tap};
+//! use std::time::Duration;
+//!
+//! let cases = vec![
+//!     TestCase::passed("smoke", "basic_session", Duration::from_millis(12)),
+//!     TestCase::failed("smoke", "broken", Duration::ZERO, "fixture exhausted", ""),
+//! ];
+//!
+//! assert!(junit_xml("ralph-smoke", &cases).contains("<testsuite name=\"smoke\""));
+//! assert!(tap(&cases).starts_with("TAP version 13\n1..2\n"));
+//! ```
+
+use std::fmt::Write;
+use std::time::Duration;
+
+/// A single test case result.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestCase {
+    /// Suite the case belongs to (a JUnit `<testsuite>`).
+    pub suite: String,
+    /// Test case name.
+    pub name: String,
+    /// JUnit `classname`; defaults to the suite name when `None`.
+    pub classname: Option<String>,
+    /// How long the case took.
+    pub duration: Duration,
+    /// Failure details, if the case failed.
+    pub failure: Option<TestFailure>,
+}
+
+/// Why a test case failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TestFailure {
+    /// One-line summary.
+    pub message: String,
+    /// Multi-line details (assertion output, errors).
+    pub details: String,
+}
+
+impl TestCase {
+    /// Creates a passing test case.
+    pub fn passed(suite: impl Into<String>, name: impl Into<String>, duration: Duration) -> Self {
+        Self {
+            suite: suite.into(),
+            name: name.into(),
+            classname: None,
+            duration,
+            failure: None,
+        }
+    }
+
+    /// Creates a failing test case.
+    pub fn failed(
+        suite: impl Into<String>,
+        name: impl Into<String>,
+        duration: Duration,
+        message: impl Into<String>,
+        details: impl Into<String>,
+    ) -> Self {
+        Self {
+            failure: Some(TestFailure {
+                message: message.into(),
+                details: details.into(),
+            }),
+            ..Self::passed(suite, name, duration)
+        }
+    }
+
+    /// Sets the JUnit classname.
+    pub fn with_classname(mut self, classname: impl Into<String>) -> Self {
+        self.classname = Some(classname.into());
+        self
+    }
+
+    /// Returns true if the case passed.
+    pub fn is_passed(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+/// Renders test cases as a JUnit XML document.
+///
+/// Suites appear in the order their first case does; `name` labels the
+/// top-level `<testsuites>` element.
+pub fn junit_xml(name: &str, cases: &[TestCase]) -> String {
+    let mut suites: Vec<(&str, Vec<&TestCase>)> = Vec::new();
+    for case in cases {
+        match suites.iter_mut().find(|(suite, _)| *suite == case.suite) {
+            Some((_, members)) => members.push(case),
+            None => suites.push((&case.suite, vec![case])),
+        }
+    }
+
+    let failures = cases.iter().filter(|c| !c.is_passed()).count();
+    let total_time: Duration = cases.iter().map(|c| c.duration).sum();
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    let _ = writeln!(
+        xml,
+        "<testsuites name=\"{}\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\">",
+        escape_xml(name),
+        cases.len(),
+        failures,
+        total_time.as_secs_f64()
+    );
+
+    for (suite, members) in suites {
+        let suite_failures = members.iter().filter(|c| !c.is_passed()).count();
+        let suite_time: Duration = members.iter().map(|c| c.duration).sum();
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\">",
+            escape_xml(suite),
+            members.len(),
+            suite_failures,
+            suite_time.as_secs_f64()
+        );
+
+        for case in members {
+            let classname = case.classname.as_deref().unwrap_or(suite);
+            let open = format!(
+                "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\"",
+                escape_xml(&case.name),
+                escape_xml(classname),
+                case.duration.as_secs_f64()
+            );
+            match &case.failure {
+                None => {
+                    let _ = writeln!(xml, "{open}/>");
+                }
+                Some(failure) => {
+                    let _ = writeln!(xml, "{open}>");
+                    let _ = writeln!(
+                        xml,
+                        "      <failure message=\"{}\">{}</failure>",
+                        escape_xml(&failure.message),
+                        escape_xml(&failure.details)
+                    );
+                    xml.push_str("    </testcase>\n");
+                }
+            }
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+/// Renders test cases as a TAP version 13 stream.
+///
+/// Failure details are attached as a YAML diagnostic block.
+pub fn tap(cases: &[TestCase]) -> String {
+    let mut out = format!("TAP version 13\n1..{}\n", cases.len());
+
+    for (index, case) in cases.iter().enumerate() {
+        let status = if case.is_passed() { "ok" } else { "not ok" };
+        let _ = writeln!(
+            out,
+            "{} {} - {} {}",
+            status,
+            index + 1,
+            case.suite.replace('#', "\\#"),
+            case.name.replace('#', "\\#")
+        );
+
+        if let Some(failure) = &case.failure {
+            out.push_str("  ---\n");
+            let _ = writeln!(out, "  message: {}", yaml_scalar(&failure.message));
+            let _ = writeln!(out, "  duration_ms: {}", case.duration.as_millis());
+            if !failure.details.is_empty() {
+                out.push_str("  details: |\n");
+                for line in failure.details.lines() {
+                    let _ = writeln!(out, "    {line}");
+                }
+            }
+            out.push_str("  ...\n");
+        }
+    }
+
+    out
+}
+
+/// Escapes text for use in XML attributes and content.
+fn escape_xml(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Control characters other than tab/newline are invalid in XML 1.0
+            c if c.is_control() && c != '\t' && c != '\n' && c != '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Quotes a single-line string as a YAML scalar.
+fn yaml_scalar(s: &str) -> String {
+    let flat = s.replace('\n', " ");
+    format!("\"{}\"", flat.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cases() -> Vec<TestCase> {
+        vec![
+            TestCase::passed("Tier 1", "connect", Duration::from_millis(1500)),
+            TestCase::failed(
+                "Tier 2",
+                "completion",
+                Duration::from_secs(2),
+                "1 assertion failed",
+                "LOOP_COMPLETE: expected <event>, got nothing",
+            )
+            .with_classname("claude"),
+            TestCase::passed("Tier 1", "auth", Duration::ZERO),
+        ]
+    }
+
+    #[test]
+    fn test_junit_groups_suites_in_first_seen_order() {
+        let xml = junit_xml("e2e", &cases());
+
+        assert!(xml.starts_with("<?xml"));
+        assert!(
+            xml.contains("<testsuites name=\"e2e\" tests=\"3\" failures=\"1\" time=\"3.500\">")
+        );
+        let tier1 = xml.find("<testsuite name=\"Tier 1\" tests=\"2\"").unwrap();
+        let tier2 = xml
+            .find("<testsuite name=\"Tier 2\" tests=\"1\" failures=\"1\"")
+            .unwrap();
+        assert!(tier1 < tier2);
+        assert!(xml.contains("<testcase name=\"connect\" classname=\"Tier 1\" time=\"1.500\"/>"));
+    }
+
+    #[test]
+    fn test_junit_failure_is_escaped() {
+        let xml = junit_xml("e2e", &cases());
+
+        assert!(xml.contains("classname=\"claude\""));
+        assert!(xml.contains(
+            "<failure message=\"1 assertion failed\">LOOP_COMPLETE: expected &lt;event&gt;, got nothing</failure>"
+        ));
+    }
+
+    #[test]
+    fn test_tap_output() {
+        let out = tap(&cases());
+
+        assert!(out.starts_with("TAP version 13\n1..3\n"));
+        assert!(out.contains("ok 1 - Tier 1 connect\n"));
+        assert!(out.contains("not ok 2 - Tier 2 completion\n"));
+        assert!(out.contains("  message: \"1 assertion failed\"\n"));
+        assert!(out.contains("  details: |\n    LOOP_COMPLETE: expected <event>, got nothing\n"));
+        assert!(out.contains("ok 3 - Tier 1 auth\n"));
+    }
+
+    #[test]
+    fn test_empty_results() {
+        assert!(tap(&[]).starts_with("TAP version 13\n1..0\n"));
+        assert!(junit_xml("none", &[]).contains("tests=\"0\""));
+    }
+}
@@ -0,0 +1,502 @@
+//! Delayed events and watchdog timers.
+//!
+//! Two kinds of timer feed events back into the loop:
+//!
+//! - **Delayed events** come from `ralph emit --delay 5m` or
+//!   `--after-iterations 2` and are published once they fall due.
+//! - **Watchdogs** come from `event_loop.watchdogs` in config. Each one waits
+//!   for an `expect` topic and publishes its `else` topic if that topic does
+//!   not arrive in time.
+//!
+//! Deadlines may be wall-clock, iteration-based, or both (whichever comes
+//! first). Timers are persisted to `.ralph/agent/timers.json` so that
+//! `ralph run --continue` picks up where the previous run stopped.
+
+use chrono::{DateTime, Utc};
+use ralph_proto::{Event, Topic};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::time::Duration;
+
+/// A watchdog declared under `event_loop.watchdogs`.
+///
+/// ```yaml
+/// event_loop:
+///   watchdogs:
+///     - expect: build.done
+///       after: build.task       # arm when build.task is published (default: loop start)
+///       within: 20m
+///       within_iterations: 3
+///       else: build.timeout
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct WatchdogConfig {
+    /// Topic pattern that satisfies the watchdog.
+    pub expect: String,
+
+    /// Wall-clock deadline, e.g. `90s`, `20m`, `1h30m`.
+    #[serde(default)]
+    pub within: Option<String>,
+
+    /// Iteration deadline, counted from when the watchdog is armed.
+    #[serde(default)]
+    pub within_iterations: Option<u32>,
+
+    /// Topic published when the deadline passes.
+    #[serde(rename = "else")]
+    pub else_topic: String,
+
+    /// Topic pattern that (re-)arms the watchdog. Armed at loop start when unset.
+    #[serde(default)]
+    pub after: Option<String>,
+}
+
+impl WatchdogConfig {
+    /// Validates deadlines, returning a human-readable reason on failure.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.expect.trim().is_empty() {
+            return Err("'expect' must not be empty".to_string());
+        }
+        if self.else_topic.trim().is_empty() {
+            return Err("'else' must not be empty".to_string());
+        }
+        if self.within.is_none() && self.within_iterations.is_none() {
+            return Err("set 'within' and/or 'within_iterations'".to_string());
+        }
+        if self.within_iterations == Some(0) {
+            return Err("'within_iterations' must be at least 1".to_string());
+        }
+        if let Some(within) = &self.within {
+            parse_duration(within)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses a duration such as `45s`, `20m`, `2h`, `1h30m` or `1d`.
+///
+/// A bare number is taken as seconds.
+pub fn parse_duration(input: &str) -> Result<Duration, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err("empty duration".to_string());
+    }
+    if let Ok(secs) = input.parse::<u64>() {
+        return Ok(Duration::from_secs(secs));
+    }
+
+    let mut total = 0u64;
+    let mut digits = String::new();
+    for c in input.chars() {
+        if c.is_ascii_digit() {
+            digits.push(c);
+            continue;
+        }
+        let unit = match c {
+            's' => 1,
+            'm' => 60,
+            'h' => 3600,
+            'd' => 86400,
+            _ => {
+                return Err(format!(
+                    "invalid duration '{input}' (use e.g. 90s, 20m, 1h30m)"
+                ));
+            }
+        };
+        let value: u64 = digits
+            .parse()
+            .map_err(|_| format!("invalid duration '{input}' (use e.g. 90s, 20m, 1h30m)"))?;
+        total = total.saturating_add(value.saturating_mul(unit));
+        digits.clear();
+    }
+    if !digits.is_empty() {
+        return Err(format!(
+            "invalid duration '{input}' (missing unit after {digits})"
+        ));
+    }
+    Ok(Duration::from_secs(total))
+}
+
+/// Formats a duration compactly for status displays (`4m`, `1h12m`, `30s`).
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 3600 {
+        let mins = (secs % 3600) / 60;
+        if mins == 0 {
+            format!("{}h", secs / 3600)
+        } else {
+            format!("{}h{}m", secs / 3600, mins)
+        }
+    } else if secs >= 60 {
+        format!("{}m", secs.div_ceil(60))
+    } else {
+        format!("{secs}s")
+    }
+}
+
+/// What a timer does when it falls due.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TimerKind {
+    /// Publish a previously emitted event.
+    Delayed { topic: String, payload: String },
+    /// Publish `else_topic` unless `expect` arrives first.
+    Watchdog { expect: String, else_topic: String },
+}
+
+/// A pending timer.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Timer {
+    /// What to publish when due.
+    #[serde(flatten)]
+    pub kind: TimerKind,
+    /// Wall-clock deadline.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_at: Option<DateTime<Utc>>,
+    /// Iteration deadline (due once the loop has completed this many iterations).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub due_iteration: Option<u32>,
+}
+
+impl Timer {
+    /// Returns the topic this timer publishes when it fires.
+    pub fn topic(&self) -> &str {
+        match &self.kind {
+            TimerKind::Delayed { topic, .. } => topic,
+            TimerKind::Watchdog { else_topic, .. } => else_topic,
+        }
+    }
+
+    /// Checks whether either deadline has passed.
+    pub fn is_due(&self, now: DateTime<Utc>, iteration: u32) -> bool {
+        self.due_at.is_some_and(|at| now >= at)
+            || self.due_iteration.is_some_and(|due| iteration >= due)
+    }
+
+    /// Builds the event published when the timer fires.
+    fn to_event(&self) -> Event {
+        match &self.kind {
+            TimerKind::Delayed { topic, payload } => Event::new(topic.as_str(), payload.as_str()),
+            TimerKind::Watchdog { expect, else_topic } => Event::new(
+                else_topic.as_str(),
+                format!("Watchdog expired: '{expect}' did not arrive in time"),
+            ),
+        }
+    }
+
+    /// One-line description for status displays, e.g. `build.timeout in 4m or 2 iterations`.
+    pub fn describe(&self, now: DateTime<Utc>, iteration: u32) -> String {
+        let mut deadlines = Vec::new();
+        if let Some(at) = self.due_at {
+            let left = (at - now).to_std().unwrap_or_default();
+            deadlines.push(format_duration(left));
+        }
+        if let Some(due) = self.due_iteration {
+            let left = due.saturating_sub(iteration);
+            deadlines.push(format!(
+                "{left} iteration{}",
+                if left == 1 { "" } else { "s" }
+            ));
+        }
+        let label = match &self.kind {
+            TimerKind::Delayed { topic, .. } => topic.clone(),
+            TimerKind::Watchdog { expect, else_topic } => {
+                format!("{else_topic} (awaiting {expect})")
+            }
+        };
+        format!("{label} in {}", deadlines.join(" or "))
+    }
+}
+
+/// The set of pending timers for a loop.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct TimerSet {
+    /// Iteration at which the set was last saved, used to rebase
+    /// iteration deadlines when a resumed loop restarts its count.
+    #[serde(default)]
+    iteration: u32,
+    timers: Vec<Timer>,
+}
+
+impl TimerSet {
+    /// Creates an empty timer set.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns true if no timers are pending.
+    pub fn is_empty(&self) -> bool {
+        self.timers.is_empty()
+    }
+
+    /// Returns the number of pending timers.
+    pub fn len(&self) -> usize {
+        self.timers.len()
+    }
+
+    /// Iterates over pending timers, soonest wall-clock deadline first.
+    pub fn iter(&self) -> impl Iterator<Item = &Timer> {
+        self.timers.iter()
+    }
+
+    /// Schedules a timer.
+    pub fn schedule(&mut self, timer: Timer) {
+        self.timers.push(timer);
+        self.timers.sort_by_key(|t| (t.due_at.is_none(), t.due_at));
+    }
+
+    /// Schedules an event for later delivery.
+    pub fn schedule_event(
+        &mut self,
+        topic: &str,
+        payload: &str,
+        due_at: Option<DateTime<Utc>>,
+        due_iteration: Option<u32>,
+    ) {
+        self.schedule(Timer {
+            kind: TimerKind::Delayed {
+                topic: topic.to_string(),
+                payload: payload.to_string(),
+            },
+            due_at,
+            due_iteration,
+        });
+    }
+
+    /// Arms watchdogs triggered by `topic`, or those without `after` when `topic` is `None`.
+    ///
+    /// An already-armed watchdog is re-armed with a fresh deadline.
+    pub fn arm_watchdogs(
+        &mut self,
+        watchdogs: &[WatchdogConfig],
+        topic: Option<&str>,
+        now: DateTime<Utc>,
+        iteration: u32,
+    ) {
+        for watchdog in watchdogs {
+            let triggered = match (&watchdog.after, topic) {
+                (None, None) => true,
+                (Some(after), Some(topic)) => Topic::new(after).matches_str(topic),
+                _ => false,
+            };
+            if !triggered {
+                continue;
+            }
+
+            let kind = TimerKind::Watchdog {
+                expect: watchdog.expect.clone(),
+                else_topic: watchdog.else_topic.clone(),
+            };
+            self.timers.retain(|t| t.kind != kind);
+            let due_at = watchdog
+                .within
+                .as_deref()
+                .and_then(|w| parse_duration(w).ok())
+                .and_then(|d| chrono::Duration::from_std(d).ok())
+                .map(|d| now + d);
+            let due_iteration = watchdog.within_iterations.map(|n| iteration + n);
+            self.schedule(Timer {
+                kind,
+                due_at,
+                due_iteration,
+            });
+        }
+    }
+
+    /// Returns true if the watchdog is currently armed.
+    pub fn has_watchdog(&self, watchdog: &WatchdogConfig) -> bool {
+        self.timers.iter().any(|t| {
+            matches!(&t.kind, TimerKind::Watchdog { expect, else_topic }
+                if *expect == watchdog.expect && *else_topic == watchdog.else_topic)
+        })
+    }
+
+    /// Disarms watchdogs satisfied by a published topic.
+    pub fn observe(&mut self, topic: &str) {
+        self.timers.retain(|t| match &t.kind {
+            TimerKind::Watchdog { expect, .. } => !Topic::new(expect).matches_str(topic),
+            TimerKind::Delayed { .. } => true,
+        });
+    }
+
+    /// Removes due timers and returns the events they publish.
+    pub fn take_due(&mut self, now: DateTime<Utc>, iteration: u32) -> Vec<Event> {
+        let (due, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut self.timers)
+            .into_iter()
+            .partition(|t| t.is_due(now, iteration));
+        self.timers = pending;
+        due.iter().map(Timer::to_event).collect()
+    }
+
+    /// Time until the earliest wall-clock deadline, if any.
+    pub fn next_due_in(&self, now: DateTime<Utc>) -> Option<Duration> {
+        self.timers
+            .iter()
+            .filter_map(|t| t.due_at)
+            .min()
+            .map(|at| (at - now).to_std().unwrap_or_default())
+    }
+
+    /// Loads a saved timer set, rebasing iteration deadlines onto `iteration`.
+    ///
+    /// A missing file yields an empty set.
+    pub fn load(path: &Path, iteration: u32) -> std::io::Result<Self> {
+        let content = match std::fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::new()),
+            Err(e) => return Err(e),
+        };
+        let mut set: Self = serde_json::from_str(&content)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        for timer in &mut set.timers {
+            if let Some(due) = timer.due_iteration.as_mut() {
+                *due = due.saturating_sub(set.iteration) + iteration;
+            }
+        }
+        set.iteration = iteration;
+        Ok(set)
+    }
+
+    /// Saves the timer set, removing the file when no timers are pending.
+    pub fn save(&mut self, path: &Path, iteration: u32) -> std::io::Result<()> {
+        if self.timers.is_empty() {
+            return match std::fs::remove_file(path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+                _ => Ok(()),
+            };
+        }
+        self.iteration = iteration;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        std::fs::write(path, json)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn watchdog(
+        within: Option<&str>,
+        iterations: Option<u32>,
+        after: Option<&str>,
+    ) -> WatchdogConfig {
+        WatchdogConfig {
+            expect: "build.done".to_string(),
+            within: within.map(str::to_string),
+            within_iterations: iterations,
+            else_topic: "build.timeout".to_string(),
+            after: after.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_duration("45s").unwrap(), Duration::from_secs(45));
+        assert_eq!(parse_duration("20m").unwrap(), Duration::from_mins(20));
+        assert_eq!(parse_duration("1h30m").unwrap(), Duration::from_mins(90));
+        assert_eq!(parse_duration("1d").unwrap(), Duration::from_hours(24));
+        assert!(parse_duration("").is_err());
+        assert!(parse_duration("20 minutes").is_err());
+        assert!(parse_duration("1h30").is_err());
+        assert!(parse_duration("m").is_err());
+    }
+
+    #[test]
+    fn test_watchdog_validation() {
+        assert!(watchdog(Some("20m"), None, None).validate().is_ok());
+        assert!(watchdog(None, Some(3), None).validate().is_ok());
+        assert!(watchdog(None, None, None).validate().is_err());
+        assert!(watchdog(None, Some(0), None).validate().is_err());
+        assert!(watchdog(Some("soon"), None, None).validate().is_err());
+    }
+
+    #[test]
+    fn test_delayed_event_fires_on_either_deadline() {
+        let now = Utc::now();
+        let mut timers = TimerSet::new();
+        timers.schedule_event(
+            "review.start",
+            "later",
+            Some(now + chrono::Duration::minutes(5)),
+            None,
+        );
+        timers.schedule_event("deploy.check", "", None, Some(2));
+
+        assert!(timers.take_due(now, 1).is_empty());
+        let fired = timers.take_due(now, 2);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].topic.as_str(), "deploy.check");
+
+        let fired = timers.take_due(now + chrono::Duration::minutes(5), 2);
+        assert_eq!(fired[0].topic.as_str(), "review.start");
+        assert_eq!(fired[0].payload, "later");
+        assert!(timers.is_empty());
+    }
+
+    #[test]
+    fn test_watchdog_arms_fires_and_disarms() {
+        let now = Utc::now();
+        let config = [watchdog(Some("20m"), Some(3), Some("build.task"))];
+        let mut timers = TimerSet::new();
+
+        // Not armed at loop start because it waits for build.task
+        timers.arm_watchdogs(&config, None, now, 0);
+        assert!(timers.is_empty());
+
+        timers.arm_watchdogs(&config, Some("build.task"), now, 1);
+        assert_eq!(timers.len(), 1);
+        // Re-arming replaces rather than duplicates
+        timers.arm_watchdogs(&config, Some("build.task"), now, 2);
+        assert_eq!(timers.len(), 1);
+
+        assert!(timers.take_due(now, 4).is_empty());
+        let fired = timers.take_due(now, 5);
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].topic.as_str(), "build.timeout");
+
+        timers.arm_watchdogs(&config, Some("build.task"), now, 6);
+        timers.observe("build.done");
+        assert!(timers.is_empty());
+    }
+
+    #[test]
+    fn test_describe_and_next_due() {
+        let now = Utc::now();
+        let mut timers = TimerSet::new();
+        timers.arm_watchdogs(&[watchdog(Some("20m"), Some(3), None)], None, now, 1);
+        let timer = timers.iter().next().unwrap();
+        assert_eq!(
+            timer.describe(now, 2),
+            "build.timeout (awaiting build.done) in 20m or 2 iterations"
+        );
+        assert_eq!(timers.next_due_in(now), Some(Duration::from_mins(20)));
+    }
+
+    #[test]
+    fn test_save_and_load_rebases_iterations() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("agent/timers.json");
+        let now = Utc::now();
+
+        let mut timers = TimerSet::new();
+        timers.schedule_event("later", "", Some(now), Some(10));
+        timers.save(&path, 8).unwrap();
+
+        // A resumed loop restarts at iteration 0: two iterations remain.
+        let loaded = TimerSet::load(&path, 0).unwrap();
+        let timer = loaded.iter().next().unwrap();
+        assert_eq!(timer.due_iteration, Some(2));
+        assert_eq!(timer.due_at, Some(now));
+
+        let mut empty = TimerSet::new();
+        empty.save(&path, 0).unwrap();
+        assert!(!path.exists());
+        assert!(TimerSet::load(&path, 0).unwrap().is_empty());
+    }
+}
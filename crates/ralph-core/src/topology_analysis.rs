@@ -0,0 +1,668 @@
+//! Static analysis of the hat event graph.
+//!
+//! Builds on [`HatRegistry`] to answer questions that basic config validation
+//! cannot: which hats can ever run from the entry topic, which events go
+//! nowhere, which publish cycles can spin forever, and whether the loop can
+//! reach the completion promise at all.
+//!
+//! Ralph is modelled as a coordinator node: Ralph receives the entry topic when
+//! no hat subscribes to it, and any event no hat handles. Once active, Ralph
+//! can dispatch any hat trigger and emit the completion promise.
+
+use crate::config::RalphConfig;
+use crate::hat_registry::HatRegistry;
+use ralph_proto::{Hat, HatId, Topic};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt;
+
+/// Topics the orchestrator emits on its own, independent of any hat.
+const SYSTEM_TOPICS: &[&str] = &[
+    "task.start",
+    "task.resume",
+    "human.response",
+    "event.malformed",
+    "build.blocked",
+    "review.blocked",
+    "verify.failed",
+    "build.task.abandoned",
+];
+
+/// How serious a topology issue is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum IssueSeverity {
+    /// The loop can run, but part of the topology is suspect.
+    Warning,
+    /// The loop cannot run as configured.
+    Error,
+}
+
+/// A problem found in the hat topology.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TopologyIssue {
+    /// `starting_event` is set but no hat subscribes to it.
+    StartingEventUnhandled { topic: String },
+    /// A hat publishes an event that no hat subscribes to.
+    OrphanPublish { hat: String, topic: String },
+    /// A hat triggers on an event that nothing can ever publish.
+    OrphanTrigger { hat: String, topic: String },
+    /// A hat can never be activated from the entry topic.
+    UnreachableHat { hat: String },
+    /// Hats that can re-trigger each other with no `max_activations` guard.
+    UnguardedCycle { hats: Vec<String>, has_exit: bool },
+    /// Nothing reachable can emit the completion promise.
+    CompletionUnreachable { promise: String },
+}
+
+impl TopologyIssue {
+    /// Returns the severity of this issue.
+    pub fn severity(&self) -> IssueSeverity {
+        match self {
+            Self::StartingEventUnhandled { .. } | Self::CompletionUnreachable { .. } => {
+                IssueSeverity::Error
+            }
+            Self::UnguardedCycle { has_exit, .. } if !has_exit => IssueSeverity::Error,
+            _ => IssueSeverity::Warning,
+        }
+    }
+}
+
+impl fmt::Display for TopologyIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::StartingEventUnhandled { topic } => {
+                write!(f, "starting_event '{topic}' has no subscribers")
+            }
+            Self::OrphanPublish { hat, topic } => {
+                write!(
+                    f,
+                    "Event '{topic}' published by '{hat}' has no hat subscribers"
+                )
+            }
+            Self::OrphanTrigger { hat, topic } => {
+                write!(f, "Trigger '{topic}' of '{hat}' is never published")
+            }
+            Self::UnreachableHat { hat } => {
+                write!(f, "Hat '{hat}' is unreachable from the entry topic")
+            }
+            Self::UnguardedCycle { hats, has_exit } => {
+                write!(
+                    f,
+                    "Cycle {} has no max_activations guard",
+                    hats.join(" -> ")
+                )?;
+                if !has_exit {
+                    write!(f, " and no exit")?;
+                }
+                Ok(())
+            }
+            Self::CompletionUnreachable { promise } => {
+                write!(
+                    f,
+                    "No reachable hat can lead to completion promise '{promise}'"
+                )
+            }
+        }
+    }
+}
+
+/// Result of analyzing a hat topology.
+#[derive(Debug, Clone, Default)]
+pub struct TopologyReport {
+    /// Topic the loop starts from.
+    pub entry_topic: String,
+    /// Hats that can be activated from the entry topic.
+    pub reachable_hats: BTreeSet<HatId>,
+    /// Whether Ralph ever coordinates (receives an event no hat handles).
+    pub ralph_active: bool,
+    /// Whether the completion promise can be emitted.
+    pub completion_reachable: bool,
+    /// Issues found, in a stable order.
+    pub issues: Vec<TopologyIssue>,
+}
+
+impl TopologyReport {
+    /// Returns issues with error severity.
+    pub fn errors(&self) -> impl Iterator<Item = &TopologyIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity() == IssueSeverity::Error)
+    }
+
+    /// Returns issues with warning severity.
+    pub fn warnings(&self) -> impl Iterator<Item = &TopologyIssue> {
+        self.issues
+            .iter()
+            .filter(|issue| issue.severity() == IssueSeverity::Warning)
+    }
+
+    /// Returns true if any issue is an error.
+    pub fn has_errors(&self) -> bool {
+        self.errors().next().is_some()
+    }
+}
+
+/// Analyzes a hat topology for reachability, orphan events and cycles.
+pub struct TopologyAnalyzer<'a> {
+    registry: &'a HatRegistry,
+    entry_topic: String,
+    starting_event: Option<&'a str>,
+    completion_promise: &'a str,
+}
+
+impl<'a> TopologyAnalyzer<'a> {
+    /// Creates an analyzer for the given configuration and registry.
+    pub fn new(config: &'a RalphConfig, registry: &'a HatRegistry) -> Self {
+        let starting_event = config.event_loop.starting_event.as_deref();
+        Self {
+            registry,
+            entry_topic: starting_event.unwrap_or("task.start").to_string(),
+            starting_event,
+            completion_promise: &config.event_loop.completion_promise,
+        }
+    }
+
+    /// Runs the analysis.
+    pub fn analyze(&self) -> TopologyReport {
+        let mut report = TopologyReport {
+            entry_topic: self.entry_topic.clone(),
+            ..TopologyReport::default()
+        };
+
+        if self.registry.is_empty() {
+            report.ralph_active = true;
+            report.completion_reachable = true;
+            return report;
+        }
+
+        if let Some(start) = self.starting_event
+            && !self.registry.has_subscriber(start)
+        {
+            report.issues.push(TopologyIssue::StartingEventUnhandled {
+                topic: start.to_string(),
+            });
+        }
+
+        self.compute_reachability(&mut report);
+
+        for hat in self.registry.all() {
+            for topic in &hat.publishes {
+                let topic = topic.as_str();
+                if topic != self.completion_promise && !self.registry.has_subscriber(topic) {
+                    report.issues.push(TopologyIssue::OrphanPublish {
+                        hat: hat.name.clone(),
+                        topic: topic.to_string(),
+                    });
+                }
+            }
+        }
+
+        // An active Ralph can publish any trigger, so triggers are only orphaned without Ralph
+        if !report.ralph_active {
+            let published = self.all_publishable_topics();
+            for hat in self.registry.all() {
+                for trigger in &hat.subscriptions {
+                    if !published.iter().any(|topic| trigger.matches_str(topic)) {
+                        report.issues.push(TopologyIssue::OrphanTrigger {
+                            hat: hat.name.clone(),
+                            topic: trigger.as_str().to_string(),
+                        });
+                    }
+                }
+            }
+        }
+
+        for hat in self.registry.all() {
+            if !report.reachable_hats.contains(&hat.id) {
+                report.issues.push(TopologyIssue::UnreachableHat {
+                    hat: hat.name.clone(),
+                });
+            }
+        }
+
+        report.issues.extend(self.unguarded_cycles());
+
+        if !report.completion_reachable {
+            report.issues.push(TopologyIssue::CompletionUnreachable {
+                promise: self.completion_promise.to_string(),
+            });
+        }
+
+        report
+    }
+
+    /// Topics a hat can cause to be published: its declared publishes, its
+    /// `default_publishes`, and `<id>.exhausted` when it has an activation cap.
+    fn emitted_topics(&self, hat: &Hat) -> Vec<String> {
+        let mut topics: Vec<String> = hat
+            .publishes
+            .iter()
+            .map(|t| t.as_str().to_string())
+            .collect();
+        if let Some(config) = self.registry.get_config(&hat.id) {
+            if let Some(default) = &config.default_publishes
+                && !topics.contains(default)
+            {
+                topics.push(default.clone());
+            }
+            if config.max_activations.is_some() {
+                topics.push(format!("{}.exhausted", hat.id));
+            }
+        }
+        topics
+    }
+
+    fn all_publishable_topics(&self) -> BTreeSet<String> {
+        let mut topics: BTreeSet<String> = SYSTEM_TOPICS.iter().map(|t| (*t).to_string()).collect();
+        topics.insert(self.entry_topic.clone());
+        for hat in self.registry.all() {
+            topics.extend(self.emitted_topics(hat));
+        }
+        topics
+    }
+
+    /// Propagates events from the entry topic until nothing new is activated.
+    fn compute_reachability(&self, report: &mut TopologyReport) {
+        let mut seen: BTreeSet<String> = BTreeSet::new();
+        let mut queue: VecDeque<String> = VecDeque::from([self.entry_topic.clone()]);
+        let mut ralph_dispatched = false;
+
+        loop {
+            while let Some(topic) = queue.pop_front() {
+                if !seen.insert(topic.clone()) {
+                    continue;
+                }
+                let subscribers = self.registry.subscribers(&Topic::new(topic.as_str()));
+                if subscribers.is_empty() {
+                    if topic != self.completion_promise {
+                        report.ralph_active = true;
+                    }
+                    continue;
+                }
+                for hat in subscribers {
+                    self.activate(hat, report, &mut queue);
+                }
+            }
+
+            if !report.ralph_active || ralph_dispatched {
+                break;
+            }
+            // Ralph can dispatch any hat directly, including wildcard-only ones
+            ralph_dispatched = true;
+            for hat in self.registry.all() {
+                self.activate(hat, report, &mut queue);
+            }
+        }
+
+        report.completion_reachable = report.ralph_active
+            || report.reachable_hats.iter().any(|id| {
+                self.registry.get(id).is_some_and(|hat| {
+                    self.emitted_topics(hat)
+                        .iter()
+                        .any(|t| t == self.completion_promise)
+                })
+            });
+    }
+
+    fn activate(&self, hat: &Hat, report: &mut TopologyReport, queue: &mut VecDeque<String>) {
+        if report.reachable_hats.insert(hat.id.clone()) {
+            queue.extend(self.emitted_topics(hat));
+        }
+    }
+
+    /// Finds strongly connected groups of hats that can re-trigger each other
+    /// with no member capped by `max_activations`.
+    fn unguarded_cycles(&self) -> Vec<TopologyIssue> {
+        let hats: Vec<&Hat> = self.registry.all().collect();
+        let index: BTreeMap<&HatId, usize> = hats
+            .iter()
+            .enumerate()
+            .map(|(i, hat)| (&hat.id, i))
+            .collect();
+
+        let edges: Vec<Vec<usize>> = hats
+            .iter()
+            .map(|hat| {
+                let mut targets = BTreeSet::new();
+                for topic in self.emitted_topics(hat) {
+                    for subscriber in self.registry.subscribers(&Topic::new(topic)) {
+                        targets.insert(index[&subscriber.id]);
+                    }
+                }
+                targets.into_iter().collect()
+            })
+            .collect();
+
+        let mut issues = Vec::new();
+        for component in strongly_connected_components(&edges) {
+            let is_cycle = component.len() > 1 || edges[component[0]].contains(&component[0]);
+            if !is_cycle {
+                continue;
+            }
+
+            let guarded = component.iter().any(|&i| {
+                self.registry
+                    .get_config(&hats[i].id)
+                    .is_some_and(|config| config.max_activations.is_some())
+            });
+            if guarded {
+                continue;
+            }
+
+            let has_exit = component.iter().any(|&i| {
+                self.emitted_topics(hats[i]).iter().any(|topic| {
+                    let subscribers = self.registry.subscribers(&Topic::new(topic.as_str()));
+                    topic == self.completion_promise
+                        || subscribers.is_empty()
+                        || subscribers
+                            .iter()
+                            .any(|s| !component.contains(&index[&s.id]))
+                })
+            });
+
+            let mut names: Vec<String> = component.iter().map(|&i| hats[i].name.clone()).collect();
+            names.sort();
+            issues.push(TopologyIssue::UnguardedCycle {
+                hats: names,
+                has_exit,
+            });
+        }
+        issues
+    }
+}
+
+/// Tarjan's algorithm over an adjacency list. Components are returned in
+/// order of their lowest node index so output is deterministic.
+fn strongly_connected_components(edges: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    struct State<'e> {
+        edges: &'e [Vec<usize>],
+        counter: usize,
+        index: Vec<Option<usize>>,
+        low: Vec<usize>,
+        stack: Vec<usize>,
+        on_stack: Vec<bool>,
+        components: Vec<Vec<usize>>,
+    }
+
+    fn visit(state: &mut State<'_>, node: usize) {
+        state.index[node] = Some(state.counter);
+        state.low[node] = state.counter;
+        state.counter += 1;
+        state.stack.push(node);
+        state.on_stack[node] = true;
+
+        for &next in &state.edges[node] {
+            match state.index[next] {
+                None => {
+                    visit(state, next);
+                    state.low[node] = state.low[node].min(state.low[next]);
+                }
+                Some(next_index) if state.on_stack[next] => {
+                    state.low[node] = state.low[node].min(next_index);
+                }
+                Some(_) => {}
+            }
+        }
+
+        if Some(state.low[node]) == state.index[node] {
+            let mut component = Vec::new();
+            while let Some(member) = state.stack.pop() {
+                state.on_stack[member] = false;
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+            component.sort_unstable();
+            state.components.push(component);
+        }
+    }
+
+    let n = edges.len();
+    let mut state = State {
+        edges,
+        counter: 0,
+        index: vec![None; n],
+        low: vec![0; n],
+        stack: Vec::new(),
+        on_stack: vec![false; n],
+        components: Vec::new(),
+    };
+    for node in 0..n {
+        if state.index[node].is_none() {
+            visit(&mut state, node);
+        }
+    }
+
+    let mut components = state.components;
+    components.sort_by_key(|c| c[0]);
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_from(yaml: &str) -> RalphConfig {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    fn analyze(yaml: &str) -> TopologyReport {
+        let config = config_from(yaml);
+        let registry = HatRegistry::from_config(&config);
+        TopologyAnalyzer::new(&config, &registry).analyze()
+    }
+
+    #[test]
+    fn test_empty_registry_has_no_issues() {
+        let report = analyze("cli:\n  backend: claude\n");
+        assert!(report.issues.is_empty());
+        assert!(report.completion_reachable);
+    }
+
+    #[test]
+    fn test_ralph_coordinated_topology_is_clean() {
+        let report = analyze(
+            r#"
+hats:
+  builder:
+    name: "Builder"
+    triggers: ["build.task"]
+    publishes: ["build.done"]
+  reviewer:
+    name: "Reviewer"
+    triggers: ["build.done"]
+    publishes: ["review.done"]
+"#,
+        );
+
+        assert!(report.ralph_active);
+        assert!(report.completion_reachable);
+        assert_eq!(report.reachable_hats.len(), 2);
+        assert_eq!(
+            report.issues,
+            vec![TopologyIssue::OrphanPublish {
+                hat: "Reviewer".to_string(),
+                topic: "review.done".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_starting_event_without_subscriber_is_error() {
+        let report = analyze(
+            r#"
+event_loop:
+  starting_event: "nobody.listens"
+hats:
+  builder:
+    name: "Builder"
+    triggers: ["build.task"]
+    publishes: ["LOOP_COMPLETE"]
+"#,
+        );
+
+        assert!(report.has_errors());
+        assert!(
+            report
+                .issues
+                .contains(&TopologyIssue::StartingEventUnhandled {
+                    topic: "nobody.listens".to_string()
+                })
+        );
+    }
+
+    #[test]
+    fn test_closed_pipeline_finds_unreachable_hats_and_orphan_triggers() {
+        let report = analyze(
+            r#"
+event_loop:
+  starting_event: "plan.start"
+  completion_promise: "LOOP_COMPLETE"
+hats:
+  planner:
+    name: "Planner"
+    triggers: ["plan.start"]
+    publishes: ["build.task"]
+  builder:
+    name: "Builder"
+    triggers: ["build.task"]
+    publishes: ["LOOP_COMPLETE"]
+  auditor:
+    name: "Auditor"
+    triggers: ["audit.request"]
+    publishes: ["build.task"]
+"#,
+        );
+
+        assert!(!report.ralph_active);
+        assert!(report.completion_reachable);
+        assert!(!report.has_errors());
+        assert!(report.issues.contains(&TopologyIssue::UnreachableHat {
+            hat: "Auditor".to_string()
+        }));
+        assert!(report.issues.contains(&TopologyIssue::OrphanTrigger {
+            hat: "Auditor".to_string(),
+            topic: "audit.request".to_string(),
+        }));
+    }
+
+    #[test]
+    fn test_completion_unreachable_without_ralph() {
+        let report = analyze(
+            r#"
+event_loop:
+  starting_event: "plan.start"
+hats:
+  planner:
+    name: "Planner"
+    triggers: ["plan.start"]
+    publishes: ["build.task"]
+  builder:
+    name: "Builder"
+    triggers: ["build.task"]
+    publishes: ["plan.start"]
+"#,
+        );
+
+        assert!(!report.completion_reachable);
+        assert!(
+            report
+                .issues
+                .contains(&TopologyIssue::CompletionUnreachable {
+                    promise: "LOOP_COMPLETE".to_string()
+                })
+        );
+        // Planner <-> Builder loops forever with no way out
+        let cycle = report
+            .issues
+            .iter()
+            .find(|i| matches!(i, TopologyIssue::UnguardedCycle { .. }))
+            .unwrap();
+        assert_eq!(
+            cycle,
+            &TopologyIssue::UnguardedCycle {
+                hats: vec!["Builder".to_string(), "Planner".to_string()],
+                has_exit: false,
+            }
+        );
+        assert_eq!(cycle.severity(), IssueSeverity::Error);
+    }
+
+    #[test]
+    fn test_cycle_with_exit_is_warning_and_guard_silences_it() {
+        let yaml = r#"
+hats:
+  builder:
+    name: "Builder"
+    triggers: ["build.task", "review.rejected"]
+    publishes: ["build.done"]
+  reviewer:
+    name: "Reviewer"
+    triggers: ["build.done"]
+    publishes: ["review.rejected", "review.approved"]
+"#;
+        let report = analyze(yaml);
+        let cycle = report
+            .issues
+            .iter()
+            .find(|i| matches!(i, TopologyIssue::UnguardedCycle { .. }))
+            .unwrap();
+        assert_eq!(cycle.severity(), IssueSeverity::Warning);
+        assert_eq!(
+            cycle.to_string(),
+            "Cycle Builder -> Reviewer has no max_activations guard"
+        );
+
+        let guarded = yaml.replace(
+            "    publishes: [\"build.done\"]\n",
+            "    publishes: [\"build.done\"]\n    max_activations: 3\n",
+        );
+        let report = analyze(&guarded);
+        assert!(
+            !report
+                .issues
+                .iter()
+                .any(|i| matches!(i, TopologyIssue::UnguardedCycle { .. }))
+        );
+    }
+
+    #[test]
+    fn test_self_loop_is_a_cycle() {
+        let report = analyze(
+            r#"
+hats:
+  poller:
+    name: "Poller"
+    triggers: ["poll.tick"]
+    publishes: ["poll.tick", "poll.done"]
+"#,
+        );
+        assert!(report.issues.contains(&TopologyIssue::UnguardedCycle {
+            hats: vec!["Poller".to_string()],
+            has_exit: true,
+        }));
+    }
+
+    #[test]
+    fn test_exhausted_topic_counts_as_published() {
+        let report = analyze(
+            r#"
+event_loop:
+  starting_event: "build.task"
+hats:
+  builder:
+    name: "Builder"
+    triggers: ["build.task"]
+    publishes: ["build.task"]
+    max_activations: 3
+  rescuer:
+    name: "Rescuer"
+    triggers: ["builder.exhausted"]
+    publishes: ["LOOP_COMPLETE"]
+"#,
+        );
+
+        assert!(report.issues.is_empty(), "{:?}", report.issues);
+        assert!(report.completion_reachable);
+    }
+}
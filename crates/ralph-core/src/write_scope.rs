@@ -0,0 +1,566 @@
+//! Per-hat write scopes enforced after each iteration.
+//!
+//! A hat with a `write_scope` may only modify matching paths:
+//!
+//! ```yaml
+//! hats:
+//!   reviewer:
+//!     write_scope:
+//!       allow: ["specs/**", "*.md"]   # paths the hat may modify (empty = everything)
+//!       deny: ["specs/frozen/**"]     # paths it may never modify
+//!       on_violation: reject          # reject (default) | revert
+//! ```
+//!
+//! The orchestrator snapshots the git working tree before the iteration and
+//! compares it afterwards. Out-of-scope changes either cause the hat's events
+//! to be rejected or are reverted; both publish a `scope.violation` event
+//! naming the files.
+//!
+//! Under `.ralph/`, the files the agent is meant to write (events JSONL,
+//! scratchpad, tasks, memories) are always writable and the orchestrator's
+//! own output (diagnostics, Telegram state) is ignored. Loop state the
+//! orchestrator reads back (held approvals, timers) is never writable; it is
+//! compared by content, so it is protected even when `.ralph/` is gitignored.
+//! Workspaces inside a larger repository only see changes under their own
+//! directory, with paths relative to it.
+
+use crate::git_ops::GitOpsError;
+use crate::path_glob::file_matches;
+use ralph_proto::Event;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+/// Topic published when a hat modifies files outside its write scope.
+pub const SCOPE_VIOLATION_TOPIC: &str = "scope.violation";
+
+/// Files under `.ralph/` the agent writes as part of its work.
+const AGENT_FILES: &[&str] = &[
+    ".ralph/events.jsonl",
+    ".ralph/events-*.jsonl",
+    ".ralph/agent/scratchpad.md",
+    ".ralph/agent/tasks.jsonl",
+    ".ralph/agent/memories.md",
+];
+
+/// Files the orchestrator itself writes while a hat runs.
+const ORCHESTRATOR_FILES: &[&str] = &[".ralph/diagnostics/**", ".ralph/telegram-state.json"];
+
+/// Loop state the orchestrator reads back; no hat may modify it.
+const LOOP_STATE_FILES: &[&str] = &[".ralph/agent/approvals.json", ".ralph/agent/timers.json"];
+
+/// Paths a hat may modify.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(default)]
+pub struct WriteScope {
+    /// Glob patterns the hat may modify. Empty allows every path not denied.
+    /// Patterns without a `/` also match the file name alone.
+    pub allow: Vec<String>,
+    /// Glob patterns the hat may never modify; these win over `allow`.
+    pub deny: Vec<String>,
+    /// What to do with out-of-scope changes.
+    pub on_violation: ScopeViolationAction,
+}
+
+/// How out-of-scope changes are handled.
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ScopeViolationAction {
+    /// Keep the changes but drop the events the hat wrote this iteration.
+    #[default]
+    Reject,
+    /// Restore the files to their state before the iteration; events still count.
+    Revert,
+}
+
+impl WriteScope {
+    /// Returns true if the hat may modify `path` (relative to the workspace).
+    pub fn permits(&self, path: &str) -> bool {
+        if LOOP_STATE_FILES.contains(&path) {
+            return false;
+        }
+        if AGENT_FILES
+            .iter()
+            .any(|pattern| file_matches(pattern, path))
+        {
+            return true;
+        }
+        let matches = |pattern: &String| {
+            let pattern = match pattern.strip_suffix('/') {
+                Some(dir) => format!("{dir}/**"),
+                None => pattern.clone(),
+            };
+            file_matches(&pattern, path)
+        };
+        (self.allow.is_empty() || self.allow.iter().any(matches)) && !self.deny.iter().any(matches)
+    }
+
+    /// Checks `changed` paths and returns the violation, if any.
+    pub fn check(&self, hat: &str, changed: &[String]) -> Option<ScopeViolation> {
+        let files: Vec<String> = changed
+            .iter()
+            .filter(|path| !self.permits(path))
+            .cloned()
+            .collect();
+        (!files.is_empty()).then(|| ScopeViolation {
+            hat: hat.to_string(),
+            files,
+            action: self.on_violation,
+        })
+    }
+}
+
+/// Files a hat modified outside its write scope.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScopeViolation {
+    /// The hat that made the changes.
+    pub hat: String,
+    /// Out-of-scope paths, relative to the workspace.
+    pub files: Vec<String>,
+    /// How the violation was handled.
+    pub action: ScopeViolationAction,
+}
+
+impl ScopeViolation {
+    /// Builds the `scope.violation` event explaining what was touched.
+    pub fn to_event(&self) -> Event {
+        let consequence = match self.action {
+            ScopeViolationAction::Reject => {
+                "Its events from this iteration were rejected; the changes are still in the working tree."
+            }
+            ScopeViolationAction::Revert => "The changes were reverted.",
+        };
+        let files: Vec<String> = self.files.iter().map(|file| format!("- {file}")).collect();
+        Event::new(
+            SCOPE_VIOLATION_TOPIC,
+            format!(
+                "Hat '{}' modified files outside its write_scope:\n{}\n{consequence}",
+                self.hat,
+                files.join("\n")
+            ),
+        )
+    }
+}
+
+/// Working tree state captured before an iteration.
+#[derive(Debug, Clone)]
+pub struct WorkspaceSnapshot {
+    head: Option<String>,
+    /// Contents of files already dirty before the iteration (`None` = deleted).
+    dirty: BTreeMap<String, Option<Vec<u8>>>,
+    /// Contents of the loop state files, which git may not track.
+    loop_state: BTreeMap<String, Option<Vec<u8>>>,
+}
+
+impl WorkspaceSnapshot {
+    /// Captures `HEAD` and the contents of every dirty file in `workspace`.
+    pub fn capture(workspace: &Path) -> Result<Self, GitOpsError> {
+        let head = git_head(workspace)?;
+        let read = |path: String| {
+            let content = fs::read(workspace.join(&path)).ok();
+            (path, content)
+        };
+        let dirty = dirty_paths(workspace)?.into_iter().map(read).collect();
+        let loop_state = LOOP_STATE_FILES
+            .iter()
+            .map(|path| read((*path).to_string()))
+            .collect();
+        Ok(Self {
+            head,
+            dirty,
+            loop_state,
+        })
+    }
+
+    /// Returns the paths modified since the snapshot, including committed ones.
+    pub fn changed_paths(&self, workspace: &Path) -> Result<Vec<String>, GitOpsError> {
+        let mut changed = BTreeSet::new();
+
+        for (path, before) in &self.loop_state {
+            if *before != fs::read(workspace.join(path)).ok() {
+                changed.insert(path.clone());
+            }
+        }
+
+        let dirty_now = dirty_paths(workspace)?;
+        for path in &dirty_now {
+            match self.dirty.get(path) {
+                Some(before) if *before == fs::read(workspace.join(path)).ok() => {}
+                _ => {
+                    changed.insert(path.clone());
+                }
+            }
+        }
+        // Files dirty before and clean now were reset or committed.
+        for path in self.dirty.keys() {
+            if !dirty_now.contains(path) {
+                changed.insert(path.clone());
+            }
+        }
+
+        let head = git_head(workspace)?;
+        if head != self.head {
+            // Both list paths relative to, and only under, the workspace.
+            let mut command = Command::new("git");
+            match &self.head {
+                Some(before) => command.args([
+                    "diff",
+                    "--name-only",
+                    "--relative",
+                    "-z",
+                    before,
+                    "HEAD",
+                    "--",
+                ]),
+                // No commit before the iteration: everything committed is new.
+                None => command.args(["ls-tree", "-r", "--name-only", "-z", "HEAD"]),
+            };
+            let output = command.current_dir(workspace).output()?;
+            if !output.status.success() {
+                return Err(GitOpsError::Git(
+                    String::from_utf8_lossy(&output.stderr).to_string(),
+                ));
+            }
+            changed.extend(
+                String::from_utf8_lossy(&output.stdout)
+                    .split('\0')
+                    .filter(|path| !path.is_empty())
+                    .map(String::from),
+            );
+        }
+
+        changed.retain(|path| {
+            !ORCHESTRATOR_FILES
+                .iter()
+                .any(|pattern| file_matches(pattern, path))
+        });
+        Ok(changed.into_iter().collect())
+    }
+
+    /// Restores `paths` to their state at the snapshot.
+    ///
+    /// Files dirty at the snapshot get their captured contents back; other
+    /// files are restored from the snapshot's `HEAD` (index and working tree),
+    /// or removed if they did not exist there. Commits are left alone.
+    pub fn revert(&self, workspace: &Path, paths: &[String]) -> Result<(), GitOpsError> {
+        for path in paths {
+            let full_path = workspace.join(path);
+            if let Some(before) = self.dirty.get(path).or_else(|| self.loop_state.get(path)) {
+                match before {
+                    Some(content) => {
+                        if let Some(parent) = full_path.parent() {
+                            fs::create_dir_all(parent)?;
+                        }
+                        fs::write(&full_path, content)?;
+                    }
+                    None => remove_file(&full_path)?,
+                }
+                continue;
+            }
+
+            let tracked = self.head.as_ref().is_some_and(|head| {
+                // `./` resolves the path against the workspace, not the repo root
+                Command::new("git")
+                    .args(["cat-file", "-e", &format!("{head}:./{path}")])
+                    .current_dir(workspace)
+                    .output()
+                    .is_ok_and(|output| output.status.success())
+            });
+            let output = if let (true, Some(head)) = (tracked, &self.head) {
+                Command::new("git")
+                    .args(["restore", "--source", head, "--staged", "--worktree", "--"])
+                    .arg(path)
+                    .current_dir(workspace)
+                    .output()?
+            } else {
+                remove_file(&full_path)?;
+                Command::new("git")
+                    .args(["rm", "-q", "--cached", "--ignore-unmatch", "--"])
+                    .arg(path)
+                    .current_dir(workspace)
+                    .output()?
+            };
+            if !output.status.success() {
+                return Err(GitOpsError::Git(
+                    String::from_utf8_lossy(&output.stderr).to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn remove_file(path: &Path) -> std::io::Result<()> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e),
+        _ => Ok(()),
+    }
+}
+
+/// Returns the `HEAD` commit, or `None` before the first commit.
+fn git_head(workspace: &Path) -> Result<Option<String>, GitOpsError> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--verify", "-q", "HEAD"])
+        .current_dir(workspace)
+        .output()?;
+    Ok(output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string()))
+}
+
+/// Runs git in `workspace` and returns its stdout.
+fn git_output(workspace: &Path, args: &[&str]) -> Result<String, GitOpsError> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(workspace)
+        .output()?;
+    if !output.status.success() {
+        return Err(GitOpsError::Git(
+            String::from_utf8_lossy(&output.stderr).to_string(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Returns staged, unstaged and untracked paths, relative to the workspace.
+///
+/// `git status` reports paths from the repository root even when run in a
+/// subdirectory, so the workspace's prefix is stripped from each.
+fn dirty_paths(workspace: &Path) -> Result<BTreeSet<String>, GitOpsError> {
+    let prefix = git_output(workspace, &["rev-parse", "--show-prefix"])?;
+    let prefix = prefix.trim_end_matches('\n');
+    let stdout = git_output(
+        workspace,
+        &[
+            "status",
+            "--porcelain",
+            "-z",
+            "--untracked-files=all",
+            "--",
+            ".",
+        ],
+    )?;
+
+    let mut entries = stdout.split('\0').filter(|entry| !entry.is_empty());
+    let mut paths = BTreeSet::new();
+    let mut insert = |path: &str| {
+        // Rename sources may lie outside the workspace
+        if let Some(relative) = path.strip_prefix(prefix) {
+            paths.insert(relative.to_string());
+        }
+    };
+    while let Some(entry) = entries.next() {
+        let Some(path) = entry.get(3..) else {
+            continue;
+        };
+        insert(path);
+        // Renames and copies are followed by their source path.
+        if (entry.starts_with('R') || entry.starts_with('C'))
+            && let Some(source) = entries.next()
+        {
+            insert(source);
+        }
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {args:?} failed");
+    }
+
+    fn init_repo() -> TempDir {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        git(dir, &["init", "-q", "--initial-branch=main"]);
+        git(dir, &["config", "user.email", "test@test.local"]);
+        git(dir, &["config", "user.name", "Test User"]);
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("src/main.rs"), "fn main() {}\n").unwrap();
+        fs::write(dir.join("README.md"), "# Test\n").unwrap();
+        git(dir, &["add", "."]);
+        git(dir, &["commit", "-q", "-m", "Initial commit"]);
+        temp
+    }
+
+    fn scope(allow: &[&str], deny: &[&str]) -> WriteScope {
+        WriteScope {
+            allow: allow.iter().map(ToString::to_string).collect(),
+            deny: deny.iter().map(ToString::to_string).collect(),
+            on_violation: ScopeViolationAction::Reject,
+        }
+    }
+
+    #[test]
+    fn test_permits_allow_and_deny() {
+        let scope = scope(&["specs/", "*.md"], &["specs/frozen/**"]);
+        assert!(scope.permits("specs/api.md"));
+        assert!(scope.permits("specs/nested/plan.txt"));
+        assert!(scope.permits("docs/guide.md"));
+        assert!(scope.permits(".ralph/agent/scratchpad.md"));
+        assert!(scope.permits(".ralph/events-20260101-120000.jsonl"));
+        assert!(!scope.permits(".ralph/agent/approvals.json"));
+        assert!(!scope.permits(".ralph/agent/timers.json"));
+        assert!(!scope.permits(".ralph/ralph.yml"));
+        assert!(!scope.permits("specs/frozen/v1.md"));
+        assert!(!scope.permits("src/main.rs"));
+
+        let deny_only = WriteScope {
+            deny: vec!["Cargo.lock".to_string()],
+            ..WriteScope::default()
+        };
+        assert!(deny_only.permits("src/main.rs"));
+        assert!(!deny_only.permits("Cargo.lock"));
+        assert!(!deny_only.permits(".ralph/agent/approvals.json"));
+    }
+
+    #[test]
+    fn test_check_reports_violation_event() {
+        let scope = scope(&["specs/**"], &[]);
+        let changed = vec!["specs/a.md".to_string(), "src/main.rs".to_string()];
+        let violation = scope.check("reviewer", &changed).unwrap();
+        assert_eq!(violation.files, vec!["src/main.rs"]);
+
+        let event = violation.to_event();
+        assert_eq!(event.topic.as_str(), SCOPE_VIOLATION_TOPIC);
+        assert!(event.payload.contains("Hat 'reviewer'"));
+        assert!(event.payload.contains("- src/main.rs"));
+        assert!(event.payload.contains("rejected"));
+
+        assert!(scope.check("reviewer", &changed[..1]).is_none());
+    }
+
+    #[test]
+    fn test_changed_paths_ignores_preexisting_changes() {
+        let temp = init_repo();
+        let dir = temp.path();
+        fs::write(dir.join("README.md"), "# Edited before\n").unwrap();
+        fs::write(dir.join("notes.txt"), "draft\n").unwrap();
+
+        let snapshot = WorkspaceSnapshot::capture(dir).unwrap();
+        assert!(snapshot.changed_paths(dir).unwrap().is_empty());
+
+        fs::write(dir.join("notes.txt"), "draft 2\n").unwrap();
+        fs::write(dir.join("src/main.rs"), "fn main() { todo!() }\n").unwrap();
+        fs::write(dir.join("src/new.rs"), "\n").unwrap();
+        assert_eq!(
+            snapshot.changed_paths(dir).unwrap(),
+            vec!["notes.txt", "src/main.rs", "src/new.rs"]
+        );
+    }
+
+    #[test]
+    fn test_changed_paths_includes_commits() {
+        let temp = init_repo();
+        let dir = temp.path();
+        let snapshot = WorkspaceSnapshot::capture(dir).unwrap();
+
+        fs::write(dir.join("src/lib.rs"), "\n").unwrap();
+        git(dir, &["add", "."]);
+        git(dir, &["commit", "-q", "-m", "Add lib"]);
+        assert_eq!(snapshot.changed_paths(dir).unwrap(), vec!["src/lib.rs"]);
+    }
+
+    #[test]
+    fn test_changed_paths_sees_loop_state_under_gitignored_ralph_dir() {
+        let temp = init_repo();
+        let dir = temp.path();
+        fs::write(dir.join(".gitignore"), ".ralph/\n").unwrap();
+        git(dir, &["add", ".gitignore"]);
+        git(dir, &["commit", "-q", "-m", "Ignore .ralph"]);
+        fs::create_dir_all(dir.join(".ralph/agent")).unwrap();
+        fs::write(dir.join(".ralph/agent/approvals.json"), "[held]").unwrap();
+        let snapshot = WorkspaceSnapshot::capture(dir).unwrap();
+
+        fs::write(dir.join(".ralph/agent/approvals.json"), "[]").unwrap();
+        fs::write(dir.join(".ralph/agent/timers.json"), "{}").unwrap();
+        let changed = snapshot.changed_paths(dir).unwrap();
+        assert_eq!(
+            changed,
+            vec![".ralph/agent/approvals.json", ".ralph/agent/timers.json"]
+        );
+
+        let violation = scope(&[], &[]).check("builder", &changed).unwrap();
+        snapshot.revert(dir, &violation.files).unwrap();
+        assert_eq!(
+            fs::read_to_string(dir.join(".ralph/agent/approvals.json")).unwrap(),
+            "[held]"
+        );
+        assert!(!dir.join(".ralph/agent/timers.json").exists());
+    }
+
+    #[test]
+    fn test_workspace_in_subdirectory_uses_workspace_relative_paths() {
+        let temp = init_repo();
+        let repo = temp.path();
+        let dir = repo.join("packages/app");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("lib.rs"), "\n").unwrap();
+        git(repo, &["add", "."]);
+        git(repo, &["commit", "-q", "-m", "Add app"]);
+        fs::write(dir.join("draft.md"), "draft\n").unwrap();
+        let snapshot = WorkspaceSnapshot::capture(&dir).unwrap();
+
+        // Edits outside the workspace are not the hat's concern
+        fs::write(repo.join("README.md"), "# Elsewhere\n").unwrap();
+        fs::write(dir.join("draft.md"), "draft 2\n").unwrap();
+        fs::write(dir.join("lib.rs"), "broken\n").unwrap();
+        assert_eq!(
+            snapshot.changed_paths(&dir).unwrap(),
+            vec!["draft.md", "lib.rs"]
+        );
+
+        snapshot
+            .revert(&dir, &["draft.md".to_string(), "lib.rs".to_string()])
+            .unwrap();
+        assert_eq!(fs::read_to_string(dir.join("draft.md")).unwrap(), "draft\n");
+        assert_eq!(fs::read_to_string(dir.join("lib.rs")).unwrap(), "\n");
+        assert!(snapshot.changed_paths(&dir).unwrap().is_empty());
+
+        fs::write(dir.join("new.rs"), "\n").unwrap();
+        git(&dir, &["add", "new.rs"]);
+        git(&dir, &["commit", "-q", "-m", "Add new"]);
+        assert_eq!(snapshot.changed_paths(&dir).unwrap(), vec!["new.rs"]);
+    }
+
+    #[test]
+    fn test_revert_restores_snapshot_state() {
+        let temp = init_repo();
+        let dir = temp.path();
+        fs::write(dir.join("notes.txt"), "draft\n").unwrap();
+        let snapshot = WorkspaceSnapshot::capture(dir).unwrap();
+
+        fs::write(dir.join("notes.txt"), "overwritten\n").unwrap();
+        fs::write(dir.join("src/main.rs"), "broken\n").unwrap();
+        git(dir, &["add", "src/main.rs"]);
+        fs::remove_file(dir.join("README.md")).unwrap();
+        fs::write(dir.join("src/new.rs"), "\n").unwrap();
+
+        let changed = snapshot.changed_paths(dir).unwrap();
+        snapshot.revert(dir, &changed).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(dir.join("notes.txt")).unwrap(),
+            "draft\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("src/main.rs")).unwrap(),
+            "fn main() {}\n"
+        );
+        assert!(dir.join("README.md").exists());
+        assert!(!dir.join("src/new.rs").exists());
+        assert!(snapshot.changed_paths(dir).unwrap().is_empty());
+    }
+}
@@ -6,7 +6,7 @@ use ralph_core::{EventLoop, RalphConfig};
 #[test]
 fn test_event_loop_uses_custom_template_when_configured() {
     // T10: EventLoop uses configured template
-    let yaml = r#"
+    let yaml = r"
 event_loop:
   initial_prompt_template: |
     ## CUSTOM ORIENTATION
@@ -17,7 +17,7 @@ event_loop:
     
     ## CUSTOM INSTRUCTIONS
     Follow these custom instructions for this task.
-"#;
+";
 
     let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
     let event_loop = EventLoop::new(config);
@@ -42,7 +42,7 @@ event_loop:
 #[test]
 fn test_build_prompt_returns_custom_template_content() {
     // T11: build_prompt returns template output
-    let yaml = r#"
+    let yaml = r"
 event_loop:
   initial_prompt_template: |
     # My Custom Prompt Template
@@ -54,7 +54,7 @@ event_loop:
     - Be thorough
     - Follow best practices
     - Complete one task at a time
-"#;
+";
 
     let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
     let event_loop = EventLoop::new(config);
@@ -111,7 +111,7 @@ event_loop:
 #[test]
 fn test_custom_template_with_memories_disabled() {
     // T13: Template works with memories off
-    let yaml = r#"
+    let yaml = r"
 memories:
   enabled: false
 event_loop:
@@ -123,7 +123,7 @@ event_loop:
     
     ## INSTRUCTIONS
     Work with current context only.
-"#;
+";
     let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
     let event_loop = EventLoop::new(config);
 
@@ -143,7 +143,7 @@ event_loop:
 #[test]
 fn test_custom_template_in_solo_mode() {
     // T14: Template works in solo mode
-    let yaml = r#"
+    let yaml = r"
 event_loop:
   initial_prompt_template: |
     ## SOLO MODE TEMPLATE
@@ -156,7 +156,7 @@ event_loop:
     1. Analyze the task
     2. Execute directly
     3. Report completion
-"#;
+";
     let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
     let event_loop = EventLoop::new(config);
 
@@ -176,7 +176,7 @@ event_loop:
 #[test]
 fn test_custom_template_in_multi_hat_mode() {
     // T15: Template works in multi-hat mode
-    let yaml = r#"
+    let yaml = r"
 event_loop:
   initial_prompt_template: |
     ## MULTI-HAT TEMPLATE
@@ -188,7 +188,7 @@ event_loop:
     - Delegate appropriately
     - Track handoffs
     - Monitor progress
-"#;
+";
     let config: RalphConfig = serde_yaml::from_str(yaml).unwrap();
     let event_loop = EventLoop::new(config);
 
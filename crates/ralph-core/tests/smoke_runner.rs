@@ -1,7 +1,7 @@
 #![cfg(feature = "recording")]
 //! Integration tests for the smoke test replay runner.
 
-use ralph_core::testing::{list_fixtures, SmokeRunner, SmokeTestConfig, TerminationReason};
+use ralph_core::testing::{SmokeRunner, SmokeTestConfig, TerminationReason, list_fixtures};
 use std::path::PathBuf;
 
 /// Returns the path to the test fixtures directory.
@@ -180,8 +180,8 @@ fn test_all_discovered_fixtures_are_valid() {
 
 mod regression_detection {
     use super::*;
-    use ralph_core::testing::{ReplayBackend, SmokeTestError};
     use ralph_core::Record;
+    use ralph_core::testing::{ReplayBackend, SmokeTestError};
     use ralph_proto::TerminalWrite;
     use std::io::Write;
     use std::time::Duration;
@@ -624,10 +624,10 @@ Still working..."#;
         let line = make_write_line("Quick output", 0);
         let fixture_path = create_fixture(temp_dir.path(), "quick.jsonl", &line);
 
-        let config = SmokeTestConfig::new(&fixture_path).with_timeout(Duration::from_secs(60));
+        let config = SmokeTestConfig::new(&fixture_path).with_timeout(Duration::from_mins(1));
 
         // Verify config was set
-        assert_eq!(config.timeout, Duration::from_secs(60));
+        assert_eq!(config.timeout, Duration::from_mins(1));
 
         let result = SmokeRunner::run(&config).expect("Should complete within 60s");
         assert!(result.completed_successfully());
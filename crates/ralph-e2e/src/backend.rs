@@ -35,8 +35,8 @@ impl Backend {
     /// Returns the default timeout for this backend.
     pub fn default_timeout(&self) -> Duration {
         match self {
-            Backend::Claude => Duration::from_secs(600), // 10 minutes - Claude iterations can take 60-120s each
-            Backend::Kiro | Backend::OpenCode => Duration::from_secs(300), // 5 minutes
+            Backend::Claude => Duration::from_mins(10), // 10 minutes - Claude iterations can take 60-120s each
+            Backend::Kiro | Backend::OpenCode => Duration::from_mins(5), // 5 minutes
         }
     }
 
@@ -48,6 +48,32 @@ impl Backend {
         }
     }
 
+    /// Returns the headless arguments ralph passes to this backend before the prompt.
+    ///
+    /// Mirrors the named backends in `ralph-adapters` so that cassette recording
+    /// can launch the real CLI exactly as a live run would.
+    pub fn headless_args(&self) -> &'static [&'static str] {
+        match self {
+            Backend::Claude => &[
+                "--dangerously-skip-permissions",
+                "--verbose",
+                "--output-format",
+                "stream-json",
+                "--disallowedTools=TodoWrite,TaskCreate,TaskUpdate,TaskList,TaskGet",
+            ],
+            Backend::Kiro => &["chat", "--no-interactive", "--trust-all-tools"],
+            Backend::OpenCode => &["run"],
+        }
+    }
+
+    /// Returns the flag that precedes the prompt, if the prompt is not positional.
+    pub fn prompt_flag(&self) -> Option<&'static str> {
+        match self {
+            Backend::Claude => Some("-p"),
+            Backend::Kiro | Backend::OpenCode => None,
+        }
+    }
+
     /// Returns the backend name in lowercase (for config files).
     pub fn as_config_str(&self) -> &'static str {
         match self {
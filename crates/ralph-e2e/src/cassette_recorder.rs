@@ -0,0 +1,264 @@
+//! Cassette recording for the `record` subcommand.
+//!
+//! This module wraps a real backend CLI as a pass-through proxy. ralph invokes
+//! it as a custom backend; the proxy runs the real command, forwards its
+//! output, and appends what it saw to a cassette in the format `mock_cli`
+//! replays.
+//!
+//! # Usage
+//!
+//! ```bash
+//! # Record one backend invocation (ralph appends the prompt)
+//! ralph-e2e record --cassette cassettes/e2e/connect-claude.jsonl -- \
+//!     claude --verbose --output-format stream-json -p "Say PONG"
+//! ```
+//!
+//! # Output translation
+//!
+//! Backends that stream NDJSON (`--output-format stream-json`) are translated
+//! as they are recorded: assistant text is forwarded and recorded as terminal
+//! output, and Bash tool calls are recorded as `bus.publish` events carrying a
+//! `command` field so `mock-cli --allow` can re-execute them. Plain-text
+//! backends are forwarded and recorded verbatim.
+//!
+//! Each invocation appends to the cassette, so a multi-iteration run produces
+//! one cassette holding every iteration in order.
+
+use ralph_core::{Record, SessionRecorder};
+use ralph_proto::TerminalWrite;
+use serde_json::Value;
+use std::fs::{self, OpenOptions};
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::Arc;
+use std::thread;
+use thiserror::Error;
+
+/// Errors that can occur while recording a cassette.
+#[derive(Debug, Error)]
+pub enum RecordError {
+    /// No backend command was given.
+    #[error("no backend command to record")]
+    MissingCommand,
+
+    /// Failed to create or open the cassette file.
+    #[error("failed to open cassette: {path}: {source}")]
+    CassetteOpen {
+        path: String,
+        source: std::io::Error,
+    },
+
+    /// Failed to spawn the backend command.
+    #[error("failed to spawn '{command}': {source}")]
+    Spawn {
+        command: String,
+        source: std::io::Error,
+    },
+
+    /// I/O error while proxying backend output.
+    #[error("proxy error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+/// What a single line of backend output contributes to the cassette.
+#[derive(Debug, Default, PartialEq, Eq)]
+struct TranslatedLine {
+    /// Text to forward to ralph and record as terminal output.
+    text: String,
+    /// Shell commands the backend executed.
+    commands: Vec<String>,
+}
+
+/// Runs `command` as a pass-through proxy, appending its output to `cassette`.
+///
+/// Returns the exit code of the backend so the caller can propagate it.
+pub fn run(cassette: &Path, command: &[String]) -> Result<i32, RecordError> {
+    let (program, args) = command.split_first().ok_or(RecordError::MissingCommand)?;
+
+    if let Some(parent) = cassette.parent()
+        && !parent.as_os_str().is_empty()
+    {
+        fs::create_dir_all(parent).map_err(|e| RecordError::CassetteOpen {
+            path: cassette.display().to_string(),
+            source: e,
+        })?;
+    }
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(cassette)
+        .map_err(|e| RecordError::CassetteOpen {
+            path: cassette.display().to_string(),
+            source: e,
+        })?;
+    let recorder = Arc::new(SessionRecorder::new(file));
+
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::inherit())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| RecordError::Spawn {
+            command: program.clone(),
+            source: e,
+        })?;
+
+    // Stderr is forwarded verbatim from its own thread so neither pipe can block the other
+    let stderr = child.stderr.take().expect("stderr is piped");
+    let stderr_recorder = Arc::clone(&recorder);
+    let stderr_thread = thread::spawn(move || proxy_stderr(stderr, &stderr_recorder));
+
+    let stdout = child.stdout.take().expect("stdout is piped");
+    let mut out = io::stdout().lock();
+    for line in BufReader::new(stdout).lines() {
+        let line = line?;
+        let translated = translate_line(&line);
+
+        if !translated.text.is_empty() {
+            out.write_all(translated.text.as_bytes())?;
+            out.flush()?;
+            record_write(&recorder, translated.text.as_bytes(), true);
+        }
+        for command in translated.commands {
+            recorder.record_meta(Record::new(
+                "bus.publish",
+                serde_json::json!({
+                    "topic": "tool.bash",
+                    "payload": command,
+                    "command": command,
+                }),
+            ));
+        }
+    }
+
+    let status = child.wait()?;
+    let _ = stderr_thread.join();
+    recorder.flush()?;
+
+    Ok(status.code().unwrap_or(1))
+}
+
+/// Forwards stderr chunks and records them as non-stdout terminal writes.
+fn proxy_stderr<R: Read, W: Write>(mut stderr: R, recorder: &SessionRecorder<W>) {
+    let mut buf = [0u8; 4096];
+    let mut err = io::stderr();
+    while let Ok(n) = stderr.read(&mut buf) {
+        if n == 0 {
+            break;
+        }
+        let _ = err.write_all(&buf[..n]);
+        record_write(recorder, &buf[..n], false);
+    }
+}
+
+/// Records a terminal write at the current session offset.
+fn record_write<W: Write>(recorder: &SessionRecorder<W>, bytes: &[u8], stdout: bool) {
+    let offset_ms = recorder.elapsed().as_millis() as u64;
+    // Written flat (not as a tagged UxEvent) to match the existing cassettes
+    recorder.record_meta(Record::new(
+        "ux.terminal.write",
+        TerminalWrite::new(bytes, stdout, offset_ms),
+    ));
+}
+
+/// Translates one line of backend output.
+///
+/// Stream-JSON lines contribute their assistant text and Bash commands;
+/// other JSON messages (system, tool results, usage) are dropped. Anything
+/// that is not JSON passes through unchanged.
+fn translate_line(line: &str) -> TranslatedLine {
+    let Ok(value) = serde_json::from_str::<Value>(line) else {
+        return TranslatedLine {
+            text: format!("{line}\n"),
+            commands: Vec::new(),
+        };
+    };
+
+    let mut translated = TranslatedLine::default();
+    if value.get("type").and_then(Value::as_str) != Some("assistant") {
+        return translated;
+    }
+
+    let content = value
+        .pointer("/message/content")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    for block in content {
+        match block.get("type").and_then(Value::as_str) {
+            Some("text") => {
+                if let Some(text) = block.get("text").and_then(Value::as_str) {
+                    translated.text.push_str(text);
+                    translated.text.push('\n');
+                }
+            }
+            Some("tool_use") => {
+                if let Some(command) = block.pointer("/input/command").and_then(Value::as_str) {
+                    translated.commands.push(command.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+    translated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ralph_core::SessionPlayer;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_translate_plain_text_passes_through() {
+        let translated = translate_line("PONG");
+        assert_eq!(translated.text, "PONG\n");
+        assert!(translated.commands.is_empty());
+    }
+
+    #[test]
+    fn test_translate_stream_json_assistant_message() {
+        let line = r#"{"type":"assistant","message":{"content":[{"type":"text","text":"Adding a task"},{"type":"tool_use","name":"Bash","input":{"command":"ralph task add 'x'"}}]}}"#;
+        let translated = translate_line(line);
+        assert_eq!(translated.text, "Adding a task\n");
+        assert_eq!(translated.commands, vec!["ralph task add 'x'"]);
+    }
+
+    #[test]
+    fn test_translate_drops_non_assistant_json() {
+        let translated = translate_line(r#"{"type":"system","subtype":"init"}"#);
+        assert_eq!(translated, TranslatedLine::default());
+    }
+
+    #[test]
+    fn test_run_without_command() {
+        let temp = TempDir::new().unwrap();
+        let result = run(&temp.path().join("c.jsonl"), &[]);
+        assert!(matches!(result, Err(RecordError::MissingCommand)));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_records_replayable_cassette() {
+        let temp = TempDir::new().unwrap();
+        let cassette = temp.path().join("nested/echo.jsonl");
+        let command = vec!["echo".to_string(), "PONG".to_string()];
+
+        assert_eq!(run(&cassette, &command).unwrap(), 0);
+        // A second invocation appends, like a second iteration would
+        assert_eq!(run(&cassette, &command).unwrap(), 0);
+
+        let player = SessionPlayer::from_bytes(&fs::read(&cassette).unwrap()).unwrap();
+        assert_eq!(player.collect_terminal_output().unwrap(), "PONG\nPONG\n");
+    }
+
+    #[test]
+    fn test_run_with_missing_program() {
+        let temp = TempDir::new().unwrap();
+        let command = vec!["definitely-not-a-real-backend-cli".to_string()];
+        let result = run(&temp.path().join("c.jsonl"), &command);
+        assert!(matches!(result, Err(RecordError::Spawn { .. })));
+    }
+}
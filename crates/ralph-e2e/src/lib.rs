@@ -37,19 +37,22 @@ pub use crate::analyzer::{
 };
 pub use crate::auth::{AuthChecker, BackendInfo};
 pub use crate::backend::Backend;
+pub use crate::cassette_recorder::{RecordError, run as run_record};
 pub use crate::executor::{
     EventRecord, ExecutionResult, ExecutorError, PromptSource, RalphExecutor, ScenarioConfig,
     find_workspace_root, resolve_ralph_binary,
 };
 pub use crate::mock::{
     CassetteError, CassetteResolver, DEFAULT_CASSETTE_DIR, MockConfig, build_mock_cli_args,
+    build_record_cli_args, is_stale_cassette,
 };
 pub use crate::mock_cli::{MockCliError, run as run_mock_cli};
 pub use crate::models::{Assertion, ReportFormat, TestResult};
 pub use crate::reporter::{
-    AnalyzedResultData, BackendSummary, JsonReporter, MarkdownReporter, QualityBreakdown,
-    ReportSummary, ReportWriter, ReporterError, TerminalReporter, TestReport, TierSummary,
-    Verbosity, create_incremental_progress_callback, create_progress_callback,
+    AnalyzedResultData, BackendSummary, JsonReporter, JunitReporter, MarkdownReporter,
+    QualityBreakdown, ReportSummary, ReportWriter, ReporterError, TapReporter, TerminalReporter,
+    TestReport, TierSummary, Verbosity, create_incremental_progress_callback,
+    create_progress_callback,
 };
 pub use crate::runner::{
     ProgressCallback, ProgressEvent, RunConfig, RunResults, RunnerError, TestRunner,
@@ -105,6 +108,7 @@ pub use crate::workspace::WorkspaceManager;
 pub mod analyzer;
 pub mod auth;
 mod backend;
+pub mod cassette_recorder;
 pub mod executor;
 pub mod mock;
 pub mod mock_cli;
@@ -71,6 +71,7 @@ use ralph_e2e::{
     create_incremental_progress_callback,
     resolve_ralph_binary,
     run_mock_cli,
+    run_record,
 };
 
 /// Backend selection for E2E tests.
@@ -142,6 +143,17 @@ pub enum Command {
         #[arg(long)]
         allow: Option<String>,
     },
+
+    /// Record a real backend invocation into a cassette (used as custom backend).
+    Record {
+        /// Path to the cassette file to append to
+        #[arg(long)]
+        cassette: std::path::PathBuf,
+
+        /// Backend command and arguments to proxy (after --)
+        #[arg(last = true, required = true)]
+        command: Vec<String>,
+    },
 }
 
 /// Options for running E2E tests.
@@ -186,6 +198,10 @@ pub struct TestOpts {
     /// Replay speed for mock mode (0.0 = instant, 10.0 = 10x faster)
     #[arg(long, default_value = "0.0")]
     pub mock_speed: f32,
+
+    /// Re-record missing or stale cassettes against the real backend (mock mode)
+    #[arg(long, requires = "mock")]
+    pub update_cassettes: bool,
 }
 
 /// Report output format.
@@ -198,6 +214,10 @@ pub enum ReportFormat {
     Json,
     /// Both markdown and JSON
     Both,
+    /// JUnit XML format (CI dashboards)
+    Junit,
+    /// TAP format (Test Anything Protocol)
+    Tap,
 }
 
 impl ReportFormat {
@@ -207,6 +227,8 @@ impl ReportFormat {
             ReportFormat::Markdown => LibReportFormat::Markdown,
             ReportFormat::Json => LibReportFormat::Json,
             ReportFormat::Both => LibReportFormat::Both,
+            ReportFormat::Junit => LibReportFormat::Junit,
+            ReportFormat::Tap => LibReportFormat::Tap,
         }
     }
 }
@@ -268,6 +290,13 @@ fn main() {
                 }
                 return;
             }
+            Command::Record { cassette, command } => match run_record(&cassette, &command) {
+                Ok(code) => std::process::exit(code),
+                Err(e) => {
+                    eprintln!("{} {}", "Error:".red().bold(), e);
+                    std::process::exit(1);
+                }
+            },
         }
     }
 
@@ -279,7 +308,12 @@ fn main() {
     );
     println!("{}", "━".repeat(40).dimmed());
 
-    if cli.test_opts.mock {
+    if cli.test_opts.update_cassettes {
+        println!(
+            "{}",
+            "Mode: Mock (recording missing or stale cassettes)".dimmed()
+        );
+    } else if cli.test_opts.mock {
         println!("{}", "Mode: Mock (cassette replay)".dimmed());
     }
 
@@ -367,7 +401,7 @@ async fn list_scenarios(opts: &TestOpts, verbosity: Verbosity) {
 
 async fn run_tests(opts: &TestOpts, verbosity: Verbosity) {
     // Check backend availability first (skip in mock mode)
-    if !opts.mock && verbosity != Verbosity::Quiet {
+    if (!opts.mock || opts.update_cassettes) && verbosity != Verbosity::Quiet {
         println!();
         let checker = AuthChecker::new();
 
@@ -423,7 +457,9 @@ async fn run_tests(opts: &TestOpts, verbosity: Verbosity) {
 
     // Configure mock mode if enabled
     if opts.mock {
-        let mock_config = MockConfig::default().with_speed(opts.mock_speed);
+        let mock_config = MockConfig::default()
+            .with_speed(opts.mock_speed)
+            .with_update_cassettes(opts.update_cassettes);
         config = config.with_mock(mock_config);
     }
 
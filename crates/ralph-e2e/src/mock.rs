@@ -40,6 +40,9 @@
 //! ```
 
 use crate::Backend;
+use ralph_core::SessionPlayer;
+use std::fs::File;
+use std::io::BufReader;
 use std::path::{Path, PathBuf};
 use thiserror::Error;
 
@@ -81,6 +84,9 @@ pub struct MockConfig {
     /// Commands allowed to be executed during mock replay.
     /// Format: comma-separated command prefixes (e.g., "ralph task add,ralph tools memory add").
     pub allow_commands: Option<String>,
+
+    /// Re-record cassettes that are missing or stale against the real backend.
+    pub update_cassettes: bool,
 }
 
 impl Default for MockConfig {
@@ -89,6 +95,7 @@ impl Default for MockConfig {
             cassette_dir: PathBuf::from(DEFAULT_CASSETTE_DIR),
             speed: 0.0, // Instant by default for CI
             allow_commands: Some("ralph task add,ralph task close,ralph tools memory add".into()),
+            update_cassettes: false,
         }
     }
 }
@@ -114,6 +121,12 @@ impl MockConfig {
         self
     }
 
+    /// Enables re-recording of missing or stale cassettes.
+    pub fn with_update_cassettes(mut self, update: bool) -> Self {
+        self.update_cassettes = update;
+        self
+    }
+
     /// Disables command execution during replay.
     pub fn without_commands(mut self) -> Self {
         self.allow_commands = None;
@@ -221,6 +234,33 @@ impl CassetteResolver {
     pub fn cassette_dir(&self) -> &Path {
         &self.cassette_dir
     }
+
+    /// Returns the path to (re-)record for a scenario, or `None` if its cassette is usable.
+    ///
+    /// A stale cassette is re-recorded in place. A missing one is recorded as
+    /// `<scenario>-<backend>.jsonl`, since the output is specific to the backend
+    /// it was captured from.
+    pub fn recording_target(&self, scenario: &str, backend: Backend) -> Option<PathBuf> {
+        match self.resolve(scenario, backend) {
+            Ok(path) if is_stale_cassette(&path) => Some(path),
+            Ok(_) => None,
+            Err(_) => self.candidates(scenario, backend).into_iter().next(),
+        }
+    }
+}
+
+/// Returns true if a cassette cannot be replayed.
+///
+/// A cassette is stale when it cannot be parsed or contains no terminal
+/// output, which is what `mock-cli` replays.
+pub fn is_stale_cassette(path: &Path) -> bool {
+    let Ok(file) = File::open(path) else {
+        return true;
+    };
+    match SessionPlayer::from_reader(BufReader::new(file)) {
+        Ok(player) => player.terminal_writes().is_empty(),
+        Err(_) => true,
+    }
 }
 
 /// Builds the command-line arguments for invoking the mock CLI.
@@ -246,6 +286,23 @@ pub fn build_mock_cli_args(cassette_path: &Path, config: &MockConfig) -> Vec<Str
     args
 }
 
+/// Builds the command-line arguments for recording a cassette.
+///
+/// `ralph-e2e record` wraps the real backend CLI, so ralph sees a custom
+/// backend whose output matches what `mock-cli` will later replay. The
+/// prompt (and the backend's prompt flag) are appended by ralph after these.
+pub fn build_record_cli_args(cassette_path: &Path, backend: Backend) -> Vec<String> {
+    let mut args = vec![
+        "record".to_string(),
+        "--cassette".to_string(),
+        cassette_path.to_string_lossy().to_string(),
+        "--".to_string(),
+        backend.command().to_string(),
+    ];
+    args.extend(backend.headless_args().iter().map(|s| (*s).to_string()));
+    args
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -435,4 +492,72 @@ mod tests {
             "Should find cassette when using resolved absolute path"
         );
     }
+
+    #[test]
+    fn test_recording_target_skips_usable_cassette() {
+        let temp = TempDir::new().unwrap();
+        create_test_cassette(temp.path(), "connect.jsonl");
+
+        let resolver = CassetteResolver::new(temp.path());
+        assert!(
+            resolver
+                .recording_target("connect", Backend::Claude)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_recording_target_for_missing_cassette_is_backend_specific() {
+        let temp = TempDir::new().unwrap();
+
+        let resolver = CassetteResolver::new(temp.path());
+        let target = resolver.recording_target("connect", Backend::Kiro).unwrap();
+        assert!(target.ends_with("connect-kiro.jsonl"));
+    }
+
+    #[test]
+    fn test_recording_target_rerecords_stale_cassette_in_place() {
+        let temp = TempDir::new().unwrap();
+        // Parseable, but has nothing for mock-cli to replay
+        fs::write(
+            temp.path().join("connect.jsonl"),
+            r#"{"ts":1000,"event":"_meta.loop_start","data":{}}"#,
+        )
+        .unwrap();
+
+        let resolver = CassetteResolver::new(temp.path());
+        let target = resolver
+            .recording_target("connect", Backend::Claude)
+            .unwrap();
+        assert!(target.ends_with("connect.jsonl"));
+    }
+
+    #[test]
+    fn test_is_stale_cassette() {
+        let temp = TempDir::new().unwrap();
+        create_test_cassette(temp.path(), "good.jsonl");
+        fs::write(temp.path().join("bad.jsonl"), "not json").unwrap();
+
+        assert!(!is_stale_cassette(&temp.path().join("good.jsonl")));
+        assert!(is_stale_cassette(&temp.path().join("bad.jsonl")));
+        assert!(is_stale_cassette(&temp.path().join("missing.jsonl")));
+    }
+
+    #[test]
+    fn test_build_record_cli_args() {
+        let cassette = PathBuf::from("/path/to/connect-claude.jsonl");
+        let args = build_record_cli_args(&cassette, Backend::Claude);
+
+        assert_eq!(
+            &args[..5],
+            &[
+                "record",
+                "--cassette",
+                "/path/to/connect-claude.jsonl",
+                "--",
+                "claude"
+            ]
+        );
+        assert!(args.contains(&"stream-json".to_string()));
+    }
 }
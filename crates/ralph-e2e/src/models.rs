@@ -16,6 +16,10 @@ pub enum ReportFormat {
     Json,
     /// Both markdown and JSON
     Both,
+    /// JUnit XML (CI dashboards)
+    Junit,
+    /// Test Anything Protocol
+    Tap,
 }
 
 /// Result of a single test scenario execution.
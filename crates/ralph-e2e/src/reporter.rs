@@ -4,6 +4,7 @@
 //! - `TerminalReporter`: Colored terminal output for progress and results
 //! - `MarkdownReporter`: Agent-readable markdown report generation
 //! - `JsonReporter`: Machine-readable JSON report generation
+//! - `JunitReporter` / `TapReporter`: JUnit XML and TAP output for CI
 //! - `ReportWriter`: Orchestrates writing reports to files
 //!
 //! # Example
@@ -33,6 +34,7 @@ use crate::models::TestResult;
 use crate::runner::{ProgressEvent, RunResults};
 use chrono::{DateTime, Utc};
 use colored::Colorize;
+use ralph_core::testing::report::{TestCase, junit_xml, tap};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::io::{self, Write};
@@ -1584,6 +1586,81 @@ impl Default for JsonReporter {
     }
 }
 
+// ============================================================================
+// JUnit / TAP Reporters
+// ============================================================================
+
+/// Converts a scenario result into a JUnit/TAP test case.
+///
+/// Tiers become suites and the backend is used as the JUnit classname.
+/// Failed assertions make up the failure details.
+fn to_test_case(result: &TestResult) -> TestCase {
+    let suite = result.tier.as_str();
+    let name = result.scenario_id.as_str();
+    let case = if result.passed {
+        TestCase::passed(suite, name, result.duration)
+    } else {
+        let failed: Vec<_> = result.assertions.iter().filter(|a| !a.passed).collect();
+        let message = match failed.len() {
+            1 => format!("{} failed", failed[0].name),
+            n => format!("{} assertions failed", n),
+        };
+        let details = failed
+            .iter()
+            .map(|a| format!("{}: expected {}, got {}", a.name, a.expected, a.actual))
+            .collect::<Vec<_>>()
+            .join("\n");
+        TestCase::failed(suite, name, result.duration, message, details)
+    };
+    case.with_classname(result.backend.to_lowercase())
+}
+
+/// Generates JUnit XML reports for CI dashboards.
+///
+/// Each tier is a `<testsuite>` and each scenario a `<testcase>`.
+pub struct JunitReporter;
+
+impl JunitReporter {
+    /// Creates a new JUnit reporter.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Generates a JUnit XML report from run results.
+    pub fn generate(&self, results: &RunResults) -> String {
+        let cases: Vec<TestCase> = results.results.iter().map(to_test_case).collect();
+        junit_xml("ralph-e2e", &cases)
+    }
+}
+
+impl Default for JunitReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Generates TAP (Test Anything Protocol) reports.
+pub struct TapReporter;
+
+impl TapReporter {
+    /// Creates a new TAP reporter.
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Generates a TAP report from run results.
+    pub fn generate(&self, results: &RunResults) -> String {
+        let cases: Vec<TestCase> = results.results.iter().map(to_test_case).collect();
+        tap(&cases)
+    }
+}
+
+impl Default for TapReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 // ============================================================================
 // Report Writer
 // ============================================================================
@@ -1631,6 +1708,14 @@ impl ReportWriter {
                 written_files.push(md_path);
                 written_files.push(json_path);
             }
+            crate::models::ReportFormat::Junit => {
+                let path = self.write_junit(results)?;
+                written_files.push(path);
+            }
+            crate::models::ReportFormat::Tap => {
+                let path = self.write_tap(results)?;
+                written_files.push(path);
+            }
         }
 
         Ok(written_files)
@@ -1665,6 +1750,26 @@ impl ReportWriter {
 
         Ok(path)
     }
+
+    /// Writes a JUnit XML report.
+    pub fn write_junit(&self, results: &RunResults) -> Result<PathBuf, ReporterError> {
+        let content = JunitReporter::new().generate(results);
+
+        let path = self.output_dir.join("report.xml");
+        std::fs::write(&path, content)?;
+
+        Ok(path)
+    }
+
+    /// Writes a TAP report.
+    pub fn write_tap(&self, results: &RunResults) -> Result<PathBuf, ReporterError> {
+        let content = TapReporter::new().generate(results);
+
+        let path = self.output_dir.join("report.tap");
+        std::fs::write(&path, content)?;
+
+        Ok(path)
+    }
 }
 
 // ============================================================================
@@ -2134,6 +2239,56 @@ mod reporter_tests {
         std::fs::remove_dir_all(&temp_dir).ok();
     }
 
+    // ==================== JUnit / TAP Tests ====================
+
+    #[test]
+    fn test_junit_reporter_groups_by_tier() {
+        let xml = JunitReporter::new().generate(&mock_run_results_mixed());
+
+        assert!(xml.contains("<testsuites name=\"ralph-e2e\" tests=\"2\" failures=\"1\""));
+        assert!(
+            xml.contains("<testsuite name=\"Tier 1: Connectivity\" tests=\"1\" failures=\"0\"")
+        );
+        assert!(
+            xml.contains(
+                "<testcase name=\"claude-connect\" classname=\"claude\" time=\"12.000\"/>"
+            )
+        );
+        assert!(xml.contains("<failure message=\"Agent mentions Builder failed\">"));
+        assert!(xml.contains(
+            "Agent mentions Builder: expected Contains &apos;I am the Builder&apos;, got No mention of Builder"
+        ));
+    }
+
+    #[test]
+    fn test_tap_reporter_output() {
+        let out = TapReporter::new().generate(&mock_run_results_mixed());
+
+        assert!(out.starts_with("TAP version 13\n1..2\n"));
+        assert!(out.contains("ok 1 - Tier 1: Connectivity claude-connect\n"));
+        assert!(out.contains("not ok 2 - Tier 5: Hat Collections hat-instructions\n"));
+    }
+
+    #[test]
+    fn test_report_writer_junit_and_tap() {
+        let temp_dir =
+            std::env::temp_dir().join(format!("ralph-e2e-test-junit-{}", std::process::id()));
+        let writer = ReportWriter::new(temp_dir.clone());
+        let results = mock_run_results_mixed();
+
+        let junit = writer
+            .write(&results, None, crate::models::ReportFormat::Junit)
+            .unwrap();
+        let tap = writer
+            .write(&results, None, crate::models::ReportFormat::Tap)
+            .unwrap();
+        assert!(junit[0].ends_with("report.xml"));
+        assert!(tap[0].ends_with("report.tap"));
+        assert!(junit[0].exists() && tap[0].exists());
+
+        std::fs::remove_dir_all(&temp_dir).ok();
+    }
+
     #[test]
     fn test_report_writer_creates_directory() {
         let temp_dir = std::env::temp_dir().join(format!(
@@ -25,7 +25,7 @@
 
 use crate::Backend;
 use crate::executor::RalphExecutor;
-use crate::mock::{CassetteResolver, MockConfig, build_mock_cli_args};
+use crate::mock::{CassetteResolver, MockConfig, build_mock_cli_args, build_record_cli_args};
 use crate::models::TestResult;
 use crate::scenarios::{ScenarioError, TestScenario};
 use crate::workspace::WorkspaceManager;
@@ -461,19 +461,41 @@ impl TestRunner {
         let cassette_dir = mock_config.resolve_cassette_dir();
         let resolver = CassetteResolver::new(&cassette_dir);
 
-        // Resolve cassette path for this scenario
-        let cassette_path = resolver.resolve(scenario_id, backend).map_err(|e| {
-            RunnerError::WorkspaceError(format!("Cassette resolution failed: {}", e))
-        })?;
+        // Re-record missing or stale cassettes through the real backend when asked to
+        let record_target = if mock_config.update_cassettes {
+            resolver.recording_target(scenario_id, backend)
+        } else {
+            None
+        };
+
+        let (mock_args, prompt_flag) = if let Some(target) = record_target {
+            // The recorder appends per invocation, so start from an empty cassette
+            if target.exists() {
+                fs::remove_file(&target).map_err(|e| {
+                    RunnerError::WorkspaceError(format!(
+                        "Failed to remove stale cassette {}: {}",
+                        target.display(),
+                        e
+                    ))
+                })?;
+            }
+            (
+                build_record_cli_args(&target, backend),
+                backend.prompt_flag(),
+            )
+        } else {
+            // Resolve cassette path for this scenario
+            let cassette_path = resolver.resolve(scenario_id, backend).map_err(|e| {
+                RunnerError::WorkspaceError(format!("Cassette resolution failed: {}", e))
+            })?;
+            (build_mock_cli_args(&cassette_path, mock_config), None)
+        };
 
         // Get the ralph-e2e binary path (same as the currently running binary)
         let mock_cli_binary = std::env::current_exe().map_err(|e| {
             RunnerError::WorkspaceError(format!("Failed to get current exe: {}", e))
         })?;
 
-        // Build the mock-cli args
-        let mock_args = build_mock_cli_args(&cassette_path, mock_config);
-
         // Read the existing ralph.yml to preserve non-backend config
         let ralph_yml_path = workspace_path.join("ralph.yml");
         let existing_content = fs::read_to_string(&ralph_yml_path)
@@ -485,7 +507,7 @@ impl TestRunner {
 
         // Override CLI backend settings
         if let serde_yaml::Value::Mapping(ref mut map) = config {
-            let cli_config = serde_yaml::Mapping::from_iter(vec![
+            let mut cli_config = serde_yaml::Mapping::from_iter(vec![
                 (
                     serde_yaml::Value::String("backend".to_string()),
                     serde_yaml::Value::String("custom".to_string()),
@@ -504,6 +526,12 @@ impl TestRunner {
                     ),
                 ),
             ]);
+            if let Some(flag) = prompt_flag {
+                cli_config.insert(
+                    serde_yaml::Value::String("prompt_flag".to_string()),
+                    serde_yaml::Value::String(flag.to_string()),
+                );
+            }
 
             map.insert(
                 serde_yaml::Value::String("cli".to_string()),
@@ -1051,4 +1079,49 @@ mod tests {
 
         cleanup_workspace(&workspace);
     }
+
+    #[test]
+    fn test_configure_mock_mode_records_missing_cassette() {
+        let workspace = test_workspace_base("mock-record");
+        let cassette_dir = workspace.join("cassettes");
+        let scenario_dir = workspace.join("scenario");
+        std::fs::create_dir_all(&scenario_dir).unwrap();
+        std::fs::write(scenario_dir.join("ralph.yml"), "cli:\n  backend: claude\n").unwrap();
+
+        let runner = TestRunner::new(WorkspaceManager::new(workspace.clone()), Vec::new());
+        let mock_config = MockConfig::new(&cassette_dir).with_update_cassettes(true);
+        runner
+            .configure_mock_mode(&scenario_dir, "connect", Backend::Claude, &mock_config)
+            .unwrap();
+
+        let written: serde_yaml::Value =
+            serde_yaml::from_str(&std::fs::read_to_string(scenario_dir.join("ralph.yml")).unwrap())
+                .unwrap();
+        let args: Vec<&str> = written["cli"]["args"]
+            .as_sequence()
+            .unwrap()
+            .iter()
+            .filter_map(|v| v.as_str())
+            .collect();
+        assert_eq!(args[0], "record");
+        assert!(args[2].ends_with("connect-claude.jsonl"));
+        assert_eq!(written["cli"]["prompt_flag"].as_str(), Some("-p"));
+
+        cleanup_workspace(&workspace);
+    }
+
+    #[test]
+    fn test_configure_mock_mode_without_update_requires_cassette() {
+        let workspace = test_workspace_base("mock-missing");
+        let scenario_dir = workspace.join("scenario");
+        std::fs::create_dir_all(&scenario_dir).unwrap();
+
+        let runner = TestRunner::new(WorkspaceManager::new(workspace.clone()), Vec::new());
+        let mock_config = MockConfig::new(workspace.join("cassettes"));
+        let result =
+            runner.configure_mock_mode(&scenario_dir, "connect", Backend::Claude, &mock_config);
+        assert!(matches!(result, Err(RunnerError::WorkspaceError(_))));
+
+        cleanup_workspace(&workspace);
+    }
 }
@@ -0,0 +1,595 @@
+//! Payload conditions for conditional event routing.
+//!
+//! A condition is a small boolean expression evaluated against an event's
+//! payload during routing. JSON payloads are addressed with `$`-rooted paths;
+//! text payloads are matched with regular expressions:
+//!
+//! ```text
+//! $.failures > 0
+//! $.status == "rejected" || $.summary =~ /regress/i
+//! /FAIL(ED)?/
+//! !$.draft && $.files[0] != null
+//! ```
+//!
+//! A payload that is not valid JSON is treated as a single string at `$`.
+//! A path that does not resolve makes any comparison on it false.
+
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::cmp::Ordering;
+
+/// Error produced when a condition expression fails to parse.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("{message} at position {position}")]
+pub struct ConditionError {
+    /// Byte offset into the expression where parsing failed.
+    pub position: usize,
+    /// What went wrong.
+    pub message: String,
+}
+
+/// A parsed payload condition.
+#[derive(Debug, Clone)]
+pub struct Condition {
+    source: String,
+    expr: Expr,
+}
+
+impl Condition {
+    /// Parses a condition expression.
+    pub fn parse(source: &str) -> Result<Self, ConditionError> {
+        let tokens = tokenize(source)?;
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            end: source.len(),
+        };
+        let expr = parser.parse_or()?;
+        if let Some((position, _)) = parser.tokens.get(parser.pos) {
+            return Err(ConditionError {
+                position: *position,
+                message: "unexpected trailing input".to_string(),
+            });
+        }
+        Ok(Self {
+            source: source.trim().to_string(),
+            expr,
+        })
+    }
+
+    /// Returns the expression as written.
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Evaluates the condition against an event payload.
+    pub fn matches(&self, payload: &str) -> bool {
+        let root = serde_json::from_str(payload).unwrap_or_else(|_| Value::String(payload.into()));
+        self.expr.eval(payload, &root)
+    }
+}
+
+impl std::fmt::Display for Condition {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl Serialize for Condition {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.source)
+    }
+}
+
+impl<'de> Deserialize<'de> for Condition {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let source = String::deserialize(deserializer)?;
+        Self::parse(&source).map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Or(Box<Expr>, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    /// Truthiness of a single operand.
+    Truthy(Operand),
+    Compare(Operand, CmpOp, Operand),
+    /// `operand =~ /regex/`
+    Matches(Operand, Regex),
+    /// Bare `/regex/`, matched against the raw payload text.
+    Text(Regex),
+}
+
+#[derive(Debug, Clone)]
+enum Operand {
+    Path(Vec<Segment>),
+    Literal(Value),
+}
+
+#[derive(Debug, Clone)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CmpOp {
+    Eq,
+    Ne,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl Expr {
+    fn eval(&self, text: &str, root: &Value) -> bool {
+        match self {
+            Self::Or(a, b) => a.eval(text, root) || b.eval(text, root),
+            Self::And(a, b) => a.eval(text, root) && b.eval(text, root),
+            Self::Not(inner) => !inner.eval(text, root),
+            Self::Truthy(operand) => operand.resolve(root).is_some_and(truthy),
+            Self::Compare(lhs, op, rhs) => match (lhs.resolve(root), rhs.resolve(root)) {
+                (Some(a), Some(b)) => compare(a, *op, b),
+                _ => false,
+            },
+            Self::Matches(operand, re) => operand.resolve(root).is_some_and(|value| match value {
+                Value::String(s) => re.is_match(s),
+                other => re.is_match(&other.to_string()),
+            }),
+            Self::Text(re) => re.is_match(text),
+        }
+    }
+}
+
+impl Operand {
+    fn resolve<'a>(&'a self, root: &'a Value) -> Option<&'a Value> {
+        match self {
+            Self::Literal(value) => Some(value),
+            Self::Path(segments) => {
+                segments
+                    .iter()
+                    .try_fold(root, |value, segment| match segment {
+                        Segment::Key(key) => value.get(key.as_str()),
+                        Segment::Index(index) => value.get(*index),
+                    })
+            }
+        }
+    }
+}
+
+fn truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(b) => *b,
+        Value::Number(n) => n.as_f64().is_some_and(|n| n != 0.0),
+        Value::String(s) => !s.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(fields) => !fields.is_empty(),
+    }
+}
+
+fn compare(a: &Value, op: CmpOp, b: &Value) -> bool {
+    let ordering = match (a, b) {
+        (Value::Number(x), Value::Number(y)) => x
+            .as_f64()
+            .zip(y.as_f64())
+            .and_then(|(x, y)| x.partial_cmp(&y)),
+        (Value::String(x), Value::String(y)) => Some(x.cmp(y)),
+        _ => None,
+    };
+    match op {
+        CmpOp::Eq => ordering.map_or(a == b, Ordering::is_eq),
+        CmpOp::Ne => ordering.map_or(a != b, Ordering::is_ne),
+        CmpOp::Gt => ordering.is_some_and(Ordering::is_gt),
+        CmpOp::Ge => ordering.is_some_and(Ordering::is_ge),
+        CmpOp::Lt => ordering.is_some_and(Ordering::is_lt),
+        CmpOp::Le => ordering.is_some_and(Ordering::is_le),
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Token {
+    LParen,
+    RParen,
+    And,
+    Or,
+    Not,
+    Cmp(CmpOp),
+    Match,
+    Regex(Regex),
+    Path(Vec<Segment>),
+    Literal(Value),
+}
+
+fn error(position: usize, message: impl Into<String>) -> ConditionError {
+    ConditionError {
+        position,
+        message: message.into(),
+    }
+}
+
+fn tokenize(source: &str) -> Result<Vec<(usize, Token)>, ConditionError> {
+    let bytes = source.as_bytes();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let start = i;
+        let rest = &source[i..];
+        let token = match bytes[i] {
+            b if b.is_ascii_whitespace() => {
+                i += 1;
+                continue;
+            }
+            b'(' => {
+                i += 1;
+                Token::LParen
+            }
+            b')' => {
+                i += 1;
+                Token::RParen
+            }
+            _ if rest.starts_with("&&") => {
+                i += 2;
+                Token::And
+            }
+            _ if rest.starts_with("||") => {
+                i += 2;
+                Token::Or
+            }
+            _ if rest.starts_with("=~") => {
+                i += 2;
+                Token::Match
+            }
+            _ if rest.starts_with("==") => {
+                i += 2;
+                Token::Cmp(CmpOp::Eq)
+            }
+            _ if rest.starts_with("!=") => {
+                i += 2;
+                Token::Cmp(CmpOp::Ne)
+            }
+            _ if rest.starts_with(">=") => {
+                i += 2;
+                Token::Cmp(CmpOp::Ge)
+            }
+            _ if rest.starts_with("<=") => {
+                i += 2;
+                Token::Cmp(CmpOp::Le)
+            }
+            b'>' => {
+                i += 1;
+                Token::Cmp(CmpOp::Gt)
+            }
+            b'<' => {
+                i += 1;
+                Token::Cmp(CmpOp::Lt)
+            }
+            b'!' => {
+                i += 1;
+                Token::Not
+            }
+            b'/' => {
+                let (re, len) = lex_regex(source, i)?;
+                i += len;
+                Token::Regex(re)
+            }
+            b'"' | b'\'' => {
+                let (s, len) = lex_string(source, i)?;
+                i += len;
+                Token::Literal(Value::String(s))
+            }
+            b'$' => {
+                let (segments, len) = lex_path(source, i)?;
+                i += len;
+                Token::Path(segments)
+            }
+            b'-' | b'0'..=b'9' => {
+                let len = rest
+                    .char_indices()
+                    .skip(1)
+                    .find(|(_, c)| !(c.is_ascii_digit() || *c == '.'))
+                    .map_or(rest.len(), |(n, _)| n);
+                let number: f64 = rest[..len]
+                    .parse()
+                    .map_err(|_| error(start, format!("invalid number '{}'", &rest[..len])))?;
+                i += len;
+                Token::Literal(
+                    serde_json::Number::from_f64(number).map_or(Value::Null, Value::Number),
+                )
+            }
+            b if b.is_ascii_alphabetic() => {
+                let len = rest
+                    .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+                    .unwrap_or(rest.len());
+                let word = &rest[..len];
+                i += len;
+                match word {
+                    "true" => Token::Literal(Value::Bool(true)),
+                    "false" => Token::Literal(Value::Bool(false)),
+                    "null" => Token::Literal(Value::Null),
+                    _ => {
+                        return Err(error(
+                            start,
+                            format!(
+                                "unknown word '{word}' (paths start with '$', strings are quoted)"
+                            ),
+                        ));
+                    }
+                }
+            }
+            _ => {
+                let c = rest.chars().next().unwrap_or_default();
+                return Err(error(start, format!("unexpected character '{c}'")));
+            }
+        };
+        tokens.push((start, token));
+    }
+
+    Ok(tokens)
+}
+
+/// Lexes `/pattern/` with an optional trailing `i` flag.
+fn lex_regex(source: &str, start: usize) -> Result<(Regex, usize), ConditionError> {
+    let body_start = start + 1;
+    let mut pattern = String::new();
+    let mut chars = source[body_start..].char_indices();
+    let end = loop {
+        match chars.next() {
+            Some((n, '/')) => break body_start + n + 1,
+            Some((_, '\\')) => match chars.next() {
+                Some((_, '/')) => pattern.push('/'),
+                Some((_, c)) => {
+                    pattern.push('\\');
+                    pattern.push(c);
+                }
+                None => return Err(error(start, "unterminated regex")),
+            },
+            Some((_, c)) => pattern.push(c),
+            None => return Err(error(start, "unterminated regex")),
+        }
+    };
+    let (end, pattern) = if source[end..].starts_with('i')
+        && !source[end + 1..].starts_with(|c: char| c.is_ascii_alphanumeric())
+    {
+        (end + 1, format!("(?i){pattern}"))
+    } else {
+        (end, pattern)
+    };
+    let re = Regex::new(&pattern).map_err(|e| error(start, format!("invalid regex: {e}")))?;
+    Ok((re, end - start))
+}
+
+/// Lexes a single- or double-quoted string with backslash escapes.
+fn lex_string(source: &str, start: usize) -> Result<(String, usize), ConditionError> {
+    let quote = source[start..].chars().next().unwrap_or('"');
+    let mut value = String::new();
+    let mut chars = source[start + 1..].char_indices();
+    loop {
+        match chars.next() {
+            Some((n, c)) if c == quote => return Ok((value, n + 2)),
+            Some((_, '\\')) => match chars.next() {
+                Some((_, 'n')) => value.push('\n'),
+                Some((_, 't')) => value.push('\t'),
+                Some((_, c)) => value.push(c),
+                None => return Err(error(start, "unterminated string")),
+            },
+            Some((_, c)) => value.push(c),
+            None => return Err(error(start, "unterminated string")),
+        }
+    }
+}
+
+/// Lexes `$`, `$.a.b`, `$.items[0]` and `$["odd key"]`.
+fn lex_path(source: &str, start: usize) -> Result<(Vec<Segment>, usize), ConditionError> {
+    let mut segments = Vec::new();
+    let mut i = start + 1;
+    loop {
+        let rest = &source[i..];
+        if let Some(after_dot) = rest.strip_prefix('.') {
+            let len = after_dot
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '_' && c != '-')
+                .unwrap_or(after_dot.len());
+            if len == 0 {
+                return Err(error(i, "expected field name after '.'"));
+            }
+            segments.push(Segment::Key(after_dot[..len].to_string()));
+            i += 1 + len;
+        } else if let Some(after_bracket) = rest.strip_prefix('[') {
+            if after_bracket.starts_with(['"', '\'']) {
+                let (key, len) = lex_string(source, i + 1)?;
+                segments.push(Segment::Key(key));
+                i += 1 + len;
+            } else {
+                let len = after_bracket
+                    .find(|c: char| !c.is_ascii_digit())
+                    .unwrap_or(after_bracket.len());
+                let index = after_bracket[..len]
+                    .parse()
+                    .map_err(|_| error(i, "expected array index or quoted key after '['"))?;
+                segments.push(Segment::Index(index));
+                i += 1 + len;
+            }
+            if !source[i..].starts_with(']') {
+                return Err(error(i, "expected ']'"));
+            }
+            i += 1;
+        } else {
+            return Ok((segments, i - start));
+        }
+    }
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    pos: usize,
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos).map(|(_, t)| t)
+    }
+
+    fn position(&self) -> usize {
+        self.tokens.get(self.pos).map_or(self.end, |(p, _)| *p)
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.pos).map(|(_, t)| t.clone());
+        self.pos += 1;
+        token
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, ConditionError> {
+        let mut lhs = self.parse_and()?;
+        while matches!(self.peek(), Some(Token::Or)) {
+            self.pos += 1;
+            lhs = Expr::Or(Box::new(lhs), Box::new(self.parse_and()?));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, ConditionError> {
+        let mut lhs = self.parse_unary()?;
+        while matches!(self.peek(), Some(Token::And)) {
+            self.pos += 1;
+            lhs = Expr::And(Box::new(lhs), Box::new(self.parse_unary()?));
+        }
+        Ok(lhs)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, ConditionError> {
+        if matches!(self.peek(), Some(Token::Not)) {
+            self.pos += 1;
+            return Ok(Expr::Not(Box::new(self.parse_unary()?)));
+        }
+        self.parse_primary()
+    }
+
+    fn parse_primary(&mut self) -> Result<Expr, ConditionError> {
+        let position = self.position();
+        match self.next() {
+            Some(Token::LParen) => {
+                let inner = self.parse_or()?;
+                match self.next() {
+                    Some(Token::RParen) => Ok(inner),
+                    _ => Err(error(position, "unclosed '('")),
+                }
+            }
+            Some(Token::Regex(re)) => Ok(Expr::Text(re)),
+            Some(Token::Path(segments)) => self.parse_comparison(Operand::Path(segments)),
+            Some(Token::Literal(value)) => self.parse_comparison(Operand::Literal(value)),
+            Some(_) => Err(error(position, "expected a path, literal, regex or '('")),
+            None => Err(error(position, "expected an expression")),
+        }
+    }
+
+    fn parse_comparison(&mut self, lhs: Operand) -> Result<Expr, ConditionError> {
+        match self.peek() {
+            Some(Token::Cmp(op)) => {
+                let op = *op;
+                self.pos += 1;
+                let position = self.position();
+                let rhs = match self.next() {
+                    Some(Token::Path(segments)) => Operand::Path(segments),
+                    Some(Token::Literal(value)) => Operand::Literal(value),
+                    _ => return Err(error(position, "expected a path or literal after operator")),
+                };
+                Ok(Expr::Compare(lhs, op, rhs))
+            }
+            Some(Token::Match) => {
+                self.pos += 1;
+                let position = self.position();
+                match self.next() {
+                    Some(Token::Regex(re)) => Ok(Expr::Matches(lhs, re)),
+                    _ => Err(error(position, "expected /regex/ after '=~'")),
+                }
+            }
+            _ => Ok(Expr::Truthy(lhs)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn eval(expr: &str, payload: &str) -> bool {
+        Condition::parse(expr).unwrap().matches(payload)
+    }
+
+    #[test]
+    fn test_json_field_comparisons() {
+        let payload = r#"{"failures": 3, "status": "rejected", "meta": {"files": ["a.rs"]}}"#;
+        assert!(eval("$.failures > 0", payload));
+        assert!(eval("$.failures >= 3", payload));
+        assert!(!eval("$.failures < 3", payload));
+        assert!(eval("$.status == \"rejected\"", payload));
+        assert!(eval("$.status != 'approved'", payload));
+        assert!(eval("$.meta.files[0] == \"a.rs\"", payload));
+        assert!(eval("$[\"status\"] == \"rejected\"", payload));
+        assert!(eval("$.failures == 3.0", payload));
+    }
+
+    #[test]
+    fn test_missing_fields_never_compare() {
+        let payload = r#"{"failures": 0}"#;
+        assert!(!eval("$.errors > 0", payload));
+        assert!(!eval("$.errors == null", payload));
+        assert!(!eval("$.errors != 1", payload));
+        assert!(!eval("$.failures", payload));
+        assert!(eval("!$.errors", payload));
+    }
+
+    #[test]
+    fn test_boolean_operators_and_precedence() {
+        let payload = r#"{"a": true, "b": false, "n": 2}"#;
+        assert!(eval("$.a && !$.b", payload));
+        assert!(eval("$.b || $.n == 2", payload));
+        assert!(eval("$.b && $.a || $.a", payload));
+        assert!(!eval("$.b && ($.a || $.a)", payload));
+    }
+
+    #[test]
+    fn test_regex_on_text_and_fields() {
+        assert!(eval("/FAIL(ED)?/", "3 tests FAILED"));
+        assert!(!eval("/FAIL/", "all passed"));
+        assert!(eval("/fail/i", "Build FAILED"));
+        assert!(eval("$ =~ /^done/", "done: ok"));
+        assert!(eval(
+            "$.summary =~ /regress/",
+            r#"{"summary": "perf regression"}"#
+        ));
+        assert!(eval("$.count =~ /^4/", r#"{"count": 42}"#));
+        assert!(eval(r"/a\/b/", "path a/b"));
+    }
+
+    #[test]
+    fn test_parse_errors_report_position() {
+        let err = Condition::parse("$.failures >").unwrap_err();
+        assert_eq!(err.position, 12);
+        assert!(Condition::parse("failures > 0").is_err());
+        assert!(Condition::parse("$.a == \"unterminated").is_err());
+        assert!(Condition::parse("/[unclosed/").is_err());
+        assert!(Condition::parse("($.a").is_err());
+        assert!(Condition::parse("$.a $.b").is_err());
+        assert!(Condition::parse("").is_err());
+        assert!(Condition::parse("$.").is_err());
+    }
+
+    #[test]
+    fn test_serde_round_trip_keeps_source() {
+        let condition = Condition::parse("  $.failures > 0 ").unwrap();
+        assert_eq!(condition.as_str(), "$.failures > 0");
+        let json = serde_json::to_string(&condition).unwrap();
+        assert_eq!(json, "\"$.failures > 0\"");
+        let back: Condition = serde_json::from_str(&json).unwrap();
+        assert!(back.matches(r#"{"failures": 1}"#));
+        assert!(serde_json::from_str::<Condition>("\"$.a >\"").is_err());
+    }
+}
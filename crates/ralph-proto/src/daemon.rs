@@ -3,21 +3,42 @@
 //! Defines the [`DaemonAdapter`] trait that communication adapters (Telegram,
 //! Slack, etc.) implement to support `ralph bot daemon`. The CLI layer creates
 //! the adapter and passes a [`StartLoopFn`] callback — the adapter calls it
-//! when a user sends a message that should start an orchestration loop.
+//! when a queued request should start an orchestration loop.
 
 use std::future::Future;
 use std::path::PathBuf;
 use std::pin::Pin;
 
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+/// A request to start an orchestration loop.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LoopRequest {
+    /// The prompt for the loop.
+    pub prompt: String,
+    /// Run alongside the loop that holds the primary slot, in its own
+    /// worktree and without a communication service of its own.
+    pub parallel: bool,
+}
+
+/// How a loop started through [`StartLoopFn`] ended.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LoopOutcome {
+    /// The loop ID (e.g., `primary-20250101-120000` or a worktree name).
+    pub loop_id: Option<String>,
+    /// Termination reason as reported to the user (e.g., `"CompletionPromise"`).
+    pub termination: String,
+}
 
 /// Callback the adapter calls to start an orchestration loop.
 ///
-/// Accepts a prompt string, returns `Ok(description)` on success (e.g.,
-/// `"CompletionPromise"`) or `Err` on failure. The adapter doesn't need
-/// to know about `TerminationReason` — it just reports the result.
+/// Returns the [`LoopOutcome`] on completion or `Err` on failure. The adapter
+/// doesn't need to know about `TerminationReason` — it just reports the result.
 pub type StartLoopFn = Box<
-    dyn Fn(String) -> Pin<Box<dyn Future<Output = anyhow::Result<String>> + Send>> + Send + Sync,
+    dyn Fn(LoopRequest) -> Pin<Box<dyn Future<Output = anyhow::Result<LoopOutcome>> + Send>>
+        + Send
+        + Sync,
 >;
 
 /// A communication adapter that can run in daemon mode.
@@ -110,10 +110,11 @@ impl EventBus {
         let mut fallback_recipients = Vec::new();
 
         for (id, hat) in &self.hats {
-            if hat.has_specific_subscription(&event.topic) {
-                // Hat has a specific subscription for this topic
+            if hat.accepts_specific(&event) {
+                // Hat has a specific subscription for this topic (and its
+                // payload condition, if any, holds)
                 specific_recipients.push(id.clone());
-            } else if hat.is_subscribed(&event.topic) {
+            } else if hat.accepts(&event) {
                 // Hat matches only via global wildcard (fallback)
                 fallback_recipients.push(id.clone());
             }
@@ -218,6 +219,26 @@ mod tests {
         assert!(recipients.is_empty());
     }
 
+    #[test]
+    fn test_conditional_subscription_falls_back_when_unmet() {
+        let mut bus = EventBus::new();
+
+        let fixer = Hat::new("fixer", "Fixer")
+            .subscribe("review.done")
+            .with_condition(
+                "review.done",
+                crate::Condition::parse("$.failures > 0").unwrap(),
+            );
+        bus.register(fixer);
+        bus.register(Hat::new("ralph", "Ralph").subscribe("*"));
+
+        let recipients = bus.publish(Event::new("review.done", r#"{"failures": 1}"#));
+        assert_eq!(recipients, vec![HatId::new("fixer")]);
+
+        let recipients = bus.publish(Event::new("review.done", r#"{"failures": 0}"#));
+        assert_eq!(recipients, vec![HatId::new("ralph")]);
+    }
+
     #[test]
     fn test_direct_target() {
         let mut bus = EventBus::new();
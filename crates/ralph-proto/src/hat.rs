@@ -2,8 +2,9 @@
 //!
 //! A hat defines how the CLI agent should behave for a given iteration.
 
-use crate::Topic;
+use crate::{Condition, Event, Topic};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Unique identifier for a hat.
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
@@ -60,6 +61,13 @@ pub struct Hat {
 
     /// Instructions prepended to prompts for this hat.
     pub instructions: String,
+
+    /// Payload conditions keyed by subscription pattern.
+    ///
+    /// A subscription with a condition only matches events whose payload
+    /// satisfies it; subscriptions without one match on topic alone.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub conditions: HashMap<String, Condition>,
 }
 
 impl Hat {
@@ -72,6 +80,7 @@ impl Hat {
             subscriptions: Vec::new(),
             publishes: Vec::new(),
             instructions: String::new(),
+            conditions: HashMap::new(),
         }
     }
 
@@ -92,6 +101,7 @@ impl Hat {
             subscriptions: vec![Topic::new("*")],
             publishes: vec![Topic::new("task.done")],
             instructions: String::new(),
+            conditions: HashMap::new(),
         }
     }
 
@@ -112,6 +122,7 @@ impl Hat {
             ],
             publishes: vec![Topic::new("build.task")],
             instructions: String::new(),
+            conditions: HashMap::new(),
         }
     }
 
@@ -127,6 +138,7 @@ impl Hat {
             subscriptions: vec![Topic::new("build.task")],
             publishes: vec![Topic::new("build.done"), Topic::new("build.blocked")],
             instructions: String::new(),
+            conditions: HashMap::new(),
         }
     }
 
@@ -174,6 +186,37 @@ impl Hat {
             .any(|sub| !sub.is_global_wildcard() && sub.matches(topic))
     }
 
+    /// Attaches a payload condition to one of this hat's subscriptions.
+    #[must_use]
+    pub fn with_condition(mut self, pattern: impl Into<String>, condition: Condition) -> Self {
+        self.conditions.insert(pattern.into(), condition);
+        self
+    }
+
+    /// Checks if this hat accepts the event, honouring payload conditions.
+    pub fn accepts(&self, event: &Event) -> bool {
+        self.subscriptions
+            .iter()
+            .any(|sub| self.subscription_accepts(sub, event))
+    }
+
+    /// Like `accepts()`, but ignores global wildcard subscriptions.
+    ///
+    /// Used for routing priority, mirroring `has_specific_subscription()`.
+    pub fn accepts_specific(&self, event: &Event) -> bool {
+        self.subscriptions
+            .iter()
+            .any(|sub| !sub.is_global_wildcard() && self.subscription_accepts(sub, event))
+    }
+
+    fn subscription_accepts(&self, sub: &Topic, event: &Event) -> bool {
+        sub.matches(&event.topic)
+            && self
+                .conditions
+                .get(sub.as_str())
+                .is_none_or(|condition| condition.matches(&event.payload))
+    }
+
     /// Returns true if all subscriptions are global wildcards (`*`).
     ///
     /// Used to identify fallback handlers like Ralph.
@@ -224,4 +267,19 @@ mod tests {
         assert!(!hat.is_subscribed(&Topic::new("task.start")));
         assert!(!hat.is_subscribed(&Topic::new("build.done")));
     }
+
+    #[test]
+    fn test_conditional_subscription() {
+        let hat = Hat::new("fixer", "Fixer")
+            .subscribe("review.done")
+            .subscribe("build.*")
+            .with_condition("review.done", Condition::parse("$.failures > 0").unwrap());
+
+        assert!(hat.accepts(&Event::new("review.done", r#"{"failures": 2}"#)));
+        assert!(!hat.accepts(&Event::new("review.done", r#"{"failures": 0}"#)));
+        assert!(!hat.accepts(&Event::new("review.done", "looks good")));
+        // Unconditioned subscriptions still match on topic alone.
+        assert!(hat.accepts(&Event::new("build.done", "ok")));
+        assert!(hat.accepts_specific(&Event::new("build.done", "ok")));
+    }
 }
@@ -7,8 +7,10 @@
 //! - Event and `EventBus` types for pub/sub messaging
 //! - Hat definitions for agent personas
 //! - Topic matching for event routing
+//! - Payload conditions for conditional routing
 //! - Common error types
 
+mod condition;
 pub mod daemon;
 mod error;
 mod event;
@@ -18,12 +20,13 @@ pub mod robot;
 mod topic;
 mod ux_event;
 
-pub use daemon::{DaemonAdapter, StartLoopFn};
+pub use condition::{Condition, ConditionError};
+pub use daemon::{DaemonAdapter, LoopOutcome, LoopRequest, StartLoopFn};
 pub use error::{Error, Result};
 pub use event::Event;
 pub use event_bus::EventBus;
 pub use hat::{Hat, HatId};
-pub use robot::{CheckinContext, RobotService};
+pub use robot::{AccessList, CheckinContext, RobotAccessRule, RobotRole, RobotService};
 pub use topic::Topic;
 pub use ux_event::{
     FrameCapture, TerminalColorMode, TerminalResize, TerminalWrite, TuiFrame, UxEvent,
@@ -57,6 +57,13 @@ pub trait RobotService: Send + Sync {
         context: Option<&CheckinContext>,
     ) -> anyhow::Result<i32>;
 
+    /// Send a one-off notification (Markdown), outside any question/answer
+    /// exchange — e.g. the result of a scheduled run.
+    ///
+    /// Returns `Ok(0)` if no recipient is configured (skipped silently),
+    /// or the message ID on success.
+    fn send_notification(&self, message: &str) -> anyhow::Result<i32>;
+
     /// Get the configured response timeout in seconds.
     fn timeout_secs(&self) -> u64;
 
@@ -71,3 +78,167 @@ pub trait RobotService: Send + Sync {
     /// Called during loop termination to cleanly shut down the backend.
     fn stop(self: Box<Self>);
 }
+
+/// What a human is allowed to do through a robot backend.
+///
+/// Roles are ordered: an operator can do everything a viewer can.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum RobotRole {
+    /// Read-only: status, tail, tasks, memories.
+    Viewer,
+    /// Full control: start loops, stop/restart, guidance, answering questions.
+    Operator,
+}
+
+impl std::fmt::Display for RobotRole {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RobotRole::Viewer => f.write_str("viewer"),
+            RobotRole::Operator => f.write_str("operator"),
+        }
+    }
+}
+
+/// Grants a role to a chat or to a user.
+///
+/// A chat rule covers everyone in that chat (including groups); a user rule
+/// follows the user into any chat the bot is in. With both set, the rule
+/// covers only that user within that chat.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RobotAccessRule {
+    /// Chat the rule applies to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chat_id: Option<i64>,
+    /// User the rule applies to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_id: Option<i64>,
+    /// Role granted.
+    pub role: RobotRole,
+}
+
+/// Access list for a robot backend.
+///
+/// An empty list is unrestricted: every sender is an operator, which keeps
+/// single-chat setups working without configuration.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccessList {
+    rules: Vec<RobotAccessRule>,
+}
+
+impl AccessList {
+    /// Creates an access list from rules.
+    pub fn new(rules: Vec<RobotAccessRule>) -> Self {
+        Self { rules }
+    }
+
+    /// Creates an access list granting a single chat the operator role.
+    pub fn single_chat(chat_id: i64) -> Self {
+        Self::new(vec![RobotAccessRule {
+            chat_id: Some(chat_id),
+            user_id: None,
+            role: RobotRole::Operator,
+        }])
+    }
+
+    /// Returns true if no rules are configured (unrestricted).
+    pub fn is_unrestricted(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Returns the configured rules.
+    pub fn rules(&self) -> &[RobotAccessRule] {
+        &self.rules
+    }
+
+    /// Returns the highest role granted to a sender, or `None` if unauthorized.
+    pub fn role_for(&self, chat_id: i64, user_id: Option<i64>) -> Option<RobotRole> {
+        if self.is_unrestricted() {
+            return Some(RobotRole::Operator);
+        }
+        self.rules
+            .iter()
+            .filter(|rule| {
+                (rule.chat_id.is_some() || rule.user_id.is_some())
+                    && rule.chat_id.is_none_or(|id| id == chat_id)
+                    && rule.user_id.is_none_or(|id| Some(id) == user_id)
+            })
+            .map(|rule| rule.role)
+            .max()
+    }
+
+    /// Returns true if the sender has at least the `required` role.
+    pub fn allows(&self, chat_id: i64, user_id: Option<i64>, required: RobotRole) -> bool {
+        self.role_for(chat_id, user_id)
+            .is_some_and(|role| role >= required)
+    }
+
+    /// Chats the bot can notify: configured chats plus users' private chats.
+    ///
+    /// In Telegram, a user's private chat ID equals their user ID.
+    pub fn notify_chats(&self) -> Vec<i64> {
+        let mut chats: Vec<i64> = self
+            .rules
+            .iter()
+            .filter_map(|rule| rule.chat_id.or(rule.user_id))
+            .collect();
+        chats.sort_unstable();
+        chats.dedup();
+        chats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(chat_id: Option<i64>, user_id: Option<i64>, role: RobotRole) -> RobotAccessRule {
+        RobotAccessRule {
+            chat_id,
+            user_id,
+            role,
+        }
+    }
+
+    #[test]
+    fn empty_access_list_is_unrestricted() {
+        let access = AccessList::default();
+        assert!(access.is_unrestricted());
+        assert_eq!(access.role_for(1, None), Some(RobotRole::Operator));
+    }
+
+    #[test]
+    fn role_for_takes_highest_matching_rule() {
+        let access = AccessList::new(vec![
+            rule(Some(-100), None, RobotRole::Viewer),
+            rule(None, Some(7), RobotRole::Operator),
+        ]);
+
+        // Anyone in the group can view; user 7 operates from anywhere.
+        assert_eq!(access.role_for(-100, Some(3)), Some(RobotRole::Viewer));
+        assert_eq!(access.role_for(-100, Some(7)), Some(RobotRole::Operator));
+        assert_eq!(access.role_for(7, Some(7)), Some(RobotRole::Operator));
+        assert_eq!(access.role_for(42, Some(3)), None);
+
+        assert!(access.allows(-100, Some(3), RobotRole::Viewer));
+        assert!(!access.allows(-100, Some(3), RobotRole::Operator));
+        assert_eq!(access.notify_chats(), vec![-100, 7]);
+
+        let scoped = AccessList::new(vec![rule(Some(-100), Some(7), RobotRole::Operator)]);
+        assert_eq!(scoped.role_for(-100, Some(7)), Some(RobotRole::Operator));
+        assert_eq!(scoped.role_for(-100, Some(3)), None);
+        assert_eq!(scoped.role_for(7, Some(7)), None);
+    }
+
+    #[test]
+    fn rules_deserialize_from_yaml_like_json() {
+        let rules: Vec<RobotAccessRule> = serde_json::from_str(
+            r#"[{"chat_id": 1, "role": "operator"}, {"user_id": 2, "role": "viewer"}]"#,
+        )
+        .unwrap();
+        assert_eq!(rules[0], rule(Some(1), None, RobotRole::Operator));
+        assert_eq!(rules[1], rule(None, Some(2), RobotRole::Viewer));
+    }
+}
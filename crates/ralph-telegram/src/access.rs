@@ -0,0 +1,146 @@
+//! Role checks for incoming Telegram messages.
+//!
+//! Viewers may use read-only commands (including `/queue` without a prompt);
+//! everything else — starting or queueing loops, `/cancel`, `/priority`,
+//! `/stop`, `/restart`, guidance and answers to questions — needs an operator.
+//! Inline-keyboard buttons follow the same split: "Show diff" is read-only,
+//! answering, merging and discarding need an operator.
+
+use ralph_proto::{AccessList, RobotRole};
+use tracing::warn;
+
+/// Commands that only read loop state.
+const VIEWER_COMMANDS: &[&str] = &[
+    "/help",
+    "/status",
+    "/tasks",
+    "/memories",
+    "/tail",
+    "/merges",
+];
+
+/// Returns the role needed to act on a message.
+pub fn required_role(text: &str) -> RobotRole {
+    let mut words = text.split_whitespace();
+    let command = words
+        .next()
+        .and_then(|word| word.split('@').next())
+        .unwrap_or_default();
+    let lists_queue = command == "/queue" && words.next().is_none();
+    if VIEWER_COMMANDS.contains(&command) || lists_queue {
+        RobotRole::Viewer
+    } else {
+        RobotRole::Operator
+    }
+}
+
+/// Checks a message against the access list.
+///
+/// Returns the sender's role when allowed. Otherwise logs the rejection and
+/// returns the reply to send back.
+pub fn authorize(
+    access: &AccessList,
+    chat_id: i64,
+    user_id: Option<i64>,
+    text: &str,
+) -> Result<RobotRole, String> {
+    authorize_role(access, chat_id, user_id, required_role(text))
+}
+
+/// Checks that a sender has at least the `required` role.
+///
+/// Used directly for inline-keyboard button presses, which carry callback
+/// data rather than message text.
+pub fn authorize_role(
+    access: &AccessList,
+    chat_id: i64,
+    user_id: Option<i64>,
+    required: RobotRole,
+) -> Result<RobotRole, String> {
+    match access.role_for(chat_id, user_id) {
+        Some(role) if role >= required => Ok(role),
+        Some(role) => {
+            warn!(
+                chat_id,
+                user_id,
+                role = %role,
+                required = %required,
+                "Rejected Telegram message: insufficient role"
+            );
+            Err(format!(
+                "⛔ This needs the {required} role (you are a {role})."
+            ))
+        }
+        None => {
+            warn!(
+                chat_id,
+                user_id, "Rejected Telegram message from unauthorized sender"
+            );
+            Err("⛔ You are not authorized to use this bot.".to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ralph_proto::RobotAccessRule;
+
+    fn access() -> AccessList {
+        AccessList::new(vec![
+            RobotAccessRule {
+                chat_id: Some(-100),
+                user_id: None,
+                role: RobotRole::Viewer,
+            },
+            RobotAccessRule {
+                chat_id: None,
+                user_id: Some(7),
+                role: RobotRole::Operator,
+            },
+        ])
+    }
+
+    #[test]
+    fn required_role_distinguishes_read_only_commands() {
+        assert_eq!(required_role("/status"), RobotRole::Viewer);
+        assert_eq!(required_role("/tail@ralph_bot"), RobotRole::Viewer);
+        assert_eq!(required_role("/stop"), RobotRole::Operator);
+        assert_eq!(required_role("/restart now"), RobotRole::Operator);
+        assert_eq!(required_role("add logging please"), RobotRole::Operator);
+        assert_eq!(required_role("/queue"), RobotRole::Viewer);
+        assert_eq!(required_role("/queue add logging"), RobotRole::Operator);
+        assert_eq!(required_role("/cancel 3"), RobotRole::Operator);
+        assert_eq!(required_role("/merges"), RobotRole::Viewer);
+    }
+
+    #[test]
+    fn authorize_enforces_roles() {
+        let access = access();
+
+        assert_eq!(
+            authorize(&access, -100, Some(3), "/status"),
+            Ok(RobotRole::Viewer)
+        );
+        assert_eq!(
+            authorize(&access, -100, Some(3), "/stop").unwrap_err(),
+            "⛔ This needs the operator role (you are a viewer)."
+        );
+        assert_eq!(
+            authorize(&access, -100, Some(7), "/stop"),
+            Ok(RobotRole::Operator)
+        );
+        assert_eq!(
+            authorize(&access, 55, Some(3), "/status").unwrap_err(),
+            "⛔ You are not authorized to use this bot."
+        );
+    }
+
+    #[test]
+    fn unrestricted_access_allows_everything() {
+        assert_eq!(
+            authorize(&AccessList::default(), 1, None, "/stop"),
+            Ok(RobotRole::Operator)
+        );
+    }
+}
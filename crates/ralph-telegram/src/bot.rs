@@ -3,6 +3,7 @@ use std::path::Path;
 use async_trait::async_trait;
 
 use crate::error::{TelegramError, TelegramResult};
+use ralph_core::redaction::redact;
 
 /// Trait abstracting Telegram bot operations for testability.
 ///
@@ -33,6 +34,79 @@ pub trait BotApi: Send + Sync {
         file_path: &Path,
         caption: Option<&str>,
     ) -> TelegramResult<i32>;
+
+    /// Send a text message with an inline keyboard (one `Vec` per row).
+    ///
+    /// Returns the Telegram message ID of the sent message.
+    async fn send_keyboard(
+        &self,
+        chat_id: i64,
+        text: &str,
+        rows: &[Vec<InlineButton>],
+    ) -> TelegramResult<i32>;
+
+    /// Replace the text of a sent message, removing its inline keyboard.
+    async fn edit_message(&self, chat_id: i64, message_id: i32, text: &str) -> TelegramResult<()>;
+
+    /// Acknowledge a button press, showing `text` as a short notification.
+    async fn answer_callback(&self, callback_id: &str, text: &str) -> TelegramResult<()>;
+}
+
+/// A button in an inline keyboard attached to a message.
+///
+/// Pressing it sends `callback_data` (at most 64 bytes) back to the bot as a
+/// callback query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InlineButton {
+    pub text: String,
+    pub callback_data: String,
+}
+
+impl InlineButton {
+    /// Create a button with a label and the data sent back when it's pressed.
+    pub fn new(text: impl Into<String>, callback_data: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            callback_data: callback_data.into(),
+        }
+    }
+}
+
+/// The public Telegram Bot API server.
+pub const DEFAULT_API_URL: &str = "https://api.telegram.org";
+
+/// Parses a Bot API base URL such as `RObot.telegram.api_url`.
+///
+/// Point this at a self-hosted Bot API server, or at
+/// [`MockTelegramServer`](crate::testing::MockTelegramServer) to run offline.
+pub fn parse_api_url(api_url: &str) -> TelegramResult<url::Url> {
+    url::Url::parse(api_url)
+        .map_err(|e| TelegramError::Startup(format!("invalid Telegram API URL '{api_url}': {e}")))
+}
+
+/// Builds a teloxide client, optionally talking to a non-default API server.
+///
+/// Loopback servers (a local mock) bypass any system proxy.
+pub(crate) fn teloxide_bot(token: &str, api_url: Option<&url::Url>) -> teloxide::Bot {
+    let loopback = api_url.is_some_and(|url| {
+        matches!(
+            url.host_str(),
+            Some("127.0.0.1" | "localhost" | "[::1]" | "::1")
+        )
+    });
+    let bot = if cfg!(test) || loopback {
+        let client = teloxide::net::default_reqwest_settings()
+            .no_proxy()
+            .build()
+            .expect("Client creation failed");
+        teloxide::Bot::with_client(token, client)
+    } else {
+        teloxide::Bot::new(token)
+    };
+    match api_url {
+        Some(url) => bot.set_api_url(url.clone()),
+        None => bot,
+    }
 }
 
 /// Wraps a `teloxide::Bot` and provides formatted messaging for Ralph.
@@ -43,21 +117,24 @@ pub struct TelegramBot {
 impl TelegramBot {
     /// Create a new TelegramBot from a bot token.
     pub fn new(token: &str) -> Self {
-        if cfg!(test) {
-            let client = teloxide::net::default_reqwest_settings()
-                .no_proxy()
-                .build()
-                .expect("Client creation failed");
-            Self {
-                bot: teloxide::Bot::with_client(token, client),
-            }
-        } else {
-            Self {
-                bot: teloxide::Bot::new(token),
-            }
+        Self {
+            bot: teloxide_bot(token, None),
         }
     }
 
+    /// Talk to the Bot API server at `api_url` instead of `api.telegram.org`.
+    #[must_use]
+    pub fn with_api_url(self, api_url: &url::Url) -> Self {
+        Self {
+            bot: teloxide_bot(self.bot.token(), Some(api_url)),
+        }
+    }
+
+    /// Wrap an already configured teloxide client.
+    pub(crate) fn from_bot(bot: teloxide::Bot) -> Self {
+        Self { bot }
+    }
+
     /// Format an outgoing question message using Telegram HTML.
     ///
     /// Includes emoji, hat name, iteration number, and the question text.
@@ -278,7 +355,7 @@ impl BotApi for TelegramBot {
 
         let result = self
             .bot
-            .send_message(teloxide::types::ChatId(chat_id), text)
+            .send_message(teloxide::types::ChatId(chat_id), redact(text))
             .parse_mode(ParseMode::Html)
             .await
             .map_err(|e| TelegramError::Send {
@@ -305,7 +382,7 @@ impl BotApi for TelegramBot {
             .send_document(teloxide::types::ChatId(chat_id), input_file);
 
         if let Some(cap) = caption {
-            request = request.caption(cap).parse_mode(ParseMode::Html);
+            request = request.caption(redact(cap)).parse_mode(ParseMode::Html);
         }
 
         let result = request.await.map_err(|e| TelegramError::Send {
@@ -332,7 +409,7 @@ impl BotApi for TelegramBot {
             .send_photo(teloxide::types::ChatId(chat_id), input_file);
 
         if let Some(cap) = caption {
-            request = request.caption(cap).parse_mode(ParseMode::Html);
+            request = request.caption(redact(cap)).parse_mode(ParseMode::Html);
         }
 
         let result = request.await.map_err(|e| TelegramError::Send {
@@ -342,106 +419,76 @@ impl BotApi for TelegramBot {
 
         Ok(result.id.0)
     }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::sync::{Arc, Mutex};
 
-    /// A mock BotApi for testing that records sent messages.
-    struct MockBot {
-        sent: Arc<Mutex<Vec<(i64, String)>>>,
-        next_id: Arc<Mutex<i32>>,
-        should_fail: bool,
-    }
-
-    impl MockBot {
-        fn new() -> Self {
-            Self {
-                sent: Arc::new(Mutex::new(Vec::new())),
-                next_id: Arc::new(Mutex::new(1)),
-                should_fail: false,
-            }
-        }
+    async fn send_keyboard(
+        &self,
+        chat_id: i64,
+        text: &str,
+        rows: &[Vec<InlineButton>],
+    ) -> TelegramResult<i32> {
+        use teloxide::payloads::SendMessageSetters;
+        use teloxide::prelude::*;
+        use teloxide::types::{InlineKeyboardButton, InlineKeyboardMarkup, ParseMode};
 
-        fn failing() -> Self {
-            Self {
-                sent: Arc::new(Mutex::new(Vec::new())),
-                next_id: Arc::new(Mutex::new(1)),
-                should_fail: true,
-            }
-        }
+        let keyboard = InlineKeyboardMarkup::new(rows.iter().map(|row| {
+            row.iter()
+                .map(|button| InlineKeyboardButton::callback(&button.text, &button.callback_data))
+                .collect::<Vec<_>>()
+        }));
+        let result = self
+            .bot
+            .send_message(teloxide::types::ChatId(chat_id), redact(text))
+            .parse_mode(ParseMode::Html)
+            .reply_markup(keyboard)
+            .await
+            .map_err(|e| TelegramError::Send {
+                attempts: 1,
+                reason: e.to_string(),
+            })?;
 
-        fn sent_messages(&self) -> Vec<(i64, String)> {
-            self.sent.lock().unwrap().clone()
-        }
+        Ok(result.id.0)
     }
 
-    #[async_trait]
-    impl BotApi for MockBot {
-        async fn send_message(&self, chat_id: i64, text: &str) -> TelegramResult<i32> {
-            if self.should_fail {
-                return Err(TelegramError::Send {
-                    attempts: 1,
-                    reason: "mock failure".to_string(),
-                });
-            }
-            self.sent.lock().unwrap().push((chat_id, text.to_string()));
-            let mut id = self.next_id.lock().unwrap();
-            let current = *id;
-            *id += 1;
-            Ok(current)
-        }
+    async fn edit_message(&self, chat_id: i64, message_id: i32, text: &str) -> TelegramResult<()> {
+        use teloxide::payloads::EditMessageTextSetters;
+        use teloxide::prelude::*;
+        use teloxide::types::{MessageId, ParseMode};
+
+        self.bot
+            .edit_message_text(
+                teloxide::types::ChatId(chat_id),
+                MessageId(message_id),
+                redact(text),
+            )
+            .parse_mode(ParseMode::Html)
+            .await
+            .map_err(|e| TelegramError::Send {
+                attempts: 1,
+                reason: e.to_string(),
+            })?;
+        Ok(())
+    }
 
-        async fn send_document(
-            &self,
-            chat_id: i64,
-            file_path: &Path,
-            caption: Option<&str>,
-        ) -> TelegramResult<i32> {
-            if self.should_fail {
-                return Err(TelegramError::Send {
-                    attempts: 1,
-                    reason: "mock failure".to_string(),
-                });
-            }
-            let label = format!(
-                "[doc:{}]{}",
-                file_path.display(),
-                caption.map(|c| format!(" {c}")).unwrap_or_default()
-            );
-            self.sent.lock().unwrap().push((chat_id, label));
-            let mut id = self.next_id.lock().unwrap();
-            let current = *id;
-            *id += 1;
-            Ok(current)
-        }
+    async fn answer_callback(&self, callback_id: &str, text: &str) -> TelegramResult<()> {
+        use teloxide::payloads::AnswerCallbackQuerySetters;
+        use teloxide::prelude::*;
 
-        async fn send_photo(
-            &self,
-            chat_id: i64,
-            file_path: &Path,
-            caption: Option<&str>,
-        ) -> TelegramResult<i32> {
-            if self.should_fail {
-                return Err(TelegramError::Send {
-                    attempts: 1,
-                    reason: "mock failure".to_string(),
-                });
-            }
-            let label = format!(
-                "[photo:{}]{}",
-                file_path.display(),
-                caption.map(|c| format!(" {c}")).unwrap_or_default()
-            );
-            self.sent.lock().unwrap().push((chat_id, label));
-            let mut id = self.next_id.lock().unwrap();
-            let current = *id;
-            *id += 1;
-            Ok(current)
-        }
+        self.bot
+            .answer_callback_query(callback_id.to_string())
+            .text(text)
+            .await
+            .map_err(|e| TelegramError::Send {
+                attempts: 1,
+                reason: e.to_string(),
+            })?;
+        Ok(())
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_bot::MockBot;
 
     #[test]
     fn format_question_includes_hat_and_loop() {
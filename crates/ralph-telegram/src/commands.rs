@@ -2,6 +2,10 @@ use std::path::Path;
 
 use crate::bot::escape_html;
 use crate::loop_lock::{LockState, lock_path, lock_state};
+use crate::queue::{Job, JobQueue, JobStatus, QueueManager};
+
+/// Finished jobs listed by `/queue`.
+const RECENT_JOBS: usize = 5;
 
 /// Check if a message is a bot command (starts with `/`).
 pub fn is_command(text: &str) -> bool {
@@ -27,8 +31,25 @@ pub fn handle_command(text: &str, workspace_root: &Path) -> Option<String> {
     }
 }
 
+/// Handle the job queue commands (`/queue`, `/cancel`, `/priority`).
+///
+/// Separate from [`handle_command`] because `/queue <prompt>` records the
+/// requesting chat. Returns `None` for any other text.
+pub fn handle_queue_command(text: &str, workspace_root: &Path, chat_id: i64) -> Option<String> {
+    let (command, args) = parse_command(text);
+    let queue = QueueManager::for_workspace(workspace_root);
+    let response = match command {
+        "/queue" if args.is_empty() => cmd_queue_list(&queue),
+        "/queue" => cmd_queue_add(&queue, args, chat_id),
+        "/cancel" => cmd_cancel(&queue, args),
+        "/priority" => cmd_priority(&queue, args),
+        _ => return None,
+    };
+    Some(response)
+}
+
 /// Split a command string into the command name and optional arguments.
-fn parse_command(text: &str) -> (&str, &str) {
+pub(crate) fn parse_command(text: &str) -> (&str, &str) {
     // Handle @bot suffix: /status@ralph_bot -> /status
     if let Some((first, rest)) = text.split_once(char::is_whitespace) {
         let cmd = first.split('@').next().unwrap_or(first);
@@ -39,7 +60,7 @@ fn parse_command(text: &str) -> (&str, &str) {
     }
 }
 
-fn truncate_with_ellipsis(input: &str, max_chars: usize) -> String {
+pub(crate) fn truncate_with_ellipsis(input: &str, max_chars: usize) -> String {
     if input.chars().count() <= max_chars {
         input.to_string()
     } else {
@@ -58,6 +79,11 @@ fn cmd_help() -> String {
         "/tasks — Open tasks",
         "/memories — Recent memories",
         "/tail — Last 20 events",
+        "/queue — Queued and recent jobs",
+        "/queue &lt;prompt&gt; — Queue a loop request",
+        "/cancel &lt;id&gt; — Cancel a queued job",
+        "/priority &lt;id&gt; — Move a queued job to the front",
+        "/merges — Worktree loops waiting to be merged",
         "/restart — Restart the orchestration loop",
         "/stop — Stop the orchestration loop",
         "/help — This message",
@@ -399,6 +425,136 @@ fn cmd_stop(workspace_root: &Path) -> String {
     }
 }
 
+/// `/queue` — Running, queued and recently finished jobs.
+fn cmd_queue_list(queue: &QueueManager) -> String {
+    let queue = match queue.load() {
+        Ok(queue) => queue,
+        Err(e) => return format!("Failed to read job queue: {}", escape_html(&e.to_string())),
+    };
+    format_queue(&queue)
+}
+
+fn format_queue(queue: &JobQueue) -> String {
+    let active: Vec<&Job> = queue
+        .jobs
+        .iter()
+        .filter(|job| job.status == JobStatus::Running)
+        .chain(queue.queued())
+        .collect();
+    let recent: Vec<&Job> = queue
+        .jobs
+        .iter()
+        .rev()
+        .filter(|job| job.status.is_finished())
+        .take(RECENT_JOBS)
+        .collect();
+
+    if active.is_empty() && recent.is_empty() {
+        return "Queue is empty. Send /queue &lt;prompt&gt; to add a job.".to_string();
+    }
+
+    let mut lines = vec!["<b>Job Queue</b>".to_string(), String::new()];
+    if active.is_empty() {
+        lines.push("No queued jobs.".to_string());
+    }
+    lines.extend(active.into_iter().map(format_job));
+    if !recent.is_empty() {
+        lines.push(String::new());
+        lines.push("<b>Recent</b>".to_string());
+        lines.extend(recent.into_iter().map(format_job));
+    }
+    lines.join("\n")
+}
+
+fn format_job(job: &Job) -> String {
+    let icon = match job.status {
+        JobStatus::Queued => "⏳",
+        JobStatus::Running => "▶️",
+        JobStatus::Done => "✅",
+        JobStatus::Failed => "❌",
+        JobStatus::Cancelled => "🚫",
+    };
+    let mut line = format!(
+        "{icon} #{} {} — <i>{}</i>",
+        job.id,
+        job.status,
+        escape_html(&truncate_with_ellipsis(&job.prompt, 60))
+    );
+    if let Some(loop_id) = &job.loop_id {
+        line.push_str(&format!(" (loop <code>{}</code>)", escape_html(loop_id)));
+    }
+    if let Some(termination) = &job.termination {
+        line.push_str(&format!(": {}", escape_html(termination)));
+    }
+    line
+}
+
+/// `/queue <prompt>` — Queue a loop request for the requesting chat.
+fn cmd_queue_add(queue: &QueueManager, prompt: &str, chat_id: i64) -> String {
+    match queue.update(|queue| {
+        let id = queue.enqueue(prompt, chat_id);
+        (id, queue.position(id).unwrap_or(1))
+    }) {
+        Ok((id, position)) => format!(
+            "Queued job #{id} (position {position}). You'll be notified when it starts and finishes."
+        ),
+        Err(e) => format!("Failed to queue job: {}", escape_html(&e.to_string())),
+    }
+}
+
+/// `/cancel <id>` — Cancel a queued job.
+fn cmd_cancel(queue: &QueueManager, args: &str) -> String {
+    let Some(id) = parse_job_id(args) else {
+        return "Usage: /cancel &lt;job id&gt;".to_string();
+    };
+    let result = queue.update(|queue| {
+        if queue.cancel(id) {
+            format!("Cancelled job #{id}.")
+        } else {
+            describe_unqueued_job(queue, id)
+        }
+    });
+    result.unwrap_or_else(|e| {
+        format!(
+            "Failed to update job queue: {}",
+            escape_html(&e.to_string())
+        )
+    })
+}
+
+/// `/priority <id>` — Move a queued job to the front of the queue.
+fn cmd_priority(queue: &QueueManager, args: &str) -> String {
+    let Some(id) = parse_job_id(args) else {
+        return "Usage: /priority &lt;job id&gt;".to_string();
+    };
+    let result = queue.update(|queue| {
+        if queue.prioritize(id) {
+            format!("Job #{id} moved to the front of the queue.")
+        } else {
+            describe_unqueued_job(queue, id)
+        }
+    });
+    result.unwrap_or_else(|e| {
+        format!(
+            "Failed to update job queue: {}",
+            escape_html(&e.to_string())
+        )
+    })
+}
+
+fn parse_job_id(args: &str) -> Option<u32> {
+    args.trim().trim_start_matches('#').parse().ok()
+}
+
+/// Explains why a job can't be cancelled or reprioritized.
+fn describe_unqueued_job(queue: &JobQueue, id: u32) -> String {
+    match queue.get(id).map(|job| job.status) {
+        Some(JobStatus::Running) => format!("Job #{id} is already running — use /stop to stop it."),
+        Some(status) => format!("Job #{id} already finished ({status})."),
+        None => format!("No job #{id} in the queue."),
+    }
+}
+
 /// `/tail` — Last 20 lines of the current events file.
 fn cmd_tail(workspace_root: &Path) -> String {
     // Find current events file
@@ -538,6 +694,61 @@ mod tests {
         assert!(result.contains("/memories"));
         assert!(result.contains("/tail"));
         assert!(result.contains("/help"));
+        assert!(result.contains("/queue"));
+        assert!(result.contains("/merges"));
+    }
+
+    #[test]
+    fn queue_commands_manage_jobs() {
+        let dir = TempDir::new().unwrap();
+        let root = dir.path();
+
+        assert!(handle_queue_command("/status", root, 1).is_none());
+        assert!(
+            handle_queue_command("/queue", root, 1)
+                .unwrap()
+                .starts_with("Queue is empty")
+        );
+
+        assert_eq!(
+            handle_queue_command("/queue add <b>logging</b>", root, 1).unwrap(),
+            "Queued job #1 (position 1). You'll be notified when it starts and finishes."
+        );
+        handle_queue_command("/queue write docs", root, 2).unwrap();
+        assert_eq!(
+            handle_queue_command("/priority #2", root, 1).unwrap(),
+            "Job #2 moved to the front of the queue."
+        );
+
+        let list = handle_queue_command("/queue@ralph_bot", root, 1).unwrap();
+        assert!(list.contains("&lt;b&gt;logging&lt;/b&gt;"), "{list}");
+        assert!(list.find("#2").unwrap() < list.find("#1").unwrap());
+
+        assert_eq!(
+            handle_queue_command("/cancel 1", root, 1).unwrap(),
+            "Cancelled job #1."
+        );
+        assert_eq!(
+            handle_queue_command("/cancel 1", root, 1).unwrap(),
+            "Job #1 already finished (cancelled)."
+        );
+        assert_eq!(
+            handle_queue_command("/priority 9", root, 1).unwrap(),
+            "No job #9 in the queue."
+        );
+        assert!(
+            handle_queue_command("/cancel soon", root, 1)
+                .unwrap()
+                .starts_with("Usage")
+        );
+
+        let queue = QueueManager::for_workspace(root).load().unwrap();
+        assert_eq!(queue.get(2).unwrap().chat_id, 2);
+        assert!(
+            handle_queue_command("/queue", root, 1)
+                .unwrap()
+                .contains("<b>Recent</b>")
+        );
     }
 
     #[test]
@@ -3,47 +3,273 @@
 //! Implements [`DaemonAdapter`] for Telegram, providing a persistent process
 //! that listens for messages and starts orchestration loops on demand.
 //!
-//! Uses a **turn-taking model**: the daemon polls Telegram while idle, but
-//! stops polling when a loop starts — the loop's own [`TelegramService`]
-//! takes over for the full Telegram feature set (commands, guidance,
-//! responses, check-ins). When the loop finishes, the daemon resumes.
+//! Serves several chats and users with viewer/operator roles (see [`crate::access`]).
+//!
+//! Every loop request becomes a job in the persistent [`crate::queue`]. Jobs
+//! start when the primary loop slot is free, or alongside the running loop in
+//! a worktree when parallel loops are enabled. Each job's loop ID and
+//! termination reason are reported back to the chat that requested it.
+//!
+//! Uses a **turn-taking model**: the daemon polls Telegram while no primary
+//! loop of its own runs, but stops polling when one starts — the loop's own
+//! [`TelegramService`] takes over for the full Telegram feature set
+//! (commands including `/queue`, guidance, responses, check-ins). When the
+//! loop finishes, the daemon resumes and starts the next queued job.
+//!
+//! While idle, the daemon also applies inline-keyboard button presses (see
+//! [`crate::keyboard`]). Merge buttons only work then: `merge_button_state`
+//! blocks merging while a primary loop runs.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
 
 use async_trait::async_trait;
+use tokio::task::JoinHandle;
 use tracing::{error, info, warn};
 
-use ralph_proto::daemon::{DaemonAdapter, StartLoopFn};
+use ralph_proto::AccessList;
+use ralph_proto::daemon::{DaemonAdapter, LoopOutcome, LoopRequest, StartLoopFn};
 
 use crate::bot::{BotApi, TelegramBot, escape_html};
+use crate::handler::MessageHandler;
+use crate::keyboard::{ButtonPress, CallbackContext, MergeAnnouncer};
 use crate::loop_lock::{LockState, lock_path, lock_state};
+use crate::queue::{Job, JobQueue, JobStatus, QueueManager};
 use crate::state::StateManager;
 
+/// How often running jobs are checked while a loop owns Telegram polling.
+const JOB_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Long-poll timeout with nothing running or queued.
+const IDLE_POLL_TIMEOUT_SECS: u64 = 30;
+
+/// Long-poll timeout while jobs run or wait, so they're reaped and started promptly.
+const BUSY_POLL_TIMEOUT_SECS: u64 = 5;
+
 async fn wait_for_shutdown(shutdown: Arc<AtomicBool>) {
     while !shutdown.load(Ordering::Relaxed) {
         tokio::time::sleep(std::time::Duration::from_millis(200)).await;
     }
 }
 
+/// A job whose loop has been started.
+struct RunningJob {
+    job: Job,
+    /// Runs in place holding the loop lock; its `TelegramService` polls Telegram.
+    primary: bool,
+    handle: JoinHandle<anyhow::Result<LoopOutcome>>,
+}
+
 /// A Telegram-based daemon adapter.
 ///
-/// Polls Telegram for messages while idle and delegates loop execution
-/// to the provided [`StartLoopFn`] callback. Supports `/status` commands
-/// and graceful shutdown via `SIGINT`/`SIGTERM`.
+/// Polls Telegram for messages while idle, queues loop requests and delegates
+/// loop execution to the provided [`StartLoopFn`] callback. Serves every chat
+/// and user in its [`AccessList`]: viewers can use read-only commands,
+/// operators can also queue loops and use `/cancel`, `/priority`, `/stop` and
+/// `/restart`. Supports graceful shutdown via `SIGINT`/`SIGTERM`.
 pub struct TelegramDaemon {
     bot_token: String,
-    chat_id: i64,
+    access: AccessList,
+    parallel: bool,
+    api_url: Option<url::Url>,
 }
 
 impl TelegramDaemon {
-    /// Create a new Telegram daemon.
+    /// Create a new Telegram daemon serving a single chat.
     ///
     /// `bot_token` — Telegram Bot API token.
-    /// `chat_id` — The Telegram chat to communicate with.
+    /// `chat_id` — The Telegram chat to communicate with (operator role).
     pub fn new(bot_token: String, chat_id: i64) -> Self {
-        Self { bot_token, chat_id }
+        Self::with_access(bot_token, AccessList::single_chat(chat_id))
+    }
+
+    /// Create a Telegram daemon serving the chats and users in `access`.
+    pub fn with_access(bot_token: String, access: AccessList) -> Self {
+        Self {
+            bot_token,
+            access,
+            parallel: false,
+            api_url: None,
+        }
+    }
+
+    /// Start queued jobs alongside a running loop, in worktrees
+    /// (`features.parallel`). Off by default: jobs run one at a time.
+    #[must_use]
+    pub fn with_parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    /// Talk to the Bot API server at `api_url` instead of `api.telegram.org`.
+    #[must_use]
+    pub fn with_api_url(mut self, api_url: url::Url) -> Self {
+        self.api_url = Some(api_url);
+        self
+    }
+
+    /// Send a message to every chat the daemon serves.
+    async fn broadcast(&self, bot: &TelegramBot, text: &str) {
+        for chat_id in self.access.notify_chats() {
+            if let Err(e) = bot.send_message(chat_id, text).await {
+                warn!(chat_id, error = %e, "Failed to send daemon notification");
+            }
+        }
+    }
+
+    /// Decides whether a job can start now, and how.
+    ///
+    /// Returns `Some(false)` to start in the primary slot, `Some(true)` to
+    /// start alongside the loop holding the lock, or `None` to wait. While one
+    /// of our primary jobs is starting up or shutting down the lock may not
+    /// reflect it, so nothing starts until it is either held or released.
+    fn start_mode(&self, lock: LockState, running: &[RunningJob]) -> Option<bool> {
+        if lock == LockState::Active {
+            self.parallel.then_some(true)
+        } else if running.iter().any(|job| job.primary) {
+            None
+        } else {
+            Some(false)
+        }
+    }
+
+    /// Starts queued jobs while a slot is available.
+    async fn dispatch_jobs(
+        &self,
+        bot: &TelegramBot,
+        queue: &QueueManager,
+        state_manager: &StateManager,
+        workspace_root: &Path,
+        start_loop: &StartLoopFn,
+        running: &mut Vec<RunningJob>,
+    ) {
+        loop {
+            let lock = match lock_state(workspace_root) {
+                Ok(lock) => lock,
+                Err(e) => {
+                    warn!(error = %e, "Failed to check loop lock state");
+                    return;
+                }
+            };
+            let Some(parallel) = self.start_mode(lock, running) else {
+                return;
+            };
+            let job = match queue.update(JobQueue::start_next) {
+                Ok(Some(job)) => job,
+                Ok(None) => return,
+                Err(e) => {
+                    warn!(error = %e, "Failed to update job queue");
+                    return;
+                }
+            };
+
+            if lock == LockState::Stale {
+                warn!(
+                    lock_path = %lock_path(workspace_root).display(),
+                    "Found stale loop lock; starting new loop"
+                );
+            }
+            if !parallel {
+                // The chat that requested the primary loop receives its questions and check-ins.
+                match state_manager.load_or_default() {
+                    Ok(mut state) => {
+                        state.chat_id = Some(job.chat_id);
+                        if let Err(e) = state_manager.save(&state) {
+                            warn!(error = %e, "Failed to persist Telegram state");
+                        }
+                    }
+                    Err(e) => warn!(error = %e, "Failed to load Telegram state"),
+                }
+            }
+
+            info!(job_id = job.id, parallel, "Starting queued job");
+            let ack = format!(
+                "Starting job #{}{}: <i>{}</i>",
+                job.id,
+                if parallel { " in a worktree" } else { "" },
+                escape_html(&job.prompt)
+            );
+            let _ = bot.send_message(job.chat_id, &ack).await;
+
+            let handle = tokio::spawn(start_loop(LoopRequest {
+                prompt: job.prompt.clone(),
+                parallel,
+            }));
+            running.push(RunningJob {
+                job,
+                primary: !parallel,
+                handle,
+            });
+        }
+    }
+
+    /// Records finished jobs and reports their result to the requesting chat.
+    async fn reap_jobs(
+        &self,
+        bot: &TelegramBot,
+        queue: &QueueManager,
+        running: &mut Vec<RunningJob>,
+    ) {
+        let mut index = 0;
+        while index < running.len() {
+            if !running[index].handle.is_finished() {
+                index += 1;
+                continue;
+            }
+            let RunningJob { job, handle, .. } = running.swap_remove(index);
+            let (status, loop_id, termination) = match handle.await {
+                Ok(Ok(outcome)) => (JobStatus::Done, outcome.loop_id, outcome.termination),
+                Ok(Err(e)) => (JobStatus::Failed, None, e.to_string()),
+                Err(e) => (JobStatus::Failed, None, e.to_string()),
+            };
+            info!(job_id = job.id, %status, termination = %termination, "Job finished");
+
+            if let Err(e) =
+                queue.update(|queue| queue.finish(job.id, status, loop_id.clone(), &termination))
+            {
+                warn!(error = %e, "Failed to update job queue");
+            }
+            let notification = job_result_message(job.id, status, loop_id.as_deref(), &termination);
+            let _ = bot.send_message(job.chat_id, &notification).await;
+        }
+    }
+
+    /// Queues a plain message as a loop request and returns the reply, if any.
+    ///
+    /// Jobs that start right away are acknowledged when they start.
+    fn queue_prompt(
+        &self,
+        queue: &QueueManager,
+        workspace_root: &Path,
+        text: &str,
+        chat_id: i64,
+        running: &[RunningJob],
+    ) -> Option<String> {
+        let (id, position) = match queue.update(|queue| {
+            let id = queue.enqueue(text, chat_id);
+            (id, queue.position(id).unwrap_or(1))
+        }) {
+            Ok(queued) => queued,
+            Err(e) => {
+                warn!(error = %e, "Failed to queue loop request");
+                return Some("Failed to queue your request; try again in a moment.".to_string());
+            }
+        };
+
+        let starts_now = match lock_state(workspace_root) {
+            Ok(lock) => match self.start_mode(lock, running) {
+                Some(parallel) => parallel || position == 1,
+                None => false,
+            },
+            Err(_) => false,
+        };
+        (!starts_now).then(|| {
+            format!(
+                "A loop is already running — queued as job #{id} (position {position}). Send /queue to see the queue."
+            )
+        })
     }
 }
 
@@ -54,13 +280,26 @@ impl DaemonAdapter for TelegramDaemon {
         workspace_root: PathBuf,
         start_loop: StartLoopFn,
     ) -> anyhow::Result<()> {
-        let bot = TelegramBot::new(&self.bot_token);
-        let chat_id = self.chat_id;
+        let raw_bot = crate::bot::teloxide_bot(&self.bot_token, self.api_url.as_ref());
+        let bot = TelegramBot::from_bot(raw_bot.clone());
+
+        let state_path = workspace_root.join(".ralph/telegram-state.json");
+        let state_manager = StateManager::new(&state_path);
+        let queue = QueueManager::for_workspace(&workspace_root);
+        let handler = MessageHandler::new(StateManager::new(&state_path), &workspace_root);
+        let callbacks = CallbackContext {
+            workspace_root: &workspace_root,
+            access: &self.access,
+            state_manager: &state_manager,
+            handler: &handler,
+        };
+        let mut merges = MergeAnnouncer::new(&workspace_root);
 
-        let state_manager = StateManager::new(workspace_root.join(".ralph/telegram-state.json"));
+        // Jobs a previous daemon left running never reported back.
+        fail_running_jobs(&bot, &queue, "interrupted (daemon restarted)").await;
 
         // Send greeting
-        let _ = bot.send_message(chat_id, "Ralph daemon online 🤖").await;
+        self.broadcast(&bot, "Ralph daemon online 🤖").await;
 
         // Install signal handlers for graceful shutdown
         let shutdown = Arc::new(AtomicBool::new(false));
@@ -89,15 +328,53 @@ impl DaemonAdapter for TelegramDaemon {
         }
 
         let mut offset: i32 = 0;
+        let mut running: Vec<RunningJob> = Vec::new();
 
         // Main daemon loop
         'daemon: while !shutdown.load(Ordering::Relaxed) {
+            self.reap_jobs(&bot, &queue, &mut running).await;
+            self.dispatch_jobs(
+                &bot,
+                &queue,
+                &state_manager,
+                &workspace_root,
+                &start_loop,
+                &mut running,
+            )
+            .await;
+
+            // ── Loop Running: hand off Telegram to the loop ──
+            // The loop's TelegramService polls getUpdates, handles commands
+            // (including /queue), guidance, responses, check-ins. We just
+            // watch for jobs finishing.
+            if running.iter().any(|job| job.primary) {
+                tokio::select! {
+                    _ = wait_for_shutdown(shutdown.clone()) => break 'daemon,
+                    () = tokio::time::sleep(JOB_CHECK_INTERVAL) => {}
+                }
+                continue;
+            }
+
             // ── Idle: poll Telegram for messages ──
+            if let Ok(state) = state_manager.load_or_default()
+                && let Some(chat_id) = state.chat_id
+            {
+                merges.announce(&bot, chat_id, &workspace_root).await;
+            }
+
+            let has_queued = queue
+                .load()
+                .is_ok_and(|queue| queue.queued().next().is_some());
+            let timeout_secs = if running.is_empty() && !has_queued {
+                IDLE_POLL_TIMEOUT_SECS
+            } else {
+                BUSY_POLL_TIMEOUT_SECS
+            };
             let updates = match tokio::select! {
                 _ = wait_for_shutdown(shutdown.clone()) => {
                     break 'daemon;
                 }
-                updates = poll_updates(&self.bot_token, 30, offset) => updates,
+                updates = poll_updates(&raw_bot, timeout_secs, offset) => updates,
             } {
                 Ok(u) => u,
                 Err(e) => {
@@ -109,12 +386,25 @@ impl DaemonAdapter for TelegramDaemon {
 
             for update in updates {
                 offset = update.update_id + 1;
+                let chat_id = update.chat_id;
+
+                if let Some(press) = &update.button {
+                    crate::keyboard::handle_button_press(&bot, &callbacks, press).await;
+                    continue;
+                }
 
                 let text = match update.text.as_deref() {
                     Some(t) => t,
                     None => continue,
                 };
 
+                if let Err(rejection) =
+                    crate::access::authorize(&self.access, chat_id, update.user_id, text)
+                {
+                    let _ = bot.send_message(chat_id, &rejection).await;
+                    continue;
+                }
+
                 if let Ok(mut state) = state_manager.load_or_default() {
                     if state.chat_id.is_none() {
                         state.chat_id = Some(chat_id);
@@ -128,113 +418,97 @@ impl DaemonAdapter for TelegramDaemon {
                     warn!("Failed to load Telegram state");
                 }
 
-                info!(text = %text, "Daemon received message");
+                info!(chat_id, text = %text, "Daemon received message");
 
-                // Handle daemon-only commands
-                if text.starts_with('/') {
-                    match text.split_whitespace().next().unwrap_or("") {
-                        "/status" => {
-                            let msg = match lock_state(&workspace_root) {
-                                Ok(LockState::Active) => "A loop is running.".to_string(),
-                                Ok(LockState::Stale) => {
-                                    "No active loop (stale lock file found).".to_string()
-                                }
-                                Ok(LockState::Inactive) => {
-                                    "Idle — waiting for messages.".to_string()
-                                }
-                                Err(e) => format!("Failed to check lock state: {}", e),
-                            };
-                            let _ = bot.send_message(chat_id, &msg).await;
-                        }
-                        _ => {
-                            let _ = bot
-                                .send_message(
-                                    chat_id,
-                                    "Unknown command. I only handle /status while idle.",
-                                )
-                                .await;
-                        }
-                    }
+                if crate::commands::parse_command(text).0 == "/merges" {
+                    crate::keyboard::send_merge_reviews(&bot, chat_id, &workspace_root).await;
                     continue;
                 }
 
-                // Regular message → check lock state
-                let lock_path = lock_path(&workspace_root);
-                let state = match lock_state(&workspace_root) {
-                    Ok(state) => state,
-                    Err(e) => {
-                        warn!(error = %e, "Failed to check loop lock state");
-                        let _ = bot
-                            .send_message(
-                                chat_id,
-                                "Failed to check loop state; try again in a moment.",
-                            )
-                            .await;
-                        continue;
-                    }
-                };
-                if state == LockState::Active {
-                    let _ = bot
-                        .send_message(
-                            chat_id,
-                            "A loop is already running — it will receive your messages directly.",
-                        )
-                        .await;
+                // Handle commands while idle
+                if text.starts_with('/') {
+                    let reply =
+                        crate::commands::handle_queue_command(text, &workspace_root, chat_id)
+                            .unwrap_or_else(|| idle_command_reply(text, &workspace_root));
+                    let _ = bot.send_message(chat_id, &reply).await;
                     continue;
                 }
 
-                if state == LockState::Stale {
-                    warn!(
-                        lock_path = %lock_path.display(),
-                        "Found stale loop lock; starting new loop"
-                    );
-                }
-
-                // No loop running — start one with this message as prompt
-                let escaped = escape_html(text);
-                let ack = format!("Starting loop: <i>{}</i>", escaped);
-                let _ = bot.send_message(chat_id, &ack).await;
-
-                // ── Loop Running: hand off Telegram to the loop ──
-                // The loop's TelegramService polls getUpdates, handles commands,
-                // guidance, responses, check-ins. We just await completion.
-                let prompt = text.to_string();
-                let mut loop_handle = tokio::spawn(start_loop(prompt));
-                let result = tokio::select! {
-                    _ = wait_for_shutdown(shutdown.clone()) => {
-                        loop_handle.abort();
-                        let _ = loop_handle.await;
-                        break 'daemon;
-                    }
-                    result = &mut loop_handle => result,
-                };
-
-                // Loop finished — daemon resumes polling.
-                match result {
-                    Ok(Ok(description)) => {
-                        let notification =
-                            format!("Loop complete ({}).", escape_html(&description));
-                        let _ = bot.send_message(chat_id, &notification).await;
-                    }
-                    Ok(Err(e)) => {
-                        let notification = format!("Loop failed: {}", escape_html(&e.to_string()));
-                        let _ = bot.send_message(chat_id, &notification).await;
-                    }
-                    Err(e) => {
-                        let notification = format!("Loop failed: {}", escape_html(&e.to_string()));
-                        let _ = bot.send_message(chat_id, &notification).await;
-                    }
+                // Regular message → queue it as a loop request
+                if let Some(reply) =
+                    self.queue_prompt(&queue, &workspace_root, text, chat_id, &running)
+                {
+                    let _ = bot.send_message(chat_id, &reply).await;
                 }
             }
         }
 
+        // Shutdown: stop running loops and tell their chats.
+        for job in running.drain(..) {
+            job.handle.abort();
+            let _ = job.handle.await;
+        }
+        fail_running_jobs(&bot, &queue, "interrupted (daemon shut down)").await;
+
         // Farewell
-        let _ = bot.send_message(chat_id, "Ralph daemon offline 👋").await;
+        self.broadcast(&bot, "Ralph daemon offline 👋").await;
 
         Ok(())
     }
 }
 
+/// Marks jobs still recorded as running as failed and tells their chats.
+async fn fail_running_jobs(bot: &TelegramBot, queue: &QueueManager, reason: &str) {
+    let interrupted = match queue.update(|queue| queue.fail_running(reason)) {
+        Ok(interrupted) => interrupted,
+        Err(e) => {
+            warn!(error = %e, "Failed to update job queue");
+            return;
+        }
+    };
+    for job in interrupted {
+        let notice = job_result_message(job.id, job.status, job.loop_id.as_deref(), reason);
+        let _ = bot.send_message(job.chat_id, &notice).await;
+    }
+}
+
+/// Formats the notice sent to the requesting chat when a job ends.
+fn job_result_message(
+    id: u32,
+    status: JobStatus,
+    loop_id: Option<&str>,
+    termination: &str,
+) -> String {
+    let loop_part = loop_id
+        .map(|loop_id| format!(" (loop <code>{}</code>)", escape_html(loop_id)))
+        .unwrap_or_default();
+    match status {
+        JobStatus::Done => format!(
+            "Job #{id} complete{loop_part}: {}.",
+            escape_html(termination)
+        ),
+        _ => format!("Job #{id} failed{loop_part}: {}", escape_html(termination)),
+    }
+}
+
+/// Reply to a command received while no loop is running.
+///
+/// `/status` reports the daemon's own state; other commands share the
+/// in-loop handlers (so `/stop` and `/restart` report that no loop is active).
+fn idle_command_reply(text: &str, workspace_root: &Path) -> String {
+    if text.split_whitespace().next().unwrap_or("") == "/status" {
+        return match lock_state(workspace_root) {
+            Ok(LockState::Active) => "A loop is running.".to_string(),
+            Ok(LockState::Stale) => "No active loop (stale lock file found).".to_string(),
+            Ok(LockState::Inactive) => "Idle — waiting for messages.".to_string(),
+            Err(e) => format!("Failed to check lock state: {}", e),
+        };
+    }
+
+    crate::commands::handle_command(text, workspace_root)
+        .unwrap_or_else(|| "Unknown command. Send /help for the list of commands.".to_string())
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Lightweight Telegram polling (teloxide Bot client)
 // ─────────────────────────────────────────────────────────────────────────────
@@ -242,7 +516,11 @@ impl DaemonAdapter for TelegramDaemon {
 /// A minimal parsed update for daemon idle polling.
 struct DaemonUpdate {
     update_id: i32,
+    chat_id: i64,
+    user_id: Option<i64>,
     text: Option<String>,
+    /// Set for inline-keyboard button presses.
+    button: Option<ButtonPress>,
 }
 
 /// Long-poll `getUpdates` using the teloxide Bot client.
@@ -250,14 +528,13 @@ struct DaemonUpdate {
 /// Uses teloxide's built-in HTTP client rather than raw `reqwest`
 /// since `ralph-telegram` already depends on teloxide.
 async fn poll_updates(
-    token: &str,
+    bot: &teloxide::Bot,
     timeout_secs: u64,
     offset: i32,
 ) -> anyhow::Result<Vec<DaemonUpdate>> {
     use teloxide::payloads::GetUpdatesSetters;
     use teloxide::requests::Requester;
 
-    let bot = teloxide::Bot::new(token);
     let request = bot
         .get_updates()
         .offset(offset)
@@ -272,14 +549,30 @@ async fn poll_updates(
         #[allow(clippy::cast_possible_wrap)]
         let id = update.id.0 as i32;
 
-        let text = match update.kind {
-            teloxide::types::UpdateKind::Message(ref msg) => msg.text().map(String::from),
-            _ => None,
+        let msg = match update.kind {
+            teloxide::types::UpdateKind::Message(ref msg) => msg,
+            teloxide::types::UpdateKind::CallbackQuery(ref query) => {
+                if let Some(press) = ButtonPress::from_query(query) {
+                    results.push(DaemonUpdate {
+                        update_id: id,
+                        chat_id: press.chat_id,
+                        user_id: press.user_id,
+                        text: None,
+                        button: Some(press),
+                    });
+                }
+                continue;
+            }
+            _ => continue,
         };
 
+        #[allow(clippy::cast_possible_wrap)]
         results.push(DaemonUpdate {
             update_id: id,
-            text,
+            chat_id: msg.chat.id.0,
+            user_id: msg.from.as_ref().map(|user| user.id.0 as i64),
+            text: msg.text().map(String::from),
+            button: None,
         });
     }
 
@@ -290,10 +583,124 @@ async fn poll_updates(
 mod tests {
     use super::*;
 
+    use ralph_proto::{RobotAccessRule, RobotRole};
+    use tempfile::TempDir;
+
     #[test]
     fn test_telegram_daemon_creation() {
         let daemon = TelegramDaemon::new("test-token".to_string(), 12345);
         assert_eq!(daemon.bot_token, "test-token");
-        assert_eq!(daemon.chat_id, 12345);
+        assert_eq!(daemon.access.notify_chats(), vec![12345]);
+        assert_eq!(
+            daemon.access.role_for(12345, None),
+            Some(RobotRole::Operator)
+        );
+    }
+
+    #[test]
+    fn test_telegram_daemon_with_access_serves_all_chats() {
+        let access = AccessList::new(vec![
+            RobotAccessRule {
+                chat_id: Some(-100),
+                user_id: None,
+                role: RobotRole::Viewer,
+            },
+            RobotAccessRule {
+                chat_id: None,
+                user_id: Some(7),
+                role: RobotRole::Operator,
+            },
+        ]);
+        let daemon = TelegramDaemon::with_access("test-token".to_string(), access);
+        assert_eq!(daemon.access.notify_chats(), vec![-100, 7]);
+    }
+
+    #[test]
+    fn test_idle_command_reply() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(
+            idle_command_reply("/status", dir.path()),
+            "Idle — waiting for messages."
+        );
+        assert_eq!(
+            idle_command_reply("/stop", dir.path()),
+            "No active loop to stop."
+        );
+        assert!(idle_command_reply("/help", dir.path()).contains("/tail"));
+        assert!(idle_command_reply("/bogus", dir.path()).starts_with("Unknown command"));
+    }
+
+    fn finished_job(primary: bool) -> RunningJob {
+        RunningJob {
+            job: Job {
+                id: 1,
+                prompt: "p".to_string(),
+                chat_id: 1,
+                status: JobStatus::Running,
+                enqueued_at: chrono::Utc::now(),
+                started_at: None,
+                finished_at: None,
+                loop_id: None,
+                termination: None,
+            },
+            primary,
+            handle: tokio::spawn(async {
+                Ok(LoopOutcome {
+                    loop_id: None,
+                    termination: "CompletionPromise".to_string(),
+                })
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_start_mode_respects_parallel_setting() {
+        let serial = TelegramDaemon::new("t".to_string(), 1);
+        let parallel = TelegramDaemon::new("t".to_string(), 1).with_parallel(true);
+        let primary = vec![finished_job(true)];
+        let worktree = vec![finished_job(false)];
+
+        assert_eq!(serial.start_mode(LockState::Inactive, &[]), Some(false));
+        assert_eq!(serial.start_mode(LockState::Stale, &worktree), Some(false));
+        assert_eq!(serial.start_mode(LockState::Active, &[]), None);
+        assert_eq!(parallel.start_mode(LockState::Active, &primary), Some(true));
+        // Our primary job hasn't taken (or has just released) the lock.
+        assert_eq!(parallel.start_mode(LockState::Inactive, &primary), None);
+    }
+
+    #[tokio::test]
+    async fn test_queue_prompt_replies_only_when_job_waits() {
+        let dir = TempDir::new().unwrap();
+        let daemon = TelegramDaemon::new("t".to_string(), 1);
+        let queue = QueueManager::for_workspace(dir.path());
+
+        // Nothing running: the job starts right away and is acknowledged then.
+        assert_eq!(
+            daemon.queue_prompt(&queue, dir.path(), "first", 1, &[]),
+            None
+        );
+        // A primary job is running: the next request waits.
+        let running = vec![finished_job(true)];
+        assert_eq!(
+            daemon
+                .queue_prompt(&queue, dir.path(), "second", 2, &running)
+                .unwrap(),
+            "A loop is already running — queued as job #2 (position 2). Send /queue to see the queue."
+        );
+
+        let jobs = queue.load().unwrap();
+        assert_eq!(jobs.get(2).unwrap().chat_id, 2);
+    }
+
+    #[test]
+    fn test_job_result_message_reports_loop_and_reason() {
+        assert_eq!(
+            job_result_message(3, JobStatus::Done, Some("primary-1"), "CompletionPromise"),
+            "Job #3 complete (loop <code>primary-1</code>): CompletionPromise."
+        );
+        assert_eq!(
+            job_result_message(4, JobStatus::Failed, None, "boom <x>"),
+            "Job #4 failed: boom &lt;x&gt;"
+        );
     }
 }
@@ -1,6 +1,7 @@
 use std::path::{Path, PathBuf};
 
 use chrono::Utc;
+use ralph_core::{APPROVAL_DECISIONS_FILE, APPROVAL_RESPONSE_TOPIC};
 
 use crate::error::TelegramResult;
 use crate::state::{StateManager, TelegramState};
@@ -25,7 +26,11 @@ impl MessageHandler {
     /// Determines target loop, classifies as response or guidance, and appends
     /// the appropriate event to the loop's events.jsonl.
     ///
-    /// Returns the event topic that was written (`"human.response"` or `"human.guidance"`).
+    /// Returns the event topic that was written: the pending question's reply
+    /// topic (`"human.response"` unless the question set another), or
+    /// `"human.guidance"`. Answers to approval requests go to the loop's
+    /// approval decisions file rather than its events file, which the agent
+    /// can write to.
     pub fn handle_message(
         &self,
         state: &mut TelegramState,
@@ -40,14 +45,17 @@ impl MessageHandler {
             tracing::info!(chat_id, "auto-detected chat ID from first message");
         }
 
-        let target_loop = self.determine_target_loop(state, text, reply_to_message_id);
-        let events_path = self.get_events_path(&target_loop);
-        let is_response = state.pending_questions.contains_key(&target_loop);
-
-        let topic = if is_response {
-            "human.response"
+        let target_loop = self.determine_target_loop(state, text, chat_id, reply_to_message_id);
+        let reply_topic = state
+            .pending_questions
+            .get(&target_loop)
+            .map(|question| question.reply_topic().to_string());
+        let is_response = reply_topic.is_some();
+        let topic = reply_topic.as_deref().unwrap_or("human.guidance");
+        let events_path = if topic == APPROVAL_RESPONSE_TOPIC {
+            self.ralph_dir(&target_loop).join(APPROVAL_DECISIONS_FILE)
         } else {
-            "human.guidance"
+            self.get_events_path(&target_loop)
         };
 
         let timestamp = Utc::now().to_rfc3339();
@@ -79,18 +87,21 @@ impl MessageHandler {
     /// Determine which loop a message is targeted at.
     ///
     /// Priority:
-    /// 1. Reply to a pending question message → that loop
+    /// 1. Reply to a pending question message in the same chat → that loop
     /// 2. `@loop-id` prefix → extracted loop ID
     /// 3. Default → "main"
     fn determine_target_loop(
         &self,
         state: &TelegramState,
         text: &str,
+        chat_id: i64,
         reply_to_message_id: Option<i32>,
     ) -> String {
         // Check reply-to routing
         if let Some(reply_id) = reply_to_message_id
-            && let Some(loop_id) = self.state_manager.get_loop_for_reply(state, reply_id)
+            && let Some(loop_id) = self
+                .state_manager
+                .get_loop_for_reply(state, chat_id, reply_id)
         {
             return loop_id;
         }
@@ -111,14 +122,7 @@ impl MessageHandler {
     /// Reads the `current-events` marker to find the timestamped events file.
     /// Falls back to the default `events.jsonl` if the marker doesn't exist.
     fn get_events_path(&self, loop_id: &str) -> PathBuf {
-        let ralph_dir = if loop_id == "main" {
-            self.workspace_root.join(".ralph")
-        } else {
-            self.workspace_root
-                .join(".worktrees")
-                .join(loop_id)
-                .join(".ralph")
-        };
+        let ralph_dir = self.ralph_dir(loop_id);
 
         let marker_path = ralph_dir.join("current-events");
         if let Ok(contents) = std::fs::read_to_string(&marker_path) {
@@ -141,6 +145,18 @@ impl MessageHandler {
         ralph_dir.join("events.jsonl")
     }
 
+    /// Get the `.ralph/` directory of a given loop.
+    fn ralph_dir(&self, loop_id: &str) -> PathBuf {
+        if loop_id == "main" {
+            self.workspace_root.join(".ralph")
+        } else {
+            self.workspace_root
+                .join(".worktrees")
+                .join(loop_id)
+                .join(".ralph")
+        }
+    }
+
     /// Append an event line to the given file atomically.
     fn append_event(&self, path: &Path, event_line: &str) -> TelegramResult<()> {
         use std::fs::OpenOptions;
@@ -225,6 +241,9 @@ mod tests {
             crate::state::PendingQuestion {
                 asked_at: chrono::Utc::now(),
                 message_id: 42,
+                chat_id: Some(123),
+                options: Vec::new(),
+                reply_topic: None,
             },
         );
 
@@ -242,6 +261,35 @@ mod tests {
         assert!(!state.pending_questions.contains_key("main"));
     }
 
+    #[test]
+    fn writes_the_questions_reply_topic() {
+        let (handler, dir, mut state) = setup();
+        state.pending_questions.insert(
+            "main".to_string(),
+            crate::state::PendingQuestion {
+                asked_at: chrono::Utc::now(),
+                message_id: 42,
+                chat_id: Some(123),
+                options: Vec::new(),
+                reply_topic: Some("approval.response".to_string()),
+            },
+        );
+
+        let topic = handler
+            .handle_message(&mut state, "reject: not today", 123, Some(42))
+            .unwrap();
+
+        assert_eq!(topic, "approval.response");
+        // Approval answers never land in the events file the agent writes
+        assert!(!dir.path().join(".ralph/events.jsonl").exists());
+        let decisions_path = dir.path().join(".ralph/approval-decisions.jsonl");
+        let contents = std::fs::read_to_string(decisions_path).unwrap();
+        let event: serde_json::Value = serde_json::from_str(contents.trim()).unwrap();
+        assert_eq!(event["topic"], "approval.response");
+        assert_eq!(event["payload"], "reject: not today");
+        assert!(!state.pending_questions.contains_key("main"));
+    }
+
     #[test]
     fn routes_at_prefix_to_correct_loop() {
         let (handler, dir, mut state) = setup();
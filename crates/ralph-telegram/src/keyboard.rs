@@ -0,0 +1,683 @@
+//! Inline keyboards for merge reviews and multiple-choice questions.
+//!
+//! Two kinds of messages carry buttons:
+//!
+//! - **Merge reviews** — worktree loops in the merge queue as `Queued` or
+//!   `NeedsReview` get Merge / Discard / Show diff buttons. Merging follows
+//!   [`merge_button_state`], so it is refused while the primary loop runs.
+//! - **Questions** — a `human.interact` payload of the form
+//!   `{"question": "...", "options": ["A", "B"]}` is sent with one button per
+//!   option. The chosen option is written back as `human.response`, exactly
+//!   as if it had been typed as a reply.
+//!
+//! Button presses arrive as callback queries whose data is one of
+//! `answer:<index>`, `merge:<loop-id>`, `discard:<loop-id>` or
+//! `diff:<loop-id>`.
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use ralph_core::{
+    LoopRegistry, MergeButtonState, MergeEntry, MergeQueue, MergeState, list_ralph_worktrees,
+    merge_button_state, remove_worktree, smart_merge_summary,
+};
+use ralph_proto::{AccessList, RobotRole};
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use crate::bot::{BotApi, InlineButton, escape_html, markdown_to_telegram_html};
+use crate::commands::truncate_with_ellipsis;
+use crate::handler::MessageHandler;
+use crate::state::StateManager;
+
+/// Longest prompt excerpt shown in a merge review.
+const REVIEW_PROMPT_CHARS: usize = 200;
+
+/// Longest `git diff --stat` output sent for "Show diff".
+const DIFF_CHARS: usize = 3500;
+
+/// A `human.interact` payload, optionally with answer choices.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Interaction {
+    pub question: String,
+    pub options: Vec<String>,
+    /// Topic the answer is written as, when not `human.response`.
+    pub reply_topic: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct StructuredInteraction {
+    question: String,
+    #[serde(default)]
+    options: Vec<String>,
+    #[serde(default)]
+    reply_topic: Option<String>,
+}
+
+impl Interaction {
+    /// Parses a `human.interact` payload.
+    ///
+    /// A JSON object with a `question` (and optional `options` and
+    /// `reply_topic`) is structured; anything else is a free-text question
+    /// with no options.
+    pub fn parse(payload: &str) -> Self {
+        match serde_json::from_str::<StructuredInteraction>(payload.trim()) {
+            Ok(structured) => Self {
+                question: structured.question,
+                options: structured
+                    .options
+                    .into_iter()
+                    .map(|option| option.trim().to_string())
+                    .filter(|option| !option.is_empty())
+                    .collect(),
+                reply_topic: structured.reply_topic,
+            },
+            Err(_) => Self {
+                question: payload.to_string(),
+                options: Vec::new(),
+                reply_topic: None,
+            },
+        }
+    }
+}
+
+/// One button per answer option, each on its own row.
+pub fn question_keyboard(options: &[String]) -> Vec<Vec<InlineButton>> {
+    options
+        .iter()
+        .enumerate()
+        .map(|(index, option)| vec![InlineButton::new(option, format!("answer:{index}"))])
+        .collect()
+}
+
+/// Renders a structured question's text for sending with its option buttons.
+pub fn format_choice_question(question: &str) -> String {
+    format!("❓ {}", markdown_to_telegram_html(question))
+}
+
+/// Merge / Discard / Show diff buttons for a worktree loop.
+pub fn merge_keyboard(loop_id: &str) -> Vec<Vec<InlineButton>> {
+    vec![vec![
+        InlineButton::new("✅ Merge", format!("merge:{loop_id}")),
+        InlineButton::new("🗑 Discard", format!("discard:{loop_id}")),
+        InlineButton::new("📄 Show diff", format!("diff:{loop_id}")),
+    ]]
+}
+
+/// What a pressed button asks for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CallbackAction {
+    /// Answer the question on the pressed message with the option at this index.
+    Answer(usize),
+    Merge(String),
+    Discard(String),
+    Diff(String),
+}
+
+impl CallbackAction {
+    /// Parses callback data produced by [`question_keyboard`] or [`merge_keyboard`].
+    pub fn parse(data: &str) -> Option<Self> {
+        let (kind, arg) = data.split_once(':')?;
+        if arg.is_empty() {
+            return None;
+        }
+        match kind {
+            "answer" => arg.parse().ok().map(Self::Answer),
+            "merge" => Some(Self::Merge(arg.to_string())),
+            "discard" => Some(Self::Discard(arg.to_string())),
+            "diff" => Some(Self::Diff(arg.to_string())),
+            _ => None,
+        }
+    }
+
+    /// The role needed to press the button.
+    pub fn required_role(&self) -> RobotRole {
+        match self {
+            Self::Diff(_) => RobotRole::Viewer,
+            Self::Answer(_) | Self::Merge(_) | Self::Discard(_) => RobotRole::Operator,
+        }
+    }
+}
+
+/// A button press received as a Telegram callback query.
+#[derive(Debug, Clone)]
+pub struct ButtonPress {
+    pub callback_id: String,
+    pub chat_id: i64,
+    pub user_id: Option<i64>,
+    /// The message the keyboard is attached to.
+    pub message_id: i32,
+    /// Plain text of that message, used when editing it after the press.
+    pub message_text: Option<String>,
+    pub data: String,
+}
+
+impl ButtonPress {
+    /// Extracts a button press from a callback query.
+    ///
+    /// Returns `None` for presses without data or on messages too old for
+    /// Telegram to include.
+    pub(crate) fn from_query(query: &teloxide::types::CallbackQuery) -> Option<Self> {
+        let message = query.message.as_ref()?;
+        #[allow(clippy::cast_possible_wrap)]
+        Some(Self {
+            callback_id: query.id.clone(),
+            chat_id: message.chat().id.0,
+            user_id: Some(query.from.id.0 as i64),
+            message_id: message.id().0,
+            message_text: query
+                .regular_message()
+                .and_then(|msg| msg.text())
+                .map(String::from),
+            data: query.data.clone()?,
+        })
+    }
+}
+
+/// Where button presses are applied.
+pub struct CallbackContext<'a> {
+    pub workspace_root: &'a Path,
+    pub access: &'a AccessList,
+    pub state_manager: &'a StateManager,
+    pub handler: &'a MessageHandler,
+}
+
+/// Authorizes and applies a button press, then answers the callback query
+/// with a short notification.
+pub async fn handle_button_press(bot: &dyn BotApi, ctx: &CallbackContext<'_>, press: &ButtonPress) {
+    let notice = match CallbackAction::parse(&press.data) {
+        None => "This button is no longer supported.".to_string(),
+        Some(action) => match crate::access::authorize_role(
+            ctx.access,
+            press.chat_id,
+            press.user_id,
+            action.required_role(),
+        ) {
+            Err(rejection) => rejection,
+            Ok(_) => {
+                info!(chat_id = press.chat_id, data = %press.data, "Button pressed");
+                apply_action(bot, ctx, press, action).await
+            }
+        },
+    };
+
+    if let Err(e) = bot.answer_callback(&press.callback_id, &notice).await {
+        warn!(error = %e, "Failed to answer callback query");
+    }
+}
+
+async fn apply_action(
+    bot: &dyn BotApi,
+    ctx: &CallbackContext<'_>,
+    press: &ButtonPress,
+    action: CallbackAction,
+) -> String {
+    let result = match action {
+        CallbackAction::Answer(index) => answer_question(ctx, press, index),
+        CallbackAction::Merge(loop_id) => start_merge(ctx.workspace_root, &loop_id),
+        CallbackAction::Discard(loop_id) => discard_loop(ctx.workspace_root, &loop_id),
+        CallbackAction::Diff(loop_id) => {
+            let text = diff_message(ctx.workspace_root, &loop_id);
+            if let Err(e) = bot.send_message(press.chat_id, &text).await {
+                warn!(error = %e, "Failed to send diff");
+            }
+            return "Diff sent.".to_string();
+        }
+    };
+
+    match result {
+        Ok(Outcome { notice, footer }) => {
+            // Replace the keyboard so the same action can't be pressed twice.
+            let original = press.message_text.as_deref().unwrap_or_default();
+            let text = format!("{}\n\n{footer}", escape_html(original));
+            if let Err(e) = bot
+                .edit_message(press.chat_id, press.message_id, text.trim_start())
+                .await
+            {
+                warn!(error = %e, "Failed to update message after button press");
+            }
+            notice
+        }
+        Err(notice) => notice,
+    }
+}
+
+/// A completed action: the notification to show and the line appended to
+/// the message in place of its keyboard.
+struct Outcome {
+    notice: String,
+    footer: String,
+}
+
+fn answer_question(
+    ctx: &CallbackContext<'_>,
+    press: &ButtonPress,
+    index: usize,
+) -> Result<Outcome, String> {
+    let mut state = ctx
+        .state_manager
+        .load_or_default()
+        .map_err(|e| format!("Failed to load state: {e}"))?;
+    let loop_id = ctx
+        .state_manager
+        .get_loop_for_reply(&state, press.chat_id, press.message_id)
+        .ok_or("This question is no longer waiting for an answer.")?;
+    let choice = state
+        .pending_questions
+        .get(&loop_id)
+        .and_then(|question| question.options.get(index))
+        .cloned()
+        .ok_or("That option is not available.")?;
+
+    ctx.handler
+        .handle_message(&mut state, &choice, press.chat_id, Some(press.message_id))
+        .map_err(|e| format!("Failed to record answer: {e}"))?;
+
+    Ok(Outcome {
+        notice: format!("Answered: {choice}"),
+        footer: format!("✅ <b>{}</b>", escape_html(&choice)),
+    })
+}
+
+fn start_merge(workspace_root: &Path, loop_id: &str) -> Result<Outcome, String> {
+    match merge_button_state(workspace_root, loop_id) {
+        Ok(MergeButtonState::Active) => {}
+        Ok(MergeButtonState::Blocked { reason }) => return Err(format!("Can't merge: {reason}")),
+        Err(e) => return Err(format!("Failed to check merge state: {e}")),
+    }
+
+    // `ralph loops merge` runs the merge-ralph loop, which outlives the button press.
+    let exe = std::env::current_exe().map_err(|e| format!("Failed to find ralph: {e}"))?;
+    Command::new(exe)
+        .args(["loops", "merge", loop_id])
+        .current_dir(workspace_root)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to start merge: {e}"))?;
+
+    info!(loop_id, "Started merge from Telegram");
+    Ok(Outcome {
+        notice: "Merge started.".to_string(),
+        footer: "🔀 <b>Merging…</b>".to_string(),
+    })
+}
+
+fn discard_loop(workspace_root: &Path, loop_id: &str) -> Result<Outcome, String> {
+    let registry = LoopRegistry::new(workspace_root);
+    if let Ok(Some(entry)) = registry.get(loop_id)
+        && entry.is_alive()
+    {
+        return Err("The loop is still running. Stop it first.".to_string());
+    }
+
+    let queue = MergeQueue::new(workspace_root);
+    if let Ok(Some(entry)) = queue.get_entry(loop_id)
+        && !entry.state.is_terminal()
+    {
+        queue
+            .discard(loop_id, Some("Discarded from Telegram"))
+            .map_err(|e| format!("Failed to discard: {e}"))?;
+    }
+    let _ = registry.deregister(loop_id);
+
+    let branch = format!("ralph/{loop_id}");
+    if let Some(worktree) = list_ralph_worktrees(workspace_root)
+        .unwrap_or_default()
+        .into_iter()
+        .find(|worktree| worktree.branch == branch)
+    {
+        remove_worktree(workspace_root, &worktree.path)
+            .map_err(|e| format!("Failed to remove worktree: {e}"))?;
+    }
+
+    info!(loop_id, "Discarded loop from Telegram");
+    Ok(Outcome {
+        notice: "Loop discarded.".to_string(),
+        footer: "🗑 <b>Discarded</b>".to_string(),
+    })
+}
+
+/// `git diff --stat` of a loop's branch against `main`, as Telegram HTML.
+fn diff_message(workspace_root: &Path, loop_id: &str) -> String {
+    let range = format!("main...ralph/{loop_id}");
+    let output = Command::new("git")
+        .args(["diff", "--stat", &range])
+        .current_dir(workspace_root)
+        .output();
+
+    match output {
+        Ok(output) if output.status.success() => {
+            let stat = String::from_utf8_lossy(&output.stdout);
+            if stat.trim().is_empty() {
+                format!("No changes on <code>ralph/{}</code>.", escape_html(loop_id))
+            } else {
+                format!(
+                    "<b>Diff for <code>{}</code></b>\n<pre>{}</pre>",
+                    escape_html(loop_id),
+                    escape_html(&truncate_with_ellipsis(stat.trim_end(), DIFF_CHARS))
+                )
+            }
+        }
+        Ok(output) => format!(
+            "Failed to diff <code>{}</code>: {}",
+            escape_html(&range),
+            escape_html(String::from_utf8_lossy(&output.stderr).trim())
+        ),
+        Err(e) => format!("Failed to run git: {}", escape_html(&e.to_string())),
+    }
+}
+
+/// Merge queue entries waiting for a decision, oldest first.
+fn pending_merges(workspace_root: &Path) -> Vec<MergeEntry> {
+    match MergeQueue::new(workspace_root).list() {
+        Ok(entries) => entries
+            .into_iter()
+            .filter(|entry| matches!(entry.state, MergeState::Queued | MergeState::NeedsReview))
+            .collect(),
+        Err(e) => {
+            warn!(error = %e, "Failed to read merge queue");
+            Vec::new()
+        }
+    }
+}
+
+/// The text of a merge review message for a queue entry.
+pub fn merge_review_text(workspace_root: &Path, entry: &MergeEntry) -> String {
+    let loop_id = escape_html(&entry.loop_id);
+    let mut lines = match entry.state {
+        MergeState::NeedsReview => vec![format!("⚠️ <b>Needs review</b>: <code>{loop_id}</code>")],
+        _ => vec![format!("🔀 <b>Ready to merge</b>: <code>{loop_id}</code>")],
+    };
+    lines.push(format!(
+        "<i>{}</i>",
+        escape_html(&truncate_with_ellipsis(&entry.prompt, REVIEW_PROMPT_CHARS))
+    ));
+    if let Ok(summary) = smart_merge_summary(workspace_root, &entry.loop_id) {
+        lines.push(format!("Summary: {}", escape_html(&summary)));
+    }
+    if let Some(reason) = &entry.failure_reason {
+        lines.push(format!("Merge failed: {}", escape_html(reason)));
+    }
+    if let Ok(MergeButtonState::Blocked { reason }) =
+        merge_button_state(workspace_root, &entry.loop_id)
+    {
+        lines.push(format!("⏸ Merge blocked: {}", escape_html(&reason)));
+    }
+    lines.join("\n")
+}
+
+/// Sends a review message with buttons for each pending merge.
+///
+/// Returns the number of reviews sent.
+pub async fn send_merge_reviews(bot: &dyn BotApi, chat_id: i64, workspace_root: &Path) -> usize {
+    let entries = pending_merges(workspace_root);
+    if entries.is_empty() {
+        let _ = bot
+            .send_message(chat_id, "No worktree loops are waiting to be merged.")
+            .await;
+        return 0;
+    }
+    for entry in &entries {
+        send_review(bot, chat_id, workspace_root, entry).await;
+    }
+    entries.len()
+}
+
+async fn send_review(bot: &dyn BotApi, chat_id: i64, workspace_root: &Path, entry: &MergeEntry) {
+    let text = merge_review_text(workspace_root, entry);
+    if let Err(e) = bot
+        .send_keyboard(chat_id, &text, &merge_keyboard(&entry.loop_id))
+        .await
+    {
+        warn!(error = %e, loop_id = %entry.loop_id, "Failed to send merge review");
+    }
+}
+
+/// Announces merge queue entries that start waiting for a decision.
+///
+/// Entries already waiting when the announcer is created are assumed to be
+/// known (`/merges` lists them), so restarting the bot doesn't repeat them.
+pub struct MergeAnnouncer {
+    announced: HashSet<String>,
+}
+
+impl MergeAnnouncer {
+    /// Creates an announcer that treats the currently pending entries as announced.
+    pub fn new(workspace_root: &Path) -> Self {
+        Self {
+            announced: pending_merges(workspace_root)
+                .into_iter()
+                .map(|entry| Self::key(&entry))
+                .collect(),
+        }
+    }
+
+    /// Sends a review for each entry that became pending since the last call.
+    pub async fn announce(&mut self, bot: &dyn BotApi, chat_id: i64, workspace_root: &Path) {
+        for entry in pending_merges(workspace_root) {
+            if self.announced.insert(Self::key(&entry)) {
+                send_review(bot, chat_id, workspace_root, &entry).await;
+            }
+        }
+    }
+
+    // A loop that is retried and fails again moves back to NeedsReview;
+    // keying on the state announces it again.
+    fn key(entry: &MergeEntry) -> String {
+        format!("{}:{:?}", entry.loop_id, entry.state)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_bot::MockBot;
+    use ralph_proto::RobotAccessRule;
+    use tempfile::TempDir;
+
+    struct Fixture {
+        dir: TempDir,
+        state_manager: StateManager,
+        handler: MessageHandler,
+        access: AccessList,
+    }
+
+    impl Fixture {
+        fn new() -> Self {
+            let dir = TempDir::new().unwrap();
+            let state_path = dir.path().join(".ralph/telegram-state.json");
+            Self {
+                state_manager: StateManager::new(&state_path),
+                handler: MessageHandler::new(StateManager::new(&state_path), dir.path()),
+                access: AccessList::default(),
+                dir,
+            }
+        }
+
+        fn ctx(&self) -> CallbackContext<'_> {
+            CallbackContext {
+                workspace_root: self.dir.path(),
+                access: &self.access,
+                state_manager: &self.state_manager,
+                handler: &self.handler,
+            }
+        }
+    }
+
+    fn press(data: &str, message_id: i32) -> ButtonPress {
+        ButtonPress {
+            callback_id: "cb-1".to_string(),
+            chat_id: 100,
+            user_id: Some(7),
+            message_id,
+            message_text: Some("❓ Which database?".to_string()),
+            data: data.to_string(),
+        }
+    }
+
+    #[test]
+    fn interaction_parses_structured_and_plain_payloads() {
+        let structured = Interaction::parse(
+            r#"{"question": "Which DB?", "options": ["Postgres", " ", "SQLite"]}"#,
+        );
+        assert_eq!(structured.question, "Which DB?");
+        assert_eq!(structured.options, vec!["Postgres", "SQLite"]);
+        assert_eq!(structured.reply_topic, None);
+
+        let approval = Interaction::parse(
+            r#"{"question": "Approve?", "options": ["Approve"], "reply_topic": "approval.response"}"#,
+        );
+        assert_eq!(approval.reply_topic.as_deref(), Some("approval.response"));
+
+        let plain = Interaction::parse("Should I use {braces}?");
+        assert_eq!(plain.question, "Should I use {braces}?");
+        assert!(plain.options.is_empty());
+    }
+
+    #[test]
+    fn callback_actions_round_trip_through_keyboards() {
+        let merge = merge_keyboard("ralph-20250124-a3f2");
+        let actions: Vec<_> = merge[0]
+            .iter()
+            .map(|button| CallbackAction::parse(&button.callback_data).unwrap())
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                CallbackAction::Merge("ralph-20250124-a3f2".into()),
+                CallbackAction::Discard("ralph-20250124-a3f2".into()),
+                CallbackAction::Diff("ralph-20250124-a3f2".into()),
+            ]
+        );
+        assert_eq!(actions[2].required_role(), RobotRole::Viewer);
+        assert_eq!(actions[0].required_role(), RobotRole::Operator);
+
+        let answers = question_keyboard(&["Yes".into(), "No".into()]);
+        assert_eq!(
+            CallbackAction::parse(&answers[1][0].callback_data),
+            Some(CallbackAction::Answer(1))
+        );
+        assert_eq!(CallbackAction::parse("answer:x"), None);
+        assert_eq!(CallbackAction::parse("merge:"), None);
+    }
+
+    #[tokio::test]
+    async fn answer_button_writes_human_response() {
+        let fixture = Fixture::new();
+        let mut state = fixture.state_manager.load_or_default().unwrap();
+        fixture
+            .state_manager
+            .add_pending_question(
+                &mut state,
+                "main",
+                Some(100),
+                42,
+                vec!["Postgres".into(), "SQLite".into()],
+                None,
+            )
+            .unwrap();
+
+        let bot = MockBot::new();
+        handle_button_press(&bot, &fixture.ctx(), &press("answer:1", 42)).await;
+
+        let events =
+            std::fs::read_to_string(fixture.dir.path().join(".ralph/events.jsonl")).unwrap();
+        let event: serde_json::Value = serde_json::from_str(events.trim()).unwrap();
+        assert_eq!(event["topic"], "human.response");
+        assert_eq!(event["payload"], "SQLite");
+
+        assert_eq!(
+            bot.answers(),
+            vec![("cb-1".to_string(), "Answered: SQLite".to_string())]
+        );
+        let edits = bot.edits();
+        assert_eq!(edits.len(), 1);
+        assert_eq!(edits[0].1, 42);
+        assert!(edits[0].2.ends_with("✅ <b>SQLite</b>"));
+
+        // The question is answered, so pressing again changes nothing.
+        handle_button_press(&bot, &fixture.ctx(), &press("answer:0", 42)).await;
+        assert_eq!(
+            bot.answers()[1].1,
+            "This question is no longer waiting for an answer."
+        );
+        let events =
+            std::fs::read_to_string(fixture.dir.path().join(".ralph/events.jsonl")).unwrap();
+        assert_eq!(events.lines().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn buttons_respect_roles() {
+        let mut fixture = Fixture::new();
+        fixture.access = AccessList::new(vec![RobotAccessRule {
+            chat_id: Some(100),
+            user_id: None,
+            role: RobotRole::Viewer,
+        }]);
+
+        let bot = MockBot::new();
+        handle_button_press(&bot, &fixture.ctx(), &press("discard:loop-1", 5)).await;
+
+        assert_eq!(
+            bot.answers()[0].1,
+            "⛔ This needs the operator role (you are a viewer)."
+        );
+        assert!(bot.edits().is_empty());
+    }
+
+    #[tokio::test]
+    async fn merge_reviews_list_pending_entries_with_buttons() {
+        let fixture = Fixture::new();
+        let root = fixture.dir.path();
+        let queue = MergeQueue::new(root);
+        queue.enqueue("loop-a", "add logging").unwrap();
+        queue.enqueue("loop-b", "fix tests").unwrap();
+        queue.discard("loop-b", None).unwrap();
+
+        let bot = MockBot::new();
+        assert_eq!(send_merge_reviews(&bot, 100, root).await, 1);
+        let keyboards = bot.keyboards();
+        assert_eq!(keyboards.len(), 1);
+        let (chat_id, text, rows) = &keyboards[0];
+        assert_eq!(*chat_id, 100);
+        assert!(text.contains("<code>loop-a</code>"));
+        assert!(text.contains("add logging"));
+        assert_eq!(rows[0][0].callback_data, "merge:loop-a");
+
+        // Only entries that start waiting after creation are announced.
+        let mut announcer = MergeAnnouncer::new(root);
+        announcer.announce(&bot, 100, root).await;
+        assert_eq!(bot.keyboards().len(), 1);
+        queue.enqueue("loop-c", "write docs").unwrap();
+        announcer.announce(&bot, 100, root).await;
+        announcer.announce(&bot, 100, root).await;
+        let keyboards = bot.keyboards();
+        assert_eq!(keyboards.len(), 2);
+        assert!(keyboards[1].1.contains("loop-c"));
+    }
+
+    #[tokio::test]
+    async fn discard_button_updates_queue_and_message() {
+        let fixture = Fixture::new();
+        let root = fixture.dir.path();
+        MergeQueue::new(root)
+            .enqueue("loop-a", "add logging")
+            .unwrap();
+
+        let bot = MockBot::new();
+        let mut discard = press("discard:loop-a", 9);
+        discard.message_text = Some("Ready to merge: loop-a".to_string());
+        handle_button_press(&bot, &fixture.ctx(), &discard).await;
+
+        let entry = MergeQueue::new(root).get_entry("loop-a").unwrap().unwrap();
+        assert_eq!(entry.state, MergeState::Discarded);
+        assert_eq!(bot.answers()[0].1, "Loop discarded.");
+        assert_eq!(
+            bot.edits()[0].2,
+            "Ready to merge: loop-a\n\n🗑 <b>Discarded</b>"
+        );
+    }
+}
@@ -13,18 +13,32 @@
 //! - [`StateManager`] — Persists chat ID, pending questions, and reply routing
 //! - [`MessageHandler`] — Processes incoming messages and writes events to JSONL
 //! - [`TelegramService`] — Lifecycle management for the bot within the event loop
+//! - [`TelegramDaemon`] — Persistent bot serving the allowed chats between loops
+//! - [`access`] — Viewer/operator role checks for incoming messages
+//! - [`queue`] — Persistent job queue for loop requests received while busy
+//! - [`keyboard`] — Inline-keyboard merge reviews and multiple-choice questions
+//! - [`testing`] — A local mock of the Bot API for offline tests
 //! - [`error`] — Error types for startup, send, and receive failures
 
+pub mod access;
 mod bot;
 pub mod commands;
 pub mod daemon;
 mod error;
 mod handler;
+pub mod keyboard;
 mod loop_lock;
+#[cfg(test)]
+mod mock_bot;
+pub mod queue;
 mod service;
 mod state;
+pub mod testing;
 
-pub use bot::{BotApi, TelegramBot, escape_html, markdown_to_telegram_html};
+pub use bot::{
+    BotApi, DEFAULT_API_URL, InlineButton, TelegramBot, escape_html, markdown_to_telegram_html,
+    parse_api_url,
+};
 pub use daemon::TelegramDaemon;
 pub use error::{TelegramError, TelegramResult};
 pub use handler::MessageHandler;
@@ -0,0 +1,149 @@
+//! In-memory [`BotApi`] used by unit tests in place of the Telegram Bot API.
+
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use async_trait::async_trait;
+
+use crate::bot::{BotApi, InlineButton};
+use crate::error::{TelegramError, TelegramResult};
+
+/// A mock BotApi for testing that records everything sent through it.
+pub(crate) struct MockBot {
+    sent: Arc<Mutex<Vec<(i64, String)>>>,
+    keyboards: Arc<Mutex<Vec<(i64, String, Vec<Vec<InlineButton>>)>>>,
+    edits: Arc<Mutex<Vec<(i64, i32, String)>>>,
+    answers: Arc<Mutex<Vec<(String, String)>>>,
+    next_id: Arc<Mutex<i32>>,
+    should_fail: bool,
+}
+
+impl MockBot {
+    pub(crate) fn new() -> Self {
+        Self {
+            sent: Arc::new(Mutex::new(Vec::new())),
+            keyboards: Arc::new(Mutex::new(Vec::new())),
+            edits: Arc::new(Mutex::new(Vec::new())),
+            answers: Arc::new(Mutex::new(Vec::new())),
+            next_id: Arc::new(Mutex::new(1)),
+            should_fail: false,
+        }
+    }
+
+    pub(crate) fn failing() -> Self {
+        Self {
+            should_fail: true,
+            ..Self::new()
+        }
+    }
+
+    /// Messages sent with `send_message`, `send_document` and `send_photo`.
+    pub(crate) fn sent_messages(&self) -> Vec<(i64, String)> {
+        self.sent.lock().unwrap().clone()
+    }
+
+    /// Messages sent with an inline keyboard.
+    pub(crate) fn keyboards(&self) -> Vec<(i64, String, Vec<Vec<InlineButton>>)> {
+        self.keyboards.lock().unwrap().clone()
+    }
+
+    /// Edited messages as `(chat_id, message_id, text)`.
+    pub(crate) fn edits(&self) -> Vec<(i64, i32, String)> {
+        self.edits.lock().unwrap().clone()
+    }
+
+    /// Answered callbacks as `(callback_id, text)`.
+    pub(crate) fn answers(&self) -> Vec<(String, String)> {
+        self.answers.lock().unwrap().clone()
+    }
+
+    fn check(&self) -> TelegramResult<()> {
+        if self.should_fail {
+            return Err(TelegramError::Send {
+                attempts: 1,
+                reason: "mock failure".to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    fn next_message_id(&self) -> i32 {
+        let mut id = self.next_id.lock().unwrap();
+        let current = *id;
+        *id += 1;
+        current
+    }
+}
+
+#[async_trait]
+impl BotApi for MockBot {
+    async fn send_message(&self, chat_id: i64, text: &str) -> TelegramResult<i32> {
+        self.check()?;
+        self.sent.lock().unwrap().push((chat_id, text.to_string()));
+        Ok(self.next_message_id())
+    }
+
+    async fn send_document(
+        &self,
+        chat_id: i64,
+        file_path: &Path,
+        caption: Option<&str>,
+    ) -> TelegramResult<i32> {
+        self.check()?;
+        let label = format!(
+            "[doc:{}]{}",
+            file_path.display(),
+            caption.map(|c| format!(" {c}")).unwrap_or_default()
+        );
+        self.sent.lock().unwrap().push((chat_id, label));
+        Ok(self.next_message_id())
+    }
+
+    async fn send_photo(
+        &self,
+        chat_id: i64,
+        file_path: &Path,
+        caption: Option<&str>,
+    ) -> TelegramResult<i32> {
+        self.check()?;
+        let label = format!(
+            "[photo:{}]{}",
+            file_path.display(),
+            caption.map(|c| format!(" {c}")).unwrap_or_default()
+        );
+        self.sent.lock().unwrap().push((chat_id, label));
+        Ok(self.next_message_id())
+    }
+
+    async fn send_keyboard(
+        &self,
+        chat_id: i64,
+        text: &str,
+        rows: &[Vec<InlineButton>],
+    ) -> TelegramResult<i32> {
+        self.check()?;
+        self.keyboards
+            .lock()
+            .unwrap()
+            .push((chat_id, text.to_string(), rows.to_vec()));
+        Ok(self.next_message_id())
+    }
+
+    async fn edit_message(&self, chat_id: i64, message_id: i32, text: &str) -> TelegramResult<()> {
+        self.check()?;
+        self.edits
+            .lock()
+            .unwrap()
+            .push((chat_id, message_id, text.to_string()));
+        Ok(())
+    }
+
+    async fn answer_callback(&self, callback_id: &str, text: &str) -> TelegramResult<()> {
+        self.check()?;
+        self.answers
+            .lock()
+            .unwrap()
+            .push((callback_id.to_string(), text.to_string()));
+        Ok(())
+    }
+}
@@ -0,0 +1,353 @@
+//! Persistent job queue for loop requests received over Telegram.
+//!
+//! Prompts that arrive while a loop is running are queued at
+//! `.ralph/telegram-queue.json` instead of being dropped. The daemon starts
+//! queued jobs when the current loop finishes (or alongside it in a worktree
+//! when parallel loops are enabled) and reports each job's loop ID and
+//! termination reason back to the chat that requested it.
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::error::TelegramResult;
+
+/// Location of the queue file relative to the workspace root.
+pub const QUEUE_FILE: &str = ".ralph/telegram-queue.json";
+
+/// Finished jobs kept in the queue file for `/queue`.
+const MAX_FINISHED_JOBS: usize = 20;
+
+/// Lifecycle of a queued job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Done,
+    Failed,
+    Cancelled,
+}
+
+impl JobStatus {
+    /// Returns true once the job will not run (again).
+    pub fn is_finished(self) -> bool {
+        matches!(self, Self::Done | Self::Failed | Self::Cancelled)
+    }
+}
+
+impl fmt::Display for JobStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Self::Queued => "queued",
+            Self::Running => "running",
+            Self::Done => "done",
+            Self::Failed => "failed",
+            Self::Cancelled => "cancelled",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A loop request waiting for, running in, or finished with a loop.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Job {
+    pub id: u32,
+    pub prompt: String,
+    /// The chat that requested the job; receives its start and result notices.
+    pub chat_id: i64,
+    pub status: JobStatus,
+    pub enqueued_at: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime<Utc>>,
+    /// Loop the job ran in, once known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub loop_id: Option<String>,
+    /// Final `TerminationReason`, or the error that stopped the job.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub termination: Option<String>,
+}
+
+/// Queue contents, in run order.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JobQueue {
+    /// ID for the next enqueued job.
+    #[serde(default)]
+    pub next_id: u32,
+    #[serde(default)]
+    pub jobs: Vec<Job>,
+}
+
+impl JobQueue {
+    /// Appends a job and returns its ID.
+    pub fn enqueue(&mut self, prompt: impl Into<String>, chat_id: i64) -> u32 {
+        self.next_id = self.next_id.max(1);
+        let id = self.next_id;
+        self.next_id += 1;
+        self.jobs.push(Job {
+            id,
+            prompt: prompt.into(),
+            chat_id,
+            status: JobStatus::Queued,
+            enqueued_at: Utc::now(),
+            started_at: None,
+            finished_at: None,
+            loop_id: None,
+            termination: None,
+        });
+        id
+    }
+
+    /// Returns the job with the given ID.
+    pub fn get(&self, id: u32) -> Option<&Job> {
+        self.jobs.iter().find(|job| job.id == id)
+    }
+
+    /// Returns queued jobs in run order.
+    pub fn queued(&self) -> impl Iterator<Item = &Job> {
+        self.jobs
+            .iter()
+            .filter(|job| job.status == JobStatus::Queued)
+    }
+
+    /// Returns the 1-based position of a queued job.
+    pub fn position(&self, id: u32) -> Option<usize> {
+        self.queued().position(|job| job.id == id).map(|i| i + 1)
+    }
+
+    /// Cancels a queued job. Running and finished jobs are left alone.
+    pub fn cancel(&mut self, id: u32) -> bool {
+        match self.jobs.iter_mut().find(|job| job.id == id) {
+            Some(job) if job.status == JobStatus::Queued => {
+                job.status = JobStatus::Cancelled;
+                job.finished_at = Some(Utc::now());
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Moves a queued job ahead of every other queued job.
+    pub fn prioritize(&mut self, id: u32) -> bool {
+        let Some(index) = self
+            .jobs
+            .iter()
+            .position(|job| job.id == id && job.status == JobStatus::Queued)
+        else {
+            return false;
+        };
+        let job = self.jobs.remove(index);
+        let front = self
+            .jobs
+            .iter()
+            .position(|job| job.status == JobStatus::Queued)
+            .unwrap_or(self.jobs.len());
+        self.jobs.insert(front, job);
+        true
+    }
+
+    /// Marks the first queued job as running and returns it.
+    pub fn start_next(&mut self) -> Option<Job> {
+        let job = self
+            .jobs
+            .iter_mut()
+            .find(|job| job.status == JobStatus::Queued)?;
+        job.status = JobStatus::Running;
+        job.started_at = Some(Utc::now());
+        Some(job.clone())
+    }
+
+    /// Records the result of a job and drops old finished jobs.
+    pub fn finish(
+        &mut self,
+        id: u32,
+        status: JobStatus,
+        loop_id: Option<String>,
+        termination: impl Into<String>,
+    ) {
+        if let Some(job) = self.jobs.iter_mut().find(|job| job.id == id) {
+            job.status = status;
+            job.finished_at = Some(Utc::now());
+            if loop_id.is_some() {
+                job.loop_id = loop_id;
+            }
+            job.termination = Some(termination.into());
+        }
+        self.prune();
+    }
+
+    /// Fails jobs left running by a daemon that exited without finishing them.
+    pub fn fail_running(&mut self, reason: &str) -> Vec<Job> {
+        let now = Utc::now();
+        let mut failed = Vec::new();
+        for job in &mut self.jobs {
+            if job.status == JobStatus::Running {
+                job.status = JobStatus::Failed;
+                job.finished_at = Some(now);
+                job.termination = Some(reason.to_string());
+                failed.push(job.clone());
+            }
+        }
+        self.prune();
+        failed
+    }
+
+    fn prune(&mut self) {
+        let finished = self
+            .jobs
+            .iter()
+            .filter(|job| job.status.is_finished())
+            .count();
+        let mut excess = finished.saturating_sub(MAX_FINISHED_JOBS);
+        self.jobs.retain(|job| {
+            if excess > 0 && job.status.is_finished() {
+                excess -= 1;
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
+
+/// Manages persistence of the job queue to disk.
+pub struct QueueManager {
+    path: PathBuf,
+}
+
+impl QueueManager {
+    /// Create a QueueManager that reads/writes to the given path.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// Create the QueueManager for a workspace (`<root>/.ralph/telegram-queue.json`).
+    pub fn for_workspace(workspace_root: &Path) -> Self {
+        Self::new(workspace_root.join(QUEUE_FILE))
+    }
+
+    /// Load the queue, or an empty one if the file doesn't exist.
+    pub fn load(&self) -> TelegramResult<JobQueue> {
+        if !self.path.exists() {
+            return Ok(JobQueue::default());
+        }
+        let contents = std::fs::read_to_string(&self.path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// Save the queue using atomic write (temp file + rename).
+    pub fn save(&self, queue: &JobQueue) -> TelegramResult<()> {
+        let json = serde_json::to_string_pretty(queue)?;
+        let tmp_path = self.path.with_extension("json.tmp");
+
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        std::fs::write(&tmp_path, &json)?;
+        std::fs::rename(&tmp_path, &self.path)?;
+        Ok(())
+    }
+
+    /// Load the queue, apply `f`, and save it.
+    pub fn update<T>(&self, f: impl FnOnce(&mut JobQueue) -> T) -> TelegramResult<T> {
+        let mut queue = self.load()?;
+        let result = f(&mut queue);
+        self.save(&queue)?;
+        Ok(result)
+    }
+
+    /// Return the path to the queue file.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn enqueue_and_start_in_order() {
+        let mut queue = JobQueue::default();
+        assert_eq!(queue.enqueue("first", 1), 1);
+        assert_eq!(queue.enqueue("second", 2), 2);
+        assert_eq!(queue.position(2), Some(2));
+
+        let job = queue.start_next().unwrap();
+        assert_eq!(job.id, 1);
+        assert_eq!(job.status, JobStatus::Running);
+        assert_eq!(queue.position(1), None);
+        assert_eq!(queue.position(2), Some(1));
+
+        queue.finish(
+            1,
+            JobStatus::Done,
+            Some("primary-1".into()),
+            "CompletionPromise",
+        );
+        let job = queue.get(1).unwrap();
+        assert_eq!(job.loop_id.as_deref(), Some("primary-1"));
+        assert_eq!(job.termination.as_deref(), Some("CompletionPromise"));
+        assert!(job.status.is_finished());
+    }
+
+    #[test]
+    fn cancel_and_prioritize_only_touch_queued_jobs() {
+        let mut queue = JobQueue::default();
+        queue.enqueue("a", 1);
+        queue.enqueue("b", 1);
+        queue.enqueue("c", 1);
+        queue.start_next();
+
+        assert!(!queue.cancel(1), "running jobs are stopped with /stop");
+        assert!(!queue.prioritize(1));
+        assert!(!queue.cancel(99));
+
+        assert!(queue.prioritize(3));
+        let order: Vec<u32> = queue.queued().map(|job| job.id).collect();
+        assert_eq!(order, vec![3, 2]);
+
+        assert!(queue.cancel(3));
+        assert_eq!(queue.get(3).unwrap().status, JobStatus::Cancelled);
+        assert_eq!(queue.start_next().unwrap().id, 2);
+    }
+
+    #[test]
+    fn fail_running_and_prune_finished_jobs() {
+        let mut queue = JobQueue::default();
+        for i in 0..(MAX_FINISHED_JOBS + 5) {
+            let id = queue.enqueue(format!("job {i}"), 1);
+            queue.cancel(id);
+        }
+        queue.enqueue("running", 1);
+        queue.start_next();
+
+        let failed = queue.fail_running("daemon restarted");
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].termination.as_deref(), Some("daemon restarted"));
+        assert_eq!(queue.jobs.len(), MAX_FINISHED_JOBS);
+        assert_eq!(queue.jobs.last().unwrap().prompt, "running");
+    }
+
+    #[test]
+    fn queue_persists_across_managers() {
+        let dir = TempDir::new().unwrap();
+        let manager = QueueManager::for_workspace(dir.path());
+        assert!(manager.load().unwrap().jobs.is_empty());
+
+        let id = manager
+            .update(|queue| queue.enqueue("persist me", 7))
+            .unwrap();
+
+        let reloaded = QueueManager::for_workspace(dir.path()).load().unwrap();
+        assert_eq!(reloaded.get(id).unwrap().prompt, "persist me");
+        assert_eq!(reloaded.next_id, id + 1);
+        assert!(manager.path().ends_with("telegram-queue.json"));
+    }
+}
@@ -7,6 +7,8 @@ use std::time::{Duration, Instant};
 use chrono::Utc;
 use tracing::{debug, info, warn};
 
+use ralph_proto::AccessList;
+
 use crate::bot::TelegramBot;
 use crate::error::{TelegramError, TelegramResult};
 use crate::handler::MessageHandler;
@@ -91,6 +93,8 @@ pub struct TelegramService {
     handler: MessageHandler,
     bot: TelegramBot,
     shutdown: Arc<AtomicBool>,
+    access: AccessList,
+    api_url: Option<url::Url>,
 }
 
 impl TelegramService {
@@ -123,9 +127,33 @@ impl TelegramService {
             handler,
             bot,
             shutdown,
+            access: AccessList::default(),
+            api_url: None,
         })
     }
 
+    /// Restrict incoming messages and commands to the given access list.
+    ///
+    /// Without one, every sender is treated as an operator.
+    #[must_use]
+    pub fn with_access(mut self, access: AccessList) -> Self {
+        self.access = access;
+        self
+    }
+
+    /// Talk to the Bot API server at `api_url` instead of `api.telegram.org`.
+    #[must_use]
+    pub fn with_api_url(mut self, api_url: url::Url) -> Self {
+        self.bot = self.bot.with_api_url(&api_url);
+        self.api_url = Some(api_url);
+        self
+    }
+
+    /// Get the access list applied to incoming messages.
+    pub fn access(&self) -> &AccessList {
+        &self.access
+    }
+
     /// Get a reference to the workspace root.
     pub fn workspace_root(&self) -> &PathBuf {
         &self.workspace_root
@@ -189,15 +217,23 @@ impl TelegramService {
             TelegramError::Startup("no tokio runtime available for polling".to_string())
         })?;
 
-        let raw_bot = teloxide::Bot::new(&self.bot_token);
+        let raw_bot = crate::bot::teloxide_bot(&self.bot_token, self.api_url.as_ref());
+        let api = TelegramBot::from_bot(raw_bot.clone());
         let workspace_root = self.workspace_root.clone();
         let state_path = self.workspace_root.join(".ralph/telegram-state.json");
         let shutdown = self.shutdown.clone();
         let loop_id = self.loop_id.clone();
+        let access = self.access.clone();
 
-        handle.spawn(async move {
-            Self::poll_updates(raw_bot, workspace_root, state_path, shutdown, loop_id).await;
-        });
+        handle.spawn(Box::pin(Self::poll_updates(
+            raw_bot,
+            api,
+            workspace_root,
+            state_path,
+            shutdown,
+            loop_id,
+            access,
+        )));
 
         // Send greeting if we already know the chat ID
         if let Ok(state) = self.state_manager.load_or_default()
@@ -218,12 +254,17 @@ impl TelegramService {
     ///
     /// Uses long polling (`getUpdates`) to receive messages, then routes them
     /// through `MessageHandler` to write events to the correct loop's JSONL.
+    /// Senders without the role a message needs are rejected. Inline-keyboard
+    /// button presses are applied through [`crate::keyboard`], and merge queue
+    /// entries that start waiting for a decision are announced with buttons.
     async fn poll_updates(
         bot: teloxide::Bot,
+        api: TelegramBot,
         workspace_root: PathBuf,
         state_path: PathBuf,
         shutdown: Arc<AtomicBool>,
         loop_id: String,
+        access: AccessList,
     ) {
         use teloxide::payloads::{GetUpdatesSetters, SetMessageReactionSetters};
         use teloxide::requests::Requester;
@@ -231,6 +272,13 @@ impl TelegramService {
         let state_manager = StateManager::new(&state_path);
         let handler_state_manager = StateManager::new(&state_path);
         let handler = MessageHandler::new(handler_state_manager, &workspace_root);
+        let callbacks = crate::keyboard::CallbackContext {
+            workspace_root: &workspace_root,
+            access: &access,
+            state_manager: &state_manager,
+            handler: &handler,
+        };
+        let mut merges = crate::keyboard::MergeAnnouncer::new(&workspace_root);
         let mut offset: i32 = 0;
 
         if let Ok(state) = state_manager.load_or_default()
@@ -258,6 +306,15 @@ impl TelegramService {
                         // Extract message from update kind
                         let msg = match update.kind {
                             teloxide::types::UpdateKind::Message(msg) => msg,
+                            teloxide::types::UpdateKind::CallbackQuery(query) => {
+                                if let Some(press) =
+                                    crate::keyboard::ButtonPress::from_query(&query)
+                                {
+                                    crate::keyboard::handle_button_press(&api, &callbacks, &press)
+                                        .await;
+                                }
+                                continue;
+                            }
                             _ => continue,
                         };
 
@@ -267,6 +324,8 @@ impl TelegramService {
                         };
 
                         let chat_id = msg.chat.id.0;
+                        #[allow(clippy::cast_possible_wrap)]
+                        let user_id = msg.from.as_ref().map(|user| user.id.0 as i64);
                         let reply_to: Option<i32> = msg.reply_to_message().map(|r| r.id.0);
 
                         info!(
@@ -275,14 +334,37 @@ impl TelegramService {
                             "Received Telegram message"
                         );
 
-                        // Handle bot commands before routing to handler
+                        if let Err(rejection) =
+                            crate::access::authorize(&access, chat_id, user_id, text)
+                        {
+                            let _ = bot
+                                .send_message(teloxide::types::ChatId(chat_id), rejection)
+                                .await;
+                            continue;
+                        }
+
+                        if crate::commands::parse_command(text).0 == "/merges" {
+                            crate::keyboard::send_merge_reviews(&api, chat_id, &workspace_root)
+                                .await;
+                            continue;
+                        }
+
+                        // Handle bot commands before routing to handler. Queue
+                        // commands let operators line up the next loop while this one runs.
                         if crate::commands::is_command(text)
-                            && let Some(response) =
-                                crate::commands::handle_command(text, &workspace_root)
+                            && let Some(response) = crate::commands::handle_queue_command(
+                                text,
+                                &workspace_root,
+                                chat_id,
+                            )
+                            .or_else(|| crate::commands::handle_command(text, &workspace_root))
                         {
                             use teloxide::payloads::SendMessageSetters;
                             let send_result = bot
-                                .send_message(teloxide::types::ChatId(chat_id), &response)
+                                .send_message(
+                                    teloxide::types::ChatId(chat_id),
+                                    ralph_core::redaction::redact(&response),
+                                )
                                 .parse_mode(teloxide::types::ParseMode::Html)
                                 .await;
                             if let Err(e) = send_result {
@@ -301,10 +383,10 @@ impl TelegramService {
 
                         match handler.handle_message(&mut state, text, chat_id, reply_to) {
                             Ok(topic) => {
-                                let emoji = if topic == "human.response" {
-                                    "👍"
-                                } else {
+                                let emoji = if topic == "human.guidance" {
                                     "👀"
+                                } else {
+                                    "👍"
                                 };
                                 let react_result = bot
                                     .set_message_reaction(teloxide::types::ChatId(chat_id), msg.id)
@@ -341,6 +423,12 @@ impl TelegramService {
                             warn!(error = %e, "Failed to persist Telegram state");
                         }
                     }
+
+                    if let Ok(state) = state_manager.load_or_default()
+                        && let Some(chat_id) = state.chat_id
+                    {
+                        merges.announce(&api, chat_id, &workspace_root).await;
+                    }
                 }
                 Err(e) => {
                     if !shutdown.load(Ordering::Relaxed) {
@@ -364,6 +452,10 @@ impl TelegramService {
             BotCommand::new("tasks", "Open tasks"),
             BotCommand::new("memories", "Recent memories"),
             BotCommand::new("tail", "Last 20 events"),
+            BotCommand::new("queue", "Show or add to the job queue"),
+            BotCommand::new("cancel", "Cancel a queued job"),
+            BotCommand::new("priority", "Move a queued job to the front"),
+            BotCommand::new("merges", "Worktree loops waiting to be merged"),
             BotCommand::new("stop", "Stop the loop"),
             BotCommand::new("help", "List available commands"),
         ];
@@ -398,8 +490,10 @@ impl TelegramService {
 
     /// Send a question to the human via Telegram and store it as a pending question.
     ///
-    /// The question payload is extracted from the `human.interact` event. A pending
-    /// question is stored in the state manager so that incoming replies can be
+    /// The question payload is extracted from the `human.interact` event. A
+    /// structured payload (`{"question": ..., "options": [...]}`) is sent with
+    /// one inline-keyboard button per option. A pending question is stored in
+    /// the state manager so that incoming replies and button presses can be
     /// routed back to the correct loop.
     ///
     /// On send failure, retries up to 3 times with exponential backoff (1s, 2s, 4s).
@@ -407,9 +501,19 @@ impl TelegramService {
     /// is configured (question is logged but not sent).
     pub fn send_question(&self, payload: &str) -> TelegramResult<i32> {
         let mut state = self.state_manager.load_or_default()?;
-
-        let message_id = if let Some(chat_id) = state.chat_id {
-            self.send_with_retry(chat_id, payload)?
+        let chat_id = state.chat_id;
+        let interaction = crate::keyboard::Interaction::parse(payload);
+
+        let message_id = if let Some(chat_id) = chat_id {
+            if interaction.options.is_empty() {
+                self.send_with_retry(chat_id, &interaction.question)?
+            } else {
+                self.send_keyboard_with_retry(
+                    chat_id,
+                    &crate::keyboard::format_choice_question(&interaction.question),
+                    &crate::keyboard::question_keyboard(&interaction.options),
+                )?
+            }
         } else {
             warn!(
                 loop_id = %self.loop_id,
@@ -419,8 +523,14 @@ impl TelegramService {
             0
         };
 
-        self.state_manager
-            .add_pending_question(&mut state, &self.loop_id, message_id)?;
+        self.state_manager.add_pending_question(
+            &mut state,
+            &self.loop_id,
+            chat_id,
+            message_id,
+            interaction.options,
+            interaction.reply_topic,
+        )?;
 
         debug!(
             loop_id = %self.loop_id,
@@ -499,6 +609,22 @@ impl TelegramService {
         self.send_with_retry(chat_id, &msg)
     }
 
+    /// Send a one-off Markdown notification to the human via Telegram.
+    ///
+    /// Returns `Ok(0)` if no chat ID is configured.
+    pub fn send_notification(&self, message: &str) -> TelegramResult<i32> {
+        let state = self.state_manager.load_or_default()?;
+        let Some(chat_id) = state.chat_id else {
+            debug!(
+                loop_id = %self.loop_id,
+                "No chat ID configured — skipping notification"
+            );
+            return Ok(0);
+        };
+
+        self.send_with_retry(chat_id, &crate::bot::markdown_to_telegram_html(message))
+    }
+
     /// Send a document (file) to the human via Telegram.
     ///
     /// Loads the chat ID from state and sends the file at `file_path` with an
@@ -557,6 +683,31 @@ impl TelegramService {
         )
     }
 
+    /// Attempt to send a message with an inline keyboard, with exponential
+    /// backoff retries.
+    fn send_keyboard_with_retry(
+        &self,
+        chat_id: i64,
+        text: &str,
+        rows: &[Vec<crate::bot::InlineButton>],
+    ) -> TelegramResult<i32> {
+        use crate::bot::BotApi;
+
+        let handle = tokio::runtime::Handle::try_current().map_err(|_| TelegramError::Send {
+            attempts: 0,
+            reason: "no tokio runtime available for sending".to_string(),
+        })?;
+
+        retry_with_backoff(
+            |_attempt| {
+                tokio::task::block_in_place(|| {
+                    handle.block_on(self.bot.send_keyboard(chat_id, text, rows))
+                })
+            },
+            |delay| std::thread::sleep(delay),
+        )
+    }
+
     /// Attempt to send a document with exponential backoff retries.
     fn send_document_with_retry(
         &self,
@@ -771,6 +922,10 @@ impl ralph_proto::RobotService for TelegramService {
         )?)
     }
 
+    fn send_notification(&self, message: &str) -> anyhow::Result<i32> {
+        Ok(TelegramService::send_notification(self, message)?)
+    }
+
     fn timeout_secs(&self) -> u64 {
         self.timeout_secs
     }
@@ -881,6 +1036,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn send_question_stores_structured_options() {
+        let dir = TempDir::new().unwrap();
+        let service = test_service(&dir);
+
+        service
+            .send_question(r#"{"question": "Which DB?", "options": ["Postgres", "SQLite"]}"#)
+            .unwrap();
+
+        let state = service.state_manager().load_or_default().unwrap();
+        assert_eq!(
+            state.pending_questions["main"].options,
+            vec!["Postgres", "SQLite"]
+        );
+    }
+
     #[test]
     fn send_question_returns_message_id() {
         let dir = TempDir::new().unwrap();
@@ -32,6 +32,28 @@ pub struct PendingQuestion {
 
     /// The Telegram message ID, used to match reply-to routing.
     pub message_id: i32,
+
+    /// The chat the question was sent to. Message IDs are only unique per
+    /// chat, so replies are matched on both. `None` for questions persisted
+    /// before chats were tracked.
+    #[serde(default)]
+    pub chat_id: Option<i64>,
+
+    /// Answer choices offered as inline-keyboard buttons, in button order.
+    /// Empty for free-text questions.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub options: Vec<String>,
+
+    /// Topic the answer is written as. `None` means `human.response`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reply_topic: Option<String>,
+}
+
+impl PendingQuestion {
+    /// Topic the answer to this question is written as.
+    pub fn reply_topic(&self) -> &str {
+        self.reply_topic.as_deref().unwrap_or("human.response")
+    }
 }
 
 /// Manages persistence of Telegram bot state to disk.
@@ -79,18 +101,26 @@ impl StateManager {
         }))
     }
 
-    /// Add a pending question for a given loop.
+    /// Add a pending question for a given loop, asked in `chat_id`, with
+    /// the answer `options` shown as buttons (empty for free-text answers).
+    /// The answer is written as `reply_topic`, or `human.response` when `None`.
     pub fn add_pending_question(
         &self,
         state: &mut TelegramState,
         loop_id: &str,
+        chat_id: Option<i64>,
         message_id: i32,
+        options: Vec<String>,
+        reply_topic: Option<String>,
     ) -> TelegramResult<()> {
         state.pending_questions.insert(
             loop_id.to_string(),
             PendingQuestion {
                 asked_at: Utc::now(),
                 message_id,
+                chat_id,
+                options,
+                reply_topic,
             },
         );
         self.save(state)
@@ -106,16 +136,19 @@ impl StateManager {
         self.save(state)
     }
 
-    /// Given a reply_to_message_id, find which loop it belongs to.
+    /// Given a reply in `chat_id` to `reply_message_id`, find which loop it belongs to.
     pub fn get_loop_for_reply(
         &self,
         state: &TelegramState,
+        chat_id: i64,
         reply_message_id: i32,
     ) -> Option<String> {
         state
             .pending_questions
             .iter()
-            .find(|(_, q)| q.message_id == reply_message_id)
+            .find(|(_, q)| {
+                q.message_id == reply_message_id && q.chat_id.is_none_or(|id| id == chat_id)
+            })
             .map(|(loop_id, _)| loop_id.clone())
     }
 
@@ -170,7 +203,8 @@ mod tests {
         let (mgr, _dir) = test_manager();
         let mut state = mgr.load_or_default().unwrap();
 
-        mgr.add_pending_question(&mut state, "main", 42).unwrap();
+        mgr.add_pending_question(&mut state, "main", Some(1), 42, Vec::new(), None)
+            .unwrap();
         assert!(state.pending_questions.contains_key("main"));
         assert_eq!(state.pending_questions["main"].message_id, 42);
 
@@ -183,15 +217,48 @@ mod tests {
         let (mgr, _dir) = test_manager();
         let mut state = mgr.load_or_default().unwrap();
 
-        mgr.add_pending_question(&mut state, "main", 10).unwrap();
-        mgr.add_pending_question(&mut state, "feature-auth", 20)
+        mgr.add_pending_question(&mut state, "main", Some(1), 10, Vec::new(), None)
+            .unwrap();
+        mgr.add_pending_question(&mut state, "feature-auth", Some(1), 20, Vec::new(), None)
             .unwrap();
 
-        assert_eq!(mgr.get_loop_for_reply(&state, 10), Some("main".to_string()));
         assert_eq!(
-            mgr.get_loop_for_reply(&state, 20),
+            mgr.get_loop_for_reply(&state, 1, 10),
+            Some("main".to_string())
+        );
+        assert_eq!(
+            mgr.get_loop_for_reply(&state, 1, 20),
             Some("feature-auth".to_string())
         );
-        assert_eq!(mgr.get_loop_for_reply(&state, 99), None);
+        assert_eq!(mgr.get_loop_for_reply(&state, 1, 99), None);
+    }
+
+    #[test]
+    fn reply_routing_is_per_chat() {
+        let (mgr, _dir) = test_manager();
+        let mut state = mgr.load_or_default().unwrap();
+
+        // Message IDs are per chat: the same ID in two chats must not collide.
+        mgr.add_pending_question(&mut state, "main", Some(1), 10, Vec::new(), None)
+            .unwrap();
+        mgr.add_pending_question(&mut state, "feature-auth", Some(2), 10, Vec::new(), None)
+            .unwrap();
+
+        assert_eq!(
+            mgr.get_loop_for_reply(&state, 1, 10),
+            Some("main".to_string())
+        );
+        assert_eq!(
+            mgr.get_loop_for_reply(&state, 2, 10),
+            Some("feature-auth".to_string())
+        );
+        assert_eq!(mgr.get_loop_for_reply(&state, 3, 10), None);
+
+        // Questions persisted without a chat match replies from any chat.
+        state.pending_questions.get_mut("main").unwrap().chat_id = None;
+        assert_eq!(
+            mgr.get_loop_for_reply(&state, 3, 10),
+            Some("main".to_string())
+        );
     }
 }
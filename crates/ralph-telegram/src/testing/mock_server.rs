@@ -0,0 +1,765 @@
+//! A local stand-in for the Telegram Bot API.
+//!
+//! [`MockTelegramServer`] listens on a loopback port and answers the Bot API
+//! methods Ralph uses (`getMe`, `getUpdates`, `sendMessage`, `sendDocument`,
+//! `sendPhoto`, `editMessageText`, `answerCallbackQuery`, `setMyCommands`,
+//! `setMessageReaction`). Tests script what the user does — messages,
+//! replies, button presses — and inspect what the bot sent.
+//!
+//! Point a bot at it with `RObot.telegram.api_url` (or
+//! `RALPH_TELEGRAM_API_URL`), [`TelegramService::with_api_url`] or
+//! [`TelegramDaemon::with_api_url`]:
+//!
+//! ```no_run
+//! # async fn example() -> std::io::Result<()> {
+//! use ralph_telegram::testing::MockTelegramServer;
+//!
+//! let server = MockTelegramServer::start().await?;
+//! let api_url = ralph_telegram::parse_api_url(&server.api_url()).unwrap();
+//! let bot = ralph_telegram::TelegramBot::new("test-token").with_api_url(&api_url);
+//! server.send_user_message(100, 7, "/status");
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`TelegramService::with_api_url`]: crate::TelegramService::with_api_url
+//! [`TelegramDaemon::with_api_url`]: crate::TelegramDaemon::with_api_url
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+
+use serde_json::{Value, json};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Notify;
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+/// User ID of the mock bot itself.
+const BOT_USER_ID: i64 = 1;
+
+/// Longest `getUpdates` long poll the server holds open.
+const MAX_POLL: Duration = Duration::from_secs(30);
+
+/// A Bot API request received by the server.
+#[derive(Debug, Clone)]
+pub struct ApiCall {
+    /// Method name, e.g. `sendMessage`.
+    pub method: String,
+    /// JSON, form and query parameters merged into one object. Multipart
+    /// file fields hold the uploaded file name.
+    pub params: Value,
+}
+
+/// A message the bot sent or edited.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SentMessage {
+    /// `sendMessage`, `sendDocument`, `sendPhoto` or `editMessageText`.
+    pub method: String,
+    pub chat_id: i64,
+    pub message_id: i32,
+    /// Message text, or the caption of a document or photo.
+    pub text: String,
+    /// Inline keyboard attached to the message, as sent.
+    pub reply_markup: Option<Value>,
+    /// Uploaded file name for documents and photos.
+    pub file_name: Option<String>,
+}
+
+#[derive(Default)]
+struct ServerState {
+    next_update_id: i32,
+    next_message_id: i32,
+    next_callback_id: u32,
+    updates: Vec<Value>,
+    calls: Vec<ApiCall>,
+    sent: Vec<SentMessage>,
+}
+
+struct Shared {
+    state: Mutex<ServerState>,
+    /// Signalled when an update is queued.
+    updates_ready: Notify,
+    /// Signalled after every handled request.
+    activity: Notify,
+}
+
+impl Shared {
+    fn state(&self) -> MutexGuard<'_, ServerState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// An in-process Telegram Bot API server for offline tests.
+///
+/// Stops listening when dropped.
+pub struct MockTelegramServer {
+    port: u16,
+    shared: Arc<Shared>,
+    accept_task: JoinHandle<()>,
+}
+
+impl MockTelegramServer {
+    /// Starts the server on a free loopback port.
+    pub async fn start() -> std::io::Result<Self> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let port = listener.local_addr()?.port();
+        let shared = Arc::new(Shared {
+            state: Mutex::new(ServerState {
+                next_update_id: 1,
+                next_message_id: 1,
+                next_callback_id: 1,
+                ..ServerState::default()
+            }),
+            updates_ready: Notify::new(),
+            activity: Notify::new(),
+        });
+
+        let accept_shared = shared.clone();
+        let accept_task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let shared = accept_shared.clone();
+                tokio::spawn(async move {
+                    if let Err(e) = serve_connection(stream, &shared).await {
+                        tracing::debug!(error = %e, "mock Telegram connection failed");
+                    }
+                });
+            }
+        });
+
+        Ok(Self {
+            port,
+            shared,
+            accept_task,
+        })
+    }
+
+    /// Base URL to use as `RObot.telegram.api_url`.
+    pub fn api_url(&self) -> String {
+        format!("http://127.0.0.1:{}/", self.port)
+    }
+
+    /// Queues a text message from a user. Returns its message ID.
+    pub fn send_user_message(&self, chat_id: i64, user_id: i64, text: &str) -> i32 {
+        self.queue_message(chat_id, user_id, text, None)
+    }
+
+    /// Queues a user's reply to one of the bot's messages. Returns its message ID.
+    pub fn send_user_reply(&self, chat_id: i64, user_id: i64, text: &str, reply_to: i32) -> i32 {
+        self.queue_message(chat_id, user_id, text, Some(reply_to))
+    }
+
+    /// Queues a press of an inline-keyboard button on one of the bot's
+    /// messages. Returns the callback query ID.
+    pub fn press_button(&self, chat_id: i64, user_id: i64, message_id: i32, data: &str) -> String {
+        let mut state = self.shared.state();
+        let callback_id = format!("cb-{}", state.next_callback_id);
+        state.next_callback_id += 1;
+        let text = state
+            .sent
+            .iter()
+            .rev()
+            .find(|sent| sent.chat_id == chat_id && sent.message_id == message_id)
+            .map(|sent| sent.text.clone())
+            .unwrap_or_default();
+        let query = json!({
+            "id": callback_id,
+            "from": user_json(user_id),
+            "chat_instance": chat_id.to_string(),
+            "data": data,
+            "message": message_json(chat_id, message_id, BOT_USER_ID, &text),
+        });
+        push_update(&mut state, "callback_query", query);
+        drop(state);
+        self.shared.updates_ready.notify_waiters();
+        callback_id
+    }
+
+    /// Every request received so far, in order.
+    pub fn calls(&self) -> Vec<ApiCall> {
+        self.shared.state().calls.clone()
+    }
+
+    /// Requests received so far for one method.
+    pub fn calls_to(&self, method: &str) -> Vec<ApiCall> {
+        self.shared
+            .state()
+            .calls
+            .iter()
+            .filter(|call| call.method == method)
+            .cloned()
+            .collect()
+    }
+
+    /// Messages sent or edited by the bot so far, in order.
+    pub fn sent_messages(&self) -> Vec<SentMessage> {
+        self.shared.state().sent.clone()
+    }
+
+    /// Waits until the bot has sent a message matching `predicate`.
+    pub async fn wait_for_message(
+        &self,
+        timeout: Duration,
+        predicate: impl Fn(&SentMessage) -> bool,
+    ) -> Option<SentMessage> {
+        self.wait_until(timeout, || {
+            self.shared
+                .state()
+                .sent
+                .iter()
+                .find(|m| predicate(m))
+                .cloned()
+        })
+        .await
+    }
+
+    /// Waits until the bot has called `method`, returning the first such call.
+    pub async fn wait_for_call(&self, method: &str, timeout: Duration) -> Option<ApiCall> {
+        self.wait_until(timeout, || {
+            self.shared
+                .state()
+                .calls
+                .iter()
+                .find(|call| call.method == method)
+                .cloned()
+        })
+        .await
+    }
+
+    async fn wait_until<T>(&self, timeout: Duration, check: impl Fn() -> Option<T>) -> Option<T> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let activity = self.shared.activity.notified();
+            if let Some(found) = check() {
+                return Some(found);
+            }
+            if tokio::time::timeout_at(deadline, activity).await.is_err() {
+                return check();
+            }
+        }
+    }
+
+    fn queue_message(&self, chat_id: i64, user_id: i64, text: &str, reply_to: Option<i32>) -> i32 {
+        let mut state = self.shared.state();
+        let message_id = state.next_message_id;
+        state.next_message_id += 1;
+        let mut message = message_json(chat_id, message_id, user_id, text);
+        if let Some(reply_to) = reply_to {
+            let original = state
+                .sent
+                .iter()
+                .rev()
+                .find(|sent| sent.chat_id == chat_id && sent.message_id == reply_to)
+                .map(|sent| sent.text.clone())
+                .unwrap_or_default();
+            message["reply_to_message"] = message_json(chat_id, reply_to, BOT_USER_ID, &original);
+        }
+        push_update(&mut state, "message", message);
+        drop(state);
+        self.shared.updates_ready.notify_waiters();
+        message_id
+    }
+}
+
+impl Drop for MockTelegramServer {
+    fn drop(&mut self) {
+        self.accept_task.abort();
+    }
+}
+
+fn push_update(state: &mut ServerState, kind: &str, payload: Value) {
+    let update_id = state.next_update_id;
+    state.next_update_id += 1;
+    state
+        .updates
+        .push(json!({ "update_id": update_id, kind: payload }));
+}
+
+fn user_json(user_id: i64) -> Value {
+    if user_id == BOT_USER_ID {
+        json!({ "id": BOT_USER_ID, "is_bot": true, "first_name": "Ralph", "username": "ralph_mock_bot" })
+    } else {
+        json!({ "id": user_id, "is_bot": false, "first_name": format!("User {user_id}") })
+    }
+}
+
+fn chat_json(chat_id: i64) -> Value {
+    if chat_id < 0 {
+        json!({ "id": chat_id, "type": "group", "title": format!("Group {chat_id}") })
+    } else {
+        json!({ "id": chat_id, "type": "private", "first_name": format!("User {chat_id}") })
+    }
+}
+
+fn message_json(chat_id: i64, message_id: i32, from: i64, text: &str) -> Value {
+    json!({
+        "message_id": message_id,
+        "date": chrono::Utc::now().timestamp(),
+        "chat": chat_json(chat_id),
+        "from": user_json(from),
+        "text": text,
+    })
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Bot API methods
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Answers one Bot API call with the `result` payload, or an error description.
+async fn dispatch(shared: &Shared, method: &str, params: &Value) -> Result<Value, String> {
+    match method {
+        "getMe" => Ok(user_json(BOT_USER_ID)),
+        "getUpdates" => Ok(get_updates(shared, params).await),
+        "sendMessage" => send(shared, method, params, "text"),
+        "sendDocument" | "sendPhoto" => send(shared, method, params, "caption"),
+        "editMessageText" => edit_message(shared, params),
+        "answerCallbackQuery" | "setMyCommands" | "setMessageReaction" | "deleteWebhook" => {
+            Ok(Value::Bool(true))
+        }
+        _ => Err(format!("Not Found: method {method} is not mocked")),
+    }
+}
+
+async fn get_updates(shared: &Shared, params: &Value) -> Value {
+    let offset = param_i64(params, "offset").unwrap_or(0);
+    let timeout = param_i64(params, "timeout")
+        .and_then(|secs| u64::try_from(secs).ok())
+        .map_or(Duration::ZERO, Duration::from_secs)
+        .min(MAX_POLL);
+    let deadline = Instant::now() + timeout;
+
+    loop {
+        let ready = shared.updates_ready.notified();
+        {
+            let mut state = shared.state();
+            // Like Telegram, an offset confirms every earlier update.
+            state
+                .updates
+                .retain(|update| update["update_id"].as_i64().unwrap_or(0) >= offset);
+            if !state.updates.is_empty() {
+                return Value::Array(state.updates.clone());
+            }
+        }
+        if tokio::time::timeout_at(deadline, ready).await.is_err() {
+            return Value::Array(Vec::new());
+        }
+    }
+}
+
+fn send(shared: &Shared, method: &str, params: &Value, text_field: &str) -> Result<Value, String> {
+    let chat_id = param_i64(params, "chat_id").ok_or("Bad Request: chat_id is required")?;
+    let text = param_str(params, text_field).unwrap_or_default();
+    if method == "sendMessage" && text.is_empty() {
+        return Err("Bad Request: message text is empty".to_string());
+    }
+    let file_field = match method {
+        "sendDocument" => Some("document"),
+        "sendPhoto" => Some("photo"),
+        _ => None,
+    };
+    let file_name = file_field.and_then(|field| param_str(params, field));
+
+    let mut state = shared.state();
+    let message_id = state.next_message_id;
+    state.next_message_id += 1;
+    state.sent.push(SentMessage {
+        method: method.to_string(),
+        chat_id,
+        message_id,
+        text: text.clone(),
+        reply_markup: reply_markup(params),
+        file_name: file_name.clone(),
+    });
+
+    let mut message = message_json(chat_id, message_id, BOT_USER_ID, &text);
+    if method != "sendMessage" {
+        let object = message.as_object_mut().expect("message is an object");
+        object.remove("text");
+        if !text.is_empty() {
+            object.insert("caption".into(), Value::String(text));
+        }
+        let file = json!({
+            "file_id": format!("file-{message_id}"),
+            "file_unique_id": format!("unique-{message_id}"),
+            "file_size": 1,
+        });
+        if method == "sendDocument" {
+            let mut document = file;
+            document["file_name"] = json!(file_name.unwrap_or_default());
+            object.insert("document".into(), document);
+        } else {
+            let mut photo = file;
+            photo["width"] = json!(1);
+            photo["height"] = json!(1);
+            object.insert("photo".into(), json!([photo]));
+        }
+    }
+    Ok(message)
+}
+
+fn edit_message(shared: &Shared, params: &Value) -> Result<Value, String> {
+    let chat_id = param_i64(params, "chat_id").ok_or("Bad Request: chat_id is required")?;
+    let message_id = param_i64(params, "message_id")
+        .and_then(|id| i32::try_from(id).ok())
+        .ok_or("Bad Request: message_id is required")?;
+    let text = param_str(params, "text").unwrap_or_default();
+
+    let mut state = shared.state();
+    if !state
+        .sent
+        .iter()
+        .any(|sent| sent.chat_id == chat_id && sent.message_id == message_id)
+    {
+        return Err("Bad Request: message to edit not found".to_string());
+    }
+    state.sent.push(SentMessage {
+        method: "editMessageText".to_string(),
+        chat_id,
+        message_id,
+        text: text.clone(),
+        reply_markup: reply_markup(params),
+        file_name: None,
+    });
+    Ok(message_json(chat_id, message_id, BOT_USER_ID, &text))
+}
+
+fn reply_markup(params: &Value) -> Option<Value> {
+    match params.get("reply_markup")? {
+        Value::String(raw) => serde_json::from_str(raw).ok(),
+        other => Some(other.clone()),
+    }
+}
+
+fn param_i64(params: &Value, key: &str) -> Option<i64> {
+    match params.get(key)? {
+        Value::Number(n) => n.as_i64(),
+        Value::String(s) => s.parse().ok(),
+        _ => None,
+    }
+}
+
+fn param_str(params: &Value, key: &str) -> Option<String> {
+    match params.get(key)? {
+        Value::String(s) => Some(s.clone()),
+        Value::Null => None,
+        other => Some(other.to_string()),
+    }
+}
+
+// ─────────────────────────────────────────────────────────────────────────────
+// Minimal HTTP/1.1 handling
+// ─────────────────────────────────────────────────────────────────────────────
+
+struct Request {
+    path: String,
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+/// Serves a single request, then closes the connection.
+async fn serve_connection(mut stream: TcpStream, shared: &Shared) -> std::io::Result<()> {
+    let Some(request) = read_request(&mut stream).await? else {
+        return Ok(());
+    };
+
+    let (status, body) = match parse_call(&request) {
+        Some(call) => {
+            shared.state().calls.push(call.clone());
+            let response = match dispatch(shared, &call.method, &call.params).await {
+                Ok(result) => ("200 OK", json!({ "ok": true, "result": result })),
+                Err(description) => (
+                    "400 Bad Request",
+                    json!({ "ok": false, "error_code": 400, "description": description }),
+                ),
+            };
+            shared.activity.notify_waiters();
+            response
+        }
+        None => (
+            "404 Not Found",
+            json!({ "ok": false, "error_code": 404, "description": "Not Found" }),
+        ),
+    };
+
+    let body = body.to_string();
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
+
+async fn read_request(stream: &mut TcpStream) -> std::io::Result<Option<Request>> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 8192];
+    let header_end = loop {
+        if let Some(pos) = find(&buf, b"\r\n\r\n") {
+            break pos;
+        }
+        let n = stream.read(&mut chunk).await?;
+        if n == 0 {
+            return Ok(None);
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    };
+
+    let head = String::from_utf8_lossy(&buf[..header_end]).into_owned();
+    let mut lines = head.split("\r\n");
+    let path = lines
+        .next()
+        .and_then(|line| line.split_whitespace().nth(1))
+        .unwrap_or("/")
+        .to_string();
+    let headers: HashMap<String, String> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(name, value)| (name.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+
+    let mut body = buf[header_end + 4..].to_vec();
+    if headers
+        .get("transfer-encoding")
+        .is_some_and(|te| te.eq_ignore_ascii_case("chunked"))
+    {
+        while find(&body, b"0\r\n\r\n").is_none_or(|pos| !is_final_chunk(&body, pos)) {
+            let n = stream.read(&mut chunk).await?;
+            if n == 0 {
+                break;
+            }
+            body.extend_from_slice(&chunk[..n]);
+        }
+        body = decode_chunked(&body);
+    } else {
+        let length: usize = headers
+            .get("content-length")
+            .and_then(|len| len.parse().ok())
+            .unwrap_or(0);
+        while body.len() < length {
+            let n = stream.read(&mut chunk).await?;
+            if n == 0 {
+                break;
+            }
+            body.extend_from_slice(&chunk[..n]);
+        }
+    }
+
+    Ok(Some(Request {
+        path,
+        headers,
+        body,
+    }))
+}
+
+/// True when the `0\r\n\r\n` at `pos` is the terminating chunk rather than data.
+fn is_final_chunk(body: &[u8], pos: usize) -> bool {
+    pos == 0 || body[..pos].ends_with(b"\r\n")
+}
+
+fn decode_chunked(mut body: &[u8]) -> Vec<u8> {
+    let mut decoded = Vec::new();
+    while let Some(line_end) = find(body, b"\r\n") {
+        let size_line = String::from_utf8_lossy(&body[..line_end]);
+        let size_hex = size_line.split(';').next().unwrap_or("").trim();
+        let Ok(size) = usize::from_str_radix(size_hex, 16) else {
+            break;
+        };
+        if size == 0 {
+            break;
+        }
+        let start = line_end + 2;
+        let end = (start + size).min(body.len());
+        decoded.extend_from_slice(&body[start..end]);
+        body = body.get(end + 2..).unwrap_or_default();
+    }
+    decoded
+}
+
+/// Parses `/bot<token>/<method>` plus query, JSON, form or multipart parameters.
+fn parse_call(request: &Request) -> Option<ApiCall> {
+    let (path, query) = request
+        .path
+        .split_once('?')
+        .unwrap_or((request.path.as_str(), ""));
+    let method = path
+        .trim_start_matches('/')
+        .strip_prefix("bot")?
+        .split_once('/')?
+        .1;
+    // Method names are case-insensitive (teloxide sends `SendMessage`);
+    // record them in the documented camelCase.
+    let mut chars = method.chars();
+    let method = chars
+        .next()
+        .map(|first| first.to_ascii_lowercase().to_string() + chars.as_str())
+        .unwrap_or_default();
+
+    let mut params = serde_json::Map::new();
+    for (key, value) in parse_urlencoded(query) {
+        params.insert(key, Value::String(value));
+    }
+
+    let content_type = request
+        .headers
+        .get("content-type")
+        .map(String::as_str)
+        .unwrap_or_default();
+    if content_type.starts_with("application/json") {
+        if let Ok(Value::Object(body)) = serde_json::from_slice(&request.body) {
+            params.extend(body);
+        }
+    } else if content_type.starts_with("application/x-www-form-urlencoded") {
+        for (key, value) in parse_urlencoded(&String::from_utf8_lossy(&request.body)) {
+            params.insert(key, Value::String(value));
+        }
+    } else if let Some(boundary) = content_type
+        .split(';')
+        .find_map(|part| part.trim().strip_prefix("boundary="))
+    {
+        let fields: HashMap<String, String> =
+            parse_multipart(&request.body, boundary.trim_matches('"'))
+                .into_iter()
+                .collect();
+        for (key, value) in &fields {
+            // Uploads are sent as `document=attach://<part>` plus a file part.
+            let value = value
+                .strip_prefix("attach://")
+                .and_then(|part| fields.get(part))
+                .unwrap_or(value);
+            params.insert(key.clone(), Value::String(value.clone()));
+        }
+    }
+
+    Some(ApiCall {
+        method,
+        params: Value::Object(params),
+    })
+}
+
+fn parse_urlencoded(input: &str) -> Vec<(String, String)> {
+    input
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(key), percent_decode(value))
+        })
+        .collect()
+}
+
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'+' => decoded.push(b' '),
+            b'%' if i + 2 < bytes.len() => {
+                let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).unwrap_or_default();
+                match u8::from_str_radix(hex, 16) {
+                    Ok(byte) => {
+                        decoded.push(byte);
+                        i += 2;
+                    }
+                    Err(_) => decoded.push(b'%'),
+                }
+            }
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// Returns multipart fields as `(name, value)`; file fields yield their file name.
+fn parse_multipart(body: &[u8], boundary: &str) -> Vec<(String, String)> {
+    let delimiter = format!("--{boundary}");
+    let mut fields = Vec::new();
+    let mut rest = body;
+    while let Some(start) = find(rest, delimiter.as_bytes()) {
+        rest = &rest[start + delimiter.len()..];
+        if rest.starts_with(b"--") {
+            break;
+        }
+        let Some(header_end) = find(rest, b"\r\n\r\n") else {
+            break;
+        };
+        let headers = String::from_utf8_lossy(&rest[..header_end]).into_owned();
+        let content_start = header_end + 4;
+        let content_end = find(&rest[content_start..], delimiter.as_bytes())
+            .map_or(rest.len(), |end| content_start + end);
+        let content = rest[content_start..content_end]
+            .strip_suffix(b"\r\n")
+            .unwrap_or(&rest[content_start..content_end]);
+
+        let name = disposition_param(&headers, "name");
+        let file_name = disposition_param(&headers, "filename");
+        if let Some(name) = name {
+            let value = file_name.unwrap_or_else(|| String::from_utf8_lossy(content).into_owned());
+            fields.push((name, value));
+        }
+        rest = &rest[content_end..];
+    }
+    fields
+}
+
+fn disposition_param(headers: &str, key: &str) -> Option<String> {
+    let needle = format!("{key}=\"");
+    headers
+        .split(';')
+        .map(str::trim)
+        .find_map(|part| part.strip_prefix(&needle))
+        .and_then(|value| value.split('"').next())
+        .map(String::from)
+}
+
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    haystack
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_call_merges_query_and_json_params() {
+        let request = Request {
+            path: "/bot123:abc/sendMessage?disable_notification=true".to_string(),
+            headers: HashMap::from([("content-type".into(), "application/json".into())]),
+            body: br#"{"chat_id": 5, "text": "hi"}"#.to_vec(),
+        };
+        let call = parse_call(&request).unwrap();
+        assert_eq!(call.method, "sendMessage");
+        assert_eq!(param_i64(&call.params, "chat_id"), Some(5));
+        assert_eq!(param_str(&call.params, "text").as_deref(), Some("hi"));
+        assert_eq!(
+            param_str(&call.params, "disable_notification").as_deref(),
+            Some("true")
+        );
+    }
+
+    #[test]
+    fn parse_multipart_reads_fields_and_file_names() {
+        let body = b"--XyZ\r\nContent-Disposition: form-data; name=\"chat_id\"\r\n\r\n42\r\n\
+--XyZ\r\nContent-Disposition: form-data; name=\"document\"; filename=\"report.txt\"\r\n\
+Content-Type: text/plain\r\n\r\nline one\r\nline two\r\n--XyZ--\r\n";
+        assert_eq!(
+            parse_multipart(body, "XyZ"),
+            vec![
+                ("chat_id".to_string(), "42".to_string()),
+                ("document".to_string(), "report.txt".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn decode_chunked_joins_chunks() {
+        assert_eq!(
+            decode_chunked(b"5\r\nhello\r\n6\r\n world\r\n0\r\n\r\n"),
+            b"hello world"
+        );
+        assert_eq!(percent_decode("a%20b+c%2Fd"), "a b c/d");
+    }
+}
@@ -0,0 +1,5 @@
+//! Testing utilities for running the Telegram integration offline.
+
+pub mod mock_server;
+
+pub use mock_server::{ApiCall, MockTelegramServer, SentMessage};
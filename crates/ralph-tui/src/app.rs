@@ -84,6 +84,14 @@ pub fn dispatch_action(action: Action, state: &mut TuiState, viewport_height: us
         Action::GuidanceNow => {
             state.start_guidance(crate::state::GuidanceMode::Now);
         }
+        Action::Approve => {
+            state.approve();
+        }
+        Action::Reject => {
+            if !state.pending_approvals.is_empty() {
+                state.start_guidance(crate::state::GuidanceMode::Reject);
+            }
+        }
         Action::None => {}
     }
     false
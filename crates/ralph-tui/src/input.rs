@@ -40,6 +40,10 @@ pub enum Action {
     GuidanceNext,
     /// Open guidance input for current iteration (urgent)
     GuidanceNow,
+    /// Approve the oldest event held for approval
+    Approve,
+    /// Open reason input to reject the oldest event held for approval
+    Reject,
     /// Key not mapped to any action
     None,
 }
@@ -57,6 +61,8 @@ pub enum Action {
 /// - `/`: Start search
 /// - `n`: Next search match
 /// - `N`: Previous search match
+/// - `a`: Approve held event
+/// - `r`: Reject held event (with reason)
 /// - `?`: Show help
 /// - `Esc`: Dismiss help/cancel search
 pub fn map_key(key: KeyEvent) -> Action {
@@ -83,6 +89,10 @@ pub fn map_key(key: KeyEvent) -> Action {
         KeyCode::Char(':') => Action::GuidanceNext,
         KeyCode::Char('!') => Action::GuidanceNow,
 
+        // Approval gates
+        KeyCode::Char('a') => Action::Approve,
+        KeyCode::Char('r') => Action::Reject,
+
         // Help
         KeyCode::Char('?') => Action::ShowHelp,
         KeyCode::Esc => Action::DismissHelp,
@@ -209,6 +219,14 @@ mod tests {
         assert_eq!(map_key(key), Action::GuidanceNow);
     }
 
+    #[test]
+    fn a_and_r_answer_approvals() {
+        let key = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::NONE);
+        assert_eq!(map_key(key), Action::Approve);
+        let key = KeyEvent::new(KeyCode::Char('r'), KeyModifiers::NONE);
+        assert_eq!(map_key(key), Action::Reject);
+    }
+
     // AC17: Unknown Key Returns None
     #[test]
     fn unknown_key_returns_none() {
@@ -84,6 +84,15 @@ impl Tui {
         self
     }
 
+    /// Sets the file approval decisions from the `a`/`r` keys are appended to.
+    #[must_use]
+    pub fn with_approval_decisions_path(self, path: std::path::PathBuf) -> Self {
+        if let Ok(mut state) = self.state.lock() {
+            state.approval_decisions_path = Some(path);
+        }
+        self
+    }
+
     /// Returns the shared state for external updates.
     pub fn state(&self) -> Arc<Mutex<TuiState>> {
         Arc::clone(&self.state)
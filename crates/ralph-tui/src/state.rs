@@ -100,6 +100,9 @@ pub enum GuidanceMode {
     Next,
     /// Guidance for the current iteration (written immediately to events.jsonl)
     Now,
+    /// Reason for rejecting the oldest event held for approval
+    /// (written immediately to the approval decisions file)
+    Reject,
 }
 
 /// Result of attempting to send guidance.
@@ -185,6 +188,18 @@ pub struct TuiState {
     /// Currently active task (if any) for display in TUI widgets.
     pub active_task: Option<TaskSummary>,
 
+    // ========================================================================
+    // Timer State
+    // ========================================================================
+    /// Pending delayed events and watchdogs, soonest first, as display strings.
+    pub pending_timers: Vec<String>,
+
+    // ========================================================================
+    // Approval State
+    // ========================================================================
+    /// Events held for human approval, oldest first, as display strings.
+    pub pending_approvals: Vec<String>,
+
     // ========================================================================
     // Guidance State
     // ========================================================================
@@ -196,6 +211,8 @@ pub struct TuiState {
     pub guidance_next_queue: Arc<Mutex<Vec<String>>>,
     /// Path to events.jsonl for writing "now" guidance directly.
     pub events_path: Option<std::path::PathBuf>,
+    /// Path human approval decisions are appended to.
+    pub approval_decisions_path: Option<std::path::PathBuf>,
     /// Brief flash message after attempting to send guidance.
     /// (mode, result, when)
     pub guidance_flash: Option<(GuidanceMode, GuidanceResult, Instant)>,
@@ -234,11 +251,14 @@ impl TuiState {
             // Task tracking state
             task_counts: TaskCounts::default(),
             active_task: None,
+            pending_timers: Vec::new(),
+            pending_approvals: Vec::new(),
             // Guidance state
             guidance_mode: None,
             guidance_input: String::new(),
             guidance_next_queue: Arc::new(Mutex::new(Vec::new())),
             events_path: None,
+            approval_decisions_path: None,
             guidance_flash: None,
         }
     }
@@ -276,11 +296,14 @@ impl TuiState {
             // Task tracking state
             task_counts: TaskCounts::default(),
             active_task: None,
+            pending_timers: Vec::new(),
+            pending_approvals: Vec::new(),
             // Guidance state
             guidance_mode: None,
             guidance_input: String::new(),
             guidance_next_queue: Arc::new(Mutex::new(Vec::new())),
             events_path: None,
+            approval_decisions_path: None,
             guidance_flash: None,
         }
     }
@@ -317,6 +340,7 @@ impl TuiState {
                 let saved_pending_backend = self.pending_backend.clone();
                 let saved_guidance_next_queue = Arc::clone(&self.guidance_next_queue);
                 let saved_events_path = self.events_path.clone();
+                let saved_approval_decisions_path = self.approval_decisions_path.clone();
                 *self = Self::new();
                 self.hat_map = saved_hat_map;
                 self.loop_started = saved_loop_started; // Keep original timer
@@ -328,6 +352,7 @@ impl TuiState {
                 self.pending_backend = saved_pending_backend;
                 self.guidance_next_queue = saved_guidance_next_queue;
                 self.events_path = saved_events_path;
+                self.approval_decisions_path = saved_approval_decisions_path;
                 if let Some((hat_id, hat_display)) = custom_hat.clone() {
                     self.pending_hat = Some((hat_id, hat_display));
                 } else {
@@ -506,6 +531,13 @@ impl TuiState {
         }
     }
 
+    /// Records the model requested for the latest iteration.
+    pub fn set_latest_iteration_model(&mut self, model: Option<String>) {
+        if let Some(buffer) = self.iterations.last_mut() {
+            buffer.model = model;
+        }
+    }
+
     /// Finalizes the latest iteration's elapsed time if it isn't already set.
     pub fn finish_latest_iteration(&mut self) {
         let Some(buffer) = self.iterations.last_mut() else {
@@ -539,6 +571,12 @@ impl TuiState {
             .and_then(|buffer| buffer.backend.as_deref())
     }
 
+    /// Returns the model of the currently viewed iteration, if one was requested.
+    pub fn current_iteration_model(&self) -> Option<&str> {
+        self.current_iteration()
+            .and_then(|buffer| buffer.model.as_deref())
+    }
+
     /// Returns a reference to the currently viewed iteration buffer.
     pub fn current_iteration(&self) -> Option<&IterationBuffer> {
         self.iterations.get(self.current_view)
@@ -753,13 +791,24 @@ impl TuiState {
                 }
             }
             GuidanceMode::Now => {
-                let ok = self.write_guidance_event(&input);
+                let ok = self.write_event("human.guidance", &input);
                 if ok {
                     (true, GuidanceResult::Sent)
                 } else {
                     (false, GuidanceResult::Failed)
                 }
             }
+            GuidanceMode::Reject => {
+                let ok = self.record_decision(&format!("reject: {input}"));
+                if ok {
+                    if !self.pending_approvals.is_empty() {
+                        self.pending_approvals.remove(0);
+                    }
+                    (true, GuidanceResult::Sent)
+                } else {
+                    (false, GuidanceResult::Failed)
+                }
+            }
         };
 
         self.guidance_flash = Some((mode, result, Instant::now()));
@@ -768,15 +817,33 @@ impl TuiState {
         ok
     }
 
-    /// Writes a human.guidance event directly to events.jsonl.
-    fn write_guidance_event(&self, message: &str) -> bool {
+    /// Approves the oldest event held for approval.
+    ///
+    /// Returns true if an approval was written to the decisions file.
+    pub fn approve(&mut self) -> bool {
+        if self.pending_approvals.is_empty() || !self.record_decision("approve") {
+            return false;
+        }
+        self.pending_approvals.remove(0);
+        true
+    }
+
+    /// Appends an approval decision to the decisions file.
+    fn record_decision(&self, reply: &str) -> bool {
+        self.approval_decisions_path
+            .as_ref()
+            .is_some_and(|path| ralph_core::record_decision(path, reply).is_ok())
+    }
+
+    /// Writes an event directly to events.jsonl.
+    fn write_event(&self, topic: &str, message: &str) -> bool {
         let Some(ref path) = self.events_path else {
             return false;
         };
 
         let timestamp = chrono::Utc::now().to_rfc3339();
         let event = serde_json::json!({
-            "topic": "human.guidance",
+            "topic": topic,
             "payload": message,
             "ts": timestamp,
         });
@@ -859,6 +926,8 @@ pub struct IterationBuffer {
     pub hat_display: Option<String>,
     /// Backend used for this iteration (e.g., "claude", "kiro").
     pub backend: Option<String>,
+    /// Model requested for this iteration (e.g., "opus"), if the hat picked one.
+    pub model: Option<String>,
     /// When this iteration started (for elapsed time calculation).
     pub started_at: Option<Instant>,
     /// Frozen elapsed duration for this iteration (set when completed).
@@ -875,6 +944,7 @@ impl IterationBuffer {
             following_bottom: true, // Start following bottom for auto-scroll
             hat_display: None,
             backend: None,
+            model: None,
             started_at: None,
             elapsed: None,
         }
@@ -2315,6 +2385,37 @@ mod tests {
             assert!(event["ts"].is_string());
         }
 
+        #[test]
+        fn approval_keys_write_decisions_outside_the_events_file() {
+            let dir = tempfile::tempdir().unwrap();
+            let events_path = dir.path().join("events.jsonl");
+            let decisions_path = dir.path().join("approval-decisions.jsonl");
+
+            let mut state = TuiState::new();
+            state.events_path = Some(events_path.clone());
+            state.approval_decisions_path = Some(decisions_path.clone());
+            assert!(!state.approve(), "nothing held");
+
+            state.pending_approvals = vec!["deploy.start".to_string(), "db.migrate".to_string()];
+            assert!(state.approve());
+            state.start_guidance(GuidanceMode::Reject);
+            state.guidance_input = "not during the freeze".to_string();
+            assert!(state.send_guidance());
+            assert!(state.pending_approvals.is_empty());
+
+            assert!(!events_path.exists());
+            let payloads: Vec<String> = std::fs::read_to_string(&decisions_path)
+                .unwrap()
+                .lines()
+                .map(|line| {
+                    let event: serde_json::Value = serde_json::from_str(line).unwrap();
+                    assert_eq!(event["topic"], "approval.response");
+                    event["payload"].as_str().unwrap().to_string()
+                })
+                .collect();
+            assert_eq!(payloads, vec!["approve", "reject: not during the freeze"]);
+        }
+
         #[test]
         fn send_guidance_now_without_events_path_fails() {
             let mut state = TuiState::new();
@@ -29,6 +29,7 @@ impl Widget for Footer<'_> {
             let label = match mode {
                 crate::state::GuidanceMode::Next => "guidance (next)",
                 crate::state::GuidanceMode::Now => "guidance (now!)",
+                crate::state::GuidanceMode::Reject => "reject reason",
             };
             let line = Line::from(vec![
                 Span::raw(" "),
@@ -49,6 +50,9 @@ impl Widget for Footer<'_> {
                 (crate::state::GuidanceMode::Now, crate::state::GuidanceResult::Sent) => {
                     ("\u{2713} guidance sent (now!)", Color::Green)
                 }
+                (crate::state::GuidanceMode::Reject, crate::state::GuidanceResult::Sent) => {
+                    ("\u{2713} rejection sent", Color::Green)
+                }
                 (_, crate::state::GuidanceResult::Failed) => {
                     ("\u{2717} failed to send guidance", Color::Red)
                 }
@@ -130,6 +134,32 @@ impl Widget for Footer<'_> {
         };
         left_spans.push(Span::raw(elapsed_display));
 
+        // Show the soonest pending timer (delayed event or watchdog)
+        if let Some(next) = self.state.pending_timers.first() {
+            let more = match self.state.pending_timers.len() - 1 {
+                0 => String::new(),
+                n => format!(" (+{n} more)"),
+            };
+            left_spans.push(Span::raw(" │ "));
+            left_spans.push(Span::styled(
+                format!("⏱ {next}{more}"),
+                Style::default().fg(Color::Cyan),
+            ));
+        }
+
+        // Show the oldest event held for approval
+        if let Some(next) = self.state.pending_approvals.first() {
+            let more = match self.state.pending_approvals.len() - 1 {
+                0 => String::new(),
+                n => format!(" (+{n} more)"),
+            };
+            left_spans.push(Span::raw(" │ "));
+            left_spans.push(Span::styled(
+                format!("⏸ approve {next}? a/r{more}"),
+                Style::default().fg(Color::Magenta),
+            ));
+        }
+
         let indicator_text = if self.state.loop_completed {
             "■ DONE"
         } else {
@@ -241,6 +271,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn footer_shows_pending_approvals() {
+        let mut state = TuiState::new();
+        state.pending_approvals = vec!["deploy.start (9m left)".to_string()];
+
+        let text = render_to_string_with_width(&state, 120);
+
+        assert!(
+            text.contains("⏸ approve deploy.start (9m left)? a/r"),
+            "footer should show the held event, got: {text}"
+        );
+    }
+
+    #[test]
+    fn footer_shows_pending_timers() {
+        let mut state = TuiState::new();
+        state.pending_timers = vec![
+            "build.timeout (awaiting build.done) in 4m".to_string(),
+            "review.start in 2 iterations".to_string(),
+        ];
+
+        let text = render_to_string_with_width(&state, 120);
+
+        assert!(
+            text.contains("⏱ build.timeout (awaiting build.done) in 4m (+1 more)"),
+            "should show the next timer, got: {}",
+            text
+        );
+    }
+
     #[test]
     fn footer_shows_elapsed_time() {
         // Given loop_started is set (simulating 2 minutes 30 seconds elapsed)
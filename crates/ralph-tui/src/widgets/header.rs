@@ -63,7 +63,10 @@ pub fn render(state: &TuiState, width: u16) -> Paragraph<'static> {
     let hat_with_backend = if let Some(backend) = state.current_iteration_backend()
         && width > WIDTH_COMPRESS
     {
-        format!("{hat_display} @{backend}")
+        match state.current_iteration_model() {
+            Some(model) => format!("{hat_display} @{backend} ({model})"),
+            None => format!("{hat_display} @{backend}"),
+        }
     } else {
         hat_display.clone()
     };
@@ -253,6 +256,35 @@ mod tests {
         assert!(text.contains("02:05"), "should show 02:05, got: {}", text);
     }
 
+    #[test]
+    fn header_shows_iteration_model() {
+        let mut state = TuiState::new();
+        state.start_new_iteration_with_metadata(
+            Some("🔨 Builder".to_string()),
+            Some("claude".to_string()),
+        );
+        state.set_latest_iteration_model(Some("opus".to_string()));
+        state.start_new_iteration_with_metadata(
+            Some("🧪 Reviewer".to_string()),
+            Some("claude".to_string()),
+        );
+
+        let text = render_to_string(&state);
+        assert!(
+            !text.contains("(opus)"),
+            "latest iteration has no model, got: {}",
+            text
+        );
+
+        state.current_view = 0;
+        let text = render_to_string(&state);
+        assert!(
+            text.contains("@claude (opus)"),
+            "should show model, got: {}",
+            text
+        );
+    }
+
     #[test]
     fn header_uses_per_iteration_hat_from_events_when_reviewing() {
         use std::collections::HashMap;
@@ -699,7 +731,7 @@ mod tests {
         let event = Event::new("task.start", "");
         state.update(&event);
         if let Some(iteration) = state.iterations.get_mut(0) {
-            iteration.elapsed = Some(Duration::from_secs(300));
+            iteration.elapsed = Some(Duration::from_mins(5));
         }
 
         let text = render_to_string(&state);
@@ -72,6 +72,10 @@ pub fn render(f: &mut Frame, area: Rect) {
             Span::styled("  !", Style::default().fg(Color::Cyan)),
             Span::raw("      Send guidance (now, current iteration)"),
         ]),
+        Line::from(vec![
+            Span::styled("  a/r", Style::default().fg(Color::Cyan)),
+            Span::raw("    Approve/reject held event"),
+        ]),
         Line::from(""),
         Line::from(Span::styled("Other:", Style::default().fg(Color::Yellow))),
         Line::from(vec![